@@ -0,0 +1,273 @@
+use serde_json::Value;
+
+use crate::aws::S3Service;
+use crate::errors;
+use crate::models::KeyRewrite;
+
+/// Block Public Access settings of a bucket
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BlockPublicAccess {
+    pub block_public_acls: bool,
+    pub ignore_public_acls: bool,
+    pub block_public_policy: bool,
+    pub restrict_public_buckets: bool,
+}
+
+impl BlockPublicAccess {
+    pub fn all(&self) -> bool {
+        self.block_public_acls
+            && self.ignore_public_acls
+            && self.block_public_policy
+            && self.restrict_public_buckets
+    }
+}
+
+/// How far beyond the owning account a grant reaches
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Exposure {
+    /// Every principal, narrowed only by policy conditions, or any
+    /// authenticated AWS user
+    Wide,
+    /// Anyone, without credentials
+    Public,
+}
+
+impl Exposure {
+    pub fn label(self) -> &'static str {
+        match self {
+            Exposure::Wide => "wide",
+            Exposure::Public => "public",
+        }
+    }
+}
+
+/// One grant of the bucket policy or ACL that reaches past the account
+#[derive(Clone, Debug)]
+pub struct AccessFinding {
+    /// Key prefix the grant covers; empty for the whole bucket
+    pub prefix: String,
+    pub exposure: Exposure,
+    /// What is granted to whom, e.g. "policy allows s3:GetObject to everyone"
+    pub detail: String,
+    /// Overridden by the bucket's Block Public Access settings
+    pub blocked: bool,
+}
+
+/// Who besides the owning account can reach a bucket's objects. Account-wide
+/// Block Public Access and object ACLs are not read.
+#[derive(Clone, Debug)]
+pub struct AccessAudit {
+    pub bucket: String,
+    pub block: Option<BlockPublicAccess>,
+    /// S3's own verdict on the bucket policy, when there is one
+    pub policy_public: Option<bool>,
+    pub findings: Vec<AccessFinding>,
+    /// Settings that could not be read, with why
+    pub unreadable: Vec<String>,
+}
+
+impl AccessAudit {
+    /// Read the access settings of `bucket`. A setting that cannot be read,
+    /// typically for lack of permission, is listed in `unreadable` and the
+    /// rest are still checked.
+    pub async fn inspect(s3: &S3Service, bucket: &str) -> Self {
+        let mut unreadable = Vec::new();
+        let mut note = |what: &str, err: anyhow::Error| {
+            unreadable.push(format!("{what}: {}", errors::category(&err)));
+        };
+        let block = s3.public_access_block(bucket).await.unwrap_or_else(|err| {
+            note("Block Public Access", err);
+            None
+        });
+        let policy_public = s3.policy_is_public(bucket).await.unwrap_or_else(|err| {
+            note("policy status", err);
+            None
+        });
+        let mut findings = Vec::new();
+        match s3.bucket_policy(bucket).await {
+            Ok(Some(policy)) => {
+                let restricted = block.is_some_and(|block| block.restrict_public_buckets);
+                findings.extend(policy_findings(bucket, &policy, restricted));
+            }
+            Ok(None) => {}
+            Err(err) => note("bucket policy", err),
+        }
+        match s3.bucket_group_grants(bucket).await {
+            Ok(grants) => {
+                let ignored = block.is_some_and(|block| block.ignore_public_acls);
+                findings.extend(acl_findings(&grants, ignored));
+            }
+            Err(err) => note("bucket ACL", err),
+        }
+        Self {
+            bucket: bucket.to_string(),
+            block,
+            policy_public,
+            findings,
+            unreadable,
+        }
+    }
+
+    /// Widest exposure in effect anywhere in the bucket
+    pub fn exposure(&self) -> Option<Exposure> {
+        self.findings
+            .iter()
+            .filter(|finding| !finding.blocked)
+            .map(|finding| finding.exposure)
+            .max()
+    }
+
+    /// Widest exposure in effect for `key`
+    pub fn exposure_of(&self, key: &str) -> Option<Exposure> {
+        self.findings
+            .iter()
+            .filter(|finding| !finding.blocked && key.starts_with(&finding.prefix))
+            .map(|finding| finding.exposure)
+            .max()
+    }
+}
+
+/// How the access of copied keys changes from `source` to `dest`, where
+/// `rewrite` names them: how many become more exposed, and how many lose
+/// access they had
+pub fn drift<'a>(
+    source: &AccessAudit,
+    dest: &AccessAudit,
+    keys: impl IntoIterator<Item = &'a str>,
+    rewrite: &KeyRewrite,
+) -> (usize, usize) {
+    let (mut wider, mut narrower) = (0, 0);
+    for key in keys {
+        let dest_key = rewrite.apply(key);
+        match dest.exposure_of(&dest_key).cmp(&source.exposure_of(key)) {
+            std::cmp::Ordering::Greater => wider += 1,
+            std::cmp::Ordering::Less => narrower += 1,
+            std::cmp::Ordering::Equal => {}
+        }
+    }
+    (wider, narrower)
+}
+
+/// Allow statements of a bucket policy whose principal is everyone. With
+/// `restricted` (RestrictPublicBuckets) S3 ignores the unconditional ones.
+pub fn policy_findings(bucket: &str, policy: &str, restricted: bool) -> Vec<AccessFinding> {
+    let Ok(document) = serde_json::from_str::<Value>(policy) else {
+        return Vec::new();
+    };
+    let statements = match &document["Statement"] {
+        Value::Array(statements) => statements.iter().collect(),
+        statement @ Value::Object(_) => vec![statement],
+        _ => Vec::new(),
+    };
+    let mut findings = Vec::new();
+    for statement in statements {
+        if statement["Effect"] != "Allow" || !everyone(&statement["Principal"]) {
+            continue;
+        }
+        let actions = match strings(&statement["Action"]) {
+            actions if actions.is_empty() => match strings(&statement["NotAction"]) {
+                excluded if excluded.is_empty() => "nothing".to_string(),
+                excluded => format!("everything but {}", excluded.join(", ")),
+            },
+            actions => actions.join(", "),
+        };
+        let conditions: Vec<String> = statement["Condition"]
+            .as_object()
+            .into_iter()
+            .flat_map(|operators| operators.values())
+            .filter_map(Value::as_object)
+            .flat_map(|keys| keys.keys().cloned())
+            .collect();
+        let (exposure, detail) = if conditions.is_empty() {
+            (
+                Exposure::Public,
+                format!("policy allows {actions} to everyone"),
+            )
+        } else {
+            (
+                Exposure::Wide,
+                format!(
+                    "policy allows {actions} to everyone, with conditions on {}",
+                    conditions.join(", ")
+                ),
+            )
+        };
+        for prefix in resource_prefixes(bucket, &strings(&statement["Resource"])) {
+            findings.push(AccessFinding {
+                prefix,
+                exposure,
+                detail: detail.clone(),
+                blocked: restricted && exposure == Exposure::Public,
+            });
+        }
+    }
+    findings
+}
+
+/// ACL grants to all users or to any authenticated AWS user. With `ignored`
+/// (IgnorePublicAcls) S3 disregards them.
+pub fn acl_findings(grants: &[(String, String)], ignored: bool) -> Vec<AccessFinding> {
+    grants
+        .iter()
+        .filter_map(|(uri, permission)| {
+            let (exposure, whom) = match uri.rsplit('/').next()? {
+                "AllUsers" => (Exposure::Public, "all users"),
+                "AuthenticatedUsers" => (Exposure::Wide, "any authenticated AWS user"),
+                _ => return None,
+            };
+            Some(AccessFinding {
+                prefix: String::new(),
+                exposure,
+                detail: format!("ACL grants {permission} to {whom}"),
+                blocked: ignored,
+            })
+        })
+        .collect()
+}
+
+/// `"*"`, `{"AWS": "*"}` or `{"AWS": [..., "*"]}`
+fn everyone(principal: &Value) -> bool {
+    principal == "*" || strings(&principal["AWS"]).iter().any(|aws| aws == "*")
+}
+
+/// A string or an array of strings
+fn strings(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(value) => vec![value.clone()],
+        Value::Array(values) => values
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Key prefixes of `bucket` the resources name: the part of an object ARN
+/// before its first wildcard, or the whole bucket for the bucket's own ARN
+/// and `*`. Resources of other buckets are left out.
+fn resource_prefixes(bucket: &str, resources: &[String]) -> Vec<String> {
+    let mut prefixes: Vec<String> = Vec::new();
+    for resource in resources {
+        let prefix = if resource == "*" {
+            String::new()
+        } else {
+            let Some((_, path)) = resource.split_once(":::") else {
+                continue;
+            };
+            match path.split_once('/') {
+                Some((name, pattern)) if name == bucket => pattern
+                    .split(['*', '?'])
+                    .next()
+                    .unwrap_or_default()
+                    .to_string(),
+                None if path == bucket => String::new(),
+                _ => continue,
+            }
+        };
+        if !prefixes.contains(&prefix) {
+            prefixes.push(prefix);
+        }
+    }
+    prefixes
+}
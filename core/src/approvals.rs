@@ -0,0 +1,317 @@
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::inventory::parse_csv_line;
+use crate::owners::bucket_matches;
+
+/// Sign-off file to import at startup (`--approvals`)
+pub fn import_path(args: &[String]) -> Option<PathBuf> {
+    args.windows(2)
+        .find(|pair| pair[0] == "--approvals")
+        .map(|pair| PathBuf::from(&pair[1]))
+}
+
+/// Where a prefix stands in the migration sign-off
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalState {
+    Approved,
+    Pending,
+    Blocked,
+}
+
+impl ApprovalState {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "approved" => Some(ApprovalState::Approved),
+            "pending" => Some(ApprovalState::Pending),
+            "blocked" => Some(ApprovalState::Blocked),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ApprovalState::Approved => "approved",
+            ApprovalState::Pending => "pending",
+            ApprovalState::Blocked => "blocked",
+        }
+    }
+}
+
+/// Sign-off of one prefix, as imported
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PrefixApproval {
+    /// Bucket name, or a prefix ending in `*`; every bucket when omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bucket: Option<String>,
+    pub prefix: String,
+    pub state: ApprovalState,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approver: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// How the keys of a pending action stand, by the approval of their prefix
+#[derive(Clone, Debug, Default)]
+pub struct ApprovalTally {
+    pub approved: usize,
+    pub pending: usize,
+    pub blocked: usize,
+    /// Keys under no imported prefix
+    pub unreviewed: usize,
+    /// Prefixes the pending and blocked keys fall under, in the order first seen
+    pub pending_prefixes: Vec<String>,
+    pub blocked_prefixes: Vec<String>,
+}
+
+impl ApprovalTally {
+    /// Keys that are not approved, blocked ones included
+    pub fn unapproved(&self) -> usize {
+        self.pending + self.blocked + self.unreviewed
+    }
+
+    /// Why a batch over these keys may not run: it touches a blocked prefix,
+    /// or with `require_approval` any prefix that is not approved
+    pub fn refusal(&self, require_approval: bool) -> Option<String> {
+        if self.blocked > 0 {
+            return Some(format!(
+                "{} objects are under blocked prefixes ({})",
+                self.blocked,
+                self.blocked_prefixes.join(", ")
+            ));
+        }
+        (require_approval && self.unapproved() > 0).then(|| {
+            format!(
+                "{} objects are under prefixes not approved yet (--require-approval)",
+                self.unapproved()
+            )
+        })
+    }
+}
+
+/// Prefix approvals imported from a sign-off file, persisted in
+/// `approvals.json` so they apply until the next import
+#[derive(Default)]
+pub struct ApprovalStore {
+    file_path: PathBuf,
+    approvals: Vec<PrefixApproval>,
+}
+
+impl ApprovalStore {
+    pub fn new() -> Result<Self> {
+        let config_dir = directories::ProjectDirs::from("com", "bucket-brigade", "bucket-brigade")
+            .map(|dirs| dirs.config_dir().to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        fs::create_dir_all(&config_dir)?;
+        let file_path = config_dir.join("approvals.json");
+
+        let approvals = if file_path.exists() {
+            let content = fs::read_to_string(&file_path)?;
+            serde_json::from_str(&content).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            file_path,
+            approvals,
+        })
+    }
+
+    pub fn approvals(&self) -> &[PrefixApproval] {
+        &self.approvals
+    }
+
+    /// Whether a sign-off was ever imported; without one nothing is checked
+    pub fn is_empty(&self) -> bool {
+        self.approvals.is_empty()
+    }
+
+    /// Replace the approvals with those of a `.csv` or `.yaml` sign-off file.
+    /// Returns how many prefixes it listed.
+    pub fn import(&mut self, path: &Path) -> Result<usize> {
+        let content =
+            fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let approvals = match extension.as_str() {
+            "csv" => parse_csv(&content),
+            "yaml" | "yml" => parse_yaml(&content),
+            _ => bail!("{}: expected a .csv, .yaml or .yml file", path.display()),
+        }
+        .with_context(|| format!("importing {}", path.display()))?;
+        self.approvals = approvals;
+        self.save()?;
+        Ok(self.approvals.len())
+    }
+
+    /// The approval covering `key` in `bucket`: the entry with the longest
+    /// matching prefix, preferring one named for the bucket over a pattern
+    pub fn lookup(&self, bucket: &str, key: &str) -> Option<&PrefixApproval> {
+        self.approvals
+            .iter()
+            .filter(|approval| {
+                bucket_matches(approval.bucket.as_deref(), bucket)
+                    && key.starts_with(&approval.prefix)
+            })
+            .max_by_key(|approval| {
+                (
+                    approval.prefix.len(),
+                    approval.bucket.as_deref() == Some(bucket),
+                )
+            })
+    }
+
+    pub fn tally<'a>(
+        &self,
+        bucket: &str,
+        keys: impl IntoIterator<Item = &'a str>,
+    ) -> ApprovalTally {
+        let mut tally = ApprovalTally::default();
+        for key in keys {
+            let Some(approval) = self.lookup(bucket, key) else {
+                tally.unreviewed += 1;
+                continue;
+            };
+            let prefixes = match approval.state {
+                ApprovalState::Approved => {
+                    tally.approved += 1;
+                    continue;
+                }
+                ApprovalState::Pending => {
+                    tally.pending += 1;
+                    &mut tally.pending_prefixes
+                }
+                ApprovalState::Blocked => {
+                    tally.blocked += 1;
+                    &mut tally.blocked_prefixes
+                }
+            };
+            if !prefixes.contains(&approval.prefix) {
+                prefixes.push(approval.prefix.clone());
+            }
+        }
+        tally
+    }
+
+    fn save(&self) -> Result<()> {
+        let json = serde_json::to_string_pretty(&self.approvals)?;
+        fs::write(&self.file_path, json)?;
+        Ok(())
+    }
+}
+
+/// One approval from its fields, as named in either file format
+fn approval(fields: &[(String, String)], line: usize) -> Result<PrefixApproval> {
+    let field = |name: &str| {
+        fields
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.trim())
+            .filter(|value| !value.is_empty())
+    };
+    let prefix = field("prefix").with_context(|| format!("line {line}: no prefix"))?;
+    let status = field("status")
+        .or_else(|| field("state"))
+        .with_context(|| format!("line {line}: no status"))?;
+    let state = ApprovalState::parse(status).with_context(|| {
+        format!("line {line}: status `{status}` is not approved, pending or blocked")
+    })?;
+    Ok(PrefixApproval {
+        bucket: field("bucket").map(str::to_string),
+        prefix: prefix.to_string(),
+        state,
+        approver: field("approver").map(str::to_string),
+        note: field("note").map(str::to_string),
+    })
+}
+
+/// A CSV with a header row naming its columns: `prefix` and `status` are
+/// required, `bucket`, `approver` and `note` optional
+fn parse_csv(content: &str) -> Result<Vec<PrefixApproval>> {
+    let mut lines = content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+    let Some((_, header)) = lines.next() else {
+        return Ok(Vec::new());
+    };
+    let columns: Vec<String> = parse_csv_line(header)
+        .into_iter()
+        .map(|column| column.trim().to_string())
+        .collect();
+    lines
+        .map(|(index, line)| {
+            let fields: Vec<(String, String)> =
+                columns.iter().cloned().zip(parse_csv_line(line)).collect();
+            approval(&fields, index + 1)
+        })
+        .collect()
+}
+
+/// A YAML list of flat mappings, optionally under one top-level key:
+///
+/// ```yaml
+/// approvals:
+///   - bucket: media-archive
+///     prefix: team-x/
+///     status: approved
+/// ```
+///
+/// Nothing else of YAML is understood: no nesting, anchors or multi-line
+/// values.
+fn parse_yaml(content: &str) -> Result<Vec<PrefixApproval>> {
+    let mut approvals = Vec::new();
+    let mut fields: Vec<(String, String)> = Vec::new();
+    let mut started_at = 0;
+    for (index, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') || line == "---" {
+            continue;
+        }
+        let entry = match line.strip_prefix('-') {
+            Some(rest) => {
+                if !fields.is_empty() {
+                    approvals.push(approval(&fields, started_at)?);
+                    fields.clear();
+                }
+                started_at = index + 1;
+                rest.trim_start()
+            }
+            None => line,
+        };
+        if entry.is_empty() {
+            continue;
+        }
+        let (key, value) = entry
+            .split_once(':')
+            .with_context(|| format!("line {}: expected `key: value`", index + 1))?;
+        let value = value.trim();
+        // A key without a value opens the list, like `approvals:`
+        if value.is_empty() && fields.is_empty() && !raw.starts_with([' ', '-']) {
+            continue;
+        }
+        let value = value
+            .split_once(" #")
+            .map_or(value, |(value, _)| value)
+            .trim();
+        let value = value
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+            .unwrap_or(value);
+        fields.push((key.trim().to_string(), value.to_string()));
+    }
+    if !fields.is_empty() {
+        approvals.push(approval(&fields, started_at)?);
+    }
+    Ok(approvals)
+}
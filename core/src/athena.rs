@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+
+use crate::coldness::Activity;
+use crate::models::ObjectInfo;
+
+/// Inventory table to query (`--athena-table`, `--athena-workgroup`,
+/// `--athena-output`)
+#[derive(Clone, Debug)]
+pub struct AthenaConfig {
+    pub database: String,
+    pub table: String,
+    pub workgroup: String,
+    /// Query result location, when the workgroup does not set one
+    pub output_location: Option<String>,
+    /// Endpoint and connection settings shared with S3
+    pub sdk: crate::aws::SdkOptions,
+}
+
+impl AthenaConfig {
+    /// `None` unless `--athena-table <database>.<table>` is given
+    pub fn from_args(args: &[String]) -> Option<Self> {
+        let value = |flag: &str| {
+            args.windows(2)
+                .find(|pair| pair[0] == flag)
+                .map(|pair| pair[1].clone())
+        };
+        let (database, table) = value("--athena-table")?
+            .split_once('.')
+            .map(|(d, t)| (d.to_string(), t.to_string()))?;
+        Some(Self {
+            database,
+            table,
+            workgroup: value("--athena-workgroup").unwrap_or_else(|| "primary".to_string()),
+            output_location: value("--athena-output"),
+            sdk: crate::aws::SdkOptions::from_args(args),
+        })
+    }
+
+    /// Objects of `bucket` in the latest inventory partition matching `condition`
+    #[cfg_attr(not(feature = "athena"), allow(dead_code))]
+    fn query(&self, bucket: &str, condition: &str) -> String {
+        let table = format!("\"{}\".\"{}\"", self.database, self.table);
+        let mut sql = format!(
+            "SELECT key, size, last_modified_date, storage_class FROM {table} \
+             WHERE bucket = '{}' AND dt = (SELECT max(dt) FROM {table}) \
+             AND is_delete_marker IS NOT TRUE",
+            bucket.replace('\'', "''")
+        );
+        let condition = condition.trim();
+        if !condition.is_empty() {
+            sql.push_str(&format!(" AND ({condition})"));
+        }
+        sql
+    }
+}
+
+/// Database, workgroup and connection a query runs with
+pub struct AthenaQuery<'a> {
+    pub database: &'a str,
+    pub workgroup: &'a str,
+    /// Query result location, when the workgroup does not set one
+    pub output_location: Option<&'a str>,
+    pub sdk: &'a crate::aws::SdkOptions,
+}
+
+impl AthenaConfig {
+    #[cfg_attr(not(feature = "athena"), allow(dead_code))]
+    fn athena_query(&self) -> AthenaQuery<'_> {
+        AthenaQuery {
+            database: &self.database,
+            workgroup: &self.workgroup,
+            output_location: self.output_location.as_deref(),
+            sdk: &self.sdk,
+        }
+    }
+}
+
+/// Successful GetObject and HeadObject calls of each key of `bucket` since
+/// `since`, with the last GetObject, from a CloudTrail table with the columns of the table AWS documents for
+/// CloudTrail logs
+#[cfg_attr(not(feature = "athena"), allow(dead_code))]
+fn last_reads_sql(table: &str, bucket: &str, since: DateTime<Utc>) -> String {
+    format!(
+        "SELECT json_extract_scalar(requestparameters, '$.key') AS key, \
+         max(CASE WHEN eventname = 'GetObject' THEN eventtime END), \
+         count_if(eventname = 'GetObject'), count_if(eventname = 'HeadObject') \
+         FROM \"{table}\" \
+         WHERE eventsource = 's3.amazonaws.com' AND eventname IN ('GetObject', 'HeadObject') \
+         AND errorcode IS NULL \
+         AND json_extract_scalar(requestparameters, '$.bucketName') = '{}' \
+         AND eventtime >= '{}' \
+         GROUP BY 1",
+        bucket.replace('\'', "''"),
+        since.format("%Y-%m-%dT%H:%M:%SZ")
+    )
+}
+
+/// Run the inventory query in Athena and wait for its results
+#[cfg(feature = "athena")]
+pub async fn query_inventory(
+    config: &AthenaConfig,
+    bucket: &str,
+    condition: &str,
+) -> Result<Vec<ObjectInfo>> {
+    use crate::models::StorageClassTier;
+
+    let rows = run_query(&config.athena_query(), &config.query(bucket, condition)).await?;
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let value = |index: usize| row.get(index).cloned().unwrap_or_default();
+            let class = value(3);
+            ObjectInfo {
+                key: value(0),
+                size: value(1).parse().unwrap_or_default(),
+                last_modified: Some(value(2)).filter(|m| !m.is_empty()),
+                storage_class: StorageClassTier::from(
+                    (!class.is_empty())
+                        .then(|| aws_sdk_s3::types::StorageClass::from(class.as_str())),
+                ),
+                restore_state: None,
+                restore_expiry: None,
+                owner: None,
+                tags: None,
+                encryption: None,
+                version: None,
+            }
+        })
+        .collect())
+}
+
+/// Query a CloudTrail table for the reads and HEADs of each key of `bucket`
+#[cfg(feature = "athena")]
+pub async fn query_last_reads(
+    query: &AthenaQuery<'_>,
+    table: &str,
+    bucket: &str,
+    since: DateTime<Utc>,
+) -> Result<HashMap<String, Activity>> {
+    let rows = run_query(query, &last_reads_sql(table, bucket, since)).await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let key = row.first().filter(|key| !key.is_empty())?.clone();
+            let count = |index: usize| row.get(index).and_then(|n| n.parse().ok());
+            // No GetObject leaves the last read empty
+            let last_read = row
+                .get(1)
+                .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+                .map(|at| at.with_timezone(&Utc));
+            let activity = Activity {
+                gets: count(2).unwrap_or_default(),
+                heads: count(3).unwrap_or_default(),
+                last_read,
+            };
+            Some((key, activity))
+        })
+        .collect())
+}
+
+/// Run `sql` and wait for its results, one row of column values at a time
+#[cfg(feature = "athena")]
+async fn run_query(query: &AthenaQuery<'_>, sql: &str) -> Result<Vec<Vec<String>>> {
+    use std::time::Duration;
+
+    use anyhow::{Context, bail};
+    use aws_sdk_athena::types::{QueryExecutionContext, QueryExecutionState, ResultConfiguration};
+
+    let sdk_config = crate::aws::load_sdk_config(query.sdk).await?;
+    let client = aws_sdk_athena::Client::new(&sdk_config);
+
+    let mut start = client
+        .start_query_execution()
+        .query_string(sql)
+        .work_group(query.workgroup)
+        .query_execution_context(
+            QueryExecutionContext::builder()
+                .database(query.database)
+                .build(),
+        );
+    if let Some(output) = query.output_location {
+        start = start.result_configuration(
+            ResultConfiguration::builder()
+                .output_location(output)
+                .build(),
+        );
+    }
+    let execution_id = start
+        .send()
+        .await?
+        .query_execution_id()
+        .context("Athena returned no query execution id")?
+        .to_string();
+
+    loop {
+        let execution = client
+            .get_query_execution()
+            .query_execution_id(&execution_id)
+            .send()
+            .await?;
+        let status = execution.query_execution().and_then(|q| q.status());
+        match status.and_then(|s| s.state()) {
+            Some(QueryExecutionState::Succeeded) => break,
+            Some(QueryExecutionState::Failed) | Some(QueryExecutionState::Cancelled) => {
+                bail!(
+                    "Athena query {execution_id} did not succeed: {}",
+                    status
+                        .and_then(|s| s.state_change_reason())
+                        .unwrap_or("no reason given")
+                );
+            }
+            _ => tokio::time::sleep(Duration::from_secs(1)).await,
+        }
+    }
+
+    let mut rows = Vec::new();
+    let mut pages = client
+        .get_query_results()
+        .query_execution_id(&execution_id)
+        .into_paginator()
+        .send();
+    let mut header = true;
+    while let Some(page) = pages.next().await {
+        let page = page?;
+        for row in page.result_set().map(|r| r.rows()).unwrap_or_default() {
+            // The first row of the first page holds the column names
+            if std::mem::take(&mut header) {
+                continue;
+            }
+            rows.push(
+                row.data()
+                    .iter()
+                    .map(|d| d.var_char_value().unwrap_or_default().to_string())
+                    .collect(),
+            );
+        }
+    }
+    Ok(rows)
+}
+
+/// The Athena client is only compiled with the `athena` feature
+#[cfg(not(feature = "athena"))]
+pub async fn query_inventory(
+    _config: &AthenaConfig,
+    _bucket: &str,
+    _condition: &str,
+) -> Result<Vec<ObjectInfo>> {
+    anyhow::bail!("this build has no Athena support; rebuild with `cargo build --features athena`")
+}
+
+/// The Athena client is only compiled with the `athena` feature
+#[cfg(not(feature = "athena"))]
+pub async fn query_last_reads(
+    _query: &AthenaQuery<'_>,
+    _table: &str,
+    _bucket: &str,
+    _since: DateTime<Utc>,
+) -> Result<HashMap<String, Activity>> {
+    anyhow::bail!("this build has no Athena support; rebuild with `cargo build --features athena`")
+}
@@ -0,0 +1,155 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::waves::format_bytes;
+
+/// Caps the confirmation dialog cycles through with `w`, in bytes per second
+pub const CAP_PRESETS: [u64; 6] = [
+    10_000_000,
+    50_000_000,
+    100_000_000,
+    250_000_000,
+    500_000_000,
+    1_000_000_000,
+];
+
+/// Copies smaller than this are dominated by request latency, so they say
+/// nothing about throughput
+const MIN_SAMPLE_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Weight of the newest copy in a region's measured throughput
+const SMOOTHING: f64 = 0.2;
+
+/// Aggregate cap on cross-region copies from `--max-bandwidth`
+pub fn max_bandwidth(args: &[String]) -> Option<u64> {
+    args.windows(2)
+        .find(|pair| pair[0] == "--max-bandwidth")
+        .and_then(|pair| parse_rate(&pair[1]))
+}
+
+/// Bytes per second from a rate such as `200MB/s`, `500M`, `1Gbit/s` or a
+/// plain byte count. Bytes use decimal prefixes, as link speeds do.
+pub fn parse_rate(value: &str) -> Option<u64> {
+    let value = value.trim().to_ascii_lowercase();
+    let value = value.strip_suffix("/s").unwrap_or(&value);
+    let split = value
+        .find(|ch: char| !(ch.is_ascii_digit() || ch == '.'))
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let factor = match unit.trim() {
+        "" | "b" => 1.0,
+        "k" | "kb" => 1e3,
+        "m" | "mb" => 1e6,
+        "g" | "gb" => 1e9,
+        "kbit" | "kbps" => 1e3 / 8.0,
+        "mbit" | "mbps" => 1e6 / 8.0,
+        "gbit" | "gbps" => 1e9 / 8.0,
+        _ => return None,
+    };
+    let rate = (number * factor) as u64;
+    (rate > 0).then_some(rate)
+}
+
+pub fn format_rate(bytes_per_second: u64) -> String {
+    format!("{}/s", format_bytes(bytes_per_second))
+}
+
+/// The next preset after `cap`, then no cap, then the first preset again
+pub fn next_cap(cap: Option<u64>) -> Option<u64> {
+    match cap {
+        None => Some(CAP_PRESETS[0]),
+        Some(cap) => CAP_PRESETS.iter().copied().find(|preset| *preset > cap),
+    }
+}
+
+/// Staggers cross-region copies so the bytes they move stay under a cap.
+/// Every copy reserves the link for its size at the cap, after the copies
+/// reserved before it, so one shared throttle keeps all jobs together under
+/// the cap, and a job started right after another cannot burst.
+#[derive(Debug, Default)]
+pub struct Throttle {
+    free_at: Option<Instant>,
+}
+
+impl Throttle {
+    /// Reserve the link for `bytes` at `rate` bytes per second. Returns how
+    /// long to wait before sending them.
+    pub fn reserve(&mut self, bytes: u64, rate: u64) -> Duration {
+        let now = Instant::now();
+        let start = self.free_at.filter(|at| *at > now).unwrap_or(now);
+        let busy = Duration::from_secs_f64(bytes as f64 / rate.max(1) as f64);
+        self.free_at = Some(start + busy);
+        start - now
+    }
+}
+
+/// Copy throughput measured into one region
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RegionThroughput {
+    /// Smoothed over recent copies
+    pub bytes_per_second: f64,
+    pub samples: u64,
+    pub measured_at: String, // ISO 8601 timestamp
+}
+
+/// Copy throughput per destination region, persisted in `throughput.json`
+#[derive(Default)]
+pub struct ThroughputLog {
+    file_path: PathBuf,
+    regions: HashMap<String, RegionThroughput>,
+}
+
+impl ThroughputLog {
+    pub fn new() -> Result<Self> {
+        let config_dir = directories::ProjectDirs::from("com", "bucket-brigade", "bucket-brigade")
+            .map(|dirs| dirs.config_dir().to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        fs::create_dir_all(&config_dir)?;
+        let file_path = config_dir.join("throughput.json");
+
+        let regions = if file_path.exists() {
+            let content = fs::read_to_string(&file_path)?;
+            serde_json::from_str(&content).unwrap_or_default()
+        } else {
+            HashMap::new()
+        };
+
+        Ok(Self { file_path, regions })
+    }
+
+    pub fn get(&self, region: &str) -> Option<&RegionThroughput> {
+        self.regions.get(region)
+    }
+
+    /// Take in a copy of `bytes` into `region` that took `elapsed`. Call
+    /// `save` once the batch is over.
+    pub fn record(&mut self, region: &str, bytes: u64, elapsed: Duration) {
+        if bytes < MIN_SAMPLE_BYTES || elapsed.is_zero() {
+            return;
+        }
+        let rate = bytes as f64 / elapsed.as_secs_f64();
+        let entry = self
+            .regions
+            .entry(region.to_string())
+            .or_insert(RegionThroughput {
+                bytes_per_second: rate,
+                samples: 0,
+                measured_at: String::new(),
+            });
+        entry.bytes_per_second = SMOOTHING * rate + (1.0 - SMOOTHING) * entry.bytes_per_second;
+        entry.samples += 1;
+        entry.measured_at = chrono::Utc::now().to_rfc3339();
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let json = serde_json::to_string_pretty(&self.regions)?;
+        fs::write(&self.file_path, json)?;
+        Ok(())
+    }
+}
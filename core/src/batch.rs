@@ -0,0 +1,150 @@
+use std::cell::Cell;
+
+use anyhow::Result;
+
+use crate::aws::S3Service;
+use crate::errors;
+use crate::models::{ObjectLockPlan, StorageClassTier};
+use crate::multipart::{self, CopyOutcome, MultipartStore};
+
+/// Attempts a transition makes before its failure rate is judged, and again
+/// after each "continue"
+pub const FAILURE_SAMPLE: usize = 20;
+
+/// Failure rate above which a transition batch pauses for a decision
+pub const FAILURE_PAUSE_RATE: f64 = 0.10;
+
+/// How a running batch should wind down after the user asks to quit
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShutdownChoice {
+    /// Let the in-flight object (every part of a multipart copy) complete, then quit
+    FinishCurrent,
+    /// Stop at the next safe point, keeping multipart uploads resumable, then quit
+    Checkpoint,
+    /// Cancel the in-flight request and abort any multipart upload, then quit
+    Abort,
+}
+
+/// Move one object to `target` in place, using a multipart copy when it is too
+/// large for CopyObject
+pub async fn transition_object(
+    s3: &S3Service,
+    uploads: &mut MultipartStore,
+    bucket: &str,
+    key: &str,
+    large: bool,
+    target: StorageClassTier,
+    stop: &Cell<Option<ShutdownChoice>>,
+) -> Result<CopyOutcome> {
+    if large {
+        multipart::copy_large_object(s3, uploads, bucket, bucket, key, key, Some(target), stop)
+            .await
+    } else {
+        s3.transition_storage_class(bucket, key, target)
+            .await
+            .map(|_| CopyOutcome::Completed)
+    }
+}
+
+/// Apply a batch's Object Lock settings to an object it just archived.
+/// Returns the journal note, or why the settings were not applied.
+pub async fn apply_object_lock(
+    s3: &S3Service,
+    bucket: &str,
+    key: &str,
+    lock: &ObjectLockPlan,
+) -> std::result::Result<String, String> {
+    if let Some(retention) = &lock.retention {
+        s3.put_object_retention(bucket, key, retention)
+            .await
+            .map_err(|err| {
+                format!(
+                    "archived, but setting retention failed: {}",
+                    errors::describe(&err)
+                )
+            })?;
+    }
+    if lock.legal_hold {
+        s3.put_object_legal_hold(bucket, key).await.map_err(|err| {
+            format!(
+                "archived, but placing the legal hold failed: {}",
+                errors::describe(&err)
+            )
+        })?;
+    }
+    Ok(lock.summary())
+}
+
+/// Copy one object onto itself under the KMS key `kms_key_id`, keeping its
+/// class, using a multipart copy when it is too large for CopyObject
+#[allow(clippy::too_many_arguments)]
+pub async fn reencrypt_object(
+    s3: &S3Service,
+    uploads: &mut MultipartStore,
+    bucket: &str,
+    key: &str,
+    large: bool,
+    class: StorageClassTier,
+    kms_key_id: &str,
+    stop: &Cell<Option<ShutdownChoice>>,
+) -> Result<CopyOutcome> {
+    if large {
+        let upload = s3
+            .start_multipart_copy(bucket, key, bucket, key, Some(class), Some(kms_key_id))
+            .await?;
+        uploads.add(upload.clone());
+        multipart::resume_copy(s3, uploads, upload, stop).await
+    } else {
+        s3.reencrypt_object(bucket, key, kms_key_id, &class)
+            .await
+            .map(|_| CopyOutcome::Completed)
+    }
+}
+
+/// Whether an object encrypted under `current` (a key ARN, as HeadObject
+/// reports it) already uses `wanted`, given as an ARN or a key id. Aliases
+/// cannot be resolved without KMS, so they never match.
+pub fn uses_kms_key(current: Option<&str>, wanted: &str) -> bool {
+    current.is_some_and(|arn| arn == wanted || arn.ends_with(&format!(":key/{wanted}")))
+}
+
+/// The S3 API an object copy of this size goes through, for telemetry
+pub fn copy_api(large: bool) -> &'static str {
+    if large {
+        "UploadPartCopy"
+    } else {
+        "CopyObject"
+    }
+}
+
+/// Check a finished copy at `dest_key` against its source, then delete the
+/// source. Returns how the copy was verified, or why the source was kept.
+pub async fn move_source(
+    s3: &S3Service,
+    source_bucket: &str,
+    dest_bucket: &str,
+    key: &str,
+    dest_key: &str,
+    multipart: bool,
+) -> std::result::Result<String, String> {
+    let (source, dest) = tokio::join!(
+        s3.fingerprint(source_bucket, key),
+        s3.fingerprint(dest_bucket, dest_key)
+    );
+    let source = source
+        .map_err(|err| format!("could not check the source: {}", errors::describe(&err)))?
+        .ok_or("the source disappeared during the copy")?;
+    let dest = dest
+        .map_err(|err| format!("could not check the copy: {}", errors::describe(&err)))?
+        .ok_or("the copy is missing from the destination")?;
+    let how = dest
+        .verify_copy_of(&source, multipart)
+        .map_err(|reason| format!("copy not verified ({reason}); source kept"))?;
+    s3.delete_object(source_bucket, key).await.map_err(|err| {
+        format!(
+            "verified ({how}) but deleting the source failed: {}",
+            errors::describe(&err)
+        )
+    })?;
+    Ok(format!("verified: {how}; source deleted"))
+}
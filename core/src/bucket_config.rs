@@ -0,0 +1,246 @@
+use aws_sdk_s3::types::{
+    BucketVersioningStatus, CorsRule, LifecycleRule, ServerSideEncryptionConfiguration, Tag,
+};
+
+/// Bucket settings that can be copied from one bucket to another
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigPart {
+    Lifecycle,
+    Cors,
+    Tags,
+    Encryption,
+    Versioning,
+}
+
+impl ConfigPart {
+    pub const ALL: [ConfigPart; 5] = [
+        ConfigPart::Lifecycle,
+        ConfigPart::Cors,
+        ConfigPart::Tags,
+        ConfigPart::Encryption,
+        ConfigPart::Versioning,
+    ];
+
+    /// Catalog key of the part's name
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            ConfigPart::Lifecycle => "config_part.lifecycle",
+            ConfigPart::Cors => "config_part.cors",
+            ConfigPart::Tags => "config_part.tags",
+            ConfigPart::Encryption => "config_part.encryption",
+            ConfigPart::Versioning => "config_part.versioning",
+        }
+    }
+}
+
+/// The copyable configuration of one bucket. Settings that are not
+/// configured are empty (or `None`).
+#[derive(Clone, Debug, Default)]
+pub struct BucketConfig {
+    pub lifecycle: Vec<LifecycleRule>,
+    pub cors: Vec<CorsRule>,
+    pub tags: Vec<Tag>,
+    pub encryption: Option<ServerSideEncryptionConfiguration>,
+    /// `None` for a bucket that never had versioning enabled
+    pub versioning: Option<BucketVersioningStatus>,
+}
+
+impl BucketConfig {
+    pub fn same(&self, other: &BucketConfig, part: ConfigPart) -> bool {
+        match part {
+            ConfigPart::Lifecycle => self.lifecycle == other.lifecycle,
+            ConfigPart::Cors => self.cors == other.cors,
+            ConfigPart::Tags => self.tags == other.tags,
+            ConfigPart::Encryption => self.encryption == other.encryption,
+            ConfigPart::Versioning => self.versioning == other.versioning,
+        }
+    }
+
+    /// One line per rule, tag or setting of `part`, as shown in the diff
+    pub fn describe(&self, part: ConfigPart) -> Vec<String> {
+        match part {
+            ConfigPart::Lifecycle => self.lifecycle.iter().map(describe_lifecycle_rule).collect(),
+            ConfigPart::Cors => self.cors.iter().map(describe_cors_rule).collect(),
+            ConfigPart::Tags => self
+                .tags
+                .iter()
+                .map(|tag| format!("{} = {}", tag.key(), tag.value()))
+                .collect(),
+            ConfigPart::Encryption => self
+                .encryption
+                .iter()
+                .flat_map(|config| config.rules())
+                .map(|rule| {
+                    let mut line = rule
+                        .apply_server_side_encryption_by_default()
+                        .map(|default| match default.kms_master_key_id() {
+                            Some(key) => format!("{} with key {key}", default.sse_algorithm()),
+                            None => default.sse_algorithm().to_string(),
+                        })
+                        .unwrap_or_else(|| "no default algorithm".to_string());
+                    if rule.bucket_key_enabled() == Some(true) {
+                        line.push_str(", bucket key enabled");
+                    }
+                    line
+                })
+                .collect(),
+            ConfigPart::Versioning => vec![
+                self.versioning
+                    .as_ref()
+                    .map(|status| status.to_string())
+                    .unwrap_or_else(|| "Never enabled".to_string()),
+            ],
+        }
+    }
+}
+
+fn describe_lifecycle_rule(rule: &LifecycleRule) -> String {
+    let mut parts = vec![format!(
+        "{} [{}]",
+        rule.id().unwrap_or("(no id)"),
+        rule.status()
+    )];
+    if let Some(filter) = rule.filter() {
+        let prefix = filter
+            .prefix()
+            .or(filter.and().and_then(|and| and.prefix()));
+        if let Some(prefix) = prefix.filter(|prefix| !prefix.is_empty()) {
+            parts.push(format!("prefix {prefix}"));
+        }
+        let tags = filter
+            .tag()
+            .into_iter()
+            .chain(filter.and().map(|and| and.tags()).unwrap_or_default());
+        for tag in tags {
+            parts.push(format!("tag {}={}", tag.key(), tag.value()));
+        }
+    }
+    let transitions: Vec<String> = rule
+        .transitions()
+        .iter()
+        .map(|transition| {
+            format!(
+                "{}d→{}",
+                transition.days().unwrap_or_default(),
+                transition
+                    .storage_class()
+                    .map(|class| class.as_str())
+                    .unwrap_or("?")
+            )
+        })
+        .collect();
+    if !transitions.is_empty() {
+        parts.push(transitions.join(", "));
+    }
+    if let Some(days) = rule.expiration().and_then(|expiration| expiration.days()) {
+        parts.push(format!("expire {days}d"));
+    }
+    if let Some(days) = rule
+        .noncurrent_version_expiration()
+        .and_then(|expiration| expiration.noncurrent_days())
+    {
+        parts.push(format!("noncurrent expire {days}d"));
+    }
+    if let Some(days) = rule
+        .abort_incomplete_multipart_upload()
+        .and_then(|abort| abort.days_after_initiation())
+    {
+        parts.push(format!("abort uploads {days}d"));
+    }
+    parts.join(" · ")
+}
+
+fn describe_cors_rule(rule: &CorsRule) -> String {
+    let mut line = format!(
+        "{} from {}",
+        rule.allowed_methods().join(","),
+        rule.allowed_origins().join(",")
+    );
+    if !rule.allowed_headers().is_empty() {
+        line.push_str(&format!(" · headers {}", rule.allowed_headers().join(",")));
+    }
+    if let Some(seconds) = rule.max_age_seconds() {
+        line.push_str(&format!(" · max age {seconds}s"));
+    }
+    line
+}
+
+pub enum DiffLine {
+    Same(String),
+    /// Only in the destination; copying replaces it
+    Removed(String),
+    /// Only in the source; copying adds it
+    Added(String),
+}
+
+/// Copying selected configuration from `source` to `dest`, reviewed as a diff
+/// before anything is written
+pub struct ConfigCopy {
+    pub source: String,
+    pub dest: String,
+    pub source_config: BucketConfig,
+    pub dest_config: BucketConfig,
+    pub selected: Vec<ConfigPart>,
+    pub cursor: usize,
+}
+
+impl ConfigCopy {
+    /// Parts that differ start selected
+    pub fn new(
+        source: String,
+        dest: String,
+        source_config: BucketConfig,
+        dest_config: BucketConfig,
+    ) -> Self {
+        let selected = ConfigPart::ALL
+            .into_iter()
+            .filter(|part| !source_config.same(&dest_config, *part))
+            .collect();
+        Self {
+            source,
+            dest,
+            source_config,
+            dest_config,
+            selected,
+            cursor: 0,
+        }
+    }
+
+    pub fn differs(&self, part: ConfigPart) -> bool {
+        !self.source_config.same(&self.dest_config, part)
+    }
+
+    pub fn is_selected(&self, part: ConfigPart) -> bool {
+        self.selected.contains(&part)
+    }
+
+    /// Select or deselect the part under the cursor
+    pub fn toggle(&mut self) {
+        let part = ConfigPart::ALL[self.cursor];
+        if self.is_selected(part) {
+            self.selected.retain(|p| *p != part);
+        } else {
+            self.selected.push(part);
+        }
+    }
+
+    /// The destination's lines of `part` that copying removes, then the
+    /// source's lines with those it adds marked
+    pub fn diff(&self, part: ConfigPart) -> Vec<DiffLine> {
+        let source = self.source_config.describe(part);
+        let dest = self.dest_config.describe(part);
+        let mut lines: Vec<DiffLine> = dest
+            .iter()
+            .filter(|line| !source.contains(line))
+            .map(|line| DiffLine::Removed(line.clone()))
+            .collect();
+        lines.extend(source.into_iter().map(|line| {
+            if dest.contains(&line) {
+                DiffLine::Same(line)
+            } else {
+                DiffLine::Added(line)
+            }
+        }));
+        lines
+    }
+}
@@ -0,0 +1,645 @@
+use std::cell::Cell;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Stdio;
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::process::{Child, ChildStdout};
+use tokio::task::JoinHandle;
+
+use crate::aws::{ObjectAttributes, PutCondition, S3Service};
+use crate::batch::ShutdownChoice;
+use crate::hooks::shell;
+use crate::models::{ObjectInfo, StorageClassTier};
+use crate::multipart::StreamUpload;
+use crate::transform::{finish_piped, write_input};
+
+/// Objects this size or larger are left out of a bundle. Below it, the
+/// per-object overhead Deep Archive bills (and the transition request each
+/// one costs) outweighs the storage saved.
+pub const SMALL_OBJECT_SIZE: i64 = 128 * 1024;
+
+const BLOCK: usize = 512;
+
+/// Name GNU tar gives the entry that carries a name too long for a header
+const LONG_NAME: &str = "././@LongLink";
+
+/// How a bundle's tar stream is compressed, by piping it through the
+/// matching command
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArchiveCompression {
+    None,
+    #[default]
+    Zstd,
+    Gzip,
+}
+
+impl ArchiveCompression {
+    pub fn next(self) -> Self {
+        match self {
+            ArchiveCompression::None => ArchiveCompression::Zstd,
+            ArchiveCompression::Zstd => ArchiveCompression::Gzip,
+            ArchiveCompression::Gzip => ArchiveCompression::None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ArchiveCompression::None => "none (plain tar)",
+            ArchiveCompression::Zstd => "zstd",
+            ArchiveCompression::Gzip => "gzip",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ArchiveCompression::None => ".tar",
+            ArchiveCompression::Zstd => ".tar.zst",
+            ArchiveCompression::Gzip => ".tar.gz",
+        }
+    }
+
+    fn command(&self) -> Option<&'static str> {
+        match self {
+            ArchiveCompression::None => None,
+            ArchiveCompression::Zstd => Some("zstd -c -q"),
+            ArchiveCompression::Gzip => Some("gzip -c"),
+        }
+    }
+
+    fn decompress_command(&self) -> Option<&'static str> {
+        match self {
+            ArchiveCompression::None => None,
+            ArchiveCompression::Zstd => Some("zstd -dc -q"),
+            ArchiveCompression::Gzip => Some("gzip -dc"),
+        }
+    }
+
+    fn content_type(&self) -> &'static str {
+        match self {
+            ArchiveCompression::None => "application/x-tar",
+            ArchiveCompression::Zstd => "application/zstd",
+            ArchiveCompression::Gzip => "application/gzip",
+        }
+    }
+}
+
+/// Where one object ended up in a bundle
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BundleEntry {
+    pub key: String,
+    pub size: i64,
+    /// Byte offset of the object's content in the uncompressed tar stream
+    pub offset: u64,
+    pub last_modified: Option<String>,
+}
+
+/// Index of a bundle, written as `<archive>.index.json` in Standard next to
+/// the archive so it can be read without a restore
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BundleIndex {
+    pub bucket: String,
+    pub archive_key: String,
+    pub compression: ArchiveCompression,
+    pub storage_class: StorageClassTier,
+    /// Size of the archive object
+    pub archive_size: i64,
+    pub created_at: String, // ISO 8601 timestamp
+    pub entries: Vec<BundleEntry>,
+}
+
+pub fn index_key(archive_key: &str) -> String {
+    format!("{archive_key}.index.json")
+}
+
+/// The archive `key` names, when it is a bundle archive or its index
+pub fn archive_of(key: &str) -> Option<&str> {
+    let archive = key.strip_suffix(".index.json").unwrap_or(key);
+    let name = archive.rsplit('/').next().unwrap_or(archive);
+    let is_archive = name.starts_with("bundle-")
+        && [
+            ArchiveCompression::None,
+            ArchiveCompression::Zstd,
+            ArchiveCompression::Gzip,
+        ]
+        .iter()
+        .any(|compression| name.ends_with(compression.extension()));
+    is_archive.then_some(archive)
+}
+
+/// Where a member retrieved as a local file is written:
+/// `retrieved/<bucket>/<key>` in the config directory. Empty, `.` and `..`
+/// key segments are dropped so a key cannot point outside it.
+pub fn local_path(bucket: &str, key: &str) -> PathBuf {
+    let mut path = directories::ProjectDirs::from("com", "bucket-brigade", "bucket-brigade")
+        .map(|dirs| dirs.config_dir().to_path_buf())
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("retrieved")
+        .join(bucket);
+    for segment in key.split('/') {
+        if !matches!(segment, "" | "." | "..") {
+            path.push(segment);
+        }
+    }
+    path
+}
+
+/// Read the index of the archive at `archive_key` from the bucket
+pub async fn read_index(s3: &S3Service, bucket: &str, archive_key: &str) -> Result<BundleIndex> {
+    let json = s3
+        .get_object_text(bucket, &index_key(archive_key))
+        .await
+        .with_context(|| format!("could not read the index of {archive_key}"))?;
+    Ok(serde_json::from_str(&json)?)
+}
+
+/// Indexes of the bundles written or opened here, persisted in
+/// `bundles.json`, so a bundled key can be found after its original is gone
+#[derive(Default)]
+pub struct BundleCatalog {
+    file_path: PathBuf,
+    indexes: Vec<BundleIndex>,
+}
+
+impl BundleCatalog {
+    pub fn new() -> Result<Self> {
+        let config_dir = directories::ProjectDirs::from("com", "bucket-brigade", "bucket-brigade")
+            .map(|dirs| dirs.config_dir().to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        fs::create_dir_all(&config_dir)?;
+        let file_path = config_dir.join("bundles.json");
+
+        let indexes = if file_path.exists() {
+            let content = fs::read_to_string(&file_path)?;
+            serde_json::from_str(&content).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self { file_path, indexes })
+    }
+
+    pub fn get(&self, bucket: &str, archive_key: &str) -> Option<&BundleIndex> {
+        self.indexes
+            .iter()
+            .find(|index| index.bucket == bucket && index.archive_key == archive_key)
+    }
+
+    /// Every known bundle of `bucket`, oldest first
+    pub fn in_bucket<'a>(&'a self, bucket: &'a str) -> impl Iterator<Item = &'a BundleIndex> {
+        self.indexes
+            .iter()
+            .filter(move |index| index.bucket == bucket)
+    }
+
+    /// The newest bundle of `bucket` holding `key`, and where in it
+    pub fn locate(&self, bucket: &str, key: &str) -> Option<(&BundleIndex, &BundleEntry)> {
+        self.indexes
+            .iter()
+            .rev()
+            .filter(|index| index.bucket == bucket)
+            .find_map(|index| {
+                let entry = index.entries.iter().find(|entry| entry.key == key)?;
+                Some((index, entry))
+            })
+    }
+
+    /// Add `index`, replacing an earlier copy of the same archive's
+    pub fn add(&mut self, index: BundleIndex) -> Result<()> {
+        self.indexes
+            .retain(|known| known.bucket != index.bucket || known.archive_key != index.archive_key);
+        self.indexes.push(index);
+        self.save()
+    }
+
+    fn save(&self) -> Result<()> {
+        let json = serde_json::to_string_pretty(&self.indexes)?;
+        fs::write(&self.file_path, json)?;
+        Ok(())
+    }
+}
+
+/// Reads members out of an archive in offset order. A plain tar is read
+/// with one ranged GET per member; a compressed one is streamed once through
+/// its decompressor, skipping what lies between members. Each member's tar
+/// header is checked against the index before its content is returned.
+pub struct MemberReader<'a> {
+    s3: &'a S3Service,
+    index: &'a BundleIndex,
+    members: Vec<BundleEntry>,
+    next: usize,
+    stream: Option<Decompressed>,
+}
+
+/// A compressed archive on its way through the decompressor
+struct Decompressed {
+    command: &'static str,
+    child: Child,
+    stdout: ChildStdout,
+    /// Bytes of the tar stream read so far
+    position: u64,
+    feed: JoinHandle<Result<()>>,
+}
+
+impl Drop for Decompressed {
+    fn drop(&mut self) {
+        self.feed.abort();
+    }
+}
+
+impl<'a> MemberReader<'a> {
+    /// The archive must be readable: not archived, or with a restored copy
+    pub fn new(s3: &'a S3Service, index: &'a BundleIndex, mut members: Vec<BundleEntry>) -> Self {
+        members.sort_by_key(|entry| entry.offset);
+        Self {
+            s3,
+            index,
+            members,
+            next: 0,
+            stream: None,
+        }
+    }
+
+    /// The next member and its content, until every member was read
+    pub async fn next(&mut self) -> Result<Option<(BundleEntry, Vec<u8>)>> {
+        let Some(entry) = self.members.get(self.next).cloned() else {
+            return Ok(None);
+        };
+        self.next += 1;
+        if entry.offset < BLOCK as u64 {
+            bail!("the index puts {} before the first header", entry.key);
+        }
+        let start = entry.offset - BLOCK as u64;
+        let len = BLOCK as u64 + entry.size as u64;
+        let data = match self.index.compression.decompress_command() {
+            None => {
+                self.s3
+                    .get_object_range(&self.index.bucket, &self.index.archive_key, start, len)
+                    .await?
+            }
+            Some(command) => self.read_stream(command, start, len, &entry.key).await?,
+        };
+        check_header(&data[..BLOCK], &entry)?;
+        Ok(Some((entry, data[BLOCK..].to_vec())))
+    }
+
+    async fn read_stream(
+        &mut self,
+        command: &'static str,
+        start: u64,
+        len: u64,
+        key: &str,
+    ) -> Result<Vec<u8>> {
+        let stream = match self.stream.take() {
+            Some(stream) => self.stream.insert(stream),
+            None => {
+                let stream = self.start_stream(command).await?;
+                self.stream.insert(stream)
+            }
+        };
+        if start < stream.position {
+            bail!("the index lists {key} out of order");
+        }
+        let skip = start - stream.position;
+        let skipped =
+            tokio::io::copy(&mut (&mut stream.stdout).take(skip), &mut tokio::io::sink()).await?;
+        let mut data = vec![0; len as usize];
+        let read = if skipped == skip {
+            read_full(&mut stream.stdout, &mut data).await?
+        } else {
+            0
+        };
+        stream.position += skipped + read as u64;
+        if read < data.len() {
+            // The decompressor failing is the likelier reason than a short archive
+            let status = stream.child.wait().await?;
+            if !status.success() {
+                bail!("{} {status}", stream.command);
+            }
+            bail!("the archive ended before {key}");
+        }
+        Ok(data)
+    }
+
+    async fn start_stream(&self, command: &'static str) -> Result<Decompressed> {
+        let source = self
+            .s3
+            .get_object_stream(&self.index.bucket, &self.index.archive_key)
+            .await?;
+        let mut child = shell(command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("could not start `{command}`"))?;
+        let mut stdin = child.stdin.take().context("decompressor has no stdin")?;
+        let stdout = child.stdout.take().context("decompressor has no stdout")?;
+        let mut body = source.body;
+        // Fed from a task, so the archive keeps flowing while members are written
+        let feed = tokio::spawn(async move {
+            while let Some(chunk) = body.try_next().await? {
+                if !write_input(&mut stdin, &chunk).await? {
+                    break;
+                }
+            }
+            anyhow::Ok(())
+        });
+        Ok(Decompressed {
+            command,
+            child,
+            stdout,
+            position: 0,
+            feed,
+        })
+    }
+}
+
+/// Fill `data` from `reader`, short only at the end of the stream
+async fn read_full(reader: &mut ChildStdout, data: &mut [u8]) -> Result<usize> {
+    let mut filled = 0;
+    while filled < data.len() {
+        let read = reader.read(&mut data[filled..]).await?;
+        if read == 0 {
+            break;
+        }
+        filled += read;
+    }
+    Ok(filled)
+}
+
+/// Whether the header block before a member's content is the entry the
+/// index says it is: the same name (as far as the header holds it) and size
+fn check_header(block: &[u8], entry: &BundleEntry) -> Result<()> {
+    let name = entry.key.as_bytes();
+    let name = &name[..name.len().min(100)];
+    let name_matches =
+        block[..name.len()] == *name && (name.len() == 100 || block[name.len()] == 0);
+    let size = std::str::from_utf8(&block[124..135])
+        .ok()
+        .and_then(|digits| u64::from_str_radix(digits.trim_matches(['\0', ' ']), 8).ok());
+    if !name_matches || size != Some(entry.size as u64) {
+        bail!(
+            "the archive does not hold {} at offset {}; its index may be stale",
+            entry.key,
+            entry.offset
+        );
+    }
+    Ok(())
+}
+
+/// `<common prefix>bundle-<timestamp>.tar[.zst|.gz]`, next to the objects
+pub fn archive_key<'a>(
+    keys: impl IntoIterator<Item = &'a str>,
+    compression: ArchiveCompression,
+) -> String {
+    let mut keys = keys.into_iter();
+    let mut prefix = keys.next().unwrap_or_default();
+    for key in keys {
+        let common = prefix
+            .char_indices()
+            .zip(key.chars())
+            .find(|((_, a), b)| a != b)
+            .map_or(prefix.len().min(key.len()), |((index, _), _)| index);
+        prefix = &prefix[..common];
+    }
+    let prefix = prefix.rfind('/').map_or("", |end| &prefix[..=end]);
+    format!(
+        "{prefix}bundle-{}{}",
+        Utc::now().format("%Y%m%d-%H%M%S"),
+        compression.extension()
+    )
+}
+
+/// Write `objects` from `bucket` into one tar archive at `archive_key` in
+/// Deep Archive, then its index. The tar stream is built one object at a
+/// time and uploaded as it is produced, compressed on the way by the
+/// compression's command. Checks `stop` before each object; a checkpoint,
+/// an abort or a failure aborts the upload, so the archive is written whole
+/// or not at all.
+/// The open upload's id is kept in `upload` for a caller that drops this
+/// future to abort. `progress` gets the number of objects added and the
+/// latest key.
+#[allow(clippy::too_many_arguments)]
+pub async fn write_archive(
+    s3: &S3Service,
+    bucket: &str,
+    objects: &[ObjectInfo],
+    archive_key: &str,
+    compression: ArchiveCompression,
+    upload: &Cell<Option<String>>,
+    stop: &Cell<Option<ShutdownChoice>>,
+    progress: impl FnMut(usize, &str),
+) -> Result<BundleIndex> {
+    let result = build(
+        s3,
+        bucket,
+        objects,
+        archive_key,
+        compression,
+        upload,
+        stop,
+        progress,
+    )
+    .await;
+    if result.is_err() {
+        StreamUpload::abort(s3, bucket, archive_key, upload).await;
+    }
+    let (entries, written) = result?;
+
+    // A short archive must not be mistaken for a complete one
+    let stored = s3
+        .fingerprint(bucket, archive_key)
+        .await?
+        .context("the archive is missing after its upload")?;
+    if stored.size != written {
+        bail!(
+            "the archive holds {} bytes, {} were written",
+            stored.size,
+            written
+        );
+    }
+    let index = BundleIndex {
+        bucket: bucket.to_string(),
+        archive_key: archive_key.to_string(),
+        compression,
+        storage_class: StorageClassTier::GlacierDeepArchive,
+        archive_size: written,
+        created_at: Utc::now().to_rfc3339(),
+        entries,
+    };
+    let json = serde_json::to_string_pretty(&index)?;
+    s3.put_object_text(bucket, &index_key(archive_key), &json, PutCondition::Always)
+        .await?;
+    Ok(index)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn build(
+    s3: &S3Service,
+    bucket: &str,
+    objects: &[ObjectInfo],
+    archive_key: &str,
+    compression: ArchiveCompression,
+    upload: &Cell<Option<String>>,
+    stop: &Cell<Option<ShutdownChoice>>,
+    progress: impl FnMut(usize, &str),
+) -> Result<(Vec<BundleEntry>, i64)> {
+    let attributes = ObjectAttributes {
+        content_type: Some(compression.content_type().to_string()),
+        metadata: None,
+        storage_class: Some(StorageClassTier::GlacierDeepArchive),
+    };
+    let members: i64 = objects.iter().map(|obj| obj.size.max(0)).sum();
+    let mut output = StreamUpload::new(s3, bucket, archive_key, attributes, upload, members);
+    let mut entries = Vec::new();
+    match compression.command() {
+        Some(command) => {
+            let mut child = shell(command)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .kill_on_drop(true)
+                .spawn()
+                .with_context(|| format!("could not start `{command}`"))?;
+            let mut stdin = child.stdin.take().context("compressor has no stdin")?;
+            let stdout = child.stdout.take().context("compressor has no stdout")?;
+            let stderr = child.stderr.take().context("compressor has no stderr")?;
+            let feed = async {
+                entries = write_tar(s3, bucket, objects, &mut stdin, stop, progress).await?;
+                // Closing stdin tells the compressor the input ended
+                drop(stdin);
+                anyhow::Ok(())
+            };
+            finish_piped(child, stdout, stderr, feed, &mut output, command).await?;
+        }
+        None => {
+            let (mut writer, mut reader) = tokio::io::duplex(BLOCK * 128);
+            let feed = async {
+                entries = write_tar(s3, bucket, objects, &mut writer, stop, progress).await?;
+                drop(writer);
+                anyhow::Ok(())
+            };
+            let drain = async {
+                let mut chunk = vec![0; BLOCK * 128];
+                loop {
+                    let read = reader.read(&mut chunk).await?;
+                    if read == 0 {
+                        break;
+                    }
+                    output.write(&chunk[..read]).await?;
+                }
+                anyhow::Ok(())
+            };
+            tokio::try_join!(feed, drain)?;
+        }
+    }
+    let written = output.finish().await?;
+    Ok((entries, written))
+}
+
+/// Write each object as a tar entry to `sink`, then the end-of-archive
+/// blocks. Returns where each object's content starts.
+async fn write_tar(
+    s3: &S3Service,
+    bucket: &str,
+    objects: &[ObjectInfo],
+    sink: &mut (impl AsyncWrite + Unpin),
+    stop: &Cell<Option<ShutdownChoice>>,
+    mut progress: impl FnMut(usize, &str),
+) -> Result<Vec<BundleEntry>> {
+    let mut entries = Vec::with_capacity(objects.len());
+    let mut position = 0;
+    for (index, obj) in objects.iter().enumerate() {
+        // Finishing the current object finishes the archive; it is one object
+        if matches!(
+            stop.get(),
+            Some(ShutdownChoice::Checkpoint | ShutdownChoice::Abort)
+        ) {
+            bail!("stopped before the archive was complete");
+        }
+        let stream = s3
+            .get_object_stream(bucket, &obj.key)
+            .await
+            .with_context(|| format!("could not read {}", obj.key))?;
+        let content = stream.body.collect().await?.into_bytes();
+        let mtime = obj
+            .last_modified
+            .as_deref()
+            .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+            .map_or(0, |at| at.timestamp());
+        let header = entry_header(&obj.key, content.len() as u64, mtime);
+        sink.write_all(&header).await?;
+        position += header.len() as u64;
+        entries.push(BundleEntry {
+            key: obj.key.clone(),
+            size: content.len() as i64,
+            offset: position,
+            last_modified: obj.last_modified.clone(),
+        });
+        sink.write_all(&content).await?;
+        sink.write_all(&vec![0; padding(content.len())]).await?;
+        position += (content.len() + padding(content.len())) as u64;
+        progress(index + 1, &obj.key);
+    }
+    sink.write_all(&[0; BLOCK * 2]).await?;
+    sink.flush().await?;
+    Ok(entries)
+}
+
+/// Zero bytes that fill the last block of `len` bytes of content
+fn padding(len: usize) -> usize {
+    (BLOCK - len % BLOCK) % BLOCK
+}
+
+/// Header block(s) of a regular file entry. A name longer than the header's
+/// 100 bytes goes in a GNU long-name entry first, which GNU tar, bsdtar and
+/// Python's tarfile all read.
+fn entry_header(name: &str, size: u64, mtime: i64) -> Vec<u8> {
+    let mut blocks = Vec::with_capacity(BLOCK);
+    if name.len() > 100 {
+        let mut long = name.as_bytes().to_vec();
+        long.push(0);
+        blocks.extend_from_slice(&header(LONG_NAME, long.len() as u64, 0, b'L'));
+        let pad = padding(long.len());
+        blocks.extend_from_slice(&long);
+        blocks.extend(std::iter::repeat_n(0, pad));
+    }
+    blocks.extend_from_slice(&header(name, size, mtime, b'0'));
+    blocks
+}
+
+/// One ustar header block. `name` is cut at 100 bytes; `entry_header`
+/// carries longer names.
+fn header(name: &str, size: u64, mtime: i64, kind: u8) -> [u8; BLOCK] {
+    let mut block = [0; BLOCK];
+    let name = name.as_bytes();
+    let name = &name[..name.len().min(100)];
+    block[..name.len()].copy_from_slice(name);
+    octal(&mut block[100..108], 0o644);
+    octal(&mut block[108..116], 0);
+    octal(&mut block[116..124], 0);
+    octal(&mut block[124..136], size);
+    octal(&mut block[136..148], mtime.max(0) as u64);
+    block[156] = kind;
+    block[257..263].copy_from_slice(b"ustar\0");
+    block[263..265].copy_from_slice(b"00");
+    // The checksum is summed with its own field as spaces
+    block[148..156].fill(b' ');
+    let sum: u32 = block.iter().map(|byte| *byte as u32).sum();
+    let sum = format!("{sum:06o}\0 ");
+    block[148..156].copy_from_slice(sum.as_bytes());
+    block
+}
+
+/// `value` as zero-padded octal filling `field` but its last byte, a NUL
+fn octal(field: &mut [u8], value: u64) {
+    let digits = format!("{value:0width$o}", width = field.len() - 1);
+    let digits = &digits.as_bytes()[digits.len() - (field.len() - 1)..];
+    field[..digits.len()].copy_from_slice(digits);
+    field[field.len() - 1] = 0;
+}
@@ -0,0 +1,109 @@
+use chrono::{DateTime, Utc};
+
+use crate::models::StorageClassTier;
+
+/// Score from which a prefix is shown as cold
+pub const COLD_SCORE: u8 = 80;
+
+/// Requests seen for a key, or for every key under a prefix
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Activity {
+    pub gets: u64,
+    pub heads: u64,
+    /// Latest successful GET. A HEAD reads metadata only, so it counts as a
+    /// request but not as a read.
+    pub last_read: Option<DateTime<Utc>>,
+}
+
+impl Activity {
+    pub fn record_get(&mut self, at: DateTime<Utc>) {
+        self.gets += 1;
+        self.last_read = self.last_read.max(Some(at));
+    }
+
+    pub fn record_head(&mut self) {
+        self.heads += 1;
+    }
+
+    /// Add the requests of `other`, keeping the later read
+    pub fn merge(&mut self, other: &Activity) {
+        self.gets += other.gets;
+        self.heads += other.heads;
+        self.last_read = self.last_read.max(other.last_read);
+    }
+
+    pub fn requests(&self) -> u64 {
+        self.gets + self.heads
+    }
+}
+
+/// How cold a set of objects is, from 0 (read today and often) to 100 (not
+/// requested at all within the lookback)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Coldness {
+    pub score: u8,
+    /// Days since the last read, or since the lookback started or the newest
+    /// object was written when nothing was read. A lower bound either way.
+    pub idle_days: i64,
+    /// Nothing was read within the lookback
+    pub unread: bool,
+    pub requests: u64,
+}
+
+impl Coldness {
+    /// Score `activity` seen since `since`. `written` is when the newest of
+    /// the objects was written, if known: an object cannot have gone unread
+    /// for longer than it has existed.
+    pub fn of(
+        activity: &Activity,
+        since: DateTime<Utc>,
+        written: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let window_days = (now - since).num_days().max(1);
+        let idle_from = [activity.last_read, Some(since), written]
+            .into_iter()
+            .flatten()
+            .max()
+            .unwrap_or(since);
+        let idle_days = (now - idle_from).num_days().clamp(0, window_days);
+        // Idle share of the lookback, damped by how often anything under
+        // it is still requested
+        let idle = idle_days as f64 / window_days as f64;
+        let per_day = activity.requests() as f64 / window_days as f64;
+        let score = (100.0 * idle / (1.0 + per_day)).round() as u8;
+        Self {
+            score,
+            idle_days,
+            unread: activity.last_read.is_none(),
+            requests: activity.requests(),
+        }
+    }
+
+    /// The coldest class whose minimum storage duration the objects have
+    /// already sat idle for, or `None` when they are read too recently to
+    /// leave STANDARD. Deep Archive needs a year without reads, so a lookback
+    /// shorter than that never suggests it.
+    pub fn suggested_class(&self) -> Option<StorageClassTier> {
+        match self.idle_days {
+            365.. => Some(StorageClassTier::GlacierDeepArchive),
+            180.. => Some(StorageClassTier::GlacierFlexibleRetrieval),
+            90.. => Some(StorageClassTier::GlacierInstantRetrieval),
+            30.. => Some(StorageClassTier::StandardIa),
+            _ => None,
+        }
+    }
+
+    /// "cold 100, unread 90d" or "cold 12, read 3d ago, 40 requests"
+    pub fn label(&self) -> String {
+        let idle = if self.unread {
+            format!("unread {}d", self.idle_days)
+        } else {
+            format!("read {}d ago", self.idle_days)
+        };
+        match self.requests {
+            0 => format!("cold {}, {idle}", self.score),
+            requests => format!("cold {}, {idle}, {requests} requests", self.score),
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Result;
+use futures::{StreamExt, stream};
+
+use crate::aws::S3Service;
+use crate::models::KeyRewrite;
+
+/// Up to this many destination keys are looked up one HeadObject each; more
+/// are found by listing the destination under their common prefix
+const HEAD_LIMIT: usize = 200;
+
+/// HeadObject requests in flight while looking up destination keys
+const HEAD_CONCURRENCY: usize = 16;
+
+/// What a renamed key would land on
+#[derive(Clone, Debug)]
+pub enum CollisionCause {
+    /// An object already in the destination bucket
+    Existing {
+        size: i64,
+        /// RFC 3339, when S3 reported it
+        last_modified: Option<String>,
+    },
+    /// An earlier key of the same copy renamed to the same destination key
+    Batch { other: String },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CollisionChoice {
+    Skip,
+    Overwrite,
+}
+
+#[derive(Clone, Debug)]
+pub struct Collision {
+    pub key: String,
+    pub dest_key: String,
+    pub cause: CollisionCause,
+    pub choice: CollisionChoice,
+}
+
+/// Destination keys a renaming copy would overwrite, each to be skipped or
+/// overwritten before the copy is confirmed
+pub struct CollisionReview {
+    pub dest_bucket: String,
+    /// By destination key
+    pub collisions: Vec<Collision>,
+    pub cursor: usize,
+}
+
+impl CollisionReview {
+    /// Look for the collisions of copying `keys` to `dest_bucket` renamed by
+    /// `rewrite`. Every collision starts out skipped.
+    pub async fn find(
+        s3: &S3Service,
+        dest_bucket: &str,
+        rewrite: &KeyRewrite,
+        keys: &[String],
+    ) -> Result<Self> {
+        let mut first_of: HashMap<String, &str> = HashMap::new();
+        let mut collisions = Vec::new();
+        let mut renamed = Vec::with_capacity(keys.len());
+        for key in keys {
+            let dest_key = rewrite.apply(key);
+            match first_of.get(&dest_key) {
+                Some(other) => collisions.push(Collision {
+                    key: key.clone(),
+                    dest_key,
+                    cause: CollisionCause::Batch {
+                        other: other.to_string(),
+                    },
+                    choice: CollisionChoice::Skip,
+                }),
+                None => {
+                    first_of.insert(dest_key.clone(), key);
+                    renamed.push((key, dest_key));
+                }
+            }
+        }
+
+        let dest_keys: Vec<&str> = renamed.iter().map(|(_, dest)| dest.as_str()).collect();
+        let mut existing = existing_objects(s3, dest_bucket, &dest_keys).await?;
+        for (key, dest_key) in renamed {
+            if let Some((size, last_modified)) = existing.remove(&dest_key) {
+                collisions.push(Collision {
+                    key: key.clone(),
+                    dest_key,
+                    cause: CollisionCause::Existing {
+                        size,
+                        last_modified,
+                    },
+                    choice: CollisionChoice::Skip,
+                });
+            }
+        }
+        collisions.sort_by(|a, b| a.dest_key.cmp(&b.dest_key).then(a.key.cmp(&b.key)));
+        Ok(Self {
+            dest_bucket: dest_bucket.to_string(),
+            collisions,
+            cursor: 0,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.collisions.is_empty()
+    }
+
+    /// Decide the collision under the cursor
+    pub fn choose(&mut self, choice: CollisionChoice) {
+        if let Some(collision) = self.collisions.get_mut(self.cursor) {
+            collision.choice = choice;
+        }
+    }
+
+    pub fn choose_all(&mut self, choice: CollisionChoice) {
+        for collision in &mut self.collisions {
+            collision.choice = choice;
+        }
+    }
+
+    /// Source keys to leave out of the copy
+    pub fn skipped(&self) -> HashSet<&str> {
+        self.collisions
+            .iter()
+            .filter(|collision| collision.choice == CollisionChoice::Skip)
+            .map(|collision| collision.key.as_str())
+            .collect()
+    }
+}
+
+/// Size and last-modified time of the `dest_keys` that exist in `bucket`
+async fn existing_objects(
+    s3: &S3Service,
+    bucket: &str,
+    dest_keys: &[&str],
+) -> Result<HashMap<String, (i64, Option<String>)>> {
+    let mut existing = HashMap::new();
+    if dest_keys.len() <= HEAD_LIMIT {
+        let owned: Vec<String> = dest_keys.iter().map(|key| key.to_string()).collect();
+        let mut heads = stream::iter(owned)
+            .map(|key| async move {
+                let head = s3.fingerprint(bucket, &key).await;
+                (key, head)
+            })
+            .buffer_unordered(HEAD_CONCURRENCY);
+        while let Some((key, head)) = heads.next().await {
+            if let Some(head) = head? {
+                let modified = head.last_modified.map(|at| at.to_rfc3339());
+                existing.insert(key, (head.size, modified));
+            }
+        }
+        return Ok(existing);
+    }
+
+    let wanted: HashSet<&str> = dest_keys.iter().copied().collect();
+    let prefix = common_prefix(dest_keys);
+    let prefix = (!prefix.is_empty()).then_some(prefix);
+    let mut token = None;
+    loop {
+        let (objects, next) = s3
+            .list_objects_paginated(bucket, prefix, None, token, 1000)
+            .await?;
+        for obj in objects {
+            if wanted.contains(obj.key.as_str()) {
+                existing.insert(obj.key, (obj.size, obj.last_modified));
+            }
+        }
+        match next {
+            Some(next) => token = Some(next),
+            None => return Ok(existing),
+        }
+    }
+}
+
+/// The longest prefix every key starts with
+fn common_prefix<'a>(keys: &[&'a str]) -> &'a str {
+    let Some((first, rest)) = keys.split_first() else {
+        return "";
+    };
+    let mut len = first.len();
+    for key in rest {
+        len = first
+            .char_indices()
+            .zip(key.chars())
+            .take_while(|((_, a), b)| a == b)
+            .last()
+            .map_or(0, |((index, ch), _)| index + ch.len_utf8())
+            .min(len);
+    }
+    &first[..len]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn common_prefix_of_keys() {
+        assert_eq!(common_prefix(&[]), "");
+        assert_eq!(common_prefix(&["logs/a.gz"]), "logs/a.gz");
+        assert_eq!(
+            common_prefix(&["logs/2019/a", "logs/2019/b", "logs/2020/c"]),
+            "logs/20"
+        );
+        assert_eq!(common_prefix(&["logs/a", "raw/a"]), "");
+        assert_eq!(common_prefix(&["logs/a", "logs"]), "logs");
+    }
+
+    #[test]
+    fn common_prefix_stops_on_a_char_boundary() {
+        // 'é' and 'è' share their first UTF-8 byte
+        assert_eq!(common_prefix(&["café/1", "cafè/2"]), "caf");
+        assert_eq!(common_prefix(&["日本/a", "日本/b"]), "日本/");
+    }
+}
@@ -0,0 +1,96 @@
+use std::time::{Duration, Instant};
+
+use crate::errors::{self, ErrorKind};
+
+/// Consecutive network failures after which AWS counts as unreachable
+const OFFLINE_AFTER: u32 = 3;
+
+/// How often to check whether AWS is reachable again while offline
+pub const PROBE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Whether requests reach AWS, judged from how recent ones ended
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Health {
+    Online,
+    /// Some requests failed to connect, but not enough to call it an outage
+    Degraded,
+    Offline,
+}
+
+/// What to do with a network error that was just recorded
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NetworkFailure {
+    Log,
+    /// This failure made AWS count as unreachable
+    WentOffline,
+    /// Already offline; the error is counted instead of logged
+    Suppress,
+}
+
+/// Tracks network failures. While offline, background refresh and batches
+/// wait, and a probe checks every `PROBE_INTERVAL` whether AWS is back.
+#[derive(Debug, Default)]
+pub struct Connectivity {
+    failures: u32,
+    offline_since: Option<Instant>,
+    pub probed_at: Option<Instant>,
+    /// Network errors left out of the log during the current outage
+    suppressed: usize,
+}
+
+impl Connectivity {
+    pub fn health(&self) -> Health {
+        if self.offline_since.is_some() {
+            Health::Offline
+        } else if self.failures > 0 {
+            Health::Degraded
+        } else {
+            Health::Online
+        }
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.offline_since.is_some()
+    }
+
+    pub fn offline_for(&self) -> Option<Duration> {
+        self.offline_since.map(|since| since.elapsed())
+    }
+
+    pub fn probe_due(&self) -> bool {
+        self.is_offline()
+            && self
+                .probed_at
+                .is_none_or(|at| at.elapsed() >= PROBE_INTERVAL)
+    }
+
+    pub fn record_network_failure(&mut self) -> NetworkFailure {
+        if self.is_offline() {
+            self.suppressed += 1;
+            return NetworkFailure::Suppress;
+        }
+        self.failures += 1;
+        if self.failures < OFFLINE_AFTER {
+            return NetworkFailure::Log;
+        }
+        self.offline_since = Some(Instant::now());
+        self.probed_at = Some(Instant::now());
+        NetworkFailure::WentOffline
+    }
+
+    /// Record a request that reached AWS, whatever its outcome. Returns how
+    /// long the outage lasted and how many errors were not logged, if this
+    /// ends one.
+    pub fn record_reached(&mut self) -> Option<(Duration, usize)> {
+        self.failures = 0;
+        let since = self.offline_since.take()?;
+        self.probed_at = None;
+        Some((since.elapsed(), std::mem::take(&mut self.suppressed)))
+    }
+}
+
+/// Whether `err` means the request never reached AWS: the connection failed
+/// or timed out, as opposed to AWS answering with an error
+pub fn is_network_error(err: &anyhow::Error) -> bool {
+    errors::classify(err) == Some(ErrorKind::Network)
+}
@@ -0,0 +1,411 @@
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::aws::{PutCondition, S3Service};
+use crate::models::StorageClassTier;
+use crate::shards::{ShardPlan, ShardWork};
+use crate::tickets::TicketLink;
+
+/// How long a claim on a shard holds unless its worker renews it. Workers
+/// compare expiry times written by other machines, so their clocks should
+/// agree to well within this.
+pub const LEASE_SECS: i64 = 120;
+
+/// An S3 prefix where sharded plans are shared with `--worker` processes on
+/// other machines (`--state-bucket`).
+///
+/// Under `plans/<id>/` it holds `plan.json`, the manifest, and per shard
+/// `shards/NNNNNN.json` (its keys), `claims/NNNNNN.json` (the worker's
+/// lease) and `progress/NNNNNN.json`. A `closed` marker retires a plan that
+/// is done or was dropped. Claims are taken and renewed with conditional
+/// puts, so two workers never run the same shard, and nothing is ever
+/// deleted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SharedState {
+    pub bucket: String,
+    /// Empty, or ending in `/`
+    pub prefix: String,
+}
+
+/// A shared plan as workers see it, without its keys
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Manifest {
+    pub id: String,
+    pub bucket: String,
+    pub label: String,
+    pub work: ShardWork,
+    pub created_at: String, // ISO 8601 timestamp
+    pub shards: Vec<ManifestShard>,
+    #[serde(default)]
+    pub note: Option<String>,
+    #[serde(default)]
+    pub ticket: Option<TicketLink>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ManifestShard {
+    pub name: String,
+    pub keys: usize,
+    /// Storage class of each key of a restore, so workers track the restore
+    /// under the class it came from
+    #[serde(default)]
+    pub storage_classes: BTreeMap<String, StorageClassTier>,
+}
+
+/// A worker's claim on a shard
+#[derive(Debug, Serialize, Deserialize)]
+struct Lease {
+    worker: String,
+    expires_at: DateTime<Utc>,
+    #[serde(default)]
+    released: bool,
+}
+
+impl Lease {
+    fn new(worker: &str) -> Self {
+        Self {
+            worker: worker.to_string(),
+            expires_at: Utc::now() + chrono::Duration::seconds(LEASE_SECS),
+            released: false,
+        }
+    }
+
+    fn is_live(&self) -> bool {
+        !self.released && self.expires_at > Utc::now()
+    }
+}
+
+/// A shard this worker holds the lease on
+#[derive(Debug)]
+pub struct Claim {
+    pub plan_id: String,
+    pub index: usize,
+    worker: String,
+    etag: String,
+    /// ETag of the shard's progress as this worker last read or wrote it
+    progress_etag: Option<String>,
+}
+
+/// How far the workers got with a shard, rewritten at every checkpoint
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ShardProgress {
+    /// Workers that ran it, oldest first
+    #[serde(default)]
+    pub workers: Vec<String>,
+    /// Keys of the pass in progress that were handled
+    #[serde(default)]
+    pub cursor: usize,
+    /// What a retry pass runs; `None` while the first pass runs every key
+    #[serde(default)]
+    pub retry_keys: Option<Vec<String>>,
+    #[serde(default)]
+    pub succeeded: usize,
+    /// Keys that failed and have not been retried since
+    #[serde(default)]
+    pub failed_keys: Vec<String>,
+    /// The pass in progress reached its last key
+    #[serde(default)]
+    pub finished: bool,
+    /// Set from the TUI: run the failed keys again
+    #[serde(default)]
+    pub retry: bool,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+}
+
+impl ShardProgress {
+    pub fn is_done(&self) -> bool {
+        self.finished && self.failed_keys.is_empty()
+    }
+
+    /// Whether a worker should pick the shard up
+    pub fn wants_work(&self) -> bool {
+        !self.finished || (self.retry && !self.failed_keys.is_empty())
+    }
+}
+
+impl SharedState {
+    /// `--state-bucket s3://bucket/prefix`; `s3://` and the prefix are optional
+    pub fn from_args(args: &[String]) -> Option<Self> {
+        args.windows(2)
+            .find(|pair| pair[0] == "--state-bucket")
+            .and_then(|pair| Self::parse(&pair[1]))
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        let rest = value.strip_prefix("s3://").unwrap_or(value);
+        let (bucket, prefix) = rest.split_once('/').unwrap_or((rest, ""));
+        if bucket.is_empty() {
+            return None;
+        }
+        let prefix = prefix.trim_matches('/');
+        Some(Self {
+            bucket: bucket.to_string(),
+            prefix: if prefix.is_empty() {
+                String::new()
+            } else {
+                format!("{prefix}/")
+            },
+        })
+    }
+
+    pub fn url(&self) -> String {
+        format!("s3://{}/{}", self.bucket, self.prefix)
+    }
+
+    fn plan_key(&self, plan_id: &str, name: &str) -> String {
+        format!("{}plans/{plan_id}/{name}", self.prefix)
+    }
+
+    fn shard_key(&self, plan_id: &str, dir: &str, index: usize) -> String {
+        self.plan_key(plan_id, &format!("{dir}/{index:06}.json"))
+    }
+
+    async fn get<T: for<'de> Deserialize<'de>>(
+        &self,
+        s3: &S3Service,
+        key: &str,
+    ) -> Result<Option<(T, String)>> {
+        let Some((body, etag)) = s3.get_object_tagged(&self.bucket, key).await? else {
+            return Ok(None);
+        };
+        let value = serde_json::from_str(&body)
+            .with_context(|| format!("unreadable s3://{}/{key}", self.bucket))?;
+        Ok(Some((value, etag)))
+    }
+
+    async fn put<T: Serialize>(
+        &self,
+        s3: &S3Service,
+        key: &str,
+        value: &T,
+        condition: PutCondition<'_>,
+    ) -> Result<Option<String>> {
+        let body = serde_json::to_string(value)?;
+        s3.put_object_text(&self.bucket, key, &body, condition)
+            .await
+    }
+
+    /// Upload a plan's shards, then its manifest, which is what makes
+    /// workers see it
+    pub async fn publish(&self, s3: &S3Service, plan: &ShardPlan) -> Result<()> {
+        for (index, shard) in plan.shards.iter().enumerate() {
+            let key = self.shard_key(&plan.id, "shards", index);
+            self.put(s3, &key, &shard.keys, PutCondition::Always)
+                .await?;
+        }
+        let manifest = Manifest {
+            id: plan.id.clone(),
+            bucket: plan.bucket.clone(),
+            label: plan.label.clone(),
+            work: plan.work.clone(),
+            created_at: plan.created_at.clone(),
+            shards: plan
+                .shards
+                .iter()
+                .map(|shard| ManifestShard {
+                    name: shard.name.clone(),
+                    keys: shard.keys.len(),
+                    storage_classes: shard.storage_classes.clone(),
+                })
+                .collect(),
+            note: plan.note.clone(),
+            ticket: plan.ticket.clone(),
+        };
+        let key = self.plan_key(&plan.id, "plan.json");
+        self.put(s3, &key, &manifest, PutCondition::Always).await?;
+        Ok(())
+    }
+
+    /// Ids of every plan under the prefix, closed ones included
+    pub async fn plan_ids(&self, s3: &S3Service) -> Result<Vec<String>> {
+        let prefix = format!("{}plans/", self.prefix);
+        let mut ids = Vec::new();
+        let mut token = None;
+        loop {
+            let page = s3
+                .list_folder_page(&self.bucket, Some(&prefix), token, 1000)
+                .await?;
+            ids.extend(page.folders.iter().filter_map(|folder| {
+                folder
+                    .strip_prefix(&prefix)
+                    .map(|id| id.trim_end_matches('/').to_string())
+            }));
+            token = page.next_token;
+            if token.is_none() {
+                return Ok(ids);
+            }
+        }
+    }
+
+    /// The manifest of a plan, or `None` while it is still being published
+    pub async fn manifest(&self, s3: &S3Service, plan_id: &str) -> Result<Option<Manifest>> {
+        let key = self.plan_key(plan_id, "plan.json");
+        Ok(self.get(s3, &key).await?.map(|(manifest, _)| manifest))
+    }
+
+    pub async fn is_closed(&self, s3: &S3Service, plan_id: &str) -> Result<bool> {
+        let key = self.plan_key(plan_id, "closed");
+        Ok(s3.fingerprint(&self.bucket, &key).await?.is_some())
+    }
+
+    /// Retire a plan, so workers stop claiming its shards. Shards being run
+    /// stop at their next checkpoint.
+    pub async fn close(&self, s3: &S3Service, plan_id: &str, reason: &str) -> Result<()> {
+        let key = self.plan_key(plan_id, "closed");
+        s3.put_object_text(&self.bucket, &key, reason, PutCondition::Always)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn shard_keys(
+        &self,
+        s3: &S3Service,
+        plan_id: &str,
+        index: usize,
+    ) -> Result<Vec<(String, i64)>> {
+        let key = self.shard_key(plan_id, "shards", index);
+        self.get(s3, &key)
+            .await?
+            .map(|(keys, _)| keys)
+            .with_context(|| format!("shard {index} of plan {plan_id} has no key list"))
+    }
+
+    /// A shard's progress and its ETag, or `None` before any worker ran it
+    pub async fn progress(
+        &self,
+        s3: &S3Service,
+        plan_id: &str,
+        index: usize,
+    ) -> Result<Option<(ShardProgress, String)>> {
+        let key = self.shard_key(plan_id, "progress", index);
+        self.get(s3, &key).await
+    }
+
+    /// Checkpoint a claimed shard's progress over the version the claim last
+    /// read or wrote. Returns false, saving nothing, when it changed since:
+    /// another worker took the shard over and wrote its own.
+    pub async fn save_progress(
+        &self,
+        s3: &S3Service,
+        claim: &mut Claim,
+        progress: &ShardProgress,
+    ) -> Result<bool> {
+        let key = self.shard_key(&claim.plan_id, "progress", claim.index);
+        let condition = match &claim.progress_etag {
+            Some(etag) => PutCondition::IfMatch(etag),
+            None => PutCondition::IfAbsent,
+        };
+        match self.put(s3, &key, progress, condition).await? {
+            Some(etag) => {
+                claim.progress_etag = Some(etag);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Ask the workers to run a finished shard's failed keys again. Returns
+    /// false when it has none, or its progress changed meanwhile.
+    pub async fn request_retry(&self, s3: &S3Service, plan_id: &str, index: usize) -> Result<bool> {
+        let Some((mut progress, etag)) = self.progress(s3, plan_id, index).await? else {
+            return Ok(false);
+        };
+        if !progress.finished || progress.failed_keys.is_empty() {
+            return Ok(false);
+        }
+        progress.retry = true;
+        let key = self.shard_key(plan_id, "progress", index);
+        Ok(self
+            .put(s3, &key, &progress, PutCondition::IfMatch(&etag))
+            .await?
+            .is_some())
+    }
+
+    /// The worker holding a live claim on a shard
+    pub async fn holder(
+        &self,
+        s3: &S3Service,
+        plan_id: &str,
+        index: usize,
+    ) -> Result<Option<String>> {
+        let key = self.shard_key(plan_id, "claims", index);
+        Ok(self
+            .get::<Lease>(s3, &key)
+            .await?
+            .filter(|(lease, _)| lease.is_live())
+            .map(|(lease, _)| lease.worker))
+    }
+
+    /// Claim a shard for `worker`, unless another worker holds a live claim
+    /// or takes it at the same moment, and read its progress as of then
+    pub async fn claim(
+        &self,
+        s3: &S3Service,
+        plan_id: &str,
+        index: usize,
+        worker: &str,
+    ) -> Result<Option<(Claim, ShardProgress)>> {
+        let key = self.shard_key(plan_id, "claims", index);
+        let etag = match self.get::<Lease>(s3, &key).await? {
+            Some((lease, _)) if lease.is_live() && lease.worker != worker => return Ok(None),
+            Some((_, etag)) => {
+                self.put(s3, &key, &Lease::new(worker), PutCondition::IfMatch(&etag))
+                    .await?
+            }
+            None => {
+                self.put(s3, &key, &Lease::new(worker), PutCondition::IfAbsent)
+                    .await?
+            }
+        };
+        let Some(etag) = etag else {
+            return Ok(None);
+        };
+        // Another worker may have finished it before the claim was taken
+        let (progress, progress_etag) = match self.progress(s3, plan_id, index).await? {
+            Some((progress, progress_etag)) => (progress, Some(progress_etag)),
+            None => (ShardProgress::default(), None),
+        };
+        let claim = Claim {
+            plan_id: plan_id.to_string(),
+            index,
+            worker: worker.to_string(),
+            etag,
+            progress_etag,
+        };
+        Ok(Some((claim, progress)))
+    }
+
+    /// Extend a claim. Returns false when it was lost, e.g. because this
+    /// worker stalled past its expiry and another took the shard over.
+    pub async fn renew(&self, s3: &S3Service, claim: &mut Claim) -> Result<bool> {
+        let key = self.shard_key(&claim.plan_id, "claims", claim.index);
+        let lease = Lease::new(&claim.worker);
+        match self
+            .put(s3, &key, &lease, PutCondition::IfMatch(&claim.etag))
+            .await?
+        {
+            Some(etag) => {
+                claim.etag = etag;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Give a shard up, so another worker can claim it at once
+    pub async fn release(&self, s3: &S3Service, claim: Claim) -> Result<()> {
+        let key = self.shard_key(&claim.plan_id, "claims", claim.index);
+        let lease = Lease {
+            released: true,
+            ..Lease::new(&claim.worker)
+        };
+        // A claim lost meanwhile is not ours to release
+        self.put(s3, &key, &lease, PutCondition::IfMatch(&claim.etag))
+            .await?;
+        Ok(())
+    }
+}
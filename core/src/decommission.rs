@@ -0,0 +1,163 @@
+use crate::aws::S3Service;
+use crate::errors;
+
+/// Outcome of one decommission check. Only a failed check blocks deleting
+/// the bucket.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CheckState {
+    Passed(String),
+    Warning(String),
+    Failed(String),
+}
+
+pub struct DecommissionCheck {
+    pub label_key: &'static str,
+    pub state: CheckState,
+}
+
+/// Where the assistant is in taking the bucket down
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecommissionStep {
+    Review,
+    ConfirmLifecycleDelete,
+    /// The bucket name has to be typed to confirm
+    ConfirmBucketDelete,
+}
+
+/// Checklist for retiring a migrated source bucket
+pub struct Decommission {
+    pub bucket: String,
+    pub checks: Vec<DecommissionCheck>,
+    pub lifecycle_rules: usize,
+    pub step: DecommissionStep,
+    pub confirm_input: String,
+}
+
+impl Decommission {
+    /// Check that `bucket` is empty and nothing else depends on it. `others`
+    /// are the buckets whose replication rules are searched for it.
+    pub async fn inspect(s3: &S3Service, bucket: &str, others: &[String]) -> Self {
+        let mut checks = Vec::new();
+
+        let state = match s3.has_objects(bucket).await {
+            Ok(false) => CheckState::Passed("no current objects".into()),
+            Ok(true) => CheckState::Failed("current objects remain".into()),
+            Err(err) => CheckState::Failed(format!(
+                "could not list objects: {}",
+                errors::describe(&err)
+            )),
+        };
+        checks.push(DecommissionCheck {
+            label_key: "decommission.check.objects",
+            state,
+        });
+
+        let state = match s3.count_versions(bucket).await {
+            Ok((0, 0, _)) => CheckState::Passed("no versions or delete markers".into()),
+            Ok((versions, markers, more)) => CheckState::Failed(format!(
+                "{versions}{plus} noncurrent versions and {markers}{plus} delete markers remain",
+                plus = if more { "+" } else { "" }
+            )),
+            Err(err) => CheckState::Failed(format!(
+                "could not list versions: {}",
+                errors::describe(&err)
+            )),
+        };
+        checks.push(DecommissionCheck {
+            label_key: "decommission.check.versions",
+            state,
+        });
+
+        let state = match s3.replication_destinations(bucket).await {
+            Ok(destinations) if destinations.is_empty() => {
+                CheckState::Passed("no replication rules".into())
+            }
+            Ok(destinations) => {
+                CheckState::Failed(format!("replicates to {}", destinations.join(", ")))
+            }
+            Err(err) => CheckState::Failed(format!(
+                "could not read replication: {}",
+                errors::describe(&err)
+            )),
+        };
+        checks.push(DecommissionCheck {
+            label_key: "decommission.check.replication_out",
+            state,
+        });
+
+        let arn = s3.partition().bucket_arn(bucket);
+        let mut sources = Vec::new();
+        let mut unreadable = 0;
+        for other in others.iter().filter(|other| *other != bucket) {
+            match s3.replication_destinations(other).await {
+                Ok(destinations) if destinations.contains(&arn) => sources.push(other.clone()),
+                Ok(_) => {}
+                Err(_) => unreadable += 1,
+            }
+        }
+        let state = if !sources.is_empty() {
+            CheckState::Failed(format!("replicated into from {}", sources.join(", ")))
+        } else if unreadable > 0 {
+            CheckState::Warning(format!(
+                "none found, but {unreadable} of {} buckets could not be read",
+                others.len()
+            ))
+        } else {
+            CheckState::Passed(format!(
+                "none of {} other buckets replicate here",
+                others.len()
+            ))
+        };
+        checks.push(DecommissionCheck {
+            label_key: "decommission.check.replication_in",
+            state,
+        });
+
+        let state = match s3.notification_targets(bucket).await {
+            Ok(0) => CheckState::Passed("no event notifications".into()),
+            Ok(count) => CheckState::Failed(format!("{count} notification targets configured")),
+            Err(err) => CheckState::Failed(format!(
+                "could not read notifications: {}",
+                errors::describe(&err)
+            )),
+        };
+        checks.push(DecommissionCheck {
+            label_key: "decommission.check.notifications",
+            state,
+        });
+
+        let (state, lifecycle_rules) = match s3.lifecycle_rule_count(bucket).await {
+            Ok(0) => (CheckState::Passed("no lifecycle configuration".into()), 0),
+            Ok(count) => (
+                CheckState::Failed(format!("{count} lifecycle rules – delete them first")),
+                count,
+            ),
+            Err(err) => (
+                CheckState::Failed(format!(
+                    "could not read lifecycle rules: {}",
+                    errors::describe(&err)
+                )),
+                0,
+            ),
+        };
+        checks.push(DecommissionCheck {
+            label_key: "decommission.check.lifecycle",
+            state,
+        });
+
+        Self {
+            bucket: bucket.to_string(),
+            checks,
+            lifecycle_rules,
+            step: DecommissionStep::Review,
+            confirm_input: String::new(),
+        }
+    }
+
+    /// Whether every blocking check passed, so the bucket can be deleted
+    pub fn ready(&self) -> bool {
+        self.checks
+            .iter()
+            .all(|check| !matches!(check.state, CheckState::Failed(_)))
+    }
+}
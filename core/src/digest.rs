@@ -0,0 +1,575 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, NaiveTime, Utc};
+use serde::Deserialize;
+
+use crate::aws::{S3Service, SdkOptions};
+use crate::models::StorageClassTier;
+use crate::policy::PolicySummary;
+use crate::pricing;
+use crate::report::{STYLE, escape};
+use crate::waves::format_bytes;
+
+/// Failures quoted per run in the email; the report lists more
+const EMAIL_FAILURES: usize = 5;
+
+/// Failures listed per run in the detailed report
+const REPORT_FAILURES: usize = 500;
+
+/// How long the link to an uploaded report works, the most S3 allows
+const REPORT_LINK: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// `digest.json` in the config directory: saved policies the daemon runs
+/// every day, and where the daily digest of those runs goes
+#[derive(Clone, Debug, Deserialize)]
+pub struct DigestConfig {
+    #[serde(default)]
+    pub runs: Vec<ScheduledPolicyRun>,
+    /// Local time the digest goes out, `HH:MM`
+    #[serde(default = "default_send_at")]
+    pub send_at: String,
+    /// Without it the digest is only written to the exports directory
+    #[serde(default)]
+    pub email: Option<EmailSettings>,
+    /// `s3://bucket/prefix/` each detailed report is uploaded under, so the
+    /// email links to it rather than naming a file on the daemon's machine
+    #[serde(default)]
+    pub report_prefix: Option<String>,
+    /// Endpoint and credentials of the daemon, used for SES
+    #[serde(skip)]
+    pub sdk: SdkOptions,
+}
+
+fn default_send_at() -> String {
+    "07:00".to_string()
+}
+
+/// A saved policy run against a bucket at the same local time every day
+#[derive(Clone, Debug, Deserialize)]
+pub struct ScheduledPolicyRun {
+    pub policy: String,
+    pub bucket: String,
+    /// `HH:MM`
+    pub at: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct EmailSettings {
+    pub from: String,
+    pub to: Vec<String>,
+    /// Send through Amazon SES with the daemon's AWS credentials
+    #[serde(default)]
+    pub ses: Option<SesSettings>,
+    /// Send through an SMTP relay, over STARTTLS
+    #[serde(default)]
+    pub smtp: Option<SmtpSettings>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct SesSettings {
+    /// The daemon's region when omitted
+    #[serde(default)]
+    pub region: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct SmtpSettings {
+    pub host: String,
+    #[serde(default = "default_smtp_port")]
+    pub port: u16,
+    #[serde(default)]
+    pub username: Option<String>,
+    /// Environment variable holding the password, which stays out of the file
+    #[serde(default)]
+    pub password_env: Option<String>,
+}
+
+fn default_smtp_port() -> u16 {
+    587
+}
+
+impl DigestConfig {
+    /// The configuration in `digest.json`, or `None` when there is none
+    pub fn load() -> Result<Option<Self>> {
+        let path = directories::ProjectDirs::from("com", "bucket-brigade", "bucket-brigade")
+            .map(|dirs| dirs.config_dir().to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("digest.json");
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path)?;
+        let config: Self = serde_json::from_str(&content)
+            .with_context(|| format!("{} is not valid", path.display()))?;
+        config
+            .validate()
+            .with_context(|| format!("{} is not valid", path.display()))?;
+        Ok(Some(config))
+    }
+
+    fn validate(&self) -> Result<()> {
+        let time = |text: &str| NaiveTime::parse_from_str(text.trim(), "%H:%M").is_ok();
+        if !time(&self.send_at) {
+            bail!("send_at `{}` is not a time like 07:00", self.send_at);
+        }
+        for run in &self.runs {
+            if !time(&run.at) {
+                bail!(
+                    "the run of {} on {} is at `{}`, not a time like 02:30",
+                    run.policy,
+                    run.bucket,
+                    run.at
+                );
+            }
+        }
+        if let Some(email) = &self.email {
+            if email.to.is_empty() {
+                bail!("email.to names no recipient");
+            }
+            if email.ses.is_some() == email.smtp.is_some() {
+                bail!("email needs exactly one of ses and smtp");
+            }
+        }
+        if let Some(prefix) = &self.report_prefix {
+            report_location(prefix)?;
+        }
+        Ok(())
+    }
+}
+
+/// Bucket and key prefix of an `s3://bucket/prefix/` location
+fn report_location(url: &str) -> Result<(&str, &str)> {
+    let rest = url
+        .strip_prefix("s3://")
+        .with_context(|| format!("report_prefix `{url}` does not start with s3://"))?;
+    let (bucket, prefix) = rest.split_once('/').unwrap_or((rest, ""));
+    if bucket.is_empty() {
+        bail!("report_prefix `{url}` names no bucket");
+    }
+    Ok((bucket, prefix))
+}
+
+/// One scheduled policy run, as the digest reports it
+#[derive(Clone, Debug)]
+pub struct DigestRun {
+    pub policy: String,
+    pub bucket: String,
+    pub target_class: Option<StorageClassTier>,
+    pub started_at: DateTime<Utc>,
+    /// What the run did, or why it failed or never started
+    pub outcome: std::result::Result<PolicySummary, String>,
+    /// Monthly storage cost the run's transitions save, at list prices
+    pub savings: Option<f64>,
+}
+
+impl DigestRun {
+    /// The monthly savings of a finished run in a bucket in `region`, or
+    /// `None` when no class it moved from is priced
+    pub fn savings_of(
+        summary: &PolicySummary,
+        region: Option<&str>,
+        target: &StorageClassTier,
+    ) -> Option<f64> {
+        summary
+            .moved_from
+            .iter()
+            .filter_map(|(class, bytes)| pricing::monthly_savings(region, class, target, *bytes))
+            .reduce(|a, b| a + b)
+    }
+
+    fn summary(&self) -> Option<&PolicySummary> {
+        self.outcome.as_ref().ok()
+    }
+
+    fn title(&self) -> String {
+        match &self.target_class {
+            Some(class) => format!("{} on {} (to {})", self.policy, self.bucket, class.label()),
+            None => format!("{} on {}", self.policy, self.bucket),
+        }
+    }
+
+    /// One line of the run's results
+    fn result(&self) -> String {
+        let summary = match &self.outcome {
+            Ok(summary) => summary,
+            Err(err) => return format!("failed: {err}"),
+        };
+        let mut line = format!(
+            "{} of {} matches transitioned ({}), {} failed",
+            summary.transitioned,
+            summary.matched,
+            format_bytes(summary.bytes),
+            summary.failed.len()
+        );
+        if summary.restores_requested > 0 {
+            let _ = write!(line, ", {} restores requested", summary.restores_requested);
+        }
+        if let Some(savings) = self.savings {
+            let _ = write!(line, ", saving ${savings:.2}/month");
+        }
+        if summary.interrupted {
+            line.push_str(", stopped early");
+        }
+        line
+    }
+}
+
+/// The policy runs since the last digest, rendered for the email and for
+/// the detailed report it links to
+pub struct Digest {
+    pub runs: Vec<DigestRun>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Digest {
+    pub fn new(runs: Vec<DigestRun>) -> Self {
+        Self {
+            runs,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn transitioned(&self) -> usize {
+        self.runs
+            .iter()
+            .filter_map(DigestRun::summary)
+            .map(|summary| summary.transitioned)
+            .sum()
+    }
+
+    fn bytes(&self) -> u64 {
+        self.runs
+            .iter()
+            .filter_map(DigestRun::summary)
+            .map(|summary| summary.bytes)
+            .sum()
+    }
+
+    /// Objects that failed, plus runs that failed as a whole
+    fn failures(&self) -> usize {
+        self.runs
+            .iter()
+            .map(|run| match &run.outcome {
+                Ok(summary) => summary.failed.len(),
+                Err(_) => 1,
+            })
+            .sum()
+    }
+
+    fn savings(&self) -> Option<f64> {
+        self.runs
+            .iter()
+            .filter_map(|run| run.savings)
+            .reduce(|a, b| a + b)
+    }
+
+    pub fn subject(&self) -> String {
+        let mut subject = format!(
+            "bucket-brigade: {} policy runs, {} objects transitioned ({})",
+            self.runs.len(),
+            self.transitioned(),
+            format_bytes(self.bytes())
+        );
+        let failures = self.failures();
+        if failures > 0 {
+            let _ = write!(subject, ", {failures} failures");
+        }
+        subject
+    }
+
+    fn totals(&self) -> String {
+        let mut totals = format!(
+            "{} objects transitioned, {}, {} failures",
+            self.transitioned(),
+            format_bytes(self.bytes()),
+            self.failures()
+        );
+        if let Some(savings) = self.savings() {
+            let _ = write!(
+                totals,
+                ", projected savings ${savings:.2}/month at list prices"
+            );
+        }
+        totals
+    }
+
+    /// The plain-text part of the email; `report` is the link to, or path
+    /// of, the detailed report
+    pub fn text(&self, report: &str) -> String {
+        let mut text = format!("{}\n\n", self.totals());
+        if self.runs.is_empty() {
+            text.push_str("No scheduled policy ran since the last digest.\n");
+        }
+        for run in &self.runs {
+            let _ = writeln!(
+                text,
+                "- {} at {}: {}",
+                run.title(),
+                run.started_at.format("%Y-%m-%d %H:%M UTC"),
+                run.result()
+            );
+            for (key, reason) in run
+                .summary()
+                .into_iter()
+                .flat_map(|s| &s.failed)
+                .take(EMAIL_FAILURES)
+            {
+                let _ = writeln!(text, "    {key}: {reason}");
+            }
+        }
+        let _ = write!(text, "\nDetailed report: {report}\n");
+        text
+    }
+
+    /// The HTML part of the email
+    pub fn html(&self, report: &str) -> String {
+        let mut page = self.head("bucket-brigade daily digest");
+        let _ = writeln!(page, "<p>{}</p>", escape(&self.totals()));
+        self.runs_table(&mut page);
+        let link = if report.starts_with("https://") {
+            format!("<a href=\"{}\">Detailed report</a>", escape(report))
+        } else {
+            format!("Detailed report: {}", escape(report))
+        };
+        let _ = writeln!(page, "<p>{link}</p>\n</body>\n</html>");
+        page
+    }
+
+    /// Every run with its failures, as one self-contained page
+    pub fn report_html(&self) -> String {
+        let mut page = self.head("Scheduled policy runs");
+        let _ = writeln!(page, "<p>{}</p>", escape(&self.totals()));
+        self.runs_table(&mut page);
+        for run in &self.runs {
+            let Some(summary) = run.summary() else {
+                continue;
+            };
+            let _ = writeln!(page, "<h2>{}</h2>", escape(&run.title()));
+            let _ = writeln!(
+                page,
+                "<p class=\"meta\">{} matched · {} already in the class · {} protected · \
+                 {} waiting for a restore · journal {}</p>",
+                summary.matched,
+                summary.already_in_class,
+                summary.protected,
+                summary.needs_restore,
+                escape(summary.job_id.as_deref().unwrap_or("–"))
+            );
+            if summary.failed.is_empty() {
+                continue;
+            }
+            page.push_str("<table>\n<tr><th>Key</th><th>Error</th></tr>\n");
+            for (key, reason) in summary.failed.iter().take(REPORT_FAILURES) {
+                let _ = writeln!(
+                    page,
+                    "<tr><td>{}</td><td class=\"failed\">{}</td></tr>",
+                    escape(key),
+                    escape(reason)
+                );
+            }
+            page.push_str("</table>\n");
+            if summary.failed.len() > REPORT_FAILURES {
+                let _ = writeln!(
+                    page,
+                    "<p class=\"meta\">{} more failures are in the run's journal.</p>",
+                    summary.failed.len() - REPORT_FAILURES
+                );
+            }
+        }
+        page.push_str(
+            "<p class=\"meta\">Savings are at list prices for the first 50 TB tier; \
+             treat them as guidance, not a quote.</p>\n</body>\n</html>\n",
+        );
+        page
+    }
+
+    fn head(&self, title: &str) -> String {
+        format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>{title}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n<h1>{title}</h1>\n\
+             <p class=\"meta\">Generated {}</p>\n",
+            self.created_at.format("%Y-%m-%d %H:%M UTC")
+        )
+    }
+
+    fn runs_table(&self, page: &mut String) {
+        if self.runs.is_empty() {
+            page.push_str("<p>No scheduled policy ran since the last digest.</p>\n");
+            return;
+        }
+        page.push_str(
+            "<table>\n<tr><th>Run</th><th>Started</th><th class=\"num\">Transitioned</th>\
+             <th class=\"num\">Size</th><th class=\"num\">Failed</th>\
+             <th class=\"num\">Saves per month</th></tr>\n",
+        );
+        for run in &self.runs {
+            let (transitioned, bytes, failed) = match &run.outcome {
+                Ok(summary) => (
+                    summary.transitioned.to_string(),
+                    format_bytes(summary.bytes),
+                    summary.failed.len().to_string(),
+                ),
+                Err(err) => ("–".to_string(), "–".to_string(), escape(err)),
+            };
+            let _ = writeln!(
+                page,
+                "<tr><td>{}</td><td>{}</td><td class=\"num\">{transitioned}</td>\
+                 <td class=\"num\">{bytes}</td><td class=\"num\">{failed}</td>\
+                 <td class=\"num\">{}</td></tr>",
+                escape(&run.title()),
+                run.started_at.format("%Y-%m-%d %H:%M"),
+                run.savings
+                    .map(|savings| format!("${savings:.2}"))
+                    .unwrap_or_else(|| "–".to_string())
+            );
+        }
+        page.push_str("</table>\n");
+    }
+
+    /// Write the detailed report to the exports directory, upload it under
+    /// `report_prefix` when one is set, and email the digest when email is
+    /// set up. Returns where the report can be read.
+    pub async fn deliver(&self, config: &DigestConfig, s3: &S3Service) -> Result<String> {
+        let dir = directories::ProjectDirs::from("com", "bucket-brigade", "bucket-brigade")
+            .map(|dirs| dirs.config_dir().to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("exports");
+        fs::create_dir_all(&dir)?;
+        let name = format!(
+            "policy-digest-{}.html",
+            self.created_at.format("%Y%m%d-%H%M%S")
+        );
+        let report = self.report_html();
+        let path = dir.join(&name);
+        fs::write(&path, &report)?;
+        let location = match &config.report_prefix {
+            Some(prefix) => {
+                let (bucket, prefix) = report_location(prefix)?;
+                let key = format!("{prefix}{name}");
+                s3.publish_html(bucket, &key, &report, REPORT_LINK)
+                    .await
+                    .with_context(|| format!("uploading the report to s3://{bucket}/{key}"))?
+            }
+            None => path.display().to_string(),
+        };
+        if let Some(email) = &config.email {
+            let (text, html) = (self.text(&location), self.html(&location));
+            send(email, &config.sdk, &self.subject(), &text, &html).await?;
+        }
+        Ok(location)
+    }
+}
+
+#[cfg(feature = "email")]
+async fn send(
+    email: &EmailSettings,
+    sdk: &SdkOptions,
+    subject: &str,
+    text: &str,
+    html: &str,
+) -> Result<()> {
+    match (&email.ses, &email.smtp) {
+        (Some(ses), _) => send_ses(email, ses, sdk, subject, text, html).await,
+        (None, Some(smtp)) => send_smtp(email, smtp, subject, text, html).await,
+        (None, None) => bail!("email needs exactly one of ses and smtp"),
+    }
+}
+
+#[cfg(feature = "email")]
+async fn send_ses(
+    email: &EmailSettings,
+    ses: &SesSettings,
+    sdk: &SdkOptions,
+    subject: &str,
+    text: &str,
+    html: &str,
+) -> Result<()> {
+    use aws_sdk_sesv2::types::{Body, Content, Destination, EmailContent, Message};
+
+    let sdk_config = crate::aws::load_sdk_config(sdk).await?;
+    let mut config = aws_sdk_sesv2::config::Builder::from(&sdk_config);
+    if let Some(region) = &ses.region {
+        config = config.region(aws_sdk_sesv2::config::Region::new(region.clone()));
+    }
+    let client = aws_sdk_sesv2::Client::from_conf(config.build());
+    let content = |data: &str| Content::builder().data(data).charset("UTF-8").build();
+    let message = Message::builder()
+        .subject(content(subject)?)
+        .body(
+            Body::builder()
+                .text(content(text)?)
+                .html(content(html)?)
+                .build(),
+        )
+        .build();
+    client
+        .send_email()
+        .from_email_address(&email.from)
+        .destination(
+            Destination::builder()
+                .set_to_addresses(Some(email.to.clone()))
+                .build(),
+        )
+        .content(EmailContent::builder().simple(message).build())
+        .send()
+        .await
+        .context("SES refused the digest")?;
+    Ok(())
+}
+
+#[cfg(feature = "email")]
+async fn send_smtp(
+    email: &EmailSettings,
+    smtp: &SmtpSettings,
+    subject: &str,
+    text: &str,
+    html: &str,
+) -> Result<()> {
+    use lettre::message::MultiPart;
+    use lettre::transport::smtp::authentication::Credentials;
+    use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+
+    let mut message = Message::builder()
+        .from(email.from.parse().context("email.from is not an address")?)
+        .subject(subject);
+    for to in &email.to {
+        message = message.to(to
+            .parse()
+            .with_context(|| format!("{to} is not an address"))?);
+    }
+    let message = message.multipart(MultiPart::alternative_plain_html(
+        text.to_string(),
+        html.to_string(),
+    ))?;
+    let mut transport =
+        AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&smtp.host)?.port(smtp.port);
+    if let Some(username) = &smtp.username {
+        let password = match &smtp.password_env {
+            Some(var) => std::env::var(var).with_context(|| format!("{var} is not set"))?,
+            None => String::new(),
+        };
+        transport = transport.credentials(Credentials::new(username.clone(), password));
+    }
+    transport
+        .build()
+        .send(message)
+        .await
+        .with_context(|| format!("{} refused the digest", smtp.host))?;
+    Ok(())
+}
+
+/// Sending is only compiled with the `email` feature
+#[cfg(not(feature = "email"))]
+async fn send(
+    _email: &EmailSettings,
+    _sdk: &SdkOptions,
+    _subject: &str,
+    _text: &str,
+    _html: &str,
+) -> Result<()> {
+    bail!("this build cannot send email; rebuild with `cargo build --features email`")
+}
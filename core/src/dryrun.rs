@@ -0,0 +1,163 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use chrono::Utc;
+
+use crate::models::{ObjectInfo, RestoreStatus, StorageClassTier};
+use crate::reconcile::csv_field;
+
+/// What a transition would do with one targeted object
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DryRunOutcome {
+    Transition,
+    /// Already in the target class
+    AlreadyInClass,
+    /// Archived without a completed restore, so it cannot be copied in place
+    NeedsRestore,
+    /// Matched a protected pattern
+    Protected,
+}
+
+impl DryRunOutcome {
+    pub const SKIPPED: [DryRunOutcome; 3] = [
+        DryRunOutcome::AlreadyInClass,
+        DryRunOutcome::NeedsRestore,
+        DryRunOutcome::Protected,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DryRunOutcome::Transition => "transition",
+            DryRunOutcome::AlreadyInClass => "already in class",
+            DryRunOutcome::NeedsRestore => "needs restore",
+            DryRunOutcome::Protected => "protected",
+        }
+    }
+}
+
+pub struct DryRunEntry {
+    pub key: String,
+    pub size: i64,
+    pub storage_class: StorageClassTier,
+    pub outcome: DryRunOutcome,
+}
+
+/// Objects a transition would move out of one class
+pub struct ClassBreakdown {
+    pub class: StorageClassTier,
+    pub count: usize,
+    pub bytes: i64,
+}
+
+/// What a transition would do with each targeted object, worked out from
+/// the listing without calling S3
+pub struct DryRun {
+    pub bucket: String,
+    pub target_class: StorageClassTier,
+    pub entries: Vec<DryRunEntry>,
+}
+
+impl DryRun {
+    /// `objects` are the would-be targets, each with whether it is protected
+    pub fn new<'a>(
+        bucket: &str,
+        target_class: StorageClassTier,
+        objects: impl IntoIterator<Item = (&'a ObjectInfo, bool)>,
+    ) -> Self {
+        let entries = objects
+            .into_iter()
+            .map(|(obj, protected)| {
+                let outcome = if protected {
+                    DryRunOutcome::Protected
+                } else if obj.storage_class == target_class {
+                    DryRunOutcome::AlreadyInClass
+                } else if RestoreStatus::of(obj)
+                    .is_some_and(|status| status != RestoreStatus::Restored)
+                {
+                    DryRunOutcome::NeedsRestore
+                } else {
+                    DryRunOutcome::Transition
+                };
+                DryRunEntry {
+                    key: obj.key.clone(),
+                    size: obj.size,
+                    storage_class: obj.storage_class.clone(),
+                    outcome,
+                }
+            })
+            .collect();
+        Self {
+            bucket: bucket.to_string(),
+            target_class,
+            entries,
+        }
+    }
+
+    /// Number and total size of the objects with `outcome`
+    pub fn total(&self, outcome: DryRunOutcome) -> (usize, i64) {
+        self.entries
+            .iter()
+            .filter(|entry| entry.outcome == outcome)
+            .fold((0, 0), |(count, bytes), entry| {
+                (count + 1, bytes + entry.size)
+            })
+    }
+
+    /// Objects that would transition, by their current class, largest first
+    pub fn by_class(&self) -> Vec<ClassBreakdown> {
+        let mut classes: Vec<ClassBreakdown> = Vec::new();
+        for entry in &self.entries {
+            if entry.outcome != DryRunOutcome::Transition {
+                continue;
+            }
+            match classes.iter_mut().find(|c| c.class == entry.storage_class) {
+                Some(class) => {
+                    class.count += 1;
+                    class.bytes += entry.size;
+                }
+                None => classes.push(ClassBreakdown {
+                    class: entry.storage_class.clone(),
+                    count: 1,
+                    bytes: entry.size,
+                }),
+            }
+        }
+        classes.sort_by_key(|class| std::cmp::Reverse(class.bytes));
+        classes
+    }
+
+    pub fn skipped(&self) -> impl Iterator<Item = &DryRunEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.outcome != DryRunOutcome::Transition)
+    }
+
+    /// Write every object and its outcome as CSV to the exports directory
+    /// and return its path
+    pub fn export(&self) -> Result<PathBuf> {
+        let dir = directories::ProjectDirs::from("com", "bucket-brigade", "bucket-brigade")
+            .map(|dirs| dirs.config_dir().to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("exports");
+        fs::create_dir_all(&dir)?;
+        let path = dir.join(format!(
+            "{}-dry-run-{}.csv",
+            self.bucket,
+            Utc::now().format("%Y%m%d-%H%M%S")
+        ));
+        let mut csv = String::from("key,size,storage_class,target_class,outcome\n");
+        for entry in &self.entries {
+            csv.push_str(&format!(
+                "{},{},{},{},{}\n",
+                csv_field(&entry.key),
+                entry.size,
+                entry.storage_class.label(),
+                self.target_class.label(),
+                entry.outcome.label()
+            ));
+        }
+        fs::write(&path, csv)?;
+        Ok(path)
+    }
+}
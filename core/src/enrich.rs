@@ -0,0 +1,140 @@
+use futures::stream::{self, StreamExt};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::aws::S3Service;
+use crate::models::{ObjectEncryption, ObjectInfo, RestoreState, StorageClassTier};
+
+/// HeadObject requests in flight at once
+const ENRICH_CONCURRENCY: usize = 10;
+
+/// What HeadObject added to a listed object
+pub struct Enriched {
+    pub key: String,
+    pub restore_state: Option<RestoreState>,
+    pub restore_expiry: Option<String>,
+    pub encryption: Option<ObjectEncryption>,
+}
+
+impl Enriched {
+    /// Copy the fetched fields onto the listed object, keeping the ones
+    /// only ListObjectsV2 reports
+    pub fn apply(&self, obj: &mut ObjectInfo) {
+        obj.restore_state = self.restore_state.clone();
+        obj.restore_expiry = self.restore_expiry.clone();
+        obj.encryption = self.encryption.clone();
+    }
+}
+
+/// Whether a listed object is worth a HeadObject: ListObjectsV2 reports
+/// neither restore state nor encryption, and only archived objects need
+/// the restore state before they can be acted on
+pub fn needs_enrichment(obj: &ObjectInfo) -> bool {
+    // HeadObject without a version ID describes the current version only
+    obj.is_current()
+        && matches!(
+            obj.storage_class,
+            StorageClassTier::GlacierFlexibleRetrieval | StorageClassTier::GlacierDeepArchive
+        )
+}
+
+/// Handle to the HeadObject pipeline of one bucket. Keys are queued as
+/// listing pages arrive and fetched `ENRICH_CONCURRENCY` at a time; dropping
+/// the handle stops the pipeline.
+pub struct EnrichHandle {
+    pub bucket: String,
+    keys: mpsc::UnboundedSender<String>,
+    events: mpsc::UnboundedReceiver<Enriched>,
+    /// Event received by `ready` and not yet taken by `try_next`
+    pending: Option<Enriched>,
+    /// Keys queued and not yet reported back
+    outstanding: usize,
+    task: JoinHandle<()>,
+}
+
+impl EnrichHandle {
+    pub fn enqueue(&mut self, keys: impl IntoIterator<Item = String>) {
+        for key in keys {
+            if self.keys.send(key).is_ok() {
+                self.outstanding += 1;
+            }
+        }
+    }
+
+    /// Keys still waiting for their HeadObject
+    pub fn outstanding(&self) -> usize {
+        self.outstanding
+    }
+
+    /// Wait until `try_next` has an event to return
+    pub async fn ready(&mut self) {
+        if self.pending.is_some() {
+            return;
+        }
+        match self.events.recv().await {
+            Some(event) => self.pending = Some(event),
+            None => std::future::pending().await,
+        }
+    }
+
+    pub fn try_next(&mut self) -> Option<Enriched> {
+        let event = self
+            .pending
+            .take()
+            .or_else(|| self.events.try_recv().ok())?;
+        self.outstanding = self.outstanding.saturating_sub(1);
+        Some(event)
+    }
+}
+
+impl Drop for EnrichHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Start the HeadObject pipeline for `bucket`. A key whose HeadObject fails
+/// is reported with nothing fetched, so its state stays unknown.
+pub fn spawn_enrichment(s3: S3Service, bucket: String) -> EnrichHandle {
+    let (key_tx, mut key_rx) = mpsc::unbounded_channel::<String>();
+    let (event_tx, event_rx) = mpsc::unbounded_channel();
+    let task_bucket = bucket.clone();
+    let task = tokio::spawn(async move {
+        let mut heads = stream::poll_fn(move |cx| key_rx.poll_recv(cx))
+            .map(|key| {
+                let s3 = s3.clone();
+                let bucket = task_bucket.clone();
+                async move {
+                    match s3.refresh_object(&bucket, &key).await {
+                        Ok(head) => Enriched {
+                            key,
+                            restore_state: head.restore_state,
+                            restore_expiry: head.restore_expiry,
+                            encryption: head.encryption,
+                        },
+                        Err(_) => Enriched {
+                            key,
+                            restore_state: None,
+                            restore_expiry: None,
+                            encryption: None,
+                        },
+                    }
+                }
+            })
+            .buffer_unordered(ENRICH_CONCURRENCY);
+        while let Some(enriched) = heads.next().await {
+            if event_tx.send(enriched).is_err() {
+                break;
+            }
+        }
+    });
+
+    EnrichHandle {
+        bucket,
+        keys: key_tx,
+        events: event_rx,
+        pending: None,
+        outstanding: 0,
+        task,
+    }
+}
@@ -0,0 +1,252 @@
+use std::error::Error;
+
+use aws_sdk_s3::error::{ConnectorError, ErrorMetadata, ProvideErrorMetadata, SdkError};
+use aws_sdk_s3::operation::complete_multipart_upload::CompleteMultipartUploadError;
+use aws_sdk_s3::operation::copy_object::CopyObjectError;
+use aws_sdk_s3::operation::create_bucket::CreateBucketError;
+use aws_sdk_s3::operation::create_multipart_upload::CreateMultipartUploadError;
+use aws_sdk_s3::operation::delete_bucket::DeleteBucketError;
+use aws_sdk_s3::operation::delete_object::DeleteObjectError;
+use aws_sdk_s3::operation::delete_objects::DeleteObjectsError;
+use aws_sdk_s3::operation::get_object::GetObjectError;
+use aws_sdk_s3::operation::get_object_lock_configuration::GetObjectLockConfigurationError;
+use aws_sdk_s3::operation::get_object_tagging::GetObjectTaggingError;
+use aws_sdk_s3::operation::head_object::HeadObjectError;
+use aws_sdk_s3::operation::list_buckets::ListBucketsError;
+use aws_sdk_s3::operation::list_object_versions::ListObjectVersionsError;
+use aws_sdk_s3::operation::list_objects_v2::ListObjectsV2Error;
+use aws_sdk_s3::operation::put_bucket_tagging::PutBucketTaggingError;
+use aws_sdk_s3::operation::put_object::PutObjectError;
+use aws_sdk_s3::operation::put_object_legal_hold::PutObjectLegalHoldError;
+use aws_sdk_s3::operation::put_object_retention::PutObjectRetentionError;
+use aws_sdk_s3::operation::restore_object::RestoreObjectError;
+use aws_sdk_s3::operation::select_object_content::SelectObjectContentError;
+use aws_sdk_s3::operation::upload_part_copy::UploadPartCopyError;
+
+/// What went wrong with an AWS request, grouped by what the user can do
+/// about it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The request never reached AWS
+    Network,
+    AccessDenied,
+    /// Missing, expired or mistyped credentials
+    Credentials,
+    /// The KMS key of the object or bucket cannot be used
+    Kms,
+    NoSuchBucket,
+    NoSuchKey,
+    /// The bucket is in another region than the request went to
+    WrongRegion,
+    Throttled,
+    /// Archived, or already being restored
+    InvalidObjectState,
+    /// Any other error code
+    Other(String),
+}
+
+impl ErrorKind {
+    fn from_code(code: &str, message: Option<&str>) -> Self {
+        match code {
+            "AccessDenied" | "AllAccessDisabled" | "Forbidden"
+                if message.is_some_and(|m| m.contains("kms:")) =>
+            {
+                ErrorKind::Kms
+            }
+            "AccessDenied" | "AllAccessDisabled" | "Forbidden" => ErrorKind::AccessDenied,
+            "InvalidAccessKeyId"
+            | "SignatureDoesNotMatch"
+            | "ExpiredToken"
+            | "InvalidToken"
+            | "TokenRefreshRequired" => ErrorKind::Credentials,
+            "NoSuchBucket" => ErrorKind::NoSuchBucket,
+            "NoSuchKey" | "NotFound" => ErrorKind::NoSuchKey,
+            "PermanentRedirect"
+            | "TemporaryRedirect"
+            | "AuthorizationHeaderMalformed"
+            | "IllegalLocationConstraintException" => ErrorKind::WrongRegion,
+            "SlowDown"
+            | "Throttling"
+            | "ThrottlingException"
+            | "RequestLimitExceeded"
+            | "ServiceUnavailable" => ErrorKind::Throttled,
+            "InvalidObjectState"
+            | "ObjectAlreadyInActiveTierError"
+            | "RestoreAlreadyInProgress" => ErrorKind::InvalidObjectState,
+            code if code.starts_with("KMS.") => ErrorKind::Kms,
+            code => ErrorKind::Other(code.to_string()),
+        }
+    }
+
+    /// What the error means and what to check, for the status log
+    fn hint(&self) -> &'static str {
+        match self {
+            ErrorKind::Network => "AWS could not be reached; check the network or proxy",
+            ErrorKind::AccessDenied => {
+                "access denied; check the IAM policy, the bucket policy and any SCPs"
+            }
+            ErrorKind::Credentials => {
+                "the credentials were rejected; refresh the session or check the profile"
+            }
+            ErrorKind::Kms => {
+                "the KMS key cannot be used; it may be disabled or deleted, or kms:Decrypt / \
+                 kms:GenerateDataKey is not allowed"
+            }
+            ErrorKind::NoSuchBucket => "the bucket does not exist or was deleted",
+            ErrorKind::NoSuchKey => {
+                "the object was not found; the key list may be stale or point at another bucket"
+            }
+            ErrorKind::WrongRegion => {
+                "the bucket is in another region than the request was sent to"
+            }
+            ErrorKind::Throttled => {
+                "S3 is throttling requests; lower the concurrency or retry later"
+            }
+            ErrorKind::InvalidObjectState => {
+                "the object is archived or already being restored; restore it first or wait"
+            }
+            ErrorKind::Other(_) => "",
+        }
+    }
+}
+
+/// Operations whose service errors are recognised, with how to find their
+/// error metadata in an error chain
+const OPERATIONS: &[(&str, MetadataOf)] = &[
+    ("ListObjectsV2", metadata::<ListObjectsV2Error>),
+    ("ListObjectVersions", metadata::<ListObjectVersionsError>),
+    ("ListBuckets", metadata::<ListBucketsError>),
+    ("HeadObject", metadata::<HeadObjectError>),
+    ("GetObject", metadata::<GetObjectError>),
+    ("GetObjectTagging", metadata::<GetObjectTaggingError>),
+    ("CopyObject", metadata::<CopyObjectError>),
+    (
+        "CreateMultipartUpload",
+        metadata::<CreateMultipartUploadError>,
+    ),
+    ("UploadPartCopy", metadata::<UploadPartCopyError>),
+    (
+        "CompleteMultipartUpload",
+        metadata::<CompleteMultipartUploadError>,
+    ),
+    ("DeleteObject", metadata::<DeleteObjectError>),
+    ("DeleteObjects", metadata::<DeleteObjectsError>),
+    ("RestoreObject", metadata::<RestoreObjectError>),
+    ("SelectObjectContent", metadata::<SelectObjectContentError>),
+    ("CreateBucket", metadata::<CreateBucketError>),
+    ("DeleteBucket", metadata::<DeleteBucketError>),
+    ("PutBucketTagging", metadata::<PutBucketTaggingError>),
+    (
+        "GetObjectLockConfiguration",
+        metadata::<GetObjectLockConfigurationError>,
+    ),
+    ("PutObjectRetention", metadata::<PutObjectRetentionError>),
+    ("PutObjectLegalHold", metadata::<PutObjectLegalHoldError>),
+    ("PutObject", metadata::<PutObjectError>),
+];
+
+type MetadataOf = for<'a> fn(&'a (dyn Error + 'static)) -> Option<&'a ErrorMetadata>;
+
+fn metadata<'a, E: ProvideErrorMetadata + Error + 'static>(
+    cause: &'a (dyn Error + 'static),
+) -> Option<&'a ErrorMetadata> {
+    cause.downcast_ref::<E>().map(|err| err.meta())
+}
+
+/// The service error in `err`'s chain: its position, operation and metadata
+fn service_error(err: &anyhow::Error) -> Option<(usize, &'static str, &ErrorMetadata)> {
+    err.chain().enumerate().find_map(|(index, cause)| {
+        OPERATIONS
+            .iter()
+            .find_map(|(operation, metadata)| Some((index, *operation, metadata(cause)?)))
+    })
+}
+
+/// Error code of a HEAD response, which has no body to carry one
+fn head_status_code(err: &anyhow::Error) -> Option<&'static str> {
+    let status = err
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<SdkError<HeadObjectError>>())?
+        .raw_response()?
+        .status()
+        .as_u16();
+    match status {
+        301 => Some("PermanentRedirect"),
+        403 => Some("AccessDenied"),
+        404 => Some("NotFound"),
+        503 => Some("SlowDown"),
+        _ => None,
+    }
+}
+
+fn network_error_at(err: &anyhow::Error) -> Option<usize> {
+    err.chain().position(|cause| {
+        cause
+            .downcast_ref::<ConnectorError>()
+            .is_some_and(|err| err.is_io() || err.is_timeout())
+    })
+}
+
+pub fn classify(err: &anyhow::Error) -> Option<ErrorKind> {
+    if network_error_at(err).is_some() {
+        return Some(ErrorKind::Network);
+    }
+    let (_, _, meta) = service_error(err)?;
+    let code = meta.code().or_else(|| head_status_code(err))?;
+    Some(ErrorKind::from_code(code, meta.message()))
+}
+
+/// `err` as shown to the user: for AWS errors the code, the operation and
+/// what to check, after any context the error was given
+pub fn describe(err: &anyhow::Error) -> String {
+    // The SDK error itself sits just before the service or connector error
+    let (at, detail) = if let Some(at) = network_error_at(err) {
+        (at, ErrorKind::Network.hint().to_string())
+    } else if let Some((at, operation, meta)) = service_error(err) {
+        let detail = match meta.code().or_else(|| head_status_code(err)) {
+            Some(code) => match ErrorKind::from_code(code, meta.message()) {
+                ErrorKind::Other(_) => format!(
+                    "{code} ({operation}): {}",
+                    meta.message().unwrap_or("no message provided")
+                ),
+                kind => format!("{code} ({operation}): {}", kind.hint()),
+            },
+            None => format!(
+                "{operation} failed: {}",
+                meta.message().unwrap_or("unknown error")
+            ),
+        };
+        (at, detail)
+    } else {
+        return format!("{err:#}");
+    };
+    let context: Vec<String> = err
+        .chain()
+        .take(at.saturating_sub(1))
+        .map(|cause| cause.to_string())
+        .collect();
+    if context.is_empty() {
+        detail
+    } else {
+        format!("{}: {detail}", context.join(": "))
+    }
+}
+
+/// Short, groupable description of `err`, such as its error code
+pub fn category(err: &anyhow::Error) -> String {
+    if network_error_at(err).is_some() {
+        return "network failure".to_string();
+    }
+    if let Some((_, _, meta)) = service_error(err)
+        && let Some(code) = meta.code().or_else(|| head_status_code(err))
+    {
+        return code.to_string();
+    }
+    err.to_string()
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .take(60)
+        .collect()
+}
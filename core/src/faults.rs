@@ -0,0 +1,212 @@
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use aws_sdk_s3::primitives::SdkBody;
+use aws_smithy_runtime_api::client::http::{
+    HttpClient, HttpConnector, HttpConnectorFuture, HttpConnectorSettings, SharedHttpClient,
+    SharedHttpConnector,
+};
+use aws_smithy_runtime_api::client::orchestrator::{HttpRequest, HttpResponse};
+use aws_smithy_runtime_api::client::result::ConnectorError;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_runtime_api::http::StatusCode;
+
+/// Environment variable that turns on failure injection, like `--inject-faults`
+pub const INJECT_FAULTS_ENV: &str = "BUCKET_BRIGADE_INJECT_FAULTS";
+
+/// How long an injected timeout takes to fail
+const TIMEOUT_DELAY: Duration = Duration::from_secs(2);
+
+/// A failure that can be injected in place of an S3 response
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Fault {
+    /// 503 SlowDown, which the SDK retries with backoff
+    Throttle,
+    /// 403 AccessDenied, which is not retried
+    AccessDenied,
+    /// No response at all
+    Timeout,
+}
+
+impl Fault {
+    pub const ALL: [Fault; 3] = [Fault::Throttle, Fault::AccessDenied, Fault::Timeout];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Fault::Throttle => "throttle",
+            Fault::AccessDenied => "denied",
+            Fault::Timeout => "timeout",
+        }
+    }
+
+    fn parse(name: &str) -> Option<Fault> {
+        Fault::ALL
+            .into_iter()
+            .find(|fault| fault.label() == name.trim().to_ascii_lowercase())
+    }
+}
+
+/// Simulated S3 failures for rehearsing retries, journaling and resume
+/// (`--inject-faults <rate>[:kinds]`). A `rate` share of S3 requests is
+/// answered with one of `kinds`, picked at random, without being sent.
+/// Clones share the count of injected failures.
+#[derive(Clone, Debug)]
+pub struct FaultInjection {
+    /// Share of requests that fail, above 0 and at most 1
+    pub rate: f64,
+    pub kinds: Vec<Fault>,
+    injected: Arc<AtomicUsize>,
+}
+
+impl FaultInjection {
+    /// `--inject-faults`, or `BUCKET_BRIGADE_INJECT_FAULTS`, when either is set
+    pub fn from_args(args: &[String]) -> Result<Option<Self>> {
+        let spec = args
+            .windows(2)
+            .find(|pair| pair[0] == "--inject-faults")
+            .map(|pair| pair[1].clone())
+            .or_else(|| {
+                std::env::var(INJECT_FAULTS_ENV)
+                    .ok()
+                    .filter(|value| !value.is_empty())
+            });
+        spec.map(|spec| Self::parse(&spec)).transpose()
+    }
+
+    /// `0.1`, `10%`, or either followed by `:kind,kind` with kinds from
+    /// `throttle`, `denied` and `timeout` (all three when left out)
+    pub fn parse(spec: &str) -> Result<Self> {
+        let (rate, kinds) = match spec.split_once(':') {
+            Some((rate, kinds)) => (rate.trim(), Some(kinds)),
+            None => (spec.trim(), None),
+        };
+        let rate = match rate.strip_suffix('%') {
+            Some(percent) => percent.trim().parse::<f64>().map(|value| value / 100.0),
+            None => rate.parse::<f64>(),
+        }
+        .with_context(|| format!("Invalid fault rate `{rate}`"))?;
+        if !(rate > 0.0 && rate <= 1.0) {
+            bail!("Fault rate must be above 0 and at most 1 (or 100%)");
+        }
+        let kinds = match kinds {
+            Some(kinds) => kinds
+                .split(',')
+                .map(|name| {
+                    Fault::parse(name).with_context(|| {
+                        format!("Unknown fault `{name}` (use throttle, denied or timeout)")
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?,
+            None => Fault::ALL.to_vec(),
+        };
+        if kinds.is_empty() {
+            bail!("No fault kinds given");
+        }
+        Ok(Self {
+            rate,
+            kinds,
+            injected: Arc::new(AtomicUsize::new(0)),
+        })
+    }
+
+    /// Failures injected so far
+    pub fn injected(&self) -> usize {
+        self.injected.load(Ordering::Relaxed)
+    }
+
+    /// e.g. `10% of S3 requests (throttle, timeout)`
+    pub fn summary(&self) -> String {
+        let kinds: Vec<_> = self.kinds.iter().map(Fault::label).collect();
+        format!(
+            "{}% of S3 requests ({})",
+            self.rate * 100.0,
+            kinds.join(", ")
+        )
+    }
+
+    /// Wrap `inner` so requests fail at this rate
+    pub fn wrap(&self, inner: SharedHttpClient) -> SharedHttpClient {
+        SharedHttpClient::new(FaultyClient {
+            inner,
+            faults: self.clone(),
+        })
+    }
+
+    fn pick(&self) -> Option<Fault> {
+        if fastrand::f64() >= self.rate {
+            return None;
+        }
+        self.injected.fetch_add(1, Ordering::Relaxed);
+        Some(self.kinds[fastrand::usize(..self.kinds.len())])
+    }
+}
+
+#[derive(Debug)]
+struct FaultyClient {
+    inner: SharedHttpClient,
+    faults: FaultInjection,
+}
+
+impl HttpClient for FaultyClient {
+    fn http_connector(
+        &self,
+        settings: &HttpConnectorSettings,
+        components: &RuntimeComponents,
+    ) -> SharedHttpConnector {
+        SharedHttpConnector::new(FaultyConnector {
+            inner: self.inner.http_connector(settings, components),
+            faults: self.faults.clone(),
+        })
+    }
+}
+
+struct FaultyConnector {
+    inner: SharedHttpConnector,
+    faults: FaultInjection,
+}
+
+impl fmt::Debug for FaultyConnector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FaultyConnector")
+            .field("rate", &self.faults.rate)
+            .finish()
+    }
+}
+
+impl HttpConnector for FaultyConnector {
+    fn call(&self, request: HttpRequest) -> HttpConnectorFuture {
+        match self.faults.pick() {
+            None => self.inner.call(request),
+            Some(Fault::Throttle) => HttpConnectorFuture::ready(Ok(error_response(
+                503,
+                "SlowDown",
+                "Please reduce your request rate. (injected)",
+            ))),
+            Some(Fault::AccessDenied) => HttpConnectorFuture::ready(Ok(error_response(
+                403,
+                "AccessDenied",
+                "Access Denied (injected)",
+            ))),
+            Some(Fault::Timeout) => HttpConnectorFuture::new(async {
+                tokio::time::sleep(TIMEOUT_DELAY).await;
+                Err(ConnectorError::timeout("injected timeout".into()))
+            }),
+        }
+    }
+}
+
+/// An S3 error response as the service would send it
+fn error_response(status: u16, code: &str, message: &str) -> HttpResponse {
+    let body = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<Error><Code>{code}</Code><Message>{message}</Message><RequestId>INJECTED</RequestId></Error>"
+    );
+    let status = StatusCode::try_from(status).expect("valid status code");
+    let mut response = HttpResponse::new(status, SdkBody::from(body));
+    response
+        .headers_mut()
+        .insert("Content-Type", "application/xml");
+    response
+}
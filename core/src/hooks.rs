@@ -0,0 +1,130 @@
+use std::process::Stdio;
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use tokio::process::Command;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// What a batch does when the per-object hook fails
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HookFailure {
+    Ignore,
+    /// Log the failure and go on with the next object
+    Warn,
+    /// Stop the batch after the object whose hook failed
+    Stop,
+}
+
+/// Command run after each object a transition, restore or copy processed
+/// successfully (`--object-hook`). `{bucket}`, `{key}` and `{action}` are
+/// replaced with shell-quoted values; the same values are also passed as
+/// `BUCKET_BRIGADE_BUCKET`, `BUCKET_BRIGADE_KEY` and `BUCKET_BRIGADE_ACTION`.
+#[derive(Clone, Debug)]
+pub struct ObjectHook {
+    pub template: String,
+    pub on_failure: HookFailure,
+    timeout: Duration,
+}
+
+impl ObjectHook {
+    pub fn from_args(args: &[String]) -> Option<Self> {
+        let value = |flag: &str| {
+            args.windows(2)
+                .find(|pair| pair[0] == flag)
+                .map(|pair| pair[1].as_str())
+        };
+        let template = value("--object-hook").filter(|t| !t.trim().is_empty())?;
+        let on_failure = match value("--hook-failure") {
+            Some("ignore") => HookFailure::Ignore,
+            Some("stop") => HookFailure::Stop,
+            _ => HookFailure::Warn,
+        };
+        let timeout = value("--hook-timeout")
+            .and_then(|secs| secs.parse().ok())
+            .filter(|secs| *secs > 0)
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_TIMEOUT);
+        Some(Self {
+            template: template.to_string(),
+            on_failure,
+            timeout,
+        })
+    }
+
+    /// The template with its placeholders filled in, in one pass so that a
+    /// key containing `{action}` stays as it is
+    fn command_line(&self, bucket: &str, key: &str, action: &str) -> String {
+        let values = [("{bucket}", bucket), ("{key}", key), ("{action}", action)];
+        let mut line = String::new();
+        let mut rest = self.template.as_str();
+        while let Some(start) = rest.find('{') {
+            line.push_str(&rest[..start]);
+            rest = &rest[start..];
+            match values.iter().find(|(name, _)| rest.starts_with(name)) {
+                Some((name, value)) => {
+                    line.push_str(&shell_quote(value));
+                    rest = &rest[name.len()..];
+                }
+                None => {
+                    line.push('{');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        line.push_str(rest);
+        line
+    }
+
+    /// Run the hook for one object. Fails if the command cannot be started,
+    /// exits unsuccessfully or runs past the timeout.
+    pub async fn run(&self, bucket: &str, key: &str, action: &str) -> Result<()> {
+        let line = self.command_line(bucket, key, action);
+        let mut command = shell(&line);
+        command
+            .env("BUCKET_BRIGADE_BUCKET", bucket)
+            .env("BUCKET_BRIGADE_KEY", key)
+            .env("BUCKET_BRIGADE_ACTION", action)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        let output = tokio::time::timeout(self.timeout, command.output())
+            .await
+            .with_context(|| format!("hook timed out after {}s", self.timeout.as_secs()))?
+            .context("could not start the hook")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            match stderr.lines().find(|line| !line.trim().is_empty()) {
+                Some(line) => bail!("hook {}: {}", output.status, line.trim()),
+                None => bail!("hook {}", output.status),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+pub(crate) fn shell(line: &str) -> Command {
+    let mut command = Command::new("sh");
+    command.arg("-c").arg(line);
+    command
+}
+
+#[cfg(windows)]
+pub(crate) fn shell(line: &str) -> Command {
+    let mut command = Command::new("cmd");
+    command.arg("/C").arg(line);
+    command
+}
+
+/// Quote `value` as one shell word
+#[cfg(unix)]
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[cfg(windows)]
+fn shell_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "\"\""))
+}
@@ -0,0 +1,234 @@
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+
+use crate::models::{ObjectInfo, StorageClassTier};
+
+/// Columns of a CSV manifest without a schema (S3 Batch Operations style)
+const DEFAULT_SCHEMA: &str = "Bucket, Key";
+
+/// Where the rows of an inventory query come from
+#[derive(Clone, Debug)]
+pub struct InventorySource {
+    /// Column names in file order, as in an inventory `fileSchema`
+    pub schema: Vec<String>,
+    /// Bucket the CSV data files live in
+    pub files_bucket: String,
+    pub files: Vec<String>,
+    /// Whether keys in the files are URL-encoded (S3 Inventory does this)
+    pub encoded_keys: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Manifest {
+    destination_bucket: String,
+    file_format: String,
+    file_schema: String,
+    files: Vec<ManifestFile>,
+}
+
+#[derive(Deserialize)]
+struct ManifestFile {
+    key: String,
+}
+
+impl InventorySource {
+    /// Read an S3 Inventory `manifest.json`
+    pub fn from_manifest(json: &str) -> Result<Self> {
+        let manifest: Manifest =
+            serde_json::from_str(json).context("not an S3 Inventory manifest.json")?;
+        if !manifest.file_format.eq_ignore_ascii_case("csv") {
+            bail!(
+                "inventory format {} is not supported; configure a CSV inventory",
+                manifest.file_format
+            );
+        }
+        let files_bucket = manifest
+            .destination_bucket
+            .rsplit(":::")
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        Ok(Self {
+            schema: split_schema(&manifest.file_schema),
+            files_bucket,
+            files: manifest.files.into_iter().map(|f| f.key).collect(),
+            encoded_keys: true,
+        })
+    }
+
+    /// A single headerless CSV file of `Bucket, Key` rows
+    pub fn from_csv(bucket: &str, key: &str) -> Self {
+        Self {
+            schema: split_schema(DEFAULT_SCHEMA),
+            files_bucket: bucket.to_string(),
+            files: vec![key.to_string()],
+            encoded_keys: false,
+        }
+    }
+
+    fn column(&self, name: &str) -> Option<usize> {
+        let name = canonical_column(name);
+        self.schema
+            .iter()
+            .position(|c| c.eq_ignore_ascii_case(name))
+    }
+
+    /// S3 Select expression returning bucket, key, size, last modified and
+    /// storage class (when the schema has them) for rows matching `condition`
+    pub fn select_expression(&self, condition: &str) -> Result<String> {
+        let key = self
+            .column("Key")
+            .context("the manifest has no Key column")?;
+        let mut columns = vec![format!("s._{}", key + 1)];
+        for name in ["Bucket", "Size", "LastModifiedDate", "StorageClass"] {
+            columns.push(match self.column(name) {
+                Some(index) => format!("s._{}", index + 1),
+                None => "''".to_string(),
+            });
+        }
+        let mut sql = format!("SELECT {} FROM S3Object s", columns.join(", "));
+        let condition = condition.trim();
+        if !condition.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&self.translate_condition(condition)?);
+        }
+        Ok(sql)
+    }
+
+    /// Replace column names in a user condition with positional references,
+    /// leaving quoted literals alone. Size is cast so it compares as a number.
+    fn translate_condition(&self, condition: &str) -> Result<String> {
+        let mut out = String::new();
+        let mut chars = condition.chars().peekable();
+        while let Some(ch) = chars.next() {
+            if ch == '\'' {
+                out.push(ch);
+                for quoted in chars.by_ref() {
+                    out.push(quoted);
+                    if quoted == '\'' {
+                        break;
+                    }
+                }
+            } else if ch.is_ascii_alphabetic() || ch == '_' {
+                let mut word = ch.to_string();
+                while let Some(&next) = chars.peek() {
+                    if next.is_ascii_alphanumeric() || next == '_' {
+                        word.push(next);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                match self.column(&word) {
+                    Some(index) if canonical_column(&word) == "Size" => {
+                        out.push_str(&format!("CAST(s._{} AS INT)", index + 1));
+                    }
+                    Some(index) => out.push_str(&format!("s._{}", index + 1)),
+                    None if is_column_alias(&word) => {
+                        bail!("column {word} is not in this manifest");
+                    }
+                    None => out.push_str(&word),
+                }
+            } else {
+                out.push(ch);
+            }
+        }
+        Ok(out)
+    }
+
+    /// Turn one output record of `select_expression` into an object in `bucket`.
+    /// Rows for other buckets are dropped.
+    pub fn parse_record(&self, line: &str, bucket: &str) -> Option<ObjectInfo> {
+        let fields = parse_csv_line(line);
+        let [key, row_bucket, size, modified, class] = fields.as_slice() else {
+            return None;
+        };
+        if !row_bucket.is_empty() && row_bucket != bucket {
+            return None;
+        }
+        let key = if self.encoded_keys {
+            urlencoding::decode(key).ok()?.into_owned()
+        } else {
+            key.clone()
+        };
+        Some(ObjectInfo {
+            key,
+            size: size.parse().unwrap_or_default(),
+            last_modified: (!modified.is_empty()).then(|| modified.clone()),
+            storage_class: if class.is_empty() {
+                StorageClassTier::Unknown(String::new())
+            } else {
+                StorageClassTier::from(Some(aws_sdk_s3::types::StorageClass::from(class.as_str())))
+            },
+            restore_state: None,
+            restore_expiry: None,
+            owner: None,
+            tags: None,
+            encryption: None,
+            version: None,
+        })
+    }
+}
+
+fn split_schema(schema: &str) -> Vec<String> {
+    schema.split(',').map(|c| c.trim().to_string()).collect()
+}
+
+/// Map friendly names used in the query prompt to inventory column names
+fn canonical_column(name: &str) -> &str {
+    match name.to_ascii_lowercase().as_str() {
+        "key" => "Key",
+        "bucket" => "Bucket",
+        "size" => "Size",
+        "last_modified" | "lastmodified" | "lastmodifieddate" => "LastModifiedDate",
+        "storage_class" | "storageclass" => "StorageClass",
+        _ => name,
+    }
+}
+
+fn is_column_alias(name: &str) -> bool {
+    canonical_column(name) != name
+        || ["Key", "Bucket", "Size", "LastModifiedDate", "StorageClass"].contains(&name)
+}
+
+/// Split one CSV record, honouring double-quoted fields
+pub fn parse_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(ch),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+/// Split prompt input into the manifest location and the optional condition:
+/// `s3://bucket/path/manifest.json where size > 1000`
+pub fn parse_prompt(input: &str) -> Result<(String, String, String)> {
+    let input = input.trim();
+    let (location, rest) = input.split_once(char::is_whitespace).unwrap_or((input, ""));
+    let path = location
+        .strip_prefix("s3://")
+        .context("start with the manifest location, e.g. s3://bucket/path/manifest.json")?;
+    let (bucket, key) = path
+        .split_once('/')
+        .filter(|(bucket, key)| !bucket.is_empty() && !key.is_empty())
+        .context("the manifest location needs a bucket and a key")?;
+    let rest = rest.trim();
+    let condition = match rest.get(..5) {
+        Some(word) if word.eq_ignore_ascii_case("where") => rest[5..].trim(),
+        _ => rest,
+    };
+    Ok((bucket.to_string(), key.to_string(), condition.to_string()))
+}
@@ -0,0 +1,230 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::models::{JobKind, JobRecord, JournalEntry};
+use crate::notify;
+use crate::tickets::TicketLink;
+
+/// Recent jobs whose request times feed duration estimates
+const LATENCY_SAMPLE_JOBS: usize = 5;
+
+/// Persists batch job summaries and a per-key journal for each job
+pub struct JobHistory {
+    file_path: PathBuf,
+    journal_dir: PathBuf,
+    jobs: Vec<JobRecord>,
+    /// Total request time and count per running job
+    timings: HashMap<String, (Duration, u32)>,
+}
+
+impl JobHistory {
+    pub fn new() -> Result<Self> {
+        let config_dir = directories::ProjectDirs::from("com", "bucket-brigade", "bucket-brigade")
+            .map(|dirs| dirs.config_dir().to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        let journal_dir = config_dir.join("journal");
+        fs::create_dir_all(&journal_dir)?;
+        let file_path = config_dir.join("jobs.json");
+
+        let jobs = if file_path.exists() {
+            let content = fs::read_to_string(&file_path)?;
+            serde_json::from_str(&content).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            file_path,
+            journal_dir,
+            jobs,
+            timings: HashMap::new(),
+        })
+    }
+
+    /// Record the start of a batch job, with the operator's note on why it
+    /// runs and its change ticket, and return its id
+    pub fn begin(
+        &mut self,
+        kind: JobKind,
+        bucket: &str,
+        target: &str,
+        total: usize,
+        note: Option<String>,
+        ticket: Option<TicketLink>,
+    ) -> String {
+        let id = uuid::Uuid::new_v4().to_string();
+        self.jobs.push(JobRecord {
+            id: id.clone(),
+            kind,
+            bucket: bucket.to_string(),
+            target: target.to_string(),
+            started_at: chrono::Utc::now().to_rfc3339(),
+            finished_at: None,
+            total,
+            succeeded: 0,
+            failed: 0,
+            interrupted: false,
+            request_ms: None,
+            note,
+            ticket,
+        });
+        if let Some(job) = self.jobs.last() {
+            notify::job_started(job);
+        }
+        let _ = self.save();
+        id
+    }
+
+    /// Append the outcome for one key to the job's journal
+    pub fn record(&mut self, job_id: &str, key: &str, error: Option<String>) {
+        self.record_with_note(job_id, key, error, None);
+    }
+
+    /// Append an outcome that carries a note, such as how a move was verified
+    pub fn record_with_note(
+        &mut self,
+        job_id: &str,
+        key: &str,
+        error: Option<String>,
+        note: Option<String>,
+    ) {
+        if let Some(job) = self.jobs.iter_mut().find(|j| j.id == job_id) {
+            if error.is_some() {
+                job.failed += 1;
+            } else {
+                job.succeeded += 1;
+            }
+        }
+        let entry = JournalEntry {
+            key: key.to_string(),
+            at: chrono::Utc::now().to_rfc3339(),
+            error,
+            note,
+        };
+        let _ = self.append_entry(job_id, &entry);
+    }
+
+    /// Add the time one request of the job took to its mean request time
+    pub fn time_request(&mut self, job_id: &str, elapsed: Duration) {
+        let (total, count) = self.timings.entry(job_id.to_string()).or_default();
+        *total += elapsed;
+        *count += 1;
+        let mean = (*total / *count).as_millis() as u64;
+        if let Some(job) = self.jobs.iter_mut().find(|j| j.id == job_id) {
+            job.request_ms = Some(mean);
+        }
+    }
+
+    /// Mean request time over the last few jobs of `kind` that measured it
+    pub fn request_latency(&self, kind: JobKind) -> Option<Duration> {
+        let samples: Vec<u64> = self
+            .jobs()
+            .filter(|job| job.kind == kind)
+            .filter_map(|job| job.request_ms)
+            .take(LATENCY_SAMPLE_JOBS)
+            .collect();
+        if samples.is_empty() {
+            return None;
+        }
+        let mean = samples.iter().sum::<u64>() / samples.len() as u64;
+        Some(Duration::from_millis(mean))
+    }
+
+    pub fn finish(&mut self, job_id: &str) {
+        if let Some(job) = self.jobs.iter_mut().find(|j| j.id == job_id) {
+            job.finished_at = Some(chrono::Utc::now().to_rfc3339());
+            notify::job_finished(job);
+        }
+        self.timings.remove(job_id);
+        let _ = self.save();
+    }
+
+    /// Close a job that was stopped before every key was processed
+    pub fn interrupt(&mut self, job_id: &str) {
+        if let Some(job) = self.jobs.iter_mut().find(|j| j.id == job_id) {
+            job.interrupted = true;
+        }
+        self.finish(job_id);
+    }
+
+    /// Jobs ordered from most recent to oldest
+    pub fn jobs(&self) -> impl Iterator<Item = &JobRecord> {
+        self.jobs.iter().rev()
+    }
+
+    /// Keys of `bucket` that a `kind` job handled successfully at or after
+    /// `since`. Jobs are read from disk so those of other instances count.
+    pub fn succeeded_since(
+        &self,
+        kind: JobKind,
+        bucket: &str,
+        since: DateTime<Utc>,
+    ) -> HashSet<String> {
+        let jobs: Vec<JobRecord> = fs::read_to_string(&self.file_path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_else(|| self.jobs.clone());
+        let recent = |at: &str| DateTime::parse_from_rfc3339(at).is_ok_and(|at| at >= since);
+        jobs.iter()
+            .filter(|job| job.kind == kind && job.bucket == bucket)
+            .filter(|job| job.finished_at.as_deref().is_none_or(recent))
+            .flat_map(|job| self.entries(&job.id).unwrap_or_default())
+            .filter(|entry| entry.error.is_none() && recent(&entry.at))
+            .map(|entry| entry.key)
+            .collect()
+    }
+
+    /// Keys that any job of `kind` on `bucket` with `target` processed
+    /// without error, however long ago, such as objects already re-encrypted
+    /// under a KMS key
+    pub fn succeeded_for(&self, kind: JobKind, bucket: &str, target: &str) -> HashSet<String> {
+        self.jobs
+            .iter()
+            .filter(|job| job.kind == kind && job.bucket == bucket && job.target == target)
+            .flat_map(|job| self.entries(&job.id).unwrap_or_default())
+            .filter(|entry| entry.error.is_none())
+            .map(|entry| entry.key)
+            .collect()
+    }
+
+    /// Read the per-key results of a job from its journal
+    pub fn entries(&self, job_id: &str) -> Result<Vec<JournalEntry>> {
+        let path = self.journal_path(job_id);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let reader = BufReader::new(fs::File::open(path)?);
+        let mut entries = Vec::new();
+        for line in reader.lines() {
+            if let Ok(entry) = serde_json::from_str(&line?) {
+                entries.push(entry);
+            }
+        }
+        Ok(entries)
+    }
+
+    fn journal_path(&self, job_id: &str) -> PathBuf {
+        self.journal_dir.join(format!("{job_id}.jsonl"))
+    }
+
+    fn append_entry(&self, job_id: &str, entry: &JournalEntry) -> Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.journal_path(job_id))?;
+        writeln!(file, "{}", serde_json::to_string(entry)?)?;
+        Ok(())
+    }
+
+    fn save(&self) -> Result<()> {
+        let json = serde_json::to_string_pretty(&self.jobs)?;
+        fs::write(&self.file_path, json)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+//! The migration engine behind bucket-brigade, for tools that drive S3
+//! storage-class migrations without the terminal UI.
+//!
+//! - [`aws::S3Service`] wraps every S3 call: listings, copies, transitions,
+//!   restores and bucket configuration.
+//! - [`mask::ObjectMask`] selects objects by prefix, suffix, regex, class,
+//!   size, age or a [`script`] predicate.
+//! - [`batch`] has the per-object steps of a transition or move, and
+//!   [`multipart`] the resumable copies they use for large objects.
+//! - [`journal::JobHistory`] records every batch and each key it touched;
+//!   [`tracker::RestoreTracker`] follows Glacier restores until they finish.
+//! - [`protect`], [`schedule`], [`waves`] and [`hooks`] are the policies a
+//!   batch applies around those steps.
+//! - [`faults::FaultInjection`] makes S3 requests fail at random, for
+//!   rehearsing how a batch retries and resumes.
+//!
+//! State files live under the same project directories as the binary's, so
+//! a tool built on this crate sees the same history, tracked restores and
+//! pending multipart uploads.
+//!
+//! ```no_run
+//! use s3_migration_core::aws::{S3Service, SdkOptions};
+//! use s3_migration_core::mask::{MaskKind, ObjectMask};
+//!
+//! # async fn run() -> anyhow::Result<()> {
+//! let s3 = S3Service::new(&SdkOptions::default()).await?;
+//! let logs = ObjectMask {
+//!     name: "logs".into(),
+//!     pattern: "logs/".into(),
+//!     kind: MaskKind::Prefix,
+//!     case_sensitive: true,
+//!     storage_class_filter: None,
+//!     owner_filter: None,
+//!     unread_days: None,
+//!     min_size: None,
+//!     max_size: None,
+//!     older_than_days: None,
+//!     newer_than_days: None,
+//! };
+//! let (objects, _) = s3
+//!     .list_objects_paginated("my-bucket", None, None, None, 1000)
+//!     .await?;
+//! for obj in objects.iter().filter(|obj| logs.matches_object(obj)) {
+//!     println!("{} {}", obj.key, obj.storage_class.label());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod access;
+pub mod approvals;
+pub mod athena;
+pub mod aws;
+pub mod bandwidth;
+pub mod batch;
+pub mod bucket_config;
+pub mod bundle;
+pub mod coldness;
+pub mod collisions;
+pub mod connectivity;
+pub mod coordination;
+pub mod decommission;
+pub mod digest;
+pub mod dryrun;
+pub mod enrich;
+pub mod errors;
+pub mod faults;
+pub mod hooks;
+pub mod inventory;
+pub mod journal;
+pub mod lifecycle;
+pub mod loader;
+pub mod mask;
+pub mod models;
+pub mod moves;
+pub mod multipart;
+pub mod notify;
+pub mod owners;
+pub mod partition;
+pub mod plan;
+pub mod policy;
+pub mod presets;
+pub mod preview;
+pub mod pricing;
+pub mod protect;
+pub mod recency;
+pub mod reconcile;
+pub mod report;
+pub mod schedule;
+pub mod script;
+pub mod shards;
+pub mod sizes;
+pub mod tags;
+pub mod telemetry;
+pub mod tickets;
+pub mod tracker;
+pub mod transform;
+pub mod update;
+pub mod versions;
+pub mod waves;
@@ -0,0 +1,317 @@
+use anyhow::{Result, bail};
+use chrono::{DateTime, Utc};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::mask::{MaskKind, ObjectMask};
+use crate::models::{ObjectInfo, StorageClassTier};
+use crate::multipart::MULTIPART_COPY_THRESHOLD;
+use crate::pricing;
+
+/// A prefix mask plus target class, expressed as a bucket lifecycle transition
+#[derive(Clone, Debug)]
+pub struct LifecycleRule {
+    pub bucket: String,
+    pub prefix: String,
+    pub storage_class: StorageClassTier,
+    /// Days after object creation; the smallest value S3 accepts for the class
+    pub days: u32,
+}
+
+/// Why a class cannot be the target of a lifecycle transition
+pub fn unavailable(class: &StorageClassTier) -> Option<&'static str> {
+    match class {
+        StorageClassTier::Standard => Some("lifecycle rules cannot transition to STANDARD"),
+        StorageClassTier::ReducedRedundancy | StorageClassTier::Unknown(_) => {
+            Some("not a lifecycle transition target")
+        }
+        _ => None,
+    }
+}
+
+impl LifecycleRule {
+    /// Build a rule from the active mask. Mask settings a lifecycle filter cannot
+    /// express are returned as warnings.
+    pub fn from_mask(
+        bucket: &str,
+        mask: &ObjectMask,
+        storage_class: StorageClassTier,
+    ) -> Result<(Self, Vec<String>)> {
+        if !matches!(mask.kind, MaskKind::Prefix) {
+            bail!(
+                "lifecycle rules filter by prefix only; the active mask is {}",
+                mask.kind
+            );
+        }
+        if let Some(reason) = unavailable(&storage_class) {
+            bail!("{reason}");
+        }
+        let mut warnings = Vec::new();
+        if !mask.case_sensitive {
+            warnings.push("lifecycle prefixes are case-sensitive".to_string());
+        }
+        if let Some(class) = &mask.storage_class_filter {
+            warnings.push(format!(
+                "the {} class filter is not part of the rule",
+                class.label()
+            ));
+        }
+        if mask.owner_filter.is_some() {
+            warnings.push("the owner filter is not part of the rule".to_string());
+        }
+        if mask.unread_days.is_some() {
+            warnings.push(
+                "lifecycle rules count days since writing, not since the last read".to_string(),
+            );
+        }
+        if mask.min_size.is_some() || mask.max_size.is_some() {
+            warnings.push("the size filter is not part of the rule".to_string());
+        }
+        if mask.older_than_days.is_some() || mask.newer_than_days.is_some() {
+            warnings.push("the last-modified filter is not part of the rule".to_string());
+        }
+        let days = match storage_class {
+            StorageClassTier::StandardIa | StorageClassTier::OneZoneIa => 30,
+            _ => 0,
+        };
+        Ok((
+            Self {
+                bucket: bucket.to_string(),
+                prefix: mask.pattern.clone(),
+                storage_class,
+                days,
+            },
+            warnings,
+        ))
+    }
+
+    /// Rule id and Terraform resource name, e.g. `logs-2024-to-glacier`
+    fn id(&self) -> String {
+        let slug: String = format!("{}-to-{}", self.prefix, self.storage_class.label())
+            .to_ascii_lowercase()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect();
+        let slug = slug.trim_matches('-').to_string();
+        if slug.is_empty() {
+            "bucket-brigade".to_string()
+        } else {
+            slug
+        }
+    }
+
+    pub fn terraform(&self) -> String {
+        let name = format!("{}_{}", self.bucket, self.id()).replace(['-', '.'], "_");
+        format!(
+            r#"# Exported by bucket-brigade. aws_s3_bucket_lifecycle_configuration owns the
+# bucket's whole lifecycle configuration: merge this rule into an existing
+# resource for the bucket rather than adding a second one.
+resource "aws_s3_bucket_lifecycle_configuration" "{name}" {{
+  bucket = "{bucket}"
+
+  rule {{
+    id     = "{id}"
+    status = "Enabled"
+
+    filter {{
+      prefix = "{prefix}"
+    }}
+
+    transition {{
+      days          = {days}
+      storage_class = "{class}"
+    }}
+  }}
+}}
+"#,
+            bucket = hcl_escape(&self.bucket),
+            id = hcl_escape(&self.id()),
+            prefix = hcl_escape(&self.prefix),
+            days = self.days,
+            class = self.storage_class.label(),
+        )
+    }
+
+    pub fn cloudformation(&self) -> String {
+        format!(
+            r#"# Exported by bucket-brigade. Add the rule to the LifecycleConfiguration of
+# the AWS::S3::Bucket resource for {bucket}.
+LifecycleConfiguration:
+  Rules:
+    - Id: {id}
+      Status: Enabled
+      Prefix: {prefix}
+      Transitions:
+        - StorageClass: {class}
+          TransitionInDays: {days}
+"#,
+            bucket = self.bucket,
+            id = yaml_quote(&self.id()),
+            prefix = yaml_quote(&self.prefix),
+            class = self.storage_class.label(),
+            days = self.days,
+        )
+    }
+
+    /// Write the Terraform and CloudFormation snippets to the exports directory
+    pub fn export(&self) -> Result<(PathBuf, PathBuf)> {
+        let dir = directories::ProjectDirs::from("com", "bucket-brigade", "bucket-brigade")
+            .map(|dirs| dirs.config_dir().to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("exports");
+        fs::create_dir_all(&dir)?;
+        let stem = format!("{}-{}", self.bucket, self.id());
+        let terraform = dir.join(format!("{stem}.tf"));
+        let cloudformation = dir.join(format!("{stem}.cfn.yaml"));
+        fs::write(&terraform, self.terraform())?;
+        fs::write(&cloudformation, self.cloudformation())?;
+        Ok((terraform, cloudformation))
+    }
+}
+
+/// Days the comparison adds up costs over
+pub const COMPARISON_DAYS: i64 = 365;
+
+/// Lifecycle transitions run asynchronously, usually a day or two after an
+/// object becomes eligible
+pub const LIFECYCLE_LAG_DAYS: i64 = 2;
+
+/// Lifecycle rules skip objects smaller than this by default
+pub const LIFECYCLE_MIN_SIZE: i64 = 128 * 1024;
+
+/// What one mechanism costs over [`COMPARISON_DAYS`], in USD
+#[derive(Clone, Debug, Default)]
+pub struct MechanismCost {
+    /// Objects it moves to the target class within the period
+    pub moved: usize,
+    pub request_fees: f64,
+    /// Charged for leaving the source class before its minimum duration
+    pub early_deletion: f64,
+    pub storage: f64,
+}
+
+impl MechanismCost {
+    pub fn total(&self) -> f64 {
+        self.request_fees + self.early_deletion + self.storage
+    }
+}
+
+/// Dry-run comparison of a lifecycle rule with an immediate client-side
+/// transition of the same objects, so the cheaper mechanism can be picked
+#[derive(Clone, Debug)]
+pub struct MechanismComparison {
+    /// Objects not already in the target class
+    pub objects: usize,
+    pub bytes: i64,
+    pub client: MechanismCost,
+    pub lifecycle: MechanismCost,
+    /// Objects the rule leaves in place for being under [`LIFECYCLE_MIN_SIZE`]
+    pub too_small: usize,
+    /// Objects a client-side transition copies in parts
+    pub multipart: usize,
+    /// Objects without a modification time, assumed new
+    pub undated: usize,
+    /// Days until the rule has moved every object it will move
+    pub lifecycle_days: i64,
+    /// Region the prices are for
+    pub price_region: &'static str,
+}
+
+impl MechanismComparison {
+    /// Price both mechanisms for `objects`. `None` when there are no prices
+    /// for the region or the target class.
+    pub fn new(
+        rule: &LifecycleRule,
+        objects: &[&ObjectInfo],
+        region: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Option<Self> {
+        let (target_price, price_region) =
+            pricing::monthly_price_per_gb(region, &rule.storage_class)?;
+        let fallback_price = pricing::monthly_price_per_gb(region, &StorageClassTier::Standard)
+            .map_or(target_price, |(price, _)| price);
+        let copy_fee = pricing::copy_price_per_1000(&rule.storage_class) / 1000.0;
+        let transition_fee = pricing::transition_price_per_1000(&rule.storage_class) / 1000.0;
+        let months = |days: i64| days as f64 / 30.0;
+
+        let mut comparison = Self {
+            objects: 0,
+            bytes: 0,
+            client: MechanismCost::default(),
+            lifecycle: MechanismCost::default(),
+            too_small: 0,
+            multipart: 0,
+            undated: 0,
+            lifecycle_days: 0,
+            price_region,
+        };
+        for obj in objects {
+            if obj.storage_class == rule.storage_class {
+                continue;
+            }
+            comparison.objects += 1;
+            comparison.bytes += obj.size;
+            let gb = obj.size as f64 / (1024.0 * 1024.0 * 1024.0);
+            let source_price = pricing::monthly_price_per_gb(region, &obj.storage_class)
+                .map_or(fallback_price, |(price, _)| price);
+            let age = match obj
+                .last_modified
+                .as_deref()
+                .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+            {
+                Some(at) => (now - at.with_timezone(&Utc)).num_days().max(0),
+                None => {
+                    comparison.undated += 1;
+                    0
+                }
+            };
+            let minimum = i64::from(obj.storage_class.min_storage_days());
+            let early_deletion =
+                |at_age: i64| months((minimum - at_age).max(0)) * gb * source_price;
+
+            // Client-side: one CopyObject now, the target's price from today
+            comparison.client.moved += 1;
+            comparison.client.request_fees += copy_fee;
+            comparison.client.early_deletion += early_deletion(age);
+            comparison.client.storage += months(COMPARISON_DAYS) * gb * target_price;
+            if obj.size > MULTIPART_COPY_THRESHOLD {
+                comparison.multipart += 1;
+            }
+
+            // Lifecycle: the source price until the object is old enough
+            if obj.size < LIFECYCLE_MIN_SIZE {
+                comparison.too_small += 1;
+                comparison.lifecycle.storage += months(COMPARISON_DAYS) * gb * source_price;
+                continue;
+            }
+            let wait =
+                ((i64::from(rule.days) - age).max(0) + LIFECYCLE_LAG_DAYS).min(COMPARISON_DAYS);
+            comparison.lifecycle.storage += months(wait) * gb * source_price
+                + months(COMPARISON_DAYS - wait) * gb * target_price;
+            if wait < COMPARISON_DAYS {
+                comparison.lifecycle.moved += 1;
+                comparison.lifecycle.request_fees += transition_fee;
+                comparison.lifecycle.early_deletion += early_deletion(age + wait);
+                comparison.lifecycle_days = comparison.lifecycle_days.max(wait);
+            }
+        }
+        Some(comparison)
+    }
+
+    /// Whether an immediate client-side transition costs less than the rule
+    pub fn client_is_cheaper(&self) -> bool {
+        self.client.total() < self.lifecycle.total()
+    }
+}
+
+fn hcl_escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace("${", "$${")
+        .replace("%{", "%%{")
+}
+
+fn yaml_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
@@ -0,0 +1,441 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use futures::stream::{self, StreamExt};
+use tokio::sync::{mpsc, watch};
+use tokio::task::JoinHandle;
+
+use crate::aws::{S3Service, start_after_for};
+use crate::models::ObjectInfo;
+
+/// Number of key-range partitions listed concurrently
+const LISTING_CONCURRENCY: usize = 8;
+/// Page size used by each partition stream (ListObjectsV2 maximum)
+const PARTITION_PAGE_SIZE: i32 = 1000;
+
+pub enum ListingEvent {
+    Planned(usize),
+    Page(Vec<ObjectInfo>),
+    /// Subfolders (CommonPrefixes) of a folder listing
+    Folders(Vec<String>),
+    PartitionDone,
+    Failed(anyhow::Error),
+    Finished,
+}
+
+/// A contiguous slice of the bucket keyspace: keys in `(start_after, end]`,
+/// optionally clipped to keys at or after `floor`
+#[derive(Clone, Debug)]
+struct KeyRange {
+    start_after: Option<String>,
+    end: Option<String>,
+    floor: Option<String>,
+}
+
+/// Handle to a background listing of one bucket.
+/// Pages are only fetched while fewer objects than the current demand have been loaded.
+/// Dropping the handle stops the listing.
+pub struct ListingHandle {
+    pub bucket: String,
+    pub partitions: usize,
+    pub partitions_done: usize,
+    /// A partition failed, so the listing will not cover the whole bucket
+    pub failed: bool,
+    demand: watch::Sender<usize>,
+    loaded: Arc<AtomicUsize>,
+    events: mpsc::UnboundedReceiver<ListingEvent>,
+    /// Event received by `ready` and not yet taken by `try_next`
+    pending: Option<ListingEvent>,
+    task: JoinHandle<()>,
+}
+
+impl ListingHandle {
+    /// Raise the number of objects the listing should load before pausing
+    pub fn request(&self, count: usize) {
+        self.demand.send_if_modified(|current| {
+            if count > *current {
+                *current = count;
+                true
+            } else {
+                false
+            }
+        });
+    }
+
+    pub fn demand(&self) -> usize {
+        *self.demand.borrow()
+    }
+
+    /// Keep listing until the whole bucket has been loaded
+    pub fn load_all(&self) {
+        self.demand.send_replace(usize::MAX);
+    }
+
+    /// Stop fetching new pages beyond what has already been loaded
+    pub fn pause(&self) {
+        self.demand.send_replace(self.loaded.load(Ordering::SeqCst));
+    }
+
+    pub fn is_loading_all(&self) -> bool {
+        self.demand() == usize::MAX
+    }
+
+    /// Wait until `try_next` has an event to return
+    pub async fn ready(&mut self) {
+        if self.pending.is_some() {
+            return;
+        }
+        match self.events.recv().await {
+            Some(event) => self.pending = Some(event),
+            // The listing task is gone, so nothing will ever arrive
+            None => std::future::pending().await,
+        }
+    }
+
+    pub fn try_next(&mut self) -> Option<ListingEvent> {
+        let event = self
+            .pending
+            .take()
+            .or_else(|| self.events.try_recv().ok())?;
+        match event {
+            ListingEvent::Planned(count) => self.partitions = count,
+            ListingEvent::PartitionDone => self.partitions_done += 1,
+            ListingEvent::Failed(_) => self.failed = true,
+            _ => {}
+        }
+        Some(event)
+    }
+}
+
+impl Drop for ListingHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Start listing `bucket` in the background, partitioned by key range so that
+/// several ListObjectsV2 streams run concurrently. With `prefix`, only keys
+/// under it are listed; with `start_at`, keys sorting before it are never
+/// requested.
+pub fn spawn_parallel_listing(
+    s3: S3Service,
+    bucket: String,
+    prefix: Option<String>,
+    start_at: Option<String>,
+    initial_demand: usize,
+) -> ListingHandle {
+    let (demand_tx, demand_rx) = watch::channel(initial_demand);
+    let (event_tx, event_rx) = mpsc::unbounded_channel();
+    let loaded = Arc::new(AtomicUsize::new(0));
+
+    let task_bucket = bucket.clone();
+    let task_loaded = loaded.clone();
+    let task = tokio::spawn(async move {
+        let ranges =
+            plan_partitions(&s3, &task_bucket, prefix.as_deref(), start_at.as_deref()).await;
+        let _ = event_tx.send(ListingEvent::Planned(ranges.len()));
+        let loaded = task_loaded;
+
+        stream::iter(ranges)
+            .map(|range| {
+                list_partition(
+                    &s3,
+                    &task_bucket,
+                    prefix.as_deref(),
+                    range,
+                    demand_rx.clone(),
+                    loaded.clone(),
+                    event_tx.clone(),
+                )
+            })
+            .buffer_unordered(LISTING_CONCURRENCY)
+            .collect::<Vec<_>>()
+            .await;
+
+        let _ = event_tx.send(ListingEvent::Finished);
+    });
+
+    ListingHandle {
+        bucket,
+        partitions: 0,
+        partitions_done: 0,
+        failed: false,
+        demand: demand_tx,
+        loaded,
+        events: event_rx,
+        pending: None,
+        task,
+    }
+}
+
+/// Split the keyspace using delimiter-discovered prefixes when the bucket has a
+/// manageable number of them, otherwise by leading character. Under a prefix
+/// the split is by the character after it.
+async fn plan_partitions(
+    s3: &S3Service,
+    bucket: &str,
+    prefix: Option<&str>,
+    start_at: Option<&str>,
+) -> Vec<KeyRange> {
+    let top_level = match prefix {
+        Some(_) => None,
+        None => s3.list_top_level_prefixes(bucket).await.ok().flatten(),
+    };
+    let boundaries = match top_level {
+        Some(prefixes) if prefixes.len() >= 2 => prefixes,
+        _ => ('0'..='9')
+            .chain('A'..='Z')
+            .chain('a'..='z')
+            .map(|c| format!("{}{c}", prefix.unwrap_or_default()))
+            .collect(),
+    };
+
+    let mut ranges = Vec::with_capacity(boundaries.len() + 1);
+    let mut start_after = None;
+    for boundary in boundaries {
+        ranges.push(KeyRange {
+            start_after: start_after.clone(),
+            end: Some(boundary.clone()),
+            floor: None,
+        });
+        start_after = Some(boundary);
+    }
+    ranges.push(KeyRange {
+        start_after,
+        end: None,
+        floor: None,
+    });
+
+    let Some(start) = start_at else {
+        return ranges;
+    };
+
+    // StartAfter is exclusive, so begin just before the start key and filter client-side
+    let before_start = start_after_for(start);
+    ranges
+        .into_iter()
+        .filter(|range| range.end.as_deref().is_none_or(|end| end >= start))
+        .map(|mut range| {
+            if range
+                .start_after
+                .as_deref()
+                .is_none_or(|after| after < start)
+            {
+                if let Some(before) = &before_start
+                    && range
+                        .start_after
+                        .as_deref()
+                        .is_none_or(|after| after < before.as_str())
+                {
+                    range.start_after = Some(before.clone());
+                }
+                range.floor = Some(start.to_string());
+            }
+            range
+        })
+        .collect()
+}
+
+async fn list_partition(
+    s3: &S3Service,
+    bucket: &str,
+    prefix: Option<&str>,
+    range: KeyRange,
+    mut demand: watch::Receiver<usize>,
+    loaded: Arc<AtomicUsize>,
+    events: mpsc::UnboundedSender<ListingEvent>,
+) {
+    let mut token: Option<String> = None;
+    loop {
+        // Pause until the UI asks for more objects
+        if demand
+            .wait_for(|wanted| loaded.load(Ordering::SeqCst) < *wanted)
+            .await
+            .is_err()
+        {
+            return;
+        }
+
+        let page = s3
+            .list_objects_paginated(
+                bucket,
+                prefix,
+                range.start_after.as_deref(),
+                token.take(),
+                PARTITION_PAGE_SIZE,
+            )
+            .await;
+
+        match page {
+            Ok((mut objects, next_token)) => {
+                if let Some(floor) = &range.floor {
+                    objects.retain(|o| o.key.as_str() >= floor.as_str());
+                }
+                let mut reached_end = next_token.is_none();
+                if let Some(end) = &range.end
+                    && let Some(pos) = objects.iter().position(|o| o.key.as_str() > end.as_str())
+                {
+                    objects.truncate(pos);
+                    reached_end = true;
+                }
+
+                loaded.fetch_add(objects.len(), Ordering::SeqCst);
+                if !objects.is_empty() && events.send(ListingEvent::Page(objects)).is_err() {
+                    return;
+                }
+                if reached_end {
+                    let _ = events.send(ListingEvent::PartitionDone);
+                    return;
+                }
+                token = next_token;
+            }
+            Err(err) => {
+                let _ = events.send(ListingEvent::Failed(err));
+                let _ = events.send(ListingEvent::PartitionDone);
+                return;
+            }
+        }
+    }
+}
+
+/// List one folder of `bucket` in the background with the `/` delimiter:
+/// subfolders arrive as `Folders`, the objects directly in it as `Page`s.
+/// Like the parallel listing, pages are only fetched while demand allows.
+pub fn spawn_folder_listing(
+    s3: S3Service,
+    bucket: String,
+    prefix: Option<String>,
+    initial_demand: usize,
+) -> ListingHandle {
+    let (demand_tx, mut demand_rx) = watch::channel(initial_demand);
+    let (event_tx, event_rx) = mpsc::unbounded_channel();
+    let loaded = Arc::new(AtomicUsize::new(0));
+
+    let task_bucket = bucket.clone();
+    let task_loaded = loaded.clone();
+    let task = tokio::spawn(async move {
+        let _ = event_tx.send(ListingEvent::Planned(1));
+        let mut token = None;
+        loop {
+            if demand_rx
+                .wait_for(|wanted| task_loaded.load(Ordering::SeqCst) < *wanted)
+                .await
+                .is_err()
+            {
+                return;
+            }
+            let page = s3
+                .list_folder_page(
+                    &task_bucket,
+                    prefix.as_deref(),
+                    token.take(),
+                    PARTITION_PAGE_SIZE,
+                )
+                .await;
+            match page {
+                Ok(page) => {
+                    // Folders count toward demand too, so a level with
+                    // only subfolders keeps paging
+                    task_loaded
+                        .fetch_add(page.objects.len() + page.folders.len(), Ordering::SeqCst);
+                    if !page.folders.is_empty() {
+                        let _ = event_tx.send(ListingEvent::Folders(page.folders));
+                    }
+                    if !page.objects.is_empty() {
+                        let _ = event_tx.send(ListingEvent::Page(page.objects));
+                    }
+                    match page.next_token {
+                        Some(next) => token = Some(next),
+                        None => break,
+                    }
+                }
+                Err(err) => {
+                    let _ = event_tx.send(ListingEvent::Failed(err));
+                    break;
+                }
+            }
+        }
+        let _ = event_tx.send(ListingEvent::PartitionDone);
+        let _ = event_tx.send(ListingEvent::Finished);
+    });
+
+    ListingHandle {
+        bucket,
+        partitions: 0,
+        partitions_done: 0,
+        failed: false,
+        demand: demand_tx,
+        loaded,
+        events: event_rx,
+        pending: None,
+        task,
+    }
+}
+
+/// List every version and delete marker under `prefix` of `bucket` in the
+/// background with ListObjectVersions. Its pages follow one marker, so there
+/// is a single stream rather than partitions.
+pub fn spawn_version_listing(
+    s3: S3Service,
+    bucket: String,
+    prefix: Option<String>,
+    initial_demand: usize,
+) -> ListingHandle {
+    let (demand_tx, mut demand_rx) = watch::channel(initial_demand);
+    let (event_tx, event_rx) = mpsc::unbounded_channel();
+    let loaded = Arc::new(AtomicUsize::new(0));
+
+    let task_bucket = bucket.clone();
+    let task_loaded = loaded.clone();
+    let task = tokio::spawn(async move {
+        let _ = event_tx.send(ListingEvent::Planned(1));
+        let mut marker = None;
+        loop {
+            if demand_rx
+                .wait_for(|wanted| task_loaded.load(Ordering::SeqCst) < *wanted)
+                .await
+                .is_err()
+            {
+                return;
+            }
+            let page = s3
+                .list_versions_page(
+                    &task_bucket,
+                    prefix.as_deref(),
+                    marker.take(),
+                    PARTITION_PAGE_SIZE,
+                )
+                .await;
+            match page {
+                Ok(page) => {
+                    task_loaded.fetch_add(page.objects.len(), Ordering::SeqCst);
+                    if !page.objects.is_empty() {
+                        let _ = event_tx.send(ListingEvent::Page(page.objects));
+                    }
+                    match page.next {
+                        Some(next) => marker = Some(next),
+                        None => break,
+                    }
+                }
+                Err(err) => {
+                    let _ = event_tx.send(ListingEvent::Failed(err));
+                    break;
+                }
+            }
+        }
+        let _ = event_tx.send(ListingEvent::PartitionDone);
+        let _ = event_tx.send(ListingEvent::Finished);
+    });
+
+    ListingHandle {
+        bucket,
+        partitions: 0,
+        partitions_done: 0,
+        failed: false,
+        demand: demand_tx,
+        loaded,
+        events: event_rx,
+        pending: None,
+        task,
+    }
+}
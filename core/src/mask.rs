@@ -0,0 +1,339 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::models::{ObjectInfo, StorageClassTier};
+use crate::recency::LastReads;
+use crate::script;
+use crate::waves::format_bytes;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MaskKind {
+    Prefix,
+    Suffix,
+    Contains,
+    Regex,
+    /// Shell-style pattern over the whole key: `*` and `?` stay within one
+    /// path segment, `**` spans any number of them
+    Glob,
+    /// Rhai expression over the whole object, see `script::Predicate`
+    Script,
+}
+
+impl fmt::Display for MaskKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            MaskKind::Prefix => "Prefix",
+            MaskKind::Suffix => "Suffix",
+            MaskKind::Contains => "Contains",
+            MaskKind::Regex => "Regex",
+            MaskKind::Glob => "Glob",
+            MaskKind::Script => "Script",
+        };
+        f.write_str(label)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ObjectMask {
+    pub name: String,
+    pub pattern: String,
+    pub kind: MaskKind,
+    pub case_sensitive: bool,
+    pub storage_class_filter: Option<StorageClassTier>,
+    /// Case-insensitive substring of the owner name or ID
+    #[serde(default)]
+    pub owner_filter: Option<String>,
+    /// Only objects written and not read for at least this many days,
+    /// going by the bucket's last-read lookup
+    #[serde(default)]
+    pub unread_days: Option<u32>,
+    /// Only objects of at least this many bytes
+    #[serde(default)]
+    pub min_size: Option<u64>,
+    /// Only objects of at most this many bytes
+    #[serde(default)]
+    pub max_size: Option<u64>,
+    /// Only objects last modified at least this many days ago
+    #[serde(default)]
+    pub older_than_days: Option<u32>,
+    /// Only objects last modified fewer than this many days ago
+    #[serde(default)]
+    pub newer_than_days: Option<u32>,
+}
+
+impl ObjectMask {
+    pub fn matches(&self, key: &str) -> bool {
+        self.matcher().matches(key)
+    }
+
+    /// Whether the mask selects `obj`: its key, storage class, owner, size,
+    /// age and, for script masks, the script. Scripts that fail to run
+    /// select nothing. To test many objects, build one `matcher` instead.
+    pub fn matches_object(&self, obj: &ObjectInfo) -> bool {
+        self.matcher().matches_object(obj)
+    }
+
+    /// The mask with its glob or regex compiled, for testing a whole listing
+    pub fn matcher(&self) -> MaskMatcher<'_> {
+        let source = match self.kind {
+            MaskKind::Regex => Some(Cow::Borrowed(self.pattern.as_str())),
+            MaskKind::Glob => Some(Cow::Owned(glob_regex(&self.pattern))),
+            _ => None,
+        };
+        // An invalid regex selects nothing
+        let regex = source.map(|source| {
+            RegexBuilder::new(&source)
+                .case_insensitive(!self.case_sensitive)
+                .build()
+                .ok()
+        });
+        MaskMatcher { mask: self, regex }
+    }
+
+    /// Objects whose owner was not listed never match an owner filter
+    pub fn matches_owner(&self, owner: Option<&str>) -> bool {
+        match &self.owner_filter {
+            Some(filter) => {
+                owner.is_some_and(|owner| owner.to_lowercase().contains(&filter.to_lowercase()))
+            }
+            None => true,
+        }
+    }
+
+    /// Whether `size` lies within the mask's size bounds, inclusive
+    pub fn matches_size(&self, size: i64) -> bool {
+        let size = u64::try_from(size).unwrap_or(0);
+        self.min_size.is_none_or(|min| size >= min) && self.max_size.is_none_or(|max| size <= max)
+    }
+
+    /// Objects whose last-modified time was not listed never match an age
+    /// filter
+    pub fn matches_age(&self, last_modified: Option<&str>) -> bool {
+        if self.older_than_days.is_none() && self.newer_than_days.is_none() {
+            return true;
+        }
+        let Some(modified) =
+            last_modified.and_then(|at| chrono::DateTime::parse_from_rfc3339(at).ok())
+        else {
+            return false;
+        };
+        let now = chrono::Utc::now();
+        let before = |days: u32| now - chrono::Duration::days(i64::from(days));
+        self.older_than_days
+            .is_none_or(|days| modified <= before(days))
+            && self
+                .newer_than_days
+                .is_none_or(|days| modified > before(days))
+    }
+
+    /// Whether `obj` has gone unread for `unread_days`. Nothing matches
+    /// without reads that reach back that far, or for objects whose write
+    /// time was not listed.
+    pub fn matches_unread(&self, obj: &ObjectInfo, reads: Option<&LastReads>) -> bool {
+        let Some(days) = self.unread_days else {
+            return true;
+        };
+        let Some(reads) = reads.filter(|reads| reads.covers_days(days)) else {
+            return false;
+        };
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(i64::from(days));
+        let written_before = obj
+            .last_modified
+            .as_deref()
+            .and_then(|at| chrono::DateTime::parse_from_rfc3339(at).ok())
+            .is_some_and(|at| at <= cutoff);
+        written_before && reads.of_key(&obj.key).is_none_or(|at| at < cutoff)
+    }
+
+    pub fn summary(&self) -> String {
+        let pattern_display = if self.kind == MaskKind::Script {
+            script::first_line(&self.pattern).to_string()
+        } else if self.case_sensitive {
+            self.pattern.clone()
+        } else {
+            format!("{} (insensitive)", self.pattern)
+        };
+
+        let storage_filter = if let Some(ref storage) = self.storage_class_filter {
+            format!(" + {}", storage.label())
+        } else {
+            String::new()
+        };
+
+        let owner_filter = if let Some(ref owner) = self.owner_filter {
+            format!(" + owner ~ {owner}")
+        } else {
+            String::new()
+        };
+
+        let unread_filter = if let Some(days) = self.unread_days {
+            format!(" + unread {days}d")
+        } else {
+            String::new()
+        };
+
+        let size_filter = match (self.min_size, self.max_size) {
+            (Some(min), Some(max)) => {
+                format!(" + size {}–{}", format_bytes(min), format_bytes(max))
+            }
+            (Some(min), None) => format!(" + size ≥ {}", format_bytes(min)),
+            (None, Some(max)) => format!(" + size ≤ {}", format_bytes(max)),
+            (None, None) => String::new(),
+        };
+
+        let age_filter = match (self.older_than_days, self.newer_than_days) {
+            (Some(older), Some(newer)) => format!(" + modified {older}–{newer}d ago"),
+            (Some(older), None) => format!(" + modified ≥ {older}d ago"),
+            (None, Some(newer)) => format!(" + modified < {newer}d ago"),
+            (None, None) => String::new(),
+        };
+
+        format!(
+            "{} ({:?}: {}{}{}{}{}{})",
+            self.name,
+            self.kind,
+            pattern_display,
+            storage_filter,
+            owner_filter,
+            unread_filter,
+            size_filter,
+            age_filter
+        )
+    }
+}
+
+/// An `ObjectMask` whose pattern is compiled, from `ObjectMask::matcher`
+pub struct MaskMatcher<'a> {
+    mask: &'a ObjectMask,
+    /// For Regex and Glob masks; `Some(None)` when the pattern does not compile
+    regex: Option<Option<Regex>>,
+}
+
+impl MaskMatcher<'_> {
+    pub fn matches(&self, key: &str) -> bool {
+        if let Some(regex) = &self.regex {
+            return regex.as_ref().is_some_and(|regex| regex.is_match(key));
+        }
+        match self.mask.kind {
+            MaskKind::Prefix => normalized_cmp(self.mask, key, Comparison::Prefix),
+            MaskKind::Suffix => normalized_cmp(self.mask, key, Comparison::Suffix),
+            MaskKind::Contains => normalized_cmp(self.mask, key, Comparison::Contains),
+            // Compiled above
+            MaskKind::Regex | MaskKind::Glob => false,
+            // Scripts see more than the key; see `matches_object`
+            MaskKind::Script => true,
+        }
+    }
+
+    /// See `ObjectMask::matches_object`
+    pub fn matches_object(&self, obj: &ObjectInfo) -> bool {
+        let mask = self.mask;
+        let storage_matches = mask
+            .storage_class_filter
+            .as_ref()
+            .is_none_or(|filter| &obj.storage_class == filter);
+        if !(self.matches(&obj.key)
+            && storage_matches
+            && mask.matches_owner(obj.owner.as_deref())
+            && mask.matches_size(obj.size)
+            && mask.matches_age(obj.last_modified.as_deref()))
+        {
+            return false;
+        }
+        match mask.kind {
+            MaskKind::Script => script::predicate(&mask.pattern)
+                .and_then(|predicate| predicate.eval(obj, chrono::Utc::now()))
+                .unwrap_or(false),
+            _ => true,
+        }
+    }
+}
+
+/// Anchored regex for a glob such as `logs/**/2023-*.gz`. `**/` also
+/// matches no directory at all, so that example selects `logs/2023-01.gz`.
+fn glob_regex(glob: &str) -> String {
+    let mut regex = String::from("^");
+    let mut chars = glob.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            _ => regex.push_str(&regex::escape(ch.encode_utf8(&mut [0; 4]))),
+        }
+    }
+    regex.push('$');
+    regex
+}
+
+enum Comparison {
+    Prefix,
+    Suffix,
+    Contains,
+}
+
+fn normalized<'a>(mask: &ObjectMask, input: &'a str) -> Cow<'a, str> {
+    if mask.case_sensitive {
+        Cow::Borrowed(input)
+    } else {
+        Cow::Owned(input.to_lowercase())
+    }
+}
+
+fn normalized_cmp(mask: &ObjectMask, key: &str, comparison: Comparison) -> bool {
+    let key = normalized(mask, key);
+    let pattern = normalized(mask, &mask.pattern);
+    match comparison {
+        Comparison::Prefix => key.starts_with(pattern.as_ref()),
+        Comparison::Suffix => key.ends_with(pattern.as_ref()),
+        Comparison::Contains => key.contains(pattern.as_ref()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(glob: &str, key: &str) -> bool {
+        regex::Regex::new(&glob_regex(glob)).unwrap().is_match(key)
+    }
+
+    #[test]
+    fn star_stays_within_one_level() {
+        assert!(matches("logs/*.gz", "logs/a.gz"));
+        assert!(!matches("logs/*.gz", "logs/2023/a.gz"));
+        assert!(!matches("logs/*.gz", "logs/a.gz.bak"));
+    }
+
+    #[test]
+    fn double_star_slash_matches_any_depth_or_none() {
+        assert!(matches("logs/**/2023-*.gz", "logs/2023-01.gz"));
+        assert!(matches("logs/**/2023-*.gz", "logs/a/b/2023-01.gz"));
+        assert!(!matches("logs/**/2023-*.gz", "raw/logs/2023-01.gz"));
+        assert!(matches("logs/**", "logs/a/b"));
+    }
+
+    #[test]
+    fn question_mark_is_one_char_other_than_slash() {
+        assert!(matches("v?/a", "v1/a"));
+        assert!(!matches("v?/a", "v10/a"));
+        assert!(!matches("a?b", "a/b"));
+    }
+
+    #[test]
+    fn regex_metacharacters_are_literal() {
+        assert!(matches("a+b (1).txt", "a+b (1).txt"));
+        assert!(!matches("a.txt", "abtxt"));
+    }
+}
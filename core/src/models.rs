@@ -0,0 +1,929 @@
+use serde::{Deserialize, Serialize};
+
+use aws_sdk_s3::types::{ObjectStorageClass, StorageClass, Tier};
+
+use crate::tickets::TicketLink;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BucketInfo {
+    pub name: String,
+    pub region: Option<String>,
+    pub creation_date: Option<String>,
+}
+
+/// Default encryption applied to objects written to a new bucket
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BucketEncryption {
+    /// SSE-S3 with S3-managed keys
+    S3Managed,
+    /// SSE-KMS with the account's `aws/s3` key
+    Kms,
+}
+
+impl BucketEncryption {
+    pub fn next(self) -> Self {
+        match self {
+            BucketEncryption::S3Managed => BucketEncryption::Kms,
+            BucketEncryption::Kms => BucketEncryption::S3Managed,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            BucketEncryption::S3Managed => "SSE-S3",
+            BucketEncryption::Kms => "SSE-KMS (aws/s3)",
+        }
+    }
+}
+
+/// Settings for a bucket created from the TUI
+#[derive(Clone, Debug)]
+pub struct NewBucket {
+    pub name: String,
+    pub region: String,
+    pub versioning: bool,
+    pub encryption: BucketEncryption,
+    pub block_public_access: bool,
+}
+
+impl NewBucket {
+    pub fn new(region: &str) -> Self {
+        Self {
+            name: String::new(),
+            region: region.to_string(),
+            versioning: false,
+            encryption: BucketEncryption::S3Managed,
+            block_public_access: true,
+        }
+    }
+
+    /// Why the name is not a valid general purpose bucket name, if it is not
+    pub fn name_problem(&self) -> Option<&'static str> {
+        let name = self.name.as_str();
+        if !(3..=63).contains(&name.len()) {
+            return Some("must be 3 to 63 characters long");
+        }
+        if !name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '.' || c == '-')
+        {
+            return Some("may only contain lowercase letters, digits, dots and hyphens");
+        }
+        let edge_ok = |c: Option<char>| c.is_some_and(|c| c.is_ascii_alphanumeric());
+        if !edge_ok(name.chars().next()) || !edge_ok(name.chars().last()) {
+            return Some("must start and end with a letter or digit");
+        }
+        if name.contains("..") {
+            return Some("must not contain two adjacent dots");
+        }
+        if name.split('.').count() == 4 && name.split('.').all(|part| part.parse::<u8>().is_ok()) {
+            return Some("must not be formatted as an IP address");
+        }
+        None
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TrackedRestoreRequest {
+    pub bucket: String,
+    pub key: String,
+    pub requested_at: String, // ISO 8601 timestamp
+    pub days: i32,
+    pub current_status: RestoreState,
+    #[serde(default)]
+    pub storage_class: Option<StorageClassTier>,
+    #[serde(default)]
+    pub tier: RestoreTier,
+    /// When the background check first saw the restored copy
+    #[serde(default)]
+    pub ready_at: Option<String>,
+    /// When S3 removes the restored copy again, once it is ready
+    #[serde(default)]
+    pub expires_at: Option<String>,
+    /// Readiness is not announced on the restore webhook or in Slack, for
+    /// restores requested with a quiet preset
+    #[serde(default)]
+    pub quiet: bool,
+}
+
+impl TrackedRestoreRequest {
+    /// Latest time the restore should finish, from the published typical
+    /// retrieval times for the storage class and tier
+    pub fn expected_ready_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        let requested = chrono::DateTime::parse_from_rfc3339(&self.requested_at).ok()?;
+        let deep_archive = matches!(
+            self.storage_class,
+            Some(StorageClassTier::GlacierDeepArchive)
+        );
+        let minutes = match (deep_archive, self.tier) {
+            (false, RestoreTier::Expedited) => 5,
+            (false, RestoreTier::Standard) => 5 * 60,
+            (false, RestoreTier::Bulk) => 12 * 60,
+            // Deep Archive has no expedited tier; S3 rejects it, so assume Standard
+            (true, RestoreTier::Expedited | RestoreTier::Standard) => 12 * 60,
+            (true, RestoreTier::Bulk) => 48 * 60,
+        };
+        Some(requested.with_timezone(&chrono::Utc) + chrono::Duration::minutes(minutes))
+    }
+
+    /// Share of the expected retrieval time that has passed, from 0 to 100.
+    /// S3 reports no progress of its own, so this is only an estimate.
+    pub fn progress_percent(&self, now: chrono::DateTime<chrono::Utc>) -> Option<u8> {
+        let requested = chrono::DateTime::parse_from_rfc3339(&self.requested_at).ok()?;
+        let total = (self.expected_ready_at()? - requested.with_timezone(&chrono::Utc))
+            .num_seconds()
+            .max(1);
+        let elapsed = (now - requested.with_timezone(&chrono::Utc))
+            .num_seconds()
+            .clamp(0, total);
+        Some((elapsed * 100 / total) as u8)
+    }
+}
+
+/// Glacier retrieval tier used for a restore request
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum RestoreTier {
+    Expedited,
+    #[default]
+    Standard,
+    Bulk,
+}
+
+impl RestoreTier {
+    pub fn label(&self) -> &'static str {
+        match self {
+            RestoreTier::Expedited => "Expedited",
+            RestoreTier::Standard => "Standard",
+            RestoreTier::Bulk => "Bulk",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            RestoreTier::Standard => RestoreTier::Bulk,
+            RestoreTier::Bulk => RestoreTier::Expedited,
+            RestoreTier::Expedited => RestoreTier::Standard,
+        }
+    }
+
+    pub fn to_sdk(&self) -> Tier {
+        match self {
+            RestoreTier::Expedited => Tier::Expedited,
+            RestoreTier::Standard => Tier::Standard,
+            RestoreTier::Bulk => Tier::Bulk,
+        }
+    }
+}
+
+/// A restore's journal target: the duration, plus the tier when it is not
+/// the default
+pub fn restore_label(days: i32, tier: RestoreTier) -> String {
+    if tier == RestoreTier::default() {
+        format!("{days} days")
+    } else {
+        format!("{days} days, {}", tier.label())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum JobKind {
+    Transition,
+    Restore,
+    Copy,
+    Rollback,
+    /// Copy, verify, then delete the source
+    Move,
+    /// Copy in place under a new KMS key; the job's target is the key
+    Reencrypt,
+    /// Small objects written into one archive; the job's target is its key
+    Bundle,
+    /// Members read back out of bundle archives; the job's target is where
+    /// they were written
+    Retrieve,
+    /// Noncurrent versions deleted; the journal notes each version ID
+    DeleteVersions,
+    /// Noncurrent versions moved to another class, one journal entry per key
+    TransitionVersions,
+    /// Restores requested for archived noncurrent versions
+    RestoreVersions,
+}
+
+impl JobKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            JobKind::Transition => "Transition",
+            JobKind::Restore => "Restore",
+            JobKind::Copy => "Copy",
+            JobKind::Rollback => "Rollback",
+            JobKind::Move => "Move",
+            JobKind::Reencrypt => "Re-encrypt",
+            JobKind::Bundle => "Bundle",
+            JobKind::Retrieve => "Retrieve",
+            JobKind::DeleteVersions => "Delete versions",
+            JobKind::TransitionVersions => "Move versions",
+            JobKind::RestoreVersions => "Restore versions",
+        }
+    }
+}
+
+/// Object Lock retention mode. Governance retention can be lifted by users
+/// with `s3:BypassGovernanceRetention`; compliance retention cannot be
+/// shortened or removed by anyone, including the root user.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum RetentionMode {
+    Governance,
+    Compliance,
+}
+
+impl RetentionMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            RetentionMode::Governance => "Governance",
+            RetentionMode::Compliance => "Compliance",
+        }
+    }
+}
+
+/// A retention period to set on each object
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Retention {
+    pub mode: RetentionMode,
+    pub retain_until: chrono::DateTime<chrono::Utc>,
+}
+
+/// Retention and legal hold applied to objects as a batch archives them, on
+/// buckets with Object Lock enabled
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ObjectLockPlan {
+    #[serde(default)]
+    pub retention: Option<Retention>,
+    #[serde(default)]
+    pub legal_hold: bool,
+}
+
+impl ObjectLockPlan {
+    pub fn is_empty(&self) -> bool {
+        self.retention.is_none() && !self.legal_hold
+    }
+
+    /// Next retention mode: off, governance, compliance, off. A newly set
+    /// retention keeps `retain_until` from before or defaults to a year.
+    pub fn cycle_mode(&mut self) {
+        self.retention = match self.retention.take() {
+            None => Some(Retention {
+                mode: RetentionMode::Governance,
+                retain_until: chrono::Utc::now() + chrono::TimeDelta::days(365),
+            }),
+            Some(Retention {
+                mode: RetentionMode::Governance,
+                retain_until,
+            }) => Some(Retention {
+                mode: RetentionMode::Compliance,
+                retain_until,
+            }),
+            Some(Retention {
+                mode: RetentionMode::Compliance,
+                ..
+            }) => None,
+        };
+    }
+
+    /// "Governance until 2027-06-30, legal hold", for the journal and dialogs
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(retention) = &self.retention {
+            parts.push(format!(
+                "{} until {}",
+                retention.mode.label(),
+                retention.retain_until.format("%Y-%m-%d")
+            ));
+        }
+        if self.legal_hold {
+            parts.push("legal hold".to_string());
+        }
+        if parts.is_empty() {
+            "no Object Lock".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+}
+
+/// Parse a retain-until date: `YYYY-MM-DD` (end of that day, UTC) or a period
+/// from now such as `+90d` or `+7y`. Only future dates are accepted.
+pub fn parse_retain_until(text: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    let text = text.trim();
+    let now = chrono::Utc::now();
+    let until = if let Some(period) = text.strip_prefix('+') {
+        let unit = period.chars().last()?;
+        let number: i64 = period[..period.len() - unit.len_utf8()]
+            .trim()
+            .parse()
+            .ok()?;
+        let days = match unit {
+            'd' => number,
+            'y' => number.checked_mul(365)?,
+            _ => return None,
+        };
+        now + chrono::TimeDelta::try_days(days)?
+    } else {
+        chrono::NaiveDate::parse_from_str(text, "%Y-%m-%d")
+            .ok()?
+            .and_hms_opt(23, 59, 59)?
+            .and_utc()
+    };
+    (until > now).then_some(until)
+}
+
+/// How a copy treats keys that already exist in the destination
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CopyMode {
+    #[default]
+    Overwrite,
+    /// Skip keys whose destination has the same size and ETag or checksum
+    SkipIdentical,
+    /// Copy only when the source was modified after the destination
+    OverwriteIfNewer,
+}
+
+impl CopyMode {
+    pub fn next(self) -> Self {
+        match self {
+            CopyMode::Overwrite => CopyMode::SkipIdentical,
+            CopyMode::SkipIdentical => CopyMode::OverwriteIfNewer,
+            CopyMode::OverwriteIfNewer => CopyMode::Overwrite,
+        }
+    }
+
+    /// Catalog key of the mode's description
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            CopyMode::Overwrite => "copy_mode.overwrite",
+            CopyMode::SkipIdentical => "copy_mode.skip_identical",
+            CopyMode::OverwriteIfNewer => "copy_mode.if_newer",
+        }
+    }
+
+    /// Whether copying `source` over the existing `dest` can be skipped
+    pub fn skips(&self, source: &ObjectFingerprint, dest: &ObjectFingerprint) -> bool {
+        match self {
+            CopyMode::Overwrite => false,
+            CopyMode::SkipIdentical => dest.same_content(source),
+            CopyMode::OverwriteIfNewer => source.last_modified <= dest.last_modified,
+        }
+    }
+
+    /// Why a key was skipped, for the copy summary
+    pub fn skip_reason(&self) -> &'static str {
+        match self {
+            CopyMode::Overwrite => "",
+            CopyMode::SkipIdentical => "identical",
+            CopyMode::OverwriteIfNewer => "destination not older",
+        }
+    }
+}
+
+/// How a copy renames keys on their way to the destination: a key starting
+/// with `from` gets `to` in its place. Other keys, and every key when both
+/// are empty, keep their name.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct KeyRewrite {
+    #[serde(default)]
+    pub from: String,
+    #[serde(default)]
+    pub to: String,
+}
+
+impl KeyRewrite {
+    pub fn is_empty(&self) -> bool {
+        self.from == self.to
+    }
+
+    /// The destination key of `key`
+    pub fn apply(&self, key: &str) -> String {
+        match key.strip_prefix(&self.from) {
+            Some(rest) => format!("{}{rest}", self.to),
+            None => key.to_string(),
+        }
+    }
+
+    /// "logs/ → archive/logs/", with "(root)" for an empty side
+    pub fn label(&self) -> String {
+        let side = |prefix: &str| {
+            if prefix.is_empty() {
+                "(root)".to_string()
+            } else {
+                prefix.to_string()
+            }
+        };
+        format!("{} → {}", side(&self.from), side(&self.to))
+    }
+}
+
+/// What HeadObject reports about an object, for comparing a copy with its source
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ObjectFingerprint {
+    pub size: i64,
+    pub etag: Option<String>,
+    /// Additional checksum as `algorithm:value`, when the object has one
+    pub checksum: Option<String>,
+    pub last_modified: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl ObjectFingerprint {
+    /// Same size, and the same ETag or checksum. ETags of multipart uploads
+    /// depend on the part size, so a matching checksum also counts.
+    pub fn same_content(&self, other: &ObjectFingerprint) -> bool {
+        let same = |a: &Option<String>, b: &Option<String>| a.is_some() && a == b;
+        self.size == other.size
+            && (same(&self.etag, &other.etag) || same(&self.checksum, &other.checksum))
+    }
+
+    /// Check that this object is a faithful copy of `source`, saying how it
+    /// was verified. A multipart copy gets a new ETag, so it only passes with a
+    /// matching checksum; its size alone does not prove the content.
+    pub fn verify_copy_of(
+        &self,
+        source: &ObjectFingerprint,
+        multipart: bool,
+    ) -> std::result::Result<&'static str, String> {
+        if self.size != source.size {
+            return Err(format!(
+                "size differs ({} bytes, source {} bytes)",
+                self.size, source.size
+            ));
+        }
+        if self.etag.is_some() && self.etag == source.etag {
+            Ok("ETag matches")
+        } else if self.checksum.is_some() && self.checksum == source.checksum {
+            Ok("checksum matches")
+        } else if multipart {
+            Err(
+                "multipart ETag differs and no checksum matches, so only the size could be checked"
+                    .to_string(),
+            )
+        } else {
+            Err("ETag differs".to_string())
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JobRecord {
+    pub id: String,
+    pub kind: JobKind,
+    pub bucket: String,
+    pub target: String,
+    pub started_at: String, // ISO 8601 timestamp
+    pub finished_at: Option<String>,
+    pub total: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    /// Set when the user quit before every key was processed
+    #[serde(default)]
+    pub interrupted: bool,
+    /// Mean time one request took, for duration estimates of later batches
+    #[serde(default)]
+    pub request_ms: Option<u64>,
+    /// Why the job ran, as the operator noted it: a ticket or a reason
+    #[serde(default)]
+    pub note: Option<String>,
+    /// The change ticket the job ran under, for audits
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ticket: Option<TicketLink>,
+}
+
+impl JobRecord {
+    /// Wall-clock duration of a finished job
+    pub fn duration(&self) -> Option<chrono::Duration> {
+        let started = chrono::DateTime::parse_from_rfc3339(&self.started_at).ok()?;
+        let finished = chrono::DateTime::parse_from_rfc3339(self.finished_at.as_ref()?).ok()?;
+        Some(finished - started)
+    }
+}
+
+/// Per-key outcome of a batch job, one JSON line per key in the job journal
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub key: String,
+    pub at: String,
+    pub error: Option<String>,
+    /// How a moved key was verified, and whether its source was deleted
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ObjectInfo {
+    pub key: String,
+    pub size: i64,
+    pub last_modified: Option<String>,
+    pub storage_class: StorageClassTier,
+    pub restore_state: Option<RestoreState>,
+    /// When S3 removes the restored copy (RFC 3339), from HeadObject
+    #[serde(default)]
+    pub restore_expiry: Option<String>,
+    /// Owner display name (or canonical ID), only listed when owner fetching is on
+    #[serde(default)]
+    pub owner: Option<String>,
+    /// Object tags, only fetched for script masks that read them
+    #[serde(default)]
+    pub tags: Option<Vec<(String, String)>>,
+    /// Server-side encryption, from HeadObject; `None` until it is fetched
+    /// or when S3 reports none
+    #[serde(default)]
+    pub encryption: Option<ObjectEncryption>,
+    /// Which version of the key this row is, when listed with
+    /// ListObjectVersions; `None` in an ordinary listing
+    #[serde(default)]
+    pub version: Option<ObjectVersion>,
+}
+
+impl ObjectInfo {
+    /// The version a GET of the key returns: every row of an ordinary
+    /// listing, or the latest version unless it is a delete marker
+    pub fn is_current(&self) -> bool {
+        self.version
+            .as_ref()
+            .is_none_or(|v| v.is_latest && !v.delete_marker)
+    }
+
+    /// A noncurrent version or delete marker, which only a request naming
+    /// its version ID reaches
+    pub fn is_noncurrent(&self) -> bool {
+        self.version.as_ref().is_some_and(|v| !v.is_latest)
+    }
+}
+
+/// One entry of ListObjectVersions
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ObjectVersion {
+    /// `null` for objects written before versioning was enabled
+    pub id: String,
+    pub is_latest: bool,
+    /// A delete marker rather than object data; it has no size or class
+    pub delete_marker: bool,
+}
+
+impl ObjectVersion {
+    /// An object row for this version of `key`, to be filled in from the
+    /// listing
+    pub fn into_row(self, key: &str) -> ObjectInfo {
+        ObjectInfo {
+            key: key.to_string(),
+            size: 0,
+            last_modified: None,
+            storage_class: StorageClassTier::Unknown(String::new()),
+            restore_state: None,
+            restore_expiry: None,
+            owner: None,
+            tags: None,
+            encryption: None,
+            version: Some(self),
+        }
+    }
+}
+
+/// How an object is encrypted at rest, as HeadObject reports it
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ObjectEncryption {
+    /// SSE-S3 with S3-managed keys
+    S3Managed,
+    /// SSE-KMS, with the key's ARN
+    Kms(Option<String>),
+    /// DSSE-KMS (dual-layer), with the key's ARN
+    DualLayerKms(Option<String>),
+    /// SSE-C with a key the client supplies
+    Customer,
+}
+
+impl ObjectEncryption {
+    pub fn from_head(
+        sse: Option<&aws_sdk_s3::types::ServerSideEncryption>,
+        kms_key_id: Option<&str>,
+        customer_algorithm: Option<&str>,
+    ) -> Option<Self> {
+        use aws_sdk_s3::types::ServerSideEncryption;
+
+        let key = kms_key_id.map(str::to_string);
+        match sse {
+            Some(ServerSideEncryption::AwsKms) => Some(ObjectEncryption::Kms(key)),
+            Some(ServerSideEncryption::AwsKmsDsse) => Some(ObjectEncryption::DualLayerKms(key)),
+            Some(_) => Some(ObjectEncryption::S3Managed),
+            None if customer_algorithm.is_some() => Some(ObjectEncryption::Customer),
+            None => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ObjectEncryption::S3Managed => "SSE-S3",
+            ObjectEncryption::Kms(_) => "SSE-KMS",
+            ObjectEncryption::DualLayerKms(_) => "DSSE-KMS",
+            ObjectEncryption::Customer => "SSE-C",
+        }
+    }
+
+    /// ARN of the KMS key, for the KMS kinds
+    pub fn kms_key(&self) -> Option<&str> {
+        match self {
+            ObjectEncryption::Kms(key) | ObjectEncryption::DualLayerKms(key) => key.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum RestoreState {
+    Available,
+    InProgress { expiry: Option<String> },
+    Expired,
+}
+
+/// Restore status of a listed object, as the object list labels it. Objects
+/// outside Glacier Flexible Retrieval and Deep Archive have none.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RestoreStatus {
+    NeedsRestore,
+    Restoring,
+    Restored,
+    Expired,
+}
+
+impl RestoreStatus {
+    pub const ALL: [RestoreStatus; 4] = [
+        RestoreStatus::NeedsRestore,
+        RestoreStatus::Restoring,
+        RestoreStatus::Restored,
+        RestoreStatus::Expired,
+    ];
+
+    pub fn of(obj: &ObjectInfo) -> Option<Self> {
+        match &obj.restore_state {
+            Some(RestoreState::Available) => Some(RestoreStatus::Restored),
+            Some(RestoreState::InProgress { .. }) => Some(RestoreStatus::Restoring),
+            Some(RestoreState::Expired) => Some(RestoreStatus::Expired),
+            None if matches!(
+                obj.storage_class,
+                StorageClassTier::GlacierFlexibleRetrieval | StorageClassTier::GlacierDeepArchive
+            ) =>
+            {
+                Some(RestoreStatus::NeedsRestore)
+            }
+            None => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RestoreStatus::NeedsRestore => "NeedsRestore",
+            RestoreStatus::Restoring => "Restoring",
+            RestoreStatus::Restored => "Restored",
+            RestoreStatus::Expired => "Expired",
+        }
+    }
+
+    /// The next filter: each status in turn, then none
+    pub fn cycle(filter: Option<Self>) -> Option<Self> {
+        match filter {
+            None => Some(RestoreStatus::ALL[0]),
+            Some(status) => Self::ALL
+                .iter()
+                .position(|s| *s == status)
+                .and_then(|idx| Self::ALL.get(idx + 1))
+                .copied(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum StorageClassTier {
+    Standard,
+    IntelligentTiering,
+    StandardIa,
+    OneZoneIa,
+    GlacierInstantRetrieval,
+    GlacierFlexibleRetrieval,
+    GlacierDeepArchive,
+    ReducedRedundancy,
+    Unknown(String),
+}
+
+impl StorageClassTier {
+    pub const SELECTABLE: [StorageClassTier; 7] = [
+        StorageClassTier::Standard,
+        StorageClassTier::IntelligentTiering,
+        StorageClassTier::StandardIa,
+        StorageClassTier::OneZoneIa,
+        StorageClassTier::GlacierInstantRetrieval,
+        StorageClassTier::GlacierFlexibleRetrieval,
+        StorageClassTier::GlacierDeepArchive,
+    ];
+
+    pub fn selectable() -> &'static [StorageClassTier] {
+        &Self::SELECTABLE
+    }
+
+    /// Returns all storage classes with "Any" option for mask filtering
+    pub fn all_for_filter() -> Vec<(&'static str, Option<StorageClassTier>)> {
+        vec![
+            ("Any", None),
+            ("STANDARD", Some(StorageClassTier::Standard)),
+            (
+                "INTELLIGENT_TIERING",
+                Some(StorageClassTier::IntelligentTiering),
+            ),
+            ("STANDARD_IA", Some(StorageClassTier::StandardIa)),
+            ("ONEZONE_IA", Some(StorageClassTier::OneZoneIa)),
+            (
+                "GLACIER_IR",
+                Some(StorageClassTier::GlacierInstantRetrieval),
+            ),
+            ("GLACIER", Some(StorageClassTier::GlacierFlexibleRetrieval)),
+            ("DEEP_ARCHIVE", Some(StorageClassTier::GlacierDeepArchive)),
+            (
+                "REDUCED_REDUNDANCY",
+                Some(StorageClassTier::ReducedRedundancy),
+            ),
+        ]
+    }
+
+    pub fn label(&self) -> &str {
+        match self {
+            StorageClassTier::Standard => "STANDARD",
+            StorageClassTier::IntelligentTiering => "INTELLIGENT_TIERING",
+            StorageClassTier::StandardIa => "STANDARD_IA",
+            StorageClassTier::OneZoneIa => "ONEZONE_IA",
+            StorageClassTier::GlacierInstantRetrieval => "GLACIER_IR",
+            StorageClassTier::GlacierFlexibleRetrieval => "GLACIER",
+            StorageClassTier::GlacierDeepArchive => "DEEP_ARCHIVE",
+            StorageClassTier::ReducedRedundancy => "REDUCED_REDUNDANCY",
+            StorageClassTier::Unknown(label) => label.as_str(),
+        }
+    }
+
+    /// How quickly objects in this class can be read
+    pub fn retrieval_latency(&self) -> &'static str {
+        match self {
+            StorageClassTier::GlacierFlexibleRetrieval => "restore: 1-5 min to 12 h",
+            StorageClassTier::GlacierDeepArchive => "restore: 12-48 h",
+            StorageClassTier::Unknown(_) => "unknown",
+            _ => "milliseconds",
+        }
+    }
+
+    /// Glacier classes, the ones an archive with Object Lock settings targets
+    pub fn is_archival(&self) -> bool {
+        matches!(
+            self,
+            StorageClassTier::GlacierInstantRetrieval
+                | StorageClassTier::GlacierFlexibleRetrieval
+                | StorageClassTier::GlacierDeepArchive
+        )
+    }
+
+    /// Longest restore the confirmation dialog accepts for an object in
+    /// this class. A Deep Archive restore takes up to 48 hours and costs more
+    /// to repeat, so its copy may be kept longer.
+    pub fn max_restore_days(&self) -> i32 {
+        match self {
+            StorageClassTier::GlacierDeepArchive => 90,
+            _ => 30,
+        }
+    }
+
+    /// Minimum billed storage duration in days (early deletion is charged pro rata)
+    pub fn min_storage_days(&self) -> u32 {
+        match self {
+            StorageClassTier::StandardIa | StorageClassTier::OneZoneIa => 30,
+            StorageClassTier::GlacierInstantRetrieval
+            | StorageClassTier::GlacierFlexibleRetrieval => 90,
+            StorageClassTier::GlacierDeepArchive => 180,
+            _ => 0,
+        }
+    }
+
+    pub fn to_sdk(&self) -> Option<StorageClass> {
+        match self {
+            StorageClassTier::Standard => Some(StorageClass::Standard),
+            StorageClassTier::IntelligentTiering => Some(StorageClass::IntelligentTiering),
+            StorageClassTier::StandardIa => Some(StorageClass::StandardIa),
+            StorageClassTier::OneZoneIa => Some(StorageClass::OnezoneIa),
+            StorageClassTier::GlacierInstantRetrieval => Some(StorageClass::GlacierIr),
+            StorageClassTier::GlacierFlexibleRetrieval => Some(StorageClass::Glacier),
+            StorageClassTier::GlacierDeepArchive => Some(StorageClass::DeepArchive),
+            StorageClassTier::ReducedRedundancy => Some(StorageClass::ReducedRedundancy),
+            StorageClassTier::Unknown(_) => None,
+        }
+    }
+}
+
+impl From<Option<ObjectStorageClass>> for StorageClassTier {
+    fn from(value: Option<ObjectStorageClass>) -> Self {
+        match value {
+            Some(ObjectStorageClass::Standard) | None => StorageClassTier::Standard,
+            Some(ObjectStorageClass::IntelligentTiering) => StorageClassTier::IntelligentTiering,
+            Some(ObjectStorageClass::StandardIa) => StorageClassTier::StandardIa,
+            Some(ObjectStorageClass::OnezoneIa) => StorageClassTier::OneZoneIa,
+            Some(ObjectStorageClass::GlacierIr) => StorageClassTier::GlacierInstantRetrieval,
+            Some(ObjectStorageClass::Glacier) => StorageClassTier::GlacierFlexibleRetrieval,
+            Some(ObjectStorageClass::DeepArchive) => StorageClassTier::GlacierDeepArchive,
+            Some(ObjectStorageClass::ReducedRedundancy) => StorageClassTier::ReducedRedundancy,
+            Some(other) => StorageClassTier::Unknown(other.as_str().to_string()),
+        }
+    }
+}
+
+impl From<Option<StorageClass>> for StorageClassTier {
+    fn from(value: Option<StorageClass>) -> Self {
+        match value {
+            Some(StorageClass::Standard) | None => StorageClassTier::Standard,
+            Some(StorageClass::IntelligentTiering) => StorageClassTier::IntelligentTiering,
+            Some(StorageClass::StandardIa) => StorageClassTier::StandardIa,
+            Some(StorageClass::OnezoneIa) => StorageClassTier::OneZoneIa,
+            Some(StorageClass::GlacierIr) => StorageClassTier::GlacierInstantRetrieval,
+            Some(StorageClass::Glacier) => StorageClassTier::GlacierFlexibleRetrieval,
+            Some(StorageClass::DeepArchive) => StorageClassTier::GlacierDeepArchive,
+            Some(StorageClass::ReducedRedundancy) => StorageClassTier::ReducedRedundancy,
+            Some(other) => StorageClassTier::Unknown(other.as_str().to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fingerprint(size: i64, etag: Option<&str>, checksum: Option<&str>) -> ObjectFingerprint {
+        ObjectFingerprint {
+            size,
+            etag: etag.map(str::to_string),
+            checksum: checksum.map(str::to_string),
+            last_modified: None,
+        }
+    }
+
+    #[test]
+    fn rewrite_replaces_only_a_leading_prefix() {
+        let rewrite = KeyRewrite {
+            from: "logs/".into(),
+            to: "archive/logs/".into(),
+        };
+        assert_eq!(rewrite.apply("logs/2019/a.gz"), "archive/logs/2019/a.gz");
+        assert_eq!(rewrite.apply("raw/logs/a.gz"), "raw/logs/a.gz");
+        assert_eq!(rewrite.apply("logs"), "logs");
+    }
+
+    #[test]
+    fn rewrite_from_the_root_prefixes_every_key() {
+        let rewrite = KeyRewrite {
+            from: String::new(),
+            to: "2019/".into(),
+        };
+        assert_eq!(rewrite.apply("a.gz"), "2019/a.gz");
+        assert!(KeyRewrite::default().is_empty());
+        assert_eq!(KeyRewrite::default().apply("a.gz"), "a.gz");
+    }
+
+    #[test]
+    fn copy_with_another_size_fails() {
+        let source = fingerprint(10, Some("\"abc\""), None);
+        let copy = fingerprint(11, Some("\"abc\""), None);
+        assert!(copy.verify_copy_of(&source, false).is_err());
+    }
+
+    #[test]
+    fn copy_passes_on_etag_or_checksum() {
+        let source = fingerprint(10, Some("\"abc\""), Some("crc32c:AAAA"));
+        assert_eq!(
+            fingerprint(10, Some("\"abc\""), None).verify_copy_of(&source, false),
+            Ok("ETag matches")
+        );
+        assert_eq!(
+            fingerprint(10, Some("\"def-2\""), Some("crc32c:AAAA")).verify_copy_of(&source, true),
+            Ok("checksum matches")
+        );
+    }
+
+    #[test]
+    fn multipart_copy_without_a_checksum_is_not_verified() {
+        let source = fingerprint(10, Some("\"abc-3\""), None);
+        let copy = fingerprint(10, Some("\"def-2\""), None);
+        let err = copy.verify_copy_of(&source, true).unwrap_err();
+        assert!(err.contains("only the size"), "{err}");
+        assert_eq!(
+            copy.verify_copy_of(&source, false),
+            Err("ETag differs".to_string())
+        );
+    }
+
+    #[test]
+    fn missing_etags_do_not_match() {
+        let source = fingerprint(10, None, None);
+        assert!(
+            fingerprint(10, None, None)
+                .verify_copy_of(&source, false)
+                .is_err()
+        );
+    }
+}
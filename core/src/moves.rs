@@ -0,0 +1,166 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::journal::JobHistory;
+use crate::models::{CopyMode, KeyRewrite, StorageClassTier};
+
+/// Every key of a cross-bucket move, written before the first copy so an
+/// interrupted move can be resumed for the keys its journals do not show as
+/// done
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MoveManifest {
+    /// Journal id of the first run
+    pub id: String,
+    pub source_bucket: String,
+    pub dest_bucket: String,
+    #[serde(default)]
+    pub rewrite: KeyRewrite,
+    #[serde(default)]
+    pub storage_class: Option<StorageClassTier>,
+    #[serde(default)]
+    pub mode: CopyMode,
+    #[serde(default)]
+    pub max_bandwidth: Option<u64>,
+    /// (key, size); the size decides whether a resumed copy needs multipart
+    pub keys: Vec<(String, i64)>,
+    pub created_at: String, // ISO 8601 timestamp
+    /// Journal ids of the runs, the first included
+    pub runs: Vec<String>,
+}
+
+impl MoveManifest {
+    /// Keys no run moved or skipped without error, in manifest order
+    pub fn remaining(&self, history: &JobHistory) -> Vec<String> {
+        let done: HashSet<String> = self
+            .runs
+            .iter()
+            .flat_map(|run| history.entries(run).unwrap_or_default())
+            .filter(|entry| entry.error.is_none())
+            .map(|entry| entry.key)
+            .collect();
+        self.keys
+            .iter()
+            .filter(|(key, _)| !done.contains(key))
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+
+    pub fn sizes(&self) -> HashMap<String, i64> {
+        self.keys.iter().cloned().collect()
+    }
+
+    /// Whether a move of `keys` with these settings continues this one
+    fn continued_by(
+        &self,
+        source_bucket: &str,
+        dest_bucket: &str,
+        rewrite: &KeyRewrite,
+        storage_class: &Option<StorageClassTier>,
+        keys: &[String],
+    ) -> bool {
+        if self.source_bucket != source_bucket
+            || self.dest_bucket != dest_bucket
+            || &self.rewrite != rewrite
+            || &self.storage_class != storage_class
+        {
+            return false;
+        }
+        let known: HashSet<&str> = self.keys.iter().map(|(key, _)| key.as_str()).collect();
+        keys.iter().all(|key| known.contains(key.as_str()))
+    }
+}
+
+/// Unfinished moves, persisted in `moves.json` until every key is done
+#[derive(Default)]
+pub struct MoveStore {
+    file_path: PathBuf,
+    moves: Vec<MoveManifest>,
+}
+
+impl MoveStore {
+    pub fn new() -> Result<Self> {
+        let config_dir = directories::ProjectDirs::from("com", "bucket-brigade", "bucket-brigade")
+            .map(|dirs| dirs.config_dir().to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        fs::create_dir_all(&config_dir)?;
+        let file_path = config_dir.join("moves.json");
+
+        let moves = if file_path.exists() {
+            let content = fs::read_to_string(&file_path)?;
+            serde_json::from_str(&content).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self { file_path, moves })
+    }
+
+    pub fn moves(&self) -> &[MoveManifest] {
+        &self.moves
+    }
+
+    /// The unfinished move that `job_id` ran for
+    pub fn of_run(&self, job_id: &str) -> Option<&MoveManifest> {
+        self.moves
+            .iter()
+            .find(|manifest| manifest.runs.iter().any(|run| run == job_id))
+    }
+
+    /// Record `job_id` as a run of the unfinished move these keys belong to,
+    /// or start a manifest for them. Returns the manifest id.
+    #[allow(clippy::too_many_arguments)]
+    pub fn begin_run(
+        &mut self,
+        job_id: &str,
+        source_bucket: &str,
+        dest_bucket: &str,
+        rewrite: &KeyRewrite,
+        storage_class: &Option<StorageClassTier>,
+        mode: CopyMode,
+        max_bandwidth: Option<u64>,
+        keys: Vec<(String, i64)>,
+    ) -> Result<String> {
+        let names: Vec<String> = keys.iter().map(|(key, _)| key.clone()).collect();
+        let existing = self.moves.iter_mut().find(|manifest| {
+            manifest.continued_by(source_bucket, dest_bucket, rewrite, storage_class, &names)
+        });
+        let id = match existing {
+            Some(manifest) => {
+                manifest.runs.push(job_id.to_string());
+                manifest.id.clone()
+            }
+            None => {
+                self.moves.push(MoveManifest {
+                    id: job_id.to_string(),
+                    source_bucket: source_bucket.to_string(),
+                    dest_bucket: dest_bucket.to_string(),
+                    rewrite: rewrite.clone(),
+                    storage_class: storage_class.clone(),
+                    mode,
+                    max_bandwidth,
+                    keys,
+                    created_at: chrono::Utc::now().to_rfc3339(),
+                    runs: vec![job_id.to_string()],
+                });
+                job_id.to_string()
+            }
+        };
+        self.save()?;
+        Ok(id)
+    }
+
+    pub fn remove(&mut self, id: &str) -> Result<()> {
+        self.moves.retain(|manifest| manifest.id != id);
+        self.save()
+    }
+
+    fn save(&self) -> Result<()> {
+        let json = serde_json::to_string_pretty(&self.moves)?;
+        fs::write(&self.file_path, json)?;
+        Ok(())
+    }
+}
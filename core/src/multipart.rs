@@ -0,0 +1,413 @@
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::aws::{ObjectAttributes, S3Service};
+use crate::batch::ShutdownChoice;
+use crate::models::StorageClassTier;
+
+/// Objects larger than this cannot use CopyObject and are copied in parts
+pub const MULTIPART_COPY_THRESHOLD: i64 = 5 * 1024 * 1024 * 1024;
+/// Smallest part S3 accepts, other than the last
+pub const MIN_PART_SIZE: i64 = 5 * 1024 * 1024;
+/// Most parts one multipart upload can have
+pub const MAX_PARTS: i64 = 10_000;
+
+/// Times a copy starts over because its source changed before it gives up
+const SOURCE_CHANGE_RESTARTS: usize = 3;
+
+/// UploadPartCopy range size for a source of `size` bytes: the smallest
+/// whole MiB that fits it in 10,000 parts, and no less than 5 MiB
+pub fn part_size_for(size: i64) -> i64 {
+    const MIB: i64 = 1024 * 1024;
+    let fitting = ((size + MAX_PARTS - 1) / MAX_PARTS).max(MIN_PART_SIZE);
+    (fitting + MIB - 1) / MIB * MIB
+}
+
+/// How a copy ended when it did not fail
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CopyOutcome {
+    Completed,
+    /// Stopped between parts at the user's request; the upload stays in the store
+    Checkpointed,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CompletedPartRecord {
+    pub part_number: i32,
+    pub etag: String,
+}
+
+/// An in-flight multipart copy, persisted so it can be resumed after a crash
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MultipartCopy {
+    pub source_bucket: String,
+    pub source_key: String,
+    pub dest_bucket: String,
+    pub dest_key: String,
+    pub upload_id: String,
+    pub size: i64,
+    /// Chosen from the size when the upload starts, so a resume cuts the
+    /// same ranges
+    pub part_size: i64,
+    /// ETag of the source when the upload started. Every part is copied
+    /// only while the source still has it, so a source overwritten before
+    /// a resume is never stitched together with the parts of the old one.
+    #[serde(default)]
+    pub source_etag: Option<String>,
+    pub storage_class: Option<StorageClassTier>,
+    /// KMS key a re-encryption copy is written under
+    #[serde(default)]
+    pub kms_key_id: Option<String>,
+    pub completed_parts: Vec<CompletedPartRecord>,
+    pub started_at: String, // ISO 8601 timestamp
+}
+
+impl MultipartCopy {
+    pub fn part_count(&self) -> i32 {
+        ((self.size + self.part_size - 1) / self.part_size).max(1) as i32
+    }
+
+    /// Byte range (inclusive) covered by a 1-based part number
+    pub fn part_range(&self, part_number: i32) -> (i64, i64) {
+        let start = (part_number as i64 - 1) * self.part_size;
+        let end = (start + self.part_size).min(self.size) - 1;
+        (start, end)
+    }
+
+    /// Part numbers that still need to be copied
+    pub fn remaining_parts(&self) -> Vec<i32> {
+        (1..=self.part_count())
+            .filter(|n| !self.completed_parts.iter().any(|p| p.part_number == *n))
+            .collect()
+    }
+}
+
+pub struct MultipartStore {
+    file_path: PathBuf,
+    uploads: Vec<MultipartCopy>,
+}
+
+impl MultipartStore {
+    pub fn new() -> Result<Self> {
+        let config_dir = directories::ProjectDirs::from("com", "bucket-brigade", "bucket-brigade")
+            .map(|dirs| dirs.config_dir().to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        fs::create_dir_all(&config_dir)?;
+        let file_path = config_dir.join("multipart_uploads.json");
+
+        let uploads = if file_path.exists() {
+            let content = fs::read_to_string(&file_path)?;
+            serde_json::from_str(&content).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self { file_path, uploads })
+    }
+
+    pub fn uploads(&self) -> &[MultipartCopy] {
+        &self.uploads
+    }
+
+    pub fn add(&mut self, upload: MultipartCopy) {
+        self.uploads.push(upload);
+        let _ = self.save();
+    }
+
+    pub fn record_part(&mut self, upload_id: &str, part: CompletedPartRecord) {
+        if let Some(upload) = self.uploads.iter_mut().find(|u| u.upload_id == upload_id) {
+            upload.completed_parts.push(part);
+        }
+        let _ = self.save();
+    }
+
+    /// Find the persisted upload writing to a destination object
+    pub fn find(&self, dest_bucket: &str, dest_key: &str) -> Option<&MultipartCopy> {
+        self.uploads
+            .iter()
+            .find(|u| u.dest_bucket == dest_bucket && u.dest_key == dest_key)
+    }
+
+    pub fn remove(&mut self, upload_id: &str) {
+        self.uploads.retain(|u| u.upload_id != upload_id);
+        let _ = self.save();
+    }
+
+    fn save(&self) -> Result<()> {
+        let json = serde_json::to_string_pretty(&self.uploads)?;
+        fs::write(&self.file_path, json)?;
+        Ok(())
+    }
+}
+
+/// Start (and persist) a multipart copy of `key` to `dest_key` in
+/// `dest_bucket`, then copy every part and complete it
+#[allow(clippy::too_many_arguments)]
+pub async fn copy_large_object(
+    s3: &S3Service,
+    store: &mut MultipartStore,
+    source_bucket: &str,
+    dest_bucket: &str,
+    key: &str,
+    dest_key: &str,
+    storage_class: Option<StorageClassTier>,
+    stop: &Cell<Option<ShutdownChoice>>,
+) -> Result<CopyOutcome> {
+    let upload = s3
+        .start_multipart_copy(
+            source_bucket,
+            key,
+            dest_bucket,
+            dest_key,
+            storage_class,
+            None,
+        )
+        .await?;
+    store.add(upload.clone());
+    resume_copy(s3, store, upload, stop).await
+}
+
+/// Copy the parts that are still missing and complete the upload.
+/// A checkpoint request stops between parts and leaves the upload resumable.
+/// When the source has changed since the upload started, the upload is
+/// aborted and the copy starts over from the first part.
+pub async fn resume_copy(
+    s3: &S3Service,
+    store: &mut MultipartStore,
+    mut upload: MultipartCopy,
+    stop: &Cell<Option<ShutdownChoice>>,
+) -> Result<CopyOutcome> {
+    // Saved before source ETags were recorded, so its parts cannot be
+    // checked against the source
+    if upload.source_etag.is_none() {
+        upload = restart_copy(s3, store, &upload).await?;
+    }
+    let mut restarts = 0;
+    'copy: loop {
+        for part_number in upload.remaining_parts() {
+            if stop.get() == Some(ShutdownChoice::Checkpoint) {
+                return Ok(CopyOutcome::Checkpointed);
+            }
+            let Some(part) = s3.copy_part(&upload, part_number).await? else {
+                if restarts == SOURCE_CHANGE_RESTARTS {
+                    bail!(
+                        "{}/{} changed during the copy {restarts} times; nothing was completed",
+                        upload.source_bucket,
+                        upload.source_key
+                    );
+                }
+                restarts += 1;
+                upload = restart_copy(s3, store, &upload).await?;
+                continue 'copy;
+            };
+            store.record_part(&upload.upload_id, part.clone());
+            upload.completed_parts.push(part);
+        }
+        break;
+    }
+    s3.complete_multipart_copy(&upload).await?;
+    store.remove(&upload.upload_id);
+    Ok(CopyOutcome::Completed)
+}
+
+/// Abort `upload` and start the same copy again from the source as it is now
+async fn restart_copy(
+    s3: &S3Service,
+    store: &mut MultipartStore,
+    upload: &MultipartCopy,
+) -> Result<MultipartCopy> {
+    // Best effort: a lifecycle rule for incomplete uploads catches one that
+    // cannot be aborted now
+    let _ = s3.abort_multipart_copy(upload).await;
+    store.remove(&upload.upload_id);
+    let fresh = s3
+        .start_multipart_copy(
+            &upload.source_bucket,
+            &upload.source_key,
+            &upload.dest_bucket,
+            &upload.dest_key,
+            upload.storage_class.clone(),
+            upload.kms_key_id.as_deref(),
+        )
+        .await?;
+    store.add(fresh.clone());
+    Ok(fresh)
+}
+
+/// Smallest part of a `StreamUpload`, for output expected to be small
+pub const STREAM_PART_SIZE: usize = 64 * 1024 * 1024;
+/// Largest part S3 accepts
+const MAX_PART_SIZE: usize = 5 * 1024 * 1024 * 1024;
+/// Parts a `StreamUpload` sends at one size before doubling it. Starting at
+/// 64 MiB, 7,000 parts reach past the 5 TiB object limit, so output larger
+/// than expected still fits in 10,000 parts.
+const PARTS_PER_SIZE: usize = 1000;
+
+/// An object written from content produced as it goes, such as a command's
+/// output. Up to one part is sent with PutObject on `finish`; anything larger
+/// goes up as a multipart upload, part by part as it is written. Parts start
+/// at a size that fits the expected output in 10,000 parts and double every
+/// 1,000 parts, up to 5 GiB. The upload's
+/// id is kept in `upload` while it is open, so a caller that drops the
+/// writer can abort it.
+pub struct StreamUpload<'a> {
+    s3: &'a S3Service,
+    bucket: &'a str,
+    key: &'a str,
+    attributes: ObjectAttributes,
+    upload: &'a Cell<Option<String>>,
+    parts: Vec<CompletedPartRecord>,
+    part_size: usize,
+    buffer: Vec<u8>,
+    written: i64,
+}
+
+impl<'a> StreamUpload<'a> {
+    pub fn new(
+        s3: &'a S3Service,
+        bucket: &'a str,
+        key: &'a str,
+        attributes: ObjectAttributes,
+        upload: &'a Cell<Option<String>>,
+        expected: i64,
+    ) -> Self {
+        let part_size = (part_size_for(expected) as usize).clamp(STREAM_PART_SIZE, MAX_PART_SIZE);
+        Self {
+            s3,
+            bucket,
+            key,
+            attributes,
+            upload,
+            parts: Vec::new(),
+            part_size,
+            buffer: Vec::with_capacity(STREAM_PART_SIZE),
+            written: 0,
+        }
+    }
+
+    pub async fn write(&mut self, data: &[u8]) -> Result<()> {
+        self.written += data.len() as i64;
+        self.buffer.extend_from_slice(data);
+        if self.buffer.len() >= self.part_size {
+            let part = std::mem::replace(&mut self.buffer, Vec::with_capacity(STREAM_PART_SIZE));
+            self.send_part(part).await?;
+        }
+        Ok(())
+    }
+
+    async fn send_part(&mut self, part: Vec<u8>) -> Result<()> {
+        let upload_id = match self.upload.take() {
+            Some(upload_id) => upload_id,
+            None => {
+                self.s3
+                    .start_upload(self.bucket, self.key, &self.attributes)
+                    .await?
+            }
+        };
+        self.upload.set(Some(upload_id.clone()));
+        let part_number = self.parts.len() as i32 + 1;
+        let record = self
+            .s3
+            .upload_part(self.bucket, self.key, &upload_id, part_number, part)
+            .await?;
+        self.parts.push(record);
+        if self.parts.len().is_multiple_of(PARTS_PER_SIZE) {
+            self.part_size = (self.part_size * 2).min(MAX_PART_SIZE);
+        }
+        Ok(())
+    }
+
+    /// Write what is left and complete the object. Returns its size.
+    pub async fn finish(mut self) -> Result<i64> {
+        // Left in place for `send_part`, and for the caller to abort on failure
+        let upload_id = self.upload.take();
+        self.upload.set(upload_id.clone());
+        let Some(upload_id) = upload_id else {
+            let body = std::mem::take(&mut self.buffer);
+            self.s3
+                .put_object_bytes(self.bucket, self.key, body, &self.attributes)
+                .await?;
+            return Ok(self.written);
+        };
+        if !self.buffer.is_empty() {
+            let part = std::mem::take(&mut self.buffer);
+            self.send_part(part).await?;
+        }
+        let parts = std::mem::take(&mut self.parts);
+        self.s3
+            .complete_upload(self.bucket, self.key, &upload_id, parts)
+            .await?;
+        self.upload.set(None);
+        Ok(self.written)
+    }
+
+    /// Abort the multipart upload, if one was started. Best effort: a
+    /// lifecycle rule for incomplete uploads catches one that cannot be
+    /// aborted now.
+    pub async fn abort(s3: &S3Service, bucket: &str, key: &str, upload: &Cell<Option<String>>) {
+        if let Some(upload_id) = upload.take() {
+            let _ = s3.abort_upload(bucket, key, &upload_id).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIB: i64 = 1024 * 1024;
+
+    fn copy_of(size: i64) -> MultipartCopy {
+        MultipartCopy {
+            source_bucket: "src".into(),
+            source_key: "a".into(),
+            dest_bucket: "dst".into(),
+            dest_key: "a".into(),
+            upload_id: "u".into(),
+            size,
+            part_size: part_size_for(size),
+            source_etag: Some("\"e\"".into()),
+            storage_class: None,
+            kms_key_id: None,
+            completed_parts: Vec::new(),
+            started_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn small_sources_use_the_smallest_part() {
+        assert_eq!(part_size_for(6 * 1024 * MIB), MIN_PART_SIZE);
+        assert_eq!(part_size_for(MAX_PARTS * MIN_PART_SIZE), MIN_PART_SIZE);
+    }
+
+    #[test]
+    fn part_size_grows_in_whole_mib() {
+        let size = MAX_PARTS * MIN_PART_SIZE + 1;
+        assert_eq!(part_size_for(size), 6 * MIB);
+        assert_eq!(part_size_for(size) % MIB, 0);
+    }
+
+    #[test]
+    fn the_largest_object_fits_in_the_part_limit() {
+        let copy = copy_of(5 * 1024 * 1024 * MIB);
+        assert!(copy.part_count() as i64 <= MAX_PARTS);
+        let (_, end) = copy.part_range(copy.part_count());
+        assert_eq!(end, copy.size - 1);
+    }
+
+    #[test]
+    fn growing_stream_parts_reach_the_object_limit() {
+        let (mut part_size, mut total) = (STREAM_PART_SIZE as i64, 0);
+        for part in 1..=MAX_PARTS as usize {
+            total += part_size;
+            if part.is_multiple_of(PARTS_PER_SIZE) {
+                part_size = (part_size * 2).min(MAX_PART_SIZE as i64);
+            }
+        }
+        assert!(total >= 5 * 1024 * 1024 * MIB, "{total}");
+    }
+}
@@ -0,0 +1,189 @@
+use serde::Deserialize;
+use serde_json::json;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use crate::models::JobRecord;
+use crate::owners::PrefixOwner;
+
+/// Environment variable holding a URL that receives a JSON POST when a tracked
+/// restore becomes available
+pub const RESTORE_WEBHOOK_ENV: &str = "BUCKET_BRIGADE_RESTORE_WEBHOOK";
+
+/// Post a restore-completion event to the configured webhook, if any.
+/// Delivery is best effort and never blocks the UI.
+pub fn restore_ready(bucket: &str, key: &str, ready_at: Option<&str>) {
+    let text = format!("Restore ready: s3://{bucket}/{key}");
+    slack(SlackEvent::RestoreReady, bucket, &text);
+    let Ok(url) = std::env::var(RESTORE_WEBHOOK_ENV) else {
+        return;
+    };
+    if url.is_empty() {
+        return;
+    }
+    let body = json!({
+        "event": "restore_ready",
+        "bucket": bucket,
+        "key": key,
+        "ready_at": ready_at,
+        "text": text,
+    });
+    tokio::spawn(async move {
+        let _ = reqwest::Client::new().post(url).json(&body).send().await;
+    });
+}
+
+/// Announce a batch job in Slack
+pub fn job_started(job: &JobRecord) {
+    let mut text = format!(
+        ":arrow_forward: {} started on `{}` → {}: {} objects",
+        job.kind.label(),
+        job.bucket,
+        job.target,
+        job.total
+    );
+    if let Some(note) = &job.note {
+        text.push_str(&format!(" ({note})"));
+    }
+    if let Some(ticket) = &job.ticket {
+        match &ticket.url {
+            Some(url) => text.push_str(&format!(" [<{url}|{}>]", ticket.id)),
+            None => text.push_str(&format!(" [{}]", ticket.id)),
+        }
+    }
+    slack(SlackEvent::JobStarted, &job.bucket, &text);
+}
+
+/// Report how a batch job ended in Slack
+pub fn job_finished(job: &JobRecord) {
+    let (icon, outcome) = if job.interrupted {
+        (":double_vertical_bar:", "stopped early")
+    } else if job.failed > 0 {
+        (":warning:", "finished with failures")
+    } else {
+        (":white_check_mark:", "finished")
+    };
+    let text = format!(
+        "{icon} {} on `{}` → {} {outcome}: {} succeeded, {} failed of {}",
+        job.kind.label(),
+        job.bucket,
+        job.target,
+        job.succeeded,
+        job.failed,
+        job.total
+    );
+    slack(SlackEvent::JobFinished, &job.bucket, &text);
+}
+
+/// Alert Slack that a batch paused because too many requests failed
+pub fn failure_rate(bucket: &str, attempted: usize, failed: usize, top_error: Option<&str>) {
+    let mut text = format!(
+        ":rotating_light: Batch on `{bucket}` paused: {failed} of {attempted} requests failed"
+    );
+    if let Some(error) = top_error {
+        text.push_str(&format!(" (mostly {error})"));
+    }
+    text.push_str(". Waiting for a decision in the TUI.");
+    slack(SlackEvent::FailureRate, bucket, &text);
+}
+
+/// Tell the owner of a prefix that a confirmed batch touches their data,
+/// on the webhook `owners.json` records for them. Owners without one are
+/// only shown in the confirmation.
+pub fn prefix_owner(owner: &PrefixOwner, bucket: &str, action: &str, objects: usize, bytes: i64) {
+    let Some(url) = owner.webhook_url.clone().filter(|url| !url.is_empty()) else {
+        return;
+    };
+    let text = format!(
+        ":bell: {action} confirmed on `{bucket}`: {objects} objects under `{}` owned by {}",
+        owner.prefix, owner.owner
+    );
+    let body = json!({
+        "event": "prefix_owner",
+        "bucket": bucket,
+        "prefix": owner.prefix,
+        "owner": owner.owner,
+        "contact": owner.contact,
+        "action": action,
+        "objects": objects,
+        "bytes": bytes,
+        "text": text,
+    });
+    tokio::spawn(async move {
+        let _ = reqwest::Client::new().post(url).json(&body).send().await;
+    });
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum SlackEvent {
+    JobStarted,
+    JobFinished,
+    FailureRate,
+    RestoreReady,
+}
+
+/// `slack.json` in the config directory
+#[derive(Debug, Default, Deserialize)]
+struct SlackConfig {
+    /// Incoming webhook used when no route matches the bucket
+    #[serde(default)]
+    webhook_url: Option<String>,
+    /// Per-bucket webhooks, checked in order
+    #[serde(default)]
+    routes: Vec<SlackRoute>,
+    /// Events to post; all of them when omitted
+    #[serde(default)]
+    events: Option<Vec<SlackEvent>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SlackRoute {
+    /// Bucket name, or a prefix ending in `*`
+    bucket: String,
+    webhook_url: String,
+}
+
+impl SlackConfig {
+    fn load() -> Self {
+        let path = directories::ProjectDirs::from("com", "bucket-brigade", "bucket-brigade")
+            .map(|dirs| dirs.config_dir().to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("slack.json");
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn webhook(&self, event: SlackEvent, bucket: &str) -> Option<&str> {
+        if let Some(events) = &self.events
+            && !events.contains(&event)
+        {
+            return None;
+        }
+        self.routes
+            .iter()
+            .find(|route| match route.bucket.strip_suffix('*') {
+                Some(prefix) => bucket.starts_with(prefix),
+                None => route.bucket == bucket,
+            })
+            .map(|route| route.webhook_url.as_str())
+            .or(self.webhook_url.as_deref())
+            .filter(|url| !url.is_empty())
+    }
+}
+
+/// Post `text` to the Slack channel routed for `bucket`. Best effort, like the
+/// restore webhook.
+fn slack(event: SlackEvent, bucket: &str, text: &str) {
+    static CONFIG: OnceLock<SlackConfig> = OnceLock::new();
+    let Some(url) = CONFIG.get_or_init(SlackConfig::load).webhook(event, bucket) else {
+        return;
+    };
+    let url = url.to_string();
+    let body = json!({ "text": text });
+    tokio::spawn(async move {
+        let _ = reqwest::Client::new().post(url).json(&body).send().await;
+    });
+}
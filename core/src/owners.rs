@@ -0,0 +1,150 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// Who owns the data under a prefix, and how to reach them
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PrefixOwner {
+    /// Bucket name, or a prefix ending in `*`; every bucket when omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bucket: Option<String>,
+    /// Key prefix the owner is responsible for, usually ending in `/`
+    pub prefix: String,
+    pub owner: String,
+    /// Email address, channel or pager alias
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contact: Option<String>,
+    /// Webhook that hears about batches touching the prefix, Slack style
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+}
+
+/// Whether an entry's bucket pattern covers `bucket`: a name, a prefix
+/// ending in `*`, or every bucket when there is none
+pub(crate) fn bucket_matches(pattern: Option<&str>, bucket: &str) -> bool {
+    match pattern {
+        None => true,
+        Some(pattern) => match pattern.strip_suffix('*') {
+            Some(prefix) => bucket.starts_with(prefix),
+            None => pattern == bucket,
+        },
+    }
+}
+
+impl PrefixOwner {
+    /// "Team X (team-x@example.com)", or just the owner without a contact
+    pub fn label(&self) -> String {
+        match &self.contact {
+            Some(contact) => format!("{} ({contact})", self.owner),
+            None => self.owner.clone(),
+        }
+    }
+}
+
+/// Prefix owners, persisted in `owners.json`
+#[derive(Default)]
+pub struct OwnerDirectory {
+    file_path: PathBuf,
+    owners: Vec<PrefixOwner>,
+}
+
+impl OwnerDirectory {
+    pub fn new() -> Result<Self> {
+        let config_dir = directories::ProjectDirs::from("com", "bucket-brigade", "bucket-brigade")
+            .map(|dirs| dirs.config_dir().to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        fs::create_dir_all(&config_dir)?;
+        let file_path = config_dir.join("owners.json");
+
+        let owners = if file_path.exists() {
+            let content = fs::read_to_string(&file_path)?;
+            serde_json::from_str(&content).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self { file_path, owners })
+    }
+
+    pub fn owners(&self) -> &[PrefixOwner] {
+        &self.owners
+    }
+
+    /// The owner of `key` in `bucket`: the entry with the longest matching
+    /// prefix, preferring one named for the bucket over a pattern
+    pub fn lookup(&self, bucket: &str, key: &str) -> Option<&PrefixOwner> {
+        self.owners
+            .iter()
+            .filter(|owner| {
+                bucket_matches(owner.bucket.as_deref(), bucket) && key.starts_with(&owner.prefix)
+            })
+            .max_by_key(|owner| (owner.prefix.len(), owner.bucket.as_deref() == Some(bucket)))
+    }
+
+    /// Owner entries matching `keys`, each with how many keys and bytes fall
+    /// under it, in the order first seen
+    pub fn owners_of<'a, I>(&self, bucket: &str, keys: I) -> Vec<(&PrefixOwner, usize, i64)>
+    where
+        I: IntoIterator<Item = (&'a str, i64)>,
+    {
+        let mut found: Vec<(&PrefixOwner, usize, i64)> = Vec::new();
+        for (key, size) in keys {
+            let Some(owner) = self.lookup(bucket, key) else {
+                continue;
+            };
+            match found
+                .iter_mut()
+                .find(|(known, _, _)| std::ptr::eq(*known, owner))
+            {
+                Some((_, count, bytes)) => {
+                    *count += 1;
+                    *bytes += size;
+                }
+                None => found.push((owner, 1, size)),
+            }
+        }
+        found
+    }
+
+    /// Record `owner` for its bucket and prefix, replacing an entry for the
+    /// same pair. A webhook set on the old entry is kept when the new one
+    /// has none.
+    pub fn set(&mut self, mut owner: PrefixOwner) -> Result<()> {
+        match self
+            .owners
+            .iter_mut()
+            .find(|known| known.bucket == owner.bucket && known.prefix == owner.prefix)
+        {
+            Some(existing) => {
+                if owner.webhook_url.is_none() {
+                    owner.webhook_url = existing.webhook_url.take();
+                }
+                *existing = owner;
+            }
+            None => self.owners.push(owner),
+        }
+        self.save()
+    }
+
+    /// Forget the owner recorded for exactly `bucket` and `prefix`
+    pub fn remove(&mut self, bucket: &str, prefix: &str) -> Result<Option<PrefixOwner>> {
+        let Some(index) = self
+            .owners
+            .iter()
+            .position(|owner| owner.bucket.as_deref() == Some(bucket) && owner.prefix == prefix)
+        else {
+            return Ok(None);
+        };
+        let owner = self.owners.remove(index);
+        self.save()?;
+        Ok(Some(owner))
+    }
+
+    fn save(&self) -> Result<()> {
+        let json = serde_json::to_string_pretty(&self.owners)?;
+        fs::write(&self.file_path, json)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+/// AWS partition the configured credentials belong to. GovCloud and China are
+/// separate partitions with their own regions, ARNs and console.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Partition {
+    Aws,
+    AwsUsGov,
+    AwsCn,
+}
+
+const AWS_REGIONS: &[&str] = &[
+    "us-east-1",
+    "us-east-2",
+    "us-west-1",
+    "us-west-2",
+    "af-south-1",
+    "ap-east-1",
+    "ap-south-1",
+    "ap-south-2",
+    "ap-southeast-1",
+    "ap-southeast-2",
+    "ap-southeast-3",
+    "ap-southeast-4",
+    "ap-northeast-1",
+    "ap-northeast-2",
+    "ap-northeast-3",
+    "ca-central-1",
+    "ca-west-1",
+    "eu-central-1",
+    "eu-central-2",
+    "eu-west-1",
+    "eu-west-2",
+    "eu-west-3",
+    "eu-south-1",
+    "eu-south-2",
+    "eu-north-1",
+    "il-central-1",
+    "me-south-1",
+    "me-central-1",
+    "sa-east-1",
+];
+
+const AWS_US_GOV_REGIONS: &[&str] = &["us-gov-west-1", "us-gov-east-1"];
+
+const AWS_CN_REGIONS: &[&str] = &["cn-north-1", "cn-northwest-1"];
+
+impl Partition {
+    pub fn of_region(region: &str) -> Self {
+        if region.starts_with("us-gov-") {
+            Partition::AwsUsGov
+        } else if region.starts_with("cn-") {
+            Partition::AwsCn
+        } else {
+            Partition::Aws
+        }
+    }
+
+    /// Partition identifier as used in ARNs
+    pub fn id(&self) -> &'static str {
+        match self {
+            Partition::Aws => "aws",
+            Partition::AwsUsGov => "aws-us-gov",
+            Partition::AwsCn => "aws-cn",
+        }
+    }
+
+    /// Region a bucket without a location constraint is in
+    pub fn default_region(&self) -> &'static str {
+        match self {
+            Partition::Aws => "us-east-1",
+            Partition::AwsUsGov => "us-gov-west-1",
+            Partition::AwsCn => "cn-north-1",
+        }
+    }
+
+    /// Regions buckets can be created in
+    pub fn regions(&self) -> &'static [&'static str] {
+        match self {
+            Partition::Aws => AWS_REGIONS,
+            Partition::AwsUsGov => AWS_US_GOV_REGIONS,
+            Partition::AwsCn => AWS_CN_REGIONS,
+        }
+    }
+
+    pub fn bucket_arn(&self, bucket: &str) -> String {
+        format!("arn:{}:s3:::{bucket}", self.id())
+    }
+
+    /// Link to the bucket in the partition's S3 console
+    pub fn console_url(&self, bucket: &str, region: &str) -> String {
+        let host = match self {
+            Partition::Aws => "s3.console.aws.amazon.com",
+            Partition::AwsUsGov => "console.amazonaws-us-gov.com",
+            Partition::AwsCn => "console.amazonaws.cn",
+        };
+        format!("https://{host}/s3/buckets/{bucket}?region={region}")
+    }
+}
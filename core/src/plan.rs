@@ -0,0 +1,271 @@
+use std::collections::HashMap;
+
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+
+use crate::approvals::ApprovalStore;
+use crate::aws::S3Service;
+use crate::mask::{MaskKind, ObjectMask};
+use crate::models::{
+    CopyMode, JobKind, KeyRewrite, ObjectInfo, ObjectLockPlan, RestoreState, RestoreTier,
+    StorageClassTier, restore_label,
+};
+use crate::protect::ProtectedKeys;
+use crate::script;
+
+/// Keys requested per listing page while selecting a plan's objects
+const LIST_PAGE: i32 = 1000;
+
+/// A batch described as data rather than picked in the TUI, for callers such
+/// as the daemon's `submit_plan`. The objects are the bucket's keys under
+/// `prefix` that match `mask`, or exactly `keys` when given.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MigrationPlan {
+    pub bucket: String,
+    #[serde(default)]
+    pub prefix: Option<String>,
+    #[serde(default)]
+    pub mask: Option<ObjectMask>,
+    #[serde(default)]
+    pub keys: Option<Vec<String>>,
+    pub action: PlanAction,
+    /// Change ticket the batch runs under; `tickets.json` may require one
+    #[serde(default)]
+    pub ticket: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PlanAction {
+    Transition {
+        target: StorageClassTier,
+        /// Retention and legal hold to set on each archived object
+        #[serde(default)]
+        lock: ObjectLockPlan,
+    },
+    Restore {
+        days: i32,
+        /// Retrieval tier; S3 rejects Expedited for Deep Archive objects
+        #[serde(default)]
+        tier: RestoreTier,
+    },
+    Copy {
+        dest_bucket: String,
+        #[serde(default)]
+        mode: CopyMode,
+        /// Delete each source object once its copy is verified
+        #[serde(default)]
+        delete_source: bool,
+        /// Bytes per second, when the copy crosses regions; the daemon's
+        /// `--max-bandwidth` applies as well
+        #[serde(default)]
+        max_bandwidth: Option<u64>,
+        /// Prefix to replace in the destination keys
+        #[serde(default)]
+        rewrite: KeyRewrite,
+        /// Class of the copies; S3's default, Standard, when omitted
+        #[serde(default)]
+        storage_class: Option<StorageClassTier>,
+    },
+}
+
+impl PlanAction {
+    pub fn job_kind(&self) -> JobKind {
+        match self {
+            PlanAction::Transition { .. } => JobKind::Transition,
+            PlanAction::Restore { .. } => JobKind::Restore,
+            PlanAction::Copy {
+                delete_source: true,
+                ..
+            } => JobKind::Move,
+            PlanAction::Copy { .. } => JobKind::Copy,
+        }
+    }
+
+    /// The job's target as the journal shows it: a class, a duration or a bucket
+    pub fn target_label(&self) -> String {
+        match self {
+            PlanAction::Transition { target, lock } => transition_label(target, lock),
+            PlanAction::Restore { days, tier } => restore_label(*days, *tier),
+            PlanAction::Copy { dest_bucket, .. } => dest_bucket.clone(),
+        }
+    }
+
+    /// Whether the action would leave `obj` as it is, the way the TUI skips
+    /// objects already in the target class or already restored
+    fn skips(&self, obj: &ObjectInfo) -> bool {
+        match self {
+            PlanAction::Transition { target, .. } => &obj.storage_class == target,
+            PlanAction::Restore { .. } => {
+                matches!(
+                    obj.restore_state,
+                    Some(RestoreState::InProgress { .. } | RestoreState::Available)
+                ) || !matches!(
+                    obj.storage_class,
+                    StorageClassTier::GlacierFlexibleRetrieval
+                        | StorageClassTier::GlacierDeepArchive
+                )
+            }
+            PlanAction::Copy { .. } => false,
+        }
+    }
+}
+
+/// A transition's journal target: the class, plus any Object Lock settings
+pub fn transition_label(target: &StorageClassTier, lock: &ObjectLockPlan) -> String {
+    if lock.is_empty() {
+        target.label().to_string()
+    } else {
+        format!("{} ({})", target.label(), lock.summary())
+    }
+}
+
+/// The objects a plan acts on, and how many it passed over
+pub struct PlanTargets {
+    pub objects: Vec<ObjectInfo>,
+    /// Already in the target state (see `PlanAction::skips`)
+    pub skipped: usize,
+    /// Protected keys left out of a transition or restore. Copies keep them
+    /// and only refuse to delete their source.
+    pub protected: usize,
+}
+
+impl MigrationPlan {
+    pub fn validate(&self) -> Result<()> {
+        if self.bucket.trim().is_empty() {
+            bail!("the plan has no bucket");
+        }
+        match &self.action {
+            PlanAction::Transition { target, .. } if target.to_sdk().is_none() => {
+                bail!("cannot transition to {}", target.label())
+            }
+            PlanAction::Transition { target, lock }
+                if !lock.is_empty() && !target.is_archival() =>
+            {
+                bail!(
+                    "Object Lock settings apply only when archiving to a Glacier class, not {}",
+                    target.label()
+                )
+            }
+            PlanAction::Restore { days, .. } if !(1..=30).contains(days) => {
+                bail!("restore days must be between 1 and 30")
+            }
+            PlanAction::Copy { dest_bucket, .. } if dest_bucket == &self.bucket => {
+                bail!("the destination is the source bucket")
+            }
+            PlanAction::Copy {
+                storage_class: Some(class),
+                ..
+            } if class.to_sdk().is_none() => {
+                bail!("cannot copy into {}", class.label())
+            }
+            _ => {}
+        }
+        if let Some(mask) = &self.mask {
+            if mask.kind == MaskKind::Script {
+                script::predicate(&mask.pattern)?;
+            }
+            if mask.unread_days.is_some() {
+                bail!("plans do not look up last reads, so the mask cannot filter by them");
+            }
+        }
+        Ok(())
+    }
+
+    /// List the plan's objects and pick those it acts on. A plan that would
+    /// migrate keys under a blocked prefix, or with `require_approval` under
+    /// any prefix not approved, is refused; restores only read data back.
+    pub async fn targets(
+        &self,
+        s3: &S3Service,
+        protected: &ProtectedKeys,
+        approvals: &ApprovalStore,
+        require_approval: bool,
+    ) -> Result<PlanTargets> {
+        let mut objects = match &self.keys {
+            Some(keys) => {
+                let mut objects = Vec::with_capacity(keys.len());
+                for key in keys {
+                    objects.push(s3.refresh_object(&self.bucket, key).await?);
+                }
+                objects
+            }
+            None => self.list(s3).await?,
+        };
+        if let Some(mask) = &self.mask {
+            if mask.kind == MaskKind::Script && script::predicate(&mask.pattern)?.uses_tags() {
+                let keys: Vec<String> = objects.iter().map(|obj| obj.key.clone()).collect();
+                let mut tags: HashMap<String, Vec<(String, String)>> = s3
+                    .batch_object_tags(&self.bucket, &keys)
+                    .await
+                    .into_iter()
+                    .collect();
+                for obj in &mut objects {
+                    obj.tags = Some(tags.remove(&obj.key).unwrap_or_default());
+                }
+            }
+            let matcher = mask.matcher();
+            objects.retain(|obj| matcher.matches_object(obj));
+        }
+
+        let listed = objects.len();
+        if !matches!(self.action, PlanAction::Copy { .. }) {
+            objects.retain(|obj| !protected.is_protected(&self.bucket, &obj.key));
+        }
+        let protected = listed - objects.len();
+        // Listings do not report restores; HeadObject does
+        if self.keys.is_none() && matches!(self.action, PlanAction::Restore { .. }) {
+            let keys: Vec<String> = objects
+                .iter()
+                .filter(|obj| !self.action.skips(obj))
+                .map(|obj| obj.key.clone())
+                .collect();
+            let mut states: HashMap<String, (Option<RestoreState>, Option<String>)> = s3
+                .batch_refresh_restore_status(&self.bucket, &keys)
+                .await
+                .into_iter()
+                .map(|(key, state, expiry)| (key, (state, expiry)))
+                .collect();
+            for obj in &mut objects {
+                if let Some((state, expiry)) = states.remove(&obj.key) {
+                    obj.restore_state = state;
+                    obj.restore_expiry = expiry;
+                }
+            }
+        }
+        let before = objects.len();
+        objects.retain(|obj| !self.action.skips(obj));
+        if !approvals.is_empty() && !matches!(self.action, PlanAction::Restore { .. }) {
+            let tally = approvals.tally(&self.bucket, objects.iter().map(|obj| obj.key.as_str()));
+            if let Some(reason) = tally.refusal(require_approval) {
+                bail!("refused: {reason}");
+            }
+        }
+        Ok(PlanTargets {
+            skipped: before - objects.len(),
+            objects,
+            protected,
+        })
+    }
+
+    async fn list(&self, s3: &S3Service) -> Result<Vec<ObjectInfo>> {
+        let mut objects = Vec::new();
+        let mut token = None;
+        loop {
+            let (page, next) = s3
+                .list_objects_paginated(
+                    &self.bucket,
+                    self.prefix.as_deref(),
+                    None,
+                    token,
+                    LIST_PAGE,
+                )
+                .await?;
+            objects.extend(page);
+            match next {
+                Some(next) => token = Some(next),
+                None => return Ok(objects),
+            }
+        }
+    }
+}
@@ -0,0 +1,300 @@
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use crate::approvals::ApprovalStore;
+use crate::aws::S3Service;
+use crate::batch::{ShutdownChoice, transition_object};
+use crate::errors;
+use crate::journal::JobHistory;
+use crate::mask::ObjectMask;
+use crate::models::{ObjectLockPlan, RestoreStatus, RestoreTier, StorageClassTier};
+use crate::multipart::{CopyOutcome, MULTIPART_COPY_THRESHOLD, MultipartStore};
+use crate::plan::{MigrationPlan, PlanAction};
+use crate::protect::ProtectedKeys;
+use crate::tickets::{self, TicketConfig};
+
+/// A saved mask and the class its matches belong in, run against any bucket
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MigrationPolicy {
+    pub name: String,
+    pub mask: ObjectMask,
+    pub target_class: StorageClassTier,
+    /// Request a restore of this many days for archived matches that need
+    /// one, so a later run can transition them; without it they are skipped
+    #[serde(default)]
+    pub restore_days: Option<i32>,
+    /// Retrieval tier of those restores
+    #[serde(default)]
+    pub restore_tier: RestoreTier,
+    pub created_at: String, // ISO 8601 timestamp
+    /// Why the policy exists, noted when it was created; its runs carry it
+    /// into the journal
+    #[serde(default)]
+    pub note: Option<String>,
+    /// Standing change ticket the policy's runs are journaled under
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ticket: Option<String>,
+}
+
+impl MigrationPolicy {
+    /// The batch one run of the policy against `bucket` is
+    pub fn plan(&self, bucket: &str) -> MigrationPlan {
+        MigrationPlan {
+            bucket: bucket.to_string(),
+            prefix: None,
+            mask: Some(self.mask.clone()),
+            keys: None,
+            action: PlanAction::Transition {
+                target: self.target_class.clone(),
+                lock: ObjectLockPlan::default(),
+            },
+            ticket: self.ticket.clone(),
+        }
+    }
+}
+
+/// Saved policies, persisted in `policies.json`
+#[derive(Default)]
+pub struct PolicyStore {
+    file_path: PathBuf,
+    policies: Vec<MigrationPolicy>,
+}
+
+impl PolicyStore {
+    pub fn new() -> Result<Self> {
+        let config_dir = directories::ProjectDirs::from("com", "bucket-brigade", "bucket-brigade")
+            .map(|dirs| dirs.config_dir().to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        fs::create_dir_all(&config_dir)?;
+        let file_path = config_dir.join("policies.json");
+
+        let policies = if file_path.exists() {
+            let content = fs::read_to_string(&file_path)?;
+            serde_json::from_str(&content).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            file_path,
+            policies,
+        })
+    }
+
+    pub fn policies(&self) -> &[MigrationPolicy] {
+        &self.policies
+    }
+
+    pub fn get(&self, name: &str) -> Option<&MigrationPolicy> {
+        self.policies.iter().find(|policy| policy.name == name)
+    }
+
+    /// Save `policy`, replacing any policy of the same name
+    pub fn save_policy(&mut self, policy: MigrationPolicy) -> Result<()> {
+        match self.policies.iter_mut().find(|p| p.name == policy.name) {
+            Some(existing) => *existing = policy,
+            None => self.policies.push(policy),
+        }
+        self.save()
+    }
+
+    pub fn remove(&mut self, name: &str) -> Result<Option<MigrationPolicy>> {
+        let Some(index) = self.policies.iter().position(|p| p.name == name) else {
+            return Ok(None);
+        };
+        let policy = self.policies.remove(index);
+        self.save()?;
+        Ok(Some(policy))
+    }
+
+    fn save(&self) -> Result<()> {
+        let json = serde_json::to_string_pretty(&self.policies)?;
+        fs::write(&self.file_path, json)?;
+        Ok(())
+    }
+}
+
+/// How a policy run is going, reported after each object
+pub struct PolicyProgress<'a> {
+    pub done: usize,
+    pub total: usize,
+    pub key: &'a str,
+    /// Why the object failed, if it did
+    pub error: Option<&'a str>,
+}
+
+/// What a policy run did
+#[derive(Clone, Debug, Default)]
+pub struct PolicySummary {
+    /// Journal id of the run, when it transitioned anything
+    pub job_id: Option<String>,
+    /// Objects the mask matched, over the whole bucket
+    pub matched: usize,
+    pub already_in_class: usize,
+    pub protected: usize,
+    /// Archived matches that need a restore first and were left alone
+    pub needs_restore: usize,
+    pub restores_requested: usize,
+    pub transitioned: usize,
+    pub bytes: u64,
+    /// Bytes transitioned, by the class they left
+    pub moved_from: Vec<(StorageClassTier, u64)>,
+    /// (key, why) per object that failed
+    pub failed: Vec<(String, String)>,
+    /// The run was stopped before its last object
+    pub interrupted: bool,
+}
+
+/// Run `policy` against every matching object of `bucket`: list the whole
+/// bucket, skip what is already in the target class or protected, restore
+/// or skip archived objects, and transition the rest one at a time.
+/// `stop` is checked before each object, as in a TUI batch. The sign-off is
+/// checked as for a daemon plan, so a blocked prefix refuses the whole run.
+#[allow(clippy::too_many_arguments)]
+pub async fn run(
+    s3: &S3Service,
+    policy: &MigrationPolicy,
+    bucket: &str,
+    history: &mut JobHistory,
+    uploads: &mut MultipartStore,
+    stop: &Cell<Option<ShutdownChoice>>,
+    require_approval: bool,
+    mut progress: impl FnMut(PolicyProgress),
+) -> Result<PolicySummary> {
+    let plan = policy.plan(bucket);
+    plan.validate()?;
+    let ticket = tickets::resolve(TicketConfig::load()?.as_ref(), plan.ticket.as_deref()).await?;
+    if let Some(days) = policy.restore_days
+        && !(1..=30).contains(&days)
+    {
+        bail!("restore days must be between 1 and 30");
+    }
+    let protected = ProtectedKeys::new()?;
+    let approvals = ApprovalStore::new()?;
+    let targets = plan
+        .targets(s3, &protected, &approvals, require_approval)
+        .await?;
+    let mut summary = PolicySummary {
+        matched: targets.objects.len() + targets.skipped + targets.protected,
+        already_in_class: targets.skipped,
+        protected: targets.protected,
+        ..PolicySummary::default()
+    };
+
+    // Listings do not report restores, so archived matches are looked up
+    let archived: Vec<String> = targets
+        .objects
+        .iter()
+        .filter(|obj| RestoreStatus::of(obj).is_some())
+        .map(|obj| obj.key.clone())
+        .collect();
+    let states: HashMap<String, _> = s3
+        .batch_refresh_restore_status(bucket, &archived)
+        .await
+        .into_iter()
+        .map(|(key, state, _)| (key, state))
+        .collect();
+    let mut objects = Vec::with_capacity(targets.objects.len());
+    let mut restores = Vec::new();
+    for mut obj in targets.objects {
+        if let Some(state) = states.get(&obj.key) {
+            obj.restore_state = state.clone();
+        }
+        match RestoreStatus::of(&obj) {
+            None | Some(RestoreStatus::Restored) => objects.push(obj),
+            Some(RestoreStatus::Restoring) => summary.needs_restore += 1,
+            Some(RestoreStatus::NeedsRestore | RestoreStatus::Expired) => {
+                summary.needs_restore += 1;
+                restores.push(obj.key);
+            }
+        }
+    }
+    if let Some(days) = policy.restore_days {
+        for key in restores {
+            match s3
+                .request_restore(bucket, &key, days, policy.restore_tier)
+                .await
+            {
+                Ok(()) => summary.restores_requested += 1,
+                Err(err) => summary.failed.push((key, errors::describe(&err))),
+            }
+        }
+    }
+    if objects.is_empty() {
+        return Ok(summary);
+    }
+
+    let total = objects.len();
+    let job_id = history.begin(
+        plan.action.job_kind(),
+        bucket,
+        &plan.action.target_label(),
+        total,
+        policy.note.clone(),
+        ticket,
+    );
+    for (index, obj) in objects.iter().enumerate() {
+        if stop.get().is_some() {
+            summary.interrupted = true;
+            break;
+        }
+        let sent = Instant::now();
+        let result = transition_object(
+            s3,
+            uploads,
+            bucket,
+            &obj.key,
+            obj.size > MULTIPART_COPY_THRESHOLD,
+            policy.target_class.clone(),
+            stop,
+        )
+        .await;
+        history.time_request(&job_id, sent.elapsed());
+        let error = match result {
+            Ok(CopyOutcome::Completed) => {
+                history.record(&job_id, &obj.key, None);
+                let bytes = obj.size.max(0) as u64;
+                summary.transitioned += 1;
+                summary.bytes += bytes;
+                match summary
+                    .moved_from
+                    .iter_mut()
+                    .find(|(class, _)| *class == obj.storage_class)
+                {
+                    Some((_, moved)) => *moved += bytes,
+                    None => summary.moved_from.push((obj.storage_class.clone(), bytes)),
+                }
+                None
+            }
+            Ok(CopyOutcome::Checkpointed) => {
+                summary.interrupted = true;
+                break;
+            }
+            Err(err) => {
+                let reason = errors::describe(&err);
+                history.record(&job_id, &obj.key, Some(reason.clone()));
+                summary.failed.push((obj.key.clone(), reason));
+                summary.failed.last().map(|(_, reason)| reason.as_str())
+            }
+        };
+        progress(PolicyProgress {
+            done: index + 1,
+            total,
+            key: &obj.key,
+            error,
+        });
+    }
+    if summary.interrupted {
+        history.interrupt(&job_id);
+    } else {
+        history.finish(&job_id);
+    }
+    summary.job_id = Some(job_id);
+    Ok(summary)
+}
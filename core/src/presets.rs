@@ -0,0 +1,151 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::models::RestoreTier;
+
+/// A vetted set of restore settings, picked in the restore dialog instead of
+/// choosing the tier, duration and pacing by hand
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RestorePreset {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub tier: RestoreTier,
+    pub days: i32,
+    /// Requests in flight at once; the session's `--restore-concurrency`
+    /// when unset
+    #[serde(default)]
+    pub concurrency: Option<usize>,
+    /// Requests started per second; the session's `--restore-rate` when unset
+    #[serde(default)]
+    pub per_second: Option<u32>,
+    /// Announce each restored object on the restore webhook and in Slack
+    #[serde(default = "default_notify")]
+    pub notify_ready: bool,
+}
+
+fn default_notify() -> bool {
+    true
+}
+
+impl RestorePreset {
+    /// "Expedited, 1 day, 32 in flight, notify"
+    pub fn summary(&self) -> String {
+        let mut parts = vec![
+            self.tier.label().to_string(),
+            match self.days {
+                1 => "1 day".to_string(),
+                days => format!("{days} days"),
+            },
+        ];
+        if let Some(concurrency) = self.concurrency {
+            parts.push(format!("{concurrency} in flight"));
+        }
+        if let Some(rate) = self.per_second {
+            parts.push(format!("{rate}/s"));
+        }
+        parts.push(if self.notify_ready { "notify" } else { "quiet" }.to_string());
+        parts.join(", ")
+    }
+}
+
+/// The presets offered when `restore_presets.json` does not exist
+pub fn builtin_presets() -> Vec<RestorePreset> {
+    vec![
+        RestorePreset {
+            name: "Emergency recall".to_string(),
+            description: "Data needed within minutes; Expedited is charged per GB".to_string(),
+            tier: RestoreTier::Expedited,
+            days: 1,
+            concurrency: Some(32),
+            per_second: None,
+            notify_ready: true,
+        },
+        RestorePreset {
+            name: "Planned batch recall".to_string(),
+            description: "Scheduled work that can wait a few hours".to_string(),
+            tier: RestoreTier::Standard,
+            days: 7,
+            concurrency: Some(8),
+            per_second: None,
+            notify_ready: true,
+        },
+        RestorePreset {
+            name: "Archive audit".to_string(),
+            description: "Bulk read-through of old data; cheapest, up to two days".to_string(),
+            tier: RestoreTier::Bulk,
+            days: 14,
+            concurrency: Some(4),
+            per_second: Some(10),
+            notify_ready: false,
+        },
+    ]
+}
+
+/// Restore presets, read from `restore_presets.json` (a list of presets) so
+/// a team can share the ones it has vetted. The built-in presets stand in
+/// until the file exists.
+#[derive(Default)]
+pub struct RestorePresets {
+    presets: Vec<RestorePreset>,
+    /// Read from the file rather than built in
+    pub from_file: bool,
+}
+
+impl RestorePresets {
+    pub fn new() -> Result<Self> {
+        let config_dir = directories::ProjectDirs::from("com", "bucket-brigade", "bucket-brigade")
+            .map(|dirs| dirs.config_dir().to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        fs::create_dir_all(&config_dir)?;
+        let file_path = config_dir.join("restore_presets.json");
+
+        if file_path.exists() {
+            let content = fs::read_to_string(&file_path)?;
+            let presets: Vec<RestorePreset> = serde_json::from_str(&content).unwrap_or_default();
+            Ok(Self {
+                presets: presets
+                    .into_iter()
+                    .filter(|preset| !preset.name.is_empty() && preset.days > 0)
+                    .collect(),
+                from_file: true,
+            })
+        } else {
+            Ok(Self {
+                presets: builtin_presets(),
+                from_file: false,
+            })
+        }
+    }
+
+    pub fn presets(&self) -> &[RestorePreset] {
+        &self.presets
+    }
+
+    pub fn get(&self, name: &str) -> Option<&RestorePreset> {
+        self.presets.iter().find(|preset| preset.name == name)
+    }
+
+    /// The preset after (or before) `current`, wrapping through "none"
+    /// unless `required`
+    pub fn cycle(&self, current: Option<&str>, forward: bool, required: bool) -> Option<&str> {
+        let names: Vec<Option<&str>> = (!required)
+            .then_some(None)
+            .into_iter()
+            .chain(self.presets.iter().map(|preset| Some(preset.name.as_str())))
+            .collect();
+        if names.is_empty() {
+            return None;
+        }
+        let at = names.iter().position(|name| *name == current).unwrap_or(0);
+        let next = if forward {
+            (at + 1) % names.len()
+        } else {
+            (at + names.len() - 1) % names.len()
+        };
+        names[next]
+    }
+}
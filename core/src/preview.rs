@@ -0,0 +1,161 @@
+use std::collections::{BTreeMap, HashSet};
+
+/// Keys listed under one expanded group before the rest are summarised
+const KEYS_PER_GROUP: usize = 50;
+
+/// The keys of a pending action grouped by `/`-delimited prefix, so the
+/// structure of a selection can be reviewed before it is confirmed
+pub struct TargetPreview {
+    root: PreviewNode,
+    expanded: HashSet<String>,
+    pub cursor: usize,
+}
+
+#[derive(Default)]
+struct PreviewNode {
+    count: usize,
+    bytes: i64,
+    children: BTreeMap<String, PreviewNode>,
+    /// Keys that end at this level, with their sizes
+    keys: Vec<(String, i64)>,
+}
+
+pub enum PreviewRowKind {
+    Group {
+        expanded: bool,
+    },
+    Key,
+    /// Keys of the group not listed individually
+    More(usize),
+}
+
+pub struct PreviewRow {
+    /// Full prefix of a group (with trailing `/`) or full key
+    pub path: String,
+    /// Last path segment, as shown in the tree
+    pub name: String,
+    pub depth: usize,
+    pub count: usize,
+    pub bytes: i64,
+    pub kind: PreviewRowKind,
+}
+
+impl TargetPreview {
+    pub fn new<'a>(objects: impl IntoIterator<Item = (&'a str, i64)>) -> Self {
+        let mut root = PreviewNode::default();
+        for (key, size) in objects {
+            let mut node = &mut root;
+            node.count += 1;
+            node.bytes += size;
+            let mut segments = key.split_inclusive('/').peekable();
+            while let Some(segment) = segments.next() {
+                if segments.peek().is_none() && !segment.ends_with('/') {
+                    node.keys.push((key.to_string(), size));
+                    break;
+                }
+                node = node.children.entry(segment.to_string()).or_default();
+                node.count += 1;
+                node.bytes += size;
+            }
+        }
+        Self {
+            root,
+            expanded: HashSet::new(),
+            cursor: 0,
+        }
+    }
+
+    pub fn total(&self) -> (usize, i64) {
+        (self.root.count, self.root.bytes)
+    }
+
+    /// Rows of the tree as currently expanded; top-level groups are always shown
+    pub fn rows(&self) -> Vec<PreviewRow> {
+        let mut rows = Vec::new();
+        self.push_rows(&self.root, "", 0, &mut rows);
+        rows
+    }
+
+    fn push_rows(
+        &self,
+        node: &PreviewNode,
+        prefix: &str,
+        depth: usize,
+        rows: &mut Vec<PreviewRow>,
+    ) {
+        for (segment, child) in &node.children {
+            let path = format!("{prefix}{segment}");
+            let expanded = self.expanded.contains(&path);
+            rows.push(PreviewRow {
+                path: path.clone(),
+                name: segment.clone(),
+                depth,
+                count: child.count,
+                bytes: child.bytes,
+                kind: PreviewRowKind::Group { expanded },
+            });
+            if expanded {
+                self.push_rows(child, &path, depth + 1, rows);
+            }
+        }
+        for (key, size) in node.keys.iter().take(KEYS_PER_GROUP) {
+            rows.push(PreviewRow {
+                path: key.clone(),
+                name: key[prefix.len()..].to_string(),
+                depth,
+                count: 1,
+                bytes: *size,
+                kind: PreviewRowKind::Key,
+            });
+        }
+        if node.keys.len() > KEYS_PER_GROUP {
+            let rest = &node.keys[KEYS_PER_GROUP..];
+            rows.push(PreviewRow {
+                path: prefix.to_string(),
+                name: String::new(),
+                depth,
+                count: rest.len(),
+                bytes: rest.iter().map(|(_, size)| size).sum(),
+                kind: PreviewRowKind::More(rest.len()),
+            });
+        }
+    }
+
+    pub fn move_cursor(&mut self, delta: isize) {
+        let last = self.rows().len().saturating_sub(1) as isize;
+        self.cursor = (self.cursor as isize + delta).clamp(0, last) as usize;
+    }
+
+    /// Expand the group under the cursor, or collapse it if it is expanded
+    pub fn toggle(&mut self) {
+        if let Some(PreviewRow {
+            path,
+            kind: PreviewRowKind::Group { .. },
+            ..
+        }) = self.rows().into_iter().nth(self.cursor)
+            && !self.expanded.remove(&path)
+        {
+            self.expanded.insert(path);
+        }
+    }
+
+    /// Collapse the group under the cursor, or the group containing it
+    pub fn collapse(&mut self) {
+        let rows = self.rows();
+        let Some(row) = rows.get(self.cursor) else {
+            return;
+        };
+        if matches!(row.kind, PreviewRowKind::Group { expanded: true }) {
+            self.expanded.remove(&row.path);
+            return;
+        }
+        let Some(parent) = rows[..self.cursor]
+            .iter()
+            .rposition(|r| r.depth + 1 == row.depth)
+        else {
+            return;
+        };
+        self.expanded.remove(&rows[parent].path);
+        self.cursor = parent;
+    }
+}
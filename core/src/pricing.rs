@@ -0,0 +1,123 @@
+use crate::models::StorageClassTier;
+use crate::partition::Partition;
+
+/// Published S3 storage list prices in USD per GB-month for the first 50 TB
+/// tier, in the order STANDARD, INTELLIGENT_TIERING (frequent access),
+/// STANDARD_IA, ONEZONE_IA, GLACIER_IR, GLACIER, DEEP_ARCHIVE.
+/// These change rarely but do change; treat them as guidance, not a quote.
+const REGION_PRICES: &[(&str, [f64; 7])] = &[
+    (
+        "us-east-1",
+        [0.023, 0.023, 0.0125, 0.01, 0.004, 0.0036, 0.00099],
+    ),
+    (
+        "us-east-2",
+        [0.023, 0.023, 0.0125, 0.01, 0.004, 0.0036, 0.00099],
+    ),
+    (
+        "us-west-1",
+        [0.026, 0.026, 0.019, 0.0152, 0.005, 0.0045, 0.002],
+    ),
+    (
+        "us-west-2",
+        [0.023, 0.023, 0.0125, 0.01, 0.004, 0.0036, 0.00099],
+    ),
+    (
+        "ca-central-1",
+        [0.025, 0.025, 0.0138, 0.011, 0.0045, 0.0040, 0.0011],
+    ),
+    (
+        "eu-west-1",
+        [0.023, 0.023, 0.0125, 0.01, 0.004, 0.0036, 0.00099],
+    ),
+    (
+        "eu-west-2",
+        [0.024, 0.024, 0.0131, 0.0105, 0.0045, 0.0040, 0.0018],
+    ),
+    (
+        "eu-central-1",
+        [0.0245, 0.0245, 0.0135, 0.0108, 0.005, 0.0045, 0.0018],
+    ),
+    (
+        "ap-northeast-1",
+        [0.025, 0.025, 0.0138, 0.011, 0.005, 0.0045, 0.002],
+    ),
+    (
+        "ap-southeast-1",
+        [0.025, 0.025, 0.0138, 0.011, 0.005, 0.0045, 0.002],
+    ),
+    (
+        "ap-southeast-2",
+        [0.025, 0.025, 0.0138, 0.011, 0.005, 0.0045, 0.00099],
+    ),
+    (
+        "us-gov-west-1",
+        [0.039, 0.039, 0.02, 0.016, 0.005, 0.0054, 0.002],
+    ),
+    (
+        "us-gov-east-1",
+        [0.039, 0.039, 0.02, 0.016, 0.005, 0.0054, 0.002],
+    ),
+];
+
+/// Monthly storage price per GB for a class, and the region the price is for.
+/// A region missing from the table gets its partition's default region's
+/// prices; China is priced in CNY and is not in the table.
+pub fn monthly_price_per_gb(
+    region: Option<&str>,
+    class: &StorageClassTier,
+) -> Option<(f64, &'static str)> {
+    let index = match class {
+        StorageClassTier::Standard => 0,
+        StorageClassTier::IntelligentTiering => 1,
+        StorageClassTier::StandardIa => 2,
+        StorageClassTier::OneZoneIa => 3,
+        StorageClassTier::GlacierInstantRetrieval => 4,
+        StorageClassTier::GlacierFlexibleRetrieval => 5,
+        StorageClassTier::GlacierDeepArchive => 6,
+        StorageClassTier::ReducedRedundancy | StorageClassTier::Unknown(_) => return None,
+    };
+    let fallback = Partition::of_region(region.unwrap_or_default()).default_region();
+    let (name, prices) = region
+        .and_then(|region| REGION_PRICES.iter().find(|(name, _)| *name == region))
+        .or_else(|| REGION_PRICES.iter().find(|(name, _)| *name == fallback))?;
+    Some((prices[index], name))
+}
+
+/// Monthly storage cost saved by moving `bytes` from one class to another,
+/// at list prices; negative when the target costs more
+pub fn monthly_savings(
+    region: Option<&str>,
+    from: &StorageClassTier,
+    to: &StorageClassTier,
+    bytes: u64,
+) -> Option<f64> {
+    let (from, _) = monthly_price_per_gb(region, from)?;
+    let (to, _) = monthly_price_per_gb(region, to)?;
+    Some(bytes as f64 / (1024.0 * 1024.0 * 1024.0) * (from - to))
+}
+
+/// Price per 1,000 PUT/COPY requests writing into a class (us-east-1). A
+/// client-side transition is one CopyObject billed at the target's rate.
+pub fn copy_price_per_1000(class: &StorageClassTier) -> f64 {
+    match class {
+        StorageClassTier::StandardIa | StorageClassTier::OneZoneIa => 0.01,
+        StorageClassTier::GlacierInstantRetrieval => 0.02,
+        StorageClassTier::GlacierFlexibleRetrieval => 0.03,
+        StorageClassTier::GlacierDeepArchive => 0.05,
+        _ => 0.005,
+    }
+}
+
+/// Price per 1,000 lifecycle transition requests into a class (us-east-1)
+pub fn transition_price_per_1000(class: &StorageClassTier) -> f64 {
+    match class {
+        StorageClassTier::IntelligentTiering
+        | StorageClassTier::StandardIa
+        | StorageClassTier::OneZoneIa => 0.01,
+        StorageClassTier::GlacierInstantRetrieval => 0.02,
+        StorageClassTier::GlacierFlexibleRetrieval => 0.03,
+        StorageClassTier::GlacierDeepArchive => 0.05,
+        _ => 0.0,
+    }
+}
@@ -0,0 +1,86 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// A key or key pattern that bulk actions never touch
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProtectedKey {
+    pub bucket: String,
+    /// Exact key, or a prefix ending in `*`
+    pub pattern: String,
+    pub added_at: String, // ISO 8601 timestamp
+}
+
+impl ProtectedKey {
+    pub fn matches(&self, bucket: &str, key: &str) -> bool {
+        self.bucket == bucket
+            && match self.pattern.strip_suffix('*') {
+                Some(prefix) => key.starts_with(prefix),
+                None => self.pattern == key,
+            }
+    }
+}
+
+/// Protected keys, persisted in `protected_keys.json`
+#[derive(Default)]
+pub struct ProtectedKeys {
+    file_path: PathBuf,
+    entries: Vec<ProtectedKey>,
+}
+
+impl ProtectedKeys {
+    pub fn new() -> Result<Self> {
+        let config_dir = directories::ProjectDirs::from("com", "bucket-brigade", "bucket-brigade")
+            .map(|dirs| dirs.config_dir().to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        fs::create_dir_all(&config_dir)?;
+        let file_path = config_dir.join("protected_keys.json");
+
+        let entries = if file_path.exists() {
+            let content = fs::read_to_string(&file_path)?;
+            serde_json::from_str(&content).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self { file_path, entries })
+    }
+
+    pub fn is_protected(&self, bucket: &str, key: &str) -> bool {
+        self.entries.iter().any(|entry| entry.matches(bucket, key))
+    }
+
+    /// Patterns protecting keys of `bucket`, in the order they were added
+    pub fn patterns<'a>(&'a self, bucket: &'a str) -> impl Iterator<Item = &'a str> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.bucket == bucket)
+            .map(|entry| entry.pattern.as_str())
+    }
+
+    /// Protect `pattern` in `bucket`, or lift the protection if it is already
+    /// listed. Returns whether the pattern is now protected.
+    pub fn toggle(&mut self, bucket: &str, pattern: &str) -> bool {
+        let before = self.entries.len();
+        self.entries
+            .retain(|entry| !(entry.bucket == bucket && entry.pattern == pattern));
+        let protected = self.entries.len() == before;
+        if protected {
+            self.entries.push(ProtectedKey {
+                bucket: bucket.to_string(),
+                pattern: pattern.to_string(),
+                added_at: chrono::Utc::now().to_rfc3339(),
+            });
+        }
+        let _ = self.save();
+        protected
+    }
+
+    fn save(&self) -> Result<()> {
+        let json = serde_json::to_string_pretty(&self.entries)?;
+        fs::write(&self.file_path, json)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,370 @@
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use futures::stream::{self, StreamExt};
+
+use crate::athena;
+use crate::aws::S3Service;
+use crate::coldness::{Activity, Coldness};
+
+/// Days of logs read when `--access-lookback` is not given
+const DEFAULT_LOOKBACK_DAYS: i64 = 90;
+/// Log objects read at once
+const LOG_READ_CONCURRENCY: usize = 16;
+
+/// Where read times come from
+#[derive(Clone, Debug)]
+pub enum RecencySource {
+    /// S3 server access logs under `prefix` in `bucket`; `{bucket}` in the
+    /// prefix stands for the bucket looked up
+    AccessLogs { bucket: String, prefix: String },
+    /// CloudTrail data events in an Athena table, queried with the
+    /// `--athena-workgroup` and `--athena-output` settings
+    CloudTrail {
+        database: String,
+        table: String,
+        workgroup: String,
+        output_location: Option<String>,
+        sdk: crate::aws::SdkOptions,
+    },
+}
+
+/// How last-read times are looked up (`--access-logs s3://bucket/prefix` or
+/// `--cloudtrail-table <database>.<table>`, with `--access-lookback DAYS`)
+#[derive(Clone, Debug)]
+pub struct RecencyConfig {
+    pub source: RecencySource,
+    pub lookback_days: i64,
+}
+
+impl RecencyConfig {
+    /// `None` unless a log location or a CloudTrail table is given
+    pub fn from_args(args: &[String]) -> Option<Self> {
+        let value = |flag: &str| {
+            args.windows(2)
+                .find(|pair| pair[0] == flag)
+                .map(|pair| pair[1].clone())
+        };
+        let source = if let Some(location) = value("--access-logs") {
+            let rest = location.strip_prefix("s3://").unwrap_or(&location);
+            let (bucket, prefix) = rest.split_once('/').unwrap_or((rest, ""));
+            if bucket.is_empty() {
+                return None;
+            }
+            RecencySource::AccessLogs {
+                bucket: bucket.to_string(),
+                prefix: prefix.to_string(),
+            }
+        } else {
+            let (database, table) = value("--cloudtrail-table")?
+                .split_once('.')
+                .map(|(d, t)| (d.to_string(), t.to_string()))?;
+            RecencySource::CloudTrail {
+                database,
+                table,
+                workgroup: value("--athena-workgroup").unwrap_or_else(|| "primary".to_string()),
+                output_location: value("--athena-output"),
+                sdk: crate::aws::SdkOptions::from_args(args),
+            }
+        };
+        let lookback_days = value("--access-lookback")
+            .and_then(|days| days.parse().ok())
+            .filter(|days| *days > 0)
+            .unwrap_or(DEFAULT_LOOKBACK_DAYS);
+        Some(Self {
+            source,
+            lookback_days,
+        })
+    }
+
+    /// "access logs in s3://logs/bucket-a/" or "CloudTrail table trails.events"
+    pub fn label(&self, bucket: &str) -> String {
+        match &self.source {
+            RecencySource::AccessLogs {
+                bucket: logs,
+                prefix,
+            } => format!(
+                "access logs in s3://{logs}/{}",
+                prefix.replace("{bucket}", bucket)
+            ),
+            RecencySource::CloudTrail {
+                database, table, ..
+            } => format!("CloudTrail table {database}.{table}"),
+        }
+    }
+
+    /// Count the GETs and HEADs of each key of `bucket` within the lookback
+    pub async fn lookup(&self, s3: &S3Service, bucket: &str) -> Result<LastReads> {
+        let since = Utc::now() - Duration::days(self.lookback_days);
+        let (reads, sources) = match &self.source {
+            RecencySource::AccessLogs {
+                bucket: logs,
+                prefix,
+            } => {
+                let prefix = prefix.replace("{bucket}", bucket);
+                read_access_logs(s3, logs, &prefix, bucket, since).await?
+            }
+            RecencySource::CloudTrail {
+                database,
+                table,
+                workgroup,
+                output_location,
+                sdk,
+            } => {
+                let query = athena::AthenaQuery {
+                    database,
+                    workgroup,
+                    output_location: output_location.as_deref(),
+                    sdk,
+                };
+                let reads = athena::query_last_reads(&query, table, bucket, since).await?;
+                (reads, 0)
+            }
+        };
+        Ok(LastReads::new(
+            bucket.to_string(),
+            self.label(bucket),
+            since,
+            reads,
+            sources,
+        ))
+    }
+}
+
+/// When the keys of one bucket were last read and how often they were
+/// requested, as far as the logs go back
+#[derive(Clone, Debug)]
+pub struct LastReads {
+    pub bucket: String,
+    /// Where the reads were found, for the status line
+    pub source: String,
+    /// Start of the lookback; a key not read since then shows as unread
+    pub since: DateTime<Utc>,
+    /// Log objects read, for access logs
+    pub log_objects: usize,
+    reads: HashMap<String, Activity>,
+    /// Requests under each `/`-delimited prefix, the bucket root included
+    prefixes: HashMap<String, Activity>,
+}
+
+impl LastReads {
+    pub fn new(
+        bucket: String,
+        source: String,
+        since: DateTime<Utc>,
+        reads: HashMap<String, Activity>,
+        log_objects: usize,
+    ) -> Self {
+        let mut prefixes: HashMap<String, Activity> = HashMap::new();
+        for (key, activity) in &reads {
+            let ends = std::iter::once(0).chain(key.match_indices('/').map(|(i, _)| i + 1));
+            for end in ends {
+                prefixes
+                    .entry(key[..end].to_string())
+                    .or_default()
+                    .merge(activity);
+            }
+        }
+        Self {
+            bucket,
+            source,
+            since,
+            log_objects,
+            reads,
+            prefixes,
+        }
+    }
+
+    /// Keys requested within the lookback
+    pub fn len(&self) -> usize {
+        self.reads.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reads.is_empty()
+    }
+
+    /// Requests of every key, summed
+    pub fn totals(&self) -> Activity {
+        self.prefixes.get("").copied().unwrap_or_default()
+    }
+
+    pub fn of_key(&self, key: &str) -> Option<DateTime<Utc>> {
+        self.activity_of(key).last_read
+    }
+
+    pub fn activity_of(&self, key: &str) -> Activity {
+        self.reads.get(key).copied().unwrap_or_default()
+    }
+
+    /// Latest read of any key under `prefix`
+    pub fn under(&self, prefix: &str) -> Option<DateTime<Utc>> {
+        self.activity_under(prefix).last_read
+    }
+
+    /// Requests of the keys under `prefix`. Prefixes ending in `/` are
+    /// looked up directly; others are scanned.
+    pub fn activity_under(&self, prefix: &str) -> Activity {
+        if prefix.is_empty() || prefix.ends_with('/') {
+            return self.prefixes.get(prefix).copied().unwrap_or_default();
+        }
+        let mut activity = Activity::default();
+        for (_, requests) in self.reads.iter().filter(|(key, _)| key.starts_with(prefix)) {
+            activity.merge(requests);
+        }
+        activity
+    }
+
+    /// How cold the keys under `prefix` are
+    pub fn coldness_under(&self, prefix: &str) -> Coldness {
+        Coldness::of(&self.activity_under(prefix), self.since, None, Utc::now())
+    }
+
+    /// Whether the lookback reaches back `days`, so that a key it saw no
+    /// read of has gone unread for at least that long
+    pub fn covers_days(&self, days: u32) -> bool {
+        Utc::now() - self.since >= Duration::days(i64::from(days))
+    }
+
+    /// "read 3d ago", "read today" or "unread 90d"
+    pub fn label(&self, read: Option<DateTime<Utc>>) -> String {
+        let now = Utc::now();
+        match read {
+            Some(at) => match (now - at).num_days() {
+                0 => "read today".to_string(),
+                days => format!("read {days}d ago"),
+            },
+            None => format!("unread {}d", (now - self.since).num_days()),
+        }
+    }
+}
+
+/// One request of an S3 server access log
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccessLogRecord {
+    pub bucket: String,
+    pub time: DateTime<Utc>,
+    pub operation: String,
+    /// URL-decoded key, `None` for requests without one
+    pub key: Option<String>,
+    pub status: u16,
+}
+
+impl AccessLogRecord {
+    /// A successful GetObject, whole or ranged. Copies that read the object
+    /// (`REST.COPY.OBJECT_GET`) are left out so a migration does not count
+    /// as use.
+    pub fn is_read(&self) -> bool {
+        self.operation == "REST.GET.OBJECT" && matches!(self.status, 200 | 206)
+    }
+
+    /// A successful HeadObject
+    pub fn is_head(&self) -> bool {
+        self.operation == "REST.HEAD.OBJECT" && self.status == 200
+    }
+}
+
+/// Parse one line of the server access log format: space-separated fields,
+/// with the time in brackets and the request URI and user agent quoted
+pub fn parse_access_log_line(line: &str) -> Option<AccessLogRecord> {
+    let mut fields = Vec::with_capacity(10);
+    let mut rest = line.trim_start();
+    while fields.len() < 10 && !rest.is_empty() {
+        let (field, tail) = match rest.as_bytes()[0] {
+            b'[' => {
+                let end = rest.find(']')?;
+                (&rest[1..end], &rest[end + 1..])
+            }
+            b'"' => {
+                let end = rest[1..].find('"')? + 1;
+                (&rest[1..end], &rest[end + 1..])
+            }
+            _ => rest.split_at(rest.find(' ').unwrap_or(rest.len())),
+        };
+        fields.push(field);
+        rest = tail.trim_start();
+    }
+    // owner, bucket, time, remote IP, requester, request id, operation, key,
+    // request URI, status
+    if fields.len() < 10 {
+        return None;
+    }
+    let time = DateTime::parse_from_str(fields[2], "%d/%b/%Y:%H:%M:%S %z")
+        .ok()?
+        .with_timezone(&Utc);
+    let key = match fields[7] {
+        "-" => None,
+        key => Some(urlencoding::decode(key).map_or_else(|_| key.to_string(), |k| k.into_owned())),
+    };
+    Some(AccessLogRecord {
+        bucket: fields[1].to_string(),
+        time,
+        operation: fields[6].to_string(),
+        key,
+        status: fields[9].parse().unwrap_or_default(),
+    })
+}
+
+/// Read the log objects under `prefix` written since `since`, counting the
+/// GETs and HEADs of each key of `bucket`. Returns the counts and how many
+/// log objects were read.
+async fn read_access_logs(
+    s3: &S3Service,
+    logs: &str,
+    prefix: &str,
+    bucket: &str,
+    since: DateTime<Utc>,
+) -> Result<(HashMap<String, Activity>, usize)> {
+    let mut log_keys = Vec::new();
+    let mut token = None;
+    loop {
+        let (page, next) = s3
+            .list_objects_paginated(logs, Some(prefix), None, token, 1000)
+            .await
+            .with_context(|| format!("listing s3://{logs}/{prefix}"))?;
+        // A log object is written shortly after its last request
+        log_keys.extend(
+            page.into_iter()
+                .filter(|obj| {
+                    obj.last_modified
+                        .as_deref()
+                        .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+                        .is_none_or(|at| at >= since)
+                })
+                .map(|obj| obj.key),
+        );
+        token = next;
+        if token.is_none() {
+            break;
+        }
+    }
+
+    let count = log_keys.len();
+    let mut texts = stream::iter(log_keys)
+        .map(|key| async move {
+            s3.get_object_text(logs, &key)
+                .await
+                .with_context(|| format!("reading s3://{logs}/{key}"))
+        })
+        .buffer_unordered(LOG_READ_CONCURRENCY);
+    let mut reads: HashMap<String, Activity> = HashMap::new();
+    while let Some(text) = texts.next().await {
+        for record in text?.lines().filter_map(parse_access_log_line) {
+            if record.bucket != bucket || record.time < since {
+                continue;
+            }
+            let (read, head) = (record.is_read(), record.is_head());
+            let Some(key) = record.key.filter(|_| read || head) else {
+                continue;
+            };
+            let activity = reads.entry(key).or_default();
+            if read {
+                activity.record_get(record.time);
+            } else {
+                activity.record_head();
+            }
+        }
+    }
+    Ok((reads, count))
+}
@@ -0,0 +1,382 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, VecDeque};
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+
+use anyhow::Result;
+use chrono::Utc;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::aws::S3Service;
+use crate::models::{ObjectInfo, StorageClassTier};
+
+/// Keys requested per listing page on each side
+const PAGE_SIZE: i32 = 1000;
+
+/// Findings kept in memory for the popup; the export has all of them
+pub const SAMPLE_LIMIT: usize = 500;
+
+/// How a key differs between the source and the destination
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FindingKind {
+    /// In the source but not the destination
+    Missing,
+    SizeMismatch,
+    ClassMismatch,
+    /// In the destination but not the source
+    Extra,
+}
+
+impl FindingKind {
+    pub const ALL: [FindingKind; 4] = [
+        FindingKind::Missing,
+        FindingKind::SizeMismatch,
+        FindingKind::ClassMismatch,
+        FindingKind::Extra,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FindingKind::Missing => "missing",
+            FindingKind::SizeMismatch => "size mismatch",
+            FindingKind::ClassMismatch => "class mismatch",
+            FindingKind::Extra => "only in destination",
+        }
+    }
+
+    /// Whether copying the source again fixes it. A copy does not keep the
+    /// source's class, so class mismatches are only reported.
+    pub fn recopies(&self) -> bool {
+        matches!(self, FindingKind::Missing | FindingKind::SizeMismatch)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Finding {
+    pub kind: FindingKind,
+    pub key: String,
+    pub source: Option<(i64, StorageClassTier)>,
+    pub dest: Option<(i64, StorageClassTier)>,
+}
+
+pub enum ReconcileEvent {
+    /// Keys listed so far on each side, and how many matched
+    Progress {
+        source: usize,
+        dest: usize,
+        matched: usize,
+    },
+    Found(Finding),
+    Failed(anyhow::Error),
+    Finished,
+}
+
+/// Handle to a running reconciliation. Dropping it stops the comparison;
+/// the export keeps what was written so far.
+pub struct ReconcileHandle {
+    events: mpsc::UnboundedReceiver<ReconcileEvent>,
+    /// Event received by `ready` and not yet taken by `try_next`
+    pending: Option<ReconcileEvent>,
+    task: JoinHandle<()>,
+}
+
+impl ReconcileHandle {
+    /// Wait until `try_next` has an event to return
+    pub async fn ready(&mut self) {
+        if self.pending.is_some() {
+            return;
+        }
+        match self.events.recv().await {
+            Some(event) => self.pending = Some(event),
+            None => std::future::pending().await,
+        }
+    }
+
+    pub fn try_next(&mut self) -> Option<ReconcileEvent> {
+        self.pending.take().or_else(|| self.events.try_recv().ok())
+    }
+}
+
+impl Drop for ReconcileHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// What a reconciliation found, built up from its events
+pub struct Reconciliation {
+    pub source_bucket: String,
+    pub dest_bucket: String,
+    pub prefix: Option<String>,
+    /// CSV with every finding, written while the comparison runs
+    pub export: PathBuf,
+    pub source_listed: usize,
+    pub dest_listed: usize,
+    pub matched: usize,
+    pub counts: [usize; 4],
+    /// The first `SAMPLE_LIMIT` findings
+    pub sample: Vec<Finding>,
+    /// Keys a re-copy would send again, with their source size
+    pub recopy: BTreeMap<String, i64>,
+    pub finished: bool,
+    pub error: Option<String>,
+}
+
+impl Reconciliation {
+    pub fn count(&self, kind: FindingKind) -> usize {
+        self.counts[kind as usize]
+    }
+
+    pub fn discrepancies(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    pub fn apply(&mut self, event: ReconcileEvent) {
+        match event {
+            ReconcileEvent::Progress {
+                source,
+                dest,
+                matched,
+            } => {
+                self.source_listed = source;
+                self.dest_listed = dest;
+                self.matched = matched;
+            }
+            ReconcileEvent::Found(finding) => {
+                self.counts[finding.kind as usize] += 1;
+                if finding.kind.recopies()
+                    && let Some((size, _)) = &finding.source
+                {
+                    self.recopy.insert(finding.key.clone(), *size);
+                }
+                if self.sample.len() < SAMPLE_LIMIT {
+                    self.sample.push(finding);
+                }
+            }
+            ReconcileEvent::Failed(err) => {
+                self.error = Some(format!("{err:#}"));
+                self.finished = true;
+            }
+            ReconcileEvent::Finished => self.finished = true,
+        }
+    }
+}
+
+/// Compare the listings of `source` and `dest` under `prefix` in the
+/// background. Both are listed a page at a time in key order and merged, so
+/// only the findings are kept, never either listing.
+pub fn spawn_reconcile(
+    s3: S3Service,
+    source: String,
+    dest: String,
+    prefix: Option<String>,
+) -> Result<(ReconcileHandle, Reconciliation)> {
+    let export = export_path(&source, &dest)?;
+    let mut writer = BufWriter::new(File::create(&export)?);
+    writeln!(
+        writer,
+        "finding,key,source_size,dest_size,source_class,dest_class"
+    )?;
+
+    let (event_tx, event_rx) = mpsc::unbounded_channel();
+    let summary = Reconciliation {
+        source_bucket: source.clone(),
+        dest_bucket: dest.clone(),
+        prefix: prefix.clone(),
+        export,
+        source_listed: 0,
+        dest_listed: 0,
+        matched: 0,
+        counts: [0; 4],
+        sample: Vec::new(),
+        recopy: BTreeMap::new(),
+        finished: false,
+        error: None,
+    };
+    let task = tokio::spawn(async move {
+        let source = Lister::new(&s3, source, prefix.clone());
+        let dest = Lister::new(&s3, dest, prefix);
+        let event = match compare(source, dest, &mut writer, &event_tx).await {
+            Ok(()) => ReconcileEvent::Finished,
+            Err(err) => ReconcileEvent::Failed(err),
+        };
+        let _ = writer.flush();
+        let _ = event_tx.send(event);
+    });
+
+    let handle = ReconcileHandle {
+        events: event_rx,
+        pending: None,
+        task,
+    };
+    Ok((handle, summary))
+}
+
+async fn compare(
+    mut source: Lister<'_>,
+    mut dest: Lister<'_>,
+    writer: &mut BufWriter<File>,
+    events: &mpsc::UnboundedSender<ReconcileEvent>,
+) -> Result<()> {
+    let mut matched = 0;
+    let mut listed = (0, 0);
+    loop {
+        let order = match (source.peek().await?, dest.peek().await?) {
+            (None, None) => break,
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            // ListObjectsV2 returns keys in UTF-8 byte order, as String compares them
+            (Some(s), Some(d)) => s.key.cmp(&d.key),
+        };
+        let finding = match order {
+            Ordering::Less => source.pop().map(|obj| Finding {
+                kind: FindingKind::Missing,
+                key: obj.key,
+                source: Some((obj.size, obj.storage_class)),
+                dest: None,
+            }),
+            Ordering::Greater => dest.pop().map(|obj| Finding {
+                kind: FindingKind::Extra,
+                key: obj.key,
+                source: None,
+                dest: Some((obj.size, obj.storage_class)),
+            }),
+            Ordering::Equal => {
+                let (Some(s), Some(d)) = (source.pop(), dest.pop()) else {
+                    break;
+                };
+                let kind = if s.size != d.size {
+                    Some(FindingKind::SizeMismatch)
+                } else if s.storage_class != d.storage_class {
+                    Some(FindingKind::ClassMismatch)
+                } else {
+                    None
+                };
+                kind.map(|kind| Finding {
+                    kind,
+                    key: s.key,
+                    source: Some((s.size, s.storage_class)),
+                    dest: Some((d.size, d.storage_class)),
+                })
+            }
+        };
+        match finding {
+            Some(finding) => {
+                write_finding(writer, &finding)?;
+                let _ = events.send(ReconcileEvent::Found(finding));
+            }
+            None => matched += 1,
+        }
+        // Report once per page rather than per key
+        if (source.listed, dest.listed) != listed {
+            listed = (source.listed, dest.listed);
+            let _ = events.send(ReconcileEvent::Progress {
+                source: listed.0,
+                dest: listed.1,
+                matched,
+            });
+        }
+    }
+    let _ = events.send(ReconcileEvent::Progress {
+        source: source.listed,
+        dest: dest.listed,
+        matched,
+    });
+    Ok(())
+}
+
+/// One bucket's listing, a page at a time
+struct Lister<'a> {
+    s3: &'a S3Service,
+    bucket: String,
+    prefix: Option<String>,
+    token: Option<String>,
+    page: VecDeque<ObjectInfo>,
+    done: bool,
+    listed: usize,
+}
+
+impl<'a> Lister<'a> {
+    fn new(s3: &'a S3Service, bucket: String, prefix: Option<String>) -> Self {
+        Self {
+            s3,
+            bucket,
+            prefix,
+            token: None,
+            page: VecDeque::new(),
+            done: false,
+            listed: 0,
+        }
+    }
+
+    async fn peek(&mut self) -> Result<Option<&ObjectInfo>> {
+        while self.page.is_empty() && !self.done {
+            let (objects, token) = self
+                .s3
+                .list_objects_paginated(
+                    &self.bucket,
+                    self.prefix.as_deref(),
+                    None,
+                    self.token.take(),
+                    PAGE_SIZE,
+                )
+                .await?;
+            self.listed += objects.len();
+            self.done = token.is_none();
+            self.token = token;
+            self.page.extend(objects);
+        }
+        Ok(self.page.front())
+    }
+
+    fn pop(&mut self) -> Option<ObjectInfo> {
+        self.page.pop_front()
+    }
+}
+
+fn write_finding(writer: &mut impl Write, finding: &Finding) -> std::io::Result<()> {
+    let size = |side: &Option<(i64, StorageClassTier)>| {
+        side.as_ref()
+            .map(|(size, _)| size.to_string())
+            .unwrap_or_default()
+    };
+    let class = |side: &Option<(i64, StorageClassTier)>| {
+        side.as_ref()
+            .map(|(_, class)| class.label().to_string())
+            .unwrap_or_default()
+    };
+    writeln!(
+        writer,
+        "{},{},{},{},{},{}",
+        finding.kind.label(),
+        csv_field(&finding.key),
+        size(&finding.source),
+        size(&finding.dest),
+        class(&finding.source),
+        class(&finding.dest)
+    )
+}
+
+/// Quote a field that holds a comma, quote or line break
+pub(crate) fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// `exports/<source>-vs-<dest>-<timestamp>.csv` in the config directory
+fn export_path(source: &str, dest: &str) -> Result<PathBuf> {
+    let dir = directories::ProjectDirs::from("com", "bucket-brigade", "bucket-brigade")
+        .map(|dirs| dirs.config_dir().to_path_buf())
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("exports");
+    fs::create_dir_all(&dir)?;
+    Ok(dir.join(format!(
+        "{source}-vs-{dest}-{}.csv",
+        Utc::now().format("%Y%m%d-%H%M%S")
+    )))
+}
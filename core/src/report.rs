@@ -0,0 +1,338 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use chrono::Utc;
+
+use crate::models::{JobRecord, ObjectInfo, StorageClassTier};
+use crate::pricing;
+use crate::schedule::ScheduledAction;
+use crate::waves::format_bytes;
+
+/// Jobs listed in the results section, most recent first
+const REPORT_JOBS: usize = 50;
+
+/// Jobs drawn in the results chart
+const CHART_JOBS: usize = 20;
+
+const BYTES_PER_GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// Bar colors, one per storage class in `StorageClassTier::SELECTABLE` order
+const CLASS_COLORS: [&str; 7] = [
+    "#4e79a7", "#59a14f", "#f28e2b", "#edc948", "#76b7b2", "#b07aa1", "#9c755f",
+];
+
+/// Objects and bytes of one storage class
+pub struct ClassUsage {
+    pub class: StorageClassTier,
+    pub objects: usize,
+    pub bytes: u64,
+}
+
+/// Usage per storage class, largest first
+pub fn class_breakdown<'a>(objects: impl IntoIterator<Item = &'a ObjectInfo>) -> Vec<ClassUsage> {
+    let mut usage: Vec<ClassUsage> = Vec::new();
+    for obj in objects {
+        let bytes = obj.size.max(0) as u64;
+        match usage.iter_mut().find(|u| u.class == obj.storage_class) {
+            Some(u) => {
+                u.objects += 1;
+                u.bytes += bytes;
+            }
+            None => usage.push(ClassUsage {
+                class: obj.storage_class.clone(),
+                objects: 1,
+                bytes,
+            }),
+        }
+    }
+    usage.sort_by_key(|u| std::cmp::Reverse(u.bytes));
+    usage
+}
+
+/// A bucket's storage, what is planned for it and the jobs that ran on it,
+/// as one self-contained HTML page for people who do not use the terminal
+pub struct BucketReport<'a> {
+    pub bucket: &'a str,
+    pub region: Option<&'a str>,
+    /// Objects listed so far
+    pub objects: &'a [ObjectInfo],
+    /// Whether the listing reached the end of the bucket
+    pub complete: bool,
+    /// The mask or query selection, with a description of it
+    pub selection: Option<(String, Vec<&'a ObjectInfo>)>,
+    pub scheduled: Vec<&'a ScheduledAction>,
+    /// Jobs on the bucket, or copying into it, most recent first
+    pub jobs: Vec<&'a JobRecord>,
+}
+
+impl BucketReport<'_> {
+    pub fn html(&self) -> String {
+        let mut page = String::new();
+        let title = format!("Storage report: {}", self.bucket);
+        let _ = write!(
+            page,
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>{}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n<h1>{}</h1>\n",
+            escape(&title),
+            escape(&title)
+        );
+        let listed = if self.complete {
+            format!("{} objects", self.objects.len())
+        } else {
+            format!(
+                "the first {} objects listed; the bucket has more",
+                self.objects.len()
+            )
+        };
+        let _ = writeln!(
+            page,
+            "<p class=\"meta\">Region {} · generated {} · based on {}</p>",
+            escape(self.region.unwrap_or("unknown")),
+            Utc::now().format("%Y-%m-%d %H:%M UTC"),
+            escape(&listed)
+        );
+
+        page.push_str("<h2>Storage by class</h2>\n");
+        let usage = class_breakdown(self.objects);
+        if usage.is_empty() {
+            page.push_str("<p>No objects.</p>\n");
+        } else {
+            page.push_str(&usage_chart(&usage));
+            page.push_str(&self.usage_table(&usage));
+        }
+
+        page.push_str("<h2>Planned migration</h2>\n");
+        if self.selection.is_none() && self.scheduled.is_empty() {
+            page.push_str("<p>Nothing is selected or scheduled for this bucket.</p>\n");
+        }
+        if let Some((label, objects)) = &self.selection {
+            let usage = class_breakdown(objects.iter().copied());
+            let bytes: u64 = usage.iter().map(|u| u.bytes).sum();
+            let _ = writeln!(
+                page,
+                "<h3>Selected: {}</h3>\n<p>{} objects, {}</p>",
+                escape(label),
+                objects.len(),
+                format_bytes(bytes)
+            );
+            page.push_str(&self.usage_table(&usage));
+        }
+        if !self.scheduled.is_empty() {
+            page.push_str(
+                "<h3>Scheduled</h3>\n<table>\n<tr><th>Runs at</th><th>Action</th>\
+                 <th class=\"num\">Size</th></tr>\n",
+            );
+            for action in &self.scheduled {
+                let bytes: u64 = action.objects.iter().map(|o| o.size.max(0) as u64).sum();
+                let _ = writeln!(
+                    page,
+                    "<tr><td>{}</td><td>{}</td><td class=\"num\">{}</td></tr>",
+                    escape(&short_time(&action.run_at)),
+                    escape(&action.describe()),
+                    format_bytes(bytes)
+                );
+            }
+            page.push_str("</table>\n");
+        }
+
+        page.push_str("<h2>Results</h2>\n");
+        if self.jobs.is_empty() {
+            page.push_str("<p>No jobs have run on this bucket.</p>\n");
+        } else {
+            page.push_str(&jobs_chart(&self.jobs));
+            page.push_str(&jobs_table(&self.jobs));
+        }
+        page.push_str(
+            "<p class=\"meta\">Prices are list prices for the first 50 TB tier; \
+             treat them as guidance, not a quote.</p>\n</body>\n</html>\n",
+        );
+        page
+    }
+
+    /// Write the report to the exports directory and return its path
+    pub fn export(&self) -> Result<PathBuf> {
+        let dir = directories::ProjectDirs::from("com", "bucket-brigade", "bucket-brigade")
+            .map(|dirs| dirs.config_dir().to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("exports");
+        fs::create_dir_all(&dir)?;
+        let path = dir.join(format!(
+            "{}-report-{}.html",
+            self.bucket,
+            Utc::now().format("%Y%m%d-%H%M%S")
+        ));
+        fs::write(&path, self.html())?;
+        Ok(path)
+    }
+
+    fn usage_table(&self, usage: &[ClassUsage]) -> String {
+        let total: u64 = usage.iter().map(|u| u.bytes).sum();
+        let mut table = String::from(
+            "<table>\n<tr><th>Class</th><th class=\"num\">Objects</th><th class=\"num\">Size</th>\
+             <th class=\"num\">Share</th><th class=\"num\">Monthly cost</th></tr>\n",
+        );
+        for u in usage {
+            let share = if total > 0 {
+                u.bytes as f64 * 100.0 / total as f64
+            } else {
+                0.0
+            };
+            let cost = pricing::monthly_price_per_gb(self.region, &u.class)
+                .map(|(price, _)| format!("${:.2}", u.bytes as f64 / BYTES_PER_GB * price))
+                .unwrap_or_else(|| "–".to_string());
+            let _ = writeln!(
+                table,
+                "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td>\
+                 <td class=\"num\">{share:.1}%</td><td class=\"num\">{cost}</td></tr>",
+                escape(u.class.label()),
+                u.objects,
+                format_bytes(u.bytes)
+            );
+        }
+        table.push_str("</table>\n");
+        table
+    }
+}
+
+/// Horizontal bars of bytes per class
+fn usage_chart(usage: &[ClassUsage]) -> String {
+    let max = usage.iter().map(|u| u.bytes).max().unwrap_or(0).max(1);
+    let (label_width, bar_width, row) = (190, 420, 26);
+    let height = usage.len() * row + 10;
+    let mut svg = format!(
+        "<svg class=\"chart\" width=\"{}\" height=\"{height}\" role=\"img\" \
+         aria-label=\"Bytes per storage class\">\n",
+        label_width + bar_width + 110
+    );
+    for (i, u) in usage.iter().enumerate() {
+        let y = i * row + 5;
+        let width = (u.bytes as f64 / max as f64 * bar_width as f64).max(1.0);
+        let _ = writeln!(
+            svg,
+            "<text x=\"0\" y=\"{}\">{}</text>\
+             <rect x=\"{label_width}\" y=\"{y}\" width=\"{width:.1}\" height=\"{}\" fill=\"{}\"/>\
+             <text x=\"{:.1}\" y=\"{}\">{}</text>",
+            y + 15,
+            escape(u.class.label()),
+            row - 8,
+            class_color(&u.class),
+            label_width as f64 + width + 6.0,
+            y + 15,
+            format_bytes(u.bytes)
+        );
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Succeeded and failed keys of the most recent jobs, oldest on the left
+fn jobs_chart(jobs: &[&JobRecord]) -> String {
+    let shown: Vec<&&JobRecord> = jobs.iter().take(CHART_JOBS).collect();
+    let max = shown.iter().map(|j| j.total).max().unwrap_or(0).max(1);
+    let (column, chart_height) = (32, 160);
+    let width = shown.len() * column + 20;
+    let mut svg = format!(
+        "<svg class=\"chart\" width=\"{width}\" height=\"{}\" role=\"img\" \
+         aria-label=\"Succeeded and failed keys per job\">\n",
+        chart_height + 10
+    );
+    for (i, job) in shown.iter().rev().enumerate() {
+        let x = i * column + 10;
+        let scale = |count: usize| count as f64 / max as f64 * chart_height as f64;
+        let ok = scale(job.succeeded);
+        let failed = scale(job.failed);
+        let base = chart_height as f64 + 5.0;
+        let _ = writeln!(
+            svg,
+            "<g><title>{} {}: {} succeeded, {} failed</title>\
+             <rect x=\"{x}\" y=\"{:.1}\" width=\"{}\" height=\"{ok:.1}\" fill=\"#59a14f\"/>\
+             <rect x=\"{x}\" y=\"{:.1}\" width=\"{}\" height=\"{failed:.1}\" fill=\"#e15759\"/></g>",
+            escape(job.kind.label()),
+            escape(&short_time(&job.started_at)),
+            job.succeeded,
+            job.failed,
+            base - ok,
+            column - 8,
+            base - ok - failed,
+            column - 8
+        );
+    }
+    svg.push_str(
+        "</svg>\n<p class=\"meta\"><span class=\"ok\">■</span> succeeded \
+                  <span class=\"failed\">■</span> failed, oldest job on the left</p>\n",
+    );
+    svg
+}
+
+fn jobs_table(jobs: &[&JobRecord]) -> String {
+    let mut table = String::from(
+        "<table>\n<tr><th>Started</th><th>Job</th><th>Target</th><th class=\"num\">Objects</th>\
+         <th class=\"num\">Succeeded</th><th class=\"num\">Failed</th><th>Duration</th>\
+         <th>Status</th><th>Ticket</th></tr>\n",
+    );
+    for job in jobs.iter().take(REPORT_JOBS) {
+        let status = if job.interrupted {
+            "stopped early"
+        } else if job.finished_at.is_none() {
+            "not finished"
+        } else if job.failed > 0 {
+            "finished with failures"
+        } else {
+            "finished"
+        };
+        let duration = job
+            .duration()
+            .map(|d| format!("{}m {}s", d.num_minutes(), d.num_seconds() % 60))
+            .unwrap_or_else(|| "–".to_string());
+        let ticket = match &job.ticket {
+            Some(ticket) => match &ticket.url {
+                Some(url) => format!("<a href=\"{}\">{}</a>", escape(url), escape(&ticket.id)),
+                None => escape(&ticket.id),
+            },
+            None => "–".to_string(),
+        };
+        let _ = writeln!(
+            table,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td class=\"num\">{}</td>\
+             <td class=\"num\">{}</td><td class=\"num\">{}</td><td>{duration}</td><td>{status}</td>\
+             <td>{ticket}</td></tr>",
+            escape(&short_time(&job.started_at)),
+            escape(job.kind.label()),
+            escape(&job.target),
+            job.total,
+            job.succeeded,
+            job.failed
+        );
+    }
+    table.push_str("</table>\n");
+    table
+}
+
+fn class_color(class: &StorageClassTier) -> &'static str {
+    StorageClassTier::SELECTABLE
+        .iter()
+        .position(|c| c == class)
+        .map_or("#bab0ac", |i| CLASS_COLORS[i])
+}
+
+/// `2024-05-01 12:30` for an RFC 3339 timestamp, or the text as it is
+fn short_time(at: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(at)
+        .map(|at| at.with_timezone(&Utc).format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|_| at.to_string())
+}
+
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub(crate) const STYLE: &str = "body{font-family:system-ui,sans-serif;margin:2em auto;max-width:60em;\
+color:#222}h1{font-size:1.6em}h2{margin-top:2em;border-bottom:1px solid #ddd}\
+table{border-collapse:collapse;margin:1em 0}th,td{padding:.3em .8em;border-bottom:1px solid #eee;\
+text-align:left}.num{text-align:right}.meta{color:#666;font-size:.9em}\
+.chart text{font-size:12px;fill:#333}.ok{color:#59a14f}.failed{color:#e15759}";
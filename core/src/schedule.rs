@@ -0,0 +1,307 @@
+use anyhow::Result;
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::models::{
+    CopyMode, KeyRewrite, ObjectInfo, ObjectLockPlan, RestoreTier, StorageClassTier, restore_label,
+};
+use crate::plan::transition_label;
+use crate::tickets::TicketLink;
+
+/// What a scheduled action does to its objects
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum ScheduledKind {
+    Transition {
+        target_class: StorageClassTier,
+        #[serde(default)]
+        lock: ObjectLockPlan,
+    },
+    Restore {
+        days: i32,
+        #[serde(default)]
+        tier: RestoreTier,
+        #[serde(default)]
+        preset: Option<String>,
+    },
+    Copy {
+        dest_bucket: String,
+        #[serde(default)]
+        mode: CopyMode,
+        #[serde(default)]
+        delete_source: bool,
+        /// Bytes per second, when the copy crosses regions
+        #[serde(default)]
+        max_bandwidth: Option<u64>,
+        #[serde(default)]
+        rewrite: KeyRewrite,
+        #[serde(default)]
+        storage_class: Option<StorageClassTier>,
+    },
+}
+
+/// A confirmed action held back until `run_at`. The objects are recorded as
+/// they were when it was scheduled, so changes made since can be detected
+/// before it runs.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScheduledAction {
+    pub id: String,
+    pub bucket: String,
+    pub kind: ScheduledKind,
+    pub objects: Vec<ObjectInfo>,
+    pub run_at: String,     // ISO 8601 timestamp
+    pub created_at: String, // ISO 8601 timestamp
+    /// The operator's note from the confirmation, given to the job it runs
+    #[serde(default)]
+    pub note: Option<String>,
+    /// The change ticket from the confirmation, given to the job it runs
+    #[serde(default)]
+    pub ticket: Option<TicketLink>,
+}
+
+impl ScheduledAction {
+    pub fn run_at(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.run_at)
+            .ok()
+            .map(|at| at.with_timezone(&Utc))
+    }
+
+    pub fn describe(&self) -> String {
+        match &self.kind {
+            ScheduledKind::Transition { target_class, lock } => format!(
+                "Transition {} objects in {} to {}",
+                self.objects.len(),
+                self.bucket,
+                transition_label(target_class, lock)
+            ),
+            ScheduledKind::Restore { days, tier, preset } => format!(
+                "Restore {} objects in {} for {}{}",
+                self.objects.len(),
+                self.bucket,
+                restore_label(*days, *tier),
+                preset
+                    .as_ref()
+                    .map(|name| format!(" ({name})"))
+                    .unwrap_or_default()
+            ),
+            ScheduledKind::Copy {
+                dest_bucket,
+                delete_source,
+                rewrite,
+                ..
+            } => format!(
+                "{} {} objects from {} to {}{}",
+                if *delete_source { "Move" } else { "Copy" },
+                self.objects.len(),
+                self.bucket,
+                dest_bucket,
+                if rewrite.is_empty() {
+                    String::new()
+                } else {
+                    format!(" ({})", rewrite.label())
+                }
+            ),
+        }
+    }
+
+    /// Whether `current` differs from the object as it was when scheduled
+    pub fn drifted(&self, current: &ObjectInfo) -> bool {
+        self.objects
+            .iter()
+            .find(|o| o.key == current.key)
+            .is_none_or(|o| {
+                o.size != current.size
+                    || o.storage_class != current.storage_class
+                    || o.last_modified != current.last_modified
+            })
+    }
+}
+
+/// Actions waiting to run, persisted in `scheduled_actions.json`
+#[derive(Default)]
+pub struct Schedule {
+    file_path: PathBuf,
+    actions: Vec<ScheduledAction>,
+}
+
+impl Schedule {
+    pub fn new() -> Result<Self> {
+        let config_dir = directories::ProjectDirs::from("com", "bucket-brigade", "bucket-brigade")
+            .map(|dirs| dirs.config_dir().to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        fs::create_dir_all(&config_dir)?;
+        let file_path = config_dir.join("scheduled_actions.json");
+
+        let actions = if file_path.exists() {
+            let content = fs::read_to_string(&file_path)?;
+            serde_json::from_str(&content).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self { file_path, actions })
+    }
+
+    /// Scheduled actions, soonest first
+    pub fn actions(&self) -> &[ScheduledAction] {
+        &self.actions
+    }
+
+    pub fn add(
+        &mut self,
+        bucket: &str,
+        kind: ScheduledKind,
+        objects: Vec<ObjectInfo>,
+        run_at: DateTime<Utc>,
+        note: Option<String>,
+        ticket: Option<TicketLink>,
+    ) -> String {
+        let id = uuid::Uuid::new_v4().to_string();
+        self.actions.push(ScheduledAction {
+            id: id.clone(),
+            bucket: bucket.to_string(),
+            kind,
+            objects,
+            run_at: run_at.with_timezone(&Local).to_rfc3339(),
+            created_at: Utc::now().to_rfc3339(),
+            note,
+            ticket,
+        });
+        self.actions.sort_by_key(|action| action.run_at());
+        let _ = self.save();
+        id
+    }
+
+    pub fn get(&self, id: &str) -> Option<&ScheduledAction> {
+        self.actions.iter().find(|action| action.id == id)
+    }
+
+    pub fn remove(&mut self, id: &str) {
+        self.actions.retain(|action| action.id != id);
+        let _ = self.save();
+    }
+
+    /// When the next action is due
+    pub fn next_run_at(&self) -> Option<DateTime<Utc>> {
+        self.actions
+            .iter()
+            .filter_map(|action| action.run_at())
+            .min()
+    }
+
+    /// Take the first action whose time has come off the schedule
+    pub fn take_due(&mut self) -> Option<ScheduledAction> {
+        let now = Utc::now();
+        let index = self
+            .actions
+            .iter()
+            .position(|action| action.run_at().is_none_or(|at| at <= now))?;
+        let action = self.actions.remove(index);
+        let _ = self.save();
+        Some(action)
+    }
+
+    fn save(&self) -> Result<()> {
+        let json = serde_json::to_string_pretty(&self.actions)?;
+        fs::write(&self.file_path, json)?;
+        Ok(())
+    }
+}
+
+/// Local hours of the day batch jobs may run in (`--window 22:00-06:00`). A
+/// window that ends before it starts runs past midnight.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExecutionWindow {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl ExecutionWindow {
+    pub fn from_args(args: &[String]) -> Option<Self> {
+        let pair = args.windows(2).find(|pair| pair[0] == "--window")?;
+        let (start, end) = pair[1].split_once('-')?;
+        let parse = |time: &str| NaiveTime::parse_from_str(time.trim(), "%H:%M").ok();
+        let (start, end) = (parse(start)?, parse(end)?);
+        (start != end).then_some(Self { start, end })
+    }
+
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start < self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.contains(Local::now().time())
+    }
+
+    /// `at` if the window is open then, otherwise the next time it opens
+    pub fn earliest_from(&self, at: DateTime<Utc>) -> DateTime<Utc> {
+        let local = at.with_timezone(&Local);
+        if self.contains(local.time()) {
+            return at;
+        }
+        let Some(mut opens) = local
+            .date_naive()
+            .and_time(self.start)
+            .and_local_timezone(Local)
+            .earliest()
+        else {
+            return at;
+        };
+        if opens <= local {
+            opens += TimeDelta::days(1);
+        }
+        opens.with_timezone(&Utc)
+    }
+
+    pub fn label(&self) -> String {
+        format!(
+            "{}-{}",
+            self.start.format("%H:%M"),
+            self.end.format("%H:%M")
+        )
+    }
+}
+
+/// Parse a run time in local time: `HH:MM` (the next time the clock shows it),
+/// `YYYY-MM-DD HH:MM`, or a delay such as `+90m` or `+2h`
+pub fn parse_run_at(text: &str) -> Option<DateTime<Utc>> {
+    let text = text.trim();
+    let now = Local::now();
+    if let Some(delay) = text.strip_prefix('+') {
+        let unit = delay.chars().last()?;
+        let number: i64 = delay[..delay.len() - unit.len_utf8()].trim().parse().ok()?;
+        let delay = match unit {
+            'm' => TimeDelta::try_minutes(number)?,
+            'h' => TimeDelta::try_hours(number)?,
+            _ => return None,
+        };
+        return (number > 0).then(|| (now + delay).with_timezone(&Utc));
+    }
+
+    let (date, time) = match text.split_once(' ') {
+        Some((date, time)) => (
+            Some(NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?),
+            time,
+        ),
+        None => (None, text),
+    };
+    let time = NaiveTime::parse_from_str(time.trim(), "%H:%M").ok()?;
+    let mut at = date
+        .unwrap_or(now.date_naive())
+        .and_time(time)
+        .and_local_timezone(Local)
+        .earliest()?;
+    if at <= now {
+        if date.is_some() {
+            return None;
+        }
+        at += TimeDelta::days(1);
+    }
+    Some(at.with_timezone(&Utc))
+}
@@ -0,0 +1,136 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use rhai::{AST, Dynamic, Engine, Map, Scope};
+
+use crate::models::ObjectInfo;
+
+/// Upper bound on the work one evaluation may do, so a runaway loop cannot
+/// hang the UI
+const MAX_OPERATIONS: u64 = 50_000;
+
+/// Variables a script can read, in the order the editor lists them
+pub const VARIABLES: &[(&str, &str)] = &[
+    ("key", "object key"),
+    ("size", "bytes"),
+    ("age_days", "days since last modified, -1 if unknown"),
+    ("class", "storage class, such as \"GLACIER\""),
+    ("owner", "owner name or ID, \"\" unless owners are listed"),
+    (
+        "tags",
+        "object tags as a map, fetched when the script uses them",
+    ),
+];
+
+/// A mask written as a Rhai expression over an object, such as
+/// `size > 1048576 && age_days > 365 && tags["team"] == "media"`.
+/// The engine has no file, network or module access, and evaluation is
+/// capped by `MAX_OPERATIONS`.
+pub struct Predicate {
+    engine: Engine,
+    ast: AST,
+    uses_tags: bool,
+}
+
+impl Predicate {
+    pub fn compile(source: &str) -> Result<Self> {
+        let engine = sandboxed_engine();
+        let ast = engine
+            .compile_with_scope(&object_scope(None, Utc::now()), source)
+            .map_err(|err| anyhow!("{err}"))?;
+        Ok(Self {
+            engine,
+            ast,
+            uses_tags: source.contains("tags"),
+        })
+    }
+
+    /// Whether the script reads `tags`, which need a request per object
+    pub fn uses_tags(&self) -> bool {
+        self.uses_tags
+    }
+
+    pub fn eval(&self, obj: &ObjectInfo, now: DateTime<Utc>) -> Result<bool> {
+        let mut scope = object_scope(Some(obj), now);
+        self.engine
+            .eval_ast_with_scope::<bool>(&mut scope, &self.ast)
+            .map_err(|err| anyhow!("{err}"))
+    }
+}
+
+fn sandboxed_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .set_max_operations(MAX_OPERATIONS)
+        .set_max_call_levels(16)
+        .set_max_expr_depths(64, 32)
+        .set_max_string_size(64 * 1024)
+        .set_max_array_size(10_000)
+        .set_max_map_size(10_000)
+        .set_strict_variables(true);
+    engine.disable_symbol("eval");
+    // Printing would write over the terminal UI
+    engine.on_print(|_| {});
+    engine.on_debug(|_, _, _| {});
+    engine
+}
+
+/// The object's fields as constants. Without an object, only declares them
+/// for compiling; they are not constants then, so the optimizer cannot fold
+/// the placeholder values into the script.
+fn object_scope(obj: Option<&ObjectInfo>, now: DateTime<Utc>) -> Scope<'static> {
+    let mut scope = Scope::new();
+    let Some(obj) = obj else {
+        for (name, _) in VARIABLES {
+            scope.push(*name, Dynamic::UNIT);
+        }
+        return scope;
+    };
+    let age_days = obj
+        .last_modified
+        .as_deref()
+        .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+        .map(|at| (now - at.with_timezone(&Utc)).num_days())
+        .unwrap_or(-1);
+    let mut tags = Map::new();
+    for (name, value) in obj.tags.iter().flatten() {
+        tags.insert(name.into(), value.clone().into());
+    }
+    scope.push_constant("key", obj.key.clone());
+    scope.push_constant("size", obj.size);
+    scope.push_constant("age_days", age_days);
+    scope.push_constant("class", obj.storage_class.label().to_string());
+    scope.push_constant("owner", obj.owner.clone().unwrap_or_default());
+    scope.push_constant("tags", tags);
+    scope
+}
+
+/// First line of a script that is not blank or a comment, to name its mask
+pub fn first_line(source: &str) -> &str {
+    source
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with("//"))
+        .unwrap_or("")
+}
+
+thread_local! {
+    /// The last compiled script, so filtering a listing compiles it once
+    static COMPILED: RefCell<Option<(String, Rc<Predicate>)>> = const { RefCell::new(None) };
+}
+
+/// The compiled predicate for `source`, reusing the last compilation
+pub fn predicate(source: &str) -> Result<Rc<Predicate>> {
+    COMPILED.with(|cell| {
+        if let Some((cached, predicate)) = &*cell.borrow()
+            && cached == source
+        {
+            return Ok(predicate.clone());
+        }
+        let predicate = Rc::new(Predicate::compile(source)?);
+        *cell.borrow_mut() = Some((source.to_string(), predicate.clone()));
+        Ok(predicate)
+    })
+}
@@ -0,0 +1,378 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::coordination::ShardProgress;
+use crate::models::{ObjectLockPlan, RestoreTier, StorageClassTier};
+use crate::tickets::TicketLink;
+
+/// Keys per shard unless `--shard-size` says otherwise
+pub const DEFAULT_SHARD_KEYS: usize = 100_000;
+
+/// Batches with more keys than this are split into shards (`--shard-size`)
+pub fn shard_size(args: &[String]) -> usize {
+    args.windows(2)
+        .find(|pair| pair[0] == "--shard-size")
+        .and_then(|pair| pair[1].parse::<usize>().ok())
+        .filter(|n| *n > 0)
+        .unwrap_or(DEFAULT_SHARD_KEYS)
+}
+
+/// What each shard of a plan does to its objects
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum ShardWork {
+    Transition {
+        target_class: StorageClassTier,
+        #[serde(default)]
+        lock: ObjectLockPlan,
+    },
+    Restore {
+        days: i32,
+        #[serde(default)]
+        tier: RestoreTier,
+        /// Restore preset whose pacing and notification the shards use
+        #[serde(default)]
+        preset: Option<String>,
+    },
+}
+
+impl ShardWork {
+    pub fn restore_preset(&self) -> Option<&str> {
+        match self {
+            ShardWork::Restore { preset, .. } => preset.as_deref(),
+            ShardWork::Transition { .. } => None,
+        }
+    }
+}
+
+/// Keys under one prefix, or a run of neighbouring prefixes, run as a job of
+/// their own
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Shard {
+    /// The prefix, or `first … last` when small prefixes were merged
+    pub name: String,
+    pub keys: Vec<(String, i64)>,
+    /// Storage class of each key of a restore, which its tracked restore
+    /// request records
+    #[serde(default)]
+    pub storage_classes: BTreeMap<String, StorageClassTier>,
+    /// Journal ids of the jobs that ran it, oldest first
+    #[serde(default)]
+    pub runs: Vec<String>,
+    /// Keys handled without error over all its runs
+    #[serde(default)]
+    pub succeeded: usize,
+    /// Keys that failed in its latest run
+    #[serde(default)]
+    pub failed: usize,
+    /// Worker holding a claim on it, for a shared plan
+    #[serde(skip)]
+    pub worker: Option<String>,
+}
+
+impl Shard {
+    pub fn is_done(&self) -> bool {
+        self.succeeded >= self.keys.len()
+    }
+
+    /// Journal id of the latest run
+    pub fn latest_run(&self) -> Option<&str> {
+        self.runs.last().map(String::as_str)
+    }
+}
+
+/// A batch over too many keys to run as one job, split by prefix into shards
+/// that run, resume and retry independently
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ShardPlan {
+    pub id: String,
+    pub bucket: String,
+    pub label: String,
+    pub work: ShardWork,
+    pub created_at: String, // ISO 8601 timestamp
+    pub shards: Vec<Shard>,
+    /// Run by `--worker` processes through the shared state rather than as
+    /// local jobs; `runs` then holds worker ids
+    #[serde(default)]
+    pub shared: bool,
+    /// Uploaded to the shared state, so workers can claim its shards
+    #[serde(default)]
+    pub published: bool,
+    /// The operator's note on the batch, carried into each shard's job
+    #[serde(default)]
+    pub note: Option<String>,
+    /// The batch's change ticket, carried into each shard's job
+    #[serde(default)]
+    pub ticket: Option<TicketLink>,
+}
+
+impl ShardPlan {
+    /// Split `objects` (key, size) into shards of at most `shard_keys` keys
+    pub fn new(
+        bucket: &str,
+        label: &str,
+        work: ShardWork,
+        objects: Vec<(String, i64)>,
+        shard_keys: usize,
+    ) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            bucket: bucket.to_string(),
+            label: label.to_string(),
+            work,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            shards: partition(objects, shard_keys.max(1)),
+            shared: false,
+            published: false,
+            note: None,
+            ticket: None,
+        }
+    }
+
+    pub fn total_keys(&self) -> usize {
+        self.shards.iter().map(|shard| shard.keys.len()).sum()
+    }
+
+    pub fn succeeded_keys(&self) -> usize {
+        self.shards.iter().map(|shard| shard.succeeded).sum()
+    }
+
+    pub fn done_shards(&self) -> usize {
+        self.shards.iter().filter(|shard| shard.is_done()).count()
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.shards.iter().all(Shard::is_done)
+    }
+}
+
+/// Group keys by the path segment after `prefix` (up to the next `/`, or one
+/// character when there is none), descending into a group until it fits,
+/// then merge neighbouring groups up to `max` keys per shard
+fn partition(objects: Vec<(String, i64)>, max: usize) -> Vec<Shard> {
+    let mut groups = Vec::new();
+    split("", objects, max, &mut groups);
+
+    let mut shards: Vec<Shard> = Vec::new();
+    for (prefix, keys) in groups {
+        match shards.last_mut() {
+            Some(shard) if shard.keys.len() + keys.len() <= max => {
+                let first = shard.name.split(" … ").next().unwrap_or_default();
+                shard.name = format!("{first} … {prefix}");
+                shard.keys.extend(keys);
+            }
+            _ => shards.push(Shard {
+                name: prefix.clone(),
+                keys,
+                storage_classes: BTreeMap::new(),
+                runs: Vec::new(),
+                succeeded: 0,
+                failed: 0,
+                worker: None,
+            }),
+        }
+    }
+    shards
+}
+
+fn split(
+    prefix: &str,
+    objects: Vec<(String, i64)>,
+    max: usize,
+    groups: &mut Vec<(String, Vec<(String, i64)>)>,
+) {
+    if objects.len() <= max {
+        groups.push((prefix.to_string(), objects));
+        return;
+    }
+    let mut by_segment: BTreeMap<String, Vec<(String, i64)>> = BTreeMap::new();
+    for (key, size) in objects {
+        let rest = &key[prefix.len()..];
+        let end = match rest.find('/') {
+            Some(slash) => slash + 1,
+            None => rest.chars().next().map_or(0, char::len_utf8),
+        };
+        let segment = format!("{prefix}{}", &rest[..end]);
+        by_segment.entry(segment).or_default().push((key, size));
+    }
+    for (segment, objects) in by_segment {
+        // The key equal to `prefix` itself is a group of one, so this ends
+        split(&segment, objects, max, groups);
+    }
+}
+
+/// Shard plans, persisted in `shard_plans.json` until every shard is done or
+/// the plan is dropped
+#[derive(Default)]
+pub struct ShardStore {
+    file_path: PathBuf,
+    plans: Vec<ShardPlan>,
+}
+
+impl ShardStore {
+    pub fn new() -> Result<Self> {
+        let config_dir = directories::ProjectDirs::from("com", "bucket-brigade", "bucket-brigade")
+            .map(|dirs| dirs.config_dir().to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        fs::create_dir_all(&config_dir)?;
+        let file_path = config_dir.join("shard_plans.json");
+
+        let plans = if file_path.exists() {
+            let content = fs::read_to_string(&file_path)?;
+            serde_json::from_str(&content).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self { file_path, plans })
+    }
+
+    pub fn plans(&self) -> &[ShardPlan] {
+        &self.plans
+    }
+
+    pub fn plan(&self, id: &str) -> Option<&ShardPlan> {
+        self.plans.iter().find(|plan| plan.id == id)
+    }
+
+    pub fn add(&mut self, plan: ShardPlan) {
+        self.plans.push(plan);
+        let _ = self.save();
+    }
+
+    /// The plan and shard index a journal id ran
+    pub fn find_run(&self, job_id: &str) -> Option<(&ShardPlan, usize)> {
+        self.plans.iter().find_map(|plan| {
+            let index = plan
+                .shards
+                .iter()
+                .position(|shard| shard.runs.iter().any(|run| run == job_id))?;
+            Some((plan, index))
+        })
+    }
+
+    fn shard_mut(&mut self, plan_id: &str, shard: usize) -> Option<&mut Shard> {
+        self.plans
+            .iter_mut()
+            .find(|plan| plan.id == plan_id)
+            .and_then(|plan| plan.shards.get_mut(shard))
+    }
+
+    /// Record that `job_id` started running the shard, and how many of its
+    /// keys the journals of its earlier runs have as done
+    pub fn begin_run(&mut self, plan_id: &str, shard: usize, job_id: &str, succeeded: usize) {
+        if let Some(shard) = self.shard_mut(plan_id, shard) {
+            shard.runs.push(job_id.to_string());
+            shard.succeeded = succeeded;
+            shard.failed = 0;
+        }
+        let _ = self.save();
+    }
+
+    /// Correct how many keys of a shard are done, for a run that was
+    /// interrupted before it could be added up. Returns the plan if that was
+    /// its last shard, after dropping it.
+    pub fn set_succeeded(
+        &mut self,
+        plan_id: &str,
+        shard: usize,
+        succeeded: usize,
+    ) -> Option<ShardPlan> {
+        if let Some(shard) = self.shard_mut(plan_id, shard) {
+            shard.succeeded = succeeded.min(shard.keys.len());
+        }
+        self.settle(plan_id)
+    }
+
+    /// Add up a finished run of a shard. Returns the plan once every shard is
+    /// done, after dropping it.
+    pub fn finish_run(
+        &mut self,
+        job_id: &str,
+        succeeded: usize,
+        failed: usize,
+    ) -> Option<ShardPlan> {
+        let plan = self.plans.iter_mut().find_map(|plan| {
+            let shard = plan
+                .shards
+                .iter_mut()
+                .find(|shard| shard.latest_run() == Some(job_id))?;
+            shard.succeeded = (shard.succeeded + succeeded).min(shard.keys.len());
+            shard.failed = failed;
+            Some(plan.id.clone())
+        })?;
+        self.settle(&plan)
+    }
+
+    pub fn mark_published(&mut self, plan_id: &str) {
+        if let Some(plan) = self.plans.iter_mut().find(|plan| plan.id == plan_id) {
+            plan.published = true;
+        }
+        let _ = self.save();
+    }
+
+    /// Take in what the workers last reported for a shard of a shared plan:
+    /// its progress, if any worker ran it, and who holds a claim on it.
+    /// `settle_shared` saves.
+    pub fn update_shared(
+        &mut self,
+        plan_id: &str,
+        shard: usize,
+        progress: Option<&ShardProgress>,
+        worker: Option<String>,
+    ) {
+        let Some(shard) = self.shard_mut(plan_id, shard) else {
+            return;
+        };
+        shard.worker = worker;
+        if let Some(progress) = progress {
+            shard.runs = progress.workers.clone();
+            // Keys redone after a worker stopped between checkpoints count
+            // twice, so only the workers' word makes a shard done
+            shard.succeeded = if progress.is_done() {
+                shard.keys.len()
+            } else {
+                progress.succeeded.min(shard.keys.len().saturating_sub(1))
+            };
+            shard.failed = progress.failed_keys.len();
+        }
+    }
+
+    /// Save after `update_shared`, dropping the shared plans whose every
+    /// shard is done. Returns those plans.
+    pub fn settle_shared(&mut self) -> Vec<ShardPlan> {
+        let (done, open) = std::mem::take(&mut self.plans)
+            .into_iter()
+            .partition(|plan| plan.shared && plan.is_done());
+        self.plans = open;
+        let _ = self.save();
+        done
+    }
+
+    /// Save, dropping the plan if every shard is done
+    fn settle(&mut self, plan_id: &str) -> Option<ShardPlan> {
+        if self.plan(plan_id).is_some_and(ShardPlan::is_done) {
+            return self.remove(plan_id);
+        }
+        let _ = self.save();
+        None
+    }
+
+    /// Drop a plan, whatever state its shards are in
+    pub fn remove(&mut self, id: &str) -> Option<ShardPlan> {
+        let index = self.plans.iter().position(|plan| plan.id == id)?;
+        let plan = self.plans.remove(index);
+        let _ = self.save();
+        Some(plan)
+    }
+
+    fn save(&self) -> Result<()> {
+        // Not pretty-printed: a plan holds every key of its batch
+        let json = serde_json::to_string(&self.plans)?;
+        fs::write(&self.file_path, json)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Size of a bucket, measured the last time it was listed in full
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BucketSize {
+    pub bytes: u64,
+    pub objects: usize,
+    pub measured_at: String, // ISO 8601 timestamp
+}
+
+/// Bucket sizes, persisted in `bucket_sizes.json`
+#[derive(Default)]
+pub struct BucketSizes {
+    file_path: PathBuf,
+    sizes: HashMap<String, BucketSize>,
+}
+
+impl BucketSizes {
+    pub fn new() -> Result<Self> {
+        let config_dir = directories::ProjectDirs::from("com", "bucket-brigade", "bucket-brigade")
+            .map(|dirs| dirs.config_dir().to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        fs::create_dir_all(&config_dir)?;
+        let file_path = config_dir.join("bucket_sizes.json");
+
+        let sizes = if file_path.exists() {
+            let content = fs::read_to_string(&file_path)?;
+            serde_json::from_str(&content).unwrap_or_default()
+        } else {
+            HashMap::new()
+        };
+
+        Ok(Self { file_path, sizes })
+    }
+
+    pub fn get(&self, bucket: &str) -> Option<&BucketSize> {
+        self.sizes.get(bucket)
+    }
+
+    pub fn record(&mut self, bucket: &str, bytes: u64, objects: usize) {
+        self.sizes.insert(
+            bucket.to_string(),
+            BucketSize {
+                bytes,
+                objects,
+                measured_at: chrono::Utc::now().to_rfc3339(),
+            },
+        );
+        let _ = self.save();
+    }
+
+    /// Total bytes of the `buckets` whose size is known, and how many of
+    /// them that is
+    pub fn total<'a>(&self, buckets: impl IntoIterator<Item = &'a str>) -> (u64, usize) {
+        buckets
+            .into_iter()
+            .filter_map(|bucket| self.sizes.get(bucket))
+            .fold((0, 0), |(bytes, known), size| {
+                (bytes + size.bytes, known + 1)
+            })
+    }
+
+    fn save(&self) -> Result<()> {
+        let json = serde_json::to_string_pretty(&self.sizes)?;
+        fs::write(&self.file_path, json)?;
+        Ok(())
+    }
+}
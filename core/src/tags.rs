@@ -0,0 +1,140 @@
+use chrono::NaiveDate;
+
+use crate::models::BucketInfo;
+
+/// Keys of the tag set every bucket taking part in a migration gets
+pub const MIGRATION_TAGS: [&str; 3] = ["project", "owner", "decommission-date"];
+
+/// S3 allows at most this many tags on a bucket
+const MAX_BUCKET_TAGS: usize = 50;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TagStep {
+    Browse,
+    /// Typing a `key=value` pair
+    Edit,
+    /// Filling in the migration tag set and the buckets it goes on
+    Standard,
+}
+
+/// Tags of one bucket, edited in place; every change is written straight away
+pub struct TagEditor {
+    pub bucket: String,
+    pub region: Option<String>,
+    pub creation_date: Option<String>,
+    /// Sorted by key
+    pub tags: Vec<(String, String)>,
+    pub cursor: usize,
+    pub step: TagStep,
+    pub input: String,
+    /// Key of the tag being edited, replaced by what is typed
+    pub editing: Option<String>,
+    /// Values of `MIGRATION_TAGS`, followed by the bucket name or `prefix*`
+    /// they are applied to
+    pub standard: [String; 4],
+    pub standard_field: usize,
+}
+
+impl TagEditor {
+    pub fn new(bucket: &BucketInfo, mut tags: Vec<(String, String)>) -> Self {
+        tags.sort();
+        let mut standard: [String; 4] = Default::default();
+        for (value, key) in standard.iter_mut().zip(MIGRATION_TAGS) {
+            if let Some((_, existing)) = tags.iter().find(|(k, _)| k == key) {
+                value.clone_from(existing);
+            }
+        }
+        standard[3] = bucket.name.clone();
+        Self {
+            bucket: bucket.name.clone(),
+            region: bucket.region.clone(),
+            creation_date: bucket.creation_date.clone(),
+            tags,
+            cursor: 0,
+            step: TagStep::Browse,
+            input: String::new(),
+            editing: None,
+            standard,
+            standard_field: 0,
+        }
+    }
+
+    pub fn selected(&self) -> Option<&(String, String)> {
+        self.tags.get(self.cursor)
+    }
+
+    /// The typed `key=value` pair, or why it cannot be used
+    pub fn parse_input(&self) -> Result<(String, String), &'static str> {
+        let (key, value) = self
+            .input
+            .split_once('=')
+            .ok_or("enter the tag as key=value")?;
+        let (key, value) = (key.trim(), value.trim());
+        validate_tag(key, value)?;
+        let is_new = self.editing.is_none() && !self.tags.iter().any(|(k, _)| k == key);
+        if is_new && self.tags.len() >= MAX_BUCKET_TAGS {
+            return Err("a bucket can have at most 50 tags");
+        }
+        Ok((key.to_string(), value.to_string()))
+    }
+
+    /// The migration tags that were given a value, or why they cannot be used
+    pub fn standard_tags(&self) -> Result<Vec<(String, String)>, &'static str> {
+        let tags: Vec<(String, String)> = MIGRATION_TAGS
+            .iter()
+            .zip(&self.standard)
+            .filter(|(_, value)| !value.trim().is_empty())
+            .map(|(key, value)| (key.to_string(), value.trim().to_string()))
+            .collect();
+        if tags.is_empty() {
+            return Err("fill in at least one tag");
+        }
+        for (key, value) in &tags {
+            validate_tag(key, value)?;
+            if key == "decommission-date" && NaiveDate::parse_from_str(value, "%Y-%m-%d").is_err() {
+                return Err("decommission-date must be YYYY-MM-DD");
+            }
+        }
+        Ok(tags)
+    }
+
+    /// Bucket name or `prefix*` the migration tags are applied to
+    pub fn standard_pattern(&self) -> &str {
+        self.standard[3].trim()
+    }
+}
+
+fn validate_tag(key: &str, value: &str) -> Result<(), &'static str> {
+    if key.is_empty() {
+        return Err("the tag key cannot be empty");
+    }
+    if key.starts_with("aws:") {
+        return Err("keys starting with aws: are reserved");
+    }
+    if key.chars().count() > 128 {
+        return Err("tag keys are limited to 128 characters");
+    }
+    if value.chars().count() > 256 {
+        return Err("tag values are limited to 256 characters");
+    }
+    Ok(())
+}
+
+/// Set each of `new` on `tags`, replacing tags with the same key
+pub fn merge_tags(tags: &mut Vec<(String, String)>, new: &[(String, String)]) {
+    for (key, value) in new {
+        match tags.iter_mut().find(|(k, _)| k == key) {
+            Some(existing) => existing.1.clone_from(value),
+            None => tags.push((key.clone(), value.clone())),
+        }
+    }
+    tags.sort();
+}
+
+/// Whether `bucket` is `pattern`, or starts with it when it ends in `*`
+pub fn bucket_matches(pattern: &str, bucket: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => bucket.starts_with(prefix),
+        None => pattern == bucket,
+    }
+}
@@ -0,0 +1,95 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Span of recent requests the telemetry panel's rates are computed over
+const TELEMETRY_WINDOW: Duration = Duration::from_secs(10);
+
+struct Sample {
+    at: Instant,
+    api: &'static str,
+    latency: Duration,
+    ok: bool,
+    bytes: u64,
+}
+
+/// Requests made by running batches, sampled as each one completes
+#[derive(Default)]
+pub struct Telemetry {
+    samples: VecDeque<Sample>,
+}
+
+/// Mean latency of one S3 API over the telemetry window
+pub struct ApiLatency {
+    pub api: &'static str,
+    pub requests: usize,
+    pub mean: Duration,
+}
+
+impl Telemetry {
+    pub fn record(&mut self, api: &'static str, latency: Duration, ok: bool, bytes: u64) {
+        let at = Instant::now();
+        while self
+            .samples
+            .front()
+            .is_some_and(|s| at.duration_since(s.at) > TELEMETRY_WINDOW)
+        {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(Sample {
+            at,
+            api,
+            latency,
+            ok,
+            bytes,
+        });
+    }
+
+    fn recent(&self) -> impl Iterator<Item = &Sample> {
+        let now = Instant::now();
+        self.samples
+            .iter()
+            .filter(move |s| now.duration_since(s.at) <= TELEMETRY_WINDOW)
+    }
+
+    pub fn requests_per_second(&self) -> f64 {
+        self.recent().count() as f64 / TELEMETRY_WINDOW.as_secs_f64()
+    }
+
+    pub fn bytes_per_second(&self) -> f64 {
+        self.recent().map(|s| s.bytes).sum::<u64>() as f64 / TELEMETRY_WINDOW.as_secs_f64()
+    }
+
+    /// Share of recent requests that failed, or `None` before any completed
+    pub fn error_rate(&self) -> Option<f64> {
+        let (total, failed) = self.recent().fold((0, 0), |(total, failed), s| {
+            (total + 1, failed + !s.ok as usize)
+        });
+        (total > 0).then(|| failed as f64 / total as f64)
+    }
+
+    /// Mean latency per API, in the order the APIs were first seen
+    pub fn latencies(&self) -> Vec<ApiLatency> {
+        let mut apis: Vec<ApiLatency> = Vec::new();
+        let mut totals: Vec<Duration> = Vec::new();
+        for sample in self.recent() {
+            match apis.iter().position(|a| a.api == sample.api) {
+                Some(index) => {
+                    apis[index].requests += 1;
+                    totals[index] += sample.latency;
+                }
+                None => {
+                    apis.push(ApiLatency {
+                        api: sample.api,
+                        requests: 1,
+                        mean: Duration::ZERO,
+                    });
+                    totals.push(sample.latency);
+                }
+            }
+        }
+        for (api, total) in apis.iter_mut().zip(totals) {
+            api.mean = total / api.requests as u32;
+        }
+        apis
+    }
+}
@@ -0,0 +1,169 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// How long a ticket lookup waits for the tracker's API
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The change ticket a job ran under, with a link to it in the tracker
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TicketLink {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// `tickets.json` as written
+#[derive(Debug, Deserialize)]
+struct TicketFile {
+    #[serde(default)]
+    required: bool,
+    #[serde(default)]
+    pattern: Option<String>,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    check_url: Option<String>,
+    #[serde(default)]
+    authorization_env: Option<String>,
+}
+
+/// How batches are tied to change tickets in Jira, ServiceNow or another
+/// tracker, from `tickets.json` in the config directory. Without the file a
+/// ticket is optional and recorded as typed.
+#[derive(Clone, Debug)]
+pub struct TicketConfig {
+    /// Refuse batches that name no ticket
+    pub required: bool,
+    /// A ticket ID must match this in full, such as `(OPS|CHG)-\d+`; kept
+    /// as written for messages, and anchored
+    pattern: Option<(String, Regex)>,
+    /// Link to a ticket, `{ticket}` standing for its ID
+    url: Option<String>,
+    /// API endpoint that answers 2xx for a ticket that exists, `{ticket}`
+    /// standing for its ID
+    check_url: Option<String>,
+    /// Environment variable holding the Authorization header for
+    /// `check_url`, such as `Bearer …` or `Basic …`
+    authorization_env: Option<String>,
+}
+
+impl TicketConfig {
+    /// The configuration in `tickets.json`, or `None` when there is none
+    pub fn load() -> Result<Option<Self>> {
+        let path = directories::ProjectDirs::from("com", "bucket-brigade", "bucket-brigade")
+            .map(|dirs| dirs.config_dir().to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("tickets.json");
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path)?;
+        let file: TicketFile = serde_json::from_str(&content)
+            .with_context(|| format!("{} is not valid", path.display()))?;
+        let pattern = file
+            .pattern
+            .map(|pattern| Regex::new(&format!("^(?:{pattern})$")).map(|regex| (pattern, regex)))
+            .transpose()
+            .context("the ticket pattern in tickets.json is not a valid regex")?;
+        Ok(Some(Self {
+            required: file.required,
+            pattern,
+            url: file.url,
+            check_url: file.check_url,
+            authorization_env: file.authorization_env,
+        }))
+    }
+
+    /// Whether tickets are looked up in the tracker before a batch runs
+    pub fn checks_tracker(&self) -> bool {
+        self.check_url.is_some()
+    }
+
+    /// Refuse an ID that does not match the configured pattern
+    pub fn check_format(&self, id: &str) -> Result<()> {
+        if id.is_empty() {
+            bail!("the ticket ID is empty");
+        }
+        if let Some((pattern, regex)) = &self.pattern
+            && !regex.is_match(id)
+        {
+            bail!("{id} is not a ticket ID of the form {pattern}");
+        }
+        Ok(())
+    }
+
+    /// The link recorded for a ticket
+    pub fn link(&self, id: &str) -> TicketLink {
+        TicketLink {
+            id: id.to_string(),
+            url: self.url.as_ref().map(|url| fill(url, id)),
+        }
+    }
+
+    /// Check `id` against the pattern and, with `check_url`, that the
+    /// tracker knows it. A ServiceNow table query answers 200 with an empty
+    /// `result` list for an unknown ticket, which counts as not found.
+    pub async fn validate(&self, id: &str) -> Result<TicketLink> {
+        self.check_format(id)?;
+        let Some(check_url) = &self.check_url else {
+            return Ok(self.link(id));
+        };
+        let mut request = reqwest::Client::new()
+            .get(fill(check_url, id))
+            .header(reqwest::header::ACCEPT, "application/json")
+            .timeout(CHECK_TIMEOUT);
+        if let Some(var) = &self.authorization_env {
+            let value = std::env::var(var)
+                .with_context(|| format!("{var} must hold the tracker's Authorization header"))?;
+            request = request.header(reqwest::header::AUTHORIZATION, value);
+        }
+        let response = request.send().await.context("the ticket lookup failed")?;
+        let status = response.status();
+        if status == reqwest::StatusCode::NOT_FOUND {
+            bail!("the tracker has no ticket {id}");
+        }
+        if !status.is_success() {
+            bail!("the tracker answered {status} for ticket {id}");
+        }
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        if body
+            .get("result")
+            .and_then(|result| result.as_array())
+            .is_some_and(|result| result.is_empty())
+        {
+            bail!("the tracker has no ticket {id}");
+        }
+        Ok(self.link(id))
+    }
+}
+
+/// The ticket a batch runs under: `id` validated against `config`, or
+/// recorded as given when there is no `tickets.json`. Refuses a batch
+/// without a ticket when one is required.
+pub async fn resolve(
+    config: Option<&TicketConfig>,
+    id: Option<&str>,
+) -> Result<Option<TicketLink>> {
+    let id = id.map(str::trim).filter(|id| !id.is_empty());
+    match (config, id) {
+        (Some(config), Some(id)) => config.validate(id).await.map(Some),
+        (Some(config), None) if config.required => {
+            bail!("tickets.json requires a change ticket for every batch")
+        }
+        (None, Some(id)) => Ok(Some(TicketLink {
+            id: id.to_string(),
+            url: None,
+        })),
+        (_, None) => Ok(None),
+    }
+}
+
+/// `template` with `{ticket}` replaced by the encoded ID
+fn fill(template: &str, id: &str) -> String {
+    template.replace("{ticket}", &urlencoding::encode(id))
+}
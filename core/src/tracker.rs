@@ -0,0 +1,242 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::models::{RestoreState, RestoreTier, StorageClassTier, TrackedRestoreRequest};
+use crate::waves::WavePlan;
+
+pub struct RestoreTracker {
+    file_path: PathBuf,
+    requests: Vec<TrackedRestoreRequest>,
+    waves_path: PathBuf,
+    wave_plans: Vec<WavePlan>,
+}
+
+impl RestoreTracker {
+    pub fn new() -> Result<Self> {
+        let config_dir = directories::ProjectDirs::from("com", "bucket-brigade", "bucket-brigade")
+            .map(|dirs| dirs.config_dir().to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        fs::create_dir_all(&config_dir)?;
+        let file_path = config_dir.join("restore_requests.json");
+
+        let requests = if file_path.exists() {
+            let content = fs::read_to_string(&file_path)?;
+            serde_json::from_str(&content).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        let waves_path = config_dir.join("restore_waves.json");
+        let wave_plans = if waves_path.exists() {
+            let content = fs::read_to_string(&waves_path)?;
+            serde_json::from_str(&content).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            file_path,
+            requests,
+            waves_path,
+            wave_plans,
+        })
+    }
+
+    pub fn add_request(
+        &mut self,
+        bucket: String,
+        key: String,
+        days: i32,
+        storage_class: StorageClassTier,
+        tier: RestoreTier,
+        quiet: bool,
+    ) {
+        let now = chrono::Utc::now().to_rfc3339();
+        // A new request for the same object replaces the old one
+        self.requests
+            .retain(|r| !(r.bucket == bucket && r.key == key));
+        self.requests.push(TrackedRestoreRequest {
+            bucket,
+            key,
+            requested_at: now,
+            days,
+            current_status: RestoreState::InProgress { expiry: None },
+            storage_class: Some(storage_class),
+            tier,
+            ready_at: None,
+            expires_at: None,
+            quiet,
+        });
+        let _ = self.save();
+    }
+
+    /// Update the status of a tracked restore request, and when the restored
+    /// copy expires if HeadObject said so.
+    /// Returns true when the request has just become available.
+    pub fn update_status(
+        &mut self,
+        bucket: &str,
+        key: &str,
+        status: RestoreState,
+        expires_at: Option<String>,
+    ) -> bool {
+        let mut became_ready = false;
+        if let Some(req) = self
+            .requests
+            .iter_mut()
+            .find(|r| r.bucket == bucket && r.key == key)
+        {
+            became_ready = matches!(status, RestoreState::Available)
+                && !matches!(req.current_status, RestoreState::Available);
+            if became_ready {
+                req.ready_at = Some(chrono::Utc::now().to_rfc3339());
+            }
+            req.current_status = status;
+            if expires_at.is_some() {
+                req.expires_at = expires_at;
+            }
+            let _ = self.save();
+        }
+        became_ready
+    }
+
+    /// Get only active (in-progress) restore requests
+    pub fn get_active_requests(&self) -> Vec<TrackedRestoreRequest> {
+        self.requests
+            .iter()
+            .filter(|r| {
+                !matches!(
+                    r.current_status,
+                    RestoreState::Available | RestoreState::Expired
+                )
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Re-read the request list and wave plans so those saved by another
+    /// instance are seen
+    pub fn reload(&mut self) {
+        if let Ok(content) = fs::read_to_string(&self.file_path)
+            && let Ok(requests) = serde_json::from_str(&content)
+        {
+            self.requests = requests;
+        }
+        if let Ok(content) = fs::read_to_string(&self.waves_path)
+            && let Ok(plans) = serde_json::from_str(&content)
+        {
+            self.wave_plans = plans;
+        }
+    }
+
+    /// Whether the object is already in the restore queue: its restore is in
+    /// progress, or it waits in a wave of an open plan that is not out yet
+    pub fn is_queued(&self, bucket: &str, key: &str) -> bool {
+        self.requests.iter().any(|r| {
+            r.bucket == bucket
+                && r.key == key
+                && matches!(r.current_status, RestoreState::InProgress { .. })
+        }) || self.wave_plans.iter().any(|plan| {
+            plan.bucket == bucket
+                && plan
+                    .waves
+                    .iter()
+                    .any(|w| w.submitted_at.is_none() && w.keys.iter().any(|k| k == key))
+        })
+    }
+
+    /// Coalesce `keys` into the restore queue: keys listed more than once
+    /// are kept once, and keys already queued (see `is_queued`) are dropped,
+    /// so each object gets one restore request however many masks or
+    /// selections named it
+    pub fn coalesce(&self, bucket: &str, keys: &[String]) -> Vec<String> {
+        let mut seen = HashSet::new();
+        keys.iter()
+            .filter(|key| seen.insert(key.as_str()) && !self.is_queued(bucket, key))
+            .cloned()
+            .collect()
+    }
+
+    /// Whether a restore of the object was requested at or after `since`
+    pub fn requested_since(&self, bucket: &str, key: &str, since: DateTime<Utc>) -> bool {
+        self.requests.iter().any(|r| {
+            r.bucket == bucket
+                && r.key == key
+                && DateTime::parse_from_rfc3339(&r.requested_at).is_ok_and(|at| at >= since)
+        })
+    }
+
+    pub fn get_all_requests(&self) -> &[TrackedRestoreRequest] {
+        &self.requests
+    }
+
+    /// Remove completed or expired restore requests from tracking
+    pub fn remove_completed(&mut self) {
+        self.requests.retain(|r| {
+            !matches!(
+                r.current_status,
+                RestoreState::Available | RestoreState::Expired
+            )
+        });
+        let _ = self.save();
+    }
+
+    pub fn wave_plans(&self) -> &[WavePlan] {
+        &self.wave_plans
+    }
+
+    pub fn wave_plan(&self, id: &str) -> Option<&WavePlan> {
+        self.wave_plans.iter().find(|p| p.id == id)
+    }
+
+    pub fn add_wave_plan(&mut self, plan: WavePlan) {
+        self.wave_plans.push(plan);
+        let _ = self.save_waves();
+    }
+
+    /// Record that a wave has been submitted; plans are dropped once their
+    /// last wave is out
+    pub fn mark_wave_submitted(&mut self, id: &str, wave: usize) {
+        if let Some(plan) = self.wave_plans.iter_mut().find(|p| p.id == id)
+            && let Some(wave) = plan.waves.get_mut(wave)
+        {
+            wave.submitted_at = Some(chrono::Utc::now().to_rfc3339());
+        }
+        self.wave_plans.retain(|p| p.next_wave().is_some());
+        let _ = self.save_waves();
+    }
+
+    /// The next wave to submit: (plan id, wave index) of the first plan whose
+    /// current wave has no restore still in progress
+    pub fn due_wave(&self) -> Option<(String, usize)> {
+        self.wave_plans.iter().find_map(|plan| {
+            let next = plan.next_wave()?;
+            let waiting = plan.current_wave().is_some_and(|wave| {
+                wave.keys.iter().any(|key| {
+                    self.requests.iter().any(|r| {
+                        r.bucket == plan.bucket
+                            && &r.key == key
+                            && matches!(r.current_status, RestoreState::InProgress { .. })
+                    })
+                })
+            });
+            (!waiting).then(|| (plan.id.clone(), next))
+        })
+    }
+
+    fn save(&self) -> Result<()> {
+        let json = serde_json::to_string_pretty(&self.requests)?;
+        fs::write(&self.file_path, json)?;
+        Ok(())
+    }
+
+    fn save_waves(&self) -> Result<()> {
+        let json = serde_json::to_string_pretty(&self.wave_plans)?;
+        fs::write(&self.waves_path, json)?;
+        Ok(())
+    }
+}
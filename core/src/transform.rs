@@ -0,0 +1,177 @@
+use std::cell::Cell;
+use std::io::ErrorKind;
+use std::process::Stdio;
+
+use anyhow::{Context, Result, bail};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::process::{Child, ChildStderr, ChildStdin, ChildStdout};
+
+use crate::aws::S3Service;
+use crate::hooks::shell;
+use crate::models::StorageClassTier;
+use crate::multipart::StreamUpload;
+
+/// Bytes read from the command's stdout at a time
+const READ_SIZE: usize = 256 * 1024;
+
+/// Command a copy pipes each object through on its way to the destination
+/// (`--transform`), such as `gzip -c` or a WASI runtime running a module. It
+/// reads the object on stdin and writes the new content to stdout; the key
+/// and bucket are passed as `BUCKET_BRIGADE_KEY` and `BUCKET_BRIGADE_BUCKET`.
+#[derive(Clone, Debug)]
+pub struct Transform {
+    pub command: String,
+}
+
+/// Bytes that went into and came out of one transformed copy
+#[derive(Clone, Copy, Debug)]
+pub struct Transformed {
+    pub read: i64,
+    pub written: i64,
+}
+
+impl Transform {
+    pub fn from_args(args: &[String]) -> Option<Self> {
+        let command = args
+            .windows(2)
+            .find(|pair| pair[0] == "--transform")
+            .map(|pair| pair[1].trim())
+            .filter(|command| !command.is_empty())?;
+        Some(Self {
+            command: command.to_string(),
+        })
+    }
+
+    /// Stream `key` from `source_bucket` through the command to `dest_key` in
+    /// `dest_bucket` with a `StreamUpload`, in `storage_class` or else the
+    /// source's class. The upload's id is kept in `upload` while it is open,
+    /// so a caller that drops this future can abort it; a failure aborts it
+    /// here.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn copy(
+        &self,
+        s3: &S3Service,
+        source_bucket: &str,
+        dest_bucket: &str,
+        key: &str,
+        dest_key: &str,
+        storage_class: Option<StorageClassTier>,
+        upload: &Cell<Option<String>>,
+    ) -> Result<Transformed> {
+        let result = self
+            .pipe(
+                s3,
+                source_bucket,
+                dest_bucket,
+                key,
+                dest_key,
+                storage_class,
+                upload,
+            )
+            .await;
+        if result.is_err() {
+            StreamUpload::abort(s3, dest_bucket, dest_key, upload).await;
+        }
+        result
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn pipe(
+        &self,
+        s3: &S3Service,
+        source_bucket: &str,
+        dest_bucket: &str,
+        key: &str,
+        dest_key: &str,
+        storage_class: Option<StorageClassTier>,
+        upload: &Cell<Option<String>>,
+    ) -> Result<Transformed> {
+        let source = s3.get_object_stream(source_bucket, key).await?;
+        let mut child = shell(&self.command)
+            .env("BUCKET_BRIGADE_BUCKET", source_bucket)
+            .env("BUCKET_BRIGADE_KEY", key)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .context("could not start the transform")?;
+        let stdin = child.stdin.take().context("transform has no stdin")?;
+        let stdout = child.stdout.take().context("transform has no stdout")?;
+        let stderr = child.stderr.take().context("transform has no stderr")?;
+
+        let mut body = source.body;
+        let feed = async move {
+            let mut stdin = stdin;
+            while let Some(chunk) = body.try_next().await? {
+                if !write_input(&mut stdin, &chunk).await? {
+                    break;
+                }
+            }
+            // Dropping stdin closes it, which tells the command the input ended
+            anyhow::Ok(())
+        };
+        let mut attributes = source.attributes;
+        if storage_class.is_some() {
+            attributes.storage_class = storage_class;
+        }
+        // The output is sized like the input until it turns out larger
+        let mut output =
+            StreamUpload::new(s3, dest_bucket, dest_key, attributes, upload, source.size);
+        finish_piped(child, stdout, stderr, feed, &mut output, "transform").await?;
+        let written = output.finish().await?;
+        Ok(Transformed {
+            read: source.size,
+            written,
+        })
+    }
+}
+
+/// Write `data` to a command's stdin. Returns false once the command stopped
+/// reading, after which its output so far still counts.
+pub(crate) async fn write_input(stdin: &mut ChildStdin, data: &[u8]) -> Result<bool> {
+    match stdin.write_all(data).await {
+        Err(err) if err.kind() == ErrorKind::BrokenPipe => Ok(false),
+        result => result.map(|_| true).map_err(Into::into),
+    }
+}
+
+/// Run `feed`, which writes the command's input, while the command's stdout
+/// goes into `output` and its stderr is collected. Fails when the command
+/// exits unsuccessfully, naming it as `what` with the first line of its
+/// stderr; `output` is left for the caller to finish.
+pub(crate) async fn finish_piped(
+    mut child: Child,
+    mut stdout: ChildStdout,
+    mut stderr: ChildStderr,
+    feed: impl Future<Output = Result<()>>,
+    output: &mut StreamUpload<'_>,
+    what: &str,
+) -> Result<()> {
+    let drain = async {
+        let mut chunk = vec![0; READ_SIZE];
+        loop {
+            let read = stdout.read(&mut chunk).await?;
+            if read == 0 {
+                break;
+            }
+            output.write(&chunk[..read]).await?;
+        }
+        anyhow::Ok(())
+    };
+    let mut errors = Vec::new();
+    let collect = async {
+        stderr.read_to_end(&mut errors).await?;
+        anyhow::Ok(())
+    };
+    tokio::try_join!(feed, drain, collect)?;
+    let status = child.wait().await?;
+    if !status.success() {
+        let errors = String::from_utf8_lossy(&errors);
+        match errors.lines().find(|line| !line.trim().is_empty()) {
+            Some(line) => bail!("{what} {status}: {}", line.trim()),
+            None => bail!("{what} {status}"),
+        }
+    }
+    Ok(())
+}
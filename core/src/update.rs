@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde::Deserialize;
+
+/// Latest release of bucket-brigade, as served by the GitHub releases API
+pub const DEFAULT_RELEASE_URL: &str =
+    "https://api.github.com/repos/StarCityGroup/s3-migration-manager/releases/latest";
+
+/// Environment variable overriding the release endpoint, e.g. for a mirror
+pub const RELEASE_URL_ENV: &str = "BUCKET_BRIGADE_RELEASE_URL";
+
+/// How long the startup check waits for the release endpoint
+const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Where to look for newer versions at startup. `--no-update-check` turns
+/// the check off; `--release-url` or `BUCKET_BRIGADE_RELEASE_URL` point it at
+/// another endpoint that answers like GitHub's latest-release API.
+#[derive(Clone, Debug)]
+pub struct UpdateCheck {
+    pub url: String,
+}
+
+impl UpdateCheck {
+    pub fn from_args(args: &[String]) -> Option<Self> {
+        if args.iter().any(|arg| arg == "--no-update-check") {
+            return None;
+        }
+        let url = args
+            .iter()
+            .position(|arg| arg == "--release-url")
+            .and_then(|i| args.get(i + 1).cloned())
+            .or_else(|| std::env::var(RELEASE_URL_ENV).ok())
+            .filter(|url| !url.is_empty())
+            .unwrap_or_else(|| DEFAULT_RELEASE_URL.to_string());
+        Some(Self { url })
+    }
+
+    /// The latest release when it is newer than `current`
+    pub async fn newer_than(&self, current: &str) -> Result<Option<Release>> {
+        let release: Release = reqwest::Client::new()
+            .get(&self.url)
+            // GitHub rejects API requests without a user agent
+            .header(
+                reqwest::header::USER_AGENT,
+                format!("bucket-brigade/{current}"),
+            )
+            .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+            .timeout(CHECK_TIMEOUT)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(release.is_newer_than(current).then_some(release))
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Release {
+    /// Release tag such as `v0.3.1`
+    pub tag_name: String,
+    /// Release page, for the notice
+    #[serde(default)]
+    pub html_url: Option<String>,
+}
+
+impl Release {
+    /// The version without a leading `v`
+    pub fn version(&self) -> &str {
+        self.tag_name.trim_start_matches('v')
+    }
+
+    fn is_newer_than(&self, current: &str) -> bool {
+        match (parse_version(self.version()), parse_version(current)) {
+            (Some(latest), Some(current)) => latest > current,
+            _ => false,
+        }
+    }
+}
+
+/// Numeric components of `1.2.3`, ignoring any `-pre` or `+build` suffix
+fn parse_version(version: &str) -> Option<Vec<u64>> {
+    version
+        .split(['-', '+'])
+        .next()?
+        .split('.')
+        .map(|part| part.parse().ok())
+        .collect()
+}
@@ -0,0 +1,222 @@
+use anyhow::{Context, Result, bail};
+
+use crate::aws::S3Service;
+use crate::errors;
+use crate::models::{ObjectInfo, RestoreStatus, StorageClassTier};
+use crate::multipart::MULTIPART_COPY_THRESHOLD;
+
+/// One entry of a key's history, written again by copying it, or for a
+/// delete marker by placing a new marker
+#[derive(Clone, Debug)]
+struct ReplayStep {
+    version_id: String,
+    delete_marker: bool,
+    /// The target class for a version being moved, its own class otherwise
+    class: StorageClassTier,
+    moved: bool,
+    size: i64,
+}
+
+/// How the noncurrent versions of one key move to another class.
+///
+/// CopyObject always writes the newest version of its key, so a noncurrent
+/// version cannot change class in place. Instead the history from the
+/// oldest version to move up to the latest is copied again in order, the
+/// moved versions in the target class and the rest in their own, and the
+/// originals are deleted once every copy is written. The key keeps its
+/// content and version order; the replayed versions get new version IDs.
+#[derive(Clone, Debug)]
+pub struct VersionReplay {
+    pub key: String,
+    /// Oldest first
+    steps: Vec<ReplayStep>,
+    /// Version IDs of the key as planned, newest first; a replay stops if
+    /// the key's history no longer matches them
+    listed: Vec<String>,
+}
+
+impl VersionReplay {
+    /// Plan the replay of `versions`, every listed version and delete marker
+    /// of one key, newest first. `None` when no noncurrent version of the key
+    /// needs to move.
+    pub fn plan(versions: &[&ObjectInfo], target: &StorageClassTier) -> Result<Option<Self>> {
+        let Some(first) = versions.first() else {
+            return Ok(None);
+        };
+        let moves = |obj: &ObjectInfo| {
+            obj.is_noncurrent()
+                && obj.version.as_ref().is_some_and(|v| !v.delete_marker)
+                && obj.storage_class != *target
+        };
+        let Some(oldest) = versions.iter().rposition(|obj| moves(obj)) else {
+            return Ok(None);
+        };
+        if !first.version.as_ref().is_some_and(|v| v.is_latest) {
+            bail!("its latest version is not listed");
+        }
+        let mut steps = Vec::with_capacity(oldest + 1);
+        for obj in versions[..=oldest].iter().rev() {
+            let Some(version) = &obj.version else {
+                bail!("a row without a version ID is listed with its versions");
+            };
+            let moved = moves(obj);
+            if !version.delete_marker {
+                if obj.size > MULTIPART_COPY_THRESHOLD {
+                    bail!(
+                        "version {} is larger than a single CopyObject can copy",
+                        version.id
+                    );
+                }
+                if matches!(
+                    RestoreStatus::of(obj),
+                    Some(
+                        RestoreStatus::NeedsRestore
+                            | RestoreStatus::Restoring
+                            | RestoreStatus::Expired
+                    )
+                ) {
+                    bail!(
+                        "version {} is archived in {} and has no completed restore",
+                        version.id,
+                        obj.storage_class.label()
+                    );
+                }
+                if !moved && obj.storage_class.to_sdk().is_none() {
+                    bail!(
+                        "version {} is in {}, which cannot be copied back",
+                        version.id,
+                        obj.storage_class.label()
+                    );
+                }
+            }
+            steps.push(ReplayStep {
+                version_id: version.id.clone(),
+                delete_marker: version.delete_marker,
+                class: if moved {
+                    target.clone()
+                } else {
+                    obj.storage_class.clone()
+                },
+                moved,
+                size: obj.size,
+            });
+        }
+        Ok(Some(Self {
+            key: first.key.clone(),
+            steps,
+            listed: versions
+                .iter()
+                .filter_map(|obj| obj.version.as_ref().map(|v| v.id.clone()))
+                .collect(),
+        }))
+    }
+
+    /// Versions that land in the target class
+    pub fn moved(&self) -> usize {
+        self.steps.iter().filter(|step| step.moved).count()
+    }
+
+    pub fn moved_bytes(&self) -> i64 {
+        self.steps
+            .iter()
+            .filter(|step| step.moved)
+            .map(|step| step.size)
+            .sum()
+    }
+
+    /// Versions and delete markers copied again only to keep the order
+    pub fn rewritten(&self) -> usize {
+        self.steps.len() - self.moved()
+    }
+
+    /// Version IDs of the key as S3 lists them now, newest first
+    async fn history(&self, s3: &S3Service, bucket: &str) -> Result<Vec<String>> {
+        let versions = s3.list_key_versions(bucket, &self.key).await?;
+        Ok(versions
+            .into_iter()
+            .filter_map(|obj| obj.version.map(|v| v.id))
+            .collect())
+    }
+
+    /// Copy the history again and delete the originals. When a copy fails,
+    /// the copies written so far are deleted and the originals left alone.
+    /// The key's versions are listed again before the first copy and before
+    /// the deletes; if anything else wrote to the key, nothing is deleted.
+    pub async fn run(&self, s3: &S3Service, bucket: &str) -> Result<()> {
+        let history = self
+            .history(s3, bucket)
+            .await
+            .context("could not list the key's versions again")?;
+        if history != self.listed {
+            bail!("its versions changed since the replay was planned; nothing was copied");
+        }
+        let mut written = Vec::with_capacity(self.steps.len());
+        for step in &self.steps {
+            let copy = if step.delete_marker {
+                s3.put_delete_marker(bucket, &self.key).await
+            } else {
+                s3.copy_object_version(bucket, &self.key, &step.version_id, &step.class)
+                    .await
+            };
+            match copy {
+                Ok(version_id) => written.push(version_id),
+                Err(err) => {
+                    let mut left = Vec::new();
+                    for version_id in written.iter().rev() {
+                        if s3
+                            .delete_object_version(bucket, &self.key, version_id)
+                            .await
+                            .is_err()
+                        {
+                            left.push(version_id.as_str());
+                        }
+                    }
+                    let mut reason = format!(
+                        "copying version {} failed: {}",
+                        step.version_id,
+                        errors::describe(&err)
+                    );
+                    if !left.is_empty() {
+                        reason.push_str(&format!(
+                            "; removing the copies failed too, delete versions {} by hand",
+                            left.join(", ")
+                        ));
+                    }
+                    bail!("{reason}");
+                }
+            }
+        }
+        // Newest first: the copies, then the history they were made from
+        let expected: Vec<&str> = written
+            .iter()
+            .rev()
+            .chain(&self.listed)
+            .map(String::as_str)
+            .collect();
+        let kept = |why: &str| {
+            format!(
+                "{why}, so no original was deleted; versions {} are the copies, delete the ones you do not need by hand",
+                written.join(", ")
+            )
+        };
+        match self.history(s3, bucket).await {
+            Ok(history) if history.iter().map(String::as_str).eq(expected) => {}
+            Ok(_) => bail!(kept("the key changed while its versions were copied")),
+            Err(err) => bail!(kept(&format!(
+                "listing its versions again failed: {}",
+                errors::describe(&err)
+            ))),
+        }
+        for step in &self.steps {
+            s3.delete_object_version(bucket, &self.key, &step.version_id)
+                .await
+                .with_context(|| {
+                    format!(
+                        "the copies are written, but deleting the original version {} failed",
+                        step.version_id
+                    )
+                })?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,130 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::RestoreTier;
+use crate::tickets::TicketLink;
+
+/// One slice of a wave plan, submitted as a single restore batch
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RestoreWave {
+    pub keys: Vec<String>,
+    pub bytes: i64,
+    #[serde(default)]
+    pub submitted_at: Option<String>,
+}
+
+/// A large restore split into waves of at most `wave_bytes`. The first wave is
+/// submitted straight away; each later one once every restore of the wave
+/// before it has finished.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WavePlan {
+    pub id: String,
+    pub bucket: String,
+    pub days: i32,
+    #[serde(default)]
+    pub tier: RestoreTier,
+    pub wave_bytes: u64,
+    pub waves: Vec<RestoreWave>,
+    /// Restore preset the later waves are submitted with
+    #[serde(default)]
+    pub preset: Option<String>,
+    /// The operator's note on the restore, carried into each wave's job
+    #[serde(default)]
+    pub note: Option<String>,
+    /// The restore's change ticket, carried into each wave's job
+    #[serde(default)]
+    pub ticket: Option<TicketLink>,
+}
+
+impl WavePlan {
+    /// Split `objects` (key, size) into waves, keeping their order. An object
+    /// larger than the budget gets a wave of its own.
+    pub fn new(
+        bucket: &str,
+        days: i32,
+        tier: RestoreTier,
+        wave_bytes: u64,
+        objects: Vec<(String, i64)>,
+    ) -> Self {
+        let mut waves: Vec<RestoreWave> = Vec::new();
+        for (key, size) in objects {
+            let fits = waves
+                .last()
+                .is_some_and(|w| (w.bytes + size) as u64 <= wave_bytes);
+            if !fits {
+                waves.push(RestoreWave {
+                    keys: Vec::new(),
+                    bytes: 0,
+                    submitted_at: None,
+                });
+            }
+            if let Some(wave) = waves.last_mut() {
+                wave.keys.push(key);
+                wave.bytes += size;
+            }
+        }
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            bucket: bucket.to_string(),
+            days,
+            tier,
+            wave_bytes,
+            waves,
+            preset: None,
+            note: None,
+            ticket: None,
+        }
+    }
+
+    /// Index of the first wave not submitted yet
+    pub fn next_wave(&self) -> Option<usize> {
+        self.waves.iter().position(|w| w.submitted_at.is_none())
+    }
+
+    /// The most recently submitted wave
+    pub fn current_wave(&self) -> Option<&RestoreWave> {
+        self.waves.iter().rev().find(|w| w.submitted_at.is_some())
+    }
+
+    pub fn submitted(&self) -> usize {
+        self.waves
+            .iter()
+            .filter(|w| w.submitted_at.is_some())
+            .count()
+    }
+}
+
+/// Parse a size such as `1TB`, `500GB` or `750 MiB` (binary units) into bytes
+pub fn parse_size(text: &str) -> Option<u64> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let multiplier: u64 = match unit.trim().to_ascii_uppercase().as_str() {
+        "" | "B" => 1,
+        "K" | "KB" | "KIB" => 1 << 10,
+        "M" | "MB" | "MIB" => 1 << 20,
+        "G" | "GB" | "GIB" => 1 << 30,
+        "T" | "TB" | "TIB" => 1 << 40,
+        _ => return None,
+    };
+    let bytes = (number * multiplier as f64) as u64;
+    (bytes > 0).then_some(bytes)
+}
+
+/// "1.5 TB" style size for wave budgets
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
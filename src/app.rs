@@ -1,14 +1,72 @@
-use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::Instant;
 
+use tokio::task::JoinHandle;
+
+use crate::access::AccessAudit;
+use crate::approvals::ApprovalStore;
+use crate::athena::AthenaConfig;
+use crate::bandwidth::{Throttle, ThroughputLog};
+use crate::batch::ShutdownChoice;
+use crate::bucket_config::ConfigCopy;
+use crate::bundle::{ArchiveCompression, BundleCatalog, BundleEntry};
+use crate::coldness::{Activity, Coldness};
+use crate::collisions::CollisionReview;
+use crate::connectivity::{Connectivity, NetworkFailure};
+use crate::coordination::{ShardProgress, SharedState};
+use crate::crash;
+use crate::decommission::Decommission;
+use crate::dryrun::DryRun;
+use crate::enrich::EnrichHandle;
+use crate::errors::{self, ErrorKind};
+use crate::event::AppEvent;
+use crate::faults::FaultInjection;
+use crate::hooks::ObjectHook;
+use crate::i18n::{t, tf};
+use crate::jobs::JobQueue;
+use crate::lifecycle::{LifecycleRule, MechanismComparison};
+use crate::loader::ListingHandle;
 use crate::mask::{MaskKind, ObjectMask};
-use crate::models::{BucketInfo, ObjectInfo, StorageClassTier};
+use crate::models::{
+    BucketInfo, CopyMode, JournalEntry, KeyRewrite, NewBucket, ObjectInfo, ObjectLockPlan,
+    RestoreState, RestoreStatus, RestoreTier, StorageClassTier,
+};
+use crate::moves::MoveStore;
+use crate::owners::OwnerDirectory;
+use crate::policy::{MigrationPolicy, PolicyStore};
+use crate::presets::{RestorePreset, RestorePresets};
+use crate::preview::TargetPreview;
+use crate::protect::ProtectedKeys;
+use crate::recency::{LastReads, RecencyConfig};
+use crate::reconcile::{ReconcileHandle, Reconciliation};
+use crate::schedule::{ExecutionWindow, Schedule};
+use crate::shards::{DEFAULT_SHARD_KEYS, Shard, ShardPlan, ShardStore};
+use crate::sizes::BucketSizes;
+use crate::tags::TagEditor;
+use crate::telemetry::Telemetry;
+use crate::tickets::{TicketConfig, TicketLink};
+use crate::transform::Transform;
+use crate::update::Release;
+use crate::versions::VersionReplay;
+use crate::waves::format_bytes;
+
+/// Version of the running binary, shown in the header
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Status messages kept for the status bar and the log view
+const STATUS_LIMIT: usize = 500;
+
+/// Messages kept per job log; older ones are dropped and counted
+const JOB_LOG_LIMIT: usize = 2000;
 
-const STATUS_LIMIT: usize = 20;
+/// Job logs kept this session; the oldest job's log goes first
+const JOB_LOGS_KEPT: usize = 20;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ActivePane {
     Buckets,
     Objects,
+    PeerObjects,
     MaskEditor,
 }
 
@@ -23,11 +81,57 @@ pub enum AppMode {
     ViewingRestoreRequests,
     CredentialError,
     ShowingProgress,
+    JumpingToKey,
+    /// Typing the KMS key to re-encrypt the target objects under
+    EnteringKmsKey,
+    /// Typing the retain-until date of a transition's Object Lock retention
+    EnteringRetainUntil,
+    /// Typing the note the confirmed action's jobs are journaled with
+    EnteringJobNote,
+    /// Typing the change ticket the confirmed action runs under
+    EnteringTicket,
+    /// Findings of the split view's reconciliation
+    ViewingReconciliation,
+    ViewingKey,
+    ViewingJobHistory,
+    ViewingJobDetail,
+    ResumingUploads,
+    ConfirmingShutdown,
+    BatchPaused,
+    QueryingInventory,
+    ProtectingKeys,
+    PreviewingTargets,
+    /// What a pending transition would do with each target, from the confirmation
+    ViewingDryRun,
+    /// Lifecycle rule and client-side transition costs, before exporting
+    ComparingMechanisms,
+    SchedulingAction,
+    Decommissioning,
+    /// Public access and wide grants of the selected bucket
+    AuditingAccess,
+    CreatingBucket,
+    /// Destination bucket, key prefix and storage class of a copy of the
+    /// targets to another bucket
+    ChoosingCopyTarget,
+    /// Destination keys the renaming copy being confirmed would overwrite
+    ResolvingCollisions,
+    CopyingBucketConfig,
+    EditingBucketTags,
+    CommandPalette,
+    EditingScript,
+    /// Saved migration policies: list, create, edit, delete and run
+    ManagingPolicies,
+    /// Members of known bundle archives, picked for retrieval
+    ViewingBundle,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum StorageIntent {
     Transition,
+    /// Export the active prefix mask as a lifecycle rule to the picked class
+    ExportLifecycle,
+    /// Target class of the policy being created or edited
+    SavePolicy,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -36,6 +140,12 @@ pub enum MaskEditorField {
     Mode,
     Case,
     StorageClass,
+    Owner,
+    Unread,
+    MinSize,
+    MaxSize,
+    OlderThan,
+    NewerThan,
 }
 
 impl MaskEditorField {
@@ -44,18 +154,101 @@ impl MaskEditorField {
             MaskEditorField::Pattern => MaskEditorField::Mode,
             MaskEditorField::Mode => MaskEditorField::Case,
             MaskEditorField::Case => MaskEditorField::StorageClass,
-            MaskEditorField::StorageClass => MaskEditorField::Pattern,
+            MaskEditorField::StorageClass => MaskEditorField::Owner,
+            MaskEditorField::Owner => MaskEditorField::Unread,
+            MaskEditorField::Unread => MaskEditorField::MinSize,
+            MaskEditorField::MinSize => MaskEditorField::MaxSize,
+            MaskEditorField::MaxSize => MaskEditorField::OlderThan,
+            MaskEditorField::OlderThan => MaskEditorField::NewerThan,
+            MaskEditorField::NewerThan => MaskEditorField::Pattern,
         }
     }
 
     pub fn previous(self) -> Self {
         match self {
-            MaskEditorField::Pattern => MaskEditorField::StorageClass,
+            MaskEditorField::Pattern => MaskEditorField::NewerThan,
             MaskEditorField::Mode => MaskEditorField::Pattern,
             MaskEditorField::Case => MaskEditorField::Mode,
             MaskEditorField::StorageClass => MaskEditorField::Case,
+            MaskEditorField::Owner => MaskEditorField::StorageClass,
+            MaskEditorField::Unread => MaskEditorField::Owner,
+            MaskEditorField::MinSize => MaskEditorField::Unread,
+            MaskEditorField::MaxSize => MaskEditorField::MinSize,
+            MaskEditorField::OlderThan => MaskEditorField::MaxSize,
+            MaskEditorField::NewerThan => MaskEditorField::OlderThan,
+        }
+    }
+}
+
+/// Choices of the mask editor's unread filter, in days
+pub const UNREAD_DAY_CHOICES: [Option<u32>; 5] = [None, Some(30), Some(90), Some(180), Some(365)];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BucketFormField {
+    Name,
+    Region,
+    Versioning,
+    Encryption,
+    PublicAccess,
+}
+
+impl BucketFormField {
+    pub fn next(self) -> Self {
+        match self {
+            BucketFormField::Name => BucketFormField::Region,
+            BucketFormField::Region => BucketFormField::Versioning,
+            BucketFormField::Versioning => BucketFormField::Encryption,
+            BucketFormField::Encryption => BucketFormField::PublicAccess,
+            BucketFormField::PublicAccess => BucketFormField::Name,
+        }
+    }
+
+    pub fn previous(self) -> Self {
+        match self {
+            BucketFormField::Name => BucketFormField::PublicAccess,
+            BucketFormField::Region => BucketFormField::Name,
+            BucketFormField::Versioning => BucketFormField::Region,
+            BucketFormField::Encryption => BucketFormField::Versioning,
+            BucketFormField::PublicAccess => BucketFormField::Encryption,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CopyFormField {
+    Bucket,
+    From,
+    To,
+    StorageClass,
+}
+
+impl CopyFormField {
+    pub fn next(self) -> Self {
+        match self {
+            CopyFormField::Bucket => CopyFormField::From,
+            CopyFormField::From => CopyFormField::To,
+            CopyFormField::To => CopyFormField::StorageClass,
+            CopyFormField::StorageClass => CopyFormField::Bucket,
         }
     }
+
+    pub fn previous(self) -> Self {
+        match self {
+            CopyFormField::Bucket => CopyFormField::StorageClass,
+            CopyFormField::From => CopyFormField::Bucket,
+            CopyFormField::To => CopyFormField::From,
+            CopyFormField::StorageClass => CopyFormField::To,
+        }
+    }
+}
+
+/// Copy to another bucket, as typed in its form
+#[derive(Clone, Debug, Default)]
+pub struct CopyDraft {
+    pub dest_bucket: String,
+    pub rewrite: KeyRewrite,
+    /// `None` for S3's default, Standard
+    pub storage_class: Option<StorageClassTier>,
 }
 
 #[derive(Clone, Debug)]
@@ -66,6 +259,19 @@ pub struct MaskDraft {
     pub storage_class_filter: Option<StorageClassTier>,
     pub storage_class_cursor: usize,
     pub cursor_pos: usize,
+    pub owner: String,
+    pub owner_cursor: usize,
+    pub unread_days: Option<u32>,
+    /// Size bounds as typed, such as `1GB`
+    pub min_size: String,
+    pub min_size_cursor: usize,
+    pub max_size: String,
+    pub max_size_cursor: usize,
+    /// Last-modified bounds in days, as typed
+    pub older_than: String,
+    pub older_than_cursor: usize,
+    pub newer_than: String,
+    pub newer_than_cursor: usize,
 }
 
 impl Default for MaskDraft {
@@ -77,13 +283,392 @@ impl Default for MaskDraft {
             storage_class_filter: None,
             storage_class_cursor: 0,
             cursor_pos: 0,
+            owner: String::new(),
+            owner_cursor: 0,
+            unread_days: None,
+            min_size: String::new(),
+            min_size_cursor: 0,
+            max_size: String::new(),
+            max_size_cursor: 0,
+            older_than: String::new(),
+            older_than_cursor: 0,
+            newer_than: String::new(),
+            newer_than_cursor: 0,
         }
     }
 }
 
 pub enum PendingAction {
-    Transition { target_class: StorageClassTier },
-    Restore { days: i32 },
+    Transition {
+        target_class: StorageClassTier,
+        /// Retention and legal hold to set on each archived object
+        lock: ObjectLockPlan,
+    },
+    Restore {
+        days: i32,
+        tier: RestoreTier,
+        /// Preset the days and tier came from; pacing and notification
+        /// follow it too
+        preset: Option<String>,
+    },
+    /// Next wave of a planned restore, queued by the event loop rather than
+    /// confirmed by the user
+    RestoreWave { plan_id: String, wave: usize },
+    Copy {
+        source_bucket: String,
+        dest_bucket: String,
+        keys: Vec<String>,
+        mode: CopyMode,
+        /// Delete each source object once its copy is verified
+        delete_source: bool,
+        /// Cap on this batch's bytes per second when it crosses regions
+        max_bandwidth: Option<u64>,
+        /// Prefix replaced in the destination keys
+        rewrite: KeyRewrite,
+        /// Class of the copies; S3's default, Standard, when `None`
+        storage_class: Option<StorageClassTier>,
+    },
+    /// Copy the target objects in place under a new KMS key
+    Reencrypt { kms_key_id: String },
+    /// Permanently delete noncurrent versions and delete markers, listed in
+    /// the version view
+    DeleteVersions { versions: Vec<ObjectInfo> },
+    /// Move noncurrent versions to `target_class` by replaying the history
+    /// of each key, in the version view
+    TransitionVersions {
+        target_class: StorageClassTier,
+        replays: Vec<VersionReplay>,
+    },
+    /// Restore archived noncurrent versions, in the version view
+    RestoreVersions {
+        versions: Vec<ObjectInfo>,
+        days: i32,
+        tier: RestoreTier,
+    },
+    /// Write small objects into one archive in Deep Archive, with an index
+    Bundle {
+        objects: Vec<ObjectInfo>,
+        archive_key: String,
+        compression: ArchiveCompression,
+        /// Delete each bundled object once the archive is verified
+        delete_originals: bool,
+    },
+    /// Read members back out of bundle archives, restoring archives that
+    /// are still in Deep Archive first
+    RetrieveBundled {
+        bucket: String,
+        /// Archive key and entry of each member
+        members: Vec<(String, BundleEntry)>,
+        /// Write local files instead of objects under the original keys
+        local: bool,
+    },
+    /// Run a saved policy against a whole bucket, confirmed in the policy
+    /// pane rather than the confirmation dialog
+    RunPolicy {
+        policy: MigrationPolicy,
+        bucket: String,
+    },
+}
+
+/// Status text for a batch that is winding down
+pub fn shutdown_label(choice: ShutdownChoice) -> &'static str {
+    match choice {
+        ShutdownChoice::FinishCurrent => t("shutdown.finishing"),
+        ShutdownChoice::Checkpoint => t("shutdown.checkpointing"),
+        ShutdownChoice::Abort => t("shutdown.aborting"),
+    }
+}
+
+/// What to do with a batch that was paused for a high failure rate
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PauseChoice {
+    Continue,
+    Abort,
+    /// Stop, and move the objects that already succeeded back to their original class
+    Rollback,
+}
+
+/// A batch paused because too many of its requests failed
+#[derive(Clone, Debug)]
+pub struct BatchPause {
+    pub attempted: usize,
+    pub failed: usize,
+    /// Failure count per error code, most frequent first
+    pub breakdown: Vec<(String, usize)>,
+    pub choice: Option<PauseChoice>,
+}
+
+/// Objects returned by an inventory query. While its bucket is selected it
+/// replaces the listing as the object list and the target set.
+#[derive(Clone, Debug)]
+pub struct QuerySet {
+    pub bucket: String,
+    /// The prompt input that produced it
+    pub label: String,
+    pub objects: Vec<ObjectInfo>,
+}
+
+/// Where the policy pane is in its workflow
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PolicyStep {
+    Browse,
+    /// Typing the name of a new or edited policy
+    Naming,
+    /// Typing why the policy exists, after its name
+    Noting,
+    /// In the mask editor, then the class picker, on the pane's behalf
+    Editing,
+    ConfirmDelete,
+    /// Asking whether to run the selected policy against the selected bucket
+    ConfirmRun,
+}
+
+/// Members of the known bundles of a bucket, opened with `Z`
+#[derive(Clone, Debug, Default)]
+pub struct BundleView {
+    pub bucket: String,
+    /// Archive key and entry of each member
+    pub members: Vec<(String, BundleEntry)>,
+    /// Position in the members the filter shows
+    pub cursor: usize,
+    /// Members picked with Space, by their index in `members`
+    pub picked: HashSet<usize>,
+    /// Only members whose key contains this are shown
+    pub filter: String,
+    /// Typing the filter
+    pub filtering: bool,
+}
+
+impl BundleView {
+    /// Indexes in `members` of the members the filter shows
+    pub fn visible(&self) -> Vec<usize> {
+        self.members
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, entry))| entry.key.contains(&self.filter))
+            .map(|(index, _)| index)
+            .collect()
+    }
+}
+
+/// State of the policy pane
+#[derive(Clone, Debug)]
+pub struct PolicyPane {
+    pub cursor: usize,
+    pub step: PolicyStep,
+    pub name: String,
+    /// Name of the policy being edited, or `None` for a new one
+    pub editing: Option<String>,
+    pub note: String,
+    /// Mask picked in the mask editor, waiting for a target class
+    pub mask: Option<ObjectMask>,
+}
+
+impl Default for PolicyPane {
+    fn default() -> Self {
+        Self {
+            cursor: 0,
+            step: PolicyStep::Browse,
+            name: String::new(),
+            editing: None,
+            note: String::new(),
+            mask: None,
+        }
+    }
+}
+
+/// Second object list shown beside the main one in split view
+pub struct SplitPane {
+    pub bucket: String,
+    pub objects: Vec<ObjectInfo>,
+    pub selected: usize,
+    pub listing: Option<ListingHandle>,
+}
+
+impl SplitPane {
+    pub fn new(bucket: String) -> Self {
+        Self {
+            bucket,
+            objects: Vec::new(),
+            selected: 0,
+            listing: None,
+        }
+    }
+
+    pub fn selected_object(&self) -> Option<&ObjectInfo> {
+        self.objects.get(self.selected)
+    }
+
+    /// Merge a page of objects into the sorted listing, keeping the current selection
+    pub fn append_objects(&mut self, mut new_objects: Vec<ObjectInfo>) {
+        let selected_key = self.selected_object().map(|o| o.key.clone());
+        self.objects.append(&mut new_objects);
+        self.objects.sort_by(|a, b| a.key.cmp(&b.key));
+        self.objects.dedup_by(|a, b| a.key == b.key);
+        if let Some(key) = selected_key
+            && let Some(idx) = self.objects.iter().position(|o| o.key == key)
+        {
+            self.selected = idx;
+        }
+    }
+
+    pub fn should_load_more(&self) -> bool {
+        self.listing.is_some() && self.selected + 50 >= self.objects.len()
+    }
+}
+
+/// Most restore requests in flight at once, however far `+` raises it
+pub const MAX_RESTORE_CONCURRENCY: usize = 256;
+
+/// How restore requests are submitted (`--restore-concurrency`, `--restore-rate`,
+/// `--restore-wave-size`, `--restore-dedup-hours`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RestorePacing {
+    /// Requests in flight at once
+    pub concurrency: usize,
+    /// Upper bound on requests started per second; `None` sends as fast as
+    /// the concurrency allows
+    pub per_second: Option<u32>,
+    /// Restores larger than this are split into waves
+    pub wave_bytes: Option<u64>,
+    /// Keys asked to restore within this many hours are not requested again;
+    /// 0 turns the guard off
+    pub dedup_hours: u32,
+}
+
+impl Default for RestorePacing {
+    fn default() -> Self {
+        Self {
+            concurrency: 8,
+            per_second: None,
+            wave_bytes: None,
+            dedup_hours: 24,
+        }
+    }
+}
+
+impl RestorePacing {
+    pub fn from_args(args: &[String]) -> Self {
+        let value = |flag: &str| {
+            args.windows(2)
+                .find(|pair| pair[0] == flag)
+                .and_then(|pair| pair[1].parse::<u32>().ok())
+        };
+        let default = Self::default();
+        Self {
+            concurrency: value("--restore-concurrency")
+                .map(|n| (n as usize).clamp(1, MAX_RESTORE_CONCURRENCY))
+                .unwrap_or(default.concurrency),
+            per_second: value("--restore-rate").filter(|n| *n > 0),
+            wave_bytes: args
+                .windows(2)
+                .find(|pair| pair[0] == "--restore-wave-size")
+                .and_then(|pair| crate::waves::parse_size(&pair[1])),
+            dedup_hours: value("--restore-dedup-hours").unwrap_or(default.dedup_hours),
+        }
+    }
+
+    /// This pacing with `preset`'s concurrency and rate in place of the
+    /// session's, and whether its restores go unannounced
+    pub fn with_preset(self, preset: Option<&RestorePreset>) -> (Self, bool) {
+        let Some(preset) = preset else {
+            return (self, false);
+        };
+        let pacing = Self {
+            concurrency: preset
+                .concurrency
+                .map_or(self.concurrency, |n| n.clamp(1, MAX_RESTORE_CONCURRENCY)),
+            per_second: preset.per_second.filter(|n| *n > 0).or(self.per_second),
+            ..self
+        };
+        (pacing, !preset.notify_ready)
+    }
+
+    /// Earliest start of the `index`th request, relative to the batch start
+    pub fn start_offset(&self, index: usize) -> std::time::Duration {
+        match self.per_second {
+            Some(rate) => std::time::Duration::from_secs_f64(index as f64 / rate as f64),
+            None => std::time::Duration::ZERO,
+        }
+    }
+}
+
+/// A lifecycle rule about to be exported, priced against transitioning the
+/// same objects client-side
+#[derive(Clone, Debug)]
+pub struct LifecycleComparison {
+    pub rule: LifecycleRule,
+    /// Mask settings the rule cannot express
+    pub warnings: Vec<String>,
+    /// `None` without prices for the bucket's region
+    pub costs: Option<MechanismComparison>,
+}
+
+/// Where a shard of a sharded batch is, for the shard matrix
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShardState {
+    /// Never run
+    Pending,
+    /// Submitted, waiting for the jobs ahead of it
+    Queued,
+    Running,
+    Done,
+    /// Its latest run finished with failed keys
+    Failed,
+    /// Its latest run was stopped, or bucket-brigade exited during it
+    Interrupted,
+}
+
+impl ShardState {
+    pub fn label(self) -> &'static str {
+        match self {
+            ShardState::Pending => "pending",
+            ShardState::Queued => "queued",
+            ShardState::Running => "running",
+            ShardState::Done => "done",
+            ShardState::Failed => "failed",
+            ShardState::Interrupted => "interrupted",
+        }
+    }
+}
+
+/// Per-object messages of one batch job, kept out of the status log so a
+/// large batch does not push everything else out of it
+#[derive(Clone, Debug)]
+pub struct JobLog {
+    /// Journal id of the job
+    pub job_id: String,
+    /// e.g. `Transition my-bucket → GLACIER`
+    pub title: String,
+    pub messages: VecDeque<String>,
+    /// Oldest messages dropped to stay within `JOB_LOG_LIMIT`
+    pub dropped: usize,
+}
+
+/// New restore states found by a background check: (bucket, key, state,
+/// restore expiry)
+pub type RestoreUpdates = Vec<(String, String, RestoreState, Option<String>)>;
+
+/// A change to a shared plan, written to the shared state by the next sync
+#[derive(Clone, Debug)]
+pub enum SharedRequest {
+    /// Have the workers run a shard's failed keys again
+    Retry { plan_id: String, index: usize },
+    /// Stop workers claiming the plan's shards: `done` or `dropped`
+    Close {
+        plan_id: String,
+        reason: &'static str,
+    },
+}
+
+/// What a background sync with the shared state found
+#[derive(Debug, Default)]
+pub struct SharedSync {
+    /// Plans now visible to workers
+    pub published: Vec<String>,
+    /// (plan id, shard, progress, worker holding a claim) per shard read
+    pub shards: Vec<(String, usize, Option<ShardProgress>, Option<String>)>,
+    pub error: Option<String>,
 }
 
 #[derive(Clone, Debug)]
@@ -92,6 +677,10 @@ pub struct ProgressState {
     pub current: usize,
     pub total: usize,
     pub current_item: Option<String>,
+    pub stopping: Option<ShutdownChoice>,
+    /// Requests in flight at once, for batches whose parallelism can be
+    /// changed with `+`/`-` while they run
+    pub concurrency: Option<usize>,
 }
 
 impl ProgressState {
@@ -101,6 +690,8 @@ impl ProgressState {
             current: 0,
             total,
             current_item: None,
+            stopping: None,
+            concurrency: None,
         }
     }
 
@@ -126,12 +717,86 @@ pub struct App {
     pub selected_object: usize,
     pub selected_region: Option<String>,
     pub available_regions: Vec<String>,
+    /// Sizes of buckets that have been listed in full
+    pub bucket_sizes: BucketSizes,
     pub status: VecDeque<String>,
+    /// Newest status messages scrolled out below the log view. 0 follows
+    /// new messages; scrolling up pauses following until the end is reached.
+    pub log_scroll: usize,
+    /// Log view shown over a running batch's progress
+    pub show_batch_log: bool,
+    /// Per-object messages of this session's batch jobs, oldest job first
+    pub job_logs: VecDeque<JobLog>,
+    /// Job whose log the log view shows instead of the status log
+    pub log_job: Option<String>,
     pub active_pane: ActivePane,
     pub mode: AppMode,
     pub mask_draft: MaskDraft,
     pub active_mask: Option<ObjectMask>,
     pub pending_action: Option<PendingAction>,
+    /// Confirmed batch waiting for the event loop to run it
+    pub queued_batch: Option<PendingAction>,
+    /// Confirmed with `b`, waiting for the event loop to hand it to `jobs`
+    pub queued_job: Option<PendingAction>,
+    /// Ticket or reason noted in the confirmation, journaled with the jobs
+    /// the confirmed action begins
+    pub job_note: Option<String>,
+    /// Change ticket given in the confirmation, journaled and reported with
+    /// the jobs the confirmed action begins
+    pub job_ticket: Option<TicketLink>,
+    /// Lookup of a typed ticket in the tracker, from `tickets.json`
+    pub ticket_check: Option<JoinHandle<anyhow::Result<TicketLink>>>,
+    /// Transitions and restores running in the background
+    pub jobs: JobQueue,
+    /// Batches split by prefix, each shard run as a background job
+    pub shards: ShardStore,
+    /// Moves not every key of which is done yet
+    pub moves: MoveStore,
+    /// Batches over this many keys are sharded (`--shard-size`)
+    pub shard_keys: usize,
+    /// Selected cell of the shard matrix, counted over every plan's shards
+    pub shard_cursor: usize,
+    /// Where sharded batches are shared with `--worker` processes
+    /// (`--state-bucket`)
+    pub shared_state: Option<SharedState>,
+    /// Background publish and progress read of the shared plans
+    pub shard_sync: Option<JoinHandle<SharedSync>>,
+    pub shard_synced_at: Option<Instant>,
+    /// Retries and closes waiting for the next sync
+    pub shared_requests: Vec<SharedRequest>,
+    pub quit_requested: bool,
+    /// Background HeadObject check of pending restores
+    pub restore_check: Option<JoinHandle<RestoreUpdates>>,
+    pub restore_checked_at: Option<Instant>,
+    /// Restore statuses read by HeadObject enrichment or an inspect, applied
+    /// to the tracker by the event loop
+    pub refreshed_restores: RestoreUpdates,
+    /// Whether AWS is reachable, and the running check while it is not
+    pub connectivity: Connectivity,
+    pub connection_probe: Option<JoinHandle<bool>>,
+    /// Startup check for a newer release (`--no-update-check` skips it), and
+    /// the release it found
+    pub update_check: Option<JoinHandle<Option<Release>>>,
+    /// HeadObject pipeline filling in the listed objects of the main pane
+    pub enrichment: Option<EnrichHandle>,
+    /// Running comparison of the split view's buckets, and what it found
+    pub reconcile: Option<ReconcileHandle>,
+    pub reconciliation: Option<Reconciliation>,
+    pub reconcile_scroll: usize,
+    pub new_release: Option<Release>,
+    /// Deletes are refused (`--no-delete` or a `no-delete` build); the TUI
+    /// hides moves and decommissioning
+    pub no_delete: bool,
+    /// S3 requests fail on purpose (`--inject-faults`)
+    pub fault_injection: Option<FaultInjection>,
+    /// Plain ASCII, monochrome rendering (`--ascii`)
+    pub ascii: bool,
+    /// Redraw only after input or background changes (`--low-redraw`)
+    pub low_redraw: bool,
+    pub restore_pacing: RestorePacing,
+    pub restore_presets: RestorePresets,
+    /// Restores must use a preset (`--restore-presets-only`)
+    pub restore_presets_only: bool,
     pub storage_class_cursor: usize,
     pub storage_intent: StorageIntent,
     pub mask_field: MaskEditorField,
@@ -139,32 +804,143 @@ pub struct App {
     pub pending_bucket_load: bool,
     // Pagination state
     pub total_object_count: Option<usize>,
-    pub continuation_token: Option<String>,
+    pub listing: Option<ListingHandle>,
+    /// Bucket and start key when browsing a window of the listing
+    pub listing_window: Option<(String, String)>,
+    /// Bucket and prefix the listing, masks and batches are scoped to
+    pub folder: Option<(String, String)>,
+    /// List one level at a time with the `/` delimiter (`F`)
+    pub folder_view: bool,
+    /// List every version and delete marker with ListObjectVersions (`H`)
+    pub version_view: bool,
+    /// Subfolders of the current folder, in folder view
+    pub folders: Vec<String>,
+    /// Row of `folders` the cursor is on; `None` while it is on an object
+    pub folder_cursor: Option<usize>,
     pub is_loading_objects: bool,
     // Progress tracking
     pub progress: Option<ProgressState>,
+    pub batch_pause: Option<BatchPause>,
+    // Inventory query prompt
+    pub query_input: String,
+    pub query_set: Option<QuerySet>,
+    /// (bucket, key) rows left out of the mask or query selection with Space
+    pub excluded: HashSet<(String, String)>,
+    /// (bucket, key) rows picked with Space while no mask or query is active
+    pub picked: HashSet<(String, String)>,
+    /// Show only objects with this restore status (`N`); they become the targets
+    pub restore_filter: Option<RestoreStatus>,
+    /// List objects grouped by restore status (`S`)
+    pub restore_sort: bool,
+    /// The prompt holds an Athena condition rather than a manifest location
+    pub query_via_athena: bool,
+    pub athena: Option<AthenaConfig>,
+    /// Command run after each object a batch processed (`--object-hook`)
+    pub object_hook: Option<ObjectHook>,
+    /// Command copies pipe each object through (`--transform`)
+    pub transform: Option<Transform>,
+    /// Prefix tree of the pending action's keys, opened from the confirmation
+    pub preview: Option<TargetPreview>,
+    /// Owner being typed for the preview's group under the cursor (`o`)
+    pub owner_input: Option<String>,
+    /// Dry-run report of the pending transition, opened from the confirmation
+    pub dry_run: Option<DryRun>,
+    pub dry_run_scroll: usize,
+    /// Opened by picking a class to export a lifecycle rule to
+    pub lifecycle_comparison: Option<LifecycleComparison>,
+    /// Keys bulk actions always skip
+    pub protected: ProtectedKeys,
+    pub protect_input: String,
+    /// Confirmed actions waiting for their run time
+    pub schedule: Schedule,
+    pub schedule_input: String,
+    pub schedule_cursor: usize,
+    /// Hours batch jobs may start in; confirmed outside them they are queued
+    pub execution_window: Option<ExecutionWindow>,
+    /// Request samples from running batches, and whether the panel is shown
+    pub telemetry: Telemetry,
+    pub show_telemetry: bool,
+    /// Cap on cross-region copies across all jobs (`--max-bandwidth`), in
+    /// bytes per second; a copy batch may set a lower one
+    pub max_bandwidth: Option<u64>,
+    /// Paces cross-region copies under their cap
+    pub throttle: Throttle,
+    /// Copy throughput measured per destination region
+    pub throughput: ThroughputLog,
+    /// Checklist for retiring the selected bucket
+    pub decommission: Option<Decommission>,
+    /// Access audits by bucket, from `X`, the configuration review or a copy
+    pub access_audits: HashMap<String, AccessAudit>,
+    /// Where last-read times are looked up (`--access-logs`,
+    /// `--cloudtrail-table`)
+    pub recency: Option<RecencyConfig>,
+    /// Last reads by bucket, from `W`
+    pub last_reads: HashMap<String, LastReads>,
+    pub recency_lookup: Option<JoinHandle<anyhow::Result<LastReads>>>,
+    /// Configuration copy between the split view's buckets, under review
+    pub config_copy: Option<ConfigCopy>,
+    /// Tags of the selected bucket, open for editing
+    pub tag_editor: Option<TagEditor>,
+    /// Saved migration policies, and the pane that manages them
+    pub policies: PolicyStore,
+    pub policy_pane: PolicyPane,
+    /// Indexes of known bundle archives, and the members view
+    pub bundles: BundleCatalog,
+    pub bundle_view: Option<BundleView>,
+    /// Who owns which prefixes, shown before their data is touched
+    pub owners: OwnerDirectory,
+    /// Migration sign-off per prefix; bulk actions refuse blocked prefixes,
+    /// and every unapproved one with `--require-approval`
+    pub approvals: ApprovalStore,
+    pub require_approval: bool,
+    /// How change tickets are checked and linked, from `tickets.json`
+    pub tickets: Option<TicketConfig>,
+    // Create bucket form
+    pub bucket_draft: NewBucket,
+    pub bucket_field: BucketFormField,
+    // Copy to another bucket form
+    pub copy_draft: CopyDraft,
+    pub copy_field: CopyFormField,
+    /// Lookup of the destination keys a renaming copy would overwrite
+    pub collision_check: Option<JoinHandle<anyhow::Result<CollisionReview>>>,
+    /// Collisions found for the pending copy, until each is decided
+    pub collisions: Option<CollisionReview>,
+    /// Why the collision lookup failed; the copy waits until it is retried
+    /// or the operator chooses to overwrite
+    pub collision_error: Option<String>,
+    // Jump-to-key prompt
+    pub jump_input: String,
+    pub jump_sets_window: bool,
+    // KMS key prompt of the re-encryption workflow
+    pub kms_input: String,
+    // Retain-until prompt opened from the transition confirmation
+    pub retain_input: String,
+    // Restore duration typed into the restore confirmation
+    pub restore_days_input: String,
+    // Job note prompt opened from the confirmation
+    pub note_input: String,
+    // Change ticket prompt opened from the confirmation
+    pub ticket_input: String,
+    // Script mask editor; the cursor is a byte offset
+    pub script_draft: String,
+    pub script_cursor: usize,
+    // Command palette
+    pub palette_input: String,
+    pub palette_cursor: usize,
+    /// Horizontal scroll offset (in characters) of the key column
+    pub key_scroll: usize,
+    // Split view
+    pub split: Option<SplitPane>,
+    // Job history
+    pub history_cursor: usize,
+    pub history_entries: Vec<JournalEntry>,
+    pub history_scroll: usize,
+    // Interrupted multipart copies
+    pub upload_cursor: usize,
 }
 
 impl App {
     pub fn new() -> Self {
-        let available_regions = vec![
-            "All Regions".to_string(),
-            "us-east-1".to_string(),
-            "us-east-2".to_string(),
-            "us-west-1".to_string(),
-            "us-west-2".to_string(),
-            "eu-west-1".to_string(),
-            "eu-west-2".to_string(),
-            "eu-west-3".to_string(),
-            "eu-central-1".to_string(),
-            "ap-northeast-1".to_string(),
-            "ap-northeast-2".to_string(),
-            "ap-southeast-1".to_string(),
-            "ap-southeast-2".to_string(),
-            "ap-south-1".to_string(),
-            "sa-east-1".to_string(),
-            "ca-central-1".to_string(),
-        ];
         Self {
             buckets: Vec::new(),
             all_buckets: Vec::new(),
@@ -173,22 +949,132 @@ impl App {
             selected_bucket: 0,
             selected_object: 0,
             selected_region: None,
-            available_regions,
+            available_regions: vec!["All Regions".to_string()],
+            bucket_sizes: BucketSizes::default(),
             status: VecDeque::with_capacity(STATUS_LIMIT),
+            log_scroll: 0,
+            show_batch_log: false,
+            job_logs: VecDeque::new(),
+            log_job: None,
             active_pane: ActivePane::Buckets,
             mode: AppMode::Browsing,
             mask_draft: MaskDraft::default(),
             active_mask: None,
             pending_action: None,
+            queued_batch: None,
+            queued_job: None,
+            job_note: None,
+            job_ticket: None,
+            ticket_check: None,
+            jobs: JobQueue::default(),
+            shards: ShardStore::default(),
+            moves: MoveStore::default(),
+            shard_keys: DEFAULT_SHARD_KEYS,
+            shard_cursor: 0,
+            shared_state: None,
+            shard_sync: None,
+            shard_synced_at: None,
+            shared_requests: Vec::new(),
+            quit_requested: false,
+            restore_check: None,
+            refreshed_restores: Vec::new(),
+            restore_checked_at: None,
+            connectivity: Connectivity::default(),
+            connection_probe: None,
+            update_check: None,
+            enrichment: None,
+            reconcile: None,
+            reconciliation: None,
+            reconcile_scroll: 0,
+            no_delete: false,
+            fault_injection: None,
+            new_release: None,
+            ascii: false,
+            low_redraw: false,
+            restore_pacing: RestorePacing::default(),
+            restore_presets: RestorePresets::default(),
+            restore_presets_only: false,
             storage_class_cursor: 0,
             storage_intent: StorageIntent::Transition,
             mask_field: MaskEditorField::Pattern,
             last_bucket_change: None,
             pending_bucket_load: false,
             total_object_count: None,
-            continuation_token: None,
+            listing: None,
+            listing_window: None,
+            folder: None,
+            folder_view: false,
+            version_view: false,
+            folders: Vec::new(),
+            folder_cursor: None,
             is_loading_objects: false,
             progress: None,
+            batch_pause: None,
+            query_input: String::new(),
+            query_set: None,
+            excluded: HashSet::new(),
+            picked: HashSet::new(),
+            restore_filter: None,
+            restore_sort: false,
+            query_via_athena: false,
+            athena: None,
+            object_hook: None,
+            transform: None,
+            preview: None,
+            owner_input: None,
+            dry_run: None,
+            dry_run_scroll: 0,
+            lifecycle_comparison: None,
+            protected: ProtectedKeys::default(),
+            protect_input: String::new(),
+            schedule: Schedule::default(),
+            schedule_input: String::new(),
+            schedule_cursor: 0,
+            execution_window: None,
+            telemetry: Telemetry::default(),
+            show_telemetry: false,
+            max_bandwidth: None,
+            throttle: Throttle::default(),
+            throughput: ThroughputLog::default(),
+            decommission: None,
+            access_audits: HashMap::new(),
+            recency: None,
+            last_reads: HashMap::new(),
+            recency_lookup: None,
+            config_copy: None,
+            tag_editor: None,
+            policies: PolicyStore::default(),
+            policy_pane: PolicyPane::default(),
+            bundles: BundleCatalog::default(),
+            bundle_view: None,
+            owners: OwnerDirectory::default(),
+            approvals: ApprovalStore::default(),
+            require_approval: false,
+            tickets: None,
+            bucket_draft: NewBucket::new("us-east-1"),
+            bucket_field: BucketFormField::Name,
+            copy_draft: CopyDraft::default(),
+            copy_field: CopyFormField::Bucket,
+            collision_check: None,
+            collision_error: None,
+            collisions: None,
+            jump_input: String::new(),
+            kms_input: String::new(),
+            retain_input: String::new(),
+            restore_days_input: String::new(),
+            note_input: String::new(),
+            ticket_input: String::new(),
+            jump_sets_window: false,
+            script_draft: String::new(),
+            script_cursor: 0,
+            palette_input: String::new(),
+            palette_cursor: 0,
+            key_scroll: 0,
+            split: None,
+            history_cursor: 0,
+            history_entries: Vec::new(),
+            history_scroll: 0,
+            upload_cursor: 0,
         }
     }
 
@@ -198,23 +1084,341 @@ impl App {
             .map(|b| b.name.as_str())
     }
 
+    /// Region of a listed bucket, if the listing reported one
+    pub fn bucket_region(&self, name: &str) -> Option<&str> {
+        self.all_buckets
+            .iter()
+            .find(|b| b.name == name)
+            .and_then(|b| b.region.as_deref())
+    }
+
+    /// Whether both buckets are known to be in one region. Buckets whose
+    /// region is unknown count as being in different regions.
+    pub fn same_region(&self, a: &str, b: &str) -> bool {
+        self.bucket_region(a)
+            .zip(self.bucket_region(b))
+            .is_some_and(|(a, b)| a == b)
+    }
+
+    /// Bytes per second a copy from `source` to `dest` may use under a
+    /// batch's own cap and `--max-bandwidth`, or `None` when it is uncapped.
+    /// Copies within a region are never capped.
+    pub fn copy_cap(&self, source: &str, dest: &str, batch_cap: Option<u64>) -> Option<u64> {
+        if self.same_region(source, dest) {
+            return None;
+        }
+        match (batch_cap, self.max_bandwidth) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (cap, global) => cap.or(global),
+        }
+    }
+
     pub fn selected_object(&self) -> Option<&ObjectInfo> {
+        if self.folder_cursor.is_some() {
+            return None;
+        }
         self.active_objects().get(self.selected_object)
     }
 
     pub fn active_objects(&self) -> &[ObjectInfo] {
-        if self.active_mask.is_some() {
+        if let Some(query) = self.active_query() {
+            &query.objects
+        } else if self.is_filtered() {
             &self.filtered_objects
         } else {
             &self.objects
         }
     }
 
+    /// The inventory query result, while its bucket is the selected one
+    pub fn active_query(&self) -> Option<&QuerySet> {
+        self.query_set
+            .as_ref()
+            .filter(|q| self.selected_bucket_name() == Some(q.bucket.as_str()))
+    }
+
+    /// Objects an action applies to: the query result or the mask matches
+    /// minus excluded rows, the picked rows, or the selected object.
+    /// Protected keys are never included.
+    pub fn target_objects(&self) -> Vec<&ObjectInfo> {
+        self.candidate_objects()
+            .into_iter()
+            .filter(|obj| !self.is_protected(&obj.key) && obj.is_current())
+            .collect()
+    }
+
+    /// Noncurrent versions and delete markers of the keys an action applies
+    /// to, in the version view. Protected keys are never included.
+    pub fn noncurrent_targets(&self) -> Vec<&ObjectInfo> {
+        let keys: HashSet<&str> = self
+            .candidate_objects()
+            .into_iter()
+            .filter(|obj| !self.is_protected(&obj.key))
+            .map(|obj| obj.key.as_str())
+            .collect();
+        self.objects
+            .iter()
+            .filter(|obj| obj.is_noncurrent() && keys.contains(obj.key.as_str()))
+            .collect()
+    }
+
+    /// Every listed version and delete marker of the keys an action applies
+    /// to, one list per key, newest first. Protected keys are never included.
+    pub fn target_histories(&self) -> Vec<Vec<&ObjectInfo>> {
+        let keys: HashSet<&str> = self
+            .candidate_objects()
+            .into_iter()
+            .filter(|obj| !self.is_protected(&obj.key))
+            .map(|obj| obj.key.as_str())
+            .collect();
+        let mut histories: Vec<Vec<&ObjectInfo>> = Vec::new();
+        for obj in self
+            .objects
+            .iter()
+            .filter(|obj| keys.contains(obj.key.as_str()))
+        {
+            match histories.last_mut() {
+                Some(history) if history[0].key == obj.key => history.push(obj),
+                _ => histories.push(vec![obj]),
+            }
+        }
+        histories
+    }
+
+    /// Last reads looked up for the selected bucket
+    pub fn selected_last_reads(&self) -> Option<&LastReads> {
+        self.selected_bucket_name()
+            .and_then(|bucket| self.last_reads.get(bucket))
+    }
+
+    /// How cold the storage picker's objects are, once the bucket's reads
+    /// are looked up: the targets of a transition, or everything under the
+    /// prefix of a lifecycle rule. A policy runs against any bucket, so has
+    /// none.
+    pub fn picker_coldness(&self) -> Option<Coldness> {
+        let reads = self.selected_last_reads()?;
+        match self.storage_intent {
+            StorageIntent::Transition => {
+                let objects = self.target_objects();
+                if objects.is_empty() {
+                    return None;
+                }
+                let mut activity = Activity::default();
+                let mut written = None;
+                for obj in objects {
+                    activity.merge(&reads.activity_of(&obj.key));
+                    let at = obj
+                        .last_modified
+                        .as_deref()
+                        .and_then(|at| chrono::DateTime::parse_from_rfc3339(at).ok())
+                        .map(|at| at.with_timezone(&chrono::Utc));
+                    written = written.max(at);
+                }
+                Some(Coldness::of(
+                    &activity,
+                    reads.since,
+                    written,
+                    chrono::Utc::now(),
+                ))
+            }
+            StorageIntent::ExportLifecycle => self
+                .active_mask
+                .as_ref()
+                .map(|mask| reads.coldness_under(&mask.pattern)),
+            StorageIntent::SavePolicy => None,
+        }
+    }
+
+    /// The objects an action would target if none were protected
+    pub fn candidate_objects(&self) -> Vec<&ObjectInfo> {
+        if self.is_selection() {
+            self.active_objects()
+                .iter()
+                .filter(|obj| !self.is_excluded(&obj.key))
+                .collect()
+        } else if self.has_picks() {
+            self.objects
+                .iter()
+                .filter(|obj| self.is_picked(&obj.key))
+                .collect()
+        } else {
+            self.selected_object().into_iter().collect()
+        }
+    }
+
+    pub fn is_protected(&self, key: &str) -> bool {
+        self.selected_bucket_name()
+            .is_some_and(|bucket| self.protected.is_protected(bucket, key))
+    }
+
+    /// Number of would-be targets skipped because they are protected
+    pub fn protected_count(&self) -> usize {
+        self.candidate_objects()
+            .iter()
+            .filter(|obj| self.is_protected(&obj.key))
+            .count()
+    }
+
+    /// Whether a mask, restore filter or query picks the targets, rather
+    /// than the cursor
+    pub fn is_selection(&self) -> bool {
+        self.active_query().is_some() || self.active_mask.is_some() || self.restore_filter.is_some()
+    }
+
+    /// Whether the list shows `filtered_objects`: masked, filtered by
+    /// restore status or sorted by it
+    pub fn is_filtered(&self) -> bool {
+        self.active_mask.is_some() || self.restore_filter.is_some() || self.restore_sort
+    }
+
+    pub fn is_excluded(&self, key: &str) -> bool {
+        self.selected_bucket_name().is_some_and(|bucket| {
+            self.excluded
+                .contains(&(bucket.to_string(), key.to_string()))
+        })
+    }
+
+    /// Number of shown rows excluded from the selection
+    pub fn excluded_count(&self) -> usize {
+        if !self.is_selection() || self.excluded.is_empty() {
+            return 0;
+        }
+        self.active_objects()
+            .iter()
+            .filter(|obj| self.is_excluded(&obj.key))
+            .count()
+    }
+
+    pub fn is_picked(&self, key: &str) -> bool {
+        self.selected_bucket_name()
+            .is_some_and(|bucket| self.picked.contains(&(bucket.to_string(), key.to_string())))
+    }
+
+    /// Whether rows of the selected bucket are picked. Picks only choose the
+    /// targets while no mask or query does.
+    pub fn has_picks(&self) -> bool {
+        self.picked_count() > 0
+    }
+
+    /// Number of rows picked in the selected bucket
+    pub fn picked_count(&self) -> usize {
+        let Some(bucket) = self.selected_bucket_name() else {
+            return 0;
+        };
+        self.picked.iter().filter(|(b, _)| b == bucket).count()
+    }
+
+    /// Pick the selected row, or unpick it. Returns the key and whether it is
+    /// now picked.
+    pub fn toggle_pick(&mut self) -> Option<(String, bool)> {
+        let bucket = self.selected_bucket_name()?.to_string();
+        let key = self.selected_object()?.key.clone();
+        let entry = (bucket, key.clone());
+        let picked = if self.picked.remove(&entry) {
+            false
+        } else {
+            self.picked.insert(entry);
+            true
+        };
+        Some((key, picked))
+    }
+
+    /// Pick every loaded row of the selected bucket; returns how many are
+    /// picked now
+    pub fn pick_all(&mut self) -> usize {
+        let Some(bucket) = self.selected_bucket_name().map(str::to_string) else {
+            return 0;
+        };
+        for obj in &self.objects {
+            self.picked.insert((bucket.clone(), obj.key.clone()));
+        }
+        self.picked_count()
+    }
+
+    /// Forget the picks of the selected bucket
+    pub fn clear_picks(&mut self) {
+        if let Some(bucket) = self.selected_bucket_name().map(str::to_string) {
+            self.picked.retain(|(b, _)| *b != bucket);
+        }
+    }
+
+    /// Exclude the selected row from the selection, or include it again.
+    /// Returns the key and whether it is now excluded.
+    pub fn toggle_exclusion(&mut self) -> Option<(String, bool)> {
+        let bucket = self.selected_bucket_name()?.to_string();
+        let key = self.active_objects().get(self.selected_object)?.key.clone();
+        let entry = (bucket, key.clone());
+        let excluded = if self.excluded.remove(&entry) {
+            false
+        } else {
+            self.excluded.insert(entry);
+            true
+        };
+        Some((key, excluded))
+    }
+
+    /// Replace the bucket list; the region filter cycles through the regions
+    /// the buckets are in
     pub fn set_buckets(&mut self, buckets: Vec<BucketInfo>) {
         self.all_buckets = buckets;
+        let mut regions: Vec<String> = self
+            .all_buckets
+            .iter()
+            .filter_map(|b| b.region.clone())
+            .collect();
+        regions.sort();
+        regions.dedup();
+        self.available_regions = std::iter::once("All Regions".to_string())
+            .chain(regions)
+            .collect();
+        if self
+            .selected_region
+            .as_ref()
+            .is_some_and(|region| !self.available_regions.contains(region))
+        {
+            self.selected_region = None;
+        }
         self.apply_region_filter();
     }
 
+    fn buckets_in_region<'a>(&'a self, region: &'a str) -> impl Iterator<Item = &'a BucketInfo> {
+        self.all_buckets
+            .iter()
+            .filter(move |b| region == "All Regions" || b.region.as_deref() == Some(region))
+    }
+
+    /// Buckets in `region`, or in every region for "All Regions"
+    pub fn region_bucket_count(&self, region: &str) -> usize {
+        self.buckets_in_region(region).count()
+    }
+
+    /// Bucket count and known total size of `region`, e.g. "12 buckets, 3.4 TB".
+    /// Sizes come from full listings; when only some are known the total is
+    /// marked as a lower bound.
+    pub fn region_badge(&self, region: &str) -> String {
+        let count = self.region_bucket_count(region);
+        let (bytes, known) = self
+            .bucket_sizes
+            .total(self.buckets_in_region(region).map(|b| b.name.as_str()));
+        match known {
+            0 => tf("buckets.badge", &[("count", &count)]),
+            _ if known == count => tf(
+                "buckets.badge_sized",
+                &[("count", &count), ("size", &format_bytes(bytes))],
+            ),
+            _ => tf(
+                "buckets.badge_partial",
+                &[
+                    ("count", &count),
+                    // A cell holds one character, so --ascii cannot map '≥' to ">="
+                    ("at_least", &if self.ascii { ">=" } else { "≥" }),
+                    ("size", &format_bytes(bytes)),
+                    ("known", &known),
+                ],
+            ),
+        }
+    }
+
     pub fn apply_region_filter(&mut self) {
         if let Some(ref region) = self.selected_region {
             if region == "All Regions" {
@@ -238,64 +1442,139 @@ impl App {
         self.apply_region_filter();
     }
 
+    /// How an entry of `available_regions` reads in the UI
+    pub fn region_label(region: &str) -> &str {
+        if region == "All Regions" {
+            t("buckets.all_regions")
+        } else {
+            region
+        }
+    }
+
     pub fn get_current_region_display(&self) -> String {
         self.selected_region
             .clone()
             .unwrap_or_else(|| "All Regions".to_string())
     }
 
-    pub fn set_objects(&mut self, objects: Vec<ObjectInfo>) {
-        self.objects = objects;
-        self.filtered_objects = Vec::new();
-        self.selected_object = 0;
-    }
-
+    /// Merge a page of objects into the sorted listing, keeping the current selection
     pub fn append_objects(&mut self, mut new_objects: Vec<ObjectInfo>) {
+        // Read before the page lands, which shifts the rows under the index
+        let selected_key = self.selected_object().map(|o| o.key.clone());
         self.objects.append(&mut new_objects);
-        // Reapply mask if active
-        if let Some(mask) = &self.active_mask {
-            self.filtered_objects = self
-                .objects
-                .iter()
-                .filter(|&obj| {
-                    let key_matches = mask.matches(&obj.key);
-                    let storage_matches = mask
-                        .storage_class_filter
-                        .as_ref()
-                        .map(|filter| &obj.storage_class == filter)
-                        .unwrap_or(true);
-                    key_matches && storage_matches
-                })
-                .cloned()
-                .collect();
+        // Stable, so the versions of a key stay newest first
+        self.objects.sort_by(|a, b| a.key.cmp(&b.key));
+        // Jump-to-key fetches may overlap pages delivered by the listing
+        self.objects
+            .dedup_by(|a, b| a.key == b.key && a.version == b.version);
+        self.refilter();
+        self.select_key(selected_key);
+    }
+
+    /// Prefix the selected bucket's listing is scoped to, if any
+    pub fn current_prefix(&self) -> Option<&str> {
+        match (&self.folder, self.selected_bucket_name()) {
+            (Some((bucket, prefix)), Some(selected)) if bucket == selected => Some(prefix.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Subfolder rows shown above the objects. Query results are not
+    /// listed by folder, so they have none.
+    pub fn visible_folders(&self) -> &[String] {
+        if self.folder_view && self.active_query().is_none() {
+            &self.folders
+        } else {
+            &[]
         }
     }
 
+    /// Row of the Objects pane the cursor is on, counting folder rows first
+    pub fn cursor_row(&self) -> usize {
+        self.folder_cursor
+            .unwrap_or(self.visible_folders().len() + self.selected_object)
+    }
+
+    /// Move the cursor to `row`, clamped to the rows shown
+    pub fn set_cursor_row(&mut self, row: usize) {
+        let folders = self.visible_folders().len();
+        let rows = folders + self.active_objects().len();
+        if rows == 0 {
+            return;
+        }
+        let row = row.min(rows - 1);
+        if row < folders {
+            self.folder_cursor = Some(row);
+        } else {
+            self.folder_cursor = None;
+            self.selected_object = row - folders;
+        }
+    }
+
+    /// Start key of the listing window for the selected bucket, if any
+    pub fn window_start(&self) -> Option<&str> {
+        match (&self.listing_window, self.selected_bucket_name()) {
+            (Some((bucket, start)), Some(selected)) if bucket == selected => Some(start.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Whether a loaded (and visible) object starts with the given key
+    pub fn has_loaded_key(&self, target: &str) -> bool {
+        self.active_objects()
+            .iter()
+            .any(|o| o.key.starts_with(target))
+    }
+
+    /// Select the first visible object whose key sorts at or after `target`,
+    /// falling back to the last object. Returns false when nothing is loaded.
+    pub fn select_key_at_or_after(&mut self, target: &str) -> bool {
+        let objects = self.active_objects();
+        if objects.is_empty() {
+            return false;
+        }
+        let at_or_after = if self.restore_sort {
+            // Not in key order, so the first match in list order
+            objects
+                .iter()
+                .position(|o| o.key.as_str() >= target)
+                .unwrap_or(objects.len())
+        } else {
+            objects.partition_point(|o| o.key.as_str() < target)
+        };
+        self.selected_object = at_or_after.min(objects.len() - 1);
+        self.folder_cursor = None;
+        true
+    }
+
     pub fn reset_pagination(&mut self) {
         self.objects.clear();
         self.filtered_objects.clear();
+        self.folders.clear();
+        self.folder_cursor = None;
         self.total_object_count = None;
-        self.continuation_token = None;
+        self.listing = None;
         self.is_loading_objects = false;
         self.selected_object = 0;
     }
 
     pub fn has_more_objects(&self) -> bool {
-        self.continuation_token.is_some()
+        self.listing.is_some()
     }
 
     pub fn should_load_more(&self) -> bool {
         // Load more if we're near the end (within last 50 items)
         let threshold = 50;
-        let current_pos = self.selected_object;
-        let loaded_count = self.objects.len();
+        let current_pos = self.cursor_row();
+        // Folder rows count, so a level of only subfolders keeps paging
+        let loaded_count = self.visible_folders().len() + self.objects.len();
 
         if loaded_count == 0 {
             return false;
         }
 
-        // If we have a mask and few matches, load more
-        if let Some(_mask) = &self.active_mask {
+        // If we have a mask or restore filter and few matches, load more
+        if self.active_mask.is_some() || self.restore_filter.is_some() {
             let match_count = self.filtered_objects.len();
             if match_count < 100 && self.has_more_objects() {
                 return true;
@@ -306,55 +1585,127 @@ impl App {
         current_pos + threshold >= loaded_count && self.has_more_objects()
     }
 
+    /// Recompute the objects the active mask and restore filter select, in
+    /// restore status order when sorting by it
+    pub fn refilter(&mut self) {
+        if !self.is_filtered() {
+            self.filtered_objects.clear();
+            return;
+        }
+        let reads = self.selected_last_reads();
+        // Compiled once for the whole listing
+        let mask = self.active_mask.as_ref().map(|mask| (mask, mask.matcher()));
+        self.filtered_objects = self
+            .objects
+            .iter()
+            .filter(|&obj| {
+                mask.as_ref().is_none_or(|(mask, matcher)| {
+                    matcher.matches_object(obj) && mask.matches_unread(obj, reads)
+                }) && self
+                    .restore_filter
+                    .is_none_or(|status| RestoreStatus::of(obj) == Some(status))
+            })
+            .cloned()
+            .collect();
+        if self.restore_sort {
+            // Stable, so keys stay in order within a status; no status last
+            self.filtered_objects.sort_by_key(|obj| {
+                RestoreStatus::of(obj).map_or(RestoreStatus::ALL.len(), |s| s as usize)
+            });
+        }
+    }
+
+    /// Refilter, keeping the cursor on the same object
+    pub fn refilter_keeping_selection(&mut self) {
+        let selected_key = self.selected_object().map(|o| o.key.clone());
+        self.refilter();
+        self.select_key(selected_key);
+    }
+
+    /// Move the selection back onto `key` after the rows changed, or keep it
+    /// within the rows when the key is gone
+    fn select_key(&mut self, selected_key: Option<String>) {
+        if let Some(key) = selected_key
+            && let Some(idx) = self.active_objects().iter().position(|o| o.key == key)
+        {
+            self.selected_object = idx;
+        }
+        let len = self.active_objects().len();
+        if self.selected_object >= len {
+            self.selected_object = len.saturating_sub(1);
+        }
+    }
+
     pub fn apply_mask(&mut self, mask: Option<ObjectMask>) {
         self.active_mask = mask.clone();
         if let Some(mask) = mask {
-            self.filtered_objects = self
-                .objects
-                .iter()
-                .filter(|&obj| {
-                    // Filter by key pattern
-                    let key_matches = mask.matches(&obj.key);
-
-                    // Filter by storage class if specified
-                    let storage_matches = mask
-                        .storage_class_filter
-                        .as_ref()
-                        .map(|filter| &obj.storage_class == filter)
-                        .unwrap_or(true); // If no filter, all storage classes match
-
-                    key_matches && storage_matches
-                })
-                .cloned()
-                .collect();
+            self.refilter();
             self.selected_object = 0;
             if self.filtered_objects.is_empty() {
-                self.push_status("Mask applied but matched no objects");
+                self.push_status(t("status.mask_no_match"));
             } else {
-                self.push_status(&format!(
-                    "Mask '{}' matched {} objects",
-                    mask.name,
-                    self.filtered_objects.len()
+                self.push_status(&tf(
+                    "status.mask_matched",
+                    &[
+                        ("name", &mask.name),
+                        ("count", &self.filtered_objects.len()),
+                    ],
                 ));
             }
         } else {
-            self.filtered_objects.clear();
-            self.push_status("Cleared mask filter");
+            self.refilter();
+            self.push_status(t("status.mask_cleared"));
+        }
+    }
+
+    /// Show only objects with the next restore status, or all of them again
+    /// after the last one
+    pub fn cycle_restore_filter(&mut self) {
+        self.restore_filter = RestoreStatus::cycle(self.restore_filter);
+        self.excluded.clear();
+        self.refilter();
+        self.selected_object = 0;
+        match self.restore_filter {
+            Some(status) => self.push_status(&tf(
+                "status.restore_filter",
+                &[
+                    ("status", &status.label()),
+                    ("count", &self.filtered_objects.len()),
+                ],
+            )),
+            None => self.push_status(t("status.restore_filter_cleared")),
+        }
+    }
+
+    /// Group the list by restore status, or return it to key order
+    pub fn toggle_restore_sort(&mut self) {
+        self.restore_sort = !self.restore_sort;
+        self.refilter_keeping_selection();
+        if self.restore_sort {
+            self.push_status(t("status.sorted_restore"));
+        } else {
+            self.push_status(t("status.sorted_key"));
         }
     }
 
     pub fn next_pane(&mut self) {
+        let split = self.split.is_some();
         self.active_pane = match self.active_pane {
             ActivePane::Buckets => ActivePane::Objects,
+            ActivePane::Objects if split => ActivePane::PeerObjects,
             ActivePane::Objects => ActivePane::Buckets,
+            ActivePane::PeerObjects => ActivePane::Buckets,
             ActivePane::MaskEditor => ActivePane::Buckets,
         };
     }
 
     pub fn previous_pane(&mut self) {
+        let split = self.split.is_some();
         self.active_pane = match self.active_pane {
+            ActivePane::Buckets if split => ActivePane::PeerObjects,
             ActivePane::Buckets => ActivePane::Objects,
             ActivePane::Objects => ActivePane::Buckets,
+            ActivePane::PeerObjects => ActivePane::Objects,
             ActivePane::MaskEditor => ActivePane::Buckets,
         };
     }
@@ -364,23 +1715,304 @@ impl App {
             self.status.pop_front();
         }
         self.status.push_back(status.to_string());
+        crash::note(status);
+        // A paused log view keeps showing the same messages
+        if self.log_scroll > 0 && self.log_job.is_none() {
+            self.log_scroll = (self.log_scroll + 1).min(self.status.len() - 1);
+        }
+    }
+
+    pub fn shard_state(&self, plan: &ShardPlan, shard: &Shard) -> ShardState {
+        if shard.is_done() {
+            return ShardState::Done;
+        }
+        if plan.shared {
+            return match shard.latest_run() {
+                _ if shard.worker.is_some() => ShardState::Running,
+                _ if !plan.published => ShardState::Queued,
+                Some(_) if shard.failed > 0 => ShardState::Failed,
+                Some(_) => ShardState::Interrupted,
+                None => ShardState::Pending,
+            };
+        }
+        match shard.latest_run() {
+            None => ShardState::Pending,
+            Some(run) => match self.jobs.get(run) {
+                Some(job) if job.started => ShardState::Running,
+                Some(_) => ShardState::Queued,
+                None if shard.failed > 0 => ShardState::Failed,
+                None => ShardState::Interrupted,
+            },
+        }
+    }
+
+    /// The plan and index of the shard under the matrix cursor
+    pub fn selected_shard(&self) -> Option<(&ShardPlan, usize)> {
+        let mut cursor = self.shard_cursor;
+        for plan in self.shards.plans() {
+            if cursor < plan.shards.len() {
+                return Some((plan, cursor));
+            }
+            cursor -= plan.shards.len();
+        }
+        None
+    }
+
+    /// Start the log of a job just begun in the journal
+    pub fn begin_job_log(&mut self, job_id: &str, title: &str) {
+        if self.job_logs.len() == JOB_LOGS_KEPT {
+            self.job_logs.pop_front();
+        }
+        self.job_logs.push_back(JobLog {
+            job_id: job_id.to_string(),
+            title: title.to_string(),
+            messages: VecDeque::new(),
+            dropped: 0,
+        });
+    }
+
+    pub fn job_log(&self, job_id: &str) -> Option<&JobLog> {
+        self.job_logs.iter().find(|log| log.job_id == job_id)
+    }
+
+    /// Add a per-object message to a job's log. The first one also tells the
+    /// status log where to find the rest.
+    pub fn push_job_status(&mut self, job_id: &str, status: &str) {
+        let Some(log) = self.job_logs.iter_mut().find(|log| log.job_id == job_id) else {
+            self.push_status(status);
+            return;
+        };
+        if log.messages.len() == JOB_LOG_LIMIT {
+            log.messages.pop_front();
+            log.dropped += 1;
+        }
+        log.messages.push_back(status.to_string());
+        let (first, len) = (
+            log.messages.len() == 1 && log.dropped == 0,
+            log.messages.len(),
+        );
+        let notice = first.then(|| tf("status.job_log_notice", &[("title", &log.title)]));
+        crash::note(status);
+        if self.log_scroll > 0 && self.log_job.as_deref() == Some(job_id) {
+            self.log_scroll = (self.log_scroll + 1).min(len - 1);
+        }
+        if let Some(notice) = notice {
+            self.push_status(&notice);
+        }
+    }
+
+    /// Messages the log view shows: the chosen job's log, else the status log
+    pub fn shown_log(&self) -> &VecDeque<String> {
+        self.log_job
+            .as_deref()
+            .and_then(|job_id| self.job_log(job_id))
+            .map_or(&self.status, |log| &log.messages)
+    }
+
+    /// Scroll the log view by `lines` (positive is up, towards older
+    /// messages), pausing or resuming follow mode
+    pub fn scroll_log(&mut self, lines: isize) {
+        let max = self.shown_log().len().saturating_sub(1);
+        self.log_scroll = self.log_scroll.saturating_add_signed(lines).min(max);
+    }
+
+    /// Log a failed request as `context: description`. Network errors count
+    /// towards going offline, and are no longer logged once offline.
+    pub fn push_error(&mut self, context: &str, err: &anyhow::Error) {
+        self.log_failure(None, context, err);
+    }
+
+    /// Log a failed request of a job to the job's log, like `push_error`
+    pub fn push_job_error(&mut self, job_id: &str, context: &str, err: &anyhow::Error) {
+        self.log_failure(Some(job_id), context, err);
+    }
+
+    fn log_failure(&mut self, job_id: Option<&str>, context: &str, err: &anyhow::Error) {
+        let status = format!("{context}: {}", errors::describe(err));
+        match errors::classify(err) {
+            Some(ErrorKind::Network) => match self.connectivity.record_network_failure() {
+                NetworkFailure::Log => self.log_to(job_id, &status),
+                NetworkFailure::WentOffline => {
+                    self.log_to(job_id, &status);
+                    self.push_status(t("status.went_offline"));
+                }
+                NetworkFailure::Suppress => {}
+            },
+            // AWS answered, if only with an error
+            Some(_) => {
+                self.note_reached();
+                self.log_to(job_id, &status);
+            }
+            None => self.log_to(job_id, &status),
+        }
+    }
+
+    fn log_to(&mut self, job_id: Option<&str>, status: &str) {
+        match job_id {
+            Some(job_id) => self.push_job_status(job_id, status),
+            None => self.push_status(status),
+        }
+    }
+
+    /// Record a request that got an answer from AWS, announcing the end of an
+    /// outage
+    pub fn note_reached(&mut self) {
+        if let Some((down, suppressed)) = self.connectivity.record_reached() {
+            self.push_status(&tf(
+                "status.reconnected",
+                &[("seconds", &down.as_secs()), ("suppressed", &suppressed)],
+            ));
+        }
+    }
+
+    /// Apply an event to the state. This never talks to AWS; follow-up
+    /// requests an event calls for are started by the TUI event loop.
+    pub fn update(&mut self, event: AppEvent) {
+        match event {
+            AppEvent::ObjectsLoaded(objects) => {
+                self.append_objects(objects);
+                self.note_reached();
+            }
+            AppEvent::FoldersLoaded(folders) => {
+                // The cursor starts on the first row, which is now a folder
+                if self.folders.is_empty()
+                    && self.folder_cursor.is_none()
+                    && self.selected_object == 0
+                {
+                    self.folder_cursor = Some(0);
+                }
+                self.folders.extend(folders);
+                self.folders.sort();
+                self.folders.dedup();
+                self.note_reached();
+            }
+            AppEvent::Enriched(enriched) => {
+                // The mask does not depend on what HeadObject adds
+                let shown = self
+                    .objects
+                    .iter_mut()
+                    .chain(self.filtered_objects.iter_mut())
+                    .filter(|obj| obj.key == enriched.key && obj.is_current());
+                for obj in shown {
+                    enriched.apply(obj);
+                }
+                if let (Some(state), Some(enrichment)) = (&enriched.restore_state, &self.enrichment)
+                {
+                    self.refreshed_restores.push((
+                        enrichment.bucket.clone(),
+                        enriched.key.clone(),
+                        state.clone(),
+                        enriched.restore_expiry.clone(),
+                    ));
+                }
+                // Rows move in or out of a restore filter as their status arrives
+                if self.restore_filter.is_some() || self.restore_sort {
+                    self.refilter_keeping_selection();
+                }
+            }
+            AppEvent::ListingFinished => {
+                // Only a listing of the whole bucket gives its size
+                if let Some(listing) = self.listing.take()
+                    && !listing.failed
+                    && self.window_start().is_none()
+                    && self.current_prefix().is_none()
+                    && !self.folder_view
+                    && !self.version_view
+                {
+                    let bytes = self.objects.iter().map(|o| o.size.max(0) as u64).sum();
+                    self.bucket_sizes
+                        .record(&listing.bucket, bytes, self.objects.len());
+                }
+                self.is_loading_objects = false;
+                self.push_status(&tf("status.loaded_all", &[("count", &self.objects.len())]));
+            }
+            AppEvent::SplitObjectsLoaded(objects) => {
+                if let Some(split) = &mut self.split {
+                    split.append_objects(objects);
+                    self.note_reached();
+                }
+            }
+            AppEvent::SplitListingFinished => {
+                if let Some(split) = &mut self.split {
+                    split.listing = None;
+                }
+            }
+            AppEvent::JobProgress { done, key } => {
+                if let Some(progress) = &mut self.progress {
+                    progress.update(done, Some(key));
+                }
+            }
+            AppEvent::ConnectionProbed(reached) => {
+                if reached {
+                    self.note_reached();
+                }
+            }
+            AppEvent::Reconciled(event) => {
+                let Some(summary) = &mut self.reconciliation else {
+                    return;
+                };
+                summary.apply(event);
+                if !summary.finished {
+                    return;
+                }
+                let message = match &summary.error {
+                    Some(err) => tf(
+                        "status.reconcile_failed",
+                        &[
+                            ("source", &summary.source_bucket),
+                            ("dest", &summary.dest_bucket),
+                            ("listed", &summary.source_listed),
+                            ("err", &err),
+                        ],
+                    ),
+                    None => tf(
+                        "status.reconciled",
+                        &[
+                            ("source", &summary.source_bucket),
+                            ("dest", &summary.dest_bucket),
+                            ("matched", &summary.matched),
+                            ("discrepancies", &summary.discrepancies()),
+                            ("export", &summary.export.display()),
+                        ],
+                    ),
+                };
+                self.reconcile = None;
+                self.push_status(&message);
+            }
+            AppEvent::NewRelease(release) => {
+                let mut notice = tf(
+                    "status.new_release",
+                    &[("version", &release.version()), ("running", &VERSION)],
+                );
+                if let Some(url) = &release.html_url {
+                    notice.push_str(&format!(": {url}"));
+                }
+                self.push_status(&notice);
+                self.new_release = Some(release);
+            }
+            AppEvent::Error { context, err } => self.push_error(&context, &err),
+        }
     }
 
     pub fn cycle_mask_kind(&mut self) {
         self.mask_draft.kind = match self.mask_draft.kind {
             MaskKind::Prefix => MaskKind::Suffix,
             MaskKind::Suffix => MaskKind::Contains,
-            MaskKind::Contains => MaskKind::Regex,
-            MaskKind::Regex => MaskKind::Prefix,
+            MaskKind::Contains => MaskKind::Glob,
+            MaskKind::Glob => MaskKind::Regex,
+            MaskKind::Regex => MaskKind::Script,
+            MaskKind::Script => MaskKind::Prefix,
         };
     }
 
     pub fn cycle_mask_kind_backwards(&mut self) {
         self.mask_draft.kind = match self.mask_draft.kind {
-            MaskKind::Prefix => MaskKind::Regex,
+            MaskKind::Prefix => MaskKind::Script,
             MaskKind::Suffix => MaskKind::Prefix,
             MaskKind::Contains => MaskKind::Suffix,
-            MaskKind::Regex => MaskKind::Contains,
+            MaskKind::Regex => MaskKind::Glob,
+            MaskKind::Glob => MaskKind::Contains,
+            MaskKind::Script => MaskKind::Regex,
         };
     }
 
@@ -388,6 +2020,21 @@ impl App {
         self.mask_draft.case_sensitive = !self.mask_draft.case_sensitive;
     }
 
+    /// Step the unread filter through `UNREAD_DAY_CHOICES`, wrapping
+    pub fn cycle_mask_unread(&mut self, forward: bool) {
+        let count = UNREAD_DAY_CHOICES.len();
+        let current = UNREAD_DAY_CHOICES
+            .iter()
+            .position(|days| *days == self.mask_draft.unread_days)
+            .unwrap_or(0);
+        let next = if forward {
+            (current + 1) % count
+        } else {
+            (current + count - 1) % count
+        };
+        self.mask_draft.unread_days = UNREAD_DAY_CHOICES[next];
+    }
+
     pub fn set_mode(&mut self, mode: AppMode) {
         self.mode = mode;
     }
@@ -404,16 +2051,54 @@ impl App {
         self.mask_field = self.mask_field.previous();
     }
 
+    /// Why a storage class cannot be picked for the current storage intent
+    pub fn picker_class_unavailable(&self, class: &StorageClassTier) -> Option<String> {
+        match self.storage_intent {
+            StorageIntent::Transition => self.target_class_unavailable(class),
+            StorageIntent::ExportLifecycle => {
+                crate::lifecycle::unavailable(class).map(|r| r.to_string())
+            }
+            StorageIntent::SavePolicy => match class {
+                StorageClassTier::ReducedRedundancy => {
+                    Some("deprecated by AWS and costs more than STANDARD".to_string())
+                }
+                StorageClassTier::Unknown(_) => Some("not a known storage class".to_string()),
+                _ => None,
+            },
+        }
+    }
+
+    /// Why a storage class cannot be the transition target for the current
+    /// targets, or None if it is a valid choice
+    pub fn target_class_unavailable(&self, class: &StorageClassTier) -> Option<String> {
+        match class {
+            StorageClassTier::ReducedRedundancy => {
+                return Some("deprecated by AWS and costs more than STANDARD".to_string());
+            }
+            StorageClassTier::Unknown(_) => return Some("not a known storage class".to_string()),
+            _ => {}
+        }
+
+        let objects = self.target_objects();
+
+        if !objects.is_empty() && objects.iter().all(|obj| &obj.storage_class == class) {
+            return Some(if objects.len() == 1 {
+                format!("object is already {}", class.label())
+            } else {
+                format!(
+                    "all {} objects are already {}",
+                    objects.len(),
+                    class.label()
+                )
+            });
+        }
+        None
+    }
+
     /// Check if any of the targeted objects need restoration
     /// (i.e., they are in Glacier storage class and not already restored)
     pub fn any_targets_need_restoration(&self) -> bool {
-        let objects = if self.active_mask.is_some() {
-            &self.filtered_objects
-        } else if let Some(obj) = self.objects.get(self.selected_object) {
-            std::slice::from_ref(obj)
-        } else {
-            return false;
-        };
+        let objects = self.target_objects();
 
         objects.iter().any(|obj| {
             matches!(
@@ -429,13 +2114,7 @@ impl App {
 
     /// Get count of objects that need restore (not already restored/restoring)
     pub fn count_objects_needing_restore(&self) -> usize {
-        let objects = if self.active_mask.is_some() {
-            &self.filtered_objects
-        } else if let Some(obj) = self.objects.get(self.selected_object) {
-            std::slice::from_ref(obj)
-        } else {
-            return 0;
-        };
+        let objects = self.target_objects();
 
         objects
             .iter()
@@ -453,15 +2132,49 @@ impl App {
             .count()
     }
 
+    /// Pacing of a restore run with `preset`, and whether its restores go
+    /// unannounced
+    pub fn restore_settings(&self, preset: Option<&str>) -> (RestorePacing, bool) {
+        self.restore_pacing
+            .with_preset(preset.and_then(|name| self.restore_presets.get(name)))
+    }
+
+    /// Longest restore every target needing one accepts, the tightest limit
+    /// of their classes
+    pub fn max_restore_days(&self) -> i32 {
+        let objects = match &self.pending_action {
+            Some(PendingAction::RestoreVersions { versions, .. }) => versions.iter().collect(),
+            _ => self.target_objects(),
+        };
+        objects
+            .iter()
+            .filter(|obj| {
+                matches!(
+                    obj.storage_class,
+                    StorageClassTier::GlacierFlexibleRetrieval
+                        | StorageClassTier::GlacierDeepArchive
+                )
+            })
+            .map(|obj| obj.storage_class.max_restore_days())
+            .min()
+            .unwrap_or(StorageClassTier::GlacierFlexibleRetrieval.max_restore_days())
+    }
+
+    /// Bucket the pending action reads its keys from
+    pub fn pending_bucket(&self) -> Option<&str> {
+        match &self.pending_action {
+            Some(PendingAction::Copy { source_bucket, .. }) => Some(source_bucket),
+            Some(
+                PendingAction::RetrieveBundled { bucket, .. }
+                | PendingAction::RunPolicy { bucket, .. },
+            ) => Some(bucket),
+            _ => self.selected_bucket_name(),
+        }
+    }
+
     /// Get count of objects already being restored
     pub fn count_objects_restoring(&self) -> usize {
-        let objects = if self.active_mask.is_some() {
-            &self.filtered_objects
-        } else if let Some(obj) = self.objects.get(self.selected_object) {
-            std::slice::from_ref(obj)
-        } else {
-            return 0;
-        };
+        let objects = self.target_objects();
 
         objects
             .iter()
@@ -474,3 +2187,133 @@ impl App {
             .count()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s3_migration_core::enrich::Enriched;
+
+    fn object(key: &str) -> ObjectInfo {
+        ObjectInfo {
+            key: key.to_string(),
+            size: 10,
+            last_modified: None,
+            storage_class: StorageClassTier::GlacierFlexibleRetrieval,
+            restore_state: None,
+            restore_expiry: None,
+            owner: None,
+            tags: None,
+            encryption: None,
+            version: None,
+        }
+    }
+
+    fn keys(objects: &[ObjectInfo]) -> Vec<&str> {
+        objects.iter().map(|obj| obj.key.as_str()).collect()
+    }
+
+    #[test]
+    fn loaded_pages_merge_in_key_order() {
+        let mut app = App::new();
+        app.update(AppEvent::ObjectsLoaded(vec![object("b"), object("d")]));
+        app.update(AppEvent::ObjectsLoaded(vec![object("a"), object("b")]));
+        assert_eq!(keys(&app.objects), ["a", "b", "d"]);
+        assert_eq!(keys(app.active_objects()), ["a", "b", "d"]);
+    }
+
+    #[test]
+    fn loaded_pages_keep_the_selected_row() {
+        let mut app = App::new();
+        app.update(AppEvent::ObjectsLoaded(vec![object("b"), object("c")]));
+        app.selected_object = 1;
+        app.update(AppEvent::ObjectsLoaded(vec![object("a")]));
+        assert_eq!(app.selected_object().map(|obj| obj.key.as_str()), Some("c"));
+    }
+
+    #[test]
+    fn first_folders_put_the_cursor_on_a_folder() {
+        let mut app = App::new();
+        app.update(AppEvent::FoldersLoaded(vec!["raw/".into(), "logs/".into()]));
+        app.update(AppEvent::FoldersLoaded(vec![
+            "logs/".into(),
+            "media/".into(),
+        ]));
+        assert_eq!(app.folders, ["logs/", "media/", "raw/"]);
+        assert_eq!(app.folder_cursor, Some(0));
+    }
+
+    #[test]
+    fn enrichment_fills_in_the_listed_row() {
+        let mut app = App::new();
+        app.update(AppEvent::ObjectsLoaded(vec![object("a"), object("b")]));
+        app.update(AppEvent::Enriched(Enriched {
+            key: "b".into(),
+            restore_state: Some(RestoreState::Available),
+            restore_expiry: Some("2030-01-01T00:00:00Z".into()),
+            encryption: None,
+        }));
+        assert_eq!(app.objects[0].restore_state, None);
+        assert_eq!(app.objects[1].restore_state, Some(RestoreState::Available));
+        // Without a running enrichment there is no bucket to track it under
+        assert!(app.refreshed_restores.is_empty());
+    }
+
+    #[test]
+    fn restore_filter_follows_enrichment() {
+        let mut app = App::new();
+        app.restore_filter = Some(RestoreStatus::Restored);
+        app.update(AppEvent::ObjectsLoaded(vec![object("a"), object("b")]));
+        assert!(app.active_objects().is_empty());
+        app.update(AppEvent::Enriched(Enriched {
+            key: "a".into(),
+            restore_state: Some(RestoreState::Available),
+            restore_expiry: None,
+            encryption: None,
+        }));
+        assert_eq!(keys(app.active_objects()), ["a"]);
+    }
+
+    #[test]
+    fn finished_listing_stops_loading() {
+        let mut app = App::new();
+        app.is_loading_objects = true;
+        app.update(AppEvent::ObjectsLoaded(vec![object("a"), object("b")]));
+        app.update(AppEvent::ListingFinished);
+        assert!(!app.is_loading_objects);
+        assert_eq!(
+            app.status.back().map(String::as_str),
+            Some("Loaded all 2 objects")
+        );
+    }
+
+    #[test]
+    fn job_progress_moves_the_progress_bar() {
+        let mut app = App::new();
+        app.update(AppEvent::JobProgress {
+            done: 1,
+            key: "a".into(),
+        });
+        assert!(app.progress.is_none());
+
+        app.progress = Some(ProgressState::new("Transition".into(), 4));
+        app.update(AppEvent::JobProgress {
+            done: 2,
+            key: "b".into(),
+        });
+        let progress = app.progress.as_ref().unwrap();
+        assert_eq!(progress.current, 2);
+        assert_eq!(progress.current_item.as_deref(), Some("b"));
+        assert_eq!(progress.percentage(), 50);
+    }
+
+    #[test]
+    fn errors_go_to_the_status_log() {
+        let mut app = App::new();
+        app.update(AppEvent::Error {
+            context: "Listing media-archive".into(),
+            err: anyhow::anyhow!("boom"),
+        });
+        let status = app.status.back().unwrap();
+        assert!(status.starts_with("Listing media-archive: "), "{status}");
+    }
+}
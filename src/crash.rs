@@ -0,0 +1,93 @@
+use std::backtrace::Backtrace;
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::fs;
+use std::panic::PanicHookInfo;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Status messages copied into a crash report, oldest first
+const RECENT_LIMIT: usize = 50;
+
+static RECENT: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// Remember a status message for a crash report
+pub fn note(message: &str) {
+    let Ok(mut recent) = RECENT.lock() else {
+        return;
+    };
+    if recent.len() == RECENT_LIMIT {
+        recent.pop_front();
+    }
+    recent.push_back(message.to_string());
+}
+
+/// Print a panic and where its report was saved. Call once the terminal is
+/// back in cooked mode, or the message is lost in the alternate screen.
+pub fn report(info: &PanicHookInfo) {
+    let thread = std::thread::current();
+    let summary = format!(
+        "thread '{}' panicked at {}:\n{}",
+        thread.name().unwrap_or("<unnamed>"),
+        info.location()
+            .map(|at| at.to_string())
+            .unwrap_or_else(|| "an unknown location".into()),
+        payload(info)
+    );
+    eprintln!("\n{summary}\n");
+    match write_report(&summary) {
+        Ok(path) => eprintln!(
+            "bucket-brigade crashed. A crash report with a backtrace and the last status \
+             messages was written to\n  {}\nPlease attach it when reporting the problem.",
+            path.display()
+        ),
+        Err(err) => {
+            eprintln!("bucket-brigade crashed, and the crash report could not be written: {err}")
+        }
+    }
+}
+
+fn payload(info: &PanicHookInfo) -> String {
+    if let Some(message) = info.payload().downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = info.payload().downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "(no message)".to_string()
+    }
+}
+
+/// Save the report under `crashes/` in the config directory
+fn write_report(summary: &str) -> std::io::Result<PathBuf> {
+    let dir = directories::ProjectDirs::from("com", "bucket-brigade", "bucket-brigade")
+        .map(|dirs| dirs.config_dir().join("crashes"))
+        .unwrap_or_else(|| PathBuf::from("crashes"));
+    fs::create_dir_all(&dir)?;
+    let now = chrono::Local::now();
+    let path = dir.join(format!("crash-{}.txt", now.format("%Y%m%d-%H%M%S")));
+
+    let mut text = String::new();
+    let _ = writeln!(
+        text,
+        "bucket-brigade {} crashed at {}",
+        env!("CARGO_PKG_VERSION"),
+        now.to_rfc3339()
+    );
+    let _ = writeln!(
+        text,
+        "{} {}, args: {:?}\n",
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        std::env::args().skip(1).collect::<Vec<_>>()
+    );
+    let _ = writeln!(text, "{summary}\n");
+    let _ = writeln!(text, "Last status messages:");
+    if let Ok(recent) = RECENT.lock() {
+        for message in recent.iter() {
+            let _ = writeln!(text, "  {message}");
+        }
+    }
+    let _ = writeln!(text, "\nBacktrace:\n{}", Backtrace::force_capture());
+    fs::write(&path, text)?;
+    Ok(path)
+}
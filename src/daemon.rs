@@ -0,0 +1,968 @@
+use std::cell::{Cell, RefCell};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::Notify;
+use tokio::task::LocalSet;
+
+use crate::approvals::ApprovalStore;
+use crate::aws::S3Service;
+use crate::bandwidth::{Throttle, ThroughputLog};
+use crate::batch::{ShutdownChoice, apply_object_lock, move_source, transition_object};
+use crate::collisions::CollisionReview;
+use crate::digest::{Digest, DigestConfig, DigestRun};
+use crate::errors;
+use crate::hooks::{HookFailure, ObjectHook};
+use crate::journal::JobHistory;
+use crate::models::{CopyMode, JobKind, StorageClassTier};
+use crate::multipart::{self, CopyOutcome, MULTIPART_COPY_THRESHOLD, MultipartStore};
+use crate::plan::{MigrationPlan, PlanAction};
+use crate::policy::{self, MigrationPolicy, PolicyStore};
+use crate::protect::ProtectedKeys;
+use crate::schedule;
+use crate::tickets::{self, TicketConfig};
+use crate::tracker::RestoreTracker;
+
+/// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// A well-formed request the daemon cannot carry out, such as an unknown job
+const REQUEST_FAILED: i64 = -32000;
+
+/// How often a throttled job checks whether it was cancelled
+const HOLD_TICK: Duration = Duration::from_millis(200);
+
+/// Socket path from `--socket`, else `daemon.sock` in the per-user
+/// directory from `socket_dir`
+pub fn socket_path(args: &[String]) -> PathBuf {
+    if let Some(pair) = args.windows(2).find(|pair| pair[0] == "--socket") {
+        return PathBuf::from(&pair[1]);
+    }
+    socket_dir().join("daemon.sock")
+}
+
+/// `bucket-brigade` in `$XDG_RUNTIME_DIR`, else `bucket-brigade-<uid>` in the
+/// temp directory, which other users can write to
+fn socket_dir() -> PathBuf {
+    match std::env::var_os("XDG_RUNTIME_DIR") {
+        Some(dir) => PathBuf::from(dir).join("bucket-brigade"),
+        None => std::env::temp_dir().join(format!("bucket-brigade-{}", current_uid())),
+    }
+}
+
+fn current_uid() -> u32 {
+    // SAFETY: getuid has no preconditions and cannot fail
+    unsafe { libc::getuid() }
+}
+
+/// Create the default socket directory as 0700, or check that the one already
+/// there belongs to us and nobody else can get in
+fn private_socket_dir(dir: &Path) -> Result<()> {
+    use std::os::unix::fs::{DirBuilderExt, MetadataExt};
+
+    match std::fs::DirBuilder::new().mode(0o700).create(dir) {
+        Ok(()) => return Ok(()),
+        Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => {}
+        Err(err) => {
+            return Err(err).with_context(|| format!("could not create {}", dir.display()));
+        }
+    }
+    let meta = std::fs::symlink_metadata(dir)?;
+    if !meta.is_dir() || meta.uid() != current_uid() {
+        bail!(
+            "{} is not a directory owned by this user; pass --socket",
+            dir.display()
+        );
+    }
+    if meta.mode() & 0o077 != 0 {
+        bail!(
+            "{} can be opened by other users; run chmod 700 on it",
+            dir.display()
+        );
+    }
+    Ok(())
+}
+
+/// Remove the socket left over from a daemon that did not shut down cleanly,
+/// refusing anything that is not a socket we own
+fn remove_stale_socket(socket: &Path) -> Result<()> {
+    use std::os::unix::fs::{FileTypeExt, MetadataExt};
+
+    let meta = std::fs::symlink_metadata(socket)?;
+    if !meta.file_type().is_socket() || meta.uid() != current_uid() {
+        bail!(
+            "{} is not a socket owned by this user; not removing it",
+            socket.display()
+        );
+    }
+    std::fs::remove_file(socket)?;
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum JobState {
+    Queued,
+    /// Listing the bucket and matching the plan's mask
+    Selecting,
+    Running,
+    Finished,
+    Failed,
+    Cancelled,
+}
+
+/// A submitted plan and how far it got, as `job_status` reports it
+#[derive(Serialize)]
+struct Job {
+    id: u64,
+    state: JobState,
+    #[serde(skip)]
+    plan: MigrationPlan,
+    /// Saved policy the job runs on schedule, as `--run-policy` would
+    policy: Option<String>,
+    #[serde(skip)]
+    scheduled: Option<MigrationPolicy>,
+    bucket: String,
+    kind: &'static str,
+    target: String,
+    /// Objects the plan acts on, once selected
+    total: usize,
+    done: usize,
+    succeeded: usize,
+    failed: usize,
+    /// Objects already in the target state, or skipped by the copy mode
+    skipped: usize,
+    /// Protected keys left out
+    protected: usize,
+    current_key: Option<String>,
+    /// Id of the job in the history, which holds the per-key journal
+    journal_id: Option<String>,
+    /// Why the job failed or stopped early
+    error: Option<String>,
+    submitted_at: String,
+    finished_at: Option<String>,
+    /// Set by `cancel_job`; a running job stops before its next object and
+    /// checkpoints a multipart copy in flight
+    #[serde(skip)]
+    stop: Rc<Cell<Option<ShutdownChoice>>>,
+}
+
+#[derive(Default)]
+struct Jobs {
+    jobs: Vec<Job>,
+    next_id: u64,
+    /// No-delete mode: moves are refused when submitted
+    no_delete: bool,
+    /// Scheduled policy runs since the last digest was sent
+    digest: Vec<DigestRun>,
+}
+
+impl Jobs {
+    /// Queue `plan`, run as the saved policy `scheduled` when there is one
+    fn push(&mut self, plan: MigrationPlan, scheduled: Option<MigrationPolicy>) -> u64 {
+        self.next_id += 1;
+        let id = self.next_id;
+        self.jobs.push(Job {
+            id,
+            state: JobState::Queued,
+            bucket: plan.bucket.clone(),
+            kind: plan.action.job_kind().label(),
+            target: plan.action.target_label(),
+            plan,
+            policy: scheduled.as_ref().map(|policy| policy.name.clone()),
+            scheduled,
+            total: 0,
+            done: 0,
+            succeeded: 0,
+            failed: 0,
+            skipped: 0,
+            protected: 0,
+            current_key: None,
+            journal_id: None,
+            error: None,
+            submitted_at: chrono::Utc::now().to_rfc3339(),
+            finished_at: None,
+            stop: Rc::new(Cell::new(None)),
+        });
+        id
+    }
+
+    fn get(&mut self, id: u64) -> Result<&mut Job> {
+        self.jobs
+            .iter_mut()
+            .find(|job| job.id == id)
+            .with_context(|| format!("no job {id}"))
+    }
+}
+
+type Shared = Rc<(RefCell<Jobs>, Notify)>;
+
+/// Serve the JSON-RPC control interface on `socket` and run submitted plans
+/// one after another until the process is stopped. With a `digest`, its
+/// policies also run on schedule and a digest of those runs goes out daily.
+pub async fn run(
+    s3: S3Service,
+    socket: &Path,
+    hook: Option<ObjectHook>,
+    max_bandwidth: Option<u64>,
+    require_approval: bool,
+    digest: Option<DigestConfig>,
+) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    if socket.parent() == Some(socket_dir().as_path()) {
+        private_socket_dir(&socket_dir())?;
+    }
+    if std::fs::symlink_metadata(socket).is_ok() {
+        if UnixStream::connect(socket).await.is_ok() {
+            bail!("another daemon is listening on {}", socket.display());
+        }
+        remove_stale_socket(socket)?;
+    }
+    let history = JobHistory::new()?;
+    let uploads = MultipartStore::new()?;
+    let tracker = RestoreTracker::new()?;
+    let throughput = ThroughputLog::new()?;
+    // SAFETY: umask has no preconditions. The mask keeps the socket private
+    // from the moment it exists, before the chmod below.
+    let umask = unsafe { libc::umask(0o177) };
+    let listener = UnixListener::bind(socket);
+    unsafe { libc::umask(umask) };
+    let listener = listener.with_context(|| format!("could not listen on {}", socket.display()))?;
+    std::fs::set_permissions(socket, std::fs::Permissions::from_mode(0o600))?;
+    eprintln!("bucket-brigade daemon listening on {}", socket.display());
+
+    let jobs = Jobs {
+        no_delete: s3.deletes_disabled(),
+        ..Jobs::default()
+    };
+    let shared: Shared = Rc::new((RefCell::new(jobs), Notify::new()));
+    let local = LocalSet::new();
+    let result = local
+        .run_until(async {
+            if let Some(config) = digest {
+                tokio::task::spawn_local(run_schedule(shared.clone(), config, s3.clone()));
+            }
+            let engine = Engine {
+                s3,
+                hook,
+                history,
+                uploads,
+                tracker,
+                max_bandwidth,
+                require_approval,
+                throttle: Throttle::default(),
+                throughput,
+            };
+            tokio::task::spawn_local(run_jobs(shared.clone(), engine));
+            loop {
+                tokio::select! {
+                    accepted = listener.accept() => {
+                        let (stream, _) = accepted?;
+                        tokio::task::spawn_local(serve(stream, shared.clone()));
+                    }
+                    _ = tokio::signal::ctrl_c() => return Ok(()),
+                }
+            }
+        })
+        .await;
+    let _ = std::fs::remove_file(socket);
+    result
+}
+
+/// Answer one client's requests, one JSON object per line each way
+async fn serve(stream: UnixStream, shared: Shared) {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Some(response) = handle_line(&line, &shared) else {
+            continue;
+        };
+        let mut out = response.to_string();
+        out.push('\n');
+        if writer.write_all(out.as_bytes()).await.is_err() {
+            break;
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Request {
+    /// Absent for notifications, which get no response
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Deserialize)]
+struct JobParams {
+    job: u64,
+}
+
+fn handle_line(line: &str, shared: &Shared) -> Option<Value> {
+    let value: Value = match serde_json::from_str(line) {
+        Ok(value) => value,
+        Err(err) => return Some(error_response(Value::Null, PARSE_ERROR, &err.to_string())),
+    };
+    let request: Request = match serde_json::from_value(value) {
+        Ok(request) => request,
+        Err(err) => {
+            return Some(error_response(
+                Value::Null,
+                INVALID_REQUEST,
+                &err.to_string(),
+            ));
+        }
+    };
+    let id = request.id?;
+    let response = match call(&request.method, request.params, shared) {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => error_response(id, code, &message),
+    };
+    Some(response)
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+fn call(method: &str, params: Value, shared: &Shared) -> std::result::Result<Value, (i64, String)> {
+    let (jobs, wake) = &**shared;
+    let invalid = |err: serde_json::Error| (INVALID_PARAMS, err.to_string());
+    let failed = |err: anyhow::Error| (REQUEST_FAILED, format!("{err:#}"));
+    match method {
+        "submit_plan" => {
+            let plan: MigrationPlan = serde_json::from_value(params).map_err(invalid)?;
+            plan.validate().map_err(failed)?;
+            let mut jobs = jobs.borrow_mut();
+            if jobs.no_delete && plan.action.job_kind() == JobKind::Move {
+                return Err((
+                    REQUEST_FAILED,
+                    "moves delete their source, which no-delete mode refuses; submit a copy"
+                        .to_string(),
+                ));
+            }
+            let id = jobs.push(plan, None);
+            wake.notify_one();
+            Ok(json!({ "job": id }))
+        }
+        "job_status" => {
+            let params: JobParams = serde_json::from_value(params).map_err(invalid)?;
+            let mut jobs = jobs.borrow_mut();
+            let job = jobs.get(params.job).map_err(failed)?;
+            Ok(json!(job))
+        }
+        "list_jobs" => Ok(json!(jobs.borrow().jobs)),
+        "cancel_job" => {
+            let params: JobParams = serde_json::from_value(params).map_err(invalid)?;
+            let mut jobs = jobs.borrow_mut();
+            let job = jobs.get(params.job).map_err(failed)?;
+            let cancelled = match job.state {
+                JobState::Queued => {
+                    job.state = JobState::Cancelled;
+                    job.finished_at = Some(chrono::Utc::now().to_rfc3339());
+                    true
+                }
+                JobState::Selecting | JobState::Running => {
+                    job.stop.set(Some(ShutdownChoice::Checkpoint));
+                    true
+                }
+                _ => false,
+            };
+            Ok(json!({ "cancelled": cancelled }))
+        }
+        _ => Err((METHOD_NOT_FOUND, format!("unknown method {method}"))),
+    }
+}
+
+/// What the job runner works with, owned by it alone
+struct Engine {
+    s3: S3Service,
+    hook: Option<ObjectHook>,
+    history: JobHistory,
+    uploads: MultipartStore,
+    tracker: RestoreTracker,
+    /// Cap on cross-region copies across all jobs (`--max-bandwidth`)
+    max_bandwidth: Option<u64>,
+    /// Refuse plans over prefixes not approved (`--require-approval`)
+    require_approval: bool,
+    throttle: Throttle,
+    throughput: ThroughputLog,
+}
+
+/// Take queued jobs in submission order and run each to the end
+async fn run_jobs(shared: Shared, mut engine: Engine) {
+    let (jobs, wake) = &*shared;
+    loop {
+        let next = jobs
+            .borrow_mut()
+            .jobs
+            .iter_mut()
+            .find(|job| job.state == JobState::Queued)
+            .map(|job| {
+                job.state = JobState::Selecting;
+                (
+                    job.id,
+                    job.plan.clone(),
+                    job.scheduled.clone(),
+                    job.stop.clone(),
+                )
+            });
+        let Some((id, plan, scheduled, stop)) = next else {
+            wake.notified().await;
+            continue;
+        };
+        let result = match scheduled {
+            Some(policy) => {
+                engine
+                    .run_policy(jobs, id, &policy, &plan.bucket, &stop)
+                    .await
+            }
+            None => run_plan(jobs, id, &plan, &stop, &mut engine).await,
+        };
+        let mut jobs = jobs.borrow_mut();
+        let Ok(job) = jobs.get(id) else {
+            continue;
+        };
+        job.current_key = None;
+        job.finished_at = Some(chrono::Utc::now().to_rfc3339());
+        job.state = match result {
+            Err(err) => {
+                job.error = Some(errors::describe(&err));
+                JobState::Failed
+            }
+            Ok(()) if stop.get().is_some() => JobState::Cancelled,
+            Ok(()) => JobState::Finished,
+        };
+    }
+}
+
+/// Run a submitted plan, one object at a time
+async fn run_plan(
+    jobs: &RefCell<Jobs>,
+    id: u64,
+    plan: &MigrationPlan,
+    stop: &Cell<Option<ShutdownChoice>>,
+    engine: &mut Engine,
+) -> Result<()> {
+    let mut runner = Runner {
+        jobs,
+        id,
+        s3: &engine.s3,
+        hook: engine.hook.as_ref(),
+        history: &mut engine.history,
+        uploads: &mut engine.uploads,
+        tracker: &mut engine.tracker,
+        stop,
+        protected: None,
+        max_bandwidth: engine.max_bandwidth,
+        require_approval: engine.require_approval,
+        throttle: &mut engine.throttle,
+        throughput: &mut engine.throughput,
+        dest_region: None,
+    };
+    runner.run(plan).await
+}
+
+impl Engine {
+    /// Run the saved policy of a scheduled job and keep what it did for the
+    /// next digest
+    async fn run_policy(
+        &mut self,
+        jobs: &RefCell<Jobs>,
+        id: u64,
+        policy: &MigrationPolicy,
+        bucket: &str,
+        stop: &Cell<Option<ShutdownChoice>>,
+    ) -> Result<()> {
+        let update = |change: &mut dyn FnMut(&mut Job)| {
+            if let Ok(job) = jobs.borrow_mut().get(id) {
+                change(job);
+            }
+        };
+        let started_at = chrono::Utc::now();
+        let result = policy::run(
+            &self.s3,
+            policy,
+            bucket,
+            &mut self.history,
+            &mut self.uploads,
+            stop,
+            self.require_approval,
+            |progress| {
+                update(&mut |job| {
+                    job.state = JobState::Running;
+                    job.total = progress.total;
+                    job.done = progress.done;
+                    job.current_key = Some(progress.key.to_string());
+                    match progress.error {
+                        Some(_) => job.failed += 1,
+                        None => job.succeeded += 1,
+                    }
+                })
+            },
+        )
+        .await;
+        let (outcome, savings) = match &result {
+            Ok(summary) => {
+                update(&mut |job| {
+                    job.skipped = summary.already_in_class + summary.needs_restore;
+                    job.protected = summary.protected;
+                    job.journal_id = summary.job_id.clone();
+                });
+                let region = self.s3.get_bucket_region(bucket).await.ok().flatten();
+                let savings =
+                    DigestRun::savings_of(summary, region.as_deref(), &policy.target_class);
+                (Ok(summary.clone()), savings)
+            }
+            Err(err) => (Err(errors::describe(err)), None),
+        };
+        jobs.borrow_mut().digest.push(DigestRun {
+            policy: policy.name.clone(),
+            bucket: bucket.to_string(),
+            target_class: Some(policy.target_class.clone()),
+            started_at,
+            outcome,
+            savings,
+        });
+        result.map(|_| ())
+    }
+}
+
+/// How often the schedule looks for runs that came due
+const SCHEDULE_TICK: Duration = Duration::from_secs(30);
+
+/// The next time the local clock shows `at`, a `HH:MM` from the digest
+/// configuration
+fn next_run(at: &str) -> chrono::DateTime<chrono::Utc> {
+    schedule::parse_run_at(at).unwrap_or_else(|| chrono::Utc::now() + chrono::Duration::days(1))
+}
+
+/// Queue each scheduled policy run as it comes due, and send the digest of
+/// the runs that finished at `send_at` every day
+async fn run_schedule(shared: Shared, config: DigestConfig, s3: S3Service) {
+    let (jobs, wake) = &*shared;
+    let mut due: Vec<_> = config.runs.iter().map(|run| next_run(&run.at)).collect();
+    let mut send_at = next_run(&config.send_at);
+    loop {
+        tokio::time::sleep(SCHEDULE_TICK).await;
+        let now = chrono::Utc::now();
+        for (run, at) in config.runs.iter().zip(&mut due) {
+            if *at > now {
+                continue;
+            }
+            *at = next_run(&run.at);
+            // Loaded each time, so edits to a saved policy apply to its next run
+            let found = PolicyStore::new().map(|store| store.get(&run.policy).cloned());
+            let error = match found {
+                Ok(Some(policy)) => {
+                    jobs.borrow_mut()
+                        .push(policy.plan(&run.bucket), Some(policy));
+                    wake.notify_one();
+                    continue;
+                }
+                Ok(None) => format!("no saved policy named {}", run.policy),
+                Err(err) => format!("could not load the saved policies: {err:#}"),
+            };
+            jobs.borrow_mut().digest.push(DigestRun {
+                policy: run.policy.clone(),
+                bucket: run.bucket.clone(),
+                target_class: None,
+                started_at: now,
+                outcome: Err(error),
+                savings: None,
+            });
+        }
+        if send_at > now {
+            continue;
+        }
+        send_at = next_run(&config.send_at);
+        let digest = Digest::new(std::mem::take(&mut jobs.borrow_mut().digest));
+        match digest.deliver(&config, &s3).await {
+            Ok(report) if config.email.is_some() => eprintln!(
+                "Emailed the digest of {} policy runs; the report is at {report}",
+                digest.runs.len()
+            ),
+            Ok(report) => eprintln!(
+                "Wrote the digest of {} policy runs to {report}",
+                digest.runs.len()
+            ),
+            Err(err) => {
+                eprintln!("Could not send the digest, keeping its runs for the next: {err:#}");
+                jobs.borrow_mut().digest.splice(0..0, digest.runs);
+            }
+        }
+    }
+}
+
+/// One job's run, reporting into its entry in the job table
+struct Runner<'a> {
+    jobs: &'a RefCell<Jobs>,
+    id: u64,
+    s3: &'a S3Service,
+    hook: Option<&'a ObjectHook>,
+    history: &'a mut JobHistory,
+    uploads: &'a mut MultipartStore,
+    tracker: &'a mut RestoreTracker,
+    stop: &'a Cell<Option<ShutdownChoice>>,
+    /// Loaded when the job starts, so keys protected since are respected
+    protected: Option<ProtectedKeys>,
+    max_bandwidth: Option<u64>,
+    require_approval: bool,
+    throttle: &'a mut Throttle,
+    throughput: &'a mut ThroughputLog,
+    /// Region a copy job writes to, once looked up
+    dest_region: Option<String>,
+}
+
+/// What happened to one object
+enum Step {
+    /// Processed, with a note for the journal. `stop` says why the job
+    /// should stop after this object, when the hook asks for that.
+    Done {
+        note: Option<String>,
+        stop: Option<String>,
+    },
+    Skipped(String),
+    Failed(String),
+    /// A cancelled multipart copy was left resumable; the object is not done
+    Checkpointed(String),
+}
+
+impl Runner<'_> {
+    fn update(&self, change: impl FnOnce(&mut Job)) {
+        if let Ok(job) = self.jobs.borrow_mut().get(self.id) {
+            change(job);
+        }
+    }
+
+    async fn run(&mut self, plan: &MigrationPlan) -> Result<()> {
+        if let PlanAction::Transition { lock, .. } = &plan.action
+            && !lock.is_empty()
+            && !self.s3.object_lock_enabled(&plan.bucket).await?
+        {
+            bail!(
+                "{} does not have Object Lock enabled, which retention and legal holds need",
+                plan.bucket
+            );
+        }
+        let ticket =
+            tickets::resolve(TicketConfig::load()?.as_ref(), plan.ticket.as_deref()).await?;
+        let protected = self.protected.insert(ProtectedKeys::new()?);
+        let approvals = ApprovalStore::new()?;
+        let targets = plan
+            .targets(self.s3, protected, &approvals, self.require_approval)
+            .await?;
+        if let PlanAction::Copy {
+            dest_bucket,
+            rewrite,
+            ..
+        } = &plan.action
+            && !rewrite.is_empty()
+        {
+            // Nobody is there to decide which colliding keys to overwrite
+            let keys: Vec<String> = targets.objects.iter().map(|obj| obj.key.clone()).collect();
+            let review = CollisionReview::find(self.s3, dest_bucket, rewrite, &keys).await?;
+            if let Some(first) = review.collisions.first() {
+                bail!(
+                    "{} renamed keys collide in {dest_bucket}, such as {} → {}; nothing was copied",
+                    review.collisions.len(),
+                    first.key,
+                    first.dest_key
+                );
+            }
+        }
+        let total = targets.objects.len();
+        self.update(|job| {
+            job.state = JobState::Running;
+            job.total = total;
+            job.skipped = targets.skipped;
+            job.protected = targets.protected;
+        });
+        if self.stop.get().is_some() || total == 0 {
+            return Ok(());
+        }
+
+        let cap = self.copy_cap(plan).await;
+        let kind = plan.action.job_kind();
+        let job_id = self.history.begin(
+            kind,
+            &plan.bucket,
+            &plan.action.target_label(),
+            total,
+            None,
+            ticket,
+        );
+        self.update(|job| job.journal_id = Some(job_id.clone()));
+        let mut processed = 0;
+        for obj in &targets.objects {
+            if self.stop.get().is_some() {
+                break;
+            }
+            self.update(|job| job.current_key = Some(obj.key.clone()));
+            if let Some(cap) = cap {
+                let wait = self.throttle.reserve(obj.size.max(0) as u64, cap);
+                if !self.hold(wait).await {
+                    break;
+                }
+            }
+            let step = self
+                .step(plan, &job_id, &obj.key, obj.size, &obj.storage_class)
+                .await;
+            let stop = match step {
+                Step::Done { note, stop } => {
+                    self.history.record_with_note(&job_id, &obj.key, None, note);
+                    self.update(|job| job.succeeded += 1);
+                    stop
+                }
+                Step::Skipped(note) => {
+                    self.history
+                        .record_with_note(&job_id, &obj.key, None, Some(note));
+                    self.update(|job| job.skipped += 1);
+                    None
+                }
+                Step::Failed(err) => {
+                    self.history.record(&job_id, &obj.key, Some(err));
+                    self.update(|job| job.failed += 1);
+                    None
+                }
+                Step::Checkpointed(reason) => {
+                    self.update(|job| job.error = Some(reason));
+                    break;
+                }
+            };
+            processed += 1;
+            self.update(|job| job.done = processed);
+            if let Some(reason) = stop {
+                self.update(|job| job.error = Some(reason));
+                break;
+            }
+        }
+        if processed < total {
+            self.history.interrupt(&job_id);
+        } else {
+            self.history.finish(&job_id);
+        }
+        if let Err(err) = self.throughput.save() {
+            eprintln!(
+                "Could not save measured throughput: {}",
+                errors::describe(&err)
+            );
+        }
+        Ok(())
+    }
+
+    /// Bytes per second a copy job may use, or `None` for other jobs and
+    /// copies within a region. A bucket whose region cannot be looked up
+    /// counts as being in another region.
+    async fn copy_cap(&mut self, plan: &MigrationPlan) -> Option<u64> {
+        let PlanAction::Copy {
+            dest_bucket,
+            max_bandwidth,
+            ..
+        } = &plan.action
+        else {
+            return None;
+        };
+        let (source, dest) = tokio::join!(
+            self.s3.get_bucket_region(&plan.bucket),
+            self.s3.get_bucket_region(dest_bucket)
+        );
+        let (source, dest) = (source.ok().flatten(), dest.ok().flatten());
+        self.dest_region = dest.clone();
+        if source.is_some() && source == dest {
+            return None;
+        }
+        match (*max_bandwidth, self.max_bandwidth) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (cap, global) => cap.or(global),
+        }
+    }
+
+    /// Wait out a throttle. Returns false when the job is cancelled meanwhile.
+    async fn hold(&self, wait: Duration) -> bool {
+        let resume = Instant::now() + wait;
+        while self.stop.get().is_none() {
+            let left = resume.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                return true;
+            }
+            tokio::time::sleep(left.min(HOLD_TICK)).await;
+        }
+        false
+    }
+
+    async fn step(
+        &mut self,
+        plan: &MigrationPlan,
+        job_id: &str,
+        key: &str,
+        size: i64,
+        class: &StorageClassTier,
+    ) -> Step {
+        let bucket = plan.bucket.as_str();
+        let large = size > MULTIPART_COPY_THRESHOLD;
+        let sent = Instant::now();
+        let (result, hook_bucket, hook_key, action) = match &plan.action {
+            PlanAction::Transition { target, lock } => {
+                let result = transition_object(
+                    self.s3,
+                    self.uploads,
+                    bucket,
+                    key,
+                    large,
+                    target.clone(),
+                    self.stop,
+                )
+                .await;
+                let result = match result {
+                    Ok(CopyOutcome::Completed) if !lock.is_empty() => {
+                        match apply_object_lock(self.s3, bucket, key, lock).await {
+                            Ok(note) => Ok((CopyOutcome::Completed, Some(note))),
+                            Err(err) => return Step::Failed(err),
+                        }
+                    }
+                    other => other.map(|outcome| (outcome, None)),
+                };
+                (result, bucket, key.to_string(), "transition")
+            }
+            PlanAction::Restore { days, tier } => {
+                // A restore the TUI or an earlier job queued is requested once
+                self.tracker.reload();
+                if self.tracker.is_queued(bucket, key) {
+                    return Step::Skipped("already queued for restore".to_string());
+                }
+                let result = self.s3.request_restore(bucket, key, *days, *tier).await;
+                if result.is_ok() {
+                    self.tracker.add_request(
+                        bucket.to_string(),
+                        key.to_string(),
+                        *days,
+                        class.clone(),
+                        *tier,
+                        false,
+                    );
+                }
+                let result = result.map(|_| (CopyOutcome::Completed, None));
+                (result, bucket, key.to_string(), "restore")
+            }
+            PlanAction::Copy {
+                dest_bucket,
+                mode,
+                delete_source,
+                rewrite,
+                storage_class,
+                ..
+            } => {
+                let dest_key = rewrite.apply(key);
+                if *mode != CopyMode::Overwrite {
+                    let (source, dest) = tokio::join!(
+                        self.s3.fingerprint(bucket, key),
+                        self.s3.fingerprint(dest_bucket, &dest_key)
+                    );
+                    match (source, dest) {
+                        (Ok(Some(source)), Ok(Some(dest))) if mode.skips(&source, &dest) => {
+                            return Step::Skipped(format!("skipped: {}", mode.skip_reason()));
+                        }
+                        (Err(err), _) | (_, Err(err)) => {
+                            return Step::Failed(errors::describe(&err));
+                        }
+                        _ => {}
+                    }
+                }
+                let result = if large {
+                    multipart::copy_large_object(
+                        self.s3,
+                        self.uploads,
+                        bucket,
+                        dest_bucket,
+                        key,
+                        &dest_key,
+                        storage_class.clone(),
+                        self.stop,
+                    )
+                    .await
+                } else {
+                    self.s3
+                        .copy_object(bucket, key, dest_bucket, &dest_key, storage_class.clone())
+                        .await
+                        .map(|_| CopyOutcome::Completed)
+                };
+                if let (Ok(CopyOutcome::Completed), Some(region)) = (&result, &self.dest_region) {
+                    let copied = size.max(0) as u64;
+                    self.throughput.record(region, copied, sent.elapsed());
+                }
+                let result = match result {
+                    Ok(CopyOutcome::Completed) if *delete_source => {
+                        if self
+                            .protected
+                            .as_ref()
+                            .is_some_and(|protected| protected.is_protected(bucket, key))
+                        {
+                            Ok((
+                                CopyOutcome::Completed,
+                                Some("copied; source is protected and kept".to_string()),
+                            ))
+                        } else {
+                            match move_source(self.s3, bucket, dest_bucket, key, &dest_key, large)
+                                .await
+                            {
+                                Ok(note) => Ok((CopyOutcome::Completed, Some(note))),
+                                Err(err) => return Step::Failed(err),
+                            }
+                        }
+                    }
+                    other => other.map(|outcome| (outcome, None)),
+                };
+                let action = if *delete_source { "move" } else { "copy" };
+                (result, dest_bucket.as_str(), dest_key, action)
+            }
+        };
+        self.history.time_request(job_id, sent.elapsed());
+        match result {
+            Ok((CopyOutcome::Completed, note)) => Step::Done {
+                note,
+                stop: self.run_hook(hook_bucket, &hook_key, action).await.err(),
+            },
+            Ok((CopyOutcome::Checkpointed, _)) => Step::Checkpointed(format!(
+                "checkpointed multipart copy of {key}; resume it with U"
+            )),
+            Err(err) => Step::Failed(errors::describe(&err)),
+        }
+    }
+
+    /// Run the `--object-hook` command. Fails only when the batch should stop.
+    async fn run_hook(
+        &self,
+        bucket: &str,
+        key: &str,
+        action: &str,
+    ) -> std::result::Result<(), String> {
+        let Some(hook) = self.hook else {
+            return Ok(());
+        };
+        let Err(err) = hook.run(bucket, key, action).await else {
+            return Ok(());
+        };
+        match hook.on_failure {
+            HookFailure::Ignore => Ok(()),
+            HookFailure::Warn => {
+                eprintln!("Hook failed for {key}: {err:#}");
+                Ok(())
+            }
+            HookFailure::Stop => Err(format!("hook failed for {key}: {err:#}")),
+        }
+    }
+}
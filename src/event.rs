@@ -0,0 +1,37 @@
+use crate::enrich::Enriched;
+use crate::models::ObjectInfo;
+use crate::reconcile::ReconcileEvent;
+use crate::update::Release;
+
+/// Something that happened to the application in the background. The event
+/// loop turns finished background tasks into events and `App::update`
+/// applies them to the state; key presses go to the TUI's key handlers.
+pub enum AppEvent {
+    /// A page of the main pane's listing arrived
+    ObjectsLoaded(Vec<ObjectInfo>),
+    /// Subfolders of the main pane's folder listing arrived
+    FoldersLoaded(Vec<String>),
+    /// The main pane's listing reached the end of the bucket
+    ListingFinished,
+    /// A page of the split pane's listing arrived
+    SplitObjectsLoaded(Vec<ObjectInfo>),
+    SplitListingFinished,
+    /// HeadObject filled in a listed object's restore state and encryption
+    Enriched(Enriched),
+    /// The running batch moved on to its `done`-th object
+    JobProgress {
+        done: usize,
+        key: String,
+    },
+    /// Whether the connection probe got an answer from AWS
+    ConnectionProbed(bool),
+    /// Progress or a finding of the split view's reconciliation
+    Reconciled(ReconcileEvent),
+    /// The startup check found a newer release
+    NewRelease(Release),
+    /// A request failed while doing what `context` describes
+    Error {
+        context: String,
+        err: anyhow::Error,
+    },
+}
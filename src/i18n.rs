@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+/// UI languages with a message catalog in `locales/`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Locale {
+    En,
+    Ja,
+}
+
+impl Locale {
+    /// Parse a tag such as `ja`, `ja_JP.UTF-8` or `en-US`
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let lang = tag
+            .split(['_', '-', '.'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match lang.as_str() {
+            "en" => Some(Locale::En),
+            "ja" => Some(Locale::Ja),
+            _ => None,
+        }
+    }
+
+    /// `--lang <tag>` wins, then `LC_ALL`, `LC_MESSAGES` and `LANG`; English otherwise
+    pub fn detect(args: &[String]) -> Self {
+        let from_args = args
+            .windows(2)
+            .find(|pair| pair[0] == "--lang")
+            .and_then(|pair| Locale::from_tag(&pair[1]));
+        from_args
+            .or_else(|| {
+                ["LC_ALL", "LC_MESSAGES", "LANG"]
+                    .iter()
+                    .filter_map(|var| std::env::var(var).ok())
+                    .find(|value| !value.is_empty())
+                    .and_then(|value| Locale::from_tag(&value))
+            })
+            .unwrap_or(Locale::En)
+    }
+
+    fn source(&self) -> &'static str {
+        match self {
+            Locale::En => include_str!("../locales/en.json"),
+            Locale::Ja => include_str!("../locales/ja.json"),
+        }
+    }
+}
+
+struct Catalog {
+    messages: HashMap<String, String>,
+    fallback: HashMap<String, String>,
+}
+
+static CATALOG: OnceLock<Catalog> = OnceLock::new();
+
+fn parse(locale: Locale) -> HashMap<String, String> {
+    serde_json::from_str(locale.source()).unwrap_or_default()
+}
+
+/// Select the UI language. Only the first call has an effect.
+pub fn init(locale: Locale) {
+    CATALOG.get_or_init(|| Catalog {
+        messages: parse(locale),
+        fallback: parse(Locale::En),
+    });
+}
+
+/// Look up a UI string, falling back to English and then to the key itself
+pub fn t(key: &'static str) -> &'static str {
+    let catalog = CATALOG.get_or_init(|| Catalog {
+        messages: parse(Locale::En),
+        fallback: HashMap::new(),
+    });
+    catalog
+        .messages
+        .get(key)
+        .or_else(|| catalog.fallback.get(key))
+        .map(String::as_str)
+        .unwrap_or(key)
+}
+
+/// Look up a UI string and substitute `{name}` placeholders
+pub fn tf(key: &'static str, args: &[(&str, &dyn Display)]) -> String {
+    let mut message = t(key).to_string();
+    for (name, value) in args {
+        message = message.replace(&format!("{{{name}}}"), &value.to_string());
+    }
+    message
+}
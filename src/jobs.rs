@@ -0,0 +1,386 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, anyhow};
+use futures::stream::{self, StreamExt};
+use tokio::sync::{Semaphore, mpsc};
+use tokio::task::JoinHandle;
+
+use crate::app::RestorePacing;
+use crate::aws::S3Service;
+use crate::batch::apply_object_lock;
+use crate::hooks::{HookFailure, ObjectHook};
+use crate::models::{JobKind, ObjectLockPlan, RestoreTier, StorageClassTier, restore_label};
+use crate::plan::transition_label;
+use crate::shards::ShardWork;
+
+/// What a background job does to each of its objects
+#[derive(Clone, Debug)]
+pub enum JobWork {
+    Transition {
+        target_class: StorageClassTier,
+        lock: ObjectLockPlan,
+    },
+    Restore {
+        days: i32,
+        tier: RestoreTier,
+        /// Preset the restore was started with, kept for sharding
+        preset: Option<String>,
+        pacing: RestorePacing,
+        /// Tracked without announcing readiness
+        quiet: bool,
+    },
+}
+
+impl JobWork {
+    /// The S3 API each object goes through, for telemetry
+    pub fn api(&self) -> &'static str {
+        match self {
+            JobWork::Transition { .. } => "CopyObject",
+            JobWork::Restore { .. } => "RestoreObject",
+        }
+    }
+
+    /// The work of each shard of a sharded batch
+    pub fn for_shard(work: &ShardWork, pacing: RestorePacing, quiet: bool) -> Self {
+        match work {
+            ShardWork::Transition { target_class, lock } => JobWork::Transition {
+                target_class: target_class.clone(),
+                lock: lock.clone(),
+            },
+            ShardWork::Restore { days, tier, preset } => JobWork::Restore {
+                days: *days,
+                tier: *tier,
+                preset: preset.clone(),
+                pacing,
+                quiet,
+            },
+        }
+    }
+
+    /// Job kind and target the journal records it under
+    pub fn journal_target(&self) -> (JobKind, String) {
+        match self {
+            JobWork::Transition { target_class, lock } => {
+                (JobKind::Transition, transition_label(target_class, lock))
+            }
+            JobWork::Restore { days, tier, .. } => (JobKind::Restore, restore_label(*days, *tier)),
+        }
+    }
+
+    /// The action passed to the object hook
+    fn hook_action(&self) -> &'static str {
+        match self {
+            JobWork::Transition { .. } => "transition",
+            JobWork::Restore { .. } => "restore",
+        }
+    }
+}
+
+/// Progress of a background job, sent to the event loop. `job` is the id
+/// the job was journaled under.
+pub enum JobEvent {
+    /// The jobs queued before it finished and it began sending requests
+    Started { job: String },
+    /// One object was handled: `Ok` holds the journal note, if any
+    Object {
+        job: String,
+        key: String,
+        size: i64,
+        result: Result<Option<String>>,
+        elapsed: Duration,
+    },
+    /// The object hook failed after `key` was handled
+    HookFailed {
+        job: String,
+        key: String,
+        err: anyhow::Error,
+    },
+    /// No more objects will be reported
+    Finished { job: String },
+}
+
+/// A job submitted to the queue. Dropping it stops the job at once.
+pub struct BackgroundJob {
+    /// Journal id, shared with the job history
+    pub id: String,
+    pub label: String,
+    pub bucket: String,
+    pub work: JobWork,
+    pub total: usize,
+    pub done: usize,
+    pub failed: usize,
+    /// Whether it has its turn yet; later jobs wait for the earlier ones
+    pub started: bool,
+    cancel: Arc<AtomicBool>,
+    task: JoinHandle<()>,
+}
+
+impl BackgroundJob {
+    /// Whether it was asked to stop and is letting requests in flight finish
+    pub fn cancelling(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for BackgroundJob {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Transitions and restores running in tokio tasks while the TUI stays
+/// responsive. Jobs run one at a time in the order they were submitted and
+/// report each object over a channel; the event loop journals the results.
+pub struct JobQueue {
+    jobs: Vec<BackgroundJob>,
+    events_tx: mpsc::UnboundedSender<JobEvent>,
+    events: mpsc::UnboundedReceiver<JobEvent>,
+    /// Event received by `ready` and not yet taken by `try_next`
+    pending: Option<JobEvent>,
+    /// The job holding the permit is the one running
+    turn: Arc<Semaphore>,
+}
+
+impl Default for JobQueue {
+    fn default() -> Self {
+        let (events_tx, events) = mpsc::unbounded_channel();
+        Self {
+            jobs: Vec::new(),
+            events_tx,
+            events,
+            pending: None,
+            turn: Arc::new(Semaphore::new(1)),
+        }
+    }
+}
+
+impl JobQueue {
+    /// Queue `objects` (key and size) of `bucket` for `work`. `id` is the
+    /// journal id the job was begun under.
+    #[allow(clippy::too_many_arguments)]
+    pub fn submit(
+        &mut self,
+        s3: &S3Service,
+        id: String,
+        label: String,
+        bucket: String,
+        work: JobWork,
+        objects: Vec<(String, i64)>,
+        hook: Option<ObjectHook>,
+    ) {
+        let cancel = Arc::new(AtomicBool::new(false));
+        let run = JobRun {
+            s3: s3.clone(),
+            id: id.clone(),
+            bucket: bucket.clone(),
+            work: work.clone(),
+            hook,
+            cancel: cancel.clone(),
+            events: self.events_tx.clone(),
+        };
+        let turn = self.turn.clone();
+        let total = objects.len();
+        let task = tokio::spawn(async move {
+            let Ok(_turn) = turn.acquire_owned().await else {
+                return;
+            };
+            let _ = run.events.send(JobEvent::Started {
+                job: run.id.clone(),
+            });
+            run.process(objects).await;
+            let _ = run.events.send(JobEvent::Finished { job: run.id });
+        });
+        self.jobs.push(BackgroundJob {
+            id,
+            label,
+            bucket,
+            work,
+            total,
+            done: 0,
+            failed: 0,
+            started: false,
+            cancel,
+            task,
+        });
+    }
+
+    pub fn jobs(&self) -> &[BackgroundJob] {
+        &self.jobs
+    }
+
+    pub fn get(&self, id: &str) -> Option<&BackgroundJob> {
+        self.jobs.iter().find(|job| job.id == id)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+
+    /// Ask a job to stop starting requests. Returns `false` if it is not
+    /// queued or running.
+    pub fn cancel(&self, id: &str) -> bool {
+        match self.get(id) {
+            Some(job) => {
+                job.cancel.store(true, Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Take a finished job out of the queue
+    pub fn remove(&mut self, id: &str) -> Option<BackgroundJob> {
+        let idx = self.jobs.iter().position(|job| job.id == id)?;
+        Some(self.jobs.remove(idx))
+    }
+
+    /// Wait until `try_next` has an event to return
+    pub async fn ready(&mut self) {
+        if self.pending.is_some() {
+            return;
+        }
+        match self.events.recv().await {
+            Some(event) => self.pending = Some(event),
+            None => std::future::pending().await,
+        }
+    }
+
+    /// The next event, with the job's counters already updated
+    pub fn try_next(&mut self) -> Option<JobEvent> {
+        let event = self
+            .pending
+            .take()
+            .or_else(|| self.events.try_recv().ok())?;
+        match &event {
+            JobEvent::Started { job } => {
+                if let Some(job) = self.jobs.iter_mut().find(|j| j.id == *job) {
+                    job.started = true;
+                }
+            }
+            JobEvent::Object { job, result, .. } => {
+                if let Some(job) = self.jobs.iter_mut().find(|j| j.id == *job) {
+                    job.done += 1;
+                    if result.is_err() {
+                        job.failed += 1;
+                    }
+                }
+            }
+            JobEvent::HookFailed { .. } | JobEvent::Finished { .. } => {}
+        }
+        Some(event)
+    }
+}
+
+/// What the task of one job works with
+struct JobRun {
+    s3: S3Service,
+    id: String,
+    bucket: String,
+    work: JobWork,
+    hook: Option<ObjectHook>,
+    cancel: Arc<AtomicBool>,
+    events: mpsc::UnboundedSender<JobEvent>,
+}
+
+impl JobRun {
+    fn cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+
+    async fn process(&self, objects: Vec<(String, i64)>) {
+        match &self.work {
+            JobWork::Transition { target_class, lock } => {
+                for (key, size) in objects {
+                    if self.cancelled() {
+                        break;
+                    }
+                    let sent = Instant::now();
+                    let result = self.transition(&key, target_class, lock).await;
+                    if !self.report(key, size, result, sent.elapsed()).await {
+                        break;
+                    }
+                }
+            }
+            JobWork::Restore {
+                days, tier, pacing, ..
+            } => {
+                // Like a foreground restore: paced, and `concurrency` in flight
+                let started = tokio::time::Instant::now();
+                let mut requests = stream::iter(objects.into_iter().enumerate())
+                    .take_while(|_| std::future::ready(!self.cancelled()))
+                    .map(|(index, (key, size))| async move {
+                        tokio::time::sleep_until(started + pacing.start_offset(index)).await;
+                        let sent = Instant::now();
+                        let result = self
+                            .s3
+                            .request_restore(&self.bucket, &key, *days, *tier)
+                            .await;
+                        (key, size, result.map(|_| None), sent.elapsed())
+                    })
+                    .buffer_unordered(pacing.concurrency);
+                while let Some((key, size, result, elapsed)) = requests.next().await {
+                    if !self.report(key, size, result, elapsed).await {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    async fn transition(
+        &self,
+        key: &str,
+        target_class: &StorageClassTier,
+        lock: &ObjectLockPlan,
+    ) -> Result<Option<String>> {
+        self.s3
+            .transition_storage_class(&self.bucket, key, target_class.clone())
+            .await?;
+        if lock.is_empty() {
+            return Ok(None);
+        }
+        apply_object_lock(&self.s3, &self.bucket, key, lock)
+            .await
+            .map(Some)
+            .map_err(|reason| anyhow!(reason))
+    }
+
+    /// Send the result of one object and run the hook after a success.
+    /// Returns `false` when the job should stop.
+    async fn report(
+        &self,
+        key: String,
+        size: i64,
+        result: Result<Option<String>>,
+        elapsed: Duration,
+    ) -> bool {
+        let succeeded = result.is_ok();
+        let sent = self.events.send(JobEvent::Object {
+            job: self.id.clone(),
+            key: key.clone(),
+            size,
+            result,
+            elapsed,
+        });
+        if sent.is_err() {
+            return false;
+        }
+        let Some(hook) = self.hook.as_ref().filter(|_| succeeded) else {
+            return true;
+        };
+        let Err(err) = hook.run(&self.bucket, &key, self.work.hook_action()).await else {
+            return true;
+        };
+        if hook.on_failure == HookFailure::Ignore {
+            return true;
+        }
+        let _ = self.events.send(JobEvent::HookFailed {
+            job: self.id.clone(),
+            key,
+            err,
+        });
+        hook.on_failure != HookFailure::Stop
+    }
+}
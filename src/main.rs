@@ -1,29 +1,333 @@
 mod app;
-mod aws;
-mod mask;
-mod models;
-mod tracker;
+mod crash;
+#[cfg(unix)]
+mod daemon;
+mod event;
+mod i18n;
+mod jobs;
 mod tui;
+mod worker;
+
+use s3_migration_core::{
+    access, approvals, athena, aws, bandwidth, batch, bucket_config, bundle, coldness, collisions,
+    connectivity, coordination, decommission, digest, dryrun, enrich, errors, faults, hooks,
+    inventory, journal, lifecycle, loader, mask, models, moves, multipart, notify, owners,
+    partition, plan, policy, presets, preview, pricing, protect, recency, reconcile, report,
+    schedule, script, shards, sizes, tags, telemetry, tickets, tracker, transform, update,
+    versions, waves,
+};
 
 use anyhow::Result;
 
 use app::App;
 use aws::S3Service;
+use journal::JobHistory;
+use multipart::MultipartStore;
 use tracker::RestoreTracker;
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    i18n::init(i18n::Locale::detect(&args));
+
+    if args.iter().any(|arg| arg == "--version") {
+        println!("bucket-brigade {}", app::VERSION);
+        return Ok(());
+    }
+    if args.iter().any(|arg| arg == "--daemon") {
+        return run_daemon(&args).await;
+    }
+    if args.iter().any(|arg| arg == "--worker") {
+        return run_worker(&args).await;
+    }
+    if args.iter().any(|arg| arg == "--run-policy") {
+        return run_policy(&args).await;
+    }
+
     let mut app = App::new();
-    let s3 = S3Service::new().await?;
+    app.ascii = args.iter().any(|arg| arg == "--ascii");
+    app.low_redraw = args.iter().any(|arg| arg == "--low-redraw");
+    app.restore_pacing = app::RestorePacing::from_args(&args);
+    app.restore_presets = presets::RestorePresets::new()?;
+    app.restore_presets_only = args.iter().any(|arg| arg == "--restore-presets-only");
+    if app.restore_presets.from_file {
+        app.push_status(&format!(
+            "Loaded {} restore presets from restore_presets.json",
+            app.restore_presets.presets().len()
+        ));
+    }
+    app.athena = athena::AthenaConfig::from_args(&args);
+    app.recency = recency::RecencyConfig::from_args(&args);
+    app.execution_window = schedule::ExecutionWindow::from_args(&args);
+    app.object_hook = hooks::ObjectHook::from_args(&args);
+    app.update_check = update::UpdateCheck::from_args(&args).map(|check| {
+        tokio::spawn(async move { check.newer_than(app::VERSION).await.ok().flatten() })
+    });
+    app.protected = protect::ProtectedKeys::new()?;
+    app.shards = shards::ShardStore::new()?;
+    app.moves = moves::MoveStore::new()?;
+    if !app.moves.moves().is_empty() {
+        app.push_status(&format!(
+            "{} moves were interrupted – h shows them, m resumes one",
+            app.moves.moves().len()
+        ));
+    }
+    app.shard_keys = shards::shard_size(&args);
+    app.shared_state = coordination::SharedState::from_args(&args);
+    if let Some(state) = &app.shared_state {
+        app.push_status(&format!(
+            "Sharded batches are shared under {} for --worker processes to run",
+            state.url()
+        ));
+    }
+    if !app.shards.plans().is_empty() {
+        app.push_status(&format!(
+            "{} sharded batches have unfinished shards – h shows them, R resumes one",
+            app.shards.plans().len()
+        ));
+    }
+    app.schedule = schedule::Schedule::new()?;
+    app.policies = policy::PolicyStore::new()?;
+    app.bundles = bundle::BundleCatalog::new()?;
+    app.owners = owners::OwnerDirectory::new()?;
+    app.approvals = approvals::ApprovalStore::new()?;
+    if let Some(path) = approvals::import_path(&args) {
+        match app.approvals.import(&path) {
+            Ok(count) => app.push_status(&format!(
+                "Imported the sign-off of {count} prefixes from {}",
+                path.display()
+            )),
+            Err(err) => app.push_error("Approval import failed", &err),
+        }
+    }
+    app.require_approval = args.iter().any(|arg| arg == "--require-approval");
+    app.tickets = tickets::TicketConfig::load()?;
+    if app.tickets.as_ref().is_some_and(|tickets| tickets.required) {
+        app.push_status("Every batch needs a change ticket (tickets.json) – k in a confirmation");
+    }
+    app.bucket_sizes = sizes::BucketSizes::new()?;
+    app.max_bandwidth = bandwidth::max_bandwidth(&args);
+    app.throughput = bandwidth::ThroughputLog::new()?;
+    if let Some(cap) = app.max_bandwidth {
+        app.push_status(&format!(
+            "Cross-region copies are capped at {} in total",
+            bandwidth::format_rate(cap)
+        ));
+    }
+    let mut sdk_options = aws::SdkOptions::from_args(&args);
+    sdk_options.faults = faults::FaultInjection::from_args(&args)?;
+    let s3 = S3Service::new(&sdk_options).await?;
+    app.no_delete = s3.deletes_disabled();
+    app.fault_injection = s3.fault_injection().cloned();
+    if app.no_delete {
+        app.push_status("No-delete mode: moves, decommissioning and lifecycle deletes are refused");
+    }
+    if let Some(faults) = &sdk_options.faults {
+        app.push_status(&format!(
+            "Fault injection: {} fail on purpose. Do not use against production data",
+            faults.summary()
+        ));
+    }
+    if sdk_options.fips {
+        app.push_status("Using FIPS endpoints for all AWS calls");
+    }
+    if let Some(path) = &sdk_options.ca_bundle {
+        app.push_status(&format!("Trusting CA bundle {}", path.display()));
+    }
+    if let Some(hook) = &app.object_hook {
+        app.push_status(&format!(
+            "Running `{}` after each processed object",
+            hook.template
+        ));
+    }
+    app.transform = transform::Transform::from_args(&args);
+    if let Some(transform) = &app.transform {
+        app.push_status(&format!(
+            "Copies pipe each object through `{}`; moves are refused",
+            transform.command
+        ));
+    }
     let tracker = RestoreTracker::new()?;
+    let history = JobHistory::new()?;
+    let uploads = MultipartStore::new()?;
 
     // Set the initial region to the user's default AWS region
     if let Some(region) = s3.region() {
         app.set_region(Some(region.to_string()));
     }
 
-    if let Err(err) = tui::run(&mut app, &s3, tracker).await {
+    if let Err(err) = tui::run(&mut app, &s3, tracker, history, uploads).await {
         eprintln!("Application error: {err:#}");
     }
     Ok(())
 }
+
+/// `--worker`: no TUI; run shards of the batches shared under `--state-bucket`
+async fn run_worker(args: &[String]) -> Result<()> {
+    let Some(state) = coordination::SharedState::from_args(args) else {
+        anyhow::bail!("--worker needs --state-bucket s3://bucket/prefix to claim shards from");
+    };
+    let mut sdk_options = aws::SdkOptions::from_args(args);
+    sdk_options.faults = faults::FaultInjection::from_args(args)?;
+    if let Some(faults) = &sdk_options.faults {
+        eprintln!("Fault injection: {} fail on purpose", faults.summary());
+    }
+    let s3 = S3Service::new(&sdk_options).await?;
+    let hook = hooks::ObjectHook::from_args(args);
+    worker::run(s3, state, hook, app::RestorePacing::from_args(args)).await
+}
+
+/// `--run-policy <name> --bucket <bucket> [--ticket <id>]`: no TUI; run a
+/// saved policy once against a bucket and print what it did
+async fn run_policy(args: &[String]) -> Result<()> {
+    let value = |flag: &str| {
+        args.windows(2)
+            .find(|pair| pair[0] == flag)
+            .map(|pair| pair[1].clone())
+    };
+    let Some(name) = value("--run-policy") else {
+        anyhow::bail!("--run-policy needs the name of a saved policy");
+    };
+    let Some(bucket) = value("--bucket") else {
+        anyhow::bail!("--run-policy needs --bucket to run the policy against");
+    };
+    let policies = policy::PolicyStore::new()?;
+    let Some(policy) = policies.get(&name) else {
+        let names: Vec<&str> = policies
+            .policies()
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        anyhow::bail!(
+            "no saved policy named {name} (saved: {})",
+            if names.is_empty() {
+                "none".to_string()
+            } else {
+                names.join(", ")
+            }
+        );
+    };
+    // A ticket on the command line stands in for the policy's own
+    let mut policy = policy.clone();
+    if let Some(ticket) = value("--ticket") {
+        policy.ticket = Some(ticket);
+    }
+    let mut sdk_options = aws::SdkOptions::from_args(args);
+    sdk_options.faults = faults::FaultInjection::from_args(args)?;
+    let s3 = S3Service::new(&sdk_options).await?;
+    let mut history = JobHistory::new()?;
+    let mut uploads = MultipartStore::new()?;
+
+    eprintln!(
+        "Running policy {name} on {bucket}: `{}` to {}",
+        policy.mask.pattern,
+        policy.target_class.label()
+    );
+    let stop = std::cell::Cell::new(None);
+    let run = policy::run(
+        &s3,
+        &policy,
+        &bucket,
+        &mut history,
+        &mut uploads,
+        &stop,
+        args.iter().any(|arg| arg == "--require-approval"),
+        |progress| {
+            if let Some(err) = progress.error {
+                eprintln!("✗ {}: {err}", progress.key);
+            }
+            if progress.done % 100 == 0 || progress.done == progress.total {
+                eprintln!("{}/{} objects", progress.done, progress.total);
+            }
+        },
+    );
+    tokio::pin!(run);
+    let summary = loop {
+        tokio::select! {
+            summary = &mut run => break summary?,
+            _ = tokio::signal::ctrl_c(), if stop.get().is_none() => {
+                eprintln!("Stopping after the current object; large copies stay resumable");
+                stop.set(Some(batch::ShutdownChoice::Checkpoint));
+            }
+        }
+    };
+
+    println!(
+        "Policy {name} on {bucket}: {} objects matched",
+        summary.matched
+    );
+    println!(
+        "  transitioned to {}: {} ({})",
+        policy.target_class.label(),
+        summary.transitioned,
+        waves::format_bytes(summary.bytes)
+    );
+    println!(
+        "  already {}: {}",
+        policy.target_class.label(),
+        summary.already_in_class
+    );
+    println!("  protected: {}", summary.protected);
+    println!(
+        "  archived, need a restore first: {} ({} restores requested)",
+        summary.needs_restore, summary.restores_requested
+    );
+    println!("  failed: {}", summary.failed.len());
+    for (key, reason) in summary.failed.iter().take(20) {
+        println!("    {key}: {reason}");
+    }
+    if summary.failed.len() > 20 {
+        println!("    … and {} more", summary.failed.len() - 20);
+    }
+    if summary.interrupted {
+        println!("  stopped before the last object");
+    }
+    if let Some(job_id) = &summary.job_id {
+        println!("  journaled as job {job_id}; job history (h) has every key");
+    }
+    Ok(())
+}
+
+/// `--daemon`: no TUI, only the JSON-RPC control socket
+#[cfg(unix)]
+async fn run_daemon(args: &[String]) -> Result<()> {
+    let mut sdk_options = aws::SdkOptions::from_args(args);
+    sdk_options.faults = faults::FaultInjection::from_args(args)?;
+    if let Some(faults) = &sdk_options.faults {
+        eprintln!("Fault injection: {} fail on purpose", faults.summary());
+    }
+    let s3 = S3Service::new(&sdk_options).await?;
+    let socket = daemon::socket_path(args);
+    let digest = digest::DigestConfig::load()?.map(|mut config| {
+        config.sdk = sdk_options.clone();
+        config
+    });
+    if let Some(config) = &digest {
+        eprintln!(
+            "{} policies run on schedule; their digest goes out daily at {}",
+            config.runs.len(),
+            config.send_at
+        );
+    }
+    let max_bandwidth = bandwidth::max_bandwidth(args);
+    if let Some(cap) = max_bandwidth {
+        eprintln!(
+            "Cross-region copies are capped at {} in total",
+            bandwidth::format_rate(cap)
+        );
+    }
+    daemon::run(
+        s3,
+        &socket,
+        hooks::ObjectHook::from_args(args),
+        max_bandwidth,
+        args.iter().any(|arg| arg == "--require-approval"),
+        digest,
+    )
+    .await
+}
+
+#[cfg(not(unix))]
+async fn run_daemon(_args: &[String]) -> Result<()> {
+    anyhow::bail!("--daemon needs Unix domain sockets, which this platform does not have")
+}
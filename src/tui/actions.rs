@@ -0,0 +1,685 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::app::{ActivePane, AppMode};
+use crate::i18n::t;
+
+use self::ActionId::*;
+use self::Section::*;
+
+/// Everything the browsing screen can do. Handlers are in `tui::run_action`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ActionId {
+    NextPane,
+    PreviousPane,
+    MoveUp,
+    MoveDown,
+    PageUp,
+    PageDown,
+    First,
+    Last,
+    PreviousRegion,
+    NextRegion,
+    ScrollKeysLeft,
+    ScrollKeysRight,
+    LoadBucket,
+    GoToKey,
+    ListFromKey,
+    FullKey,
+    FolderView,
+    Versions,
+    OpenFolder,
+    ParentFolder,
+    EditMask,
+    ScriptMask,
+    ToggleRow,
+    PickAll,
+    RestoreFilter,
+    RestoreSort,
+    Protect,
+    ClearSelection,
+    Transition,
+    Restore,
+    Reencrypt,
+    DeleteVersions,
+    Bundle,
+    OpenBundle,
+    Inspect,
+    SplitView,
+    CopyRight,
+    CopyLeft,
+    CopyTo,
+    CopyConfig,
+    Reconcile,
+    Log,
+    Tracker,
+    History,
+    Uploads,
+    Policies,
+    Refresh,
+    LoadAll,
+    Owners,
+    QueryInventory,
+    QueryAthena,
+    ExportLifecycle,
+    ExportReport,
+    BucketTags,
+    CreateBucket,
+    Decommission,
+    AccessAudit,
+    LastReads,
+    Palette,
+    Help,
+    Quit,
+}
+
+/// Help screen section an action is listed under
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Section {
+    Navigation,
+    Masks,
+    Storage,
+    Other,
+}
+
+impl Section {
+    pub const ALL: [Section; 4] = [
+        Section::Navigation,
+        Section::Masks,
+        Section::Storage,
+        Section::Other,
+    ];
+
+    /// Locale key of the section header
+    pub fn header(&self) -> &'static str {
+        match self {
+            Section::Navigation => "help.navigation",
+            Section::Masks => "help.masks",
+            Section::Storage => "help.storage",
+            Section::Other => "help.other",
+        }
+    }
+}
+
+/// A default key for an action. Modifiers other than Shift are ignored, and
+/// Shift only when the binding does not ask for it.
+#[derive(Clone, Copy, Debug)]
+pub struct Binding {
+    pub code: KeyCode,
+    pub shift: bool,
+}
+
+impl Binding {
+    const fn key(code: KeyCode) -> Self {
+        Self { code, shift: false }
+    }
+
+    const fn char(ch: char) -> Self {
+        Self::key(KeyCode::Char(ch))
+    }
+
+    const fn shift(code: KeyCode) -> Self {
+        Self { code, shift: true }
+    }
+
+    fn matches(&self, key: &KeyEvent) -> bool {
+        self.code == key.code && (!self.shift || key.modifiers.contains(KeyModifiers::SHIFT))
+    }
+
+    /// How the key is written in the help screen and command bar
+    pub fn label(&self) -> String {
+        let key = match self.code {
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(ch) => ch.to_string(),
+            KeyCode::Tab => "Tab".to_string(),
+            KeyCode::BackTab => "Shift+Tab".to_string(),
+            KeyCode::Up => "↑".to_string(),
+            KeyCode::Down => "↓".to_string(),
+            KeyCode::Left => "←".to_string(),
+            KeyCode::Right => "→".to_string(),
+            KeyCode::PageUp => "PgUp".to_string(),
+            KeyCode::PageDown => "PgDn".to_string(),
+            KeyCode::Home => "Home".to_string(),
+            KeyCode::End => "End".to_string(),
+            KeyCode::Enter => "Enter".to_string(),
+            KeyCode::Esc => "Esc".to_string(),
+            code => format!("{code:?}"),
+        };
+        if self.shift {
+            format!("Shift+{key}")
+        } else {
+            key
+        }
+    }
+}
+
+pub struct Action {
+    pub id: ActionId,
+    /// What to type in the command palette
+    pub name: &'static str,
+    /// Locale key of the help text
+    pub description: &'static str,
+    /// Default keys; the first is the one shown
+    pub keys: &'static [Binding],
+    /// Modes the keys work in. Besides Browsing, an action can run in the
+    /// mode it opened, where its key closes it again.
+    pub modes: &'static [AppMode],
+    /// Pane that must be focused for the keys to work
+    pub pane: Option<ActivePane>,
+    pub section: Section,
+    /// Locale key of the command bar label, for actions shown there
+    pub bar: Option<&'static str>,
+}
+
+const BROWSING: &[AppMode] = &[AppMode::Browsing];
+
+impl Action {
+    const fn new(
+        id: ActionId,
+        name: &'static str,
+        description: &'static str,
+        keys: &'static [Binding],
+        section: Section,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            description,
+            keys,
+            modes: BROWSING,
+            pane: None,
+            section,
+            bar: None,
+        }
+    }
+
+    const fn modes(mut self, modes: &'static [AppMode]) -> Self {
+        self.modes = modes;
+        self
+    }
+
+    const fn pane(mut self, pane: ActivePane) -> Self {
+        self.pane = Some(pane);
+        self
+    }
+
+    const fn bar(mut self, label: &'static str) -> Self {
+        self.bar = Some(label);
+        self
+    }
+
+    pub fn key_label(&self) -> String {
+        self.keys.first().map(Binding::label).unwrap_or_default()
+    }
+}
+
+/// The registry. Order matters: the first action whose key matches wins,
+/// and the help screen and command bar list actions in this order.
+pub const ACTIONS: &[Action] = &[
+    Action::new(
+        NextPane,
+        "next-pane",
+        "help.key.next_pane",
+        &[Binding::key(KeyCode::Tab)],
+        Navigation,
+    ),
+    Action::new(
+        PreviousPane,
+        "previous-pane",
+        "help.key.previous_pane",
+        &[Binding::key(KeyCode::BackTab)],
+        Navigation,
+    ),
+    Action::new(
+        MoveUp,
+        "up",
+        "help.key.move_up",
+        &[Binding::key(KeyCode::Up)],
+        Navigation,
+    ),
+    Action::new(
+        MoveDown,
+        "down",
+        "help.key.move_down",
+        &[Binding::key(KeyCode::Down)],
+        Navigation,
+    ),
+    Action::new(
+        PageUp,
+        "page-up",
+        "help.key.page_up",
+        &[Binding::key(KeyCode::PageUp)],
+        Navigation,
+    ),
+    Action::new(
+        PageDown,
+        "page-down",
+        "help.key.page_down",
+        &[Binding::key(KeyCode::PageDown)],
+        Navigation,
+    ),
+    Action::new(
+        First,
+        "first",
+        "help.key.first",
+        &[Binding::key(KeyCode::Home)],
+        Navigation,
+    ),
+    Action::new(
+        Last,
+        "last",
+        "help.key.last",
+        &[Binding::key(KeyCode::End)],
+        Navigation,
+    ),
+    Action::new(
+        PreviousRegion,
+        "previous-region",
+        "help.key.previous_region",
+        &[Binding::key(KeyCode::Left)],
+        Navigation,
+    )
+    .pane(ActivePane::Buckets),
+    Action::new(
+        NextRegion,
+        "next-region",
+        "help.key.next_region",
+        &[Binding::key(KeyCode::Right)],
+        Navigation,
+    )
+    .pane(ActivePane::Buckets),
+    Action::new(
+        ScrollKeysLeft,
+        "scroll-left",
+        "help.key.scroll_left",
+        &[Binding::shift(KeyCode::Left)],
+        Navigation,
+    )
+    .pane(ActivePane::Objects),
+    Action::new(
+        ScrollKeysRight,
+        "scroll-right",
+        "help.key.scroll_right",
+        &[Binding::shift(KeyCode::Right)],
+        Navigation,
+    )
+    .pane(ActivePane::Objects),
+    Action::new(
+        LoadBucket,
+        "load",
+        "help.key.load_bucket",
+        &[Binding::key(KeyCode::Enter)],
+        Navigation,
+    )
+    .pane(ActivePane::Buckets),
+    Action::new(
+        GoToKey,
+        "goto",
+        "help.key.goto",
+        &[Binding::char('g')],
+        Navigation,
+    )
+    .bar("bar.goto"),
+    Action::new(
+        ListFromKey,
+        "window",
+        "help.key.window",
+        &[Binding::char('w')],
+        Navigation,
+    ),
+    Action::new(
+        FullKey,
+        "full-key",
+        "help.key.full_key",
+        &[Binding::char('K')],
+        Navigation,
+    )
+    .modes(&[AppMode::Browsing, AppMode::ViewingKey]),
+    Action::new(
+        FolderView,
+        "folders",
+        "help.key.folder_view",
+        &[Binding::char('F')],
+        Navigation,
+    ),
+    Action::new(
+        Versions,
+        "versions",
+        "help.key.versions",
+        &[Binding::char('H')],
+        Navigation,
+    ),
+    Action::new(
+        OpenFolder,
+        "open-folder",
+        "help.key.open_folder",
+        &[Binding::key(KeyCode::Enter)],
+        Navigation,
+    )
+    .pane(ActivePane::Objects),
+    Action::new(
+        ParentFolder,
+        "parent-folder",
+        "help.key.parent_folder",
+        &[Binding::key(KeyCode::Backspace)],
+        Navigation,
+    )
+    .pane(ActivePane::Objects),
+    Action::new(
+        EditMask,
+        "mask",
+        "help.key.mask_editor",
+        &[Binding::char('m')],
+        Masks,
+    )
+    .bar("bar.mask"),
+    Action::new(
+        ScriptMask,
+        "script",
+        "help.key.script_mask",
+        &[Binding::char('M')],
+        Masks,
+    ),
+    Action::new(
+        ToggleRow,
+        "pick",
+        "help.key.pick",
+        &[Binding::char(' ')],
+        Masks,
+    )
+    .pane(ActivePane::Objects),
+    Action::new(
+        PickAll,
+        "pick-all",
+        "help.key.pick_all",
+        &[Binding::char('*')],
+        Masks,
+    )
+    .pane(ActivePane::Objects),
+    Action::new(
+        RestoreFilter,
+        "restore-filter",
+        "help.key.restore_filter",
+        &[Binding::char('N')],
+        Masks,
+    )
+    .pane(ActivePane::Objects),
+    Action::new(
+        RestoreSort,
+        "restore-sort",
+        "help.key.restore_sort",
+        &[Binding::char('S')],
+        Masks,
+    )
+    .pane(ActivePane::Objects),
+    Action::new(
+        Protect,
+        "protect",
+        "help.key.protect",
+        &[Binding::char('P')],
+        Masks,
+    ),
+    Action::new(
+        ClearSelection,
+        "clear-mask",
+        "help.key.clear_mask",
+        &[Binding::key(KeyCode::Esc)],
+        Masks,
+    ),
+    Action::new(
+        Transition,
+        "transition",
+        "help.key.transition",
+        &[Binding::char('s')],
+        Storage,
+    )
+    .bar("bar.storage"),
+    Action::new(
+        Restore,
+        "restore",
+        "help.key.restore",
+        &[Binding::char('r')],
+        Storage,
+    )
+    .bar("bar.restore"),
+    Action::new(
+        Reencrypt,
+        "reencrypt",
+        "help.key.reencrypt",
+        &[Binding::char('e')],
+        Storage,
+    ),
+    Action::new(
+        DeleteVersions,
+        "delete-versions",
+        "help.key.delete_versions",
+        &[Binding::char('x')],
+        Storage,
+    ),
+    Action::new(
+        Bundle,
+        "bundle",
+        "help.key.bundle",
+        &[Binding::char('z')],
+        Storage,
+    ),
+    Action::new(
+        OpenBundle,
+        "open-bundle",
+        "help.key.open_bundle",
+        &[Binding::char('Z')],
+        Storage,
+    ),
+    Action::new(
+        Inspect,
+        "inspect",
+        "help.key.inspect",
+        &[Binding::char('i')],
+        Storage,
+    )
+    .bar("bar.info"),
+    Action::new(
+        SplitView,
+        "split",
+        "help.key.split",
+        &[Binding::char('v')],
+        Storage,
+    )
+    .bar("bar.split"),
+    Action::new(
+        CopyRight,
+        "copy-right",
+        "help.key.copy_right",
+        &[Binding::char('>')],
+        Storage,
+    ),
+    Action::new(
+        CopyLeft,
+        "copy-left",
+        "help.key.copy_left",
+        &[Binding::char('<')],
+        Storage,
+    ),
+    Action::new(
+        CopyTo,
+        "copy-to",
+        "help.key.copy_to",
+        &[Binding::char('c')],
+        Storage,
+    ),
+    Action::new(
+        CopyConfig,
+        "copy-config",
+        "help.key.copy_config",
+        &[Binding::char('B')],
+        Storage,
+    ),
+    Action::new(
+        Reconcile,
+        "reconcile",
+        "help.key.reconcile",
+        &[Binding::char('V')],
+        Storage,
+    ),
+    Action::new(
+        Log,
+        "log",
+        "help.key.log",
+        &[Binding::char('l'), Binding::char('L')],
+        Other,
+    )
+    .modes(&[AppMode::Browsing, AppMode::ViewingLog])
+    .bar("bar.log"),
+    Action::new(
+        Tracker,
+        "tracker",
+        "help.key.tracker",
+        &[Binding::char('t'), Binding::char('T')],
+        Other,
+    )
+    .modes(&[AppMode::Browsing, AppMode::ViewingRestoreRequests])
+    .bar("bar.tracker"),
+    Action::new(
+        History,
+        "history",
+        "help.key.history",
+        &[Binding::char('h')],
+        Other,
+    )
+    .bar("bar.history"),
+    Action::new(
+        Uploads,
+        "uploads",
+        "help.key.uploads",
+        &[Binding::char('U')],
+        Other,
+    ),
+    Action::new(
+        Policies,
+        "policies",
+        "help.key.policies",
+        &[Binding::char('p')],
+        Other,
+    ),
+    Action::new(
+        Refresh,
+        "refresh",
+        "help.key.refresh",
+        &[Binding::char('f')],
+        Other,
+    )
+    .bar("bar.refresh"),
+    Action::new(
+        LoadAll,
+        "load-all",
+        "help.key.load_all",
+        &[Binding::char('a')],
+        Other,
+    )
+    .bar("bar.all"),
+    Action::new(
+        Owners,
+        "owners",
+        "help.key.owner",
+        &[Binding::char('O')],
+        Other,
+    ),
+    Action::new(
+        QueryInventory,
+        "query",
+        "help.key.query",
+        &[Binding::char('Q')],
+        Other,
+    ),
+    Action::new(
+        QueryAthena,
+        "athena",
+        "help.key.athena",
+        &[Binding::char('A')],
+        Other,
+    ),
+    Action::new(
+        ExportLifecycle,
+        "export-lifecycle",
+        "help.key.export",
+        &[Binding::char('E')],
+        Other,
+    ),
+    Action::new(
+        ExportReport,
+        "export-report",
+        "help.key.report",
+        &[Binding::char('R')],
+        Other,
+    ),
+    Action::new(
+        BucketTags,
+        "bucket-tags",
+        "help.key.bucket_tags",
+        &[Binding::char('b')],
+        Other,
+    ),
+    Action::new(
+        CreateBucket,
+        "create-bucket",
+        "help.key.create_bucket",
+        &[Binding::char('C')],
+        Other,
+    ),
+    Action::new(
+        Decommission,
+        "decommission",
+        "help.key.decommission",
+        &[Binding::char('D')],
+        Other,
+    ),
+    Action::new(
+        AccessAudit,
+        "access-audit",
+        "help.key.access_audit",
+        &[Binding::char('X')],
+        Other,
+    ),
+    Action::new(
+        LastReads,
+        "last-reads",
+        "help.key.last_reads",
+        &[Binding::char('W')],
+        Other,
+    ),
+    Action::new(
+        Palette,
+        "palette",
+        "help.key.palette",
+        &[Binding::char(':')],
+        Other,
+    ),
+    Action::new(Help, "help", "help.key.help", &[Binding::char('?')], Other)
+        .modes(&[AppMode::Browsing, AppMode::ShowingHelp])
+        .bar("bar.help"),
+    Action::new(Quit, "quit", "help.key.quit", &[Binding::char('q')], Other).bar("bar.quit"),
+];
+
+/// The action `key` triggers in the current mode and pane
+pub fn lookup(key: &KeyEvent, mode: AppMode, pane: ActivePane) -> Option<ActionId> {
+    ACTIONS
+        .iter()
+        .filter(|action| action.modes.contains(&mode))
+        .filter(|action| action.pane.is_none_or(|p| p == pane))
+        .find(|action| action.keys.iter().any(|binding| binding.matches(key)))
+        .map(|action| action.id)
+}
+
+/// Actions the command palette offers for `query`, matched against their
+/// name and help text
+pub fn palette_matches(query: &str) -> Vec<&'static Action> {
+    let query = query.trim().to_lowercase();
+    ACTIONS
+        .iter()
+        .filter(|action| action.id != Palette)
+        .filter(|action| {
+            query.is_empty()
+                || action.name.contains(&query)
+                || t(action.description).to_lowercase().contains(&query)
+        })
+        .collect()
+}
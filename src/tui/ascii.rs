@@ -0,0 +1,53 @@
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Modifier};
+
+/// Rewrite a rendered frame for `--ascii` mode: the UI's own glyphs become
+/// plain ASCII and colors are dropped. Other characters, such as those of
+/// object keys, tags or a translated catalog, are left as they are. Highlights that relied on a background color are
+/// shown in reverse video instead, and the active pane (bold border) is drawn
+/// with `=` so focus is visible without color.
+pub fn downgrade(buffer: &mut Buffer) {
+    for cell in buffer.content.iter_mut() {
+        let bold = cell.modifier.contains(Modifier::BOLD);
+        let replacement = match cell.symbol() {
+            s if s.is_ascii() => None,
+            "─" | "━" | "═" if bold => Some("="),
+            "─" | "━" | "═" => Some("-"),
+            "│" | "┃" | "║" => Some("|"),
+            "┌" | "┐" | "└" | "┘" | "╭" | "╮" | "╰" | "╯" | "┼" | "├" | "┤" | "┬" | "┴" => {
+                Some("+")
+            }
+            "█" | "▉" | "▊" | "▋" | "▌" | "▍" | "▎" | "▏" => Some("#"),
+            "►" | "→" | "▶" => Some(">"),
+            "←" | "◀" => Some("<"),
+            "↑" => Some("^"),
+            // Lower bounds; the region badge writes ">=" itself
+            "≥" => Some(">"),
+            "↓" | "▼" => Some("v"),
+            "⟳" | "↻" => Some("*"),
+            // Background job badge in the status bar
+            "⚙" => Some("*"),
+            "✓" => Some("+"),
+            "✗" => Some("x"),
+            // Warnings, including the blocked-prefix line of a confirmation
+            "⚠" | "✖" => Some("!"),
+            "⛨" => Some("@"),
+            // Picked bundle members and the suggested storage class
+            "●" => Some("o"),
+            "★" => Some("*"),
+            "•" | "·" => Some("*"),
+            "–" | "—" => Some("-"),
+            "…" => Some("."),
+            _ => None,
+        };
+        if let Some(symbol) = replacement {
+            cell.set_symbol(symbol);
+        }
+
+        if !matches!(cell.bg, Color::Reset | Color::Black) {
+            cell.modifier.insert(Modifier::REVERSED);
+        }
+        cell.fg = Color::Reset;
+        cell.bg = Color::Reset;
+    }
+}
@@ -1,29 +1,92 @@
+mod actions;
+mod ascii;
+mod view;
+
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
 use std::io::{self, IsTerminal, Stdout};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::event::{self, Event, EventStream, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 use crossterm::execute;
 use crossterm::terminal::{
     EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
 };
+use futures::StreamExt;
 use ratatui::Terminal;
 use ratatui::backend::CrosstermBackend;
-use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
-use ratatui::style::{Color, Modifier, Style};
-use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Clear, Gauge, List, ListItem, ListState, Paragraph, Wrap};
-
-use aws_sdk_s3::error::{ProvideErrorMetadata, SdkError};
-use aws_sdk_s3::operation::restore_object::RestoreObjectError;
-
-use crate::app::{ActivePane, App, AppMode, MaskEditorField, PendingAction, StorageIntent};
-use crate::aws::S3Service;
-use crate::mask::ObjectMask;
-use crate::models::{RestoreState, StorageClassTier};
+
+use tokio::sync::Semaphore;
+use tokio::task::JoinHandle;
+
+use crate::access::AccessAudit;
+use crate::app::{
+    ActivePane, App, AppMode, BatchPause, BucketFormField, BundleView, CopyDraft, CopyFormField,
+    LifecycleComparison, MAX_RESTORE_CONCURRENCY, MaskDraft, MaskEditorField, PauseChoice,
+    PendingAction, PolicyStep, QuerySet, RestoreUpdates, ShardState, SharedRequest, SharedSync,
+    SplitPane, StorageIntent, shutdown_label,
+};
+use crate::approvals::ApprovalTally;
+use crate::athena;
+use crate::aws::{ObjectAttributes, S3Service};
+use crate::bandwidth::{self, format_rate};
+use crate::batch::{
+    FAILURE_PAUSE_RATE, FAILURE_SAMPLE, ShutdownChoice, apply_object_lock, copy_api, move_source,
+    reencrypt_object, transition_object, uses_kms_key,
+};
+use crate::bucket_config::{ConfigCopy, ConfigPart};
+use crate::bundle::{self, ArchiveCompression, BundleEntry, MemberReader};
+use crate::collisions::{CollisionChoice, CollisionReview};
+use crate::connectivity;
+use crate::decommission::{Decommission, DecommissionStep};
+use crate::dryrun::DryRun;
+use crate::enrich::{self, EnrichHandle};
+use crate::errors::{self, ErrorKind};
+use crate::event::AppEvent;
+use crate::hooks::HookFailure;
+use crate::i18n::{t, tf};
+use crate::inventory::{self, InventorySource};
+use crate::jobs::{JobEvent, JobQueue, JobWork};
+use crate::journal::JobHistory;
+use crate::lifecycle::{LifecycleRule, MechanismComparison};
+use crate::loader::{self, ListingEvent};
+use crate::mask::{MaskKind, ObjectMask};
+use crate::models::{CopyMode, JobKind, KeyRewrite};
+use crate::models::{
+    NewBucket, ObjectInfo, ObjectLockPlan, RestoreStatus, RestoreTier, Retention, RetentionMode,
+    StorageClassTier, parse_retain_until, restore_label,
+};
+use crate::multipart::{self, CopyOutcome, MULTIPART_COPY_THRESHOLD, MultipartStore};
+use crate::notify;
+use crate::owners::PrefixOwner;
+use crate::partition::Partition;
+use crate::plan::transition_label;
+use crate::policy::{self, MigrationPolicy};
+use crate::preview::{PreviewRowKind, TargetPreview};
+use crate::recency::LastReads;
+use crate::reconcile::{self, ReconcileHandle};
+use crate::report::BucketReport;
+use crate::schedule::{self, ScheduledAction, ScheduledKind};
+use crate::script;
+use crate::shards::{ShardPlan, ShardWork};
+use crate::tags::{self, TagEditor, TagStep};
+use crate::tickets::TicketLink;
 use crate::tracker::RestoreTracker;
+use crate::update::Release;
+use crate::versions::VersionReplay;
+use crate::waves::{self, WavePlan};
 
-pub async fn run(app: &mut App, s3: &S3Service, mut tracker: RestoreTracker) -> Result<()> {
+use actions::ActionId;
+use view::{draw, draw_batch, short_timestamp};
+
+pub async fn run(
+    app: &mut App,
+    s3: &S3Service,
+    mut tracker: RestoreTracker,
+    mut history: JobHistory,
+    mut uploads: MultipartStore,
+) -> Result<()> {
     // Verify we have a terminal before trying to initialize TUI
     if !io::stdout().is_terminal() {
         anyhow::bail!(
@@ -33,6 +96,15 @@ pub async fn run(app: &mut App, s3: &S3Service, mut tracker: RestoreTracker) ->
         );
     }
 
+    // Leave raw mode and the alternate screen before the panic is reported.
+    // A panic in a background task would leave the TUI running on a cooked
+    // terminal, so any panic ends the process.
+    std::panic::set_hook(Box::new(|info| {
+        restore_terminal();
+        crate::crash::report(info);
+        std::process::exit(101);
+    }));
+
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen)?;
@@ -40,93 +112,449 @@ pub async fn run(app: &mut App, s3: &S3Service, mut tracker: RestoreTracker) ->
     let mut terminal = Terminal::new(backend)?;
     terminal.hide_cursor()?;
 
-    app.push_status("Loading buckets…");
+    app.push_status(t("status.loading_buckets"));
     if let Err(err) = refresh_buckets(app, s3).await {
         // Check if this is a credentials error
         let err_msg = format!("{err:#}");
-        if err_msg.contains("credentials")
+        if errors::classify(&err) == Some(ErrorKind::Credentials)
+            || err_msg.contains("credentials")
             || err_msg.contains("UnrecognizedClientException")
             || err_msg.contains("InvalidAccessKeyId")
             || err_msg.contains("SignatureDoesNotMatch")
             || err_msg.contains("NoCredentialsError")
         {
             app.set_mode(AppMode::CredentialError);
-            app.push_status(&format!("AWS credentials error: {err_msg}"));
+            app.push_status(&tf("status.credentials_error", &[("err_msg", &err_msg)]));
         } else {
-            app.push_status(&format!("Failed to load buckets: {err:#}"));
+            app.push_error(t("error.load_buckets"), &err);
         }
     }
 
-    let result = event_loop(&mut terminal, app, s3, &mut tracker).await;
+    if !uploads.uploads().is_empty() {
+        app.push_status(&tf(
+            "status.interrupted_uploads",
+            &[("count", &uploads.uploads().len())],
+        ));
+        app.upload_cursor = 0;
+        app.set_mode(AppMode::ResumingUploads);
+    }
+
+    let result = event_loop(
+        &mut terminal,
+        app,
+        s3,
+        &mut tracker,
+        &mut history,
+        &mut uploads,
+    )
+    .await;
     disable_raw_mode()?;
     execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
     terminal.show_cursor()?;
     result
 }
 
+fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(io::stdout(), LeaveAlternateScreen, crossterm::cursor::Show);
+}
+
 async fn event_loop(
     terminal: &mut Terminal<CrosstermBackend<Stdout>>,
     app: &mut App,
     s3: &S3Service,
     tracker: &mut RestoreTracker,
+    history: &mut JobHistory,
+    uploads: &mut MultipartStore,
 ) -> Result<()> {
-    let mut last_refresh = std::time::Instant::now();
+    let mut last_refresh = Instant::now();
     let refresh_interval = Duration::from_secs(30);
+    let mut redraw = true;
+    let mut drawn_at = Instant::now();
+    let mut events = EventStream::new();
 
     loop {
-        terminal.draw(|frame| draw(frame, app, tracker))?;
+        // Redraw after anything changed, plus a slow heartbeat that keeps
+        // countdowns current
+        if redraw || drawn_at.elapsed() >= CLOCK_REDRAW {
+            terminal.draw(|frame| draw(frame, app, tracker, history, uploads))?;
+            drawn_at = Instant::now();
+            redraw = false;
+        }
 
         // Check if we should auto-load objects for selected bucket
         if app.pending_bucket_load
             && let Some(last_change) = app.last_bucket_change
-            && last_change.elapsed() >= Duration::from_secs(1)
+            && last_change.elapsed() >= BUCKET_LOAD_DELAY
         {
             app.pending_bucket_load = false;
-            if let Err(err) = load_objects_for_selection(app, s3).await {
-                app.push_status(&format!("Failed to load objects: {err:#}"));
-            } else {
-                // Automatically switch to Objects pane after successful load
-                app.active_pane = ActivePane::Objects;
+            load_objects_for_selection(app, s3);
+            // Automatically switch to Objects pane once loading starts
+            app.active_pane = ActivePane::Objects;
+            redraw = true;
+        }
+
+        redraw |= drain_listing_events(app, s3).await;
+        redraw |= drain_split_listing_events(app);
+        redraw |= drain_enrich_events(app);
+        if !app.refreshed_restores.is_empty() {
+            let updates = std::mem::take(&mut app.refreshed_restores);
+            apply_restore_updates(app, tracker, updates);
+            redraw = true;
+        }
+        redraw |= drain_reconcile_events(app);
+        redraw |= drain_job_events(app, tracker, history);
+        start_connection_probe(app, s3);
+        // Background work waits while AWS is unreachable
+        let online = !app.connectivity.is_offline();
+        if online {
+            start_restore_check(app, s3, tracker);
+            start_shard_sync(app, s3);
+        }
+
+        // Batches only start inside the execution window, if one is set
+        let window_open = app.execution_window.is_none_or(|window| window.is_open());
+
+        // Start the next restore wave once the previous one has completed
+        if app.mode == AppMode::Browsing
+            && window_open
+            && online
+            && let Some((plan_id, wave)) = tracker.due_wave()
+        {
+            let action = PendingAction::RestoreWave { plan_id, wave };
+            // The batch monitor reads the terminal itself while it runs
+            drop(events);
+            run_batch(terminal, app, s3, tracker, history, uploads, action).await?;
+            events = EventStream::new();
+            if app.quit_requested {
+                break;
+            }
+            redraw = true;
+            continue;
+        }
+
+        // Run the next scheduled action once its time has come
+        if app.mode == AppMode::Browsing
+            && window_open
+            && online
+            && let Some(scheduled) = app.schedule.take_due()
+        {
+            redraw = true;
+            let Some(action) = prepare_scheduled_action(app, s3, scheduled).await else {
+                continue;
+            };
+            drop(events);
+            run_batch(terminal, app, s3, tracker, history, uploads, action).await?;
+            app.job_note = None;
+            app.job_ticket = None;
+            events = EventStream::new();
+            if app.quit_requested {
+                break;
             }
+            continue;
         }
 
         // Check if we should lazy-load more objects
-        if app.should_load_more()
-            && !app.is_loading_objects
-            && let Err(err) = load_more_objects(app, s3).await
+        if app.should_load_more() && !app.is_loading_objects {
+            load_more_objects(app);
+            redraw = true;
+        }
+        if let Some(split) = &app.split
+            && split.should_load_more()
+            && let Some(listing) = &split.listing
         {
-            app.push_status(&format!("Failed to load more: {err:#}"));
+            listing.request(split.objects.len() + PAGE_SIZE);
         }
 
         // Check if it's time to auto-refresh
+        let refresh_due = online && !app.objects.is_empty() && app.selected_bucket_name().is_some();
         if last_refresh.elapsed() >= refresh_interval {
-            if !app.objects.is_empty() && app.selected_bucket_name().is_some() {
+            if refresh_due {
                 // Silently refresh with pagination
-                let _ = load_objects_for_selection(app, s3).await;
+                load_objects_for_selection(app, s3);
+                redraw = true;
             }
-            last_refresh = std::time::Instant::now();
+            last_refresh = Instant::now();
+        }
+        if redraw {
+            continue;
         }
 
-        if event::poll(Duration::from_millis(200))? {
-            match event::read()? {
-                Event::Key(key) => {
-                    if handle_key_event(key, app, s3, tracker).await? {
+        // Sleep until input, background work or the next timer wakes us
+        let mut wake_at = drawn_at + CLOCK_REDRAW;
+        if refresh_due {
+            wake_at = wake_at.min(last_refresh + refresh_interval);
+        }
+        if app.pending_bucket_load
+            && let Some(last_change) = app.last_bucket_change
+        {
+            wake_at = wake_at.min(last_change + BUCKET_LOAD_DELAY);
+        }
+        if let Some(probed_at) = app.connectivity.probed_at
+            && app.connection_probe.is_none()
+        {
+            wake_at = wake_at.min(probed_at + connectivity::PROBE_INTERVAL);
+        }
+        if let Some(checked_at) = app.restore_checked_at
+            && online
+            && app.restore_check.is_none()
+            && !tracker.get_active_requests().is_empty()
+        {
+            wake_at = wake_at.min(checked_at + RESTORE_CHECK_INTERVAL);
+        }
+        if let Some(synced_at) = app.shard_synced_at
+            && online
+            && app.shard_sync.is_none()
+            && app.shared_state.is_some()
+            && (!app.shared_requests.is_empty()
+                || app.shards.plans().iter().any(|plan| plan.shared))
+        {
+            wake_at = wake_at.min(synced_at + SHARD_SYNC_INTERVAL);
+        }
+        if let Some(mut run_at) = app.schedule.next_run_at() {
+            if let Some(window) = app.execution_window {
+                run_at = window.earliest_from(run_at.max(chrono::Utc::now()));
+            }
+            let until = (run_at - chrono::Utc::now()).to_std().unwrap_or_default();
+            wake_at = wake_at.min(Instant::now() + until);
+        }
+        if let Some(window) = app.execution_window
+            && !window_open
+            && tracker.due_wave().is_some()
+        {
+            let now = chrono::Utc::now();
+            let until = (window.earliest_from(now) - now)
+                .to_std()
+                .unwrap_or_default();
+            wake_at = wake_at.min(Instant::now() + until);
+        }
+        let split_listing = app.split.as_mut().and_then(|split| split.listing.as_mut());
+        let wake = tokio::select! {
+            event = events.next() => Wake::Input(event),
+            updates = restore_check_done(&mut app.restore_check) => Wake::RestoreCheck(updates),
+            sync = shard_sync_done(&mut app.shard_sync) => Wake::ShardSync(sync),
+            reached = probe_done(&mut app.connection_probe) => Wake::Probe(reached),
+            release = update_check_done(&mut app.update_check) => Wake::UpdateCheck(release),
+            lookup = recency_done(&mut app.recency_lookup) => Wake::Recency(lookup),
+            check = ticket_check_done(&mut app.ticket_check) => Wake::TicketCheck(check),
+            review = collision_check_done(&mut app.collision_check) => Wake::CollisionCheck(review),
+            _ = listing_ready(app.listing.as_mut()) => Wake::Background,
+            _ = listing_ready(split_listing) => Wake::Background,
+            _ = enrich_ready(app.enrichment.as_mut()) => Wake::Background,
+            _ = reconcile_ready(app.reconcile.as_mut()) => Wake::Background,
+            _ = jobs_ready(&mut app.jobs) => Wake::Background,
+            _ = tokio::time::sleep_until(wake_at.into()) => Wake::Timer,
+        };
+        redraw = true;
+        match wake {
+            Wake::Input(Some(Ok(Event::Key(key)))) => {
+                if handle_key_event(key, app, s3, history, uploads).await? {
+                    break;
+                }
+                if let Some(action) = app.queued_job.take() {
+                    start_background_job(app, s3, tracker, history, action).await;
+                    app.job_note = None;
+                    app.job_ticket = None;
+                }
+                if let Some(action) = app.queued_batch.take() {
+                    drop(events);
+                    run_batch(terminal, app, s3, tracker, history, uploads, action).await?;
+                    app.job_note = None;
+                    app.job_ticket = None;
+                    events = EventStream::new();
+                    if app.quit_requested {
                         break;
                     }
                 }
-                Event::Resize(_, _) => continue,
-                _ => continue,
             }
+            Wake::Input(Some(Err(err))) => return Err(err.into()),
+            Wake::Input(None) => break,
+            Wake::RestoreCheck(updates) => apply_restore_updates(app, tracker, updates),
+            Wake::ShardSync(sync) => apply_shard_sync(app, sync),
+            Wake::Probe(reached) => app.update(AppEvent::ConnectionProbed(reached)),
+            Wake::UpdateCheck(release) => {
+                if let Some(release) = release {
+                    app.update(AppEvent::NewRelease(release));
+                }
+            }
+            Wake::Recency(lookup) => apply_last_reads(app, lookup),
+            Wake::TicketCheck(check) => apply_ticket_check(app, check),
+            Wake::CollisionCheck(review) => apply_collision_check(app, review),
+            Wake::Input(Some(Ok(_))) | Wake::Background | Wake::Timer => {}
         }
     }
+    // Background jobs stop when the queue is dropped
+    for job in app.jobs.jobs() {
+        history.interrupt(&job.id);
+    }
     Ok(())
 }
 
+/// What woke the event loop
+enum Wake {
+    Input(Option<io::Result<Event>>),
+    RestoreCheck(RestoreUpdates),
+    ShardSync(SharedSync),
+    /// Whether the connection probe got an answer from AWS
+    Probe(bool),
+    /// A newer release, if the startup check found one
+    UpdateCheck(Option<Release>),
+    /// Last reads of a bucket, from its access logs or CloudTrail
+    Recency(Option<Result<LastReads>>),
+    /// The tracker's answer on a typed change ticket
+    TicketCheck(Option<Result<TicketLink>>),
+    /// Destination keys a renaming copy would overwrite
+    CollisionCheck(Option<Result<CollisionReview>>),
+    Background,
+    Timer,
+}
+
+/// While offline, check in the background whether AWS can be reached again
+fn start_connection_probe(app: &mut App, s3: &S3Service) {
+    if app.connection_probe.is_some() || !app.connectivity.probe_due() {
+        return;
+    }
+    app.connectivity.probed_at = Some(Instant::now());
+    let s3 = s3.clone();
+    app.connection_probe = Some(tokio::spawn(async move {
+        // Any answer from AWS, even an error, means it is reachable
+        match s3.probe().await {
+            Ok(()) => true,
+            Err(err) => !connectivity::is_network_error(&err),
+        }
+    }));
+}
+
+/// Wait for the running connection probe, or forever when none is running
+async fn probe_done(probe: &mut Option<JoinHandle<bool>>) -> bool {
+    let Some(handle) = probe else {
+        return std::future::pending().await;
+    };
+    let reached = handle.await.unwrap_or(false);
+    *probe = None;
+    reached
+}
+
+/// Wait for the startup update check, or forever once it is done
+async fn update_check_done(check: &mut Option<JoinHandle<Option<Release>>>) -> Option<Release> {
+    let Some(handle) = check else {
+        return std::future::pending().await;
+    };
+    let release = handle.await.ok().flatten();
+    *check = None;
+    release
+}
+
+/// Wait for the tracker lookup of a typed ticket, or forever when none is
+/// running
+async fn ticket_check_done(
+    check: &mut Option<JoinHandle<Result<TicketLink>>>,
+) -> Option<Result<TicketLink>> {
+    let Some(handle) = check else {
+        return std::future::pending().await;
+    };
+    let result = handle.await.ok();
+    *check = None;
+    result
+}
+
+/// Link the looked-up ticket to the pending action, or say why it was not
+fn apply_ticket_check(app: &mut App, check: Option<Result<TicketLink>>) {
+    match check {
+        Some(Ok(ticket)) => {
+            app.push_status(&tf("status.ticket_found", &[("id", &ticket.id)]));
+            app.job_ticket = Some(ticket);
+        }
+        Some(Err(err)) => app.push_error(t("error.ticket_check"), &err),
+        None => app.push_status(t("status.ticket_check_stopped")),
+    }
+}
+
+/// Wait for the lookup of a renaming copy's destination keys, or forever
+/// when none is running
+async fn collision_check_done(
+    check: &mut Option<JoinHandle<Result<CollisionReview>>>,
+) -> Option<Result<CollisionReview>> {
+    let Some(handle) = check else {
+        return std::future::pending().await;
+    };
+    let result = handle.await.ok();
+    *check = None;
+    result
+}
+
+/// Have the collisions the lookup found decided before the copy runs
+fn apply_collision_check(app: &mut App, check: Option<Result<CollisionReview>>) {
+    match check {
+        Some(Ok(review)) if review.is_empty() => app.push_status(&tf(
+            "status.no_collisions",
+            &[("bucket", &review.dest_bucket)],
+        )),
+        Some(Ok(review)) => {
+            app.push_status(&tf(
+                "status.collisions",
+                &[
+                    ("count", &review.collisions.len()),
+                    ("bucket", &review.dest_bucket),
+                ],
+            ));
+            app.collisions = Some(review);
+            if app.mode == AppMode::Confirming {
+                app.set_mode(AppMode::ResolvingCollisions);
+            }
+        }
+        Some(Err(err)) => {
+            app.push_error(t("error.collision_check"), &err);
+            collision_check_failed(app, errors::describe(&err));
+        }
+        None => collision_check_failed(app, t("collisions.check_stopped").to_string()),
+    }
+}
+
+/// Hold the copy until the failed lookup is retried or the operator chooses
+/// to overwrite whatever the renamed keys land on
+fn collision_check_failed(app: &mut App, reason: String) {
+    app.collision_error = Some(reason);
+    if app.mode == AppMode::Confirming {
+        app.set_mode(AppMode::ResolvingCollisions);
+    }
+}
+
+/// Wait for the next event of a listing, or forever when there is none
+async fn listing_ready(listing: Option<&mut loader::ListingHandle>) {
+    match listing {
+        Some(listing) => listing.ready().await,
+        None => std::future::pending().await,
+    }
+}
+
+async fn enrich_ready(enrichment: Option<&mut EnrichHandle>) {
+    match enrichment {
+        Some(enrichment) => enrichment.ready().await,
+        None => std::future::pending().await,
+    }
+}
+
+async fn reconcile_ready(reconcile: Option<&mut ReconcileHandle>) {
+    match reconcile {
+        Some(reconcile) => reconcile.ready().await,
+        None => std::future::pending().await,
+    }
+}
+
+async fn jobs_ready(jobs: &mut JobQueue) {
+    if jobs.is_empty() {
+        return std::future::pending().await;
+    }
+    jobs.ready().await
+}
+
 async fn handle_key_event(
     key: KeyEvent,
     app: &mut App,
     s3: &S3Service,
-    tracker: &mut RestoreTracker,
+    history: &mut JobHistory,
+    uploads: &mut MultipartStore,
 ) -> Result<bool> {
     if key.kind != KeyEventKind::Press {
         return Ok(false);
@@ -136,37 +564,30 @@ async fn handle_key_event(
         return Ok(true);
     }
 
+    if let Some(action) = actions::lookup(&key, app.mode, app.active_pane) {
+        return run_action(action, app, s3, history, uploads).await;
+    }
+
     match app.mode {
         AppMode::CredentialError => {
             // Any key press exits the application
             return Ok(true);
         }
-        AppMode::ShowingHelp => {
-            if matches!(key.code, KeyCode::Esc | KeyCode::Enter | KeyCode::Char('?')) {
-                app.set_mode(AppMode::Browsing);
-            }
-            return Ok(false);
-        }
         AppMode::ViewingLog => {
-            if matches!(
-                key.code,
-                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('l') | KeyCode::Char('L')
-            ) {
-                app.set_mode(AppMode::Browsing);
+            if !handle_log_keys(key, app) && matches!(key.code, KeyCode::Esc | KeyCode::Enter) {
+                close_log(app);
             }
             return Ok(false);
         }
-        AppMode::ViewingRestoreRequests => {
-            if matches!(
-                key.code,
-                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('t') | KeyCode::Char('T')
-            ) {
+        // Their own key closes them too, through the action that opened them
+        AppMode::ShowingHelp | AppMode::ViewingRestoreRequests | AppMode::ViewingKey => {
+            if matches!(key.code, KeyCode::Esc | KeyCode::Enter) {
                 app.set_mode(AppMode::Browsing);
             }
             return Ok(false);
         }
         AppMode::EditingMask => {
-            handle_mask_editor_keys(key, app);
+            handle_mask_editor_keys(key, app, s3).await;
             return Ok(false);
         }
         AppMode::SelectingStorageClass => {
@@ -174,557 +595,7464 @@ async fn handle_key_event(
             return Ok(false);
         }
         AppMode::Confirming => {
-            handle_confirmation_keys(key, app, s3, tracker).await?;
+            handle_confirmation_keys(key, app);
+            return Ok(false);
+        }
+        AppMode::ShowingProgress | AppMode::ConfirmingShutdown | AppMode::BatchPaused => {
+            // Batches handle their own keys while they run
+            return Ok(false);
+        }
+        AppMode::JumpingToKey => {
+            handle_jump_keys(key, app, s3).await;
+            return Ok(false);
+        }
+        AppMode::EnteringKmsKey => {
+            handle_kms_keys(key, app);
+            return Ok(false);
+        }
+        AppMode::EnteringRetainUntil => {
+            handle_retain_keys(key, app);
+            return Ok(false);
+        }
+        AppMode::EnteringJobNote => {
+            handle_job_note_keys(key, app);
+            return Ok(false);
+        }
+        AppMode::EnteringTicket => {
+            handle_ticket_keys(key, app);
+            return Ok(false);
+        }
+        AppMode::ResolvingCollisions => {
+            handle_collision_keys(key, app, s3);
+            return Ok(false);
+        }
+        AppMode::ViewingReconciliation => {
+            handle_reconcile_keys(key, app, s3);
+            return Ok(false);
+        }
+        AppMode::QueryingInventory => {
+            handle_query_keys(key, app, s3).await;
+            return Ok(false);
+        }
+        AppMode::ProtectingKeys => {
+            handle_protect_keys(key, app);
+            return Ok(false);
+        }
+        AppMode::PreviewingTargets => {
+            handle_preview_keys(key, app);
+            return Ok(false);
+        }
+        AppMode::ViewingDryRun => {
+            handle_dry_run_keys(key, app);
+            return Ok(false);
+        }
+        AppMode::ComparingMechanisms => {
+            handle_comparison_keys(key, app);
+            return Ok(false);
+        }
+        AppMode::SchedulingAction => {
+            handle_schedule_keys(key, app);
+            return Ok(false);
+        }
+        AppMode::Decommissioning => {
+            handle_decommission_keys(key, app, s3).await;
+            return Ok(false);
+        }
+        AppMode::AuditingAccess => {
+            handle_access_audit_keys(key, app, s3).await;
+            return Ok(false);
+        }
+        AppMode::CreatingBucket => {
+            handle_create_bucket_keys(key, app, s3).await;
+            return Ok(false);
+        }
+        AppMode::ChoosingCopyTarget => {
+            handle_copy_target_keys(key, app, s3).await;
+            return Ok(false);
+        }
+        AppMode::CopyingBucketConfig => {
+            handle_config_copy_keys(key, app, s3).await;
+            return Ok(false);
+        }
+        AppMode::EditingBucketTags => {
+            handle_tag_editor_keys(key, app, s3).await;
+            return Ok(false);
+        }
+        AppMode::ResumingUploads => {
+            handle_resume_uploads_keys(key, app, s3, uploads).await;
+            return Ok(false);
+        }
+        AppMode::ViewingJobHistory => {
+            handle_job_history_keys(key, app, s3, history);
+            return Ok(false);
+        }
+        AppMode::ViewingJobDetail => {
+            match key.code {
+                KeyCode::Esc | KeyCode::Backspace => app.set_mode(AppMode::ViewingJobHistory),
+                KeyCode::Up => app.history_scroll = app.history_scroll.saturating_sub(1),
+                KeyCode::Down if app.history_scroll + 1 < app.history_entries.len() => {
+                    app.history_scroll += 1;
+                }
+                KeyCode::PageUp => app.history_scroll = app.history_scroll.saturating_sub(10),
+                KeyCode::PageDown => {
+                    app.history_scroll =
+                        (app.history_scroll + 10).min(app.history_entries.len().saturating_sub(1));
+                }
+                _ => {}
+            }
+            return Ok(false);
+        }
+        AppMode::EditingScript => {
+            handle_script_editor_keys(key, app, s3).await;
             return Ok(false);
         }
-        AppMode::ShowingProgress => {
-            // Ignore all key presses during progress operations
+        AppMode::ManagingPolicies => {
+            handle_policy_keys(key, app);
             return Ok(false);
         }
+        AppMode::ViewingBundle => {
+            handle_bundle_keys(key, app);
+            return Ok(false);
+        }
+        AppMode::CommandPalette => {
+            return handle_palette_keys(key, app, s3, history, uploads).await;
+        }
         AppMode::Browsing => {}
     }
 
-    match key.code {
-        KeyCode::Char('q') => return Ok(true),
-        KeyCode::Tab => {
+    Ok(false)
+}
+
+/// Run an action from the registry, by its key or from the command palette.
+/// Returns whether to quit.
+async fn run_action(
+    action: ActionId,
+    app: &mut App,
+    s3: &S3Service,
+    history: &JobHistory,
+    uploads: &MultipartStore,
+) -> Result<bool> {
+    match action {
+        ActionId::Quit => return Ok(true),
+        ActionId::NextPane => {
             app.next_pane();
         }
-        KeyCode::BackTab => {
+        ActionId::PreviousPane => {
             app.previous_pane();
         }
-        KeyCode::Up => move_selection(app, -1),
-        KeyCode::Down => move_selection(app, 1),
-        KeyCode::Left => {
-            if app.active_pane == ActivePane::Buckets {
-                cycle_region(app, -1);
+        ActionId::MoveUp => move_selection(app, -1),
+        ActionId::MoveDown => move_selection(app, 1),
+        ActionId::PreviousRegion => {
+            cycle_region(app, -1);
+        }
+        ActionId::NextRegion => {
+            cycle_region(app, 1);
+        }
+        ActionId::ScrollKeysLeft => {
+            app.key_scroll = app.key_scroll.saturating_sub(KEY_SCROLL_STEP);
+        }
+        ActionId::ScrollKeysRight => {
+            let longest = app
+                .active_objects()
+                .iter()
+                .map(|o| o.key.chars().count())
+                .max()
+                .unwrap_or(0);
+            app.key_scroll = (app.key_scroll + KEY_SCROLL_STEP).min(longest.saturating_sub(1));
+        }
+        ActionId::SplitView => toggle_split_view(app, s3),
+        ActionId::History => {
+            app.history_cursor = 0;
+            app.set_mode(AppMode::ViewingJobHistory);
+        }
+        ActionId::Policies => {
+            // Closed by the pane's own keys, so `p` can be typed in a name
+            app.policy_pane.step = PolicyStep::Browse;
+            let count = app.policies.policies().len();
+            app.policy_pane.cursor = app.policy_pane.cursor.min(count.saturating_sub(1));
+            app.set_mode(AppMode::ManagingPolicies);
+        }
+        ActionId::Uploads => {
+            if uploads.uploads().is_empty() {
+                app.push_status(t("status.no_interrupted_uploads"));
+            } else {
+                app.upload_cursor = 0;
+                app.set_mode(AppMode::ResumingUploads);
             }
         }
-        KeyCode::Right => {
-            if app.active_pane == ActivePane::Buckets {
-                cycle_region(app, 1);
+        ActionId::CopyRight | ActionId::CopyLeft => {
+            if let Err(err) = begin_split_copy(app, action == ActionId::CopyRight) {
+                app.push_error(t("error.cannot_copy"), &err);
+            } else if let Some(PendingAction::Copy {
+                source_bucket,
+                dest_bucket,
+                ..
+            }) = &app.pending_action
+            {
+                let buckets = [source_bucket.clone(), dest_bucket.clone()];
+                audit_access(app, s3, &buckets, false).await;
             }
         }
-        KeyCode::PageUp => move_selection(app, -5),
-        KeyCode::PageDown => move_selection(app, 5),
-        KeyCode::Home => jump_selection(app, true),
-        KeyCode::End => jump_selection(app, false),
-        KeyCode::Char('m') => {
+        ActionId::LastReads => start_recency_lookup(app, s3),
+        ActionId::CopyTo => {
+            if let Err(err) = begin_copy_to(app) {
+                app.push_error(t("error.cannot_copy"), &err);
+            }
+        }
+        ActionId::FullKey => {
+            if app.mode == AppMode::ViewingKey {
+                app.set_mode(AppMode::Browsing);
+            } else if app.selected_object().is_some() {
+                app.set_mode(AppMode::ViewingKey);
+            } else {
+                app.push_status(t("status.select_object_key"));
+            }
+        }
+        ActionId::PageUp => move_selection(app, -5),
+        ActionId::PageDown => move_selection(app, 5),
+        ActionId::First => jump_selection(app, true),
+        ActionId::Last => jump_selection(app, false),
+        ActionId::ScriptMask => {
+            if app.selected_bucket_name().is_none() {
+                app.push_status(t("status.select_bucket"));
+            } else {
+                open_script_editor(app);
+            }
+        }
+        ActionId::EditMask => {
             app.set_mode(AppMode::EditingMask);
             app.focus_mask_field(MaskEditorField::Pattern);
             // Reset cursor position to end of pattern
             app.mask_draft.cursor_pos = app.mask_draft.pattern.len();
-            app.push_status(
-                "Mask editor active – Type to enter pattern, Tab to switch fields, Enter to apply",
-            );
+            app.push_status(t("status.mask_editor"));
         }
-        KeyCode::Char('f') => {
-            app.push_status("Refreshing buckets…");
+        ActionId::Refresh => {
+            app.push_status(t("status.refreshing_buckets"));
             if let Err(err) = refresh_buckets(app, s3).await {
-                app.push_status(&format!("Bucket refresh failed: {err:#}"));
+                app.push_error(t("error.bucket_refresh"), &err);
             }
         }
-        KeyCode::Char('i') => {
+        ActionId::Inspect => {
             if let Err(err) = refresh_selected_object(app, s3).await {
-                app.push_status(&format!("Inspect failed: {err:#}"));
+                app.push_error(t("error.inspect"), &err);
             }
         }
-        KeyCode::Enter => {
-            if app.active_pane == ActivePane::Buckets {
-                load_objects_for_selection(app, s3).await?;
-                // Automatically switch to Objects pane for intuitive navigation
-                app.active_pane = ActivePane::Objects;
-            }
+        ActionId::LoadBucket => {
+            load_objects_for_selection(app, s3);
+            // Automatically switch to Objects pane for intuitive navigation
+            app.active_pane = ActivePane::Objects;
         }
-        KeyCode::Char('s') => {
+        ActionId::Transition => {
             if let Err(err) = begin_storage_selection(app, StorageIntent::Transition) {
-                app.push_status(&format!("Storage selection unavailable: {err:#}"));
+                app.push_error(t("error.storage_selection"), &err);
             }
         }
-        KeyCode::Char('r') => {
+        ActionId::Restore => {
             if let Err(err) = initiate_restore_flow(app) {
-                app.push_status(&format!("Cannot request restore: {err:#}"));
+                app.push_error(t("error.cannot_restore"), &err);
             }
         }
-        KeyCode::Char('?') => {
-            app.set_mode(AppMode::ShowingHelp);
+        ActionId::Bundle => begin_bundle(app),
+        ActionId::OpenBundle => open_bundle(app, s3).await,
+        ActionId::DeleteVersions => begin_delete_versions(app),
+        ActionId::Reencrypt => {
+            if app.selected_bucket_name().is_none() || target_count(app) == 0 {
+                app.push_status(t("status.select_reencrypt"));
+            } else {
+                app.kms_input.clear();
+                app.set_mode(AppMode::EnteringKmsKey);
+            }
         }
-        KeyCode::Char('l') | KeyCode::Char('L') => {
-            if matches!(app.mode, AppMode::ViewingLog) {
+        ActionId::Help => {
+            if app.mode == AppMode::ShowingHelp {
                 app.set_mode(AppMode::Browsing);
             } else {
-                app.set_mode(AppMode::ViewingLog);
+                app.set_mode(AppMode::ShowingHelp);
             }
         }
-        KeyCode::Char('t') | KeyCode::Char('T') => {
-            if matches!(app.mode, AppMode::ViewingRestoreRequests) {
-                app.set_mode(AppMode::Browsing);
+        ActionId::Palette => {
+            app.palette_input.clear();
+            app.palette_cursor = 0;
+            app.set_mode(AppMode::CommandPalette);
+        }
+        ActionId::Log => {
+            if matches!(app.mode, AppMode::ViewingLog) {
+                close_log(app);
             } else {
-                app.set_mode(AppMode::ViewingRestoreRequests);
+                app.log_job = None;
+                app.log_scroll = 0;
+                app.set_mode(AppMode::ViewingLog);
             }
         }
-        KeyCode::Esc => {
-            if app.active_mask.is_some() {
-                app.apply_mask(None);
+        ActionId::LoadAll => toggle_load_all(app),
+        ActionId::Owners => toggle_owner_listing(app, s3),
+        ActionId::GoToKey => {
+            if app.selected_bucket_name().is_none() {
+                app.push_status(t("status.select_bucket"));
+            } else {
+                app.jump_input.clear();
+                app.jump_sets_window = false;
+                app.set_mode(AppMode::JumpingToKey);
             }
         }
-        _ => {}
-    }
-
-    Ok(false)
-}
-
-async fn handle_confirmation_keys(
-    key: KeyEvent,
-    app: &mut App,
-    s3: &S3Service,
-    tracker: &mut RestoreTracker,
-) -> Result<()> {
-    match key.code {
-        KeyCode::Esc | KeyCode::Char('n') => {
-            app.pending_action = None;
-            app.set_mode(AppMode::Browsing);
-            app.push_status("Cancelled");
+        ActionId::ListFromKey => {
+            if app.selected_bucket_name().is_none() {
+                app.push_status(t("status.select_bucket"));
+            } else {
+                app.jump_input = app.window_start().unwrap_or_default().to_string();
+                app.jump_sets_window = true;
+                app.set_mode(AppMode::JumpingToKey);
+            }
         }
-        KeyCode::Enter | KeyCode::Char('y') => {
-            if let Some(action) = app.pending_action.take() {
-                match action {
-                    PendingAction::Transition { target_class } => {
-                        execute_transition(app, s3, target_class).await?;
-                    }
-                    PendingAction::Restore { days } => {
-                        execute_restore(app, s3, tracker, days).await?;
-                    }
+        ActionId::FolderView => {
+            if app.selected_bucket_name().is_none() {
+                app.push_status(t("status.select_bucket"));
+            } else {
+                app.folder_view = !app.folder_view;
+                app.version_view = false;
+                if app.folder_view {
+                    app.push_status(t("status.folder_view"));
+                } else {
+                    app.push_status(t("status.flat_view"));
                 }
+                load_objects_for_selection(app, s3);
+                app.active_pane = ActivePane::Objects;
             }
-            app.set_mode(AppMode::Browsing);
         }
-        _ => {}
-    }
-    Ok(())
-}
-
-fn handle_mask_editor_keys(key: KeyEvent, app: &mut App) {
-    match key.code {
-        KeyCode::Esc => {
-            app.set_mode(AppMode::Browsing);
-            app.push_status("Mask edit cancelled");
+        ActionId::Versions => {
+            if app.selected_bucket_name().is_none() {
+                app.push_status(t("status.select_bucket"));
+            } else {
+                app.version_view = !app.version_view;
+                if app.version_view {
+                    // Versions are listed flat; a folder level has none
+                    app.folder_view = false;
+                    app.push_status(t("status.version_view"));
+                } else {
+                    app.push_status(t("status.current_only"));
+                }
+                load_objects_for_selection(app, s3);
+                app.active_pane = ActivePane::Objects;
+            }
         }
-        KeyCode::Enter => {
-            if app.mask_draft.pattern.is_empty() {
-                app.push_status("Mask pattern cannot be empty");
-                return;
+        ActionId::OpenFolder => match app
+            .folder_cursor
+            .and_then(|row| app.visible_folders().get(row))
+        {
+            Some(folder) => {
+                let folder = folder.clone();
+                open_folder(app, s3, Some(folder));
+            }
+            None if !app.folder_view => {
+                app.push_status(t("status.folders_hint"));
+            }
+            None => {}
+        },
+        ActionId::ParentFolder => {
+            if let Some(prefix) = app.current_prefix() {
+                let parent = parent_prefix(prefix);
+                open_folder(app, s3, parent);
             }
-            // Generate a name based on the pattern and kind
-            let name = format!("{} '{}'", app.mask_draft.kind, app.mask_draft.pattern);
-            let mask = ObjectMask {
-                name,
-                pattern: app.mask_draft.pattern.clone(),
-                kind: app.mask_draft.kind.clone(),
-                case_sensitive: app.mask_draft.case_sensitive,
-                storage_class_filter: app.mask_draft.storage_class_filter.clone(),
-            };
-            app.apply_mask(Some(mask));
-            app.set_mode(AppMode::Browsing);
         }
-        KeyCode::Tab => {
-            app.next_mask_field();
+        ActionId::Tracker => {
+            if matches!(app.mode, AppMode::ViewingRestoreRequests) {
+                app.set_mode(AppMode::Browsing);
+            } else {
+                app.set_mode(AppMode::ViewingRestoreRequests);
+            }
         }
-        KeyCode::BackTab => {
-            app.previous_mask_field();
+        ActionId::Protect => {
+            if app.selected_bucket_name().is_none() {
+                app.push_status(t("status.select_bucket"));
+            } else {
+                app.protect_input = app
+                    .active_objects()
+                    .get(app.selected_object)
+                    .map(|obj| obj.key.clone())
+                    .unwrap_or_default();
+                app.set_mode(AppMode::ProtectingKeys);
+            }
         }
-        KeyCode::Backspace => {
-            if matches!(app.mask_field, MaskEditorField::Pattern) {
-                if app.mask_draft.cursor_pos > 0 {
-                    app.mask_draft.pattern.remove(app.mask_draft.cursor_pos - 1);
-                    app.mask_draft.cursor_pos -= 1;
+        ActionId::BucketTags => {
+            let Some(bucket) = app.buckets.get(app.selected_bucket).cloned() else {
+                app.push_status(t("status.select_bucket"));
+                return Ok(false);
+            };
+            match s3.bucket_tags(&bucket.name).await {
+                Ok(tags) => {
+                    app.tag_editor = Some(TagEditor::new(&bucket, tags));
+                    app.set_mode(AppMode::EditingBucketTags);
                 }
+                Err(err) => app.push_error(t("error.read_bucket_tags"), &err),
             }
         }
-        KeyCode::Delete => {
-            if matches!(app.mask_field, MaskEditorField::Pattern) {
-                if app.mask_draft.cursor_pos < app.mask_draft.pattern.len() {
-                    app.mask_draft.pattern.remove(app.mask_draft.cursor_pos);
-                }
+        ActionId::Reconcile => {
+            if let Err(err) = begin_reconcile(app, s3) {
+                app.push_error(t("error.cannot_reconcile"), &err);
             }
         }
-        KeyCode::Left => match app.mask_field {
-            MaskEditorField::Pattern => {
-                if app.mask_draft.cursor_pos > 0 {
-                    app.mask_draft.cursor_pos -= 1;
-                }
+        ActionId::CopyConfig => {
+            if let Err(err) = begin_config_copy(app, s3).await {
+                app.push_error(t("error.cannot_copy_config"), &err);
             }
-            MaskEditorField::Mode => app.cycle_mask_kind_backwards(),
-            MaskEditorField::Case => app.toggle_mask_case(),
-            MaskEditorField::StorageClass => {
-                if app.mask_draft.storage_class_cursor > 0 {
-                    app.mask_draft.storage_class_cursor -= 1;
-                }
-                let all_classes = StorageClassTier::all_for_filter();
-                app.mask_draft.storage_class_filter = all_classes
-                    .get(app.mask_draft.storage_class_cursor)
-                    .and_then(|(_, filter)| filter.clone());
+        }
+        ActionId::CreateBucket => {
+            let region = app
+                .selected_region
+                .as_deref()
+                .or(s3.region())
+                .unwrap_or(s3.partition().default_region());
+            app.bucket_draft = NewBucket::new(region);
+            app.bucket_field = BucketFormField::Name;
+            app.set_mode(AppMode::CreatingBucket);
+        }
+        ActionId::Decommission => {
+            if app.no_delete {
+                app.push_status(t("status.no_delete_decommission"));
+                return Ok(false);
+            }
+            let Some(bucket) = app.selected_bucket_name().map(str::to_string) else {
+                app.push_status(t("status.select_bucket"));
+                return Ok(false);
+            };
+            app.push_status(&tf("status.checking_decommission", &[("bucket", &bucket)]));
+            app.decommission = Some(inspect_decommission(app, s3, &bucket).await);
+            app.set_mode(AppMode::Decommissioning);
+        }
+        ActionId::AccessAudit => {
+            let Some(bucket) = app.selected_bucket_name().map(str::to_string) else {
+                app.push_status(t("status.select_bucket"));
+                return Ok(false);
+            };
+            audit_access(app, s3, &[bucket], true).await;
+            app.set_mode(AppMode::AuditingAccess);
+        }
+        ActionId::ExportLifecycle => {
+            if let Err(err) = begin_storage_selection(app, StorageIntent::ExportLifecycle) {
+                app.push_error(t("error.lifecycle_export_unavailable"), &err);
             }
+        }
+        ActionId::ExportReport => match export_report(app, history) {
+            Ok(path) => app.push_status(&tf("status.report_written", &[("path", &path.display())])),
+            Err(err) => app.push_error(t("error.report_export"), &err),
         },
-        KeyCode::Right => match app.mask_field {
-            MaskEditorField::Pattern => {
-                if app.mask_draft.cursor_pos < app.mask_draft.pattern.len() {
-                    app.mask_draft.cursor_pos += 1;
+        ActionId::QueryInventory | ActionId::QueryAthena => {
+            let athena = action == ActionId::QueryAthena;
+            if app.selected_bucket_name().is_none() {
+                app.push_status(t("status.select_inventory_bucket"));
+            } else if athena && app.athena.is_none() {
+                app.push_status(t("status.athena_table_needed"));
+            } else {
+                if athena != app.query_via_athena {
+                    app.query_input.clear();
                 }
+                app.query_via_athena = athena;
+                app.set_mode(AppMode::QueryingInventory);
             }
-            MaskEditorField::Mode => app.cycle_mask_kind(),
-            MaskEditorField::Case => app.toggle_mask_case(),
-            MaskEditorField::StorageClass => {
-                let all_classes = StorageClassTier::all_for_filter();
-                if app.mask_draft.storage_class_cursor + 1 < all_classes.len() {
-                    app.mask_draft.storage_class_cursor += 1;
+        }
+        ActionId::ToggleRow => {
+            if !app.is_selection() {
+                if let Some((key, picked)) = app.toggle_pick() {
+                    let count = app.picked_count();
+                    if picked {
+                        app.push_status(&tf("status.picked", &[("key", &key), ("count", &count)]));
+                    } else {
+                        app.push_status(&tf(
+                            "status.unpicked",
+                            &[("key", &key), ("count", &count)],
+                        ));
+                    }
+                    move_selection(app, 1);
                 }
-                app.mask_draft.storage_class_filter = all_classes
-                    .get(app.mask_draft.storage_class_cursor)
-                    .and_then(|(_, filter)| filter.clone());
-            }
-        },
-        KeyCode::Home => {
-            if matches!(app.mask_field, MaskEditorField::Pattern) {
-                app.mask_draft.cursor_pos = 0;
+            } else if let Some((key, excluded)) = app.toggle_exclusion() {
+                let count = target_count(app);
+                if excluded {
+                    app.push_status(&tf("status.excluded", &[("key", &key), ("count", &count)]));
+                } else {
+                    app.push_status(&tf("status.included", &[("key", &key), ("count", &count)]));
+                }
+                move_selection(app, 1);
             }
         }
-        KeyCode::End => {
-            if matches!(app.mask_field, MaskEditorField::Pattern) {
-                app.mask_draft.cursor_pos = app.mask_draft.pattern.len();
+        ActionId::PickAll => {
+            if app.is_selection() {
+                app.excluded.clear();
+                let count = target_count(app);
+                app.push_status(&tf("status.included_all", &[("count", &count)]));
+            } else {
+                let count = app.pick_all();
+                app.push_status(&tf("status.picked_all", &[("count", &count)]));
             }
         }
-        KeyCode::Char(' ') => match app.mask_field {
-            MaskEditorField::Mode => app.cycle_mask_kind(),
-            MaskEditorField::Case => app.toggle_mask_case(),
-            MaskEditorField::StorageClass => {
-                let all_classes = StorageClassTier::all_for_filter();
-                app.mask_draft.storage_class_cursor =
-                    (app.mask_draft.storage_class_cursor + 1) % all_classes.len();
-                app.mask_draft.storage_class_filter = all_classes
-                    .get(app.mask_draft.storage_class_cursor)
-                    .and_then(|(_, filter)| filter.clone());
+        ActionId::RestoreFilter => app.cycle_restore_filter(),
+        ActionId::RestoreSort => app.toggle_restore_sort(),
+        ActionId::ClearSelection => {
+            if app.active_query().is_some() {
+                app.query_set = None;
+                app.excluded.clear();
+                app.selected_object = 0;
+                app.push_status(t("status.inventory_cleared"));
+            } else if app.active_mask.is_some() {
+                app.apply_mask(None);
+                app.excluded.clear();
+            } else if app.restore_filter.is_some() || app.restore_sort {
+                app.restore_filter = None;
+                app.restore_sort = false;
+                app.excluded.clear();
+                app.refilter_keeping_selection();
+                app.push_status(t("status.restore_filter_sort_cleared"));
+            } else if app.has_picks() {
+                let count = app.picked_count();
+                app.clear_picks();
+                app.push_status(&tf("status.picks_cleared", &[("count", &count)]));
             }
-            MaskEditorField::Pattern => {
-                app.mask_draft
-                    .pattern
-                    .insert(app.mask_draft.cursor_pos, ' ');
-                app.mask_draft.cursor_pos += 1;
+        }
+    }
+
+    Ok(false)
+}
+
+/// Command palette: type to filter the registry's actions, Enter runs the
+/// highlighted one
+async fn handle_palette_keys(
+    key: KeyEvent,
+    app: &mut App,
+    s3: &S3Service,
+    history: &JobHistory,
+    uploads: &MultipartStore,
+) -> Result<bool> {
+    let matches = actions::palette_matches(&app.palette_input);
+    match key.code {
+        KeyCode::Esc => app.set_mode(AppMode::Browsing),
+        KeyCode::Enter => {
+            app.set_mode(AppMode::Browsing);
+            if let Some(action) = matches.get(app.palette_cursor) {
+                return run_action(action.id, app, s3, history, uploads).await;
             }
-        },
+        }
+        KeyCode::Up => app.palette_cursor = app.palette_cursor.saturating_sub(1),
+        KeyCode::Down if app.palette_cursor + 1 < matches.len() => app.palette_cursor += 1,
+        KeyCode::Backspace => {
+            app.palette_input.pop();
+            app.palette_cursor = 0;
+        }
         KeyCode::Char(ch) => {
-            if matches!(app.mask_field, MaskEditorField::Pattern) {
-                app.mask_draft.pattern.insert(app.mask_draft.cursor_pos, ch);
-                app.mask_draft.cursor_pos += 1;
-            }
+            app.palette_input.push(ch);
+            app.palette_cursor = 0;
         }
         _ => {}
     }
+    Ok(false)
 }
 
-fn handle_storage_class_selector(key: KeyEvent, app: &mut App) {
+fn handle_confirmation_keys(key: KeyEvent, app: &mut App) {
+    if edit_restore_days(key, app) {
+        return;
+    }
     match key.code {
-        KeyCode::Esc => {
+        KeyCode::Esc | KeyCode::Char('n') => {
+            app.pending_action = None;
+            app.job_note = None;
+            app.job_ticket = None;
+            if let Some(check) = app.ticket_check.take() {
+                check.abort();
+            }
+            clear_collisions(app);
             app.set_mode(AppMode::Browsing);
+            app.push_status(t("status.cancelled"));
         }
-        KeyCode::Up => {
-            if app.storage_class_cursor > 0 {
-                app.storage_class_cursor -= 1;
+        KeyCode::Enter | KeyCode::Char('y') | KeyCode::Char('b') => {
+            if !restore_days_valid(app) {
+                return;
+            }
+            if let Some(refusal) = approval_refusal(app).or_else(|| ticket_refusal(app)) {
+                app.push_status(&refusal);
+                return;
+            }
+            if review_collisions(app) {
+                return;
+            }
+            let background = key.code == KeyCode::Char('b');
+            if background
+                && !matches!(
+                    app.pending_action,
+                    Some(PendingAction::Transition { .. } | PendingAction::Restore { .. })
+                )
+            {
+                app.push_status(t("status.background_only"));
+                return;
+            }
+            notify_prefix_owners(app);
+            if let Some(window) = app.execution_window
+                && !window.is_open()
+            {
+                app.push_status(&tf(
+                    "status.outside_window_queued",
+                    &[("window", &window.label())],
+                ));
+                let opens = window.earliest_from(chrono::Utc::now());
+                schedule_pending_action(app, opens);
+                return;
+            }
+            // Batches too large for one job run as shards in the background
+            let background = background
+                || (matches!(
+                    app.pending_action,
+                    Some(PendingAction::Transition { .. } | PendingAction::Restore { .. })
+                ) && target_count(app) > app.shard_keys);
+            // The event loop runs the batch so it can keep drawing progress
+            if background {
+                app.queued_job = app.pending_action.take();
+            } else {
+                app.queued_batch = app.pending_action.take();
             }
+            app.set_mode(AppMode::Browsing);
         }
-        KeyCode::Down => {
-            if app.storage_class_cursor + 1 < StorageClassTier::selectable().len() {
-                app.storage_class_cursor += 1;
+        KeyCode::Char('v') => {
+            let objects = preview_objects(app);
+            if objects.is_empty() {
+                return;
             }
+            app.preview = Some(TargetPreview::new(
+                objects.iter().map(|(key, size)| (key.as_str(), *size)),
+            ));
+            app.set_mode(AppMode::PreviewingTargets);
         }
-        KeyCode::Enter => {
-            if let Some(selected) = StorageClassTier::selectable().get(app.storage_class_cursor) {
-                match app.storage_intent {
-                    StorageIntent::Transition => {
-                        // Check if objects need restore before transition
-                        if app.any_targets_need_restoration() {
-                            app.set_mode(AppMode::Browsing);
-                            let need_restore = app.count_objects_needing_restore();
-                            app.push_status(&format!(
-                                "⚠ {} objects require restore before transition. Press 'r' to restore them first.",
-                                need_restore
-                            ));
-                            return;
-                        }
-                        app.pending_action = Some(PendingAction::Transition {
-                            target_class: selected.clone(),
-                        });
-                        app.set_mode(AppMode::Confirming);
-                        app.push_status(&format!(
-                            "Confirm transition to {} (press Enter to confirm)",
-                            selected.label()
-                        ));
-                    }
+        KeyCode::Char('r') => {
+            let Some(PendingAction::Transition { target_class, .. }) = &app.pending_action else {
+                return;
+            };
+            let Some(bucket) = app.selected_bucket_name() else {
+                return;
+            };
+            let dry_run = DryRun::new(
+                bucket,
+                target_class.clone(),
+                app.candidate_objects()
+                    .into_iter()
+                    .map(|obj| (obj, app.is_protected(&obj.key))),
+            );
+            app.dry_run = Some(dry_run);
+            app.dry_run_scroll = 0;
+            app.set_mode(AppMode::ViewingDryRun);
+        }
+        KeyCode::Char('m') => {
+            if let Some(PendingAction::Copy { mode, .. }) = &mut app.pending_action {
+                *mode = mode.next();
+            }
+        }
+        KeyCode::Char('i') => {
+            if app.restore_presets_only
+                && matches!(
+                    app.pending_action,
+                    Some(PendingAction::Restore { .. } | PendingAction::RestoreVersions { .. })
+                )
+            {
+                app.push_status(t("status.restore_presets_only"));
+            } else if let Some(PendingAction::Restore { tier, preset, .. }) =
+                &mut app.pending_action
+            {
+                *tier = tier.next();
+                *preset = None;
+            } else if let Some(PendingAction::RestoreVersions { tier, .. }) =
+                &mut app.pending_action
+            {
+                *tier = tier.next();
+            }
+        }
+        KeyCode::Char('p') => cycle_restore_preset(app),
+        KeyCode::Char('l') => {
+            if let Some(PendingAction::RetrieveBundled { local, .. }) = &mut app.pending_action {
+                *local = !*local;
+            }
+        }
+        KeyCode::Char('w') => {
+            if let Some(PendingAction::Copy { max_bandwidth, .. }) = &mut app.pending_action {
+                *max_bandwidth = bandwidth::next_cap(*max_bandwidth);
+            }
+        }
+        KeyCode::Char('z') => {
+            if let Some(PendingAction::Bundle {
+                objects,
+                archive_key,
+                compression,
+                ..
+            }) = &mut app.pending_action
+            {
+                *compression = compression.next();
+                *archive_key =
+                    bundle::archive_key(objects.iter().map(|obj| obj.key.as_str()), *compression);
+            }
+        }
+        KeyCode::Char('d') => match &mut app.pending_action {
+            Some(PendingAction::Copy { delete_source, .. }) => {
+                if app.no_delete {
+                    app.push_status(t("status.no_delete_move"));
+                } else if app.transform.is_some() && !*delete_source {
+                    app.push_status(t("status.transform_no_move"));
+                } else {
+                    *delete_source = !*delete_source;
                 }
             }
+            Some(PendingAction::Bundle {
+                delete_originals, ..
+            }) => {
+                if app.no_delete {
+                    app.push_status(t("status.no_delete_bundle"));
+                } else {
+                    *delete_originals = !*delete_originals;
+                }
+            }
+            _ => {}
+        },
+        KeyCode::Char('t') => {
+            if !restore_days_valid(app) {
+                return;
+            }
+            if let Some(refusal) = approval_refusal(app).or_else(|| ticket_refusal(app)) {
+                app.push_status(&refusal);
+                return;
+            }
+            if review_collisions(app) {
+                return;
+            }
+            app.schedule_input.clear();
+            app.schedule_cursor = 0;
+            app.set_mode(AppMode::SchedulingAction);
         }
-        _ => {}
-    }
-}
-
-fn begin_storage_selection(app: &mut App, intent: StorageIntent) -> Result<()> {
-    match intent {
-        StorageIntent::Transition => {
-            if app.selected_bucket_name().is_none() {
-                anyhow::bail!("Select a bucket first");
+        KeyCode::Char('o') => {
+            if let Some(lock) = archive_lock(app) {
+                lock.cycle_mode();
             }
-            if target_count(app) == 0 {
-                anyhow::bail!("Select at least one object (mask or row)");
+        }
+        KeyCode::Char('u') if archive_lock(app).is_some() => {
+            app.retain_input.clear();
+            app.set_mode(AppMode::EnteringRetainUntil);
+        }
+        KeyCode::Char('a') => {
+            app.note_input = app.job_note.clone().unwrap_or_default();
+            app.set_mode(AppMode::EnteringJobNote);
+        }
+        KeyCode::Char('k') => {
+            app.ticket_input = app
+                .job_ticket
+                .as_ref()
+                .map(|ticket| ticket.id.clone())
+                .unwrap_or_default();
+            app.set_mode(AppMode::EnteringTicket);
+        }
+        KeyCode::Char('g') => {
+            if let Some(lock) = archive_lock(app) {
+                lock.legal_hold = !lock.legal_hold;
             }
         }
+        _ => {}
     }
-    app.storage_intent = intent;
-    app.storage_class_cursor = 0;
-    app.set_mode(AppMode::SelectingStorageClass);
-    Ok(())
 }
 
-fn initiate_restore_flow(app: &mut App) -> Result<()> {
-    if app.selected_bucket_name().is_none() || target_count(app) == 0 {
-        anyhow::bail!("Select objects to restore first");
+/// `+`/`-` and typed digits set the duration of a pending restore. Returns
+/// whether the key was one of them.
+fn edit_restore_days(key: KeyEvent, app: &mut App) -> bool {
+    let max = app.max_restore_days();
+    let days_key = matches!(
+        key.code,
+        KeyCode::Char('+' | '=' | '-' | '0'..='9') | KeyCode::Backspace
+    );
+    if days_key
+        && app.restore_presets_only
+        && matches!(
+            app.pending_action,
+            Some(PendingAction::Restore { .. } | PendingAction::RestoreVersions { .. })
+        )
+    {
+        app.push_status(t("status.restore_presets_only"));
+        return true;
     }
-
-    let need_restore = app.count_objects_needing_restore();
-    let already_restoring = app.count_objects_restoring();
-
-    if need_restore == 0 {
-        if already_restoring > 0 {
-            app.push_status(&format!(
-                "{} objects are already being restored",
-                already_restoring
-            ));
-        } else {
-            app.push_status("No objects need restore (not Glacier or already restored)");
+    let (days, preset) = match &mut app.pending_action {
+        Some(PendingAction::Restore { days, preset, .. }) => (days, Some(preset)),
+        Some(PendingAction::RestoreVersions { days, .. }) => (days, None),
+        _ => return false,
+    };
+    let input = &mut app.restore_days_input;
+    match key.code {
+        KeyCode::Char('+') | KeyCode::Char('=') => {
+            *days = (*days + 1).clamp(1, max);
+            input.clear();
         }
-        return Ok(());
+        KeyCode::Char('-') => {
+            *days = (*days - 1).clamp(1, max);
+            input.clear();
+        }
+        KeyCode::Char(digit @ '0'..='9') => {
+            if input.len() < 3 {
+                input.push(digit);
+            }
+            *days = input.parse().unwrap_or(*days);
+        }
+        KeyCode::Backspace => {
+            input.pop();
+            *days = input.parse().unwrap_or(*days);
+        }
+        _ => return false,
     }
-
-    app.pending_action = Some(PendingAction::Restore { days: 7 });
-    app.set_mode(AppMode::Confirming);
-
-    if already_restoring > 0 {
-        app.push_status(&format!(
-            "Will restore {} objects ({} already restoring will be skipped)",
-            need_restore, already_restoring
-        ));
-    } else {
-        app.push_status(&format!(
-            "Confirm restore request for {} objects",
-            need_restore
-        ));
+    if let Some(preset) = preset {
+        *preset = None;
     }
-    Ok(())
+    true
 }
 
-async fn execute_transition(
-    app: &mut App,
-    s3: &S3Service,
-    target_class: StorageClassTier,
-) -> Result<()> {
-    let bucket = app
-        .selected_bucket_name()
-        .context("Select a bucket before transitioning")?
-        .to_string();
-    let keys = target_keys(app);
-    if keys.is_empty() {
-        app.push_status("No objects selected for transition");
-        return Ok(());
+/// `p` in the restore dialog: take the days and tier of the next preset, or
+/// go back to choosing them by hand after the last one
+fn cycle_restore_preset(app: &mut App) {
+    let Some(PendingAction::Restore { days, tier, preset }) = &mut app.pending_action else {
+        return;
+    };
+    let next = app
+        .restore_presets
+        .cycle(preset.as_deref(), true, app.restore_presets_only)
+        .and_then(|name| app.restore_presets.get(name));
+    match next {
+        Some(next) => {
+            *days = next.days;
+            *tier = next.tier;
+            *preset = Some(next.name.clone());
+        }
+        None => *preset = None,
     }
+    app.restore_days_input.clear();
+}
 
-    // Initialize progress tracking
-    let total = keys.len();
-    app.progress = Some(crate::app::ProgressState::new(
-        format!("Transitioning to {}", target_class.label()),
-        total,
+/// Whether a pending restore's duration is one its targets accept; says
+/// which range is when it is not
+fn restore_days_valid(app: &mut App) -> bool {
+    let Some(PendingAction::Restore { days, .. } | PendingAction::RestoreVersions { days, .. }) =
+        &app.pending_action
+    else {
+        return true;
+    };
+    let max = app.max_restore_days();
+    if (1..=max).contains(days) {
+        return true;
+    }
+    app.push_status(&tf(
+        "status.restore_days_range",
+        &[("max", &max), ("days", &days)],
     ));
-    app.set_mode(AppMode::ShowingProgress);
-
-    let mut success_count = 0;
-    let mut error_count = 0;
-
-    for (index, key) in keys.iter().enumerate() {
-        // Update progress
-        if let Some(progress) = &mut app.progress {
-            progress.update(index + 1, Some(key.clone()));
-        }
+    false
+}
 
-        // Yield to allow UI updates
-        tokio::task::yield_now().await;
+/// The Object Lock settings of a pending transition into a Glacier class.
+/// Other actions have none, which the status bar explains.
+fn archive_lock(app: &mut App) -> Option<&mut ObjectLockPlan> {
+    let Some(PendingAction::Transition { target_class, .. }) = &app.pending_action else {
+        return None;
+    };
+    if !target_class.is_archival() {
+        app.push_status(t("status.retention_glacier_only"));
+        return None;
+    }
+    match &mut app.pending_action {
+        Some(PendingAction::Transition { lock, .. }) => Some(lock),
+        _ => None,
+    }
+}
 
-        match s3
-            .transition_storage_class(&bucket, key, target_class.clone())
-            .await
-        {
-            Ok(_) => {
-                success_count += 1;
-            }
-            Err(err) => {
-                error_count += 1;
-                app.push_status(&format!("Transition failed for {key}: {err:#}"));
+fn handle_retain_keys(key: KeyEvent, app: &mut App) {
+    match key.code {
+        KeyCode::Esc => app.set_mode(AppMode::Confirming),
+        KeyCode::Enter => {
+            let Some(retain_until) = parse_retain_until(&app.retain_input) else {
+                app.push_status(t("status.retain_until_hint"));
+                return;
+            };
+            if let Some(PendingAction::Transition { lock, .. }) = &mut app.pending_action {
+                let mode = lock
+                    .retention
+                    .as_ref()
+                    .map_or(RetentionMode::Governance, |retention| retention.mode);
+                lock.retention = Some(Retention { mode, retain_until });
             }
+            app.set_mode(AppMode::Confirming);
+        }
+        KeyCode::Backspace => {
+            app.retain_input.pop();
+        }
+        KeyCode::Char(ch) => {
+            app.retain_input.push(ch);
         }
+        _ => {}
     }
+}
+
+fn handle_job_note_keys(key: KeyEvent, app: &mut App) {
+    match key.code {
+        KeyCode::Esc => app.set_mode(AppMode::Confirming),
+        KeyCode::Enter => {
+            let note = app.note_input.trim();
+            app.job_note = (!note.is_empty()).then(|| note.to_string());
+            app.set_mode(AppMode::Confirming);
+        }
+        KeyCode::Backspace => {
+            app.note_input.pop();
+        }
+        KeyCode::Char(ch) => {
+            app.note_input.push(ch);
+        }
+        _ => {}
+    }
+}
+
+/// Enter checks the typed ID against `tickets.json`, and with a `check_url`
+/// looks it up in the tracker in the background. An empty ID removes the
+/// ticket.
+fn handle_ticket_keys(key: KeyEvent, app: &mut App) {
+    match key.code {
+        KeyCode::Esc => app.set_mode(AppMode::Confirming),
+        KeyCode::Enter => {
+            let id = app.ticket_input.trim().to_string();
+            if let Some(check) = app.ticket_check.take() {
+                check.abort();
+            }
+            app.job_ticket = None;
+            if id.is_empty() {
+                app.set_mode(AppMode::Confirming);
+                return;
+            }
+            let Some(config) = app.tickets.clone() else {
+                app.job_ticket = Some(TicketLink { id, url: None });
+                app.set_mode(AppMode::Confirming);
+                return;
+            };
+            if let Err(err) = config.check_format(&id) {
+                app.push_status(&format!("{err:#}"));
+                return;
+            }
+            if config.checks_tracker() {
+                app.push_status(&tf("status.ticket_lookup", &[("id", &id)]));
+                app.ticket_check = Some(tokio::spawn(async move { config.validate(&id).await }));
+            } else {
+                app.job_ticket = Some(config.link(&id));
+            }
+            app.set_mode(AppMode::Confirming);
+        }
+        KeyCode::Backspace => {
+            app.ticket_input.pop();
+        }
+        KeyCode::Char(ch) => {
+            app.ticket_input.push(ch);
+        }
+        _ => {}
+    }
+}
+
+fn handle_schedule_keys(key: KeyEvent, app: &mut App) {
+    match key.code {
+        KeyCode::Esc => app.set_mode(AppMode::Confirming),
+        KeyCode::Enter => {
+            let Some(run_at) = schedule::parse_run_at(&app.schedule_input) else {
+                app.push_status(t("status.schedule_time_hint"));
+                return;
+            };
+            schedule_pending_action(app, run_at);
+        }
+        KeyCode::Up => app.schedule_cursor = app.schedule_cursor.saturating_sub(1),
+        KeyCode::Down if app.schedule_cursor + 1 < app.schedule.actions().len() => {
+            app.schedule_cursor += 1;
+        }
+        KeyCode::Delete => {
+            if let Some(action) = app.schedule.actions().get(app.schedule_cursor).cloned() {
+                app.schedule.remove(&action.id);
+                app.schedule_cursor = app.schedule_cursor.saturating_sub(1);
+                app.push_status(&tf("status.unscheduled", &[("action", &action.describe())]));
+            }
+        }
+        KeyCode::Backspace => {
+            app.schedule_input.pop();
+        }
+        KeyCode::Char(ch) => app.schedule_input.push(ch),
+        _ => {}
+    }
+}
+
+async fn handle_create_bucket_keys(key: KeyEvent, app: &mut App, s3: &S3Service) {
+    let draft = &mut app.bucket_draft;
+    match key.code {
+        KeyCode::Esc => {
+            app.set_mode(AppMode::Browsing);
+            app.push_status(t("status.create_bucket_cancelled"));
+        }
+        KeyCode::Tab => app.bucket_field = app.bucket_field.next(),
+        KeyCode::BackTab => app.bucket_field = app.bucket_field.previous(),
+        KeyCode::Enter => {
+            if let Some(problem) = draft.name_problem() {
+                app.push_status(&tf("status.bucket_name_problem", &[("problem", &problem)]));
+                return;
+            }
+            let bucket = draft.clone();
+            app.push_status(&tf(
+                "status.creating_bucket",
+                &[("bucket", &bucket.name), ("region", &bucket.region)],
+            ));
+            match s3.create_bucket(&bucket).await {
+                Ok(()) => {
+                    app.set_mode(AppMode::Browsing);
+                    app.push_status(&tf("status.bucket_created", &[("bucket", &bucket.name)]));
+                    if let Err(err) = refresh_buckets(app, s3).await {
+                        app.push_error(t("error.bucket_refresh"), &err);
+                    } else if let Some(index) =
+                        app.buckets.iter().position(|b| b.name == bucket.name)
+                    {
+                        app.selected_bucket = index;
+                    }
+                }
+                Err(err) => app.push_error(t("error.create_bucket"), &err),
+            }
+        }
+        KeyCode::Backspace if app.bucket_field == BucketFormField::Name => {
+            draft.name.pop();
+        }
+        KeyCode::Char(ch) if app.bucket_field == BucketFormField::Name => draft.name.push(ch),
+        KeyCode::Left | KeyCode::Right | KeyCode::Char(' ') => match app.bucket_field {
+            BucketFormField::Name => {}
+            BucketFormField::Region => {
+                // Credentials only work within their own partition
+                let regions = Partition::of_region(&draft.region).regions();
+                let current = regions.iter().position(|r| *r == draft.region).unwrap_or(0);
+                let delta = if key.code == KeyCode::Left { -1 } else { 1 };
+                let index = (current as isize + delta).rem_euclid(regions.len() as isize);
+                draft.region = regions[index as usize].to_string();
+            }
+            BucketFormField::Versioning => draft.versioning = !draft.versioning,
+            BucketFormField::Encryption => draft.encryption = draft.encryption.next(),
+            BucketFormField::PublicAccess => {
+                draft.block_public_access = !draft.block_public_access;
+            }
+        },
+        _ => {}
+    }
+}
+
+async fn handle_tag_editor_keys(key: KeyEvent, app: &mut App, s3: &S3Service) {
+    let Some(editor) = &mut app.tag_editor else {
+        app.set_mode(AppMode::Browsing);
+        return;
+    };
+    match editor.step {
+        TagStep::Browse => match key.code {
+            KeyCode::Esc => {
+                app.tag_editor = None;
+                app.set_mode(AppMode::Browsing);
+            }
+            KeyCode::Up => editor.cursor = editor.cursor.saturating_sub(1),
+            KeyCode::Down if editor.cursor + 1 < editor.tags.len() => editor.cursor += 1,
+            KeyCode::Char('a') => {
+                editor.input.clear();
+                editor.editing = None;
+                editor.step = TagStep::Edit;
+            }
+            KeyCode::Enter => {
+                if let Some((key, value)) = editor.selected().cloned() {
+                    editor.input = format!("{key}={value}");
+                    editor.editing = Some(key);
+                    editor.step = TagStep::Edit;
+                }
+            }
+            KeyCode::Delete if editor.selected().is_some() => {
+                let mut tags = editor.tags.clone();
+                let (key, _) = tags.remove(editor.cursor);
+                write_bucket_tags(app, s3, tags, &tf("status.tag_removed", &[("key", &key)])).await;
+            }
+            KeyCode::Char('M') => {
+                editor.standard_field = 0;
+                editor.step = TagStep::Standard;
+            }
+            _ => {}
+        },
+        TagStep::Edit => match key.code {
+            KeyCode::Esc => editor.step = TagStep::Browse,
+            KeyCode::Backspace => {
+                editor.input.pop();
+            }
+            KeyCode::Char(ch) => editor.input.push(ch),
+            KeyCode::Enter => match editor.parse_input() {
+                Ok(tag) => {
+                    let mut tags = editor.tags.clone();
+                    if let Some(old) = editor.editing.take() {
+                        tags.retain(|(key, _)| *key != old);
+                    }
+                    let message = tf("status.tag_set", &[("key", &tag.0), ("value", &tag.1)]);
+                    tags::merge_tags(&mut tags, &[tag]);
+                    editor.step = TagStep::Browse;
+                    write_bucket_tags(app, s3, tags, &message).await;
+                }
+                Err(problem) => {
+                    app.push_status(&tf("status.tag_invalid", &[("problem", &problem)]))
+                }
+            },
+            _ => {}
+        },
+        TagStep::Standard => match key.code {
+            KeyCode::Esc => editor.step = TagStep::Browse,
+            KeyCode::Tab | KeyCode::Down => {
+                editor.standard_field = (editor.standard_field + 1) % editor.standard.len();
+            }
+            KeyCode::BackTab | KeyCode::Up => {
+                editor.standard_field =
+                    (editor.standard_field + editor.standard.len() - 1) % editor.standard.len();
+            }
+            KeyCode::Backspace => {
+                editor.standard[editor.standard_field].pop();
+            }
+            KeyCode::Char(ch) => editor.standard[editor.standard_field].push(ch),
+            KeyCode::Enter => apply_migration_tags(app, s3).await,
+            _ => {}
+        },
+    }
+}
+
+/// Replace the edited bucket's tags with `tags`, keeping the editor in step
+/// with what S3 holds
+async fn write_bucket_tags(
+    app: &mut App,
+    s3: &S3Service,
+    tags: Vec<(String, String)>,
+    message: &str,
+) {
+    let Some(editor) = &mut app.tag_editor else {
+        return;
+    };
+    match s3.put_bucket_tags(&editor.bucket, &tags).await {
+        Ok(()) => {
+            editor.tags = tags;
+            editor.cursor = editor.cursor.min(editor.tags.len().saturating_sub(1));
+            app.push_status(message);
+        }
+        Err(err) => app.push_error(t("error.write_bucket_tags"), &err),
+    }
+}
+
+/// Merge the migration tag set into the tags of every bucket matching the
+/// editor's pattern
+async fn apply_migration_tags(app: &mut App, s3: &S3Service) {
+    let Some(editor) = &mut app.tag_editor else {
+        return;
+    };
+    let new_tags = match editor.standard_tags() {
+        Ok(tags) => tags,
+        Err(problem) => {
+            app.push_status(&tf(
+                "status.migration_tags_invalid",
+                &[("problem", &problem)],
+            ));
+            return;
+        }
+    };
+    let pattern = editor.standard_pattern().to_string();
+    let buckets: Vec<String> = app
+        .all_buckets
+        .iter()
+        .map(|b| b.name.clone())
+        .filter(|name| tags::bucket_matches(&pattern, name))
+        .collect();
+    if buckets.is_empty() {
+        app.push_status(&tf("status.no_bucket_matches", &[("pattern", &pattern)]));
+        return;
+    }
+    editor.step = TagStep::Browse;
+
+    let mut tagged = 0;
+    for bucket in &buckets {
+        let result = async {
+            let mut tags = s3.bucket_tags(bucket).await?;
+            tags::merge_tags(&mut tags, &new_tags);
+            s3.put_bucket_tags(bucket, &tags).await?;
+            anyhow::Ok(tags)
+        }
+        .await;
+        match result {
+            Ok(tags) => {
+                tagged += 1;
+                if let Some(editor) = &mut app.tag_editor
+                    && editor.bucket == *bucket
+                {
+                    editor.tags = tags;
+                }
+            }
+            Err(err) => app.push_error(&tf("error.tagging_bucket", &[("bucket", &bucket)]), &err),
+        }
+    }
+    app.push_status(&tf(
+        "status.migration_tags_applied",
+        &[("tagged", &tagged), ("count", &buckets.len())],
+    ));
+}
+
+/// Read both split view buckets' configuration for review, copying from the
+/// left bucket to the right one
+async fn begin_config_copy(app: &mut App, s3: &S3Service) -> Result<()> {
+    let dest = app
+        .split
+        .as_ref()
+        .context("Open split view with 'v' first")?
+        .bucket
+        .clone();
+    let source = app
+        .selected_bucket_name()
+        .context("Select a bucket first")?
+        .to_string();
+    if source == dest {
+        anyhow::bail!("both panes show {source}; select a different bucket on the left");
+    }
+    app.push_status(&tf(
+        "status.reading_config",
+        &[("source", &source), ("dest", &dest)],
+    ));
+    let source_config = s3
+        .bucket_config(&source)
+        .await
+        .with_context(|| format!("bucket {source}"))?;
+    let dest_config = s3
+        .bucket_config(&dest)
+        .await
+        .with_context(|| format!("bucket {dest}"))?;
+    audit_access(app, s3, &[source.clone(), dest.clone()], true).await;
+    app.config_copy = Some(ConfigCopy::new(source, dest, source_config, dest_config));
+    app.set_mode(AppMode::CopyingBucketConfig);
+    Ok(())
+}
+
+async fn handle_config_copy_keys(key: KeyEvent, app: &mut App, s3: &S3Service) {
+    let Some(copy) = &mut app.config_copy else {
+        app.set_mode(AppMode::Browsing);
+        return;
+    };
+    match key.code {
+        KeyCode::Esc => {
+            app.config_copy = None;
+            app.set_mode(AppMode::Browsing);
+        }
+        KeyCode::Up => copy.cursor = copy.cursor.saturating_sub(1),
+        KeyCode::Down if copy.cursor + 1 < ConfigPart::ALL.len() => copy.cursor += 1,
+        KeyCode::Char(' ') => copy.toggle(),
+        KeyCode::Char('s') => {
+            let swapped = ConfigCopy::new(
+                copy.dest.clone(),
+                copy.source.clone(),
+                copy.dest_config.clone(),
+                copy.source_config.clone(),
+            );
+            *copy = swapped;
+        }
+        KeyCode::Enter => {
+            let Some(copy) = app.config_copy.take() else {
+                return;
+            };
+            app.set_mode(AppMode::Browsing);
+            if copy.selected.is_empty() {
+                app.push_status(t("status.no_config_selected"));
+                return;
+            }
+            let mut copied = Vec::new();
+            for part in ConfigPart::ALL
+                .into_iter()
+                .filter(|part| copy.is_selected(*part))
+            {
+                match s3
+                    .apply_bucket_config(&copy.dest, &copy.source_config, part)
+                    .await
+                {
+                    Ok(()) => copied.push(t(part.label_key())),
+                    Err(err) => app.push_error(
+                        &tf(
+                            "error.copy_config_part",
+                            &[("part", &t(part.label_key())), ("dest", &copy.dest)],
+                        ),
+                        &err,
+                    ),
+                }
+            }
+            if !copied.is_empty() {
+                app.push_status(&tf(
+                    "status.config_copied",
+                    &[
+                        ("parts", &copied.join(", ")),
+                        ("source", &copy.source),
+                        ("dest", &copy.dest),
+                    ],
+                ));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Audit the access of `buckets`, keeping earlier audits unless `again`
+async fn audit_access(app: &mut App, s3: &S3Service, buckets: &[String], again: bool) {
+    for bucket in buckets {
+        if again || !app.access_audits.contains_key(bucket) {
+            app.push_status(&tf("status.checking_access", &[("bucket", &bucket)]));
+            let audit = AccessAudit::inspect(s3, bucket).await;
+            app.access_audits.insert(bucket.clone(), audit);
+        }
+    }
+}
+
+async fn handle_access_audit_keys(key: KeyEvent, app: &mut App, s3: &S3Service) {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('X') => app.set_mode(AppMode::Browsing),
+        KeyCode::Char('r') => {
+            if let Some(bucket) = app.selected_bucket_name().map(str::to_string) {
+                audit_access(app, s3, &[bucket], true).await;
+            }
+        }
+        _ => {}
+    }
+}
+
+async fn inspect_decommission(app: &App, s3: &S3Service, bucket: &str) -> Decommission {
+    let others: Vec<String> = app.all_buckets.iter().map(|b| b.name.clone()).collect();
+    Decommission::inspect(s3, bucket, &others).await
+}
+
+async fn handle_decommission_keys(key: KeyEvent, app: &mut App, s3: &S3Service) {
+    let Some(decommission) = &mut app.decommission else {
+        app.set_mode(AppMode::Browsing);
+        return;
+    };
+    let bucket = decommission.bucket.clone();
+    match decommission.step {
+        DecommissionStep::Review => match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                app.decommission = None;
+                app.set_mode(AppMode::Browsing);
+            }
+            KeyCode::Char('r') => {
+                app.decommission = Some(inspect_decommission(app, s3, &bucket).await);
+            }
+            KeyCode::Char('l') if decommission.lifecycle_rules > 0 => {
+                decommission.step = DecommissionStep::ConfirmLifecycleDelete;
+            }
+            KeyCode::Char('D') => {
+                if decommission.ready() {
+                    decommission.confirm_input.clear();
+                    decommission.step = DecommissionStep::ConfirmBucketDelete;
+                } else {
+                    app.push_status(t("status.resolve_checks"));
+                }
+            }
+            _ => {}
+        },
+        DecommissionStep::ConfirmLifecycleDelete => match key.code {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                match s3.delete_lifecycle(&bucket).await {
+                    Ok(()) => {
+                        app.push_status(&tf("status.lifecycle_deleted", &[("bucket", &bucket)]))
+                    }
+                    Err(err) => app.push_error(t("error.lifecycle_delete"), &err),
+                }
+                app.decommission = Some(inspect_decommission(app, s3, &bucket).await);
+            }
+            KeyCode::Esc | KeyCode::Char('n') => decommission.step = DecommissionStep::Review,
+            _ => {}
+        },
+        DecommissionStep::ConfirmBucketDelete => match key.code {
+            KeyCode::Esc => decommission.step = DecommissionStep::Review,
+            KeyCode::Backspace => {
+                decommission.confirm_input.pop();
+            }
+            KeyCode::Char(ch) => decommission.confirm_input.push(ch),
+            KeyCode::Enter if decommission.confirm_input != bucket => {
+                app.push_status(t("status.type_bucket_name"));
+            }
+            KeyCode::Enter => match s3.delete_bucket(&bucket).await {
+                Ok(()) => {
+                    app.push_status(&tf("status.bucket_deleted", &[("bucket", &bucket)]));
+                    app.decommission = None;
+                    app.set_mode(AppMode::Browsing);
+                    if let Err(err) = refresh_buckets(app, s3).await {
+                        app.push_error(t("error.bucket_refresh"), &err);
+                    }
+                }
+                Err(err) => {
+                    decommission.step = DecommissionStep::Review;
+                    app.push_error(t("error.bucket_delete"), &err);
+                }
+            },
+            _ => {}
+        },
+    }
+}
+
+/// Hold the confirmed action back until `run_at`, recording its objects as
+/// they are now for the drift check before it runs
+fn schedule_pending_action(app: &mut App, run_at: chrono::DateTime<chrono::Utc>) {
+    let Some(action) = app.pending_action.take() else {
+        app.set_mode(AppMode::Browsing);
+        return;
+    };
+    app.set_mode(AppMode::Browsing);
+    let selected = app.selected_bucket_name().unwrap_or_default().to_string();
+    let (bucket, kind, objects): (String, ScheduledKind, Vec<ObjectInfo>) = match action {
+        PendingAction::Transition { target_class, lock } => {
+            let objects = app
+                .target_objects()
+                .into_iter()
+                .filter(|obj| obj.storage_class != target_class)
+                .cloned()
+                .collect();
+            (
+                selected,
+                ScheduledKind::Transition { target_class, lock },
+                objects,
+            )
+        }
+        PendingAction::Restore { days, tier, preset } => {
+            let objects = app.target_objects().into_iter().cloned().collect();
+            (
+                selected,
+                ScheduledKind::Restore { days, tier, preset },
+                objects,
+            )
+        }
+        PendingAction::Copy {
+            source_bucket,
+            dest_bucket,
+            keys,
+            mode,
+            delete_source,
+            max_bandwidth,
+            rewrite,
+            storage_class,
+        } => {
+            let split_objects = app.split.iter().flat_map(|split| &split.objects);
+            let loaded: std::collections::HashMap<&str, &ObjectInfo> = app
+                .objects
+                .iter()
+                .chain(split_objects)
+                .map(|obj| (obj.key.as_str(), obj))
+                .collect();
+            let objects = keys
+                .iter()
+                .filter_map(|key| loaded.get(key.as_str()).map(|obj| (*obj).clone()))
+                .collect();
+            (
+                source_bucket,
+                ScheduledKind::Copy {
+                    dest_bucket,
+                    mode,
+                    delete_source,
+                    max_bandwidth,
+                    rewrite,
+                    storage_class,
+                },
+                objects,
+            )
+        }
+        PendingAction::RestoreWave { .. } | PendingAction::RunPolicy { .. } => return,
+        PendingAction::Reencrypt { .. } => {
+            app.push_status(t("status.schedule_reencrypt"));
+            return;
+        }
+        PendingAction::DeleteVersions { .. }
+        | PendingAction::TransitionVersions { .. }
+        | PendingAction::RestoreVersions { .. } => {
+            app.push_status(t("status.schedule_versions"));
+            return;
+        }
+        PendingAction::Bundle { .. } => {
+            app.push_status(t("status.schedule_bundle"));
+            return;
+        }
+        PendingAction::RetrieveBundled { .. } => {
+            app.push_status(t("status.schedule_retrieve"));
+            return;
+        }
+    };
+    if bucket.is_empty() || objects.is_empty() {
+        app.push_status(t("status.nothing_to_schedule"));
+        return;
+    }
+    if let Some(window) = app.execution_window
+        && window.earliest_from(run_at) > run_at
+    {
+        app.push_status(&tf(
+            "status.schedule_outside_window",
+            &[("window", &window.label())],
+        ));
+    }
+    let (note, ticket) = (app.job_note.take(), app.job_ticket.take());
+    let id = app
+        .schedule
+        .add(&bucket, kind, objects, run_at, note, ticket);
+    if let Some(action) = app.schedule.get(&id) {
+        app.push_status(&tf(
+            "status.scheduled",
+            &[
+                (
+                    "time",
+                    &run_at
+                        .with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M"),
+                ),
+                ("action", &action.describe()),
+            ],
+        ));
+    }
+}
+
+/// HeadObject requests in flight during a scheduled action's drift check
+const DRIFT_CHECK_CONCURRENCY: usize = 10;
+
+/// Check a due scheduled action's objects against S3 and turn it into a batch
+/// over the ones that have not changed since it was scheduled
+async fn prepare_scheduled_action(
+    app: &mut App,
+    s3: &S3Service,
+    scheduled: ScheduledAction,
+) -> Option<PendingAction> {
+    app.push_status(&tf(
+        "status.running_scheduled",
+        &[("action", &scheduled.describe())],
+    ));
+    let current: Vec<Result<ObjectInfo>> = futures::stream::iter(&scheduled.objects)
+        .map(|obj| s3.refresh_object(&scheduled.bucket, &obj.key))
+        .buffer_unordered(DRIFT_CHECK_CONCURRENCY)
+        .collect()
+        .await;
+    let mut missing = 0;
+    let mut changed = 0;
+    let mut unchanged = Vec::new();
+    for result in current {
+        match result {
+            Ok(obj) if scheduled.drifted(&obj) => changed += 1,
+            Ok(obj) => unchanged.push(obj),
+            Err(_) => missing += 1,
+        }
+    }
+    if missing + changed > 0 {
+        app.push_status(&tf(
+            "status.drift_check",
+            &[("missing", &missing), ("changed", &changed)],
+        ));
+    }
+    if unchanged.is_empty() {
+        app.push_status(t("status.scheduled_skipped_drift"));
+        return None;
+    }
+    let Some(index) = app.buckets.iter().position(|b| b.name == scheduled.bucket) else {
+        app.push_status(&tf(
+            "status.scheduled_skipped_region",
+            &[("bucket", &scheduled.bucket)],
+        ));
+        return None;
+    };
+
+    // Run against exactly the checked objects, the way a query result is run
+    app.selected_bucket = index;
+    load_objects_for_selection(app, s3);
+    let keys = unchanged.iter().map(|obj| obj.key.clone()).collect();
+    let label = format!("scheduled {}", short_timestamp(&scheduled.run_at));
+    show_query_set(app, scheduled.bucket.clone(), label, unchanged);
+    app.job_note = scheduled.note.clone();
+    app.job_ticket = scheduled.ticket.clone();
+    Some(match scheduled.kind {
+        ScheduledKind::Transition { target_class, lock } => {
+            PendingAction::Transition { target_class, lock }
+        }
+        ScheduledKind::Restore { days, tier, preset } => {
+            PendingAction::Restore { days, tier, preset }
+        }
+        ScheduledKind::Copy {
+            dest_bucket,
+            mode,
+            delete_source,
+            max_bandwidth,
+            rewrite,
+            storage_class,
+        } => PendingAction::Copy {
+            source_bucket: scheduled.bucket,
+            dest_bucket,
+            keys,
+            mode,
+            delete_source,
+            max_bandwidth,
+            rewrite,
+            storage_class,
+        },
+    })
+}
+
+fn handle_preview_keys(key: KeyEvent, app: &mut App) {
+    if app.owner_input.is_some() {
+        return handle_owner_input_keys(key, app);
+    }
+    if key.code == KeyCode::Char('o') {
+        return edit_prefix_owner(app);
+    }
+    let Some(preview) = &mut app.preview else {
+        app.set_mode(AppMode::Confirming);
+        return;
+    };
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('v') | KeyCode::Char('q') => {
+            app.preview = None;
+            app.set_mode(AppMode::Confirming);
+        }
+        KeyCode::Up | KeyCode::Char('k') => preview.move_cursor(-1),
+        KeyCode::Down | KeyCode::Char('j') => preview.move_cursor(1),
+        KeyCode::PageUp => preview.move_cursor(-10),
+        KeyCode::PageDown => preview.move_cursor(10),
+        KeyCode::Enter | KeyCode::Right | KeyCode::Char(' ') => preview.toggle(),
+        KeyCode::Left => preview.collapse(),
+        _ => {}
+    }
+}
+
+/// Group of the prefix preview under the cursor, with the pending action's bucket
+fn preview_group(app: &App) -> Option<(String, String)> {
+    let preview = app.preview.as_ref()?;
+    let row = preview.rows().into_iter().nth(preview.cursor)?;
+    if !matches!(row.kind, PreviewRowKind::Group { .. }) {
+        return None;
+    }
+    Some((app.pending_bucket()?.to_string(), row.path))
+}
+
+/// Start typing the owner of the group under the cursor, starting from the
+/// one recorded for exactly that bucket and prefix
+fn edit_prefix_owner(app: &mut App) {
+    let Some((bucket, prefix)) = preview_group(app) else {
+        app.push_status(t("status.owners_on_groups"));
+        return;
+    };
+    let input = app
+        .owners
+        .owners()
+        .iter()
+        .find(|owner| owner.bucket.as_deref() == Some(bucket.as_str()) && owner.prefix == prefix)
+        .map(|owner| match &owner.contact {
+            Some(contact) => format!("{}, {contact}", owner.owner),
+            None => owner.owner.clone(),
+        })
+        .unwrap_or_default();
+    app.owner_input = Some(input);
+}
+
+/// "Owner, contact" for the group under the cursor; an empty owner removes it
+fn handle_owner_input_keys(key: KeyEvent, app: &mut App) {
+    let Some(input) = &mut app.owner_input else {
+        return;
+    };
+    match key.code {
+        KeyCode::Esc => app.owner_input = None,
+        KeyCode::Backspace => {
+            input.pop();
+        }
+        KeyCode::Char(ch) => input.push(ch),
+        KeyCode::Enter => {
+            let input = app.owner_input.take().unwrap_or_default();
+            let Some((bucket, prefix)) = preview_group(app) else {
+                return;
+            };
+            let (owner, contact) = match input.split_once(',') {
+                Some((owner, contact)) => (owner.trim(), contact.trim()),
+                None => (input.trim(), ""),
+            };
+            if owner.is_empty() {
+                match app.owners.remove(&bucket, &prefix) {
+                    Ok(Some(_)) => {
+                        app.push_status(&tf("status.owner_removed", &[("prefix", &prefix)]))
+                    }
+                    Ok(None) => {}
+                    Err(err) => app.push_error(t("error.save_owners"), &err),
+                }
+                return;
+            }
+            let entry = PrefixOwner {
+                bucket: Some(bucket),
+                prefix: prefix.clone(),
+                owner: owner.to_string(),
+                contact: (!contact.is_empty()).then(|| contact.to_string()),
+                webhook_url: None,
+            };
+            let label = entry.label();
+            match app.owners.set(entry) {
+                Ok(()) => app.push_status(&tf(
+                    "status.owner_set",
+                    &[("prefix", &prefix), ("label", &label)],
+                )),
+                Err(err) => app.push_error(t("error.save_owners"), &err),
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Sign-off of the keys the pending action would migrate, when a sign-off was
+/// imported. Restores and retrievals only read data back and are not checked.
+pub(crate) fn approval_tally(app: &App) -> Option<ApprovalTally> {
+    if app.approvals.is_empty()
+        || !matches!(
+            app.pending_action,
+            Some(
+                PendingAction::Transition { .. }
+                    | PendingAction::Copy { .. }
+                    | PendingAction::Reencrypt { .. }
+                    | PendingAction::Bundle { .. }
+                    | PendingAction::DeleteVersions { .. }
+                    | PendingAction::TransitionVersions { .. }
+            )
+        )
+    {
+        return None;
+    }
+    let bucket = app.pending_bucket()?;
+    let objects = preview_objects(app);
+    Some(
+        app.approvals
+            .tally(bucket, objects.iter().map(|(key, _)| key.as_str())),
+    )
+}
+
+/// Why the pending action may not run: it touches a blocked prefix, or with
+/// `--require-approval` any prefix that is not approved
+fn approval_refusal(app: &App) -> Option<String> {
+    let reason = approval_tally(app)?.refusal(app.require_approval)?;
+    Some(tf("status.refused", &[("reason", &reason)]))
+}
+
+/// Why the pending action cannot run for want of a change ticket, if it
+/// cannot
+fn ticket_refusal(app: &App) -> Option<String> {
+    if app.ticket_check.is_some() {
+        return Some(t("status.ticket_lookup_running").to_string());
+    }
+    let required = app.tickets.as_ref().is_some_and(|tickets| tickets.required);
+    (required && app.job_ticket.is_none()).then(|| t("status.ticket_required").to_string())
+}
+
+/// Open the review of the pending copy's collisions while any are
+/// undecided. Returns whether the action has to wait for them.
+fn review_collisions(app: &mut App) -> bool {
+    if app.collision_check.is_some() {
+        app.push_status(t("status.collision_check_running"));
+        return true;
+    }
+    if app.collisions.is_some() || app.collision_error.is_some() {
+        app.set_mode(AppMode::ResolvingCollisions);
+        return true;
+    }
+    false
+}
+
+/// Forget the pending copy's collision lookup and findings
+fn clear_collisions(app: &mut App) {
+    if let Some(check) = app.collision_check.take() {
+        check.abort();
+    }
+    app.collisions = None;
+    app.collision_error = None;
+}
+
+/// Post to the webhooks of the owners whose prefixes the confirmed action
+/// touches
+fn notify_prefix_owners(app: &App) {
+    let (Some(action), Some(bucket)) = (&app.pending_action, app.pending_bucket()) else {
+        return;
+    };
+    let summary = match action {
+        PendingAction::Transition { target_class, .. } => {
+            format!("Transition to {}", target_class.label())
+        }
+        PendingAction::Restore { .. } => "Restore".to_string(),
+        PendingAction::Copy {
+            dest_bucket,
+            delete_source,
+            ..
+        } => {
+            let verb = if *delete_source { "Move" } else { "Copy" };
+            format!("{verb} to {dest_bucket}")
+        }
+        PendingAction::Reencrypt { .. } => "Re-encryption".to_string(),
+        PendingAction::DeleteVersions { .. } => "Deletion of noncurrent versions".to_string(),
+        PendingAction::TransitionVersions { target_class, .. } => {
+            format!("Move of noncurrent versions to {}", target_class.label())
+        }
+        PendingAction::RestoreVersions { .. } => "Restore of noncurrent versions".to_string(),
+        PendingAction::Bundle { archive_key, .. } => format!("Bundling into {archive_key}"),
+        PendingAction::RetrieveBundled { .. } => "Retrieval from bundles".to_string(),
+        PendingAction::RestoreWave { .. } | PendingAction::RunPolicy { .. } => return,
+    };
+    let objects = preview_objects(app);
+    let owners = app.owners.owners_of(
+        bucket,
+        objects.iter().map(|(key, size)| (key.as_str(), *size)),
+    );
+    for (owner, count, bytes) in owners {
+        notify::prefix_owner(owner, bucket, &summary, count, bytes);
+    }
+}
+
+fn handle_dry_run_keys(key: KeyEvent, app: &mut App) {
+    let Some(dry_run) = &app.dry_run else {
+        app.set_mode(AppMode::Confirming);
+        return;
+    };
+    let skipped = dry_run.skipped().count();
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('r') | KeyCode::Char('q') => {
+            app.dry_run = None;
+            app.set_mode(AppMode::Confirming);
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.dry_run_scroll = app.dry_run_scroll.saturating_sub(1);
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.dry_run_scroll = (app.dry_run_scroll + 1).min(skipped.saturating_sub(1));
+        }
+        KeyCode::PageUp => {
+            app.dry_run_scroll = app.dry_run_scroll.saturating_sub(RECONCILE_PAGE);
+        }
+        KeyCode::PageDown => {
+            app.dry_run_scroll =
+                (app.dry_run_scroll + RECONCILE_PAGE).min(skipped.saturating_sub(1));
+        }
+        KeyCode::Char('e') => match dry_run.export() {
+            Ok(path) => {
+                app.push_status(&tf("status.dry_run_exported", &[("path", &path.display())]))
+            }
+            Err(err) => app.push_error(t("error.dry_run_export"), &err),
+        },
+        _ => {}
+    }
+}
+
+/// Keys and sizes the pending action would touch, for the prefix preview
+pub(crate) fn preview_objects(app: &App) -> Vec<(String, i64)> {
+    match &app.pending_action {
+        Some(PendingAction::Transition { target_class, .. }) => app
+            .target_objects()
+            .into_iter()
+            .filter(|obj| &obj.storage_class != target_class)
+            .map(|obj| (obj.key.clone(), obj.size))
+            .collect(),
+        Some(PendingAction::Restore { .. }) => app
+            .target_objects()
+            .into_iter()
+            .map(|obj| (obj.key.clone(), obj.size))
+            .collect(),
+        Some(PendingAction::Bundle { objects, .. }) => objects
+            .iter()
+            .map(|obj| (obj.key.clone(), obj.size))
+            .collect(),
+        Some(PendingAction::RetrieveBundled { members, .. }) => members
+            .iter()
+            .map(|(_, entry)| (entry.key.clone(), entry.size))
+            .collect(),
+        Some(PendingAction::Copy { keys, .. }) => {
+            let split_objects = app.split.iter().flat_map(|split| &split.objects);
+            let sizes: std::collections::HashMap<&str, i64> = app
+                .objects
+                .iter()
+                .chain(split_objects)
+                .map(|obj| (obj.key.as_str(), obj.size))
+                .collect();
+            keys.iter()
+                .map(|key| (key.clone(), sizes.get(key.as_str()).copied().unwrap_or(0)))
+                .collect()
+        }
+        Some(PendingAction::Reencrypt { .. }) => app
+            .target_objects()
+            .into_iter()
+            .filter(|obj| readable_in_place(obj))
+            .map(|obj| (obj.key.clone(), obj.size))
+            .collect(),
+        Some(
+            PendingAction::DeleteVersions { versions }
+            | PendingAction::RestoreVersions { versions, .. },
+        ) => versions
+            .iter()
+            .map(|obj| (obj.key.clone(), obj.size))
+            .collect(),
+        Some(PendingAction::TransitionVersions { replays, .. }) => replays
+            .iter()
+            .map(|replay| (replay.key.clone(), replay.moved_bytes()))
+            .collect(),
+        Some(PendingAction::RestoreWave { .. } | PendingAction::RunPolicy { .. }) | None => {
+            Vec::new()
+        }
+    }
+}
+
+async fn handle_resume_uploads_keys(
+    key: KeyEvent,
+    app: &mut App,
+    s3: &S3Service,
+    uploads: &mut MultipartStore,
+) {
+    let count = uploads.uploads().len();
+    match key.code {
+        KeyCode::Esc => app.set_mode(AppMode::Browsing),
+        KeyCode::Up => app.upload_cursor = app.upload_cursor.saturating_sub(1),
+        KeyCode::Down if app.upload_cursor + 1 < count => app.upload_cursor += 1,
+        KeyCode::Char('r') | KeyCode::Enter => {
+            let Some(upload) = uploads.uploads().get(app.upload_cursor).cloned() else {
+                return;
+            };
+            let remaining = upload.remaining_parts().len();
+            app.push_status(&tf(
+                "status.resuming_upload",
+                &[
+                    ("key", &upload.dest_key),
+                    ("remaining", &remaining),
+                    ("parts", &upload.part_count()),
+                ],
+            ));
+            let dest = format!("{}/{}", upload.dest_bucket, upload.dest_key);
+            match multipart::resume_copy(s3, uploads, upload, &Cell::new(None)).await {
+                Ok(_) => app.push_status(&tf("status.upload_completed", &[("dest", &dest)])),
+                Err(err) => app.push_error(&tf("error.resume_upload", &[("dest", &dest)]), &err),
+            }
+        }
+        KeyCode::Char('x') => {
+            let Some(upload) = uploads.uploads().get(app.upload_cursor).cloned() else {
+                return;
+            };
+            let dest = format!("{}/{}", upload.dest_bucket, upload.dest_key);
+            match s3.abort_multipart_copy(&upload).await {
+                Ok(()) => {
+                    uploads.remove(&upload.upload_id);
+                    app.push_status(&tf("status.upload_aborted", &[("dest", &dest)]));
+                }
+                Err(err) => app.push_error(&tf("error.abort_upload", &[("dest", &dest)]), &err),
+            }
+        }
+        _ => {}
+    }
+    if uploads.uploads().is_empty() {
+        app.set_mode(AppMode::Browsing);
+    } else {
+        app.upload_cursor = app.upload_cursor.min(uploads.uploads().len() - 1);
+    }
+}
+
+fn handle_policy_keys(key: KeyEvent, app: &mut App) {
+    let count = app.policies.policies().len();
+    let pane = &mut app.policy_pane;
+    match pane.step {
+        PolicyStep::Browse => match key.code {
+            KeyCode::Esc | KeyCode::Char('p') => app.set_mode(AppMode::Browsing),
+            KeyCode::Up => pane.cursor = pane.cursor.saturating_sub(1),
+            KeyCode::Down if pane.cursor + 1 < count => pane.cursor += 1,
+            KeyCode::Char('n') => {
+                pane.name.clear();
+                pane.editing = None;
+                pane.step = PolicyStep::Naming;
+            }
+            KeyCode::Char('e') if count > 0 => {
+                let name = app.policies.policies()[pane.cursor].name.clone();
+                pane.name = name.clone();
+                pane.editing = Some(name);
+                pane.step = PolicyStep::Naming;
+            }
+            KeyCode::Char('d') if count > 0 => pane.step = PolicyStep::ConfirmDelete,
+            KeyCode::Enter | KeyCode::Char('r') if count > 0 => {
+                if app.selected_bucket_name().is_none() {
+                    app.push_status(t("status.select_policy_bucket"));
+                } else {
+                    app.policy_pane.step = PolicyStep::ConfirmRun;
+                }
+            }
+            _ => {}
+        },
+        PolicyStep::Naming => match key.code {
+            KeyCode::Esc => pane.step = PolicyStep::Browse,
+            KeyCode::Enter => {
+                let name = pane.name.trim().to_string();
+                if name.is_empty() {
+                    app.push_status(t("status.policy_name_needed"));
+                    return;
+                }
+                let taken = app.policies.get(&name).is_some();
+                if taken && pane.editing.as_deref() != Some(name.as_str()) {
+                    app.push_status(&tf("status.policy_exists", &[("name", &name)]));
+                    return;
+                }
+                pane.name = name;
+                pane.note = pane
+                    .editing
+                    .as_deref()
+                    .and_then(|n| app.policies.get(n))
+                    .and_then(|policy| policy.note.clone())
+                    .unwrap_or_default();
+                pane.step = PolicyStep::Noting;
+            }
+            KeyCode::Backspace => {
+                pane.name.pop();
+            }
+            KeyCode::Char(ch) => pane.name.push(ch),
+            _ => {}
+        },
+        PolicyStep::Noting => match key.code {
+            KeyCode::Esc => pane.step = PolicyStep::Naming,
+            KeyCode::Enter => {
+                pane.step = PolicyStep::Editing;
+                if let Some(policy) = pane.editing.as_deref().and_then(|n| app.policies.get(n)) {
+                    let mask = policy.mask.clone();
+                    load_mask_draft(app, &mask);
+                }
+                app.focus_mask_field(MaskEditorField::Pattern);
+                app.mask_draft.cursor_pos = app.mask_draft.pattern.len();
+                app.set_mode(AppMode::EditingMask);
+            }
+            KeyCode::Backspace => {
+                pane.note.pop();
+            }
+            KeyCode::Char(ch) => pane.note.push(ch),
+            _ => {}
+        },
+        PolicyStep::ConfirmDelete => {
+            pane.step = PolicyStep::Browse;
+            if !matches!(key.code, KeyCode::Char('y') | KeyCode::Enter) {
+                return;
+            }
+            let name = app.policies.policies()[pane.cursor].name.clone();
+            match app.policies.remove(&name) {
+                Ok(_) => app.push_status(&tf("status.policy_deleted", &[("name", &name)])),
+                Err(err) => app.push_error(&tf("error.delete_policy", &[("name", &name)]), &err),
+            }
+            let count = app.policies.policies().len();
+            app.policy_pane.cursor = app.policy_pane.cursor.min(count.saturating_sub(1));
+        }
+        PolicyStep::ConfirmRun => {
+            pane.step = PolicyStep::Browse;
+            if !matches!(key.code, KeyCode::Char('y') | KeyCode::Enter) {
+                return;
+            }
+            let policy = app.policies.policies()[pane.cursor].clone();
+            let Some(bucket) = app.selected_bucket_name().map(str::to_string) else {
+                return;
+            };
+            if app.tickets.as_ref().is_some_and(|tickets| tickets.required)
+                && policy.ticket.is_none()
+            {
+                app.push_status(&tf("status.policy_no_ticket", &[("name", &policy.name)]));
+                return;
+            }
+            if let Some(window) = app.execution_window
+                && !window.is_open()
+            {
+                app.push_status(&tf(
+                    "status.policy_outside_window",
+                    &[("window", &window.label())],
+                ));
+                return;
+            }
+            app.queued_batch = Some(PendingAction::RunPolicy { policy, bucket });
+            app.set_mode(AppMode::Browsing);
+        }
+        // The mask editor and class picker have the keys meanwhile
+        PolicyStep::Editing => {}
+    }
+}
+
+/// Fill the mask editor's fields from a saved mask
+fn load_mask_draft(app: &mut App, mask: &ObjectMask) {
+    let draft = &mut app.mask_draft;
+    draft.pattern = mask.pattern.clone();
+    draft.kind = mask.kind.clone();
+    draft.case_sensitive = mask.case_sensitive;
+    draft.storage_class_filter = mask.storage_class_filter.clone();
+    draft.storage_class_cursor = StorageClassTier::all_for_filter()
+        .iter()
+        .position(|(_, filter)| filter == &mask.storage_class_filter)
+        .unwrap_or(0);
+    draft.owner = mask.owner_filter.clone().unwrap_or_default();
+    draft.owner_cursor = draft.owner.len();
+    draft.unread_days = mask.unread_days;
+    draft.min_size = mask.min_size.map(waves::format_bytes).unwrap_or_default();
+    draft.min_size_cursor = draft.min_size.len();
+    draft.max_size = mask.max_size.map(waves::format_bytes).unwrap_or_default();
+    draft.max_size_cursor = draft.max_size.len();
+    draft.older_than = mask
+        .older_than_days
+        .map(|days| days.to_string())
+        .unwrap_or_default();
+    draft.older_than_cursor = draft.older_than.len();
+    draft.newer_than = mask
+        .newer_than_days
+        .map(|days| days.to_string())
+        .unwrap_or_default();
+    draft.newer_than_cursor = draft.newer_than.len();
+}
+
+/// After the mask editor, pick the class the policy moves its matches to
+fn pick_policy_class(app: &mut App, mask: ObjectMask) {
+    let current = app
+        .policy_pane
+        .editing
+        .as_deref()
+        .and_then(|name| app.policies.get(name))
+        .map(|policy| policy.target_class.clone());
+    app.policy_pane.mask = Some(mask);
+    // Nothing to check for a policy, so this cannot fail
+    let _ = begin_storage_selection(app, StorageIntent::SavePolicy);
+    if let Some(index) = current.and_then(|current| {
+        StorageClassTier::selectable()
+            .iter()
+            .position(|class| *class == current)
+    }) {
+        app.storage_class_cursor = index;
+    }
+}
+
+/// Save the policy the pane built, replacing the one being edited
+fn save_policy(app: &mut App, target_class: StorageClassTier) {
+    let pane = std::mem::take(&mut app.policy_pane);
+    app.policy_pane.cursor = pane.cursor;
+    app.set_mode(AppMode::ManagingPolicies);
+    let Some(mask) = pane.mask else {
+        return;
+    };
+    let previous = pane
+        .editing
+        .as_deref()
+        .and_then(|name| app.policies.get(name));
+    let policy = MigrationPolicy {
+        name: pane.name.clone(),
+        mask,
+        target_class,
+        restore_days: previous.and_then(|policy| policy.restore_days),
+        restore_tier: previous
+            .map(|policy| policy.restore_tier)
+            .unwrap_or_default(),
+        created_at: previous
+            .map(|policy| policy.created_at.clone())
+            .unwrap_or_else(|| chrono::Utc::now().to_rfc3339()),
+        note: Some(pane.note.trim().to_string()).filter(|note| !note.is_empty()),
+        // Set in policies.json; the pane does not edit it
+        ticket: previous.and_then(|policy| policy.ticket.clone()),
+    };
+    // A renamed policy replaces its old entry
+    if let Some(old) = pane.editing.filter(|old| *old != pane.name)
+        && let Err(err) = app.policies.remove(&old)
+    {
+        app.push_error(&tf("error.rename_policy", &[("old", &old)]), &err);
+        return;
+    }
+    match app.policies.save_policy(policy) {
+        Ok(()) => {
+            app.push_status(&tf("status.policy_saved", &[("name", &pane.name)]));
+            app.policy_pane.cursor = app
+                .policies
+                .policies()
+                .iter()
+                .position(|policy| policy.name == pane.name)
+                .unwrap_or(0);
+        }
+        Err(err) => app.push_error(&tf("error.save_policy", &[("name", &pane.name)]), &err),
+    }
+}
+
+fn handle_job_history_keys(key: KeyEvent, app: &mut App, s3: &S3Service, history: &mut JobHistory) {
+    let count = history.jobs().count();
+    let shards: usize = app.shards.plans().iter().map(|p| p.shards.len()).sum();
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('h') => app.set_mode(AppMode::Browsing),
+        KeyCode::Up => app.history_cursor = app.history_cursor.saturating_sub(1),
+        KeyCode::Down if app.history_cursor + 1 < count => {
+            app.history_cursor += 1;
+        }
+        KeyCode::Left => app.shard_cursor = app.shard_cursor.saturating_sub(1),
+        KeyCode::Right if app.shard_cursor + 1 < shards => app.shard_cursor += 1,
+        KeyCode::Char('r') => retry_shard(app, s3, history),
+        KeyCode::Char('R') => resume_shard_plan(app, s3, history),
+        KeyCode::Char('X') => drop_shard_plan(app),
+        KeyCode::Char('m') => resume_move(app, history),
+        KeyCode::Char('l') => {
+            let Some(job) = history.jobs().nth(app.history_cursor) else {
+                return;
+            };
+            if app.job_log(&job.id).is_some() {
+                app.log_job = Some(job.id.clone());
+                app.log_scroll = 0;
+                app.set_mode(AppMode::ViewingLog);
+            } else {
+                app.push_status(t("status.job_log_session_only"));
+            }
+        }
+        KeyCode::Char('x') => {
+            let Some(job) = history.jobs().nth(app.history_cursor) else {
+                return;
+            };
+            if app.jobs.cancel(&job.id) {
+                app.push_status(&tf(
+                    "status.cancelling_job",
+                    &[("kind", &job.kind.label()), ("bucket", &job.bucket)],
+                ));
+            } else {
+                app.push_status(t("status.cancel_background_only"));
+            }
+        }
+        KeyCode::Enter => {
+            let Some(job) = history.jobs().nth(app.history_cursor) else {
+                return;
+            };
+            match history.entries(&job.id) {
+                Ok(entries) => {
+                    app.history_entries = entries;
+                    app.history_scroll = 0;
+                    app.set_mode(AppMode::ViewingJobDetail);
+                }
+                Err(err) => app.push_error(t("error.read_journal"), &err),
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Confirm the selected move again for the keys none of its runs finished
+fn resume_move(app: &mut App, history: &JobHistory) {
+    let Some(job) = history.jobs().nth(app.history_cursor) else {
+        return;
+    };
+    let Some(manifest) = app.moves.of_run(&job.id).cloned() else {
+        app.push_status(t("status.resume_moves_only"));
+        return;
+    };
+    if app.no_delete {
+        app.push_status(t("status.no_delete_resume_move"));
+        return;
+    }
+    let keys = manifest.remaining(history);
+    if keys.is_empty() {
+        if let Err(err) = app.moves.remove(&manifest.id) {
+            app.push_error(t("error.drop_move_record"), &err);
+        }
+        app.push_status(t("status.move_done"));
+        return;
+    }
+    app.push_status(&tf(
+        "status.resuming_move",
+        &[
+            ("source", &manifest.source_bucket),
+            ("dest", &manifest.dest_bucket),
+            ("left", &keys.len()),
+            ("total", &manifest.keys.len()),
+        ],
+    ));
+    app.pending_action = Some(PendingAction::Copy {
+        source_bucket: manifest.source_bucket,
+        dest_bucket: manifest.dest_bucket,
+        keys,
+        mode: manifest.mode,
+        delete_source: true,
+        max_bandwidth: manifest.max_bandwidth,
+        rewrite: manifest.rewrite,
+        storage_class: manifest.storage_class,
+    });
+    app.set_mode(AppMode::Confirming);
+}
+
+/// Run the selected shard again, for the keys its earlier runs did not get to
+/// or failed on
+fn retry_shard(app: &mut App, s3: &S3Service, history: &mut JobHistory) {
+    let Some((plan, index)) = app.selected_shard() else {
+        app.push_status(t("status.no_sharded_retry"));
+        return;
+    };
+    let plan_id = plan.id.clone();
+    if plan.shared {
+        match app.shard_state(plan, &plan.shards[index]) {
+            ShardState::Failed => {
+                app.shared_requests
+                    .push(SharedRequest::Retry { plan_id, index });
+                app.shard_synced_at = None;
+                app.push_status(t("status.shard_retry_requested"));
+            }
+            ShardState::Done => app.push_status(t("status.shard_done")),
+            _ => app.push_status(t("status.shard_auto")),
+        }
+        return;
+    }
+    match app.shard_state(plan, &plan.shards[index]) {
+        ShardState::Done => app.push_status(t("status.shard_done")),
+        ShardState::Queued | ShardState::Running => {
+            app.push_status(t("status.shard_busy"));
+        }
+        ShardState::Pending | ShardState::Failed | ShardState::Interrupted => {
+            run_shard(app, s3, history, &plan_id, index);
+        }
+    }
+}
+
+/// Submit every shard of the selected shard's plan that is not done, queued
+/// or running, e.g. after bucket-brigade was restarted
+fn resume_shard_plan(app: &mut App, s3: &S3Service, history: &mut JobHistory) {
+    let Some((plan, _)) = app.selected_shard() else {
+        app.push_status(t("status.no_sharded_resume"));
+        return;
+    };
+    let plan_id = plan.id.clone();
+    if plan.shared {
+        let failed: Vec<usize> = plan
+            .shards
+            .iter()
+            .enumerate()
+            .filter(|(_, shard)| app.shard_state(plan, shard) == ShardState::Failed)
+            .map(|(index, _)| index)
+            .collect();
+        if failed.is_empty() {
+            app.push_status(t("status.no_failed_shards"));
+            return;
+        }
+        app.push_status(&tf(
+            "status.shards_retry_requested",
+            &[("count", &failed.len())],
+        ));
+        for index in failed {
+            let plan_id = plan_id.clone();
+            app.shared_requests
+                .push(SharedRequest::Retry { plan_id, index });
+        }
+        app.shard_synced_at = None;
+        return;
+    }
+    let idle: Vec<usize> = plan
+        .shards
+        .iter()
+        .enumerate()
+        .filter(|(_, shard)| {
+            matches!(
+                app.shard_state(plan, shard),
+                ShardState::Pending | ShardState::Failed | ShardState::Interrupted
+            )
+        })
+        .map(|(index, _)| index)
+        .collect();
+    if idle.is_empty() {
+        app.push_status(t("status.shards_busy"));
+        return;
+    }
+    app.push_status(&tf("status.resuming_shards", &[("count", &idle.len())]));
+    for index in idle {
+        run_shard(app, s3, history, &plan_id, index);
+    }
+}
+
+/// Stop the selected shard's plan and forget it; its journaled jobs stay in
+/// the history
+fn drop_shard_plan(app: &mut App) {
+    let Some((plan, _)) = app.selected_shard() else {
+        return;
+    };
+    let plan_id = plan.id.clone();
+    let runs: Vec<String> = plan
+        .shards
+        .iter()
+        .filter_map(|shard| shard.latest_run().map(str::to_string))
+        .collect();
+    for run in runs {
+        app.jobs.cancel(&run);
+    }
+    if plan.published {
+        // Workers stop its shards at their next checkpoint
+        app.shared_requests.push(SharedRequest::Close {
+            plan_id: plan_id.clone(),
+            reason: "dropped",
+        });
+        app.shard_synced_at = None;
+    }
+    if let Some(plan) = app.shards.remove(&plan_id) {
+        app.push_status(&tf(
+            "status.sharded_dropped",
+            &[
+                ("label", &plan.label),
+                ("bucket", &plan.bucket),
+                ("done", &plan.done_shards()),
+                ("shards", &plan.shards.len()),
+            ],
+        ));
+    }
+    app.shard_cursor = 0;
+}
+
+async fn handle_jump_keys(key: KeyEvent, app: &mut App, s3: &S3Service) {
+    match key.code {
+        KeyCode::Esc => {
+            app.set_mode(AppMode::Browsing);
+        }
+        KeyCode::Enter => {
+            let target = app.jump_input.trim().to_string();
+            app.set_mode(AppMode::Browsing);
+            if app.jump_sets_window {
+                open_listing_window(app, s3, target);
+                return;
+            }
+            if target.is_empty() {
+                return;
+            }
+            if let Err(err) = jump_to_key(app, s3, &target).await {
+                app.push_error(t("error.jump"), &err);
+            }
+        }
+        KeyCode::Backspace => {
+            app.jump_input.pop();
+        }
+        KeyCode::Char(ch) => {
+            app.jump_input.push(ch);
+        }
+        _ => {}
+    }
+}
+
+/// `x`: confirm deleting the noncurrent versions of the targeted keys
+fn begin_delete_versions(app: &mut App) {
+    if app.no_delete {
+        app.push_status(t("status.no_delete_versions"));
+        return;
+    }
+    if !app.version_view {
+        app.push_status(t("status.versions_hint"));
+        return;
+    }
+    let versions: Vec<ObjectInfo> = app.noncurrent_targets().into_iter().cloned().collect();
+    if versions.is_empty() {
+        app.push_status(t("status.no_noncurrent_loaded"));
+        return;
+    }
+    app.pending_action = Some(PendingAction::DeleteVersions { versions });
+    app.set_mode(AppMode::Confirming);
+}
+
+/// `s` in the version view: plan how the noncurrent versions of the
+/// targeted keys move to `target_class`, and confirm
+fn begin_transition_versions(app: &mut App, target_class: StorageClassTier) {
+    app.set_mode(AppMode::Browsing);
+    if app.no_delete {
+        app.push_status(t("status.no_delete_move_versions"));
+        return;
+    }
+    // A key's versions may go on past the last loaded row
+    let last = app
+        .has_more_objects()
+        .then(|| app.objects.last().map(|obj| obj.key.clone()))
+        .flatten();
+    let mut replays = Vec::new();
+    let mut skipped = Vec::new();
+    for history in app.target_histories() {
+        let key = &history[0].key;
+        if last.as_ref() == Some(key) {
+            skipped.push(format!("{key}: more of its versions are not loaded yet"));
+            continue;
+        }
+        match VersionReplay::plan(&history, &target_class) {
+            Ok(Some(replay)) => replays.push(replay),
+            Ok(None) => {}
+            Err(err) => skipped.push(format!("{key}: {err}")),
+        }
+    }
+    if let Some(first) = skipped.first() {
+        app.push_status(&tf(
+            "status.versions_skipped",
+            &[("count", &skipped.len()), ("first", &first)],
+        ));
+    }
+    if replays.is_empty() {
+        app.push_status(&tf(
+            "status.no_versions_to_move",
+            &[("class", &target_class.label())],
+        ));
+        return;
+    }
+    app.pending_action = Some(PendingAction::TransitionVersions {
+        target_class,
+        replays,
+    });
+    app.set_mode(AppMode::Confirming);
+}
+
+/// `r` in the version view: confirm restores of the archived noncurrent
+/// versions of the targeted keys
+fn begin_restore_versions(app: &mut App) -> Result<()> {
+    let versions: Vec<ObjectInfo> = app
+        .noncurrent_targets()
+        .into_iter()
+        .filter(|obj| {
+            obj.version.as_ref().is_some_and(|v| !v.delete_marker)
+                && matches!(
+                    obj.storage_class,
+                    StorageClassTier::GlacierFlexibleRetrieval
+                        | StorageClassTier::GlacierDeepArchive
+                )
+        })
+        .cloned()
+        .collect();
+    if versions.is_empty() {
+        app.push_status(t("status.no_archived_versions"));
+        return Ok(());
+    }
+    let preset = if app.restore_presets_only {
+        let first =
+            app.restore_presets.presets().first().context(
+                "--restore-presets-only is set but restore_presets.json defines no presets",
+            )?;
+        Some(first)
+    } else {
+        None
+    };
+    app.pending_action = Some(PendingAction::RestoreVersions {
+        days: preset.map_or(DEFAULT_RESTORE_DAYS, |p| p.days),
+        tier: preset.map_or(RestoreTier::default(), |p| p.tier),
+        versions,
+    });
+    app.restore_days_input.clear();
+    app.set_mode(AppMode::Confirming);
+    Ok(())
+}
+
+fn handle_kms_keys(key: KeyEvent, app: &mut App) {
+    match key.code {
+        KeyCode::Esc => {
+            app.set_mode(AppMode::Browsing);
+        }
+        KeyCode::Enter => {
+            let kms_key_id = app.kms_input.trim().to_string();
+            if kms_key_id.is_empty() {
+                app.push_status(t("status.kms_key_needed"));
+                return;
+            }
+            app.pending_action = Some(PendingAction::Reencrypt { kms_key_id });
+            app.set_mode(AppMode::Confirming);
+        }
+        KeyCode::Backspace => {
+            app.kms_input.pop();
+        }
+        KeyCode::Char(ch) => {
+            app.kms_input.push(ch);
+        }
+        _ => {}
+    }
+}
+
+fn handle_protect_keys(key: KeyEvent, app: &mut App) {
+    match key.code {
+        KeyCode::Esc => {
+            app.set_mode(AppMode::Browsing);
+        }
+        KeyCode::Enter => {
+            app.set_mode(AppMode::Browsing);
+            let pattern = app.protect_input.trim().to_string();
+            let Some(bucket) = app.selected_bucket_name().map(|b| b.to_string()) else {
+                return;
+            };
+            if pattern.is_empty() || pattern == "*" {
+                app.push_status(t("status.protect_pattern_needed"));
+                return;
+            }
+            if app.protected.toggle(&bucket, &pattern) {
+                app.push_status(&tf(
+                    "status.protected",
+                    &[("bucket", &bucket), ("pattern", &pattern)],
+                ));
+            } else {
+                app.push_status(&tf(
+                    "status.unprotected",
+                    &[("bucket", &bucket), ("pattern", &pattern)],
+                ));
+            }
+        }
+        KeyCode::Backspace => {
+            app.protect_input.pop();
+        }
+        KeyCode::Char(ch) => {
+            app.protect_input.push(ch);
+        }
+        _ => {}
+    }
+}
+
+async fn handle_query_keys(key: KeyEvent, app: &mut App, s3: &S3Service) {
+    match key.code {
+        KeyCode::Esc => {
+            app.set_mode(AppMode::Browsing);
+        }
+        KeyCode::Enter => {
+            app.set_mode(AppMode::Browsing);
+            let input = app.query_input.clone();
+            let result = if app.query_via_athena {
+                run_athena_query(app, &input).await
+            } else {
+                run_inventory_query(app, s3, &input).await
+            };
+            if let Err(err) = result {
+                app.push_error(t("error.inventory_query"), &err);
+            }
+        }
+        KeyCode::Backspace => {
+            app.query_input.pop();
+        }
+        KeyCode::Char(ch) => {
+            app.query_input.push(ch);
+        }
+        _ => {}
+    }
+}
+
+/// Run an S3 Select query over an inventory (or CSV manifest) and make the
+/// matching rows for the selected bucket the object list and target set
+async fn run_inventory_query(app: &mut App, s3: &S3Service, input: &str) -> Result<()> {
+    let bucket = app
+        .selected_bucket_name()
+        .context("Select the bucket the inventory describes")?
+        .to_string();
+    let (manifest_bucket, manifest_key, condition) = inventory::parse_prompt(input)?;
+    let source = if manifest_key.ends_with(".json") {
+        let manifest = s3.get_object_text(&manifest_bucket, &manifest_key).await?;
+        InventorySource::from_manifest(&manifest)?
+    } else {
+        InventorySource::from_csv(&manifest_bucket, &manifest_key)
+    };
+    let expression = source.select_expression(&condition)?;
+
+    let results: Vec<Result<Vec<String>>> = futures::stream::iter(&source.files)
+        .map(|file| s3.select_csv(&source.files_bucket, file, &expression))
+        .buffer_unordered(4)
+        .collect()
+        .await;
+    let mut objects = Vec::new();
+    for lines in results {
+        objects.extend(
+            lines?
+                .iter()
+                .filter_map(|line| source.parse_record(line, &bucket)),
+        );
+    }
+    app.push_status(&tf(
+        "status.inventory_matched",
+        &[
+            ("count", &objects.len()),
+            ("bucket", &bucket),
+            ("files", &source.files.len()),
+        ],
+    ));
+    show_query_set(app, bucket, input.trim().to_string(), objects);
+    Ok(())
+}
+
+/// Run the prompt's condition against the configured Athena inventory table
+async fn run_athena_query(app: &mut App, condition: &str) -> Result<()> {
+    let bucket = app
+        .selected_bucket_name()
+        .context("Select the bucket the inventory describes")?
+        .to_string();
+    let config = app
+        .athena
+        .clone()
+        .context("No Athena inventory table configured")?;
+    let objects = athena::query_inventory(&config, &bucket, condition).await?;
+    app.push_status(&tf(
+        "status.athena_matched",
+        &[
+            ("database", &config.database),
+            ("table", &config.table),
+            ("count", &objects.len()),
+            ("bucket", &bucket),
+        ],
+    ));
+    let label = format!(
+        "athena {}.{} {}",
+        config.database,
+        config.table,
+        condition.trim()
+    );
+    show_query_set(app, bucket, label, objects);
+    Ok(())
+}
+
+/// Make query results the object list and target set for `bucket`
+fn show_query_set(app: &mut App, bucket: String, label: String, mut objects: Vec<ObjectInfo>) {
+    objects.sort_by(|a, b| a.key.cmp(&b.key));
+    objects.dedup_by(|a, b| a.key == b.key);
+    app.query_set = Some(QuerySet {
+        bucket,
+        label,
+        objects,
+    });
+    app.excluded.clear();
+    app.selected_object = 0;
+    app.folder_cursor = None;
+    app.active_pane = ActivePane::Objects;
+}
+
+/// Restart the listing at `start`, leaving everything before it unloaded.
+/// An empty start key returns to listing from the beginning of the bucket.
+fn open_listing_window(app: &mut App, s3: &S3Service, start: String) {
+    let Some(bucket) = app.selected_bucket_name().map(|b| b.to_string()) else {
+        return;
+    };
+    if start.is_empty() {
+        app.listing_window = None;
+        app.push_status(t("status.listing_from_start"));
+    } else {
+        app.push_status(&tf("status.listing_from", &[("start", &start)]));
+        app.listing_window = Some((bucket, start));
+    }
+    load_objects_for_selection(app, s3);
+    app.active_pane = ActivePane::Objects;
+}
+
+/// Scope the listing to `prefix` (the whole bucket for `None`) and reload.
+/// A listing window belongs to the folder it was opened in, so it closes.
+fn open_folder(app: &mut App, s3: &S3Service, prefix: Option<String>) {
+    let Some(bucket) = app.selected_bucket_name().map(|b| b.to_string()) else {
+        return;
+    };
+    app.listing_window = None;
+    match prefix {
+        Some(prefix) => {
+            app.push_status(&tf(
+                "status.browsing_prefix",
+                &[("bucket", &bucket), ("prefix", &prefix)],
+            ));
+            app.folder = Some((bucket, prefix));
+        }
+        None => {
+            app.push_status(&tf("status.browsing_top", &[("bucket", &bucket)]));
+            app.folder = None;
+        }
+    }
+    load_objects_for_selection(app, s3);
+    app.active_pane = ActivePane::Objects;
+}
+
+/// The folder above `prefix`: `a/b/` → `a/`, `a/` → the bucket root
+fn parent_prefix(prefix: &str) -> Option<String> {
+    let trimmed = prefix.strip_suffix('/').unwrap_or(prefix);
+    trimmed.rfind('/').map(|end| trimmed[..=end].to_string())
+}
+
+/// Select the object at (or just after) `target`, fetching that region of the
+/// bucket with StartAfter when it has not been loaded yet
+async fn jump_to_key(app: &mut App, s3: &S3Service, target: &str) -> Result<()> {
+    let bucket = app
+        .selected_bucket_name()
+        .context("Select a bucket first")?
+        .to_string();
+
+    // A folder view only holds one level, so it is not fetched into
+    let prefix = app.current_prefix().unwrap_or_default().to_string();
+    if !app.has_loaded_key(target)
+        && app.has_more_objects()
+        && !app.folder_view
+        && !app.version_view
+        && target.starts_with(&prefix)
+    {
+        app.push_status(&tf("status.fetching_from", &[("target", &target)]));
+        let mut objects = s3.list_objects_from_key(&bucket, target, 1000).await?;
+        objects.retain(|obj| obj.key.starts_with(&prefix));
+        enrich_objects(app, s3, &bucket, &objects);
+        app.append_objects(objects);
+        fetch_tags_for_mask(app, s3, &bucket).await;
+    }
+
+    app.active_pane = ActivePane::Objects;
+    if !app.select_key_at_or_after(target) {
+        app.push_status(&tf("status.nothing_after", &[("target", &target)]));
+    } else if app.has_loaded_key(target) {
+        app.push_status(&tf("status.jumped", &[("target", &target)]));
+    } else {
+        app.push_status(&tf("status.jumped_nearest", &[("target", &target)]));
+    }
+    Ok(())
+}
+
+/// The mask the editor's fields describe, or `None` after saying why they
+/// do not make one
+fn mask_from_draft(app: &mut App) -> Option<ObjectMask> {
+    let owner = app.mask_draft.owner.trim().to_string();
+    let unread_days = app.mask_draft.unread_days;
+    let (min_size, max_size, older_than_days, newer_than_days) =
+        match size_and_age_from_draft(&app.mask_draft) {
+            Ok(bounds) => bounds,
+            Err(message) => {
+                app.push_status(&message);
+                return None;
+            }
+        };
+    let sized_or_aged = min_size.is_some()
+        || max_size.is_some()
+        || older_than_days.is_some()
+        || newer_than_days.is_some();
+    if app.mask_draft.pattern.is_empty()
+        && owner.is_empty()
+        && unread_days.is_none()
+        && !sized_or_aged
+    {
+        app.push_status(t("status.mask_empty"));
+        return None;
+    }
+    if app.mask_draft.kind == MaskKind::Script
+        && let Err(err) = script::predicate(&app.mask_draft.pattern)
+    {
+        app.push_error(t("error.script_compile"), &err);
+        return None;
+    }
+    // Generate a name based on the pattern and kind
+    let name = if app.mask_draft.pattern.is_empty() {
+        match unread_days {
+            _ if !owner.is_empty() => format!("Owner '{owner}'"),
+            Some(days) => format!("Unread {days}d"),
+            None => "Size and age".to_string(),
+        }
+    } else if app.mask_draft.kind == MaskKind::Script {
+        format!("Script '{}'", script::first_line(&app.mask_draft.pattern))
+    } else {
+        format!("{} '{}'", app.mask_draft.kind, app.mask_draft.pattern)
+    };
+    Some(ObjectMask {
+        name,
+        pattern: app.mask_draft.pattern.clone(),
+        kind: app.mask_draft.kind.clone(),
+        case_sensitive: app.mask_draft.case_sensitive,
+        storage_class_filter: app.mask_draft.storage_class_filter.clone(),
+        owner_filter: (!owner.is_empty()).then_some(owner),
+        unread_days,
+        min_size,
+        max_size,
+        older_than_days,
+        newer_than_days,
+    })
+}
+
+/// Minimum and maximum size in bytes, then older-than and newer-than days
+type SizeAndAge = (Option<u64>, Option<u64>, Option<u32>, Option<u32>);
+
+/// Size and last-modified bounds typed into the mask editor, blank fields
+/// leaving their bound open
+fn size_and_age_from_draft(draft: &MaskDraft) -> Result<SizeAndAge, String> {
+    let size = |text: &str| -> Result<Option<u64>, String> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Ok(None);
+        }
+        waves::parse_size(text)
+            .map(Some)
+            .ok_or_else(|| tf("status.mask_bad_size", &[("text", &text)]))
+    };
+    let days = |text: &str| -> Result<Option<u32>, String> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Ok(None);
+        }
+        text.trim_end_matches(['d', 'D'])
+            .trim()
+            .parse()
+            .map(Some)
+            .map_err(|_| tf("status.mask_bad_days", &[("text", &text)]))
+    };
+    let min_size = size(&draft.min_size)?;
+    let max_size = size(&draft.max_size)?;
+    let older_than = days(&draft.older_than)?;
+    let newer_than = days(&draft.newer_than)?;
+    if let (Some(min), Some(max)) = (min_size, max_size)
+        && min > max
+    {
+        return Err(t("status.mask_size_range").to_string());
+    }
+    if let (Some(older), Some(newer)) = (older_than, newer_than)
+        && older >= newer
+    {
+        return Err(tf(
+            "status.mask_age_range",
+            &[("older", &older), ("newer", &newer)],
+        ));
+    }
+    Ok((min_size, max_size, older_than, newer_than))
+}
+
+async fn handle_mask_editor_keys(key: KeyEvent, app: &mut App, s3: &S3Service) {
+    let for_policy = app.policy_pane.step == PolicyStep::Editing;
+    match key.code {
+        KeyCode::Esc if for_policy => {
+            app.policy_pane.step = PolicyStep::Browse;
+            app.set_mode(AppMode::ManagingPolicies);
+        }
+        KeyCode::Esc => {
+            app.set_mode(AppMode::Browsing);
+            app.push_status(t("status.mask_edit_cancelled"));
+        }
+        KeyCode::Enter => {
+            let Some(mask) = mask_from_draft(app) else {
+                return;
+            };
+            if for_policy {
+                if mask.unread_days.is_some() {
+                    app.push_status(t("status.policy_unread_filter"));
+                    return;
+                }
+                pick_policy_class(app, mask);
+                return;
+            }
+            let needs_owners = mask.owner_filter.is_some() && !s3.fetch_owner();
+            let needs_reads = mask.unread_days.filter(|days| {
+                !app.selected_last_reads()
+                    .is_some_and(|reads| reads.covers_days(*days))
+            });
+            apply_mask_with_tags(app, s3, mask).await;
+            app.excluded.clear();
+            app.set_mode(AppMode::Browsing);
+            if needs_owners {
+                s3.set_fetch_owner(true);
+                app.push_status(t("status.owner_listing_on"));
+                load_objects_for_selection(app, s3);
+            }
+            if let Some(days) = needs_reads {
+                look_up_reads_for_mask(app, s3, days);
+            }
+        }
+        KeyCode::Tab => {
+            app.next_mask_field();
+        }
+        KeyCode::BackTab => {
+            app.previous_mask_field();
+        }
+        KeyCode::Backspace => {
+            if let Some((text, cursor)) = mask_text_field(app)
+                && *cursor > 0
+            {
+                text.remove(*cursor - 1);
+                *cursor -= 1;
+            }
+        }
+        KeyCode::Delete => {
+            if let Some((text, cursor)) = mask_text_field(app)
+                && *cursor < text.len()
+            {
+                text.remove(*cursor);
+            }
+        }
+        KeyCode::Left => match app.mask_field {
+            MaskEditorField::Pattern
+            | MaskEditorField::Owner
+            | MaskEditorField::MinSize
+            | MaskEditorField::MaxSize
+            | MaskEditorField::OlderThan
+            | MaskEditorField::NewerThan => {
+                if let Some((_, cursor)) = mask_text_field(app) {
+                    *cursor = cursor.saturating_sub(1);
+                }
+            }
+            MaskEditorField::Mode => app.cycle_mask_kind_backwards(),
+            MaskEditorField::Case => app.toggle_mask_case(),
+            MaskEditorField::Unread => app.cycle_mask_unread(false),
+            MaskEditorField::StorageClass => {
+                if app.mask_draft.storage_class_cursor > 0 {
+                    app.mask_draft.storage_class_cursor -= 1;
+                }
+                let all_classes = StorageClassTier::all_for_filter();
+                app.mask_draft.storage_class_filter = all_classes
+                    .get(app.mask_draft.storage_class_cursor)
+                    .and_then(|(_, filter)| filter.clone());
+            }
+        },
+        KeyCode::Right => match app.mask_field {
+            MaskEditorField::Pattern
+            | MaskEditorField::Owner
+            | MaskEditorField::MinSize
+            | MaskEditorField::MaxSize
+            | MaskEditorField::OlderThan
+            | MaskEditorField::NewerThan => {
+                if let Some((text, cursor)) = mask_text_field(app)
+                    && *cursor < text.len()
+                {
+                    *cursor += 1;
+                }
+            }
+            MaskEditorField::Mode => app.cycle_mask_kind(),
+            MaskEditorField::Case => app.toggle_mask_case(),
+            MaskEditorField::Unread => app.cycle_mask_unread(true),
+            MaskEditorField::StorageClass => {
+                let all_classes = StorageClassTier::all_for_filter();
+                if app.mask_draft.storage_class_cursor + 1 < all_classes.len() {
+                    app.mask_draft.storage_class_cursor += 1;
+                }
+                app.mask_draft.storage_class_filter = all_classes
+                    .get(app.mask_draft.storage_class_cursor)
+                    .and_then(|(_, filter)| filter.clone());
+            }
+        },
+        KeyCode::Home => {
+            if let Some((_, cursor)) = mask_text_field(app) {
+                *cursor = 0;
+            }
+        }
+        KeyCode::End => {
+            if let Some((text, cursor)) = mask_text_field(app) {
+                *cursor = text.len();
+            }
+        }
+        KeyCode::Char(' ') => match app.mask_field {
+            MaskEditorField::Mode => app.cycle_mask_kind(),
+            MaskEditorField::Case => app.toggle_mask_case(),
+            MaskEditorField::Unread => app.cycle_mask_unread(true),
+            MaskEditorField::StorageClass => {
+                let all_classes = StorageClassTier::all_for_filter();
+                app.mask_draft.storage_class_cursor =
+                    (app.mask_draft.storage_class_cursor + 1) % all_classes.len();
+                app.mask_draft.storage_class_filter = all_classes
+                    .get(app.mask_draft.storage_class_cursor)
+                    .and_then(|(_, filter)| filter.clone());
+            }
+            MaskEditorField::Pattern
+            | MaskEditorField::Owner
+            | MaskEditorField::MinSize
+            | MaskEditorField::MaxSize
+            | MaskEditorField::OlderThan
+            | MaskEditorField::NewerThan => {
+                if let Some((text, cursor)) = mask_text_field(app) {
+                    text.insert(*cursor, ' ');
+                    *cursor += 1;
+                }
+            }
+        },
+        KeyCode::Char(ch) => {
+            if let Some((text, cursor)) = mask_text_field(app) {
+                text.insert(*cursor, ch);
+                *cursor += 1;
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Open the script editor on the active script mask, or on an example
+fn open_script_editor(app: &mut App) {
+    app.script_draft = match &app.active_mask {
+        Some(mask) if mask.kind == MaskKind::Script => mask.pattern.clone(),
+        _ if app.mask_draft.kind == MaskKind::Script && !app.mask_draft.pattern.is_empty() => {
+            app.mask_draft.pattern.clone()
+        }
+        _ => SCRIPT_EXAMPLE.to_string(),
+    };
+    app.script_cursor = app.script_draft.len();
+    app.set_mode(AppMode::EditingScript);
+}
+
+/// Starting point for a new script mask
+const SCRIPT_EXAMPLE: &str =
+    "// Objects over 1 MiB not modified for a year\nsize > 1048576 && age_days > 365";
+
+async fn handle_script_editor_keys(key: KeyEvent, app: &mut App, s3: &S3Service) {
+    let text = &mut app.script_draft;
+    let cursor = &mut app.script_cursor;
+    match key.code {
+        KeyCode::Esc => {
+            app.set_mode(AppMode::Browsing);
+            app.push_status(t("status.script_edit_cancelled"));
+        }
+        KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            if let Err(err) = script::predicate(text) {
+                app.push_error(t("error.script_compile"), &err);
+                return;
+            }
+            let mask = ObjectMask {
+                name: format!("Script '{}'", script::first_line(text)),
+                pattern: text.clone(),
+                kind: MaskKind::Script,
+                case_sensitive: false,
+                storage_class_filter: None,
+                owner_filter: None,
+                unread_days: None,
+                min_size: None,
+                max_size: None,
+                older_than_days: None,
+                newer_than_days: None,
+            };
+            app.mask_draft.kind = MaskKind::Script;
+            app.mask_draft.pattern = mask.pattern.clone();
+            app.mask_draft.cursor_pos = mask.pattern.len();
+            app.set_mode(AppMode::Browsing);
+            apply_mask_with_tags(app, s3, mask).await;
+            app.excluded.clear();
+        }
+        KeyCode::Enter => {
+            text.insert(*cursor, '\n');
+            *cursor += 1;
+        }
+        KeyCode::Tab => {
+            text.insert_str(*cursor, "    ");
+            *cursor += 4;
+        }
+        KeyCode::Backspace => {
+            if let Some(ch) = text[..*cursor].chars().next_back() {
+                *cursor -= ch.len_utf8();
+                text.remove(*cursor);
+            }
+        }
+        KeyCode::Delete if *cursor < text.len() => {
+            text.remove(*cursor);
+        }
+        KeyCode::Left => {
+            if let Some(ch) = text[..*cursor].chars().next_back() {
+                *cursor -= ch.len_utf8();
+            }
+        }
+        KeyCode::Right => {
+            if let Some(ch) = text[*cursor..].chars().next() {
+                *cursor += ch.len_utf8();
+            }
+        }
+        KeyCode::Up | KeyCode::Down => {
+            let line_start = text[..*cursor].rfind('\n').map_or(0, |i| i + 1);
+            let column = text[line_start..*cursor].chars().count();
+            let target_start = if key.code == KeyCode::Up {
+                let Some(end) = line_start.checked_sub(1) else {
+                    return;
+                };
+                text[..end].rfind('\n').map_or(0, |i| i + 1)
+            } else {
+                let Some(end) = text[*cursor..].find('\n') else {
+                    return;
+                };
+                *cursor + end + 1
+            };
+            let line = text[target_start..].split('\n').next().unwrap_or_default();
+            *cursor = target_start
+                + line
+                    .char_indices()
+                    .nth(column)
+                    .map_or(line.len(), |(index, _)| index);
+        }
+        KeyCode::Home => {
+            *cursor = text[..*cursor].rfind('\n').map_or(0, |i| i + 1);
+        }
+        KeyCode::End => {
+            *cursor += text[*cursor..].find('\n').unwrap_or(text.len() - *cursor);
+        }
+        KeyCode::Char(ch) => {
+            text.insert(*cursor, ch);
+            *cursor += ch.len_utf8();
+        }
+        _ => {}
+    }
+}
+
+/// Apply a mask, first fetching the tags of the loaded objects when it is a
+/// script that reads them
+async fn apply_mask_with_tags(app: &mut App, s3: &S3Service, mask: ObjectMask) {
+    if let Some(bucket) = app.selected_bucket_name().map(str::to_string) {
+        app.active_mask = Some(mask.clone());
+        fetch_tags_for_mask(app, s3, &bucket).await;
+    }
+    app.apply_mask(Some(mask));
+}
+
+/// Fetch the tags of loaded objects that have none yet, when the active mask
+/// is a script that reads them, and filter again with them
+async fn fetch_tags_for_mask(app: &mut App, s3: &S3Service, bucket: &str) {
+    let Some(mask) = &app.active_mask else {
+        return;
+    };
+    if mask.kind != MaskKind::Script
+        || !script::predicate(&mask.pattern).is_ok_and(|predicate| predicate.uses_tags())
+    {
+        return;
+    }
+    let keys: Vec<String> = app
+        .objects
+        .iter()
+        .filter(|obj| obj.tags.is_none())
+        .map(|obj| obj.key.clone())
+        .collect();
+    if keys.is_empty() {
+        return;
+    }
+    let mut tags: HashMap<String, Vec<(String, String)>> = s3
+        .batch_object_tags(bucket, &keys)
+        .await
+        .into_iter()
+        .collect();
+    // Objects whose tags could not be read count as untagged
+    for obj in app.objects.iter_mut().filter(|obj| obj.tags.is_none()) {
+        obj.tags = Some(tags.remove(&obj.key).unwrap_or_default());
+    }
+    app.refilter();
+}
+
+/// Text and cursor of the focused free-text mask field, if any
+fn mask_text_field(app: &mut App) -> Option<(&mut String, &mut usize)> {
+    let draft = &mut app.mask_draft;
+    match app.mask_field {
+        MaskEditorField::Pattern => Some((&mut draft.pattern, &mut draft.cursor_pos)),
+        MaskEditorField::Owner => Some((&mut draft.owner, &mut draft.owner_cursor)),
+        MaskEditorField::MinSize => Some((&mut draft.min_size, &mut draft.min_size_cursor)),
+        MaskEditorField::MaxSize => Some((&mut draft.max_size, &mut draft.max_size_cursor)),
+        MaskEditorField::OlderThan => Some((&mut draft.older_than, &mut draft.older_than_cursor)),
+        MaskEditorField::NewerThan => Some((&mut draft.newer_than, &mut draft.newer_than_cursor)),
+        _ => None,
+    }
+}
+
+/// Turn owner listing on or off and reload the current bucket to match
+fn toggle_owner_listing(app: &mut App, s3: &S3Service) {
+    let enabled = !s3.fetch_owner();
+    s3.set_fetch_owner(enabled);
+    if enabled {
+        app.push_status(t("status.owners_listed"));
+    } else {
+        app.push_status(t("status.owners_not_listed"));
+    }
+    if app.selected_bucket_name().is_some() && !app.objects.is_empty() {
+        load_objects_for_selection(app, s3);
+    }
+}
+
+fn handle_storage_class_selector(key: KeyEvent, app: &mut App) {
+    match key.code {
+        KeyCode::Esc if app.storage_intent == StorageIntent::SavePolicy => {
+            app.policy_pane.step = PolicyStep::Browse;
+            app.set_mode(AppMode::ManagingPolicies);
+        }
+        KeyCode::Esc => {
+            app.set_mode(AppMode::Browsing);
+        }
+        KeyCode::Up if app.storage_class_cursor > 0 => {
+            app.storage_class_cursor -= 1;
+        }
+        KeyCode::Down if app.storage_class_cursor + 1 < StorageClassTier::selectable().len() => {
+            app.storage_class_cursor += 1;
+        }
+        KeyCode::Enter => {
+            if let Some(selected) = StorageClassTier::selectable().get(app.storage_class_cursor) {
+                if let Some(reason) = app.picker_class_unavailable(selected) {
+                    app.push_status(&tf(
+                        "status.storage_unavailable",
+                        &[("class", &selected.label()), ("reason", &reason)],
+                    ));
+                    return;
+                }
+                match app.storage_intent {
+                    StorageIntent::Transition => begin_transition(app, selected.clone()),
+                    StorageIntent::ExportLifecycle => {
+                        if let Err(err) = compare_lifecycle_rule(app, selected.clone()) {
+                            app.set_mode(AppMode::Browsing);
+                            app.push_error(t("error.lifecycle_export"), &err);
+                        }
+                    }
+                    StorageIntent::SavePolicy => save_policy(app, selected.clone()),
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Confirm a client-side transition of the target objects to `target_class`
+/// Confirm bundling the targeted small objects into one archive. Objects at
+/// or over `SMALL_OBJECT_SIZE`, folder markers and archived objects that
+/// cannot be read are left out.
+/// Open the members of the selected bundle archive (or its index), reading
+/// its index from the bucket the first time. On any other row, open every
+/// known bundle of the bucket with the cursor on the selected key.
+async fn open_bundle(app: &mut App, s3: &S3Service) {
+    let Some(bucket) = app.selected_bucket_name().map(str::to_string) else {
+        app.push_status(t("status.select_bucket"));
+        return;
+    };
+    let selected = app.selected_object().map(|obj| obj.key.clone());
+    let archive = selected
+        .as_deref()
+        .and_then(bundle::archive_of)
+        .map(str::to_string);
+    if let Some(archive) = &archive
+        && app.bundles.get(&bucket, archive).is_none()
+    {
+        match bundle::read_index(s3, &bucket, archive).await {
+            Ok(index) => {
+                if let Err(err) = app.bundles.add(index) {
+                    app.push_error(t("error.save_bundle_index"), &err);
+                }
+            }
+            Err(err) => {
+                app.push_error(&tf("error.open_archive", &[("archive", &archive)]), &err);
+                return;
+            }
+        }
+    }
+    let members: Vec<(String, BundleEntry)> = app
+        .bundles
+        .in_bucket(&bucket)
+        .filter(|index| archive.as_ref().is_none_or(|key| &index.archive_key == key))
+        .flat_map(|index| {
+            index
+                .entries
+                .iter()
+                .map(|entry| (index.archive_key.clone(), entry.clone()))
+        })
+        .collect();
+    if members.is_empty() {
+        app.push_status(t("status.no_bundles_known"));
+        return;
+    }
+    // The newest bundle holding the selected key
+    let cursor = selected
+        .filter(|_| archive.is_none())
+        .and_then(|key| app.bundles.locate(&bucket, &key))
+        .and_then(|(index, found)| {
+            members.iter().position(|(archive_key, entry)| {
+                *archive_key == index.archive_key && entry.key == found.key
+            })
+        })
+        .unwrap_or(0);
+    app.bundle_view = Some(BundleView {
+        bucket,
+        members,
+        cursor,
+        ..BundleView::default()
+    });
+    app.set_mode(AppMode::ViewingBundle);
+}
+
+fn handle_bundle_keys(key: KeyEvent, app: &mut App) {
+    let Some(view) = &mut app.bundle_view else {
+        app.set_mode(AppMode::Browsing);
+        return;
+    };
+    if view.filtering {
+        match key.code {
+            KeyCode::Enter | KeyCode::Esc => view.filtering = false,
+            KeyCode::Backspace => {
+                view.filter.pop();
+                view.cursor = 0;
+            }
+            KeyCode::Char(ch) => {
+                view.filter.push(ch);
+                view.cursor = 0;
+            }
+            _ => {}
+        }
+        return;
+    }
+    let visible = view.visible();
+    let last = visible.len().saturating_sub(1);
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('Z') | KeyCode::Char('q') => {
+            app.bundle_view = None;
+            app.set_mode(AppMode::Browsing);
+        }
+        KeyCode::Up | KeyCode::Char('k') => view.cursor = view.cursor.saturating_sub(1),
+        KeyCode::Down | KeyCode::Char('j') => view.cursor = (view.cursor + 1).min(last),
+        KeyCode::PageUp => view.cursor = view.cursor.saturating_sub(RECONCILE_PAGE),
+        KeyCode::PageDown => view.cursor = (view.cursor + RECONCILE_PAGE).min(last),
+        KeyCode::Char('/') => view.filtering = true,
+        KeyCode::Char(' ') => {
+            if let Some(&index) = visible.get(view.cursor) {
+                if !view.picked.remove(&index) {
+                    view.picked.insert(index);
+                }
+                view.cursor = (view.cursor + 1).min(last);
+            }
+        }
+        KeyCode::Enter => {
+            let mut picked: Vec<usize> = view.picked.iter().copied().collect();
+            picked.sort_unstable();
+            if picked.is_empty() {
+                picked.extend(visible.get(view.cursor));
+            }
+            if picked.is_empty() {
+                return;
+            }
+            let members = picked
+                .into_iter()
+                .map(|index| view.members[index].clone())
+                .collect();
+            app.pending_action = Some(PendingAction::RetrieveBundled {
+                bucket: view.bucket.clone(),
+                members,
+                local: false,
+            });
+            app.set_mode(AppMode::Confirming);
+        }
+        _ => {}
+    }
+}
+
+fn begin_bundle(app: &mut App) {
+    if app.selected_bucket_name().is_none() {
+        app.push_status(t("status.select_bucket"));
+        return;
+    }
+    let targets = app.target_objects();
+    let objects: Vec<ObjectInfo> = targets
+        .iter()
+        .filter(|obj| obj.size < bundle::SMALL_OBJECT_SIZE && !obj.key.ends_with('/'))
+        .filter(|obj| readable_in_place(obj))
+        .map(|obj| (*obj).clone())
+        .collect();
+    let left_out = targets.len() - objects.len();
+    if objects.len() < 2 {
+        app.push_status(&tf(
+            "status.bundle_too_few",
+            &[(
+                "size",
+                &waves::format_bytes(bundle::SMALL_OBJECT_SIZE as u64),
+            )],
+        ));
+        return;
+    }
+    if left_out > 0 {
+        app.push_status(&tf(
+            "status.bundle_left_out",
+            &[
+                ("left_out", &left_out),
+                (
+                    "size",
+                    &waves::format_bytes(bundle::SMALL_OBJECT_SIZE as u64),
+                ),
+            ],
+        ));
+    }
+    let compression = ArchiveCompression::default();
+    let archive_key = bundle::archive_key(objects.iter().map(|obj| obj.key.as_str()), compression);
+    app.pending_action = Some(PendingAction::Bundle {
+        objects,
+        archive_key,
+        compression,
+        delete_originals: false,
+    });
+    app.set_mode(AppMode::Confirming);
+}
+
+fn begin_transition(app: &mut App, target_class: StorageClassTier) {
+    if app.version_view {
+        return begin_transition_versions(app, target_class);
+    }
+    // Check if objects need restore before transition
+    if app.any_targets_need_restoration() {
+        app.set_mode(AppMode::Browsing);
+        let need_restore = app.count_objects_needing_restore();
+        app.push_status(&tf(
+            "status.restore_before_transition",
+            &[("count", &need_restore)],
+        ));
+        return;
+    }
+    let label = target_class.label().to_string();
+    app.pending_action = Some(PendingAction::Transition {
+        target_class,
+        lock: ObjectLockPlan::default(),
+    });
+    app.set_mode(AppMode::Confirming);
+    app.push_status(&tf("status.confirm_transition", &[("label", &label)]));
+}
+
+/// Build the lifecycle rule for the active prefix mask and price it against
+/// a client-side transition of the masked objects
+fn compare_lifecycle_rule(app: &mut App, storage_class: StorageClassTier) -> Result<()> {
+    let bucket = app
+        .selected_bucket_name()
+        .context("Select a bucket first")?
+        .to_string();
+    let mask = app
+        .active_mask
+        .as_ref()
+        .context("Apply a prefix mask first")?;
+    let (rule, warnings) = LifecycleRule::from_mask(&bucket, mask, storage_class)?;
+    let region = app
+        .buckets
+        .get(app.selected_bucket)
+        .and_then(|b| b.region.clone())
+        .or_else(|| app.selected_region.clone());
+    // Archived objects need a restore before either mechanism can move them
+    let objects: Vec<&ObjectInfo> = app
+        .target_objects()
+        .into_iter()
+        .filter(|obj| {
+            !matches!(
+                obj.storage_class,
+                StorageClassTier::GlacierFlexibleRetrieval | StorageClassTier::GlacierDeepArchive
+            ) || matches!(
+                obj.restore_state,
+                Some(crate::models::RestoreState::Available)
+            )
+        })
+        .collect();
+    let costs = MechanismComparison::new(&rule, &objects, region.as_deref(), chrono::Utc::now());
+    app.lifecycle_comparison = Some(LifecycleComparison {
+        rule,
+        warnings,
+        costs,
+    });
+    app.set_mode(AppMode::ComparingMechanisms);
+    Ok(())
+}
+
+fn handle_comparison_keys(key: KeyEvent, app: &mut App) {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.lifecycle_comparison = None;
+            app.set_mode(AppMode::Browsing);
+        }
+        KeyCode::Char('e') | KeyCode::Enter => {
+            app.set_mode(AppMode::Browsing);
+            if let Err(err) = export_lifecycle_rule(app) {
+                app.push_error(t("error.lifecycle_export"), &err);
+            }
+        }
+        KeyCode::Char('c') => {
+            let Some(comparison) = app.lifecycle_comparison.take() else {
+                return;
+            };
+            begin_transition(app, comparison.rule.storage_class);
+        }
+        _ => {}
+    }
+}
+
+/// Write the compared lifecycle rule as Terraform and CloudFormation snippets
+fn export_lifecycle_rule(app: &mut App) -> Result<()> {
+    let LifecycleComparison { rule, warnings, .. } = app
+        .lifecycle_comparison
+        .take()
+        .context("No lifecycle rule to export")?;
+    let (terraform, cloudformation) = rule.export()?;
+    app.push_status(&tf(
+        "status.lifecycle_exported",
+        &[
+            ("days", &rule.days),
+            ("class", &rule.storage_class.label()),
+            ("terraform", &terraform.display()),
+            ("cloudformation", &cloudformation.display()),
+        ],
+    ));
+    for warning in warnings {
+        app.push_status(&tf("status.not_exported", &[("warning", &warning)]));
+    }
+    Ok(())
+}
+
+/// Write an HTML report of the selected bucket: its storage by class, the
+/// selection and scheduled actions, and the jobs that ran on it
+fn export_report(app: &App, history: &JobHistory) -> Result<std::path::PathBuf> {
+    let bucket = app
+        .selected_bucket_name()
+        .context("Select a bucket first")?;
+    let selection = if let Some(query) = app.active_query() {
+        Some(format!("query {}", query.label))
+    } else {
+        app.active_mask
+            .as_ref()
+            .map(|mask| format!("mask {}", mask.name))
+    };
+    let report = BucketReport {
+        bucket,
+        region: app
+            .buckets
+            .get(app.selected_bucket)
+            .and_then(|b| b.region.as_deref()),
+        objects: &app.objects,
+        complete: !app.has_more_objects(),
+        selection: selection.map(|label| (label, app.target_objects())),
+        scheduled: app
+            .schedule
+            .actions()
+            .iter()
+            .filter(|action| action.bucket == bucket)
+            .collect(),
+        jobs: history
+            .jobs()
+            .filter(|job| job.bucket == bucket || job.target == bucket)
+            .collect(),
+    };
+    report.export()
+}
+
+fn begin_storage_selection(app: &mut App, intent: StorageIntent) -> Result<()> {
+    match intent {
+        StorageIntent::Transition => {
+            if app.selected_bucket_name().is_none() {
+                anyhow::bail!("Select a bucket first");
+            }
+            if target_count(app) == 0 {
+                anyhow::bail!("Select at least one object (mask or row)");
+            }
+        }
+        StorageIntent::ExportLifecycle => {
+            if app.selected_bucket_name().is_none() {
+                anyhow::bail!("Select a bucket first");
+            }
+            match &app.active_mask {
+                Some(mask) if matches!(mask.kind, MaskKind::Prefix) => {}
+                _ => anyhow::bail!("Apply a Prefix mask to export it as a lifecycle rule"),
+            }
+        }
+        // A policy runs against whole buckets, not the loaded objects
+        StorageIntent::SavePolicy => {}
+    }
+    app.storage_intent = intent;
+    // Start on the first class that is a valid target
+    app.storage_class_cursor = StorageClassTier::selectable()
+        .iter()
+        .position(|class| app.picker_class_unavailable(class).is_none())
+        .unwrap_or(0);
+    app.set_mode(AppMode::SelectingStorageClass);
+    Ok(())
+}
+
+/// Duration a restore starts with in the confirmation dialog
+const DEFAULT_RESTORE_DAYS: i32 = 7;
+
+fn initiate_restore_flow(app: &mut App) -> Result<()> {
+    if app.selected_bucket_name().is_none() || target_count(app) == 0 {
+        anyhow::bail!("Select objects to restore first");
+    }
+    if app.version_view {
+        return begin_restore_versions(app);
+    }
+
+    let need_restore = app.count_objects_needing_restore();
+    let already_restoring = app.count_objects_restoring();
+
+    if need_restore == 0 {
+        if already_restoring > 0 {
+            app.push_status(&tf(
+                "status.already_restoring",
+                &[("count", &already_restoring)],
+            ));
+        } else {
+            app.push_status(t("status.no_restore_needed_glacier"));
+        }
+        return Ok(());
+    }
+
+    // With --restore-presets-only the dialog opens on the first preset
+    let preset = if app.restore_presets_only {
+        let first =
+            app.restore_presets.presets().first().context(
+                "--restore-presets-only is set but restore_presets.json defines no presets",
+            )?;
+        Some(first)
+    } else {
+        None
+    };
+    app.pending_action = Some(PendingAction::Restore {
+        days: preset.map_or(DEFAULT_RESTORE_DAYS, |p| p.days),
+        tier: preset.map_or(RestoreTier::default(), |p| p.tier),
+        preset: preset.map(|p| p.name.clone()),
+    });
+    app.restore_days_input.clear();
+    app.set_mode(AppMode::Confirming);
+
+    if already_restoring > 0 {
+        app.push_status(&tf(
+            "status.will_restore",
+            &[("count", &need_restore), ("restoring", &already_restoring)],
+        ));
+    } else {
+        app.push_status(&tf("status.confirm_restore", &[("count", &need_restore)]));
+    }
+    Ok(())
+}
+
+/// Interval at which a running batch redraws and checks for quit keys
+const BATCH_TICK: Duration = Duration::from_millis(100);
+
+/// How often the progress popup is redrawn in low-redraw mode when no key was
+/// pressed
+const LOW_REDRAW_BATCH_FRAME: Duration = Duration::from_secs(1);
+
+/// Longest gap between frames while idle, so restore countdowns stay current
+const CLOCK_REDRAW: Duration = Duration::from_secs(30);
+
+/// How long the bucket selection has to rest before its objects are loaded
+const BUCKET_LOAD_DELAY: Duration = Duration::from_secs(1);
+
+type Term = Terminal<CrosstermBackend<Stdout>>;
+
+/// Keeps the progress popup drawn and intercepts quit keys while a batch runs,
+/// since the event loop is blocked until the batch returns
+struct BatchMonitor<'a> {
+    terminal: &'a mut Term,
+    s3: &'a S3Service,
+    stop: &'a Cell<Option<ShutdownChoice>>,
+    drawn_at: Option<Instant>,
+}
+
+impl BatchMonitor<'_> {
+    /// Drive one request to completion, or return `None` if the user aborted it
+    async fn run<F: Future>(&mut self, app: &mut App, step: F) -> Result<Option<F::Output>> {
+        if !self.wait_online(app).await? {
+            return Ok(None);
+        }
+        tokio::pin!(step);
+        loop {
+            self.tick(app)?;
+            if self.stop.get() == Some(ShutdownChoice::Abort) {
+                return Ok(None);
+            }
+            tokio::select! {
+                output = &mut step => return Ok(Some(output)),
+                _ = tokio::time::sleep(BATCH_TICK) => {}
+            }
+        }
+    }
+
+    /// Wait out `wait` between steps. Returns false when the batch is told
+    /// to stop meanwhile, which any shutdown choice does at once.
+    async fn hold(&mut self, app: &mut App, wait: Duration) -> Result<bool> {
+        let resume = Instant::now() + wait;
+        while !self.stopping() {
+            let left = resume.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                return Ok(true);
+            }
+            self.tick(app)?;
+            tokio::time::sleep(left.min(BATCH_TICK)).await;
+        }
+        Ok(false)
+    }
+
+    /// Hold the batch on the pause popup until the user decides how to go on
+    async fn pause(&mut self, app: &mut App, pause: BatchPause) -> Result<PauseChoice> {
+        app.batch_pause = Some(pause);
+        app.set_mode(AppMode::BatchPaused);
+        loop {
+            self.tick(app)?;
+            if let Some(choice) = app.batch_pause.as_ref().and_then(|p| p.choice) {
+                app.batch_pause = None;
+                app.set_mode(AppMode::ShowingProgress);
+                return Ok(choice);
+            }
+            tokio::time::sleep(BATCH_TICK).await;
+        }
+    }
+
+    /// Hold the batch while AWS is unreachable, probing until it is back.
+    /// Returns `false` if the user quit meanwhile.
+    async fn wait_online(&mut self, app: &mut App) -> Result<bool> {
+        while app.connectivity.is_offline() {
+            self.tick(app)?;
+            if self.stopping() {
+                return Ok(false);
+            }
+            start_connection_probe(app, self.s3);
+            tokio::select! {
+                reached = probe_done(&mut app.connection_probe) => {
+                    if reached {
+                        app.note_reached();
+                    }
+                }
+                _ = tokio::time::sleep(BATCH_TICK) => {}
+            }
+        }
+        Ok(true)
+    }
+
+    /// Whether the user asked to quit and no further objects should be started
+    fn stopping(&self) -> bool {
+        self.stop.get().is_some()
+    }
+
+    fn tick(&mut self, app: &mut App) -> Result<()> {
+        let mut input = false;
+        while event::poll(Duration::ZERO)? {
+            input = true;
+            if let Event::Key(key) = event::read()?
+                && key.kind == KeyEventKind::Press
+            {
+                handle_batch_key(key, app, self.stop);
+            }
+        }
+        let due = self
+            .drawn_at
+            .is_none_or(|at| at.elapsed() >= LOW_REDRAW_BATCH_FRAME);
+        if input || !app.low_redraw || due {
+            self.terminal.draw(|frame| draw_batch(frame, app))?;
+            self.drawn_at = Some(Instant::now());
+        }
+        Ok(())
+    }
+}
+
+fn handle_batch_key(key: KeyEvent, app: &mut App, stop: &Cell<Option<ShutdownChoice>>) {
+    let ctrl_c =
+        matches!(key.code, KeyCode::Char('c')) && key.modifiers.contains(KeyModifiers::CONTROL);
+
+    if app.mode == AppMode::BatchPaused {
+        let choice = match key.code {
+            _ if ctrl_c => PauseChoice::Abort,
+            KeyCode::Char('c') => PauseChoice::Continue,
+            KeyCode::Char('r') => PauseChoice::Rollback,
+            KeyCode::Char('x') | KeyCode::Char('q') | KeyCode::Esc => PauseChoice::Abort,
+            _ => return,
+        };
+        if let Some(pause) = &mut app.batch_pause {
+            pause.choice = Some(choice);
+        }
+        return;
+    }
+
+    if app.mode == AppMode::ConfirmingShutdown {
+        let choice = match key.code {
+            KeyCode::Char('f') => Some(ShutdownChoice::FinishCurrent),
+            KeyCode::Char('c') => Some(ShutdownChoice::Checkpoint),
+            KeyCode::Char('x') => Some(ShutdownChoice::Abort),
+            KeyCode::Esc => None,
+            _ => return,
+        };
+        if let Some(choice) = choice {
+            stop.set(Some(choice));
+            app.push_status(shutdown_label(choice));
+        }
+        if let Some(progress) = &mut app.progress {
+            progress.stopping = stop.get();
+        }
+        app.set_mode(AppMode::ShowingProgress);
+        return;
+    }
+
+    if matches!(key.code, KeyCode::Char('M')) {
+        app.show_telemetry = !app.show_telemetry;
+        return;
+    }
+
+    if matches!(key.code, KeyCode::Char('l')) || (app.show_batch_log && key.code == KeyCode::Esc) {
+        app.show_batch_log = !app.show_batch_log;
+        app.log_scroll = 0;
+        // The running job's per-object messages, rather than the status log
+        app.log_job = app
+            .job_logs
+            .back()
+            .filter(|_| app.show_batch_log)
+            .map(|log| log.job_id.clone());
+        return;
+    }
+    if app.show_batch_log && handle_log_keys(key, app) {
+        return;
+    }
+
+    if let Some(concurrency) = app.progress.as_mut().and_then(|p| p.concurrency.as_mut()) {
+        match key.code {
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                *concurrency = (*concurrency + 1).min(MAX_RESTORE_CONCURRENCY);
+                return;
+            }
+            KeyCode::Char('-') => {
+                *concurrency = concurrency.saturating_sub(1).max(1);
+                return;
+            }
+            _ => {}
+        }
+    }
+
+    if ctrl_c || matches!(key.code, KeyCode::Char('q')) {
+        app.set_mode(AppMode::ConfirmingShutdown);
+    }
+}
+
+/// Scroll keys of the status log. Scrolling up pauses follow mode; `End`,
+/// `G` or `f` jumps back to the newest message and follows again.
+fn handle_log_keys(key: KeyEvent, app: &mut App) -> bool {
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') => app.scroll_log(1),
+        KeyCode::Down | KeyCode::Char('j') => app.scroll_log(-1),
+        KeyCode::PageUp => app.scroll_log(LOG_PAGE),
+        KeyCode::PageDown => app.scroll_log(-LOG_PAGE),
+        KeyCode::Home | KeyCode::Char('g') => app.scroll_log(isize::MAX),
+        KeyCode::End | KeyCode::Char('G') | KeyCode::Char('f') => app.log_scroll = 0,
+        _ => return false,
+    }
+    true
+}
+
+/// Close the log view, back to job history if it showed a job's log
+fn close_log(app: &mut App) {
+    if app.log_job.take().is_some() {
+        app.set_mode(AppMode::ViewingJobHistory);
+    } else {
+        app.set_mode(AppMode::Browsing);
+    }
+}
+
+/// Messages scrolled by PageUp and PageDown in the status log
+const LOG_PAGE: isize = 10;
+
+/// Run a confirmed batch, quitting afterwards if the user asked to
+async fn run_batch(
+    terminal: &mut Term,
+    app: &mut App,
+    s3: &S3Service,
+    tracker: &mut RestoreTracker,
+    history: &mut JobHistory,
+    uploads: &mut MultipartStore,
+    action: PendingAction,
+) -> Result<()> {
+    let stop = Cell::new(None);
+    let mut monitor = BatchMonitor {
+        terminal,
+        s3,
+        stop: &stop,
+        drawn_at: None,
+    };
+    let result = match action {
+        PendingAction::Transition { target_class, lock } => {
+            execute_transition(&mut monitor, app, s3, history, uploads, target_class, lock).await
+        }
+        PendingAction::Restore { days, tier, preset } => {
+            execute_restore(&mut monitor, app, s3, tracker, history, days, tier, preset).await
+        }
+        PendingAction::Reencrypt { kms_key_id } => {
+            execute_reencrypt(&mut monitor, app, s3, history, uploads, kms_key_id).await
+        }
+        PendingAction::DeleteVersions { versions } => {
+            execute_delete_versions(&mut monitor, app, s3, history, versions).await
+        }
+        PendingAction::TransitionVersions {
+            target_class,
+            replays,
+        } => {
+            execute_transition_versions(&mut monitor, app, s3, history, target_class, replays).await
+        }
+        PendingAction::RestoreVersions {
+            versions,
+            days,
+            tier,
+        } => execute_restore_versions(&mut monitor, app, s3, history, versions, days, tier).await,
+        PendingAction::RunPolicy { policy, bucket } => {
+            execute_policy(&mut monitor, app, s3, history, uploads, policy, bucket).await
+        }
+        PendingAction::Bundle {
+            objects,
+            archive_key,
+            compression,
+            delete_originals,
+        } => {
+            execute_bundle(
+                &mut monitor,
+                app,
+                s3,
+                history,
+                objects,
+                archive_key,
+                compression,
+                delete_originals,
+            )
+            .await
+        }
+        PendingAction::RetrieveBundled {
+            bucket,
+            members,
+            local,
+        } => {
+            execute_retrieve(
+                &mut monitor,
+                app,
+                s3,
+                tracker,
+                history,
+                bucket,
+                members,
+                local,
+            )
+            .await
+        }
+        PendingAction::RestoreWave { plan_id, wave } => {
+            let keys = tracker
+                .wave_plan(&plan_id)
+                .and_then(|plan| plan.waves.get(wave))
+                .map(|w| w.keys.clone())
+                .unwrap_or_default();
+            execute_restore_wave(
+                &mut monitor,
+                app,
+                s3,
+                tracker,
+                history,
+                &plan_id,
+                wave,
+                keys,
+            )
+            .await
+        }
+        PendingAction::Copy {
+            source_bucket,
+            dest_bucket,
+            keys,
+            mode,
+            delete_source,
+            max_bandwidth,
+            rewrite,
+            storage_class,
+        } => {
+            execute_copy(
+                &mut monitor,
+                app,
+                s3,
+                history,
+                uploads,
+                &source_bucket,
+                &dest_bucket,
+                keys,
+                mode,
+                delete_source,
+                max_bandwidth,
+                &rewrite,
+                storage_class,
+            )
+            .await
+        }
+    };
+    if stop.get().is_some() {
+        app.quit_requested = true;
+    }
+    app.show_batch_log = false;
+    app.log_job = None;
+    result
+}
+
+/// Run a saved policy against a whole bucket, as `--run-policy` does
+async fn execute_policy(
+    monitor: &mut BatchMonitor<'_>,
+    app: &mut App,
+    s3: &S3Service,
+    history: &mut JobHistory,
+    uploads: &mut MultipartStore,
+    policy: MigrationPolicy,
+    bucket: String,
+) -> Result<()> {
+    let mut progress =
+        crate::app::ProgressState::new(format!("Running policy {} on {bucket}", policy.name), 0);
+    progress.current_item = Some(format!("listing {bucket}…"));
+    app.progress = Some(progress);
+    app.set_mode(AppMode::ShowingProgress);
+
+    let latest = RefCell::new(None);
+    let run = policy::run(
+        s3,
+        &policy,
+        &bucket,
+        history,
+        uploads,
+        monitor.stop,
+        app.require_approval,
+        |progress| {
+            latest.replace(Some((
+                progress.done,
+                progress.total,
+                progress.key.to_string(),
+            )));
+        },
+    );
+    tokio::pin!(run);
+    // Not dropped on abort: the run checks the stop choice before each
+    // object and closes its journal entry itself
+    let result = loop {
+        if let Some((done, total, key)) = latest.take()
+            && let Some(progress) = &mut app.progress
+        {
+            progress.total = total;
+            progress.update(done, Some(key));
+        }
+        monitor.tick(app)?;
+        tokio::select! {
+            result = &mut run => break result,
+            _ = tokio::time::sleep(BATCH_TICK) => {}
+        }
+    };
+    app.progress = None;
+    app.set_mode(AppMode::Browsing);
+
+    let summary = match result {
+        Ok(summary) => summary,
+        Err(err) => {
+            app.push_error(
+                &tf(
+                    "error.policy_failed",
+                    &[("name", &policy.name), ("bucket", &bucket)],
+                ),
+                &err,
+            );
+            return Ok(());
+        }
+    };
+    for (key, reason) in summary.failed.iter().take(POLICY_FAILURES_SHOWN) {
+        app.push_status(&tf(
+            "status.policy_key_failed",
+            &[("name", &policy.name), ("key", &key), ("reason", &reason)],
+        ));
+    }
+    if summary.failed.len() > POLICY_FAILURES_SHOWN {
+        app.push_status(&tf(
+            "status.policy_more_failures",
+            &[("count", &(summary.failed.len() - POLICY_FAILURES_SHOWN))],
+        ));
+    }
+    if summary.needs_restore > 0 {
+        app.push_status(&tf(
+            "status.policy_needs_restore",
+            &[
+                ("count", &summary.needs_restore),
+                ("requested", &summary.restores_requested),
+            ],
+        ));
+    }
+    let key = if summary.interrupted {
+        "status.policy_stopped"
+    } else {
+        "status.policy_summary"
+    };
+    app.push_status(&tf(
+        key,
+        &[
+            ("name", &policy.name),
+            ("bucket", &bucket),
+            ("matched", &summary.matched),
+            ("transitioned", &summary.transitioned),
+            ("class", &policy.target_class.label()),
+            ("size", &waves::format_bytes(summary.bytes)),
+            ("already", &summary.already_in_class),
+            ("protected", &summary.protected),
+            ("failed", &summary.failed.len()),
+        ],
+    ));
+    Ok(())
+}
+
+/// Failures of a policy run listed in the status log; the rest are in the
+/// job's journal
+const POLICY_FAILURES_SHOWN: usize = 5;
+
+/// Days an archive restored for a retrieval stays readable
+const RETRIEVE_RESTORE_DAYS: i32 = 7;
+
+/// Read members out of their archives and write each back under its
+/// original key, or to a local file. An archive without a readable copy is
+/// restored first; its members are journaled as waiting and can be
+/// retrieved again once the restore completes.
+#[allow(clippy::too_many_arguments)]
+async fn execute_retrieve(
+    monitor: &mut BatchMonitor<'_>,
+    app: &mut App,
+    s3: &S3Service,
+    tracker: &mut RestoreTracker,
+    history: &mut JobHistory,
+    bucket: String,
+    members: Vec<(String, BundleEntry)>,
+    local: bool,
+) -> Result<()> {
+    let mut archives: Vec<(String, Vec<BundleEntry>)> = Vec::new();
+    for (archive_key, entry) in members {
+        match archives.iter_mut().find(|(key, _)| *key == archive_key) {
+            Some((_, entries)) => entries.push(entry),
+            None => archives.push((archive_key, vec![entry])),
+        }
+    }
+    let total: usize = archives.iter().map(|(_, entries)| entries.len()).sum();
+    let target = if local {
+        bundle::local_path(&bucket, "").display().to_string()
+    } else {
+        bucket.clone()
+    };
+    app.progress = Some(crate::app::ProgressState::new(
+        format!("Retrieving bundled objects from {bucket}"),
+        total,
+    ));
+    app.set_mode(AppMode::ShowingProgress);
+    let job_id = begin_job(app, history, JobKind::Retrieve, &bucket, &target, total);
+
+    let mut recorded = 0;
+    let mut retrieved = 0;
+    let mut waiting = 0;
+    let mut failed = 0;
+    'archives: for (archive_key, mut entries) in archives {
+        let Some(index) = app.bundles.get(&bucket, &archive_key).cloned() else {
+            for entry in &entries {
+                history.record(
+                    &job_id,
+                    &entry.key,
+                    Some("the bundle index is not known".into()),
+                );
+            }
+            recorded += entries.len();
+            failed += entries.len();
+            continue;
+        };
+        // The archive needs a readable copy before anything is read out of it
+        let Some(head) = monitor
+            .run(app, s3.refresh_object(&bucket, &archive_key))
+            .await?
+        else {
+            break;
+        };
+        let unreadable = match head {
+            Ok(archive) => match RestoreStatus::of(&archive) {
+                None | Some(RestoreStatus::Restored) => None,
+                Some(RestoreStatus::Restoring) => {
+                    Some(format!("waiting for the restore of {archive_key}"))
+                }
+                Some(RestoreStatus::NeedsRestore | RestoreStatus::Expired) => {
+                    let tier = RestoreTier::default();
+                    let request =
+                        s3.request_restore(&bucket, &archive_key, RETRIEVE_RESTORE_DAYS, tier);
+                    let Some(result) = monitor.run(app, request).await? else {
+                        break;
+                    };
+                    match result {
+                        Ok(()) => {
+                            tracker.add_request(
+                                bucket.clone(),
+                                archive_key.clone(),
+                                RETRIEVE_RESTORE_DAYS,
+                                archive.storage_class.clone(),
+                                tier,
+                                false,
+                            );
+                            app.push_status(&tf(
+                                "status.archive_restore_requested",
+                                &[("tier", &tier.label()), ("archive_key", &archive_key)],
+                            ));
+                            Some(format!("waiting for the restore of {archive_key}"))
+                        }
+                        Err(err) => Some(format!(
+                            "could not restore {archive_key}: {}",
+                            errors::describe(&err)
+                        )),
+                    }
+                }
+            },
+            Err(err) => Some(format!(
+                "could not read {archive_key}: {}",
+                errors::describe(&err)
+            )),
+        };
+        if let Some(reason) = unreadable {
+            for entry in &entries {
+                history.record(&job_id, &entry.key, Some(reason.clone()));
+            }
+            recorded += entries.len();
+            waiting += entries.len();
+            continue;
+        }
+
+        entries.sort_by_key(|entry| entry.offset);
+        let mut reader = MemberReader::new(s3, &index, entries.clone());
+        for (position, expected) in entries.iter().enumerate() {
+            if monitor.stopping() {
+                break 'archives;
+            }
+            let Some(next) = monitor.run(app, reader.next()).await? else {
+                break 'archives;
+            };
+            let (entry, content) = match next {
+                Ok(Some(member)) => member,
+                Ok(None) => break,
+                Err(err) => {
+                    // Members after a bad one cannot be read from a stream either
+                    let reason = errors::describe(&err);
+                    app.push_job_error(
+                        &job_id,
+                        &tf(
+                            "error.read_bundled",
+                            &[("key", &expected.key), ("archive_key", &archive_key)],
+                        ),
+                        &err,
+                    );
+                    for entry in &entries[position..] {
+                        history.record(&job_id, &entry.key, Some(reason.clone()));
+                    }
+                    recorded += entries.len() - position;
+                    failed += entries.len() - position;
+                    break;
+                }
+            };
+            app.update(AppEvent::JobProgress {
+                done: recorded + 1,
+                key: entry.key.clone(),
+            });
+            let result = if local {
+                write_local_member(&bucket, &entry.key, &content)
+            } else {
+                let Some(result) = monitor
+                    .run(app, write_back_member(s3, &bucket, &entry.key, content))
+                    .await?
+                else {
+                    break 'archives;
+                };
+                result
+            };
+            recorded += 1;
+            match result {
+                Ok(note) => {
+                    retrieved += 1;
+                    history.record_with_note(&job_id, &entry.key, None, note);
+                }
+                Err(err) => {
+                    failed += 1;
+                    history.record(&job_id, &entry.key, Some(errors::describe(&err)));
+                    app.push_job_error(
+                        &job_id,
+                        &tf("error.retrieve_bundled", &[("key", &entry.key)]),
+                        &err,
+                    );
+                }
+            }
+        }
+    }
+    if recorded < total {
+        history.interrupt(&job_id);
+    } else {
+        history.finish(&job_id);
+    }
+    app.progress = None;
+    app.set_mode(AppMode::Browsing);
+    if retrieved > 0 {
+        app.note_reached();
+    }
+    app.push_status(&tf(
+        "status.bundled_retrieved",
+        &[
+            ("retrieved", &retrieved),
+            ("target", &target),
+            ("waiting", &waiting),
+            ("failed", &failed),
+        ],
+    ));
+    Ok(())
+}
+
+/// Put a member back under its key, unless an object is already there
+async fn write_back_member(
+    s3: &S3Service,
+    bucket: &str,
+    key: &str,
+    content: Vec<u8>,
+) -> Result<Option<String>> {
+    if s3.fingerprint(bucket, key).await?.is_some() {
+        return Ok(Some("already in the bucket; left as it is".to_string()));
+    }
+    s3.put_object_bytes(bucket, key, content, &ObjectAttributes::default())
+        .await?;
+    Ok(None)
+}
+
+fn write_local_member(bucket: &str, key: &str, content: &[u8]) -> Result<Option<String>> {
+    let path = bundle::local_path(bucket, key);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(&path, content)?;
+    Ok(Some(format!("written to {}", path.display())))
+}
+
+/// Write small objects into one archive and its index, then delete the
+/// originals if asked to once the archive is verified
+#[allow(clippy::too_many_arguments)]
+async fn execute_bundle(
+    monitor: &mut BatchMonitor<'_>,
+    app: &mut App,
+    s3: &S3Service,
+    history: &mut JobHistory,
+    objects: Vec<ObjectInfo>,
+    archive_key: String,
+    compression: ArchiveCompression,
+    delete_originals: bool,
+) -> Result<()> {
+    let Some(bucket) = app.selected_bucket_name().map(str::to_string) else {
+        return Ok(());
+    };
+    let delete_originals = delete_originals && !app.no_delete;
+    let total = objects.len();
+    app.progress = Some(crate::app::ProgressState::new(
+        format!("Bundling into {archive_key}"),
+        total,
+    ));
+    app.set_mode(AppMode::ShowingProgress);
+    let job_id = begin_job(app, history, JobKind::Bundle, &bucket, &archive_key, total);
+
+    let upload = Cell::new(None);
+    let latest = RefCell::new(None);
+    let write = bundle::write_archive(
+        s3,
+        &bucket,
+        &objects,
+        &archive_key,
+        compression,
+        &upload,
+        monitor.stop,
+        |done, key| {
+            latest.replace(Some((done, key.to_string())));
+        },
+    );
+    tokio::pin!(write);
+    // Not dropped on abort: the archive checks the stop choice before each
+    // object and aborts its own upload
+    let result = loop {
+        if let Some((done, key)) = latest.take() {
+            app.update(AppEvent::JobProgress { done, key });
+        }
+        monitor.tick(app)?;
+        tokio::select! {
+            result = &mut write => break result,
+            _ = tokio::time::sleep(BATCH_TICK) => {}
+        }
+    };
+    let index = match result {
+        Ok(index) => index,
+        Err(err) => {
+            let reason = errors::describe(&err);
+            for obj in &objects {
+                history.record(&job_id, &obj.key, Some(reason.clone()));
+            }
+            if monitor.stopping() {
+                history.interrupt(&job_id);
+            } else {
+                history.finish(&job_id);
+            }
+            app.progress = None;
+            app.set_mode(AppMode::Browsing);
+            app.push_job_error(
+                &job_id,
+                &tf("error.bundling", &[("archive_key", &archive_key)]),
+                &err,
+            );
+            app.push_status(t("status.nothing_archived"));
+            return Ok(());
+        }
+    };
+    app.note_reached();
+    if let Err(err) = app.bundles.add(index.clone()) {
+        app.push_error(t("error.save_bundle_index"), &err);
+    }
+
+    let mut deleted = 0;
+    let mut failed = 0;
+    let mut recorded = 0;
+    for (index, entry) in index.entries.iter().enumerate() {
+        if !delete_originals {
+            let note = format!("in {archive_key}");
+            history.record_with_note(&job_id, &entry.key, None, Some(note));
+            recorded += 1;
+            continue;
+        }
+        if monitor.stopping() {
+            break;
+        }
+        app.update(AppEvent::JobProgress {
+            done: index + 1,
+            key: entry.key.clone(),
+        });
+        let Some(result) = monitor
+            .run(app, s3.delete_object(&bucket, &entry.key))
+            .await?
+        else {
+            break;
+        };
+        recorded += 1;
+        match result {
+            Ok(()) => {
+                deleted += 1;
+                let note = format!("in {archive_key}; source deleted");
+                history.record_with_note(&job_id, &entry.key, None, Some(note));
+            }
+            Err(err) => {
+                failed += 1;
+                let reason = format!(
+                    "archived, but deleting the source failed: {}",
+                    errors::describe(&err)
+                );
+                app.push_job_status(&job_id, &format!("{}: {reason}", entry.key));
+                history.record(&job_id, &entry.key, Some(reason));
+            }
+        }
+    }
+    if recorded < total {
+        history.interrupt(&job_id);
+    } else {
+        history.finish(&job_id);
+    }
+    app.progress = None;
+    app.set_mode(AppMode::Browsing);
+
+    app.push_status(&tf(
+        "status.bundled",
+        &[
+            ("count", &index.entries.len()),
+            ("archive_key", &archive_key),
+            ("size", &waves::format_bytes(index.archive_size as u64)),
+            ("index", &bundle::index_key(&archive_key)),
+        ],
+    ));
+    if delete_originals {
+        let key = if failed > 0 {
+            "status.bundle_sources_deleted_failed"
+        } else {
+            "status.bundle_sources_deleted"
+        };
+        app.push_status(&tf(
+            key,
+            &[
+                ("deleted", &deleted),
+                ("bucket", &bucket),
+                ("failed", &failed),
+            ],
+        ));
+        if recorded < total {
+            app.push_status(t("status.bundle_delete_stopped"));
+        }
+    }
+    Ok(())
+}
+
+/// Abort the multipart upload of an object whose copy was cancelled mid-flight
+async fn abort_interrupted_upload(
+    app: &mut App,
+    s3: &S3Service,
+    uploads: &mut MultipartStore,
+    dest_bucket: &str,
+    key: &str,
+) {
+    let Some(upload) = uploads.find(dest_bucket, key).cloned() else {
+        return;
+    };
+    match s3.abort_multipart_copy(&upload).await {
+        Ok(()) => uploads.remove(&upload.upload_id),
+        Err(err) => app.push_status(&tf(
+            "status.abort_upload_failed",
+            &[("key", &key), ("error", &errors::describe(&err))],
+        )),
+    }
+}
+
+/// Begin a job in the journal, with a log for its per-object messages and
+/// the note and ticket given in the confirmation
+fn begin_job(
+    app: &mut App,
+    history: &mut JobHistory,
+    kind: JobKind,
+    bucket: &str,
+    target: &str,
+    total: usize,
+) -> String {
+    let (note, ticket) = (app.job_note.clone(), app.job_ticket.clone());
+    begin_job_with_note(app, history, kind, bucket, target, total, note, ticket)
+}
+
+/// Begin a job that carries a note and ticket given earlier, such as a
+/// shard plan's
+#[allow(clippy::too_many_arguments)]
+fn begin_job_with_note(
+    app: &mut App,
+    history: &mut JobHistory,
+    kind: JobKind,
+    bucket: &str,
+    target: &str,
+    total: usize,
+    note: Option<String>,
+    ticket: Option<TicketLink>,
+) -> String {
+    let job_id = history.begin(kind, bucket, target, total, note, ticket);
+    app.begin_job_log(&job_id, &format!("{} {bucket} → {target}", kind.label()));
+    job_id
+}
+
+/// Run the `--object-hook` command for an object a batch just processed.
+/// Returns `false` when the batch should stop: the user aborted the hook, or
+/// it failed under `--hook-failure stop`.
+async fn run_object_hook(
+    monitor: &mut BatchMonitor<'_>,
+    app: &mut App,
+    job_id: &str,
+    bucket: &str,
+    key: &str,
+    action: &str,
+) -> Result<bool> {
+    let Some(hook) = app.object_hook.clone() else {
+        return Ok(true);
+    };
+    let Some(result) = monitor.run(app, hook.run(bucket, key, action)).await? else {
+        return Ok(false);
+    };
+    let Err(err) = result else {
+        return Ok(true);
+    };
+    match hook.on_failure {
+        HookFailure::Ignore => Ok(true),
+        HookFailure::Warn => {
+            app.push_job_error(job_id, &tf("error.hook", &[("key", &key)]), &err);
+            Ok(true)
+        }
+        HookFailure::Stop => {
+            app.push_error(&tf("error.hook_stopping", &[("key", &key)]), &err);
+            Ok(false)
+        }
+    }
+}
+
+async fn execute_transition(
+    monitor: &mut BatchMonitor<'_>,
+    app: &mut App,
+    s3: &S3Service,
+    history: &mut JobHistory,
+    uploads: &mut MultipartStore,
+    target_class: StorageClassTier,
+    lock: ObjectLockPlan,
+) -> Result<()> {
+    let bucket = app
+        .selected_bucket_name()
+        .context("Select a bucket before transitioning")?
+        .to_string();
+    if !lock.is_empty() {
+        match s3.object_lock_enabled(&bucket).await {
+            Ok(true) => {}
+            Ok(false) => {
+                app.push_status(&tf("status.no_object_lock", &[("bucket", &bucket)]));
+                return Ok(());
+            }
+            Err(err) => {
+                app.push_error(&tf("error.object_lock_check", &[("bucket", &bucket)]), &err);
+                return Ok(());
+            }
+        }
+    }
+    let (keys, skipped) = transition_keys(app, &target_class);
+    if skipped > 0 {
+        app.push_status(&tf(
+            "status.skipped_already_class",
+            &[("count", &skipped), ("class", &target_class.label())],
+        ));
+    }
+    if keys.is_empty() {
+        if skipped == 0 {
+            app.push_status(t("status.no_transition_targets"));
+        }
+        return Ok(());
+    }
+
+    // Initialize progress tracking
+    let total = keys.len();
+    app.progress = Some(crate::app::ProgressState::new(
+        format!("Transitioning to {}", target_class.label()),
+        total,
+    ));
+    app.set_mode(AppMode::ShowingProgress);
+    let job_id = begin_job(
+        app,
+        history,
+        JobKind::Transition,
+        &bucket,
+        &transition_label(&target_class, &lock),
+        total,
+    );
+
+    let mut success_count = 0;
+    let mut error_count = 0;
+    // Keys moved so far with their original class, for a rollback
+    let mut completed: Vec<(String, StorageClassTier)> = Vec::new();
+    let mut errors: Vec<(String, usize)> = Vec::new();
+    let mut next_check = FAILURE_SAMPLE;
+    let mut rollback = false;
+
+    for (index, key) in keys.iter().enumerate() {
+        if monitor.stopping() {
+            break;
+        }
+        // Update progress
+        app.update(AppEvent::JobProgress {
+            done: index + 1,
+            key: key.clone(),
+        });
+
+        let original = object_class(app, key);
+        let size = object_size(app, key);
+        let large = size > MULTIPART_COPY_THRESHOLD;
+        let step = transition_object(
+            s3,
+            uploads,
+            &bucket,
+            key,
+            large,
+            target_class.clone(),
+            monitor.stop,
+        );
+        let sent = Instant::now();
+        let Some(result) = monitor.run(app, step).await? else {
+            abort_interrupted_upload(app, s3, uploads, &bucket, key).await;
+            break;
+        };
+        history.time_request(&job_id, sent.elapsed());
+        let copied = if result.is_ok() { size as u64 } else { 0 };
+        app.telemetry
+            .record(copy_api(large), sent.elapsed(), result.is_ok(), copied);
+        if result.is_ok() {
+            app.note_reached();
+        }
+        match result {
+            Ok(CopyOutcome::Completed) => {
+                completed.push((key.clone(), original));
+                let applied = if lock.is_empty() {
+                    Ok(None)
+                } else {
+                    apply_object_lock(s3, &bucket, key, &lock).await.map(Some)
+                };
+                match applied {
+                    Ok(note) => {
+                        success_count += 1;
+                        history.record_with_note(&job_id, key, None, note);
+                    }
+                    Err(reason) => {
+                        error_count += 1;
+                        match errors.iter_mut().find(|(code, _)| code == "ObjectLock") {
+                            Some((_, count)) => *count += 1,
+                            None => errors.push(("ObjectLock".to_string(), 1)),
+                        }
+                        app.push_job_status(&job_id, &format!("{key}: {reason}"));
+                        history.record(&job_id, key, Some(reason));
+                    }
+                }
+                if !run_object_hook(monitor, app, &job_id, &bucket, key, "transition").await? {
+                    break;
+                }
+            }
+            Ok(CopyOutcome::Checkpointed) => {
+                app.push_status(&tf("status.checkpointed_upload", &[("key", &key)]));
+                break;
+            }
+            Err(err) => {
+                error_count += 1;
+                let category = errors::category(&err);
+                match errors.iter_mut().find(|(code, _)| *code == category) {
+                    Some((_, count)) => *count += 1,
+                    None => errors.push((category, 1)),
+                }
+                history.record(&job_id, key, Some(errors::describe(&err)));
+                app.push_job_error(&job_id, &tf("error.transition", &[("key", &key)]), &err);
+            }
+        }
+
+        let attempted = success_count + error_count;
+        if attempted >= next_check
+            && attempted < total
+            && error_count as f64 / attempted as f64 > FAILURE_PAUSE_RATE
+        {
+            let mut breakdown = errors.clone();
+            breakdown.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+            notify::failure_rate(
+                &bucket,
+                attempted,
+                error_count,
+                breakdown.first().map(|(category, _)| category.as_str()),
+            );
+            let pause = BatchPause {
+                attempted,
+                failed: error_count,
+                breakdown,
+                choice: None,
+            };
+            match monitor.pause(app, pause).await? {
+                PauseChoice::Continue => next_check = attempted + FAILURE_SAMPLE,
+                PauseChoice::Abort => break,
+                PauseChoice::Rollback => {
+                    rollback = true;
+                    break;
+                }
+            }
+        }
+    }
+    let processed = success_count + error_count;
+    if processed < total {
+        history.interrupt(&job_id);
+    } else {
+        history.finish(&job_id);
+    }
+
+    // Clear progress and return to browsing
+    app.progress = None;
+    app.set_mode(AppMode::Browsing);
+
+    // Show summary
+    if processed < total {
+        app.push_status(&tf(
+            "status.transition_stopped",
+            &[
+                ("processed", &processed),
+                ("total", &total),
+                ("failed", &error_count),
+            ],
+        ));
+    } else if error_count > 0 {
+        app.push_status(&tf(
+            "status.transition_complete",
+            &[
+                ("succeeded", &success_count),
+                ("failed", &error_count),
+                ("skipped", &skipped),
+            ],
+        ));
+    } else {
+        app.push_status(&tf(
+            "status.transitioned",
+            &[
+                ("count", &success_count),
+                ("class", &target_class.label()),
+                ("skipped", &skipped),
+            ],
+        ));
+    }
+
+    if rollback {
+        rollback_transition(monitor, app, s3, history, uploads, &bucket, &completed).await?;
+        app.progress = None;
+        app.set_mode(AppMode::Browsing);
+    }
+
+    load_objects_for_selection(app, s3);
+    Ok(())
+}
+
+/// Copy the targeted objects onto themselves under `kms_key_id`. Keys the
+/// journal already records as re-encrypted under that key are skipped, as
+/// are objects HeadObject shows are already using it.
+async fn execute_reencrypt(
+    monitor: &mut BatchMonitor<'_>,
+    app: &mut App,
+    s3: &S3Service,
+    history: &mut JobHistory,
+    uploads: &mut MultipartStore,
+    kms_key_id: String,
+) -> Result<()> {
+    let bucket = app
+        .selected_bucket_name()
+        .context("Select a bucket before re-encrypting")?
+        .to_string();
+    let (keys, archived) = reencrypt_keys(app);
+    if archived > 0 {
+        app.push_status(&tf(
+            "status.skipped_archived_reencrypt",
+            &[("archived", &archived)],
+        ));
+    }
+    let done = history.succeeded_for(JobKind::Reencrypt, &bucket, &kms_key_id);
+    let keys: Vec<String> = keys.into_iter().filter(|key| !done.contains(key)).collect();
+    let journaled = target_count(app) - archived - keys.len();
+    if journaled > 0 {
+        app.push_status(&tf(
+            "status.skipped_reencrypted",
+            &[("journaled", &journaled), ("kms_key_id", &kms_key_id)],
+        ));
+    }
+    if keys.is_empty() {
+        if archived == 0 && journaled == 0 {
+            app.push_status(t("status.no_reencrypt_targets"));
+        }
+        return Ok(());
+    }
+
+    let total = keys.len();
+    app.progress = Some(crate::app::ProgressState::new(
+        format!("Re-encrypting under {kms_key_id}"),
+        total,
+    ));
+    app.set_mode(AppMode::ShowingProgress);
+    let job_id = begin_job(
+        app,
+        history,
+        JobKind::Reencrypt,
+        &bucket,
+        &kms_key_id,
+        total,
+    );
+
+    let mut success_count = 0;
+    let mut error_count = 0;
+    let mut unchanged = 0;
+    for (index, key) in keys.iter().enumerate() {
+        if monitor.stopping() {
+            break;
+        }
+        app.update(AppEvent::JobProgress {
+            done: index + 1,
+            key: key.clone(),
+        });
+
+        // Objects encrypted under the key by an earlier tool or an unjournaled
+        // run don't need another copy
+        let Some(current) = monitor.run(app, s3.object_kms_key(&bucket, key)).await? else {
+            break;
+        };
+        match current {
+            Ok(current) if uses_kms_key(current.as_deref(), &kms_key_id) => {
+                unchanged += 1;
+                history.record_with_note(
+                    &job_id,
+                    key,
+                    None,
+                    Some("already under this key".to_string()),
+                );
+                continue;
+            }
+            Ok(_) => {}
+            Err(err) => {
+                error_count += 1;
+                history.record(&job_id, key, Some(errors::describe(&err)));
+                app.push_job_error(&job_id, &tf("error.reencrypt", &[("key", &key)]), &err);
+                continue;
+            }
+        }
+
+        let class = object_class(app, key);
+        let size = object_size(app, key);
+        let large = size > MULTIPART_COPY_THRESHOLD;
+        let step = reencrypt_object(
+            s3,
+            uploads,
+            &bucket,
+            key,
+            large,
+            class,
+            &kms_key_id,
+            monitor.stop,
+        );
+        let sent = Instant::now();
+        let Some(result) = monitor.run(app, step).await? else {
+            abort_interrupted_upload(app, s3, uploads, &bucket, key).await;
+            break;
+        };
+        history.time_request(&job_id, sent.elapsed());
+        let copied = if result.is_ok() { size as u64 } else { 0 };
+        app.telemetry
+            .record(copy_api(large), sent.elapsed(), result.is_ok(), copied);
+        if result.is_ok() {
+            app.note_reached();
+        }
+        match result {
+            Ok(CopyOutcome::Completed) => {
+                success_count += 1;
+                history.record(&job_id, key, None);
+                if !run_object_hook(monitor, app, &job_id, &bucket, key, "reencrypt").await? {
+                    break;
+                }
+            }
+            Ok(CopyOutcome::Checkpointed) => {
+                app.push_status(&tf("status.checkpointed_upload", &[("key", &key)]));
+                break;
+            }
+            Err(err) => {
+                error_count += 1;
+                history.record(&job_id, key, Some(errors::describe(&err)));
+                app.push_job_error(&job_id, &tf("error.reencrypt", &[("key", &key)]), &err);
+            }
+        }
+    }
+    let processed = success_count + error_count + unchanged;
+    if processed < total {
+        history.interrupt(&job_id);
+    } else {
+        history.finish(&job_id);
+    }
+
+    app.progress = None;
+    app.set_mode(AppMode::Browsing);
+
+    if processed < total {
+        app.push_status(&tf(
+            "status.reencrypt_stopped",
+            &[
+                ("processed", &processed),
+                ("total", &total),
+                ("failed", &error_count),
+            ],
+        ));
+    } else {
+        app.push_status(&tf(
+            "status.reencrypted",
+            &[
+                ("count", &success_count),
+                ("key", &kms_key_id),
+                ("unchanged", &unchanged),
+                ("failed", &error_count),
+            ],
+        ));
+    }
+    Ok(())
+}
+
+/// Permanently delete noncurrent versions and delete markers, one request
+/// at a time, and drop their rows from the version view
+async fn execute_delete_versions(
+    monitor: &mut BatchMonitor<'_>,
+    app: &mut App,
+    s3: &S3Service,
+    history: &mut JobHistory,
+    versions: Vec<ObjectInfo>,
+) -> Result<()> {
+    let bucket = app
+        .selected_bucket_name()
+        .context("Select a bucket before deleting versions")?
+        .to_string();
+    if versions.is_empty() {
+        app.push_status(t("status.no_noncurrent_selected"));
+        return Ok(());
+    }
+
+    let total = versions.len();
+    app.progress = Some(crate::app::ProgressState::new(
+        "Deleting noncurrent versions".to_string(),
+        total,
+    ));
+    app.set_mode(AppMode::ShowingProgress);
+    let job_id = begin_job(
+        app,
+        history,
+        JobKind::DeleteVersions,
+        &bucket,
+        "noncurrent versions",
+        total,
+    );
+
+    let mut deleted = HashSet::new();
+    let mut freed: i64 = 0;
+    let mut error_count = 0;
+    for (index, obj) in versions.iter().enumerate() {
+        if monitor.stopping() {
+            break;
+        }
+        let Some(version) = &obj.version else {
+            continue;
+        };
+        app.update(AppEvent::JobProgress {
+            done: index + 1,
+            key: obj.key.clone(),
+        });
+        let sent = Instant::now();
+        let request = s3.delete_object_version(&bucket, &obj.key, &version.id);
+        let Some(result) = monitor.run(app, request).await? else {
+            break;
+        };
+        history.time_request(&job_id, sent.elapsed());
+        app.telemetry
+            .record("DeleteObject", sent.elapsed(), result.is_ok(), 0);
+        let note = Some(format!("version {}", version.id));
+        match result {
+            Ok(()) => {
+                app.note_reached();
+                history.record_with_note(&job_id, &obj.key, None, note);
+                freed += obj.size;
+                deleted.insert((obj.key.clone(), version.id.clone()));
+            }
+            Err(err) => {
+                error_count += 1;
+                history.record_with_note(&job_id, &obj.key, Some(errors::describe(&err)), note);
+                app.push_job_error(
+                    &job_id,
+                    &tf(
+                        "error.delete_version",
+                        &[("version", &version.id), ("key", &obj.key)],
+                    ),
+                    &err,
+                );
+            }
+        }
+    }
+    let processed = deleted.len() + error_count;
+    if processed < total {
+        history.interrupt(&job_id);
+    } else {
+        history.finish(&job_id);
+    }
+
+    app.objects.retain(|obj| {
+        obj.version
+            .as_ref()
+            .is_none_or(|v| !deleted.contains(&(obj.key.clone(), v.id.clone())))
+    });
+    app.refilter_keeping_selection();
+    app.progress = None;
+    app.set_mode(AppMode::Browsing);
+
+    if processed < total {
+        app.push_status(&tf(
+            "status.delete_versions_stopped",
+            &[
+                ("processed", &processed),
+                ("total", &total),
+                ("failed", &error_count),
+            ],
+        ));
+    } else {
+        app.push_status(&tf(
+            "status.versions_deleted",
+            &[
+                ("count", &deleted.len()),
+                ("size", &waves::format_bytes(freed.max(0) as u64)),
+                ("failed", &error_count),
+            ],
+        ));
+    }
+    Ok(())
+}
+
+/// Move noncurrent versions to `target_class` one key at a time, then list
+/// the versions again, since every replayed version has a new ID
+async fn execute_transition_versions(
+    monitor: &mut BatchMonitor<'_>,
+    app: &mut App,
+    s3: &S3Service,
+    history: &mut JobHistory,
+    target_class: StorageClassTier,
+    replays: Vec<VersionReplay>,
+) -> Result<()> {
+    let bucket = app
+        .selected_bucket_name()
+        .context("Select a bucket before moving versions")?
+        .to_string();
+    let total = replays.len();
+    app.progress = Some(crate::app::ProgressState::new(
+        format!("Moving noncurrent versions to {}", target_class.label()),
+        total,
+    ));
+    app.set_mode(AppMode::ShowingProgress);
+    let job_id = begin_job(
+        app,
+        history,
+        JobKind::TransitionVersions,
+        &bucket,
+        target_class.label(),
+        total,
+    );
+
+    let (mut moved, mut bytes, mut processed, mut error_count) = (0, 0i64, 0, 0);
+    for (index, replay) in replays.iter().enumerate() {
+        if monitor.stopping() {
+            break;
+        }
+        app.update(AppEvent::JobProgress {
+            done: index + 1,
+            key: replay.key.clone(),
+        });
+        let sent = Instant::now();
+        let run = replay.run(s3, &bucket);
+        tokio::pin!(run);
+        // Not dropped on abort: a key stopped between its copies and the
+        // deletes would be left with a replayed version on top
+        let result = loop {
+            monitor.tick(app)?;
+            tokio::select! {
+                result = &mut run => break result,
+                _ = tokio::time::sleep(BATCH_TICK) => {}
+            }
+        };
+        history.time_request(&job_id, sent.elapsed());
+        app.telemetry
+            .record("CopyObject", sent.elapsed(), result.is_ok(), 0);
+        processed += 1;
+        let note = Some(format!(
+            "{} versions to {}, {} rewritten in place",
+            replay.moved(),
+            target_class.label(),
+            replay.rewritten()
+        ));
+        match result {
+            Ok(()) => {
+                app.note_reached();
+                history.record_with_note(&job_id, &replay.key, None, note);
+                moved += replay.moved();
+                bytes += replay.moved_bytes();
+            }
+            Err(err) => {
+                error_count += 1;
+                history.record_with_note(&job_id, &replay.key, Some(errors::describe(&err)), note);
+                app.push_job_error(
+                    &job_id,
+                    &tf("error.move_versions", &[("key", &replay.key)]),
+                    &err,
+                );
+            }
+        }
+    }
+    if processed < total {
+        history.interrupt(&job_id);
+    } else {
+        history.finish(&job_id);
+    }
+    app.progress = None;
+    app.set_mode(AppMode::Browsing);
+
+    if processed < total {
+        app.push_status(&tf(
+            "status.move_versions_stopped",
+            &[
+                ("processed", &processed),
+                ("total", &total),
+                ("failed", &error_count),
+            ],
+        ));
+    } else {
+        app.push_status(&tf(
+            "status.versions_moved",
+            &[
+                ("count", &moved),
+                ("size", &waves::format_bytes(bytes.max(0) as u64)),
+                ("class", &target_class.label()),
+                ("failed", &error_count),
+            ],
+        ));
+    }
+    if processed > error_count {
+        load_objects_for_selection(app, s3);
+    }
+    Ok(())
+}
+
+/// Request restores of archived noncurrent versions, one at a time. They
+/// are journaled but not tracked: the Pending Restores table follows keys.
+async fn execute_restore_versions(
+    monitor: &mut BatchMonitor<'_>,
+    app: &mut App,
+    s3: &S3Service,
+    history: &mut JobHistory,
+    versions: Vec<ObjectInfo>,
+    days: i32,
+    tier: RestoreTier,
+) -> Result<()> {
+    let bucket = app
+        .selected_bucket_name()
+        .context("Select a bucket before restoring versions")?
+        .to_string();
+    let total = versions.len();
+    app.progress = Some(crate::app::ProgressState::new(
+        "Restoring noncurrent versions".to_string(),
+        total,
+    ));
+    app.set_mode(AppMode::ShowingProgress);
+    let target = format!("{days} days, {}", tier.label());
+    let job_id = begin_job(
+        app,
+        history,
+        JobKind::RestoreVersions,
+        &bucket,
+        &target,
+        total,
+    );
+
+    let (mut requested, mut error_count) = (0, 0);
+    for (index, obj) in versions.iter().enumerate() {
+        if monitor.stopping() {
+            break;
+        }
+        let Some(version) = &obj.version else {
+            continue;
+        };
+        app.update(AppEvent::JobProgress {
+            done: index + 1,
+            key: obj.key.clone(),
+        });
+        let sent = Instant::now();
+        let request = s3.request_version_restore(&bucket, &obj.key, &version.id, days, tier);
+        let Some(result) = monitor.run(app, request).await? else {
+            break;
+        };
+        history.time_request(&job_id, sent.elapsed());
+        app.telemetry
+            .record("RestoreObject", sent.elapsed(), result.is_ok(), 0);
+        let note = Some(format!("version {}", version.id));
+        match result {
+            Ok(()) => {
+                app.note_reached();
+                history.record_with_note(&job_id, &obj.key, None, note);
+                requested += 1;
+            }
+            Err(err) => {
+                error_count += 1;
+                history.record_with_note(&job_id, &obj.key, Some(errors::describe(&err)), note);
+                app.push_job_error(
+                    &job_id,
+                    &tf(
+                        "error.restore_version",
+                        &[("version", &version.id), ("key", &obj.key)],
+                    ),
+                    &err,
+                );
+            }
+        }
+    }
+    let processed = requested + error_count;
+    if processed < total {
+        history.interrupt(&job_id);
+    } else {
+        history.finish(&job_id);
+    }
+    app.progress = None;
+    app.set_mode(AppMode::Browsing);
+
+    if processed < total {
+        app.push_status(&tf(
+            "status.restore_versions_stopped",
+            &[
+                ("processed", &processed),
+                ("total", &total),
+                ("failed", &error_count),
+            ],
+        ));
+    } else {
+        app.push_status(&tf(
+            "status.versions_restore_requested",
+            &[
+                ("count", &requested),
+                ("days", &days),
+                ("failed", &error_count),
+            ],
+        ));
+    }
+    Ok(())
+}
+
+/// Return the objects a paused transition already moved to their original class
+async fn rollback_transition(
+    monitor: &mut BatchMonitor<'_>,
+    app: &mut App,
+    s3: &S3Service,
+    history: &mut JobHistory,
+    uploads: &mut MultipartStore,
+    bucket: &str,
+    completed: &[(String, StorageClassTier)],
+) -> Result<()> {
+    if completed.is_empty() {
+        app.push_status(t("status.nothing_to_roll_back"));
+        return Ok(());
+    }
+
+    let total = completed.len();
+    app.progress = Some(crate::app::ProgressState::new(
+        "Rolling back to original classes".to_string(),
+        total,
+    ));
+    app.set_mode(AppMode::ShowingProgress);
+    let job_id = begin_job(
+        app,
+        history,
+        JobKind::Rollback,
+        bucket,
+        "original class",
+        total,
+    );
+
+    let mut restored = 0;
+    let mut failed = 0;
+    for (index, (key, class)) in completed.iter().enumerate() {
+        if monitor.stopping() {
+            break;
+        }
+        app.update(AppEvent::JobProgress {
+            done: index + 1,
+            key: key.clone(),
+        });
+
+        let size = object_size(app, key);
+        let large = size > MULTIPART_COPY_THRESHOLD;
+        let step = transition_object(s3, uploads, bucket, key, large, class.clone(), monitor.stop);
+        let sent = Instant::now();
+        let Some(result) = monitor.run(app, step).await? else {
+            abort_interrupted_upload(app, s3, uploads, bucket, key).await;
+            break;
+        };
+        history.time_request(&job_id, sent.elapsed());
+        let copied = if result.is_ok() { size as u64 } else { 0 };
+        app.telemetry
+            .record(copy_api(large), sent.elapsed(), result.is_ok(), copied);
+        if result.is_ok() {
+            app.note_reached();
+        }
+        match result {
+            Ok(CopyOutcome::Completed) => {
+                restored += 1;
+                history.record(&job_id, key, None);
+            }
+            Ok(CopyOutcome::Checkpointed) => {
+                app.push_status(&tf("status.checkpointed_upload", &[("key", &key)]));
+                break;
+            }
+            Err(err) => {
+                failed += 1;
+                history.record(&job_id, key, Some(errors::describe(&err)));
+                let context = tf(
+                    "error.rollback",
+                    &[("key", &key), ("class", &object_class(app, key).label())],
+                );
+                app.push_job_error(&job_id, &context, &err);
+            }
+        }
+    }
+    if restored + failed < total {
+        history.interrupt(&job_id);
+    } else {
+        history.finish(&job_id);
+    }
+
+    app.push_status(&tf(
+        "status.rolled_back",
+        &[
+            ("restored", &restored),
+            ("total", &total),
+            ("failed", &failed),
+        ],
+    ));
+    Ok(())
+}
+
+/// Archived target objects that still need a restore request: not restoring
+/// or restored already, and not queued by another restore
+fn restore_candidates(app: &mut App, tracker: &mut RestoreTracker, bucket: &str) -> Vec<String> {
+    // Get objects and filter to only those needing restore
+    let all_keys = target_keys(app);
+    let objects_map: std::collections::HashMap<_, _> = app
+        .target_objects()
+        .into_iter()
+        .map(|o| (o.key.clone(), o))
+        .collect();
+
+    let mut keys_to_restore = Vec::new();
+    let mut already_restoring = 0;
+    let mut already_available = 0;
+
+    for key in &all_keys {
+        if let Some(obj) = objects_map.get(key) {
+            match &obj.restore_state {
+                Some(crate::models::RestoreState::InProgress { .. }) => {
+                    already_restoring += 1;
+                }
+                Some(crate::models::RestoreState::Available) => {
+                    already_available += 1;
+                }
+                _ => {
+                    // Only restore if it's a Glacier object that needs restore
+                    if matches!(
+                        obj.storage_class,
+                        crate::models::StorageClassTier::GlacierFlexibleRetrieval
+                            | crate::models::StorageClassTier::GlacierDeepArchive
+                    ) {
+                        keys_to_restore.push(key.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    if already_restoring > 0 {
+        app.push_status(&tf(
+            "status.skipped_restoring",
+            &[("count", &already_restoring)],
+        ));
+    }
+    if already_available > 0 {
+        app.push_status(&tf(
+            "status.skipped_restored",
+            &[("count", &already_available)],
+        ));
+    }
+
+    // Keys another mask or selection already queued are requested once
+    tracker.reload();
+    let queued = keys_to_restore.len();
+    let keys_to_restore = tracker.coalesce(bucket, &keys_to_restore);
+    if keys_to_restore.len() < queued {
+        app.push_status(&tf(
+            "status.skipped_queued",
+            &[("count", &(queued - keys_to_restore.len()))],
+        ));
+    }
+
+    keys_to_restore
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn execute_restore(
+    monitor: &mut BatchMonitor<'_>,
+    app: &mut App,
+    s3: &S3Service,
+    tracker: &mut RestoreTracker,
+    history: &mut JobHistory,
+    days: i32,
+    tier: RestoreTier,
+    preset: Option<String>,
+) -> Result<()> {
+    let bucket = app
+        .selected_bucket_name()
+        .context("Select a bucket before restoring")?
+        .to_string();
+
+    let keys_to_restore = restore_candidates(app, tracker, &bucket);
+    if keys_to_restore.is_empty() {
+        app.push_status(t("status.no_restore_needed"));
+        return Ok(());
+    }
+
+    let total_bytes: i64 = keys_to_restore.iter().map(|k| object_size(app, k)).sum();
+    if let Some(wave_bytes) = app.restore_pacing.wave_bytes
+        && total_bytes as u64 > wave_bytes
+    {
+        let objects = keys_to_restore
+            .iter()
+            .map(|k| (k.clone(), object_size(app, k)))
+            .collect();
+        let mut plan = WavePlan::new(&bucket, days, tier, wave_bytes, objects);
+        plan.preset = preset;
+        plan.note = app.job_note.clone();
+        plan.ticket = app.job_ticket.clone();
+        app.push_status(&tf(
+            "status.waves_planned",
+            &[
+                ("count", &plan.waves.len()),
+                ("size", &waves::format_bytes(wave_bytes)),
+            ],
+        ));
+        let (plan_id, first) = (plan.id.clone(), plan.waves[0].keys.clone());
+        tracker.add_wave_plan(plan);
+        return execute_restore_wave(monitor, app, s3, tracker, history, &plan_id, 0, first).await;
+    }
+
+    let operation = "Requesting Glacier restore".to_string();
+    submit_restores(
+        monitor,
+        app,
+        s3,
+        tracker,
+        history,
+        &bucket,
+        &keys_to_restore,
+        days,
+        tier,
+        preset.as_deref(),
+        operation,
+    )
+    .await
+}
+
+/// Submit one wave of a planned restore
+#[allow(clippy::too_many_arguments)]
+async fn execute_restore_wave(
+    monitor: &mut BatchMonitor<'_>,
+    app: &mut App,
+    s3: &S3Service,
+    tracker: &mut RestoreTracker,
+    history: &mut JobHistory,
+    plan_id: &str,
+    wave: usize,
+    keys: Vec<String>,
+) -> Result<()> {
+    let Some(plan) = tracker.wave_plan(plan_id) else {
+        return Ok(());
+    };
+    let (bucket, days, tier, count) = (plan.bucket.clone(), plan.days, plan.tier, plan.waves.len());
+    let preset = plan.preset.clone();
+    // The event loop clears the note once the wave's batch is done
+    app.job_note = plan.note.clone();
+    app.job_ticket = plan.ticket.clone();
+    tracker.mark_wave_submitted(plan_id, wave);
+    app.push_status(&tf(
+        "status.submitting_wave",
+        &[
+            ("wave", &(wave + 1)),
+            ("count", &count),
+            ("bucket", &bucket),
+            ("objects", &keys.len()),
+        ],
+    ));
+    let operation = format!("Restore wave {} of {}", wave + 1, count);
+    submit_restores(
+        monitor,
+        app,
+        s3,
+        tracker,
+        history,
+        &bucket,
+        &keys,
+        days,
+        tier,
+        preset.as_deref(),
+        operation,
+    )
+    .await
+}
+
+/// `keys` without those queued by another restore since they were picked,
+/// or asked to restore within the last `--restore-dedup-hours`
+fn fresh_restore_keys(
+    app: &mut App,
+    tracker: &mut RestoreTracker,
+    history: &JobHistory,
+    bucket: &str,
+    keys_to_restore: &[String],
+) -> Vec<String> {
+    // One request per key per wave: a key queued since the wave was planned,
+    // for example by an overlapping mask, is left to that request
+    tracker.reload();
+    let queued = keys_to_restore.len();
+    let keys_to_restore = tracker.coalesce(bucket, keys_to_restore);
+    if keys_to_restore.len() < queued {
+        app.push_status(&tf(
+            "status.skipped_queued",
+            &[("count", &(queued - keys_to_restore.len()))],
+        ));
+    }
+
+    // Skip keys this or another instance asked to restore recently, even if
+    // HeadObject does not show the restore yet
+    let dedup_hours = app.restore_pacing.dedup_hours;
+    if dedup_hours > 0 {
+        let since = chrono::Utc::now() - chrono::Duration::hours(dedup_hours.into());
+        let journaled = history.succeeded_since(JobKind::Restore, bucket, since);
+        let fresh: Vec<String> = keys_to_restore
+            .iter()
+            .filter(|key| !journaled.contains(*key) && !tracker.requested_since(bucket, key, since))
+            .cloned()
+            .collect();
+        let skipped = keys_to_restore.len() - fresh.len();
+        if skipped > 0 {
+            app.push_status(&tf(
+                "status.skipped_recent",
+                &[("skipped", &skipped), ("dedup_hours", &dedup_hours)],
+            ));
+        }
+        fresh
+    } else {
+        keys_to_restore.to_vec()
+    }
+}
+
+/// Send restore requests for `keys` and track the ones that were accepted
+#[allow(clippy::too_many_arguments)]
+async fn submit_restores(
+    monitor: &mut BatchMonitor<'_>,
+    app: &mut App,
+    s3: &S3Service,
+    tracker: &mut RestoreTracker,
+    history: &mut JobHistory,
+    bucket: &str,
+    keys_to_restore: &[String],
+    days: i32,
+    tier: RestoreTier,
+    preset: Option<&str>,
+    operation: String,
+) -> Result<()> {
+    let keys_to_restore = fresh_restore_keys(app, tracker, history, bucket, keys_to_restore);
+    if keys_to_restore.is_empty() {
+        app.push_status(t("status.no_restore_needed"));
+        return Ok(());
+    }
+
+    // Initialize progress tracking
+    let total = keys_to_restore.len();
+    app.progress = Some(crate::app::ProgressState::new(operation, total));
+    app.set_mode(AppMode::ShowingProgress);
+    let job_id = begin_job(
+        app,
+        history,
+        JobKind::Restore,
+        bucket,
+        &restore_label(days, tier),
+        total,
+    );
+
+    let mut restored_keys = Vec::new();
+    let mut success_count = 0;
+    let mut error_count = 0;
+
+    // Requests run concurrently and report in completion order. Quitting stops
+    // new requests from starting while the ones in flight finish. The
+    // semaphore caps how many are in flight, and is resized when the user
+    // changes the concurrency with +/-.
+    let (pacing, quiet) = app.restore_settings(preset);
+    let mut permits = pacing.concurrency;
+    if let Some(progress) = &mut app.progress {
+        progress.concurrency = Some(permits);
+    }
+    let slots = &Semaphore::new(permits);
+    let started = tokio::time::Instant::now();
+    let stop = monitor.stop;
+    let mut requests = futures::stream::iter(keys_to_restore.iter().enumerate())
+        .take_while(|_| std::future::ready(stop.get().is_none()))
+        .map(|(index, key)| async move {
+            tokio::time::sleep_until(started + pacing.start_offset(index)).await;
+            let _slot = slots.acquire().await.ok()?;
+            // The user quit while this request waited for a slot
+            if stop.get().is_some() {
+                return None;
+            }
+            let sent = Instant::now();
+            let result = s3.request_restore(bucket, key, days, tier).await;
+            Some((key, result, sent.elapsed()))
+        })
+        .buffer_unordered(MAX_RESTORE_CONCURRENCY)
+        .filter_map(std::future::ready);
+
+    loop {
+        let Some(next) = monitor.run(app, requests.next()).await? else {
+            break;
+        };
+        let Some((key, result, elapsed)) = next else {
+            break;
+        };
+        let wanted = app
+            .progress
+            .as_ref()
+            .and_then(|p| p.concurrency)
+            .unwrap_or(permits);
+        if wanted > permits {
+            slots.add_permits(wanted - permits);
+            permits = wanted;
+        }
+        // Slots in use are taken away as their requests finish
+        while permits > wanted
+            && let Ok(slot) = slots.try_acquire()
+        {
+            slot.forget();
+            permits -= 1;
+        }
+        history.time_request(&job_id, elapsed);
+        app.telemetry
+            .record("RestoreObject", elapsed, result.is_ok(), 0);
+        if result.is_ok() {
+            app.note_reached();
+        }
+        // Update progress
+        app.update(AppEvent::JobProgress {
+            done: success_count + error_count + 1,
+            key: key.clone(),
+        });
+        match result {
+            Ok(_) => {
+                success_count += 1;
+                // Track the restore request
+                let storage_class = app
+                    .objects
+                    .iter()
+                    .find(|o| &o.key == key)
+                    .map(|o| o.storage_class.clone())
+                    .unwrap_or(StorageClassTier::GlacierFlexibleRetrieval);
+                tracker.add_request(
+                    bucket.to_string(),
+                    key.clone(),
+                    days,
+                    storage_class,
+                    tier,
+                    quiet,
+                );
+                history.record(&job_id, key, None);
+                restored_keys.push(key.clone());
+                if !run_object_hook(monitor, app, &job_id, bucket, key, "restore").await? {
+                    break;
+                }
+            }
+            Err(err) => {
+                error_count += 1;
+                history.record(&job_id, key, Some(errors::describe(&err)));
+                app.push_job_error(&job_id, &tf("error.restore", &[("key", &key)]), &err);
+            }
+        }
+    }
+    let processed = success_count + error_count;
+    if processed < total {
+        history.interrupt(&job_id);
+    } else {
+        history.finish(&job_id);
+    }
+
+    // Keep a concurrency tuned during the batch for later restores, unless
+    // it came from a preset
+    if let Some(concurrency) = app.progress.as_ref().and_then(|p| p.concurrency)
+        && preset.is_none()
+        && concurrency != pacing.concurrency
+    {
+        app.restore_pacing.concurrency = concurrency;
+        app.push_status(&tf(
+            "status.restore_concurrency",
+            &[("concurrency", &concurrency)],
+        ));
+    }
+
+    // Clear progress and return to browsing
+    app.progress = None;
+    app.set_mode(AppMode::Browsing);
+
+    // Show summary
+    if processed < total {
+        app.push_status(&tf(
+            "status.restore_requests_stopped",
+            &[
+                ("processed", &processed),
+                ("total", &total),
+                ("failed", &error_count),
+            ],
+        ));
+    } else if error_count > 0 {
+        app.push_status(&tf(
+            "status.restore_requests_complete",
+            &[("succeeded", &success_count), ("failed", &error_count)],
+        ));
+    } else {
+        app.push_status(&tf(
+            "status.restore_requested",
+            &[("count", &success_count)],
+        ));
+    }
+
+    // Manually update restore status for successfully restored objects
+    // AWS doesn't immediately reflect the status change, so we update it in memory
+    let shown = app.selected_bucket_name() == Some(bucket);
+    for obj in app.objects.iter_mut() {
+        if shown && restored_keys.contains(&obj.key) {
+            obj.restore_state = Some(crate::models::RestoreState::InProgress { expiry: None });
+        }
+    }
+
+    // Update filtered objects if a mask is active
+    if app.active_mask.is_some() {
+        let mask = app.active_mask.clone();
+        app.apply_mask(mask);
+    } else if app.is_filtered() {
+        app.refilter_keeping_selection();
+    }
+
+    Ok(())
+}
+
+/// Hand a confirmed transition or restore to the background job queue.
+/// What only a foreground batch can do stays there: restores split into
+/// waves, and objects too large for CopyObject.
+async fn start_background_job(
+    app: &mut App,
+    s3: &S3Service,
+    tracker: &mut RestoreTracker,
+    history: &mut JobHistory,
+    action: PendingAction,
+) {
+    let Some(bucket) = app.selected_bucket_name().map(str::to_string) else {
+        app.push_status(t("status.select_bucket_job"));
+        return;
+    };
+    let (label, work, objects) = match action {
+        PendingAction::Transition { target_class, lock } => {
+            if !lock.is_empty() {
+                match s3.object_lock_enabled(&bucket).await {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        app.push_status(&tf("status.no_object_lock", &[("bucket", &bucket)]));
+                        return;
+                    }
+                    Err(err) => {
+                        app.push_error(
+                            &tf("error.object_lock_check", &[("bucket", &bucket)]),
+                            &err,
+                        );
+                        return;
+                    }
+                }
+            }
+            let (keys, skipped) = transition_keys(app, &target_class);
+            if skipped > 0 {
+                app.push_status(&tf(
+                    "status.skipped_already_class",
+                    &[("count", &skipped), ("class", &target_class.label())],
+                ));
+            }
+            let objects: Vec<(String, i64)> = keys
+                .into_iter()
+                .map(|key| {
+                    let size = object_size(app, &key);
+                    (key, size)
+                })
+                .collect();
+            let (large, objects): (Vec<_>, Vec<_>) = objects
+                .into_iter()
+                .partition(|(_, size)| *size > MULTIPART_COPY_THRESHOLD);
+            if !large.is_empty() {
+                app.push_status(&tf("status.large_left_out", &[("count", &large.len())]));
+            }
+            if objects.is_empty() {
+                if skipped == 0 && large.is_empty() {
+                    app.push_status(t("status.no_transition_targets"));
+                }
+                return;
+            }
+            let label = format!("Transitioning to {}", target_class.label());
+            (label, JobWork::Transition { target_class, lock }, objects)
+        }
+        PendingAction::Restore { days, tier, preset } => {
+            let keys = restore_candidates(app, tracker, &bucket);
+            let objects: Vec<(String, i64)> = keys
+                .iter()
+                .map(|key| (key.clone(), object_size(app, key)))
+                .collect();
+            let total_bytes: i64 = objects.iter().map(|(_, size)| size).sum();
+            if let Some(wave_bytes) = app.restore_pacing.wave_bytes
+                && total_bytes as u64 > wave_bytes
+            {
+                // The event loop submits the first wave right away
+                let mut plan = WavePlan::new(&bucket, days, tier, wave_bytes, objects);
+                plan.preset = preset;
+                plan.note = app.job_note.clone();
+                plan.ticket = app.job_ticket.clone();
+                app.push_status(&tf(
+                    "status.waves_planned_foreground",
+                    &[
+                        ("count", &plan.waves.len()),
+                        ("size", &waves::format_bytes(wave_bytes)),
+                    ],
+                ));
+                tracker.add_wave_plan(plan);
+                return;
+            }
+            let keys = fresh_restore_keys(app, tracker, history, &bucket, &keys);
+            if keys.is_empty() {
+                app.push_status(t("status.no_restore_needed"));
+                return;
+            }
+            let objects = keys
+                .into_iter()
+                .map(|key| {
+                    let size = object_size(app, &key);
+                    (key, size)
+                })
+                .collect();
+            let (pacing, quiet) = app.restore_settings(preset.as_deref());
+            let work = JobWork::Restore {
+                days,
+                tier,
+                preset,
+                pacing,
+                quiet,
+            };
+            ("Requesting Glacier restore".to_string(), work, objects)
+        }
+        action => {
+            app.queued_batch = Some(action);
+            return;
+        }
+    };
+
+    if objects.len() > app.shard_keys {
+        let work = match work {
+            JobWork::Transition { target_class, lock } => {
+                ShardWork::Transition { target_class, lock }
+            }
+            JobWork::Restore {
+                days, tier, preset, ..
+            } => ShardWork::Restore { days, tier, preset },
+        };
+        let count = objects.len();
+        let restore = matches!(work, ShardWork::Restore { .. });
+        let mut plan = ShardPlan::new(&bucket, &label, work, objects, app.shard_keys);
+        if restore {
+            let classes: HashMap<&str, &StorageClassTier> = app
+                .objects
+                .iter()
+                .map(|o| (o.key.as_str(), &o.storage_class))
+                .collect();
+            for shard in &mut plan.shards {
+                shard.storage_classes = shard
+                    .keys
+                    .iter()
+                    .filter_map(|(key, _)| {
+                        let class = classes.get(key.as_str())?;
+                        Some((key.clone(), (*class).clone()))
+                    })
+                    .collect();
+            }
+        }
+        plan.note = app.job_note.clone();
+        plan.ticket = app.job_ticket.clone();
+        let (plan_id, shards) = (plan.id.clone(), plan.shards.len());
+        if let Some(state) = &app.shared_state {
+            // Workers run it once the next sync has published it
+            plan.shared = true;
+            let url = state.url();
+            app.shards.add(plan);
+            app.shard_synced_at = None;
+            app.push_status(&tf(
+                "status.sharded_shared",
+                &[
+                    ("label", &label),
+                    ("count", &count),
+                    ("shards", &shards),
+                    ("size", &app.shard_keys),
+                    ("url", &url),
+                ],
+            ));
+            return;
+        }
+        app.shards.add(plan);
+        app.push_status(&tf(
+            "status.sharded",
+            &[
+                ("label", &label),
+                ("count", &count),
+                ("shards", &shards),
+                ("size", &app.shard_keys),
+            ],
+        ));
+        for index in 0..shards {
+            run_shard(app, s3, history, &plan_id, index);
+        }
+        return;
+    }
+
+    let (kind, target) = work.journal_target();
+    let job_id = begin_job(app, history, kind, &bucket, &target, objects.len());
+    let key = if app.jobs.is_empty() {
+        "status.background_job"
+    } else {
+        "status.background_job_queued"
+    };
+    app.push_status(&tf(
+        key,
+        &[
+            ("label", &label),
+            ("count", &objects.len()),
+            ("queued", &app.jobs.jobs().len()),
+        ],
+    ));
+    let hook = app.object_hook.clone();
+    app.jobs
+        .submit(s3, job_id, label, bucket, work, objects, hook);
+}
+
+/// Submit one shard of a plan as a background job, leaving out the keys its
+/// earlier runs handled
+fn run_shard(app: &mut App, s3: &S3Service, history: &mut JobHistory, plan_id: &str, index: usize) {
+    let Some(plan) = app.shards.plan(plan_id) else {
+        return;
+    };
+    let Some(shard) = plan.shards.get(index) else {
+        return;
+    };
+    let done: HashSet<String> = shard
+        .runs
+        .iter()
+        .flat_map(|run| history.entries(run).unwrap_or_default())
+        .filter(|entry| entry.error.is_none())
+        .map(|entry| entry.key)
+        .collect();
+    let objects: Vec<(String, i64)> = shard
+        .keys
+        .iter()
+        .filter(|(key, _)| !done.contains(key))
+        .cloned()
+        .collect();
+    let succeeded = shard.keys.len() - objects.len();
+    let label = format!(
+        "{} – shard {}/{} {}",
+        plan.label,
+        index + 1,
+        plan.shards.len(),
+        shard.name
+    );
+    let (bucket, note, ticket) = (plan.bucket.clone(), plan.note.clone(), plan.ticket.clone());
+    let (pacing, quiet) = app.restore_settings(plan.work.restore_preset());
+    let work = JobWork::for_shard(&plan.work, pacing, quiet);
+    if objects.is_empty() {
+        if let Some(plan) = app.shards.set_succeeded(plan_id, index, succeeded) {
+            shard_plan_done(app, &plan);
+        }
+        return;
+    }
+    let (kind, target) = work.journal_target();
+    let job_id = begin_job_with_note(
+        app,
+        history,
+        kind,
+        &bucket,
+        &target,
+        objects.len(),
+        note,
+        ticket,
+    );
+    app.shards.begin_run(plan_id, index, &job_id, succeeded);
+    let hook = app.object_hook.clone();
+    app.jobs
+        .submit(s3, job_id, label, bucket, work, objects, hook);
+}
+
+fn shard_plan_done(app: &mut App, plan: &ShardPlan) {
+    app.push_status(&tf(
+        "status.shards_done",
+        &[
+            ("label", &plan.label),
+            ("bucket", &plan.bucket),
+            ("shards", &plan.shards.len()),
+        ],
+    ));
+}
+
+/// Journal what the background jobs report, and summarize the ones that
+/// finished
+fn drain_job_events(app: &mut App, tracker: &mut RestoreTracker, history: &mut JobHistory) -> bool {
+    let mut received = false;
+    while let Some(event) = app.jobs.try_next() {
+        received = true;
+        match event {
+            JobEvent::Started { .. } => {}
+            JobEvent::Object {
+                job,
+                key,
+                size,
+                result,
+                elapsed,
+            } => {
+                let Some((bucket, work)) = app
+                    .jobs
+                    .get(&job)
+                    .map(|j| (j.bucket.clone(), j.work.clone()))
+                else {
+                    continue;
+                };
+                history.time_request(&job, elapsed);
+                let copied = match (&work, &result) {
+                    (JobWork::Transition { .. }, Ok(_)) => size as u64,
+                    _ => 0,
+                };
+                app.telemetry
+                    .record(work.api(), elapsed, result.is_ok(), copied);
+                let note = match result {
+                    Ok(note) => note,
+                    Err(err) => {
+                        history.record(&job, &key, Some(errors::describe(&err)));
+                        let context = match work {
+                            JobWork::Transition { .. } => "error.transition",
+                            JobWork::Restore { .. } => "error.restore",
+                        };
+                        app.push_job_error(&job, &tf(context, &[("key", &key)]), &err);
+                        continue;
+                    }
+                };
+                app.note_reached();
+                history.record_with_note(&job, &key, None, note);
+                if let JobWork::Restore {
+                    days, tier, quiet, ..
+                } = work
+                {
+                    let storage_class = app
+                        .objects
+                        .iter()
+                        .find(|o| o.key == key)
+                        .map(|o| o.storage_class.clone())
+                        .unwrap_or(StorageClassTier::GlacierFlexibleRetrieval);
+                    tracker.add_request(
+                        bucket.clone(),
+                        key.clone(),
+                        days,
+                        storage_class,
+                        tier,
+                        quiet,
+                    );
+                }
+                // Show the change without listing the bucket again
+                if app.selected_bucket_name() == Some(bucket.as_str()) {
+                    for obj in app
+                        .objects
+                        .iter_mut()
+                        .chain(app.filtered_objects.iter_mut())
+                        .filter(|o| o.key == key && o.is_current())
+                    {
+                        match &work {
+                            JobWork::Transition { target_class, .. } => {
+                                obj.storage_class = target_class.clone();
+                            }
+                            JobWork::Restore { .. } => {
+                                obj.restore_state =
+                                    Some(crate::models::RestoreState::InProgress { expiry: None });
+                            }
+                        }
+                    }
+                }
+            }
+            JobEvent::HookFailed { job, key, err } => {
+                app.push_job_error(&job, &tf("error.hook", &[("key", &key)]), &err);
+            }
+            JobEvent::Finished { job } => {
+                let Some(job) = app.jobs.remove(&job) else {
+                    continue;
+                };
+                if job.done < job.total {
+                    history.interrupt(&job.id);
+                    app.push_status(&tf(
+                        "status.job_stopped",
+                        &[
+                            ("label", &job.label),
+                            ("bucket", &job.bucket),
+                            ("done", &job.done),
+                            ("total", &job.total),
+                            ("failed", &job.failed),
+                        ],
+                    ));
+                } else {
+                    history.finish(&job.id);
+                    app.push_status(&tf(
+                        "status.job_complete",
+                        &[
+                            ("label", &job.label),
+                            ("bucket", &job.bucket),
+                            ("succeeded", &(job.done - job.failed)),
+                            ("failed", &job.failed),
+                        ],
+                    ));
+                }
+                if let Some(plan) =
+                    app.shards
+                        .finish_run(&job.id, job.done - job.failed, job.failed)
+                {
+                    shard_plan_done(app, &plan);
+                }
+                if app.selected_bucket_name() == Some(job.bucket.as_str()) && app.is_filtered() {
+                    app.refilter_keeping_selection();
+                }
+            }
+        }
+    }
+    received
+}
+
+/// Interval between background checks of pending restore requests
+const RESTORE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Wait for the running restore check, or forever when none is running
+async fn restore_check_done(check: &mut Option<JoinHandle<RestoreUpdates>>) -> RestoreUpdates {
+    let Some(handle) = check else {
+        return std::future::pending().await;
+    };
+    let updates = handle.await.unwrap_or_default();
+    *check = None;
+    updates
+}
+
+/// Wait for the running last-read lookup, or forever when none is running.
+/// `None` when the task panicked.
+async fn recency_done(
+    lookup: &mut Option<JoinHandle<Result<LastReads>>>,
+) -> Option<Result<LastReads>> {
+    let Some(handle) = lookup else {
+        return std::future::pending().await;
+    };
+    let result = handle.await.ok();
+    *lookup = None;
+    result
+}
+
+/// Look up when the selected bucket's keys were last read, in the background
+fn start_recency_lookup(app: &mut App, s3: &S3Service) {
+    let Some(config) = app.recency.clone() else {
+        app.push_status(t("status.no_read_history"));
+        return;
+    };
+    let Some(bucket) = app.selected_bucket_name().map(str::to_string) else {
+        app.push_status(t("status.select_bucket"));
+        return;
+    };
+    if app.recency_lookup.is_some() {
+        app.push_status(t("status.last_reads_running"));
+        return;
+    }
+    app.push_status(&tf(
+        "status.reading_last_reads",
+        &[
+            ("source", &config.label(&bucket)),
+            ("days", &config.lookback_days),
+        ],
+    ));
+    let s3 = s3.clone();
+    app.recency_lookup = Some(tokio::spawn(
+        async move { config.lookup(&s3, &bucket).await },
+    ));
+}
+
+/// Say why an unread mask matches nothing yet, and start the lookup it
+/// needs when one can run
+fn look_up_reads_for_mask(app: &mut App, s3: &S3Service, days: u32) {
+    let lookback = app.recency.as_ref().map(|config| config.lookback_days);
+    match lookback {
+        Some(lookback) if lookback < i64::from(days) => app.push_status(&tf(
+            "status.lookback_too_short",
+            &[("lookback", &lookback), ("days", &days)],
+        )),
+        Some(_) if app.recency_lookup.is_none() => start_recency_lookup(app, s3),
+        Some(_) => app.push_status(t("status.unread_filter_pending")),
+        None => start_recency_lookup(app, s3),
+    }
+}
+
+fn apply_last_reads(app: &mut App, lookup: Option<Result<LastReads>>) {
+    match lookup {
+        Some(Ok(reads)) => {
+            let scanned = if reads.log_objects > 0 {
+                format!(" in {} log objects", reads.log_objects)
+            } else {
+                String::new()
+            };
+            let totals = reads.totals();
+            app.push_status(&tf(
+                "status.last_reads",
+                &[
+                    ("bucket", &reads.bucket),
+                    ("count", &reads.len()),
+                    ("since", &reads.since.format("%Y-%m-%d")),
+                    ("gets", &totals.gets),
+                    ("heads", &totals.heads),
+                    ("scanned", &scanned),
+                ],
+            ));
+            let shown = app.selected_bucket_name() == Some(reads.bucket.as_str());
+            app.last_reads.insert(reads.bucket.clone(), reads);
+            // An unread filter matched nothing until now
+            if shown
+                && app
+                    .active_mask
+                    .as_ref()
+                    .is_some_and(|mask| mask.unread_days.is_some())
+            {
+                app.refilter_keeping_selection();
+            }
+        }
+        Some(Err(err)) => app.push_error(t("error.last_reads"), &err),
+        None => app.push_status(t("status.last_reads_stopped")),
+    }
+}
+
+/// Interval between syncs with the shared state while shared plans run
+const SHARD_SYNC_INTERVAL: Duration = Duration::from_secs(20);
+
+/// Shared shards whose progress one sync reads at once
+const SHARD_SYNC_CONCURRENCY: usize = 16;
+
+/// Start a background sync with the shared state when one is due: write the
+/// waiting requests, publish new shared plans and read every unfinished
+/// shard's progress
+fn start_shard_sync(app: &mut App, s3: &S3Service) {
+    let Some(state) = app.shared_state.clone() else {
+        return;
+    };
+    let due = app
+        .shard_synced_at
+        .is_none_or(|at| at.elapsed() >= SHARD_SYNC_INTERVAL);
+    if app.shard_sync.is_some() || !due {
+        return;
+    }
+    app.shard_synced_at = Some(Instant::now());
+    let requests = std::mem::take(&mut app.shared_requests);
+    let shared = app.shards.plans().iter().filter(|plan| plan.shared);
+    let publish: Vec<ShardPlan> = shared
+        .clone()
+        .filter(|plan| !plan.published)
+        .cloned()
+        .collect();
+    let shards: Vec<(String, usize)> = shared
+        .filter(|plan| plan.published)
+        .flat_map(|plan| {
+            plan.shards
+                .iter()
+                .enumerate()
+                .filter(|(_, shard)| !shard.is_done())
+                .map(|(index, _)| (plan.id.clone(), index))
+        })
+        .collect();
+    if requests.is_empty() && publish.is_empty() && shards.is_empty() {
+        return;
+    }
+    let s3 = s3.clone();
+    app.shard_sync = Some(tokio::spawn(async move {
+        let mut sync = SharedSync::default();
+        for request in requests {
+            let result = match &request {
+                SharedRequest::Retry { plan_id, index } => {
+                    state.request_retry(&s3, plan_id, *index).await.map(|_| ())
+                }
+                SharedRequest::Close { plan_id, reason } => state.close(&s3, plan_id, reason).await,
+            };
+            if let Err(err) = result {
+                sync.error = Some(errors::describe(&err));
+            }
+        }
+        for plan in publish {
+            match state.publish(&s3, &plan).await {
+                Ok(()) => sync.published.push(plan.id),
+                Err(err) => sync.error = Some(errors::describe(&err)),
+            }
+        }
+        sync.shards = futures::stream::iter(shards)
+            .map(|(plan_id, index)| {
+                let (s3, state) = (&s3, &state);
+                async move {
+                    // A failed read leaves the shard as it was shown
+                    let progress = state.progress(s3, &plan_id, index).await.ok()?;
+                    let worker = state.holder(s3, &plan_id, index).await.ok()?;
+                    Some((plan_id, index, progress.map(|(p, _)| p), worker))
+                }
+            })
+            .buffer_unordered(SHARD_SYNC_CONCURRENCY)
+            .filter_map(|shard| async move { shard })
+            .collect()
+            .await;
+        sync
+    }));
+}
+
+/// Wait for the running shard sync, or forever when none is running
+async fn shard_sync_done(sync: &mut Option<JoinHandle<SharedSync>>) -> SharedSync {
+    let Some(handle) = sync else {
+        return std::future::pending().await;
+    };
+    let result = handle.await.unwrap_or_default();
+    *sync = None;
+    result
+}
+
+/// Show what the workers reported, and close the shared plans they finished
+fn apply_shard_sync(app: &mut App, sync: SharedSync) {
+    if let Some(err) = sync.error {
+        app.push_status(&tf("status.shard_sync_failed", &[("err", &err)]));
+    }
+    for plan_id in &sync.published {
+        app.shards.mark_published(plan_id);
+    }
+    if !sync.published.is_empty() {
+        // Read the new plans' shards without waiting a whole interval
+        app.shard_synced_at = None;
+    }
+    if sync.shards.is_empty() {
+        return;
+    }
+    for (plan_id, index, progress, worker) in sync.shards {
+        app.shards
+            .update_shared(&plan_id, index, progress.as_ref(), worker);
+    }
+    for plan in app.shards.settle_shared() {
+        shard_plan_done(app, &plan);
+        app.shared_requests.push(SharedRequest::Close {
+            plan_id: plan.id,
+            reason: "done",
+        });
+    }
+}
 
-    // Clear progress and return to browsing
-    app.progress = None;
-    app.set_mode(AppMode::Browsing);
+/// Apply the results of a background restore check. Newly completed restores
+/// are announced in the status bar and sent to the optional webhook.
+fn apply_restore_updates(app: &mut App, tracker: &mut RestoreTracker, updates: RestoreUpdates) {
+    let selected_bucket = app.selected_bucket_name().map(|b| b.to_string());
+    let mut listed = false;
+    for (bucket, key, state, expiry) in updates {
+        if selected_bucket.as_deref() == Some(bucket.as_str()) {
+            let shown = app
+                .objects
+                .iter_mut()
+                .chain(app.filtered_objects.iter_mut())
+                .filter(|obj| obj.key == key && obj.is_current());
+            for obj in shown {
+                obj.restore_state = Some(state.clone());
+                obj.restore_expiry = expiry.clone();
+                listed = true;
+            }
+        }
+        if tracker.update_status(&bucket, &key, state, expiry) {
+            app.push_status(&tf(
+                "status.restore_ready",
+                &[("bucket", &bucket), ("key", &key)],
+            ));
+            // Quiet restores are announced on the status line only
+            let ready_at = tracker
+                .get_all_requests()
+                .iter()
+                .find(|r| r.bucket == bucket && r.key == key)
+                .filter(|r| !r.quiet)
+                .map(|r| r.ready_at.clone());
+            if let Some(ready_at) = ready_at {
+                notify::restore_ready(&bucket, &key, ready_at.as_deref());
+            }
+        }
+    }
+    // Rows move in or out of a restore filter as their status changes
+    if listed && (app.restore_filter.is_some() || app.restore_sort) {
+        app.refilter_keeping_selection();
+    }
+}
 
-    // Show summary
-    if error_count > 0 {
-        app.push_status(&format!(
-            "Transition complete: {} succeeded, {} failed",
-            success_count, error_count
-        ));
-    } else {
-        app.push_status(&format!(
-            "Successfully transitioned {} objects to {}",
-            success_count,
-            target_class.label()
-        ));
+/// Start a background check of pending restore requests when one is due
+fn start_restore_check(app: &mut App, s3: &S3Service, tracker: &RestoreTracker) {
+    let due = app
+        .restore_checked_at
+        .is_none_or(|at| at.elapsed() >= RESTORE_CHECK_INTERVAL);
+    if app.restore_check.is_some() || !due {
+        return;
+    }
+    app.restore_checked_at = Some(Instant::now());
+    let pending = tracker.get_active_requests();
+    if pending.is_empty() {
+        return;
+    }
+    let mut by_bucket: HashMap<String, Vec<String>> = HashMap::new();
+    for req in pending {
+        by_bucket.entry(req.bucket).or_default().push(req.key);
+    }
+    let s3 = s3.clone();
+    app.restore_check = Some(tokio::spawn(async move {
+        let mut updates = RestoreUpdates::new();
+        for (bucket, keys) in by_bucket {
+            // A failed HeadObject has no state and leaves the tracked one unchanged
+            for (key, state, expiry) in s3.batch_refresh_restore_status(&bucket, &keys).await {
+                if let Some(state) = state {
+                    updates.push((bucket.clone(), key, state, expiry));
+                }
+            }
+        }
+        updates
+    }));
+}
+
+/// Pin the selected bucket to a second pane, or close split view
+fn toggle_split_view(app: &mut App, s3: &S3Service) {
+    if app.split.take().is_some() {
+        if app.active_pane == ActivePane::PeerObjects {
+            app.active_pane = ActivePane::Objects;
+        }
+        app.push_status(t("status.split_closed"));
+        return;
+    }
+
+    let Some(bucket) = app.selected_bucket_name().map(|b| b.to_string()) else {
+        app.push_status(t("status.split_select"));
+        return;
+    };
+    let mut split = SplitPane::new(bucket.clone());
+    split.listing = Some(loader::spawn_parallel_listing(
+        s3.clone(),
+        bucket.clone(),
+        None,
+        None,
+        PAGE_SIZE,
+    ));
+    app.split = Some(split);
+    app.push_status(&tf("status.split_pinned", &[("bucket", &bucket)]));
+}
+
+/// Merge pages for the pinned pane. Returns whether any event arrived.
+fn drain_split_listing_events(app: &mut App) -> bool {
+    let mut received = false;
+    while let Some(event) = app
+        .split
+        .as_mut()
+        .and_then(|split| split.listing.as_mut())
+        .and_then(|l| l.try_next())
+    {
+        received = true;
+        match event {
+            ListingEvent::Page(objects) => app.update(AppEvent::SplitObjectsLoaded(objects)),
+            ListingEvent::Failed(err) => app.update(AppEvent::Error {
+                context: "Failed to load split pane objects".to_string(),
+                err,
+            }),
+            ListingEvent::Finished => app.update(AppEvent::SplitListingFinished),
+            ListingEvent::Planned(_) | ListingEvent::PartitionDone | ListingEvent::Folders(_) => {}
+        }
+    }
+    received
+}
+
+fn drain_enrich_events(app: &mut App) -> bool {
+    let mut received = false;
+    while let Some(enriched) = app.enrichment.as_mut().and_then(|e| e.try_next()) {
+        received = true;
+        app.update(AppEvent::Enriched(enriched));
+    }
+    received
+}
+
+fn drain_reconcile_events(app: &mut App) -> bool {
+    let mut received = false;
+    while let Some(event) = app.reconcile.as_mut().and_then(|r| r.try_next()) {
+        received = true;
+        app.update(AppEvent::Reconciled(event));
+    }
+    received
+}
+
+/// Compare the main pane's bucket with the pinned one, under the active
+/// prefix mask when there is one. A finished or running comparison of the
+/// same buckets is shown again instead.
+fn begin_reconcile(app: &mut App, s3: &S3Service) -> Result<()> {
+    let split = app
+        .split
+        .as_ref()
+        .context("Open split view with 'v' first")?;
+    let source = app
+        .selected_bucket_name()
+        .context("Select a bucket first")?
+        .to_string();
+    let dest = split.bucket.clone();
+    if source == dest {
+        anyhow::bail!("both panes show {source}; select a different bucket on the left");
+    }
+    if app
+        .reconciliation
+        .as_ref()
+        .is_some_and(|r| r.source_bucket == source && r.dest_bucket == dest)
+    {
+        app.set_mode(AppMode::ViewingReconciliation);
+        return Ok(());
     }
+    start_reconcile(app, s3, source, dest)
+}
 
-    load_objects_for_selection(app, s3).await?;
+fn start_reconcile(app: &mut App, s3: &S3Service, source: String, dest: String) -> Result<()> {
+    // Listing by prefix is exact only for a case-sensitive prefix mask; a
+    // mask reaching outside the current folder is narrowed to the folder
+    let folder = app.current_prefix().unwrap_or_default();
+    let prefix = app
+        .active_mask
+        .as_ref()
+        .filter(|mask| mask.kind == MaskKind::Prefix && mask.case_sensitive)
+        .map(|mask| mask.pattern.clone())
+        .filter(|pattern| pattern.starts_with(folder))
+        .or_else(|| app.current_prefix().map(str::to_string));
+    let (handle, summary) = reconcile::spawn_reconcile(s3.clone(), source, dest, prefix)?;
+    let args: [(&str, &dyn std::fmt::Display); 4] = [
+        ("source", &summary.source_bucket),
+        ("dest", &summary.dest_bucket),
+        ("prefix", &summary.prefix.as_deref().unwrap_or_default()),
+        ("export", &summary.export.display()),
+    ];
+    app.push_status(&match summary.prefix {
+        Some(_) => tf("status.reconciling_prefix", &args),
+        None => tf("status.reconciling", &args),
+    });
+    app.reconcile = Some(handle);
+    app.reconciliation = Some(summary);
+    app.reconcile_scroll = 0;
+    app.set_mode(AppMode::ViewingReconciliation);
     Ok(())
 }
 
-async fn execute_restore(
-    app: &mut App,
-    s3: &S3Service,
-    tracker: &mut RestoreTracker,
-    days: i32,
-) -> Result<()> {
-    let bucket = app
+/// Findings scrolled per PageUp/PageDown in the reconciliation popup
+const RECONCILE_PAGE: usize = 10;
+
+fn handle_reconcile_keys(key: KeyEvent, app: &mut App, s3: &S3Service) {
+    let findings = app.reconciliation.as_ref().map_or(0, |r| r.sample.len());
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.set_mode(AppMode::Browsing),
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.reconcile_scroll = app.reconcile_scroll.saturating_sub(1);
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.reconcile_scroll = (app.reconcile_scroll + 1).min(findings.saturating_sub(1));
+        }
+        KeyCode::PageUp => {
+            app.reconcile_scroll = app.reconcile_scroll.saturating_sub(RECONCILE_PAGE);
+        }
+        KeyCode::PageDown => {
+            app.reconcile_scroll =
+                (app.reconcile_scroll + RECONCILE_PAGE).min(findings.saturating_sub(1));
+        }
+        KeyCode::Char('x') if app.reconcile.is_some() => {
+            app.reconcile = None;
+            if let Some(summary) = &mut app.reconciliation {
+                summary.finished = true;
+                summary.error = Some("stopped".to_string());
+            }
+            app.push_status(t("status.reconcile_stopped"));
+        }
+        KeyCode::Char('r') => {
+            let Some((source, dest)) = app
+                .reconciliation
+                .as_ref()
+                .map(|r| (r.source_bucket.clone(), r.dest_bucket.clone()))
+            else {
+                return;
+            };
+            if let Err(err) = start_reconcile(app, s3, source, dest) {
+                app.push_error(t("error.cannot_reconcile"), &err);
+            }
+        }
+        KeyCode::Char('c') => begin_recopy(app),
+        _ => {}
+    }
+}
+
+/// Confirm a copy of the keys a finished reconciliation found missing or
+/// with a different size in the destination
+fn begin_recopy(app: &mut App) {
+    let Some(summary) = &app.reconciliation else {
+        return;
+    };
+    if !summary.finished {
+        app.push_status(t("status.reconcile_wait"));
+        return;
+    }
+    if summary.recopy.is_empty() {
+        app.push_status(t("status.nothing_to_recopy"));
+        return;
+    }
+    let keys: Vec<String> = summary.recopy.keys().cloned().collect();
+    let (source_bucket, dest_bucket) = (summary.source_bucket.clone(), summary.dest_bucket.clone());
+    app.push_status(&tf(
+        "status.confirm_recopy",
+        &[
+            ("count", &keys.len()),
+            ("source", &source_bucket),
+            ("dest", &dest_bucket),
+        ],
+    ));
+    app.pending_action = Some(PendingAction::Copy {
+        source_bucket,
+        dest_bucket,
+        keys,
+        mode: CopyMode::Overwrite,
+        delete_source: false,
+        max_bandwidth: app.max_bandwidth,
+        rewrite: KeyRewrite::default(),
+        storage_class: None,
+    });
+    app.set_mode(AppMode::Confirming);
+}
+
+/// Prepare a copy between the two split panes. Copying right sends the main
+/// pane's targets (selection or mask) to the pinned bucket; copying left sends
+/// the pinned pane's selected object to the main bucket.
+fn begin_split_copy(app: &mut App, to_right: bool) -> Result<()> {
+    let split = app
+        .split
+        .as_ref()
+        .context("Open split view with 'v' first")?;
+    let main_bucket = app
         .selected_bucket_name()
-        .context("Select a bucket before restoring")?
+        .context("Select a bucket first")?
         .to_string();
+    if main_bucket == split.bucket {
+        anyhow::bail!("both panes show {main_bucket}; select a different bucket on the left");
+    }
 
-    // Get objects and filter to only those needing restore
-    let all_keys = target_keys(app);
-    let objects_map: std::collections::HashMap<_, _> = if app.active_mask.is_some() {
-        app.filtered_objects
-            .iter()
-            .map(|o| (o.key.clone(), o))
-            .collect()
+    let (source_bucket, dest_bucket, keys) = if to_right {
+        (main_bucket, split.bucket.clone(), target_keys(app))
     } else {
-        app.objects.iter().map(|o| (o.key.clone(), o)).collect()
+        let key = split
+            .selected_object()
+            .map(|o| o.key.clone())
+            .context("Select an object in the right pane")?;
+        (split.bucket.clone(), main_bucket, vec![key])
     };
+    if keys.is_empty() {
+        anyhow::bail!("Select at least one object (mask or row)");
+    }
 
-    let mut keys_to_restore = Vec::new();
-    let mut already_restoring = 0;
-    let mut already_available = 0;
+    app.push_status(&tf(
+        "status.confirm_copy",
+        &[
+            ("count", &keys.len()),
+            ("source", &source_bucket),
+            ("dest", &dest_bucket),
+        ],
+    ));
+    app.pending_action = Some(PendingAction::Copy {
+        source_bucket,
+        dest_bucket,
+        keys,
+        mode: CopyMode::default(),
+        delete_source: false,
+        max_bandwidth: app.max_bandwidth,
+        rewrite: KeyRewrite::default(),
+        storage_class: None,
+    });
+    app.set_mode(AppMode::Confirming);
+    Ok(())
+}
 
-    for key in &all_keys {
-        if let Some(obj) = objects_map.get(key) {
-            match &obj.restore_state {
-                Some(crate::models::RestoreState::InProgress { .. }) => {
-                    already_restoring += 1;
-                }
-                Some(crate::models::RestoreState::Available) => {
-                    already_available += 1;
-                }
-                _ => {
-                    // Only restore if it's a Glacier object that needs restore
-                    if matches!(
-                        obj.storage_class,
-                        crate::models::StorageClassTier::GlacierFlexibleRetrieval
-                            | crate::models::StorageClassTier::GlacierDeepArchive
-                    ) {
-                        keys_to_restore.push(key.clone());
-                    }
+/// Open the form for copying the targets (selection or mask) to another
+/// bucket. The key rewrite starts at the open folder or the prefix mask, so
+/// only its destination needs typing.
+fn begin_copy_to(app: &mut App) -> Result<()> {
+    app.selected_bucket_name()
+        .context("Select a bucket first")?;
+    if app.target_objects().is_empty() {
+        anyhow::bail!("Select at least one object (mask or row)");
+    }
+    let prefix = app
+        .current_prefix()
+        .map(str::to_string)
+        .or_else(|| {
+            app.active_mask
+                .as_ref()
+                .filter(|mask| mask.kind == MaskKind::Prefix)
+                .map(|mask| mask.pattern.clone())
+        })
+        .unwrap_or_default();
+    let dest_bucket = app
+        .split
+        .as_ref()
+        .map(|split| split.bucket.clone())
+        .unwrap_or_default();
+    app.copy_draft = CopyDraft {
+        dest_bucket,
+        rewrite: KeyRewrite {
+            from: prefix.clone(),
+            to: prefix,
+        },
+        storage_class: None,
+    };
+    app.copy_field = CopyFormField::Bucket;
+    app.set_mode(AppMode::ChoosingCopyTarget);
+    Ok(())
+}
+
+async fn handle_copy_target_keys(key: KeyEvent, app: &mut App, s3: &S3Service) {
+    let draft = &mut app.copy_draft;
+    let text = match app.copy_field {
+        CopyFormField::Bucket => &mut draft.dest_bucket,
+        CopyFormField::From => &mut draft.rewrite.from,
+        CopyFormField::To => &mut draft.rewrite.to,
+        CopyFormField::StorageClass => &mut String::new(),
+    };
+    match key.code {
+        KeyCode::Esc => app.set_mode(AppMode::Browsing),
+        KeyCode::Tab => app.copy_field = app.copy_field.next(),
+        KeyCode::BackTab => app.copy_field = app.copy_field.previous(),
+        KeyCode::Left | KeyCode::Right => {
+            let delta = if key.code == KeyCode::Left { -1 } else { 1 };
+            match app.copy_field {
+                CopyFormField::Bucket => cycle_copy_bucket(app, delta),
+                CopyFormField::StorageClass => {
+                    // `None` sits before the first class
+                    let classes = StorageClassTier::selectable();
+                    let current = draft
+                        .storage_class
+                        .as_ref()
+                        .and_then(|class| classes.iter().position(|c| c == class))
+                        .map_or(0, |index| index as isize + 1);
+                    let next = (current + delta).rem_euclid(classes.len() as isize + 1);
+                    draft.storage_class = (next > 0).then(|| classes[next as usize - 1].clone());
                 }
+                CopyFormField::From | CopyFormField::To => {}
+            }
+        }
+        KeyCode::Backspace => {
+            text.pop();
+        }
+        KeyCode::Char(ch) if app.copy_field != CopyFormField::StorageClass => text.push(ch),
+        KeyCode::Enter => {
+            if let Err(err) = confirm_copy_to(app) {
+                app.push_status(&format!("{err:#}"));
+                return;
+            }
+            start_collision_check(app, s3);
+            if let Some(PendingAction::Copy {
+                source_bucket,
+                dest_bucket,
+                ..
+            }) = &app.pending_action
+            {
+                let buckets = [source_bucket.clone(), dest_bucket.clone()];
+                audit_access(app, s3, &buckets, false).await;
             }
         }
+        _ => {}
     }
+}
 
-    if already_restoring > 0 {
-        app.push_status(&format!(
-            "Skipped {} objects already being restored",
-            already_restoring
-        ));
+/// Look up in the background which destination keys the pending copy's
+/// rename would overwrite. A copy that keeps its keys is not checked.
+fn start_collision_check(app: &mut App, s3: &S3Service) {
+    clear_collisions(app);
+    let Some(PendingAction::Copy {
+        dest_bucket,
+        keys,
+        rewrite,
+        ..
+    }) = &app.pending_action
+    else {
+        return;
+    };
+    if rewrite.is_empty() {
+        return;
     }
-    if already_available > 0 {
-        app.push_status(&format!(
-            "Skipped {} objects already restored",
-            already_available
-        ));
+    let (s3, dest_bucket, keys, rewrite) = (
+        s3.clone(),
+        dest_bucket.clone(),
+        keys.clone(),
+        rewrite.clone(),
+    );
+    app.push_status(&tf(
+        "status.checking_collisions",
+        &[("count", &keys.len()), ("dest_bucket", &dest_bucket)],
+    ));
+    app.collision_check = Some(tokio::spawn(async move {
+        CollisionReview::find(&s3, &dest_bucket, &rewrite, &keys).await
+    }));
+}
+
+fn handle_collision_keys(key: KeyEvent, app: &mut App, s3: &S3Service) {
+    if app.collision_error.is_some() {
+        match key.code {
+            KeyCode::Char('r') => {
+                start_collision_check(app, s3);
+                app.set_mode(AppMode::Confirming);
+            }
+            KeyCode::Char('O') => {
+                app.collision_error = None;
+                app.push_status(t("status.no_collision_check"));
+                app.set_mode(AppMode::Confirming);
+            }
+            KeyCode::Esc | KeyCode::Char('a') => {
+                abort_colliding_copy(app, t("status.copy_aborted_check"))
+            }
+            _ => {}
+        }
+        return;
+    }
+    let Some(review) = &mut app.collisions else {
+        app.set_mode(AppMode::Confirming);
+        return;
+    };
+    let last = review.collisions.len().saturating_sub(1);
+    match key.code {
+        KeyCode::Up => review.cursor = review.cursor.saturating_sub(1),
+        KeyCode::Down => review.cursor = (review.cursor + 1).min(last),
+        KeyCode::Char('s') => {
+            review.choose(CollisionChoice::Skip);
+            review.cursor = (review.cursor + 1).min(last);
+        }
+        KeyCode::Char('o') => {
+            review.choose(CollisionChoice::Overwrite);
+            review.cursor = (review.cursor + 1).min(last);
+        }
+        KeyCode::Char('S') => review.choose_all(CollisionChoice::Skip),
+        KeyCode::Char('O') => review.choose_all(CollisionChoice::Overwrite),
+        KeyCode::Esc | KeyCode::Char('a') => {
+            let count = review.collisions.len();
+            abort_colliding_copy(
+                app,
+                &tf("status.copy_aborted_collisions", &[("count", &count)]),
+            );
+        }
+        KeyCode::Enter => resolve_collisions(app),
+        _ => {}
     }
+}
 
-    if keys_to_restore.is_empty() {
-        app.push_status("No objects need restore");
-        return Ok(());
+/// Drop the pending copy from the collision review
+fn abort_colliding_copy(app: &mut App, status: &str) {
+    clear_collisions(app);
+    app.pending_action = None;
+    app.job_note = None;
+    app.job_ticket = None;
+    if let Some(check) = app.ticket_check.take() {
+        check.abort();
     }
+    app.set_mode(AppMode::Browsing);
+    app.push_status(status);
+}
 
-    // Initialize progress tracking
-    let total = keys_to_restore.len();
-    app.progress = Some(crate::app::ProgressState::new(
-        "Requesting Glacier restore".to_string(),
-        total,
+/// Leave the skipped keys out of the pending copy and return to its
+/// confirmation
+fn resolve_collisions(app: &mut App) {
+    let Some(review) = app.collisions.take() else {
+        return;
+    };
+    let skipped = review.skipped();
+    let overwritten = review.collisions.len() - skipped.len();
+    if let Some(PendingAction::Copy { keys, .. }) = &mut app.pending_action {
+        keys.retain(|key| !skipped.contains(key.as_str()));
+        if keys.is_empty() {
+            app.pending_action = None;
+            app.job_note = None;
+            app.job_ticket = None;
+            app.set_mode(AppMode::Browsing);
+            app.push_status(t("status.nothing_left_to_copy"));
+            return;
+        }
+    }
+    app.push_status(&tf(
+        "status.skipping_collisions",
+        &[("count", &skipped.len()), ("overwritten", &overwritten)],
+    ));
+    app.set_mode(AppMode::Confirming);
+}
+
+/// Step the copy form's destination through the listed buckets, leaving out
+/// the source
+fn cycle_copy_bucket(app: &mut App, delta: isize) {
+    let source = app.selected_bucket_name().unwrap_or_default();
+    let names: Vec<&str> = app
+        .buckets
+        .iter()
+        .map(|bucket| bucket.name.as_str())
+        .filter(|name| *name != source)
+        .collect();
+    if names.is_empty() {
+        return;
+    }
+    let index = match names
+        .iter()
+        .position(|name| *name == app.copy_draft.dest_bucket)
+    {
+        Some(current) => (current as isize + delta).rem_euclid(names.len() as isize) as usize,
+        None if delta < 0 => names.len() - 1,
+        None => 0,
+    };
+    app.copy_draft.dest_bucket = names[index].to_string();
+}
+
+/// Turn the copy form into a pending copy of the targets and confirm it
+fn confirm_copy_to(app: &mut App) -> Result<()> {
+    let source_bucket = app
+        .selected_bucket_name()
+        .context("Select a bucket first")?
+        .to_string();
+    let draft = app.copy_draft.clone();
+    let dest_bucket = draft.dest_bucket.trim().to_string();
+    if dest_bucket.is_empty() {
+        anyhow::bail!("Choose a destination bucket (←/→ cycles through the list)");
+    }
+    if dest_bucket == source_bucket {
+        anyhow::bail!("The destination is the source bucket; pick another one");
+    }
+    if !app.buckets.iter().any(|bucket| bucket.name == dest_bucket) {
+        anyhow::bail!("No bucket named {dest_bucket} is listed; create it with C first");
+    }
+    let keys = target_keys(app);
+    if keys.is_empty() {
+        anyhow::bail!("Select at least one object (mask or row)");
+    }
+    let class = draft
+        .storage_class
+        .as_ref()
+        .map_or("Standard", |class| class.label());
+    app.push_status(&tf(
+        "status.confirm_copy_class",
+        &[
+            ("count", &keys.len()),
+            ("source", &source_bucket),
+            ("dest", &dest_bucket),
+            ("class", &class),
+        ],
     ));
+    app.pending_action = Some(PendingAction::Copy {
+        source_bucket,
+        dest_bucket,
+        keys,
+        mode: CopyMode::default(),
+        delete_source: false,
+        max_bandwidth: app.max_bandwidth,
+        rewrite: draft.rewrite,
+        storage_class: draft.storage_class,
+    });
+    app.set_mode(AppMode::Confirming);
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn execute_copy(
+    monitor: &mut BatchMonitor<'_>,
+    app: &mut App,
+    s3: &S3Service,
+    history: &mut JobHistory,
+    uploads: &mut MultipartStore,
+    source_bucket: &str,
+    dest_bucket: &str,
+    keys: Vec<String>,
+    mode: CopyMode,
+    delete_source: bool,
+    max_bandwidth: Option<u64>,
+    rewrite: &KeyRewrite,
+    storage_class: Option<StorageClassTier>,
+) -> Result<()> {
+    // A move scheduled before no-delete mode was turned on
+    if delete_source && app.no_delete {
+        app.push_status(&tf(
+            "status.move_refused_no_delete",
+            &[("dest_bucket", &dest_bucket)],
+        ));
+        return Ok(());
+    }
+    // A transformed copy differs from its source, so a move could not verify it
+    if delete_source && app.transform.is_some() {
+        app.push_status(&tf(
+            "status.move_refused_transform",
+            &[("dest_bucket", &dest_bucket)],
+        ));
+        return Ok(());
+    }
+    let transform = app.transform.clone();
+    let (operation, kind) = if delete_source {
+        (format!("Moving to {dest_bucket}"), JobKind::Move)
+    } else {
+        (format!("Copying to {dest_bucket}"), JobKind::Copy)
+    };
+    app.progress = Some(crate::app::ProgressState::new(operation, keys.len()));
     app.set_mode(AppMode::ShowingProgress);
+    let job_id = begin_job(app, history, kind, source_bucket, dest_bucket, keys.len());
+    // Every key of a move is recorded before the first copy, so an
+    // interrupted move can be resumed from job history
+    let mut move_sizes = HashMap::new();
+    let move_id = if delete_source {
+        let sized = keys
+            .iter()
+            .map(|key| (key.clone(), copy_source_size(app, source_bucket, key)))
+            .collect();
+        let begun = app.moves.begin_run(
+            &job_id,
+            source_bucket,
+            dest_bucket,
+            rewrite,
+            &storage_class,
+            mode,
+            max_bandwidth,
+            sized,
+        );
+        match begun {
+            Ok(id) => {
+                if let Some(manifest) = app.moves.of_run(&job_id) {
+                    move_sizes = manifest.sizes();
+                }
+                Some(id)
+            }
+            Err(err) => {
+                app.push_error(t("error.record_move"), &err);
+                history.interrupt(&job_id);
+                app.progress = None;
+                app.set_mode(AppMode::Browsing);
+                return Ok(());
+            }
+        }
+    } else {
+        None
+    };
+    let cap = app.copy_cap(source_bucket, dest_bucket, max_bandwidth);
+    let dest_region = app.bucket_region(dest_bucket).map(str::to_string);
+    if let Some(cap) = cap {
+        app.push_job_status(
+            &job_id,
+            &tf("status.copy_capped", &[("rate", &format_rate(cap))]),
+        );
+    }
 
-    let mut restored_keys = Vec::new();
     let mut success_count = 0;
     let mut error_count = 0;
-
-    for (index, key) in keys_to_restore.iter().enumerate() {
-        // Update progress
-        if let Some(progress) = &mut app.progress {
-            progress.update(index + 1, Some(key.clone()));
+    let mut skipped = 0;
+    let mut deleted = 0;
+    for (index, key) in keys.iter().enumerate() {
+        if monitor.stopping() {
+            break;
+        }
+        app.update(AppEvent::JobProgress {
+            done: index + 1,
+            key: key.clone(),
+        });
+        let dest_key = rewrite.apply(key);
+
+        // Compare with what is already in the destination, so repeated runs
+        // only copy what changed
+        if mode != CopyMode::Overwrite {
+            let compare = async {
+                let (source, dest) = tokio::join!(
+                    s3.fingerprint(source_bucket, key),
+                    s3.fingerprint(dest_bucket, &dest_key)
+                );
+                anyhow::Ok((source?, dest?))
+            };
+            let sent = Instant::now();
+            let Some(compared) = monitor.run(app, compare).await? else {
+                break;
+            };
+            app.telemetry
+                .record("HeadObject", sent.elapsed(), compared.is_ok(), 0);
+            if compared.is_ok() {
+                app.note_reached();
+            }
+            match compared {
+                Ok((Some(source), Some(dest))) if mode.skips(&source, &dest) => {
+                    skipped += 1;
+                    if !delete_source {
+                        history.record(&job_id, key, None);
+                        continue;
+                    }
+                    // A move still empties the source when the destination
+                    // already holds the same content
+                    let note = if !dest.same_content(&source) {
+                        "destination is newer; source kept"
+                    } else if app.protected.is_protected(source_bucket, key) {
+                        "already identical; source is protected and kept"
+                    } else {
+                        let Some(result) = monitor
+                            .run(app, s3.delete_object(source_bucket, key))
+                            .await?
+                        else {
+                            break;
+                        };
+                        if let Err(err) = result {
+                            error_count += 1;
+                            let err =
+                                format!("deleting the source failed: {}", errors::describe(&err));
+                            app.push_job_status(
+                                &job_id,
+                                &tf("error.move", &[("key", &key), ("err", &err)]),
+                            );
+                            history.record(&job_id, key, Some(err));
+                            continue;
+                        }
+                        deleted += 1;
+                        "already identical; source deleted"
+                    };
+                    history.record_with_note(&job_id, key, None, Some(note.to_string()));
+                    continue;
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    error_count += 1;
+                    let context = tf("error.copy_compare", &[("key", &key)]);
+                    app.push_job_error(&job_id, &context, &err);
+                    history.record(&job_id, key, Some(errors::describe(&err)));
+                    continue;
+                }
+            }
         }
 
-        // Yield to allow UI updates
-        tokio::task::yield_now().await;
-
-        match s3.request_restore(&bucket, key, days).await {
-            Ok(_) => {
+        // A resumed move has the sizes its first run recorded
+        let size = move_sizes
+            .get(key)
+            .copied()
+            .unwrap_or_else(|| copy_source_size(app, source_bucket, key));
+        // Wait for this copy's turn on the link, behind every earlier copy
+        // under the cap
+        if let Some(cap) = cap {
+            let wait = app.throttle.reserve(size.max(0) as u64, cap);
+            if !monitor.hold(app, wait).await? {
+                break;
+            }
+        }
+        let stop = monitor.stop;
+        let upload = Cell::new(None);
+        let step = async {
+            if let Some(transform) = &transform {
+                transform
+                    .copy(
+                        s3,
+                        source_bucket,
+                        dest_bucket,
+                        key,
+                        &dest_key,
+                        storage_class.clone(),
+                        &upload,
+                    )
+                    .await
+                    .map(|_| CopyOutcome::Completed)
+            } else if size > MULTIPART_COPY_THRESHOLD {
+                multipart::copy_large_object(
+                    s3,
+                    uploads,
+                    source_bucket,
+                    dest_bucket,
+                    key,
+                    &dest_key,
+                    storage_class.clone(),
+                    stop,
+                )
+                .await
+            } else {
+                s3.copy_object(
+                    source_bucket,
+                    key,
+                    dest_bucket,
+                    &dest_key,
+                    storage_class.clone(),
+                )
+                .await
+                .map(|_| CopyOutcome::Completed)
+            }
+        };
+        let sent = Instant::now();
+        let Some(result) = monitor.run(app, step).await? else {
+            if let Some(upload_id) = upload.take() {
+                // The transformed upload is not resumable, so nothing of it is kept
+                let _ = s3.abort_upload(dest_bucket, &dest_key, &upload_id).await;
+            }
+            abort_interrupted_upload(app, s3, uploads, dest_bucket, &dest_key).await;
+            break;
+        };
+        history.time_request(&job_id, sent.elapsed());
+        let copied = if result.is_ok() { size as u64 } else { 0 };
+        let large = size > MULTIPART_COPY_THRESHOLD;
+        let api = if transform.is_some() {
+            "GetObject+PutObject"
+        } else {
+            copy_api(large)
+        };
+        app.telemetry
+            .record(api, sent.elapsed(), result.is_ok(), copied);
+        if result.is_ok() {
+            app.note_reached();
+        }
+        if let (Ok(CopyOutcome::Completed), Some(region)) = (&result, &dest_region) {
+            app.throughput.record(region, copied, sent.elapsed());
+        }
+        match result {
+            Ok(CopyOutcome::Completed) if delete_source => {
+                if app.protected.is_protected(source_bucket, key) {
+                    success_count += 1;
+                    let note = "copied; source is protected and kept".to_string();
+                    history.record_with_note(&job_id, key, None, Some(note));
+                    if !run_object_hook(monitor, app, &job_id, dest_bucket, &dest_key, "copy")
+                        .await?
+                    {
+                        break;
+                    }
+                    continue;
+                }
+                let step = move_source(s3, source_bucket, dest_bucket, key, &dest_key, large);
+                let Some(moved) = monitor.run(app, step).await? else {
+                    // Copied but not verified: the source stays
+                    history.record(&job_id, key, Some("move aborted after the copy".into()));
+                    error_count += 1;
+                    break;
+                };
+                match moved {
+                    Ok(note) => {
+                        success_count += 1;
+                        deleted += 1;
+                        history.record_with_note(&job_id, key, None, Some(note));
+                        if !run_object_hook(monitor, app, &job_id, dest_bucket, &dest_key, "move")
+                            .await?
+                        {
+                            break;
+                        }
+                    }
+                    Err(err) => {
+                        error_count += 1;
+                        app.push_job_status(
+                            &job_id,
+                            &tf("error.move", &[("key", &key), ("err", &err)]),
+                        );
+                        history.record(&job_id, key, Some(err));
+                    }
+                }
+            }
+            Ok(CopyOutcome::Completed) => {
                 success_count += 1;
-                // Track the restore request
-                tracker.add_request(bucket.clone(), key.clone(), days);
-                restored_keys.push(key.clone());
+                history.record(&job_id, key, None);
+                if !run_object_hook(monitor, app, &job_id, dest_bucket, &dest_key, "copy").await? {
+                    break;
+                }
+            }
+            Ok(CopyOutcome::Checkpointed) => {
+                app.push_status(&tf("status.checkpointed_upload", &[("key", &key)]));
+                break;
             }
             Err(err) => {
+                // An earlier run of the move may have deleted the source after
+                // verifying its copy, then stopped before journaling the key
+                if delete_source {
+                    let check = async {
+                        let (source, dest) = tokio::join!(
+                            s3.fingerprint(source_bucket, key),
+                            s3.fingerprint(dest_bucket, &dest_key)
+                        );
+                        matches!((source, dest), (Ok(None), Ok(Some(_))))
+                    };
+                    let Some(moved) = monitor.run(app, check).await? else {
+                        history.record(&job_id, key, Some(errors::describe(&err)));
+                        error_count += 1;
+                        break;
+                    };
+                    if moved {
+                        success_count += 1;
+                        let note = "source already deleted; the copy in the destination is kept";
+                        history.record_with_note(&job_id, key, None, Some(note.to_string()));
+                        continue;
+                    }
+                }
                 error_count += 1;
-                let detail = describe_restore_error(&err);
-                app.push_status(&format!("✗ Restore failed for {key}: {detail}"));
+                history.record(&job_id, key, Some(errors::describe(&err)));
+                app.push_job_error(&job_id, &tf("error.copy", &[("key", &key)]), &err);
+            }
+        }
+    }
+    if let Some(id) = &move_id {
+        let remaining = app
+            .moves
+            .of_run(&job_id)
+            .map(|manifest| manifest.remaining(history).len())
+            .unwrap_or_default();
+        if remaining == 0 {
+            if let Err(err) = app.moves.remove(id) {
+                app.push_error(t("error.drop_move_record"), &err);
             }
+        } else {
+            app.push_status(&tf(
+                "status.move_unfinished",
+                &[("remaining", &remaining), ("dest_bucket", &dest_bucket)],
+            ));
         }
     }
+    if let Err(err) = app.throughput.save() {
+        app.push_error(t("error.save_throughput"), &err);
+    }
+    let processed = success_count + error_count + skipped;
+    if processed < keys.len() {
+        history.interrupt(&job_id);
+    } else {
+        history.finish(&job_id);
+    }
 
-    // Clear progress and return to browsing
     app.progress = None;
     app.set_mode(AppMode::Browsing);
-
-    // Show summary
-    if error_count > 0 {
-        app.push_status(&format!(
-            "Restore requests complete: {} succeeded, {} failed",
-            success_count, error_count
+    if skipped > 0 {
+        app.push_status(&tf(
+            "status.copy_skipped",
+            &[
+                ("count", &skipped),
+                ("reason", &mode.skip_reason()),
+                ("copied", &success_count),
+                ("failed", &error_count),
+            ],
         ));
-    } else {
-        app.push_status(&format!(
-            "Successfully requested restore for {} objects",
-            success_count
+    }
+    if delete_source {
+        app.push_status(&tf(
+            "status.sources_deleted",
+            &[("deleted", &deleted), ("source_bucket", &source_bucket)],
         ));
     }
-
-    // Manually update restore status for successfully restored objects
-    // AWS doesn't immediately reflect the status change, so we update it in memory
-    for obj in app.objects.iter_mut() {
-        if restored_keys.contains(&obj.key) {
-            obj.restore_state = Some(crate::models::RestoreState::InProgress { expiry: None });
-        }
+    if processed < keys.len() {
+        app.push_status(&tf(
+            "status.copy_stopped",
+            &[
+                ("processed", &processed),
+                ("total", &keys.len()),
+                ("failed", &error_count),
+            ],
+        ));
+    } else if error_count > 0 {
+        app.push_status(&tf(
+            "status.copy_complete",
+            &[("succeeded", &success_count), ("failed", &error_count)],
+        ));
+    } else {
+        app.push_status(&tf(
+            "status.copied",
+            &[
+                ("count", &success_count),
+                ("source", &source_bucket),
+                ("dest", &dest_bucket),
+            ],
+        ));
     }
 
-    // Update filtered objects if a mask is active
-    if app.active_mask.is_some() {
-        let mask = app.active_mask.clone();
-        app.apply_mask(mask);
+    // Reload both panes so the copies show up
+    load_objects_for_selection(app, s3);
+    if let Some(split) = &mut app.split {
+        *split = SplitPane::new(split.bucket.clone());
+        split.listing = Some(loader::spawn_parallel_listing(
+            s3.clone(),
+            split.bucket.clone(),
+            None,
+            None,
+            PAGE_SIZE,
+        ));
     }
-
     Ok(())
 }
 
@@ -744,144 +8072,153 @@ async fn refresh_selected_object(app: &mut App, s3: &S3Service) -> Result<()> {
         .map(|obj| obj.key.clone())
         .context("Select an object to inspect")?;
     let refreshed = s3.refresh_object(&bucket, &key).await?;
-    if let Some(existing) = app.objects.iter_mut().find(|o| o.key == key) {
-        *existing = refreshed.clone();
+    if let Some(state) = &refreshed.restore_state {
+        app.refreshed_restores.push((
+            bucket.clone(),
+            key.clone(),
+            state.clone(),
+            refreshed.restore_expiry.clone(),
+        ));
     }
-    if let Some(mask) = &app.active_mask {
-        app.filtered_objects = app
-            .objects
-            .iter()
-            .filter(|&obj| {
-                let key_matches = mask.matches(&obj.key);
-                let storage_matches = mask
-                    .storage_class_filter
-                    .as_ref()
-                    .map(|filter| &obj.storage_class == filter)
-                    .unwrap_or(true);
-                key_matches && storage_matches
-            })
-            .cloned()
-            .collect();
+    if let Some(existing) = app
+        .objects
+        .iter_mut()
+        .find(|o| o.key == key && o.is_current())
+    {
+        // HeadObject has no owner or version; keep the listing's
+        let owner = existing.owner.take();
+        let version = existing.version.take();
+        *existing = refreshed.clone();
+        existing.owner = owner;
+        existing.version = version;
     }
-    app.push_status("Object metadata refreshed");
+    app.refilter();
+    app.push_status(t("status.metadata_refreshed"));
     Ok(())
 }
 
-async fn load_objects_for_selection(app: &mut App, s3: &S3Service) -> Result<()> {
+/// Characters scrolled per Shift+←/→ press in the key column
+const KEY_SCROLL_STEP: usize = 8;
+
+/// Objects requested from the background listing per lazy-load step
+const PAGE_SIZE: usize = 200;
+
+fn load_objects_for_selection(app: &mut App, s3: &S3Service) {
     if let Some(bucket) = app.selected_bucket_name().map(|b| b.to_string()) {
         app.reset_pagination();
         app.is_loading_objects = true;
-        app.push_status(&format!("Loading objects from {}...", bucket));
+        app.push_status(&tf("status.loading_objects", &[("bucket", &bucket)]));
 
         // Skip full count for now - it can take forever on large buckets
         // We'll show loaded count vs "more available" instead
         app.total_object_count = None;
 
-        // Load first page
-        const PAGE_SIZE: i32 = 200;
-        match s3
-            .list_objects_paginated(&bucket, None, None, PAGE_SIZE)
-            .await
-        {
-            Ok((mut objects, next_token)) => {
-                objects.sort_by(|a, b| a.key.cmp(&b.key));
-                app.set_objects(objects);
-                app.continuation_token = next_token;
-                app.apply_mask(app.active_mask.clone());
-
-                let loaded = app.objects.len();
-                if app.has_more_objects() {
-                    app.push_status(&format!("Loaded {} objects (more available)", loaded));
-                } else {
-                    app.push_status(&format!("Loaded all {} objects", loaded));
-                }
-
-                // Fetch restore status for Glacier objects
-                refresh_glacier_restore_status(app, s3, &bucket).await;
-            }
-            Err(err) => {
-                app.push_status(&format!("Failed to load objects: {err:#}"));
-            }
-        }
-
-        app.is_loading_objects = false;
+        // Pages stream in from concurrent partition listings and are merged by drain_listing_events
+        let prefix = app.current_prefix().map(|p| p.to_string());
+        let start_at = app.window_start().map(|s| s.to_string());
+        app.enrichment = None;
+        app.listing = Some(if app.version_view {
+            loader::spawn_version_listing(s3.clone(), bucket, prefix, PAGE_SIZE)
+        } else if app.folder_view {
+            loader::spawn_folder_listing(s3.clone(), bucket, prefix, PAGE_SIZE)
+        } else {
+            loader::spawn_parallel_listing(s3.clone(), bucket, prefix, start_at, PAGE_SIZE)
+        });
     }
-    Ok(())
 }
 
-async fn load_more_objects(app: &mut App, s3: &S3Service) -> Result<()> {
-    if app.is_loading_objects || !app.has_more_objects() {
-        return Ok(());
+/// Ask the background listing for another page worth of objects
+fn load_more_objects(app: &mut App) {
+    if let Some(listing) = &app.listing {
+        listing.request(app.objects.len() + PAGE_SIZE);
+        app.is_loading_objects = true;
     }
+}
 
-    if let Some(bucket) = app.selected_bucket_name().map(|b| b.to_string()) {
+/// Start or stop streaming the remainder of the bucket in the background
+fn toggle_load_all(app: &mut App) {
+    let Some(listing) = &app.listing else {
+        if app.selected_bucket_name().is_some() && !app.objects.is_empty() {
+            app.push_status(t("status.all_loaded"));
+        } else {
+            app.push_status(t("status.select_bucket_load"));
+        }
+        return;
+    };
+
+    if listing.is_loading_all() {
+        listing.pause();
+        app.is_loading_objects = false;
+        app.push_status(&tf(
+            "status.background_load_stopped",
+            &[("count", &app.objects.len())],
+        ));
+    } else {
+        listing.load_all();
         app.is_loading_objects = true;
+        app.push_status(t("status.loading_all"));
+    }
+}
 
-        const PAGE_SIZE: i32 = 200;
-        match s3
-            .list_objects_paginated(&bucket, None, app.continuation_token.clone(), PAGE_SIZE)
-            .await
-        {
-            Ok((mut new_objects, next_token)) => {
-                new_objects.sort_by(|a, b| a.key.cmp(&b.key));
-                app.append_objects(new_objects);
-                app.continuation_token = next_token;
-
-                let loaded = app.objects.len();
-                if app.has_more_objects() {
-                    app.push_status(&format!("Loaded {} objects (more available)...", loaded));
-                } else {
-                    app.push_status(&format!("Loaded all {} objects", loaded));
-                }
+/// Merge pages delivered by the background listing into the object list
+/// Merge listing pages into the object list. Returns whether any event arrived.
+async fn drain_listing_events(app: &mut App, s3: &S3Service) -> bool {
+    let Some(bucket) = app.listing.as_ref().map(|l| l.bucket.clone()) else {
+        return false;
+    };
 
-                // Fetch restore status for newly loaded Glacier objects
-                refresh_glacier_restore_status(app, s3, &bucket).await;
-            }
-            Err(err) => {
-                app.push_status(&format!("Failed to load more: {err:#}"));
+    let mut received = false;
+    let mut merged_pages = 0;
+    while let Some(event) = app.listing.as_mut().and_then(|l| l.try_next()) {
+        received = true;
+        match event {
+            ListingEvent::Page(objects) => {
+                enrich_objects(app, s3, &bucket, &objects);
+                app.update(AppEvent::ObjectsLoaded(objects));
+                merged_pages += 1;
             }
+            ListingEvent::Folders(folders) => app.update(AppEvent::FoldersLoaded(folders)),
+            ListingEvent::Planned(_) | ListingEvent::PartitionDone => {}
+            ListingEvent::Failed(err) => app.update(AppEvent::Error {
+                context: "Failed to load objects".to_string(),
+                err,
+            }),
+            ListingEvent::Finished => app.update(AppEvent::ListingFinished),
         }
+    }
 
-        app.is_loading_objects = false;
+    if merged_pages == 0 {
+        return received;
     }
-    Ok(())
-}
 
-/// Fetch accurate restore status for Glacier/Deep Archive objects
-async fn refresh_glacier_restore_status(app: &mut App, s3: &S3Service, bucket: &str) {
-    use crate::models::StorageClassTier;
+    if let Some(listing) = &app.listing {
+        let loaded = app.objects.len();
+        app.is_loading_objects = loaded < listing.demand();
+        if !app.is_loading_objects && !listing.is_loading_all() {
+            app.push_status(&tf("status.loaded_more", &[("count", &loaded)]));
+        }
+    }
 
-    // Find all Glacier objects that need restore status
-    let glacier_keys: Vec<String> = app
-        .objects
+    fetch_tags_for_mask(app, s3, &bucket).await;
+    true
+}
+
+/// Queue HeadObject requests for the listed objects that need them, on the
+/// bucket's pipeline; rows are updated as the answers arrive
+fn enrich_objects(app: &mut App, s3: &S3Service, bucket: &str, objects: &[ObjectInfo]) {
+    let keys: Vec<String> = objects
         .iter()
-        .filter(|obj| {
-            matches!(
-                obj.storage_class,
-                StorageClassTier::GlacierFlexibleRetrieval | StorageClassTier::GlacierDeepArchive
-            )
-        })
+        .filter(|obj| enrich::needs_enrichment(obj))
         .map(|obj| obj.key.clone())
         .collect();
-
-    if glacier_keys.is_empty() {
+    if keys.is_empty() {
         return;
     }
-
-    // Batch fetch restore status using HeadObject (10 concurrent requests at a time)
-    let status_results = s3.batch_refresh_restore_status(bucket, &glacier_keys).await;
-
-    // Update objects with fetched restore status
-    for (key, restore_state) in status_results {
-        if let Some(obj) = app.objects.iter_mut().find(|o| o.key == key) {
-            obj.restore_state = restore_state;
-        }
+    if app.enrichment.as_ref().is_none_or(|e| e.bucket != bucket) {
+        app.enrichment = Some(enrich::spawn_enrichment(s3.clone(), bucket.to_string()));
     }
-
-    // Re-apply mask if active to update filtered list
-    if app.active_mask.is_some() {
-        let mask = app.active_mask.clone();
-        app.apply_mask(mask);
+    if let Some(enrichment) = &mut app.enrichment {
+        enrichment.enqueue(keys);
     }
 }
 
@@ -907,19 +8244,16 @@ fn move_selection(app: &mut App, delta: isize) {
             }
         }
         ActivePane::Objects => {
-            let len = app.active_objects().len();
-            if len == 0 {
-                return;
-            }
-            let len = len as isize;
-            let mut idx = app.selected_object as isize + delta;
-            if idx < 0 {
-                idx = 0;
-            }
-            if idx >= len {
-                idx = len - 1;
+            let row = app.cursor_row() as isize + delta;
+            app.set_cursor_row(row.max(0) as usize);
+        }
+        ActivePane::PeerObjects => {
+            if let Some(split) = &mut app.split
+                && !split.objects.is_empty()
+            {
+                let last = split.objects.len() as isize - 1;
+                split.selected = (split.selected as isize + delta).clamp(0, last) as usize;
             }
-            app.selected_object = idx as usize;
         }
         ActivePane::MaskEditor => {}
     }
@@ -927,23 +8261,20 @@ fn move_selection(app: &mut App, delta: isize) {
 
 fn jump_selection(app: &mut App, start: bool) {
     match app.active_pane {
-        ActivePane::Buckets => {
-            if !app.buckets.is_empty() {
-                let new_idx = if start { 0 } else { app.buckets.len() - 1 };
-                if new_idx != app.selected_bucket {
-                    app.selected_bucket = new_idx;
-                    app.last_bucket_change = Some(std::time::Instant::now());
-                    app.pending_bucket_load = true;
-                }
+        ActivePane::Buckets if !app.buckets.is_empty() => {
+            let new_idx = if start { 0 } else { app.buckets.len() - 1 };
+            if new_idx != app.selected_bucket {
+                app.selected_bucket = new_idx;
+                app.last_bucket_change = Some(std::time::Instant::now());
+                app.pending_bucket_load = true;
             }
         }
-        ActivePane::Objects => {
-            if !app.active_objects().is_empty() {
-                app.selected_object = if start {
-                    0
-                } else {
-                    app.active_objects().len() - 1
-                };
+        ActivePane::Objects => app.set_cursor_row(if start { 0 } else { usize::MAX }),
+        ActivePane::PeerObjects => {
+            if let Some(split) = &mut app.split
+                && !split.objects.is_empty()
+            {
+                split.selected = if start { 0 } else { split.objects.len() - 1 };
             }
         }
         _ => {}
@@ -970,1072 +8301,95 @@ fn cycle_region(app: &mut App, delta: isize) {
 
     app.set_region(region_to_set);
     app.active_pane = ActivePane::Buckets; // Ensure focus returns to buckets
-    app.push_status(&format!("Region filter: {}", new_region));
+    app.push_status(&tf(
+        "status.region_filter",
+        &[
+            ("region", &App::region_label(&new_region)),
+            ("badge", &app.region_badge(&new_region)),
+        ],
+    ));
 }
 
-fn target_count(app: &App) -> usize {
-    if app.active_mask.is_some() {
-        app.filtered_objects.len()
-    } else if app.selected_object < app.objects.len() {
-        1
-    } else {
-        0
-    }
+/// Size of a loaded object in the main pane, or 0 if it is not loaded
+fn object_class(app: &App, key: &str) -> StorageClassTier {
+    app.active_objects()
+        .iter()
+        .chain(&app.objects)
+        .find(|o| o.key == key)
+        .map(|o| o.storage_class.clone())
+        .unwrap_or(StorageClassTier::Unknown(String::new()))
 }
 
-fn target_keys(app: &App) -> Vec<String> {
-    if app.active_mask.is_some() {
-        app.filtered_objects.iter().map(|o| o.key.clone()).collect()
+/// Size of a key about to be copied out of `source_bucket`, from the loaded
+/// listings or a reconciliation's re-copy list; 0 when neither has it
+fn copy_source_size(app: &App, source_bucket: &str, key: &str) -> i64 {
+    let size = if source_bucket == app.selected_bucket_name().unwrap_or_default() {
+        object_size(app, key)
     } else {
-        app.objects
-            .get(app.selected_object)
-            .map(|o| vec![o.key.clone()])
+        app.split
+            .as_ref()
+            .and_then(|split| split.objects.iter().find(|o| o.key == key))
+            .map(|o| o.size)
             .unwrap_or_default()
-    }
-}
-
-fn draw(frame: &mut ratatui::Frame, app: &App, tracker: &RestoreTracker) {
-    let size = frame.size();
-
-    // Main vertical split: content area, status, command bar
-    let vertical = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Min(10),
-            Constraint::Length(4),
-            Constraint::Length(3),
-        ])
-        .split(size);
-
-    // Main content panel: bucket selector, mask, objects, object detail
-    let main_panel = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3), // Bucket selector (compact)
-            Constraint::Length(5), // Mask panel
-            Constraint::Min(10),   // Objects list
-            Constraint::Length(8), // Selected object detail
-        ])
-        .split(vertical[0]);
-
-    draw_bucket_selector(frame, main_panel[0], app);
-    draw_mask_panel(frame, main_panel[1], app);
-    draw_objects(frame, main_panel[2], app);
-    draw_object_detail(frame, main_panel[3], app);
-    draw_status(frame, vertical[1], app);
-    draw_command_bar(frame, vertical[2]);
-
-    match app.mode {
-        AppMode::CredentialError => draw_credential_error_popup(frame),
-        AppMode::EditingMask => draw_mask_popup(frame, app),
-        AppMode::SelectingStorageClass => draw_storage_popup(frame, app),
-        AppMode::Confirming => draw_confirm_popup(frame, app),
-        AppMode::ShowingHelp => draw_help_popup(frame),
-        AppMode::ViewingLog => draw_log_popup(frame, app),
-        AppMode::ViewingRestoreRequests => draw_tracked_requests_popup(frame, tracker),
-        AppMode::ShowingProgress => draw_progress_popup(frame, app),
-        AppMode::Browsing => {}
-    }
-}
-
-fn draw_bucket_selector(frame: &mut ratatui::Frame, area: Rect, app: &App) {
-    let key_style = Style::default()
-        .bg(Color::LightCyan)
-        .fg(Color::Black)
-        .add_modifier(Modifier::BOLD);
-
-    let bucket_name = app.selected_bucket_name().unwrap_or("(no bucket selected)");
-    let bucket_info = format!("  ({}/{})  ", app.selected_bucket + 1, app.buckets.len());
-
-    let title_style = Style::default()
-        .fg(Color::LightMagenta)
-        .add_modifier(Modifier::BOLD);
-
-    let block = Block::default()
-        .borders(Borders::ALL)
-        .border_style(highlight_border(app.active_pane == ActivePane::Buckets))
-        .style(Style::default().bg(Color::Black).fg(Color::White));
-
-    let text = Line::from(vec![
-        Span::styled("Region: ", Style::default().fg(Color::Cyan)),
-        Span::styled(
-            app.get_current_region_display(),
-            Style::default()
-                .fg(Color::LightGreen)
-                .add_modifier(Modifier::BOLD),
-        ),
-        Span::raw(" "),
-        Span::styled("←", key_style),
-        Span::styled("→", key_style),
-        Span::raw(" cycle  │  "),
-        Span::styled("Bucket: ", Style::default().fg(Color::Cyan)),
-        Span::styled(bucket_name, title_style),
-        Span::raw(bucket_info),
-        Span::styled("↑", key_style),
-        Span::styled("↓", key_style),
-        Span::raw(" select"),
-    ]);
-
-    let para = Paragraph::new(text).block(block);
-    frame.render_widget(para, area);
-}
-
-fn draw_objects(frame: &mut ratatui::Frame, area: Rect, app: &App) {
-    let objects = app.active_objects();
-    let loaded_count = app.objects.len();
-    let total_count = app.total_object_count.unwrap_or(loaded_count);
-
-    let loading_indicator = if app.is_loading_objects {
-        " ⟳"
-    } else if app.has_more_objects() {
-        " +"
-    } else {
-        ""
     };
+    // A re-copy from a reconciliation includes keys that were never loaded
+    app.reconciliation
+        .as_ref()
+        .filter(|r| r.source_bucket == source_bucket)
+        .and_then(|r| r.recopy.get(key).copied())
+        .unwrap_or(size)
+}
 
-    let title = if let Some(mask) = &app.active_mask {
-        format!(
-            "Objects – mask: {} ({} matches of {} loaded{}){}",
-            mask.summary(),
-            app.filtered_objects.len(),
-            loaded_count,
-            if loaded_count < total_count {
-                format!(" of {}", total_count)
-            } else {
-                String::new()
-            },
-            loading_indicator
-        )
-    } else {
-        format!(
-            "Objects (showing {} of {}){}",
-            loaded_count, total_count, loading_indicator
-        )
-    };
-    let title_style = Style::default()
-        .fg(Color::LightCyan)
-        .add_modifier(Modifier::BOLD);
-    let block = Block::default()
-        .title(Span::styled(title, title_style))
-        .borders(Borders::ALL)
-        .border_style(highlight_border(app.active_pane == ActivePane::Objects))
-        .style(Style::default().bg(Color::Black));
-
-    // Calculate available width for the key column
-    // 2 (marker) + 1 (space) + 13 (size) + 1 (space) + 20 (storage) + 1 (space) + 13 (restore) + 2 (borders) = 53
-    let fixed_width = 53;
-    let key_width = area.width.saturating_sub(fixed_width).max(20) as usize;
-
-    let items: Vec<ListItem> = objects
+fn object_size(app: &App, key: &str) -> i64 {
+    app.active_objects()
         .iter()
-        .enumerate()
-        .map(|(idx, obj)| {
-            let is_selected = idx == app.selected_object;
-            let marker = if is_selected { "►" } else { " " };
-            let marker_style = if is_selected {
-                Style::default()
-                    .fg(Color::LightYellow)
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(Color::DarkGray)
-            };
-            let key_style = if is_selected {
-                Style::default()
-                    .fg(Color::LightGreen)
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(Color::White)
-            };
-
-            // Truncate or pad the key to fixed width
-            let key_display = if obj.key.len() > key_width {
-                format!("{}…", &obj.key[..key_width.saturating_sub(1)])
-            } else {
-                format!("{:<width$}", obj.key, width = key_width)
-            };
-
-            // Format storage class with fixed width
-            let storage_label = format!("{:<20}", obj.storage_class.label());
-
-            // Get restore status with more descriptive text
-            let (restore_symbol, restore_style) = match &obj.restore_state {
-                Some(RestoreState::Available) => (
-                    " Restored",
-                    Style::default()
-                        .fg(Color::LightGreen)
-                        .add_modifier(Modifier::BOLD),
-                ),
-                Some(RestoreState::InProgress { .. }) => (
-                    " Restoring",
-                    Style::default()
-                        .fg(Color::Yellow)
-                        .add_modifier(Modifier::BOLD),
-                ),
-                Some(RestoreState::Expired) => (" Expired", Style::default().fg(Color::Red)),
-                None => {
-                    // Check if object is in Glacier and needs restore
-                    if matches!(
-                        obj.storage_class,
-                        crate::models::StorageClassTier::GlacierFlexibleRetrieval
-                            | crate::models::StorageClassTier::GlacierDeepArchive
-                    ) {
-                        (
-                            " NeedsRestore",
-                            Style::default()
-                                .fg(Color::Magenta)
-                                .add_modifier(Modifier::BOLD),
-                        )
-                    } else {
-                        ("", Style::default().fg(Color::DarkGray))
-                    }
-                }
-            };
-
-            let spans = vec![
-                Span::styled(marker.to_string(), marker_style),
-                Span::raw(" "),
-                Span::styled(key_display, key_style),
-                Span::raw(" "),
-                Span::styled(format_size(obj.size), Style::default().fg(Color::LightCyan)),
-                Span::raw(" "),
-                Span::styled(storage_label, storage_class_color(&obj.storage_class)),
-                Span::styled(restore_symbol, restore_style),
-            ];
-
-            ListItem::new(Line::from(spans))
-        })
-        .collect();
-    let mut state = ListState::default();
-    if !objects.is_empty() {
-        state.select(Some(app.selected_object.min(objects.len() - 1)));
-    }
-    let list = List::new(items)
-        .highlight_style(Style::default().bg(Color::Blue))
-        .block(block);
-    frame.render_stateful_widget(list, area, &mut state);
-}
-
-fn draw_object_detail(frame: &mut ratatui::Frame, area: Rect, app: &App) {
-    let title_style = Style::default()
-        .fg(Color::LightYellow)
-        .add_modifier(Modifier::BOLD);
-    let block = Block::default()
-        .title(Span::styled("Selected object", title_style))
-        .borders(Borders::ALL)
-        .style(Style::default().bg(Color::Black));
-    let lines = if let Some(obj) = app.selected_object() {
-        let modified = obj
-            .last_modified
-            .clone()
-            .unwrap_or_else(|| "unknown".into());
-
-        // Match the restore status labels used in the objects list
-        let restore = match &obj.restore_state {
-            Some(RestoreState::Available) => "Restored".to_string(),
-            Some(RestoreState::InProgress { .. }) => "Restoring".to_string(),
-            Some(RestoreState::Expired) => "Expired".to_string(),
-            None => {
-                // Check if object is in Glacier and needs restore
-                if matches!(
-                    obj.storage_class,
-                    crate::models::StorageClassTier::GlacierFlexibleRetrieval
-                        | crate::models::StorageClassTier::GlacierDeepArchive
-                ) {
-                    "NeedsRestore".to_string()
-                } else {
-                    "N/A".to_string()
-                }
-            }
-        };
-
-        vec![
-            Line::from(format!("Key: {}", obj.key)),
-            Line::from(format!("Size: {}", format_size(obj.size))),
-            Line::from(format!("Storage: {}", obj.storage_class.label())),
-            Line::from(format!("Last modified: {}", modified)),
-            Line::from(format!("Restore: {}", restore)),
-        ]
-    } else {
-        vec![Line::from("No object selected")]
-    };
-    let para = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
-    frame.render_widget(para, area);
-}
-
-fn draw_mask_panel(frame: &mut ratatui::Frame, area: Rect, app: &App) {
-    let title_style = Style::default()
-        .fg(Color::LightMagenta)
-        .add_modifier(Modifier::BOLD);
-    let block = Block::default()
-        .title(Span::styled("Filter Mask", title_style))
-        .borders(Borders::ALL)
-        .border_style(highlight_border(app.active_pane == ActivePane::MaskEditor))
-        .style(Style::default().bg(Color::Black));
-
-    let content = if let Some(mask) = &app.active_mask {
-        let count_style = Style::default()
-            .fg(Color::LightYellow)
-            .add_modifier(Modifier::BOLD);
-        Line::from(vec![
-            Span::styled("Active: ", Style::default().fg(Color::Cyan)),
-            Span::styled(mask.summary(), Style::default().fg(Color::LightGreen)),
-            Span::raw("  "),
-            Span::styled(
-                format!("({} matches)", app.filtered_objects.len()),
-                count_style,
-            ),
-            Span::raw("  "),
-            Span::styled("Esc", Style::default().bg(Color::DarkGray).fg(Color::White)),
-            Span::raw(" clear  "),
-            Span::styled("m", Style::default().bg(Color::DarkGray).fg(Color::White)),
-            Span::raw(" edit"),
-        ])
-    } else {
-        Line::from(vec![
-            Span::styled("None. Press ", Style::default().fg(Color::Gray)),
-            Span::styled("m", Style::default().bg(Color::LightCyan).fg(Color::Black)),
-            Span::styled(" to create a filter mask", Style::default().fg(Color::Gray)),
-        ])
-    };
-
-    let para = Paragraph::new(content).block(block);
-    frame.render_widget(para, area);
+        .chain(&app.objects)
+        .find(|o| o.key == key)
+        .map(|o| o.size)
+        .unwrap_or_default()
 }
 
-fn draw_status(frame: &mut ratatui::Frame, area: Rect, app: &App) {
-    let lines: Vec<Line> = app
-        .status
+/// Targeted keys that actually need a transition to `target_class`, plus the
+/// number skipped because they are already in that class
+fn transition_keys(app: &App, target_class: &StorageClassTier) -> (Vec<String>, usize) {
+    let objects = app.target_objects();
+    let keys: Vec<String> = objects
         .iter()
-        .rev()
-        .map(|msg| Line::from(msg.clone()))
+        .filter(|o| &o.storage_class != target_class)
+        .map(|o| o.key.clone())
         .collect();
-    let block = Block::default()
-        .borders(Borders::ALL)
-        .title(Span::styled(
-            "Status",
-            Style::default()
-                .fg(Color::LightCyan)
-                .add_modifier(Modifier::BOLD),
-        ))
-        .style(Style::default().bg(Color::Black));
-    let para = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
-    frame.render_widget(para, area);
-}
-
-fn draw_command_bar(frame: &mut ratatui::Frame, area: Rect) {
-    let key_style = Style::default()
-        .bg(Color::LightCyan)
-        .fg(Color::Black)
-        .add_modifier(Modifier::BOLD);
-    let help = Line::from(vec![
-        Span::styled(" Tab ", key_style),
-        Span::raw(" "),
-        Span::styled(" m ", key_style),
-        Span::raw("ask "),
-        Span::styled(" s ", key_style),
-        Span::raw("torage "),
-        Span::styled(" r ", key_style),
-        Span::raw("estore "),
-        Span::styled(" i ", key_style),
-        Span::raw("nfo "),
-        Span::styled(" f ", key_style),
-        Span::raw("refresh "),
-        Span::styled(" t ", key_style),
-        Span::raw("racker "),
-        Span::styled(" ? ", key_style),
-        Span::raw("help "),
-        Span::styled(" l ", key_style),
-        Span::raw("og "),
-        Span::styled(" q ", key_style),
-        Span::raw("uit"),
-    ]);
-    let block = Block::default()
-        .borders(Borders::ALL)
-        .style(Style::default().bg(Color::Blue).fg(Color::White));
-    let para = Paragraph::new(help).block(block);
-    frame.render_widget(para, area);
-}
-
-fn draw_mask_popup(frame: &mut ratatui::Frame, app: &App) {
-    let area = centered_rect(70, 40, frame.size());
-    draw_modal_surface(frame, area);
-
-    let title_style = Style::default()
-        .fg(Color::Cyan)
-        .add_modifier(Modifier::BOLD);
-    let block = Block::default()
-        .title(Span::styled(" Create Object Filter ", title_style))
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan))
-        .style(Style::default().bg(Color::Rgb(20, 20, 30)));
-
-    let label_style = Style::default()
-        .fg(Color::LightBlue)
-        .add_modifier(Modifier::BOLD);
-    let active_style = Style::default()
-        .fg(Color::LightYellow)
-        .add_modifier(Modifier::BOLD);
-    let inactive_style = Style::default().fg(Color::Gray);
-    let hint_style = Style::default().fg(Color::DarkGray);
-
-    // Create pattern field with cursor
-    let is_pattern_focused = matches!(app.mask_field, MaskEditorField::Pattern);
-    let mut pattern_spans = vec![Span::styled("Pattern: ", label_style)];
-
-    if is_pattern_focused {
-        // Show cursor in pattern field
-        let before_cursor = &app.mask_draft.pattern[..app.mask_draft.cursor_pos];
-        let cursor_char = if app.mask_draft.cursor_pos < app.mask_draft.pattern.len() {
-            app.mask_draft
-                .pattern
-                .chars()
-                .nth(app.mask_draft.cursor_pos)
-                .unwrap()
-                .to_string()
-        } else {
-            " ".to_string()
-        };
-        let after_cursor = if app.mask_draft.cursor_pos < app.mask_draft.pattern.len() {
-            &app.mask_draft.pattern[app.mask_draft.cursor_pos + 1..]
-        } else {
-            ""
-        };
+    let skipped = objects.len() - keys.len();
+    (keys, skipped)
+}
 
-        pattern_spans.push(Span::styled(before_cursor, active_style));
-        pattern_spans.push(Span::styled(
-            cursor_char,
-            Style::default().fg(Color::Black).bg(Color::LightYellow),
-        ));
-        pattern_spans.push(Span::styled(after_cursor, active_style));
-    } else {
-        let display = if app.mask_draft.pattern.is_empty() {
-            "(empty)"
-        } else {
-            &app.mask_draft.pattern
-        };
-        pattern_spans.push(Span::styled(display, inactive_style));
-    }
-
-    let text = vec![
-        Line::from(""),
-        Line::from(pattern_spans),
-        Line::from(vec![
-            Span::styled("          ", Style::default()),
-            Span::styled("↑ Type your filter pattern here", hint_style),
-        ]),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled(
-                "Match Mode: ",
-                if matches!(app.mask_field, MaskEditorField::Mode) {
-                    active_style
-                } else {
-                    label_style
-                },
-            ),
-            Span::styled(
-                app.mask_draft.kind.to_string(),
-                if matches!(app.mask_field, MaskEditorField::Mode) {
-                    active_style
-                } else {
-                    inactive_style
-                },
-            ),
-            Span::styled("  (use ←/→ or space)", hint_style),
-        ]),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled(
-                "Case Sensitive: ",
-                if matches!(app.mask_field, MaskEditorField::Case) {
-                    active_style
-                } else {
-                    label_style
-                },
-            ),
-            Span::styled(
-                if app.mask_draft.case_sensitive {
-                    "Yes"
-                } else {
-                    "No"
-                },
-                if matches!(app.mask_field, MaskEditorField::Case) {
-                    active_style
-                } else {
-                    inactive_style
-                },
-            ),
-            Span::styled("  (space or ←/→ toggles)", hint_style),
-        ]),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled(
-                "Storage Class: ",
-                if matches!(app.mask_field, MaskEditorField::StorageClass) {
-                    active_style
-                } else {
-                    label_style
-                },
-            ),
-            Span::styled(
-                app.mask_draft
-                    .storage_class_filter
-                    .as_ref()
-                    .map(|s| s.label())
-                    .unwrap_or("Any"),
-                if matches!(app.mask_field, MaskEditorField::StorageClass) {
-                    active_style
-                } else {
-                    inactive_style
-                },
-            ),
-            Span::styled("  (use ←/→ or space)", hint_style),
-        ]),
-        Line::from(""),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled(
-                "Tab",
-                Style::default()
-                    .fg(Color::LightGreen)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(" move between fields  ", hint_style),
-            Span::styled(
-                "Enter",
-                Style::default()
-                    .fg(Color::LightGreen)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(" apply  ", hint_style),
-            Span::styled(
-                "Esc",
-                Style::default()
-                    .fg(Color::LightGreen)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(" cancel", hint_style),
-        ]),
-    ];
-    let para = Paragraph::new(text).block(block);
-    frame.render_widget(para, area);
-}
-
-fn draw_storage_popup(frame: &mut ratatui::Frame, app: &App) {
-    let area = centered_rect(40, 50, frame.size());
-    draw_modal_surface(frame, area);
-    let block = Block::default()
-        .title("Select storage class (Enter confirm, Esc cancel)")
-        .borders(Borders::ALL)
-        .style(Style::default().bg(Color::Black));
-    let items: Vec<ListItem> = StorageClassTier::selectable()
-        .iter()
-        .map(|class| ListItem::new(class.label()))
-        .collect();
-    let mut state = ListState::default();
-    state.select(Some(app.storage_class_cursor));
-    let list = List::new(items)
-        .block(block)
-        .highlight_style(Style::default().fg(Color::Black).bg(Color::Yellow));
-    frame.render_stateful_widget(list, area, &mut state);
-}
-
-fn draw_confirm_popup(frame: &mut ratatui::Frame, app: &App) {
-    let area = centered_rect(60, 40, frame.size());
-    draw_modal_surface(frame, area);
-
-    let key_style = Style::default()
-        .bg(Color::LightYellow)
-        .fg(Color::Black)
-        .add_modifier(Modifier::BOLD);
-    let warn_style = Style::default()
-        .fg(Color::LightYellow)
-        .add_modifier(Modifier::BOLD);
-    let highlight_style = Style::default()
-        .fg(Color::LightGreen)
-        .add_modifier(Modifier::BOLD);
-
-    let mut lines = Vec::new();
-
-    if let Some(action) = &app.pending_action {
-        match action {
-            PendingAction::Transition { target_class } => {
-                lines.push(Line::from(vec![Span::styled(
-                    "Transition Storage Class",
-                    warn_style,
-                )]));
-                lines.push(Line::from(""));
-                lines.push(Line::from(vec![
-                    Span::raw("  Objects: "),
-                    Span::styled(format!("{}", target_count(app)), highlight_style),
-                ]));
-                lines.push(Line::from(vec![
-                    Span::raw("  Target:  "),
-                    Span::styled(target_class.label(), highlight_style),
-                ]));
-            }
-            PendingAction::Restore { days } => {
-                lines.push(Line::from(vec![Span::styled(
-                    "Request Glacier Restore",
-                    warn_style,
-                )]));
-                lines.push(Line::from(""));
-                lines.push(Line::from(vec![
-                    Span::raw("  Objects:  "),
-                    Span::styled(format!("{}", target_count(app)), highlight_style),
-                ]));
-                lines.push(Line::from(vec![
-                    Span::raw("  Duration: "),
-                    Span::styled(format!("{} days", days), highlight_style),
-                ]));
-            }
-        }
-    }
-
-    lines.push(Line::from(""));
-    lines.push(Line::from(vec![
-        Span::styled(" Enter ", key_style),
-        Span::raw(" Confirm   "),
-        Span::styled(" Esc ", key_style),
-        Span::raw(" Cancel"),
-    ]));
-
-    let block = Block::default()
-        .title(Span::styled(
-            " Confirm Action ",
-            Style::default()
-                .fg(Color::LightYellow)
-                .add_modifier(Modifier::BOLD),
-        ))
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Yellow))
-        .style(Style::default().bg(Color::Black));
-    let para = Paragraph::new(lines).block(block);
-    frame.render_widget(para, area);
-}
-
-fn draw_help_popup(frame: &mut ratatui::Frame) {
-    let area = centered_rect(80, 80, frame.size());
-    draw_modal_surface(frame, area);
-    let title_style = Style::default()
-        .fg(Color::LightYellow)
-        .add_modifier(Modifier::BOLD);
-    let block = Block::default()
-        .title(Span::styled(
-            "Help & Workflow Guide – Press ? or Esc to close",
-            title_style,
-        ))
-        .borders(Borders::ALL)
-        .style(Style::default().bg(Color::Black));
-
-    let key_style = Style::default()
-        .fg(Color::LightCyan)
-        .add_modifier(Modifier::BOLD);
-    let header_style = Style::default()
-        .fg(Color::LightGreen)
-        .add_modifier(Modifier::BOLD);
-
-    let lines = vec![
-        Line::from(vec![Span::styled("BASIC WORKFLOW", header_style)]),
-        Line::from("1. Navigate with Tab/Shift+Tab to switch between panes (Buckets, Objects)"),
-        Line::from("2. Select a bucket with arrows, press Enter to load its objects"),
-        Line::from("3. Create a mask (press 'm') to filter objects by pattern"),
-        Line::from("4. Transition objects to different storage classes or request restores"),
-        Line::from(""),
-        Line::from(vec![Span::styled("NAVIGATION", header_style)]),
-        Line::from(vec![
-            Span::styled("Tab/Shift+Tab", key_style),
-            Span::raw(" - Switch between panes  "),
-            Span::styled("↑↓", key_style),
-            Span::raw(" - Move selection  "),
-            Span::styled("PgUp/PgDn", key_style),
-            Span::raw(" - Jump 5 items"),
-        ]),
-        Line::from(vec![
-            Span::styled("Enter", key_style),
-            Span::raw(" - Load bucket objects (Buckets pane)"),
-        ]),
-        Line::from(""),
-        Line::from(vec![Span::styled("OBJECT FILTERING (MASKS)", header_style)]),
-        Line::from(vec![
-            Span::styled("m", key_style),
-            Span::raw(" - Open mask editor to create/edit filters"),
-        ]),
-        Line::from("   • Tab moves between fields: Name → Pattern → Mode → Case"),
-        Line::from("   • Match modes: Prefix, Suffix, Contains, Regex (use arrows/space to cycle)"),
-        Line::from("   • Enter applies the mask, Esc cancels"),
-        Line::from("   • Active masks filter the object list and target all matching objects"),
-        Line::from(vec![
-            Span::styled("Esc", key_style),
-            Span::raw(" - Clear active mask and show all objects"),
-        ]),
-        Line::from(""),
-        Line::from(vec![Span::styled("STORAGE OPERATIONS", header_style)]),
-        Line::from(vec![
-            Span::styled("s", key_style),
-            Span::raw(" - Transition objects to a different storage class"),
-        ]),
-        Line::from("   • Without mask: transitions the selected object only"),
-        Line::from("   • With mask: transitions ALL matching objects"),
-        Line::from("   • Press 'o' during confirmation to toggle restore-before-transition"),
-        Line::from(vec![
-            Span::styled("r", key_style),
-            Span::raw(" - Request 7-day Glacier restore for selected/masked objects"),
-        ]),
-        Line::from(vec![
-            Span::styled("i", key_style),
-            Span::raw(" - Inspect selected object (refreshes metadata via HeadObject)"),
-        ]),
-        Line::from(""),
-        Line::from(vec![Span::styled("OTHER COMMANDS", header_style)]),
-        Line::from(vec![
-            Span::styled("l", key_style),
-            Span::raw(" - Toggle status log (view full error messages)  "),
-            Span::styled("f", key_style),
-            Span::raw(" - Refresh bucket list"),
-        ]),
-        Line::from(vec![
-            Span::styled("?", key_style),
-            Span::raw(" - Toggle this help screen  "),
-            Span::styled("q", key_style),
-            Span::raw(" or "),
-            Span::styled("Ctrl+C", key_style),
-            Span::raw(" - Quit application"),
-        ]),
-    ];
-    let para = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
-    frame.render_widget(para, area);
-}
-
-fn draw_log_popup(frame: &mut ratatui::Frame, app: &App) {
-    let area = centered_rect(70, 60, frame.size());
-    draw_modal_surface(frame, area);
-    let block = Block::default()
-        .title("Status log – Esc/l/Enter to close")
-        .borders(Borders::ALL)
-        .style(Style::default().bg(Color::Black));
-    let mut lines: Vec<Line> = app
-        .status
+/// Targeted keys a re-encryption can copy, plus the number skipped because
+/// they sit in an archive class without a completed restore
+fn reencrypt_keys(app: &App) -> (Vec<String>, usize) {
+    let objects = app.target_objects();
+    let keys: Vec<String> = objects
         .iter()
-        .rev()
-        .enumerate()
-        .map(|(idx, msg)| Line::from(format!("{:>2}. {}", idx + 1, msg)))
+        .filter(|o| readable_in_place(o))
+        .map(|o| o.key.clone())
         .collect();
-    if lines.is_empty() {
-        lines.push(Line::from("No status messages yet."));
-    }
-    let para = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
-    frame.render_widget(para, area);
-}
-
-fn draw_tracked_requests_popup(frame: &mut ratatui::Frame, tracker: &RestoreTracker) {
-    let area = centered_rect(80, 70, frame.size());
-    draw_modal_surface(frame, area);
-
-    let block = Block::default()
-        .title("Tracked Restore Requests – Esc/t/Enter to close")
-        .borders(Borders::ALL)
-        .style(Style::default().bg(Color::Black));
-
-    let requests = tracker.get_all_requests();
-
-    let mut lines: Vec<Line> = vec![
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("Bucket", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" | "),
-            Span::styled("Object Key", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" | "),
-            Span::styled("Status", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" | "),
-            Span::styled("Days", Style::default().add_modifier(Modifier::BOLD)),
-        ]),
-        Line::from(std::iter::repeat('-').take(100).collect::<String>()),
-    ];
-
-    if requests.is_empty() {
-        lines.push(Line::from(""));
-        lines.push(Line::from("No restore requests tracked yet."));
-        lines.push(Line::from(""));
-        lines.push(Line::from(
-            "Restore requests will appear here after you initiate them.",
-        ));
-    } else {
-        for req in requests {
-            let status_text = match &req.current_status {
-                RestoreState::InProgress { expiry } => {
-                    if let Some(exp) = expiry {
-                        format!("In Progress (exp: {})", exp)
-                    } else {
-                        "In Progress".to_string()
-                    }
-                }
-                RestoreState::Available => "Available".to_string(),
-                RestoreState::Expired => "Expired".to_string(),
-            };
-
-            let status_style = match &req.current_status {
-                RestoreState::InProgress { .. } => Style::default().fg(Color::Yellow),
-                RestoreState::Available => Style::default().fg(Color::Green),
-                RestoreState::Expired => Style::default().fg(Color::Red),
-            };
-
-            lines.push(Line::from(vec![
-                Span::raw(format!("{} | ", req.bucket)),
-                Span::raw(format!("{} | ", req.key)),
-                Span::styled(format!("{} | ", status_text), status_style),
-                Span::raw(format!("{} days", req.days)),
-            ]));
-        }
-    }
-
-    let para = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
-    frame.render_widget(para, area);
-}
-
-fn draw_progress_popup(frame: &mut ratatui::Frame, app: &App) {
-    let area = centered_rect(70, 30, frame.size());
-    draw_modal_surface(frame, area);
-
-    let progress = match &app.progress {
-        Some(p) => p,
-        None => return,
-    };
-
-    let title_style = Style::default()
-        .fg(Color::LightCyan)
-        .add_modifier(Modifier::BOLD);
-
-    let block = Block::default()
-        .title(Span::styled(
-            format!(" {} ", progress.operation),
-            title_style,
-        ))
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan))
-        .style(Style::default().bg(Color::Black));
-
-    let inner = block.inner(area);
-    frame.render_widget(block, area);
-
-    // Split into sections
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3), // Progress bar
-            Constraint::Length(2), // Counter
-            Constraint::Length(2), // Current item
-            Constraint::Min(1),    // Padding
-        ])
-        .split(inner);
-
-    // Progress bar
-    let gauge = Gauge::default()
-        .gauge_style(
-            Style::default()
-                .fg(Color::Cyan)
-                .bg(Color::Black)
-                .add_modifier(Modifier::BOLD),
-        )
-        .percent(progress.percentage());
-    frame.render_widget(gauge, chunks[0]);
-
-    // Counter text
-    let counter_text = format!("{} / {} objects", progress.current, progress.total);
-    let counter = Paragraph::new(counter_text)
-        .style(Style::default().fg(Color::White))
-        .alignment(Alignment::Center);
-    frame.render_widget(counter, chunks[1]);
-
-    // Current item
-    if let Some(ref item) = progress.current_item {
-        let item_text = format!("Processing: {}", item);
-        let item_para = Paragraph::new(item_text)
-            .style(Style::default().fg(Color::Gray))
-            .alignment(Alignment::Center)
-            .wrap(Wrap { trim: true });
-        frame.render_widget(item_para, chunks[2]);
-    }
-}
-
-fn draw_credential_error_popup(frame: &mut ratatui::Frame) {
-    let area = centered_rect(70, 50, frame.size());
-    draw_modal_surface(frame, area);
-
-    let error_style = Style::default().fg(Color::Red).add_modifier(Modifier::BOLD);
-    let title_style = Style::default()
-        .fg(Color::LightYellow)
-        .add_modifier(Modifier::BOLD);
-    let key_style = Style::default()
-        .bg(Color::LightYellow)
-        .fg(Color::Black)
-        .add_modifier(Modifier::BOLD);
-
-    let block = Block::default()
-        .title(Span::styled(" AWS Credentials Error ", title_style))
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Red))
-        .style(Style::default().bg(Color::Black));
-
-    let lines = vec![
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "⚠ Failed to authenticate with AWS",
-            error_style,
-        )]),
-        Line::from(""),
-        Line::from("The application could not access AWS S3. This usually means:"),
-        Line::from(""),
-        Line::from("  • AWS credentials are not configured"),
-        Line::from("  • Credentials have expired (SSO)"),
-        Line::from("  • Invalid access key or secret key"),
-        Line::from("  • Missing AWS_PROFILE environment variable"),
-        Line::from(""),
-        Line::from(vec![Span::styled("To fix this:", title_style)]),
-        Line::from(""),
-        Line::from("  1. Configure AWS credentials in ~/.aws/credentials"),
-        Line::from("  2. Set AWS_PROFILE environment variable"),
-        Line::from("  3. Run 'aws sso login' if using SSO"),
-        Line::from("  4. Set AWS_ACCESS_KEY_ID and AWS_SECRET_ACCESS_KEY"),
-        Line::from(""),
-        Line::from("For more information:"),
-        Line::from("  https://docs.aws.amazon.com/cli/latest/userguide/cli-configure-files.html"),
-        Line::from(""),
-        Line::from(""),
-        Line::from(vec![
-            Span::raw("Press "),
-            Span::styled(" any key ", key_style),
-            Span::raw(" to exit"),
-        ]),
-    ];
-
-    let para = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
-    frame.render_widget(para, area);
+    let skipped = objects.len() - keys.len();
+    (keys, skipped)
 }
 
-fn draw_modal_surface(frame: &mut ratatui::Frame, area: Rect) {
-    frame.render_widget(Clear, area);
-    let backdrop = Block::default().style(Style::default().bg(Color::Black));
-    frame.render_widget(backdrop, area);
+/// Archived objects can only be copied once a restore has made them readable
+fn readable_in_place(obj: &ObjectInfo) -> bool {
+    !matches!(
+        obj.storage_class,
+        StorageClassTier::GlacierFlexibleRetrieval | StorageClassTier::GlacierDeepArchive
+    ) || matches!(
+        obj.restore_state,
+        Some(crate::models::RestoreState::Available)
+    )
+}
 
-    let canvas = frame.size();
-    let shadow_style = Style::default().bg(Color::DarkGray);
-    if area.y + area.height < canvas.height {
-        let shadow_width = area.width.min(canvas.width.saturating_sub(area.x + 1));
-        if shadow_width > 0 {
-            let shadow = Rect::new(area.x + 1, area.y + area.height, shadow_width, 1);
-            frame.render_widget(Block::default().style(shadow_style), shadow);
-        }
-    }
-    if area.x + area.width < canvas.width {
-        let shadow_height = area.height.min(canvas.height.saturating_sub(area.y + 1));
-        if shadow_height > 0 {
-            let shadow = Rect::new(area.x + area.width, area.y + 1, 1, shadow_height);
-            frame.render_widget(Block::default().style(shadow_style), shadow);
-        }
-    }
+fn target_count(app: &App) -> usize {
+    app.target_objects().len()
 }
 
-fn describe_restore_error(err: &anyhow::Error) -> String {
-    if let Some(sdk_err) = err.downcast_ref::<SdkError<RestoreObjectError>>() {
-        match sdk_err {
-            SdkError::ServiceError(err) => {
-                let service = err.err();
-                let code = service.meta().code().unwrap_or("ServiceError");
-                let message = service
-                    .message()
-                    .map(|m| m.to_string())
-                    .unwrap_or_else(|| "no message provided".into());
-                let friendly = match code {
-                    "NoSuchKey" => {
-                        "object was not found (mask may target stale keys or bucket differs)".into()
-                    }
-                    "InvalidObjectState" => {
-                        "object is already being restored or not eligible for this operation".into()
-                    }
-                    _ => message.clone(),
-                };
-                if matches!(code, "NoSuchKey" | "InvalidObjectState") {
-                    return format!("{code}: {friendly}");
-                }
-                return format!("{code}: {message}");
-            }
-            SdkError::DispatchFailure(err) => {
-                return format!("network/dispatch failure: {err:?}");
-            }
-            SdkError::TimeoutError(_) => {
-                return "request timed out; please retry".into();
-            }
-            SdkError::ResponseError(ctx) => {
-                return format!("response error: {ctx:?}");
-            }
-            _ => {}
-        }
-    }
-    format!("{err:#}")
-}
-
-fn centered_rect(width_percent: u16, height_percent: u16, area: Rect) -> Rect {
-    let vertical = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage((100 - height_percent) / 2),
-            Constraint::Percentage(height_percent),
-            Constraint::Percentage((100 - height_percent) / 2),
-        ])
-        .split(area);
-    Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage((100 - width_percent) / 2),
-            Constraint::Percentage(width_percent),
-            Constraint::Percentage((100 - width_percent) / 2),
-        ])
-        .split(vertical[1])[1]
-}
-
-fn highlight_border(active: bool) -> Style {
-    if active {
-        Style::default()
-            .fg(Color::LightYellow)
-            .add_modifier(Modifier::BOLD)
-    } else {
-        Style::default().fg(Color::DarkGray)
-    }
-}
-
-fn format_size(size: i64) -> String {
-    const KB: f64 = 1024.0;
-    let kb = size as f64 / KB;
-    format!("{:>10.2} KB", kb)
-}
-
-fn storage_class_color(storage_class: &StorageClassTier) -> Style {
-    match storage_class {
-        StorageClassTier::Standard => Style::default()
-            .fg(Color::LightGreen)
-            .add_modifier(Modifier::BOLD),
-        StorageClassTier::StandardIa => Style::default()
-            .fg(Color::LightYellow)
-            .add_modifier(Modifier::BOLD),
-        StorageClassTier::OneZoneIa => Style::default()
-            .fg(Color::Yellow)
-            .add_modifier(Modifier::BOLD),
-        StorageClassTier::IntelligentTiering => Style::default()
-            .fg(Color::LightMagenta)
-            .add_modifier(Modifier::BOLD),
-        StorageClassTier::GlacierInstantRetrieval => Style::default()
-            .fg(Color::LightCyan)
-            .add_modifier(Modifier::BOLD),
-        StorageClassTier::GlacierFlexibleRetrieval => Style::default()
-            .fg(Color::Cyan)
-            .add_modifier(Modifier::BOLD),
-        StorageClassTier::GlacierDeepArchive => Style::default()
-            .fg(Color::LightBlue)
-            .add_modifier(Modifier::BOLD),
-        StorageClassTier::ReducedRedundancy => Style::default()
-            .fg(Color::Magenta)
-            .add_modifier(Modifier::BOLD),
-        StorageClassTier::Unknown(_) => Style::default().fg(Color::DarkGray),
-    }
+fn target_keys(app: &App) -> Vec<String> {
+    app.target_objects().iter().map(|o| o.key.clone()).collect()
 }
@@ -0,0 +1,262 @@
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Gauge, Paragraph, Wrap};
+
+use crate::app::{App, shutdown_label};
+use crate::i18n::{t, tf};
+use crate::waves;
+
+use super::{centered_rect, draw_modal_surface};
+
+pub(super) fn draw_progress_popup(frame: &mut ratatui::Frame, app: &App) {
+    let area = centered_rect(70, 30, frame.size());
+    draw_modal_surface(frame, area);
+
+    let progress = match &app.progress {
+        Some(p) => p,
+        None => return,
+    };
+
+    let title_style = Style::default()
+        .fg(Color::LightCyan)
+        .add_modifier(Modifier::BOLD);
+
+    let block = Block::default()
+        .title(Span::styled(
+            format!(" {} ", progress.operation),
+            title_style,
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    // Split into sections
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Progress bar
+            Constraint::Length(2), // Counter
+            Constraint::Length(2), // Current item
+            Constraint::Min(1),    // Padding
+        ])
+        .split(inner);
+
+    // Progress bar
+    let gauge = Gauge::default()
+        .gauge_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .bg(Color::Black)
+                .add_modifier(Modifier::BOLD),
+        )
+        .percent(progress.percentage());
+    frame.render_widget(gauge, chunks[0]);
+
+    // Counter text
+    let counter_text = tf(
+        "progress.counter",
+        &[("current", &progress.current), ("total", &progress.total)],
+    );
+    let counter = Paragraph::new(counter_text)
+        .style(Style::default().fg(Color::White))
+        .alignment(Alignment::Center);
+    frame.render_widget(counter, chunks[1]);
+
+    // Current item, or what the batch is doing after a quit request
+    if let Some(choice) = progress.stopping {
+        let para = Paragraph::new(shutdown_label(choice))
+            .style(Style::default().fg(Color::LightYellow))
+            .alignment(Alignment::Center);
+        frame.render_widget(para, chunks[3]);
+    } else {
+        let hint = match progress.concurrency {
+            Some(count) => tf("progress.hint_concurrency", &[("count", &count)]),
+            None => t("progress.hint").to_string(),
+        };
+        let hint = Paragraph::new(hint)
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center);
+        frame.render_widget(hint, chunks[3]);
+    }
+    if let Some(ref item) = progress.current_item {
+        let item_text = tf("progress.processing", &[("item", item)]);
+        let item_para = Paragraph::new(item_text)
+            .style(Style::default().fg(Color::Gray))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+        frame.render_widget(item_para, chunks[2]);
+    }
+}
+
+/// Request rates and latencies of the running batch, below the progress popup
+pub(super) fn draw_telemetry_panel(frame: &mut ratatui::Frame, app: &App) {
+    let progress = centered_rect(70, 30, frame.size());
+    let latencies = app.telemetry.latencies();
+    let height = (latencies.len().max(1) + 4) as u16;
+    let area = Rect {
+        y: progress.bottom(),
+        height: height.min(frame.size().bottom().saturating_sub(progress.bottom())),
+        ..progress
+    };
+    if area.height < 3 {
+        return;
+    }
+    draw_modal_surface(frame, area);
+
+    let label_style = Style::default().fg(Color::LightBlue);
+    let error_rate = app.telemetry.error_rate();
+    let error_style = if error_rate.is_some_and(|rate| rate > 0.0) {
+        Style::default().fg(Color::Red)
+    } else {
+        Style::default().fg(Color::LightGreen)
+    };
+    let mut lines = vec![Line::from(vec![
+        Span::styled(t("telemetry.requests"), label_style),
+        Span::raw(format!("{:.1}/s   ", app.telemetry.requests_per_second())),
+        Span::styled(t("telemetry.errors"), label_style),
+        Span::styled(
+            error_rate.map_or("-".to_string(), |rate| format!("{:.1}%", rate * 100.0)),
+            error_style,
+        ),
+        Span::raw("   "),
+        Span::styled(t("telemetry.throughput"), label_style),
+        Span::raw(format!(
+            "{}/s",
+            waves::format_bytes(app.telemetry.bytes_per_second() as u64)
+        )),
+    ])];
+    lines.push(Line::from(""));
+    if latencies.is_empty() {
+        lines.push(Line::from(Span::styled(
+            t("telemetry.waiting"),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    for latency in latencies {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{:<16}", latency.api),
+                Style::default().fg(Color::LightCyan),
+            ),
+            Span::raw(tf(
+                "telemetry.latency",
+                &[
+                    ("ms", &latency.mean.as_millis()),
+                    ("count", &latency.requests),
+                ],
+            )),
+        ]));
+    }
+    let block = Block::default()
+        .title(t("telemetry.title"))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+pub(super) fn draw_shutdown_popup(frame: &mut ratatui::Frame) {
+    let area = centered_rect(50, 30, frame.size());
+    draw_modal_surface(frame, area);
+
+    let key_style = Style::default()
+        .fg(Color::Black)
+        .bg(Color::LightYellow)
+        .add_modifier(Modifier::BOLD);
+    let lines = vec![
+        Line::from(t("shutdown.prompt")),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(" f ", key_style),
+            Span::raw(t("shutdown.finish")),
+        ]),
+        Line::from(vec![
+            Span::styled(" c ", key_style),
+            Span::raw(t("shutdown.checkpoint")),
+        ]),
+        Line::from(vec![
+            Span::styled(" x ", key_style),
+            Span::raw(t("shutdown.abort")),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(" Esc ", key_style),
+            Span::raw(t("shutdown.keep_running")),
+        ]),
+    ];
+    let block = Block::default()
+        .title(t("shutdown.title"))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .style(Style::default().bg(Color::Black));
+    let para = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
+    frame.render_widget(para, area);
+}
+
+/// Number of error kinds listed on the pause popup before the rest are summarised
+pub(super) const PAUSE_BREAKDOWN_ROWS: usize = 6;
+
+pub(super) fn draw_pause_popup(frame: &mut ratatui::Frame, app: &App) {
+    let Some(pause) = &app.batch_pause else {
+        return;
+    };
+    let area = centered_rect(60, 50, frame.size());
+    draw_modal_surface(frame, area);
+
+    let key_style = Style::default()
+        .fg(Color::Black)
+        .bg(Color::LightYellow)
+        .add_modifier(Modifier::BOLD);
+    let percent = pause.failed * 100 / pause.attempted.max(1);
+    let mut lines = vec![
+        Line::from(tf(
+            "pause.summary",
+            &[
+                ("failed", &pause.failed),
+                ("attempted", &pause.attempted),
+                ("percent", &percent),
+            ],
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            t("pause.breakdown"),
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+    ];
+    for (code, count) in pause.breakdown.iter().take(PAUSE_BREAKDOWN_ROWS) {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {count:>5}  "), Style::default().fg(Color::Red)),
+            Span::raw(code.clone()),
+        ]));
+    }
+    if pause.breakdown.len() > PAUSE_BREAKDOWN_ROWS {
+        let count = pause.breakdown.len() - PAUSE_BREAKDOWN_ROWS;
+        lines.push(Line::from(tf("pause.more", &[("count", &count)])));
+    }
+    lines.extend([
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(" c ", key_style),
+            Span::raw(t("pause.continue")),
+        ]),
+        Line::from(vec![
+            Span::styled(" r ", key_style),
+            Span::raw(t("pause.rollback")),
+        ]),
+        Line::from(vec![
+            Span::styled(" x ", key_style),
+            Span::raw(t("pause.abort")),
+        ]),
+    ]);
+    let block = Block::default()
+        .title(t("pause.title"))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red))
+        .style(Style::default().bg(Color::Black));
+    let para = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
+    frame.render_widget(para, area);
+}
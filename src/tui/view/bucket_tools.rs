@@ -0,0 +1,917 @@
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+
+use crate::access::{AccessAudit, Exposure};
+use crate::app::{App, BucketFormField, CopyFormField};
+use crate::bucket_config::{ConfigPart, DiffLine};
+use crate::collisions::{CollisionCause, CollisionChoice};
+use crate::decommission::{CheckState, DecommissionStep};
+use crate::i18n::{t, tf};
+use crate::models::StorageClassTier;
+use crate::partition::Partition;
+use crate::reconcile::FindingKind;
+use crate::tags::{self, MIGRATION_TAGS, TagStep};
+use crate::waves;
+
+use super::{centered_rect, draw_modal_surface, format_size, short_timestamp};
+
+pub(super) fn draw_tag_editor_popup(frame: &mut ratatui::Frame, app: &App) {
+    let Some(editor) = &app.tag_editor else {
+        return;
+    };
+    let area = centered_rect(70, 65, frame.size());
+    draw_modal_surface(frame, area);
+
+    let title_style = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let block = Block::default()
+        .title(Span::styled(
+            tf("tags.title", &[("bucket", &editor.bucket)]),
+            title_style,
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Rgb(20, 20, 30)));
+
+    let label_style = Style::default()
+        .fg(Color::LightBlue)
+        .add_modifier(Modifier::BOLD);
+    let active_style = Style::default()
+        .fg(Color::LightYellow)
+        .add_modifier(Modifier::BOLD);
+    let hint_style = Style::default().fg(Color::DarkGray);
+    let cursor = Span::styled(" ", Style::default().bg(Color::LightYellow));
+    let region = editor.region.as_deref().unwrap_or("us-east-1");
+    let console_url = Partition::of_region(region).console_url(&editor.bucket, region);
+
+    let mut text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(t("tags.region"), label_style),
+            Span::raw(editor.region.as_deref().unwrap_or("?").to_string()),
+            Span::styled(t("tags.created"), label_style),
+            Span::raw(editor.creation_date.as_deref().unwrap_or("?").to_string()),
+        ]),
+        Line::from(vec![
+            Span::styled(t("tags.size"), label_style),
+            Span::raw(match app.bucket_sizes.get(&editor.bucket) {
+                Some(size) => tf(
+                    "tags.size_known",
+                    &[
+                        ("size", &waves::format_bytes(size.bytes)),
+                        ("objects", &size.objects),
+                        ("at", &short_timestamp(&size.measured_at)),
+                    ],
+                ),
+                None => t("tags.size_unknown").to_string(),
+            }),
+        ]),
+        Line::from(vec![
+            Span::styled(t("tags.console"), label_style),
+            Span::styled(console_url, hint_style),
+        ]),
+        Line::from(""),
+    ];
+    if editor.tags.is_empty() {
+        text.push(Line::from(Span::styled(t("tags.empty"), hint_style)));
+    }
+    for (index, (key, value)) in editor.tags.iter().enumerate() {
+        let style = if index == editor.cursor && editor.step == TagStep::Browse {
+            Style::default().bg(Color::DarkGray).fg(Color::White)
+        } else {
+            Style::default()
+        };
+        text.push(Line::from(vec![
+            Span::styled(format!(" {key}"), style.fg(Color::LightCyan)),
+            Span::styled(" = ", style),
+            Span::styled(value.as_str(), style),
+        ]));
+    }
+    text.push(Line::from(""));
+
+    match editor.step {
+        TagStep::Browse => {
+            text.push(Line::from(Span::styled(t("tags.cost_note"), hint_style)));
+            text.push(Line::from(Span::styled(t("tags.hint"), hint_style)));
+        }
+        TagStep::Edit => {
+            text.push(Line::from(vec![
+                Span::styled(t("tags.prompt"), label_style),
+                Span::styled(editor.input.as_str(), active_style),
+                cursor,
+            ]));
+            text.push(Line::from(Span::styled(t("tags.edit_hint"), hint_style)));
+        }
+        TagStep::Standard => {
+            text.push(Line::from(Span::styled(t("tags.standard"), label_style)));
+            let labels = MIGRATION_TAGS.iter().copied().chain([t("tags.buckets")]);
+            for (index, label) in labels.enumerate() {
+                let focused = index == editor.standard_field;
+                let mut spans = vec![
+                    Span::styled(
+                        format!("  {label:<18}"),
+                        if focused { active_style } else { label_style },
+                    ),
+                    Span::styled(
+                        editor.standard[index].as_str(),
+                        if focused {
+                            active_style
+                        } else {
+                            Style::default()
+                        },
+                    ),
+                ];
+                if focused {
+                    spans.push(cursor.clone());
+                }
+                text.push(Line::from(spans));
+            }
+            let matching = app
+                .all_buckets
+                .iter()
+                .filter(|b| tags::bucket_matches(editor.standard_pattern(), &b.name))
+                .count();
+            text.push(Line::from(Span::styled(
+                tf("tags.matching", &[("count", &matching)]),
+                hint_style,
+            )));
+            text.push(Line::from(Span::styled(
+                t("tags.standard_hint"),
+                hint_style,
+            )));
+        }
+    }
+    let para = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+    frame.render_widget(para, area);
+}
+
+pub(super) fn draw_config_copy_popup(frame: &mut ratatui::Frame, app: &App) {
+    let Some(copy) = &app.config_copy else {
+        return;
+    };
+    let area = centered_rect(85, 75, frame.size());
+    draw_modal_surface(frame, area);
+
+    let title_style = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let block = Block::default()
+        .title(Span::styled(
+            tf(
+                "config_copy.title",
+                &[("source", &copy.source), ("dest", &copy.dest)],
+            ),
+            title_style,
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Rgb(20, 20, 30)));
+
+    let hint_style = Style::default().fg(Color::DarkGray);
+    let mut text = vec![Line::from("")];
+    for (index, part) in ConfigPart::ALL.into_iter().enumerate() {
+        let style = if index == copy.cursor {
+            Style::default().bg(Color::DarkGray).fg(Color::White)
+        } else {
+            Style::default()
+        };
+        let check = if copy.is_selected(part) { "[x]" } else { "[ ]" };
+        let (state, state_color) = if copy.differs(part) {
+            (t("config_copy.differs"), Color::LightYellow)
+        } else {
+            (t("config_copy.same"), Color::DarkGray)
+        };
+        text.push(Line::from(vec![
+            Span::styled(format!(" {check} {:<20}", t(part.label_key())), style),
+            Span::styled(format!(" {state}"), Style::default().fg(state_color)),
+        ]));
+    }
+
+    let part = ConfigPart::ALL[copy.cursor];
+    text.push(Line::from(""));
+    text.push(Line::from(Span::styled(
+        tf("config_copy.diff", &[("part", &t(part.label_key()))]),
+        Style::default()
+            .fg(Color::LightBlue)
+            .add_modifier(Modifier::BOLD),
+    )));
+    let diff = copy.diff(part);
+    if diff.is_empty() {
+        text.push(Line::from(Span::styled(t("config_copy.none"), hint_style)));
+    }
+    for line in diff {
+        text.push(match line {
+            DiffLine::Same(line) => Line::from(Span::styled(
+                format!("  {line}"),
+                Style::default().fg(Color::Gray),
+            )),
+            DiffLine::Removed(line) => Line::from(Span::styled(
+                format!("- {line}"),
+                Style::default().fg(Color::LightRed),
+            )),
+            DiffLine::Added(line) => Line::from(Span::styled(
+                format!("+ {line}"),
+                Style::default().fg(Color::LightGreen),
+            )),
+        });
+    }
+    // Access is not copied, but objects copied between the buckets change who can reach them
+    if let (Some(source), Some(dest)) = (
+        app.access_audits.get(&copy.source),
+        app.access_audits.get(&copy.dest),
+    ) {
+        for audit in [source, dest] {
+            text.push(Line::from(""));
+            text.push(Line::from(Span::styled(
+                tf("config_copy.access", &[("bucket", &audit.bucket)]),
+                Style::default()
+                    .fg(Color::LightBlue)
+                    .add_modifier(Modifier::BOLD),
+            )));
+            text.extend(access_lines(audit));
+        }
+        let warn_style = Style::default().fg(Color::LightRed);
+        match (source.exposure(), dest.exposure()) {
+            (from, to) if to > from => text.push(Line::from(Span::styled(
+                tf(
+                    "access.drift_wider",
+                    &[("dest", &copy.dest), ("source", &copy.source)],
+                ),
+                warn_style,
+            ))),
+            (from, to) if to < from => text.push(Line::from(Span::styled(
+                tf(
+                    "access.drift_narrower",
+                    &[("dest", &copy.dest), ("source", &copy.source)],
+                ),
+                warn_style,
+            ))),
+            _ => {}
+        }
+    }
+    text.push(Line::from(""));
+    text.push(Line::from(Span::styled(t("config_copy.hint"), hint_style)));
+    let para = Paragraph::new(text).block(block).wrap(Wrap { trim: false });
+    frame.render_widget(para, area);
+}
+
+pub(super) fn draw_reconcile_popup(frame: &mut ratatui::Frame, app: &App) {
+    let Some(summary) = &app.reconciliation else {
+        return;
+    };
+    let area = centered_rect(85, 75, frame.size());
+    draw_modal_surface(frame, area);
+
+    let title_style = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let block = Block::default()
+        .title(Span::styled(
+            tf(
+                "reconcile.title",
+                &[
+                    ("source", &summary.source_bucket),
+                    ("dest", &summary.dest_bucket),
+                ],
+            ),
+            title_style,
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Rgb(20, 20, 30)));
+
+    let hint_style = Style::default().fg(Color::DarkGray);
+    let listed: [(&str, &dyn std::fmt::Display); 2] = [
+        ("source", &summary.source_listed),
+        ("dest", &summary.dest_listed),
+    ];
+    let mut text = vec![
+        Line::from(""),
+        Line::from(if summary.finished {
+            tf("reconcile.done", &listed)
+        } else {
+            tf("reconcile.running", &listed)
+        }),
+    ];
+    if let Some(err) = &summary.error {
+        text.push(Line::from(Span::styled(
+            err.clone(),
+            Style::default().fg(Color::LightRed),
+        )));
+    }
+    let mut counts = vec![
+        Span::raw(t("reconcile.matched")),
+        Span::styled(
+            summary.matched.to_string(),
+            Style::default().fg(Color::LightGreen),
+        ),
+    ];
+    for kind in FindingKind::ALL {
+        let count = summary.count(kind);
+        let color = if count > 0 {
+            Color::LightYellow
+        } else {
+            Color::Gray
+        };
+        counts.push(Span::raw(format!("   {}: ", kind.label())));
+        counts.push(Span::styled(count.to_string(), Style::default().fg(color)));
+    }
+    text.push(Line::from(counts));
+    text.push(Line::from(vec![
+        Span::raw(t("reconcile.export")),
+        Span::styled(summary.export.display().to_string(), hint_style),
+    ]));
+    text.push(Line::from(""));
+
+    // Rows left for findings inside the border, below the header, above the hint
+    let rows = (area.height as usize).saturating_sub(text.len() + 4);
+    if summary.sample.is_empty() {
+        text.push(Line::from(Span::styled(t("reconcile.none"), hint_style)));
+    }
+    for finding in summary.sample.iter().skip(app.reconcile_scroll).take(rows) {
+        let side = |side: &Option<(i64, StorageClassTier)>| match side {
+            Some((size, class)) => format!("{} {}", format_size(*size), class.label()),
+            None => "–".to_string(),
+        };
+        text.push(Line::from(vec![
+            Span::styled(
+                format!(" {:<20}", finding.kind.label()),
+                Style::default().fg(Color::LightYellow),
+            ),
+            Span::raw(finding.key.clone()),
+            Span::styled(
+                format!("  {} → {}", side(&finding.source), side(&finding.dest)),
+                hint_style,
+            ),
+        ]));
+    }
+    let hidden = summary.discrepancies().saturating_sub(summary.sample.len());
+    if hidden > 0 {
+        text.push(Line::from(Span::styled(
+            tf("reconcile.more", &[("count", &hidden)]),
+            hint_style,
+        )));
+    }
+    text.push(Line::from(""));
+    text.push(Line::from(Span::styled(
+        tf("reconcile.hint", &[("count", &summary.recopy.len())]),
+        hint_style,
+    )));
+    let para = Paragraph::new(text).block(block);
+    frame.render_widget(para, area);
+}
+
+pub(super) fn draw_create_bucket_popup(frame: &mut ratatui::Frame, app: &App) {
+    let area = centered_rect(60, 45, frame.size());
+    draw_modal_surface(frame, area);
+
+    let title_style = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let block = Block::default()
+        .title(Span::styled(t("create_bucket.title"), title_style))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Rgb(20, 20, 30)));
+
+    let label_style = Style::default()
+        .fg(Color::LightBlue)
+        .add_modifier(Modifier::BOLD);
+    let active_style = Style::default()
+        .fg(Color::LightYellow)
+        .add_modifier(Modifier::BOLD);
+    let inactive_style = Style::default().fg(Color::Gray);
+    let hint_style = Style::default().fg(Color::DarkGray);
+
+    let draft = &app.bucket_draft;
+    let yes_no = |on: bool| {
+        if on {
+            t("create_bucket.on")
+        } else {
+            t("create_bucket.off")
+        }
+    };
+    let fields = [
+        (
+            BucketFormField::Name,
+            "create_bucket.name",
+            draft.name.clone(),
+        ),
+        (
+            BucketFormField::Region,
+            "create_bucket.region",
+            draft.region.clone(),
+        ),
+        (
+            BucketFormField::Versioning,
+            "create_bucket.versioning",
+            yes_no(draft.versioning).to_string(),
+        ),
+        (
+            BucketFormField::Encryption,
+            "create_bucket.encryption",
+            draft.encryption.label().to_string(),
+        ),
+        (
+            BucketFormField::PublicAccess,
+            "create_bucket.public_access",
+            yes_no(draft.block_public_access).to_string(),
+        ),
+    ];
+
+    let mut text = vec![Line::from("")];
+    for (field, label_key, value) in fields {
+        let focused = app.bucket_field == field;
+        let mut spans = vec![
+            Span::styled(
+                t(label_key),
+                if focused { active_style } else { label_style },
+            ),
+            Span::styled(
+                value,
+                if focused {
+                    active_style
+                } else {
+                    inactive_style
+                },
+            ),
+        ];
+        if focused && field == BucketFormField::Name {
+            spans.push(Span::styled(" ", Style::default().bg(Color::LightYellow)));
+        }
+        text.push(Line::from(spans));
+        text.push(Line::from(""));
+    }
+    if !draft.name.is_empty()
+        && let Some(problem) = draft.name_problem()
+    {
+        text.push(Line::from(Span::styled(
+            tf("create_bucket.invalid", &[("problem", &problem)]),
+            Style::default().fg(Color::LightRed),
+        )));
+    }
+    text.push(Line::from(Span::styled(
+        t("create_bucket.hint"),
+        hint_style,
+    )));
+    let para = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+    frame.render_widget(para, area);
+}
+
+pub(super) fn draw_copy_target_popup(frame: &mut ratatui::Frame, app: &App) {
+    let area = centered_rect(65, 50, frame.size());
+    draw_modal_surface(frame, area);
+
+    let title_style = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let block = Block::default()
+        .title(Span::styled(t("copy_to.title"), title_style))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Rgb(20, 20, 30)));
+
+    let label_style = Style::default()
+        .fg(Color::LightBlue)
+        .add_modifier(Modifier::BOLD);
+    let active_style = Style::default()
+        .fg(Color::LightYellow)
+        .add_modifier(Modifier::BOLD);
+    let inactive_style = Style::default().fg(Color::Gray);
+    let hint_style = Style::default().fg(Color::DarkGray);
+    let warn_style = Style::default().fg(Color::LightRed);
+
+    let draft = &app.copy_draft;
+    let fields = [
+        (
+            CopyFormField::Bucket,
+            "copy_to.bucket",
+            draft.dest_bucket.clone(),
+        ),
+        (
+            CopyFormField::From,
+            "copy_to.from",
+            draft.rewrite.from.clone(),
+        ),
+        (CopyFormField::To, "copy_to.to", draft.rewrite.to.clone()),
+        (
+            CopyFormField::StorageClass,
+            "copy_to.storage_class",
+            match &draft.storage_class {
+                Some(class) => class.label().to_string(),
+                None => t("copy_to.default_class").to_string(),
+            },
+        ),
+    ];
+
+    let targets = app.target_objects();
+    let mut text = vec![
+        Line::from(tf(
+            "copy_to.objects",
+            &[
+                ("count", &targets.len()),
+                ("bucket", &app.selected_bucket_name().unwrap_or_default()),
+            ],
+        )),
+        Line::from(""),
+    ];
+    for (field, label_key, value) in fields {
+        let focused = app.copy_field == field;
+        let mut spans = vec![
+            Span::styled(
+                t(label_key),
+                if focused { active_style } else { label_style },
+            ),
+            Span::styled(
+                value,
+                if focused {
+                    active_style
+                } else {
+                    inactive_style
+                },
+            ),
+        ];
+        if focused && field != CopyFormField::StorageClass {
+            spans.push(Span::styled(" ", Style::default().bg(Color::LightYellow)));
+        }
+        text.push(Line::from(spans));
+        text.push(Line::from(""));
+    }
+
+    let dest_bucket = draft.dest_bucket.trim();
+    if !dest_bucket.is_empty() && !app.buckets.iter().any(|b| b.name == dest_bucket) {
+        text.push(Line::from(Span::styled(
+            tf("copy_to.unknown_bucket", &[("bucket", &dest_bucket)]),
+            warn_style,
+        )));
+    }
+    // One key as it will be named, so a wrong prefix shows before confirming
+    if let Some(example) = targets
+        .iter()
+        .find(|obj| obj.key.starts_with(&draft.rewrite.from))
+        .or(targets.first())
+    {
+        let dest_key = draft.rewrite.apply(&example.key);
+        text.push(Line::from(tf(
+            "copy_to.example",
+            &[("key", &example.key), ("dest", &dest_key)],
+        )));
+    }
+    let outside = targets
+        .iter()
+        .filter(|obj| !obj.key.starts_with(&draft.rewrite.from))
+        .count();
+    if !draft.rewrite.is_empty() && outside > 0 {
+        text.push(Line::from(Span::styled(
+            tf("copy_to.outside", &[("count", &outside)]),
+            warn_style,
+        )));
+    }
+    if app.active_mask.is_some() && app.has_more_objects() {
+        text.push(Line::from(Span::styled(
+            tf("copy_to.partial", &[("count", &app.objects.len())]),
+            warn_style,
+        )));
+    }
+    text.push(Line::from(""));
+    text.push(Line::from(Span::styled(t("copy_to.hint"), hint_style)));
+    let para = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+    frame.render_widget(para, area);
+}
+
+pub(super) fn draw_collision_popup(frame: &mut ratatui::Frame, app: &App) {
+    if let Some(reason) = &app.collision_error {
+        draw_collision_failure(frame, reason);
+        return;
+    }
+    let Some(review) = &app.collisions else {
+        return;
+    };
+    let area = centered_rect(85, 70, frame.size());
+    draw_modal_surface(frame, area);
+
+    let title_style = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let block = Block::default()
+        .title(Span::styled(
+            tf(
+                "collisions.title",
+                &[
+                    ("count", &review.collisions.len()),
+                    ("bucket", &review.dest_bucket),
+                ],
+            ),
+            title_style,
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Rgb(20, 20, 30)));
+
+    let hint_style = Style::default().fg(Color::DarkGray);
+    let skipped = review.skipped().len();
+    let mut text = vec![
+        Line::from(""),
+        Line::from(tf(
+            "collisions.summary",
+            &[
+                ("skip", &skipped),
+                ("overwrite", &(review.collisions.len() - skipped)),
+            ],
+        )),
+        Line::from(""),
+    ];
+
+    // Rows left inside the border, below the header, above the hint
+    let rows = (area.height as usize).saturating_sub(text.len() + 4).max(1);
+    let first = review.cursor.saturating_sub(rows - 1);
+    for (index, collision) in review.collisions.iter().enumerate().skip(first).take(rows) {
+        let (choice, color) = match collision.choice {
+            CollisionChoice::Skip => (t("collisions.skip"), Color::LightGreen),
+            CollisionChoice::Overwrite => (t("collisions.overwrite"), Color::LightRed),
+        };
+        let cause = match &collision.cause {
+            CollisionCause::Existing {
+                size,
+                last_modified,
+            } => tf(
+                "collisions.existing",
+                &[
+                    ("size", &format_size(*size).trim()),
+                    (
+                        "modified",
+                        &last_modified
+                            .as_deref()
+                            .map(short_timestamp)
+                            .unwrap_or_else(|| "–".to_string()),
+                    ),
+                ],
+            ),
+            CollisionCause::Batch { other } => tf("collisions.batch", &[("key", other)]),
+        };
+        let mut line = Line::from(vec![
+            Span::styled(format!(" {choice:<10}"), Style::default().fg(color)),
+            Span::raw(collision.key.clone()),
+            Span::styled(" → ", hint_style),
+            Span::raw(collision.dest_key.clone()),
+            Span::styled(format!("  {cause}"), hint_style),
+        ]);
+        if index == review.cursor {
+            line = line.style(Style::default().bg(Color::DarkGray));
+        }
+        text.push(line);
+    }
+    text.push(Line::from(""));
+    text.push(Line::from(Span::styled(t("collisions.hint"), hint_style)));
+    let para = Paragraph::new(text).block(block);
+    frame.render_widget(para, area);
+}
+
+/// The copy waits on a failed collision lookup until it is retried or the
+/// operator chooses to overwrite
+fn draw_collision_failure(frame: &mut ratatui::Frame, reason: &str) {
+    let area = centered_rect(60, 30, frame.size());
+    draw_modal_surface(frame, area);
+
+    let block = Block::default()
+        .title(Span::styled(
+            t("collisions.failed_title"),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red))
+        .style(Style::default().bg(Color::Rgb(20, 20, 30)));
+    let text = vec![
+        Line::from(""),
+        Line::from(reason.to_string()),
+        Line::from(""),
+        Line::from(t("collisions.failed_prompt")),
+        Line::from(""),
+        Line::from(Span::styled(
+            t("collisions.failed_hint"),
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+    let para = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+    frame.render_widget(para, area);
+}
+
+pub(super) fn draw_access_audit_popup(frame: &mut ratatui::Frame, app: &App) {
+    let Some(audit) = app
+        .selected_bucket_name()
+        .and_then(|bucket| app.access_audits.get(bucket))
+    else {
+        return;
+    };
+    let area = centered_rect(80, 60, frame.size());
+    draw_modal_surface(frame, area);
+
+    let title_style = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let block = Block::default()
+        .title(Span::styled(
+            tf("access.title", &[("bucket", &audit.bucket)]),
+            title_style,
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Rgb(20, 20, 30)));
+
+    let mut text = vec![Line::from("")];
+    text.extend(access_lines(audit));
+    text.push(Line::from(""));
+    text.push(Line::from(Span::styled(
+        t("access.hint"),
+        Style::default().fg(Color::DarkGray),
+    )));
+    let para = Paragraph::new(text).block(block).wrap(Wrap { trim: false });
+    frame.render_widget(para, area);
+}
+
+/// Block Public Access, the policy verdict and each grant past the account
+fn access_lines(audit: &AccessAudit) -> Vec<Line<'static>> {
+    let label_style = Style::default().fg(Color::LightBlue);
+    let block = match audit.block {
+        Some(block) if block.all() => t("access.block_all").to_string(),
+        Some(block) => {
+            let off: Vec<&str> = [
+                ("BlockPublicAcls", block.block_public_acls),
+                ("IgnorePublicAcls", block.ignore_public_acls),
+                ("BlockPublicPolicy", block.block_public_policy),
+                ("RestrictPublicBuckets", block.restrict_public_buckets),
+            ]
+            .into_iter()
+            .filter(|(_, on)| !on)
+            .map(|(name, _)| name)
+            .collect();
+            tf("access.block_partial", &[("settings", &off.join(", "))])
+        }
+        None => t("access.block_none").to_string(),
+    };
+    let policy = match audit.policy_public {
+        Some(true) => t("access.policy_public"),
+        Some(false) => t("access.policy_private"),
+        None => t("access.policy_none"),
+    };
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(t("access.block"), label_style),
+            Span::raw(block),
+        ]),
+        Line::from(vec![
+            Span::styled(t("access.policy"), label_style),
+            Span::raw(policy),
+        ]),
+    ];
+    if audit.findings.is_empty() {
+        lines.push(Line::from(Span::styled(
+            t("access.clear"),
+            Style::default().fg(Color::LightGreen),
+        )));
+    }
+    for finding in &audit.findings {
+        let color = match (finding.blocked, finding.exposure) {
+            (true, _) => Color::DarkGray,
+            (false, Exposure::Public) => Color::LightRed,
+            (false, Exposure::Wide) => Color::LightYellow,
+        };
+        let scope = if finding.prefix.is_empty() {
+            t("access.whole_bucket").to_string()
+        } else {
+            finding.prefix.clone()
+        };
+        let mut spans = vec![
+            Span::styled(
+                format!(" ✗ {:<7}", finding.exposure.label()),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(format!("{scope}  "), label_style),
+            Span::styled(finding.detail.clone(), Style::default().fg(color)),
+        ];
+        if finding.blocked {
+            spans.push(Span::styled(
+                t("access.blocked"),
+                Style::default().fg(color),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+    for what in &audit.unreadable {
+        lines.push(Line::from(Span::styled(
+            tf("access.unreadable", &[("what", what)]),
+            Style::default().fg(Color::LightYellow),
+        )));
+    }
+    lines
+}
+
+pub(super) fn draw_decommission_popup(frame: &mut ratatui::Frame, app: &App) {
+    let Some(decommission) = &app.decommission else {
+        return;
+    };
+    let area = centered_rect(75, 60, frame.size());
+    draw_modal_surface(frame, area);
+
+    let title_style = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let block = Block::default()
+        .title(Span::styled(
+            tf("decommission.title", &[("bucket", &decommission.bucket)]),
+            title_style,
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Rgb(20, 20, 30)));
+
+    let hint_style = Style::default().fg(Color::DarkGray);
+    let mut text = vec![Line::from("")];
+    for check in &decommission.checks {
+        let (mark, color, detail) = match &check.state {
+            CheckState::Passed(detail) => ("✓", Color::LightGreen, detail),
+            CheckState::Warning(detail) => ("!", Color::LightYellow, detail),
+            CheckState::Failed(detail) => ("✗", Color::LightRed, detail),
+        };
+        text.push(Line::from(vec![
+            Span::styled(
+                format!(" {mark} "),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("{}: ", t(check.label_key)),
+                Style::default().fg(Color::LightBlue),
+            ),
+            Span::styled(detail.as_str(), Style::default().fg(color)),
+        ]));
+    }
+    text.push(Line::from(""));
+
+    match decommission.step {
+        DecommissionStep::Review => {
+            let verdict = if decommission.ready() {
+                Span::styled(
+                    t("decommission.ready"),
+                    Style::default().fg(Color::LightGreen),
+                )
+            } else {
+                Span::styled(
+                    t("decommission.blocked"),
+                    Style::default().fg(Color::LightRed),
+                )
+            };
+            text.push(Line::from(verdict));
+            text.push(Line::from(""));
+            text.push(Line::from(Span::styled(t("decommission.hint"), hint_style)));
+        }
+        DecommissionStep::ConfirmLifecycleDelete => {
+            text.push(Line::from(Span::styled(
+                tf(
+                    "decommission.confirm_lifecycle",
+                    &[("count", &decommission.lifecycle_rules)],
+                ),
+                Style::default()
+                    .fg(Color::LightYellow)
+                    .add_modifier(Modifier::BOLD),
+            )));
+            text.push(Line::from(Span::styled(
+                t("decommission.confirm_hint"),
+                hint_style,
+            )));
+        }
+        DecommissionStep::ConfirmBucketDelete => {
+            text.push(Line::from(Span::styled(
+                tf(
+                    "decommission.confirm_bucket",
+                    &[("bucket", &decommission.bucket)],
+                ),
+                Style::default()
+                    .fg(Color::LightRed)
+                    .add_modifier(Modifier::BOLD),
+            )));
+            text.push(Line::from(vec![
+                Span::styled(
+                    t("decommission.prompt"),
+                    Style::default()
+                        .fg(Color::LightBlue)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    decommission.confirm_input.as_str(),
+                    Style::default()
+                        .fg(Color::LightYellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(" ", Style::default().bg(Color::LightYellow)),
+            ]));
+            text.push(Line::from(Span::styled(
+                t("decommission.prompt_hint"),
+                hint_style,
+            )));
+        }
+    }
+    let para = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+    frame.render_widget(para, area);
+}
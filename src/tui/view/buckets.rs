@@ -0,0 +1,100 @@
+use ratatui::layout::{Alignment, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::block::Title;
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+use crate::app::{ActivePane, App, VERSION};
+use crate::i18n::{t, tf};
+
+use super::highlight_border;
+
+pub(super) fn draw_bucket_selector(frame: &mut ratatui::Frame, area: Rect, app: &App) {
+    let key_style = Style::default()
+        .bg(Color::LightCyan)
+        .fg(Color::Black)
+        .add_modifier(Modifier::BOLD);
+
+    let bucket_name = app
+        .selected_bucket_name()
+        .unwrap_or(t("buckets.none_selected"));
+    let bucket_info = format!("  ({}/{})  ", app.selected_bucket + 1, app.buckets.len());
+
+    let title_style = Style::default()
+        .fg(Color::LightMagenta)
+        .add_modifier(Modifier::BOLD);
+
+    // Running version, and a newer release when the startup check found one
+    let mut version = vec![Span::styled(
+        format!(" bucket-brigade v{VERSION} "),
+        Style::default().fg(Color::DarkGray),
+    )];
+    if app.no_delete {
+        version.insert(
+            0,
+            Span::styled(
+                t("buckets.no_delete"),
+                Style::default()
+                    .bg(Color::Red)
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        );
+    }
+    // Failures injected so far, so a rehearsal can tell them from real ones
+    if let Some(faults) = &app.fault_injection {
+        version.insert(
+            0,
+            Span::styled(
+                tf(
+                    "buckets.faults",
+                    &[
+                        ("rate", &(faults.rate * 100.0)),
+                        ("injected", &faults.injected()),
+                    ],
+                ),
+                Style::default()
+                    .bg(Color::Yellow)
+                    .fg(Color::Black)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        );
+    }
+    if let Some(release) = &app.new_release {
+        version.push(Span::styled(
+            tf("buckets.new_release", &[("version", &release.version())]),
+            Style::default()
+                .fg(Color::LightYellow)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    let block = Block::default()
+        .title(Title::from(Line::from(version)).alignment(Alignment::Right))
+        .borders(Borders::ALL)
+        .border_style(highlight_border(app.active_pane == ActivePane::Buckets))
+        .style(Style::default().bg(Color::Black).fg(Color::White));
+
+    let region = app.get_current_region_display();
+    let text = Line::from(vec![
+        Span::styled(t("buckets.region"), Style::default().fg(Color::Cyan)),
+        Span::styled(
+            App::region_label(&region).to_string(),
+            Style::default()
+                .fg(Color::LightGreen)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(format!(" ({}) ", app.region_badge(&region))),
+        Span::styled("←", key_style),
+        Span::styled("→", key_style),
+        Span::raw(t("buckets.cycle")),
+        Span::styled(t("buckets.bucket"), Style::default().fg(Color::Cyan)),
+        Span::styled(bucket_name, title_style),
+        Span::raw(bucket_info),
+        Span::styled("↑", key_style),
+        Span::styled("↓", key_style),
+        Span::raw(t("buckets.select")),
+    ]);
+
+    let para = Paragraph::new(text).block(block);
+    frame.render_widget(para, area);
+}
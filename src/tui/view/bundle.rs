@@ -0,0 +1,105 @@
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+use crate::app::App;
+use crate::i18n::{t, tf};
+
+use super::{centered_rect, draw_modal_surface, format_size, short_timestamp};
+
+pub(super) fn draw_bundle_popup(frame: &mut ratatui::Frame, app: &App) {
+    let Some(view) = &app.bundle_view else {
+        return;
+    };
+    let area = centered_rect(85, 75, frame.size());
+    draw_modal_surface(frame, area);
+
+    let title_style = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let block = Block::default()
+        .title(Span::styled(
+            tf("bundle.title", &[("bucket", &view.bucket)]),
+            title_style,
+        ))
+        .title_bottom(t("bundle.hint"))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Rgb(20, 20, 30)));
+
+    let label_style = Style::default()
+        .fg(Color::LightBlue)
+        .add_modifier(Modifier::BOLD);
+    let hint_style = Style::default().fg(Color::DarkGray);
+
+    let visible = view.visible();
+    let mut text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(t("bundle.filter"), label_style),
+            Span::styled(
+                view.filter.as_str(),
+                Style::default()
+                    .fg(Color::LightYellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            if view.filtering {
+                Span::styled(" ", Style::default().bg(Color::LightYellow))
+            } else {
+                Span::raw("")
+            },
+            Span::styled(
+                tf(
+                    "bundle.counts",
+                    &[
+                        ("shown", &visible.len()),
+                        ("total", &view.members.len()),
+                        ("picked", &view.picked.len()),
+                    ],
+                ),
+                hint_style,
+            ),
+        ]),
+        Line::from(""),
+    ];
+
+    // Rows left for members inside the border, scrolled to keep the cursor in view
+    let rows = (area.height as usize).saturating_sub(text.len() + 2).max(1);
+    let first = view.cursor.saturating_sub(rows - 1);
+    if visible.is_empty() {
+        text.push(Line::from(Span::styled(t("bundle.none"), hint_style)));
+    }
+    for (position, &index) in visible.iter().enumerate().skip(first).take(rows) {
+        let (archive_key, entry) = &view.members[index];
+        let style = if position == view.cursor {
+            Style::default().bg(Color::DarkGray).fg(Color::White)
+        } else {
+            Style::default()
+        };
+        let mark = if view.picked.contains(&index) {
+            "● "
+        } else {
+            "  "
+        };
+        let archive = archive_key.rsplit('/').next().unwrap_or(archive_key);
+        text.push(Line::from(vec![
+            Span::styled(mark, Style::default().fg(Color::LightGreen)),
+            Span::styled(entry.key.clone(), style),
+            Span::styled(
+                format!(
+                    "  {}  {}  {}",
+                    format_size(entry.size),
+                    entry
+                        .last_modified
+                        .as_deref()
+                        .map(short_timestamp)
+                        .unwrap_or_default(),
+                    archive
+                ),
+                hint_style,
+            ),
+        ]));
+    }
+    let para = Paragraph::new(text).block(block);
+    frame.render_widget(para, area);
+}
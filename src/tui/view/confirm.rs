@@ -0,0 +1,1425 @@
+use std::time::Duration;
+
+use ratatui::layout::Constraint;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{
+    Block, Borders, List, ListItem, ListState, Paragraph, Row, Table, TableState, Wrap,
+};
+
+use crate::access;
+use crate::app::{App, PendingAction, StorageIntent};
+use crate::bandwidth::format_rate;
+use crate::bundle;
+use crate::dryrun::DryRunOutcome;
+use crate::i18n::{t, tf};
+use crate::journal::JobHistory;
+use crate::models::{JobKind, RestoreTier, RetentionMode, StorageClassTier};
+use crate::preview::PreviewRowKind;
+use crate::pricing;
+use crate::tui::{approval_tally, preview_objects, reencrypt_keys, target_count, transition_keys};
+use crate::waves;
+
+use super::{
+    approval_color, centered_rect, draw_modal_surface, format_remaining, format_size,
+    short_timestamp,
+};
+
+pub(super) fn draw_schedule_popup(frame: &mut ratatui::Frame, app: &App) {
+    let area = centered_rect(70, 50, frame.size());
+    draw_modal_surface(frame, area);
+
+    let title_style = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let block = Block::default()
+        .title(Span::styled(t("schedule.title"), title_style))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Rgb(20, 20, 30)));
+
+    let hint_style = Style::default().fg(Color::DarkGray);
+    let mut text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                t("schedule.prompt"),
+                Style::default()
+                    .fg(Color::LightBlue)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                app.schedule_input.as_str(),
+                Style::default()
+                    .fg(Color::LightYellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" ", Style::default().bg(Color::LightYellow)),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(t("schedule.formats"), hint_style)),
+        Line::from(Span::styled(t("schedule.hint"), hint_style)),
+        Line::from(""),
+    ];
+    if app.schedule.actions().is_empty() {
+        text.push(Line::from(Span::styled(t("schedule.empty"), hint_style)));
+    }
+    for (index, action) in app.schedule.actions().iter().enumerate() {
+        let style = if index == app.schedule_cursor {
+            Style::default().bg(Color::DarkGray).fg(Color::White)
+        } else {
+            Style::default()
+        };
+        text.push(Line::from(vec![
+            Span::styled(
+                format!("{}  ", short_timestamp(&action.run_at)),
+                Style::default().fg(Color::LightCyan),
+            ),
+            Span::styled(action.describe(), style),
+        ]));
+    }
+    let para = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+    frame.render_widget(para, area);
+}
+
+pub(super) fn draw_retain_popup(frame: &mut ratatui::Frame, app: &App) {
+    let area = centered_rect(60, 20, frame.size());
+    draw_modal_surface(frame, area);
+
+    let block = Block::default()
+        .title(Span::styled(
+            t("retain.title"),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Rgb(20, 20, 30)));
+
+    let hint_style = Style::default().fg(Color::DarkGray);
+    let text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                t("retain.prompt"),
+                Style::default()
+                    .fg(Color::LightBlue)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                app.retain_input.as_str(),
+                Style::default()
+                    .fg(Color::LightYellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" ", Style::default().bg(Color::LightYellow)),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(t("retain.formats"), hint_style)),
+        Line::from(Span::styled(t("retain.hint"), hint_style)),
+    ];
+    let para = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+    frame.render_widget(para, area);
+}
+
+pub(super) fn draw_job_note_popup(frame: &mut ratatui::Frame, app: &App) {
+    let area = centered_rect(60, 20, frame.size());
+    draw_modal_surface(frame, area);
+
+    let block = Block::default()
+        .title(Span::styled(
+            t("job_note.title"),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Rgb(20, 20, 30)));
+
+    let text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                t("job_note.prompt"),
+                Style::default()
+                    .fg(Color::LightBlue)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                app.note_input.as_str(),
+                Style::default()
+                    .fg(Color::LightYellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" ", Style::default().bg(Color::LightYellow)),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            t("job_note.hint"),
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+    let para = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+    frame.render_widget(para, area);
+}
+
+pub(super) fn draw_ticket_popup(frame: &mut ratatui::Frame, app: &App) {
+    let area = centered_rect(60, 20, frame.size());
+    draw_modal_surface(frame, area);
+
+    let block = Block::default()
+        .title(Span::styled(
+            t("ticket.title"),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Rgb(20, 20, 30)));
+
+    let hint = if app
+        .tickets
+        .as_ref()
+        .is_some_and(|tickets| tickets.checks_tracker())
+    {
+        t("ticket.hint_lookup")
+    } else {
+        t("ticket.hint")
+    };
+    let text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                t("ticket.prompt"),
+                Style::default()
+                    .fg(Color::LightBlue)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                app.ticket_input.as_str(),
+                Style::default()
+                    .fg(Color::LightYellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" ", Style::default().bg(Color::LightYellow)),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(hint, Style::default().fg(Color::DarkGray))),
+    ];
+    let para = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+    frame.render_widget(para, area);
+}
+
+pub(super) fn draw_storage_popup(frame: &mut ratatui::Frame, app: &App) {
+    let area = centered_rect(75, 50, frame.size());
+    draw_modal_surface(frame, area);
+
+    let region = app
+        .buckets
+        .get(app.selected_bucket)
+        .and_then(|b| b.region.as_deref())
+        .or(app.selected_region.as_deref());
+    let mut priced_region = None;
+    // Suggested from the targets' last reads, once looked up
+    let coldness = app.picker_coldness();
+    let suggested = coldness.and_then(|coldness| coldness.suggested_class());
+    let rows: Vec<Row> = StorageClassTier::selectable()
+        .iter()
+        .map(|class| {
+            let price = match pricing::monthly_price_per_gb(region, class) {
+                Some((price, price_region)) => {
+                    priced_region = Some(price_region);
+                    format!("${price:.5}")
+                }
+                None => "-".to_string(),
+            };
+            let minimum = match class.min_storage_days() {
+                0 => "none".to_string(),
+                days => format!("{days} days"),
+            };
+            if let Some(reason) = app.picker_class_unavailable(class) {
+                // Greyed out, with the reason in place of the details
+                return Row::new(vec![
+                    class.label().to_string(),
+                    price,
+                    format!("unavailable: {reason}"),
+                ])
+                .style(
+                    Style::default()
+                        .fg(Color::DarkGray)
+                        .add_modifier(Modifier::CROSSED_OUT),
+                );
+            }
+            if suggested.as_ref() == Some(class) {
+                return Row::new(vec![
+                    format!("{} ★", class.label()),
+                    price,
+                    class.retrieval_latency().to_string(),
+                    minimum,
+                ])
+                .style(Style::default().fg(Color::LightGreen));
+            }
+            Row::new(vec![
+                class.label().to_string(),
+                price,
+                class.retrieval_latency().to_string(),
+                minimum,
+            ])
+        })
+        .collect();
+
+    let price_note = match priced_region {
+        Some(priced) if Some(priced) == region => format!("prices for {priced}"),
+        Some(priced) => format!("{priced} prices – no table for this bucket's region"),
+        None => "no price data".to_string(),
+    };
+    let coldness_note = match (coldness, &suggested) {
+        (Some(coldness), Some(_)) => format!(" · ★ suggested: {}", coldness.label()),
+        (Some(coldness), None) => format!(" · {}: no colder class suggested", coldness.label()),
+        (None, _) => String::new(),
+    };
+    let title = match app.storage_intent {
+        StorageIntent::Transition => "Select storage class (Enter confirm, Esc cancel)",
+        StorageIntent::ExportLifecycle => {
+            "Export lifecycle rule: select target class (Enter compare, Esc cancel)"
+        }
+        StorageIntent::SavePolicy => "Policy: select target class (Enter save, Esc cancel)",
+    };
+    let block = Block::default()
+        .title(title)
+        .title_bottom(format!(" USD per GB-month, {price_note}{coldness_note} "))
+        .borders(Borders::ALL)
+        .style(Style::default().bg(Color::Black));
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(20),
+            Constraint::Length(12),
+            Constraint::Length(26),
+            Constraint::Min(12),
+        ],
+    )
+    .header(
+        Row::new(vec!["Class", "$/GB-month", "Retrieval", "Min. duration"])
+            .style(Style::default().add_modifier(Modifier::BOLD))
+            .bottom_margin(1),
+    )
+    .block(block)
+    .highlight_style(Style::default().fg(Color::Black).bg(Color::Yellow));
+    let mut state = TableState::default();
+    state.select(Some(app.storage_class_cursor));
+    frame.render_stateful_widget(table, area, &mut state);
+}
+
+pub(super) fn draw_confirm_popup(frame: &mut ratatui::Frame, app: &App, history: &JobHistory) {
+    let area = centered_rect(60, 40, frame.size());
+    draw_modal_surface(frame, area);
+
+    let key_style = Style::default()
+        .bg(Color::LightYellow)
+        .fg(Color::Black)
+        .add_modifier(Modifier::BOLD);
+    let warn_style = Style::default()
+        .fg(Color::LightYellow)
+        .add_modifier(Modifier::BOLD);
+    let highlight_style = Style::default()
+        .fg(Color::LightGreen)
+        .add_modifier(Modifier::BOLD);
+
+    let mut lines = Vec::new();
+
+    if let Some(action) = &app.pending_action {
+        match action {
+            PendingAction::Transition { target_class, lock } => {
+                lines.push(Line::from(vec![Span::styled(
+                    t("confirm.transition"),
+                    warn_style,
+                )]));
+                lines.push(Line::from(""));
+                let (keys, skipped) = transition_keys(app, target_class);
+                lines.push(Line::from(vec![
+                    Span::raw(format!("  {} ", t("confirm.objects"))),
+                    Span::styled(format!("{}", keys.len()), highlight_style),
+                ]));
+                lines.push(Line::from(vec![
+                    Span::raw(format!("  {} ", t("confirm.target"))),
+                    Span::styled(target_class.label(), highlight_style),
+                ]));
+                if skipped > 0 {
+                    lines.push(Line::from(tf(
+                        "confirm.skipped",
+                        &[("count", &skipped), ("class", &target_class.label())],
+                    )));
+                }
+                if target_class.is_archival() {
+                    let setting = if lock.is_empty() {
+                        t("confirm.object_lock_off").to_string()
+                    } else {
+                        lock.summary()
+                    };
+                    lines.push(Line::from(vec![
+                        Span::raw(format!("  {} ", t("confirm.object_lock"))),
+                        Span::styled(setting, highlight_style),
+                    ]));
+                    lines.push(Line::from(t("confirm.object_lock_hint")));
+                    if lock
+                        .retention
+                        .as_ref()
+                        .is_some_and(|retention| retention.mode == RetentionMode::Compliance)
+                    {
+                        lines.push(Line::from(Span::styled(
+                            t("confirm.compliance_warning"),
+                            warn_style,
+                        )));
+                    }
+                }
+            }
+            PendingAction::Restore { days, tier, preset } => {
+                lines.push(Line::from(vec![Span::styled(
+                    t("confirm.restore"),
+                    warn_style,
+                )]));
+                lines.push(Line::from(""));
+                if !app.restore_presets.presets().is_empty() {
+                    let chosen = preset
+                        .as_deref()
+                        .and_then(|name| app.restore_presets.get(name));
+                    let label = match chosen {
+                        Some(chosen) => format!("{} ({})", chosen.name, chosen.summary()),
+                        None => t("confirm.preset_none").to_string(),
+                    };
+                    lines.push(Line::from(vec![
+                        Span::raw(format!("  {} ", t("confirm.preset"))),
+                        Span::styled(label, highlight_style),
+                    ]));
+                    if let Some(chosen) = chosen
+                        && !chosen.description.is_empty()
+                    {
+                        lines.push(Line::from(format!("    {}", chosen.description)));
+                    }
+                    lines.push(Line::from(if app.restore_presets_only {
+                        t("confirm.preset_only_hint")
+                    } else {
+                        t("confirm.preset_hint")
+                    }));
+                }
+                lines.push(Line::from(vec![
+                    Span::raw(format!("  {} ", t("confirm.objects"))),
+                    Span::styled(format!("{}", target_count(app)), highlight_style),
+                ]));
+                lines.push(Line::from(vec![
+                    Span::raw(format!("  {} ", t("confirm.duration"))),
+                    Span::styled(tf("confirm.days", &[("days", days)]), highlight_style),
+                ]));
+                let max = app.max_restore_days();
+                lines.push(Line::from(tf("confirm.days_hint", &[("max", &max)])));
+                if !(1..=max).contains(days) {
+                    lines.push(Line::from(Span::styled(
+                        tf("confirm.days_invalid", &[("max", &max)]),
+                        warn_style,
+                    )));
+                }
+                lines.push(Line::from(vec![
+                    Span::raw(format!("  {} ", t("confirm.tier"))),
+                    Span::styled(tier.label(), highlight_style),
+                ]));
+                lines.push(Line::from(t("confirm.tier_hint")));
+                if *tier == RestoreTier::Expedited {
+                    lines.push(Line::from(Span::styled(
+                        t("confirm.expedited_warning"),
+                        warn_style,
+                    )));
+                    let deep_archive = app
+                        .target_objects()
+                        .into_iter()
+                        .filter(|obj| obj.storage_class == StorageClassTier::GlacierDeepArchive)
+                        .count();
+                    if deep_archive > 0 {
+                        lines.push(Line::from(Span::styled(
+                            tf(
+                                "confirm.expedited_deep_archive",
+                                &[("count", &deep_archive)],
+                            ),
+                            warn_style,
+                        )));
+                    }
+                }
+                if let Some(wave_bytes) = app.restore_pacing.wave_bytes {
+                    lines.push(Line::from(tf(
+                        "confirm.waves",
+                        &[("size", &waves::format_bytes(wave_bytes))],
+                    )));
+                }
+            }
+            PendingAction::RestoreWave { .. } | PendingAction::RunPolicy { .. } => {}
+            PendingAction::Bundle {
+                objects,
+                archive_key,
+                compression,
+                delete_originals,
+            } => {
+                lines.push(Line::from(vec![Span::styled(
+                    t("confirm.bundle"),
+                    warn_style,
+                )]));
+                lines.push(Line::from(""));
+                let bytes: i64 = objects.iter().map(|obj| obj.size).sum();
+                lines.push(Line::from(vec![
+                    Span::raw(format!("  {} ", t("confirm.objects"))),
+                    Span::styled(
+                        format!("{} ({})", objects.len(), format_size(bytes)),
+                        highlight_style,
+                    ),
+                ]));
+                lines.push(Line::from(vec![
+                    Span::raw(format!("  {} ", t("confirm.archive"))),
+                    Span::styled(archive_key.as_str(), highlight_style),
+                ]));
+                lines.push(Line::from(vec![
+                    Span::raw(format!("  {} ", t("confirm.compression"))),
+                    Span::styled(compression.label(), highlight_style),
+                ]));
+                lines.push(Line::from(tf(
+                    "confirm.bundle_note",
+                    &[("index", &bundle::index_key(archive_key))],
+                )));
+                lines.push(Line::from(t("confirm.bundle_hint")));
+                if *delete_originals {
+                    lines.push(Line::from(Span::styled(
+                        t("confirm.bundle_delete"),
+                        warn_style,
+                    )));
+                }
+            }
+            PendingAction::RetrieveBundled {
+                bucket,
+                members,
+                local,
+            } => {
+                lines.push(Line::from(vec![Span::styled(
+                    t("confirm.retrieve"),
+                    warn_style,
+                )]));
+                lines.push(Line::from(""));
+                let bytes: i64 = members.iter().map(|(_, entry)| entry.size).sum();
+                lines.push(Line::from(vec![
+                    Span::raw(format!("  {} ", t("confirm.objects"))),
+                    Span::styled(
+                        format!("{} ({})", members.len(), format_size(bytes)),
+                        highlight_style,
+                    ),
+                ]));
+                let mut archives: Vec<&str> = members.iter().map(|(key, _)| key.as_str()).collect();
+                archives.dedup();
+                lines.push(Line::from(vec![
+                    Span::raw(format!("  {} ", t("confirm.archive"))),
+                    Span::styled(
+                        match archives.as_slice() {
+                            [archive] => archive.to_string(),
+                            _ => tf("confirm.archives", &[("count", &archives.len())]),
+                        },
+                        highlight_style,
+                    ),
+                ]));
+                let destination = if *local {
+                    bundle::local_path(bucket, "").display().to_string()
+                } else {
+                    tf("confirm.retrieve_bucket", &[("bucket", bucket)])
+                };
+                lines.push(Line::from(vec![
+                    Span::raw(format!("  {} ", t("confirm.to"))),
+                    Span::styled(destination, highlight_style),
+                ]));
+                lines.push(Line::from(t("confirm.retrieve_note")));
+                lines.push(Line::from(t("confirm.retrieve_hint")));
+            }
+            PendingAction::DeleteVersions { versions } => {
+                lines.push(Line::from(vec![Span::styled(
+                    t("confirm.delete_versions"),
+                    warn_style,
+                )]));
+                lines.push(Line::from(""));
+                lines.push(Line::from(vec![
+                    Span::raw(format!("  {} ", t("confirm.versions"))),
+                    Span::styled(format!("{}", versions.len()), highlight_style),
+                ]));
+                let markers = versions
+                    .iter()
+                    .filter(|obj| obj.version.as_ref().is_some_and(|v| v.delete_marker))
+                    .count();
+                let bytes: i64 = versions.iter().map(|obj| obj.size).sum();
+                let keys: std::collections::HashSet<&str> =
+                    versions.iter().map(|obj| obj.key.as_str()).collect();
+                lines.push(Line::from(tf(
+                    "confirm.versions_detail",
+                    &[
+                        ("versions", &(versions.len() - markers)),
+                        ("size", &waves::format_bytes(bytes.max(0) as u64)),
+                        ("markers", &markers),
+                        ("keys", &keys.len()),
+                    ],
+                )));
+                lines.push(Line::from(Span::styled(
+                    t("confirm.versions_note"),
+                    warn_style,
+                )));
+            }
+            PendingAction::TransitionVersions {
+                target_class,
+                replays,
+            } => {
+                lines.push(Line::from(vec![Span::styled(
+                    t("confirm.transition_versions"),
+                    warn_style,
+                )]));
+                lines.push(Line::from(""));
+                let moved: usize = replays.iter().map(|replay| replay.moved()).sum();
+                let bytes: i64 = replays.iter().map(|replay| replay.moved_bytes()).sum();
+                let rewritten: usize = replays.iter().map(|replay| replay.rewritten()).sum();
+                lines.push(Line::from(vec![
+                    Span::raw(format!("  {} ", t("confirm.versions"))),
+                    Span::styled(format!("{moved}"), highlight_style),
+                ]));
+                lines.push(Line::from(vec![
+                    Span::raw(format!("  {} ", t("confirm.target"))),
+                    Span::styled(target_class.label(), highlight_style),
+                ]));
+                lines.push(Line::from(tf(
+                    "confirm.replay_detail",
+                    &[
+                        ("size", &waves::format_bytes(bytes.max(0) as u64)),
+                        ("keys", &replays.len()),
+                        ("rewritten", &rewritten),
+                    ],
+                )));
+                lines.push(Line::from(Span::styled(
+                    t("confirm.replay_note"),
+                    warn_style,
+                )));
+            }
+            PendingAction::RestoreVersions {
+                versions,
+                days,
+                tier,
+            } => {
+                lines.push(Line::from(vec![Span::styled(
+                    t("confirm.restore_versions"),
+                    warn_style,
+                )]));
+                lines.push(Line::from(""));
+                let bytes: i64 = versions.iter().map(|obj| obj.size).sum();
+                lines.push(Line::from(vec![
+                    Span::raw(format!("  {} ", t("confirm.versions"))),
+                    Span::styled(
+                        format!(
+                            "{} ({})",
+                            versions.len(),
+                            waves::format_bytes(bytes.max(0) as u64)
+                        ),
+                        highlight_style,
+                    ),
+                ]));
+                lines.push(Line::from(vec![
+                    Span::raw(format!("  {} ", t("confirm.duration"))),
+                    Span::styled(tf("confirm.days", &[("days", days)]), highlight_style),
+                ]));
+                let max = app.max_restore_days();
+                lines.push(Line::from(tf("confirm.days_hint", &[("max", &max)])));
+                if !(1..=max).contains(days) {
+                    lines.push(Line::from(Span::styled(
+                        tf("confirm.days_invalid", &[("max", &max)]),
+                        warn_style,
+                    )));
+                }
+                lines.push(Line::from(vec![
+                    Span::raw(format!("  {} ", t("confirm.tier"))),
+                    Span::styled(tier.label(), highlight_style),
+                ]));
+                lines.push(Line::from(t("confirm.tier_hint")));
+                lines.push(Line::from(Span::styled(
+                    t("confirm.restore_versions_note"),
+                    warn_style,
+                )));
+            }
+            PendingAction::Reencrypt { kms_key_id } => {
+                lines.push(Line::from(vec![Span::styled(
+                    t("confirm.reencrypt"),
+                    warn_style,
+                )]));
+                lines.push(Line::from(""));
+                let (keys, archived) = reencrypt_keys(app);
+                let bucket = app.selected_bucket_name().unwrap_or_default();
+                let done = history.succeeded_for(JobKind::Reencrypt, bucket, kms_key_id);
+                let journaled = keys.iter().filter(|key| done.contains(*key)).count();
+                lines.push(Line::from(vec![
+                    Span::raw(format!("  {} ", t("confirm.objects"))),
+                    Span::styled(format!("{}", keys.len() - journaled), highlight_style),
+                ]));
+                lines.push(Line::from(vec![
+                    Span::raw(format!("  {} ", t("confirm.kms_key"))),
+                    Span::styled(kms_key_id.as_str(), highlight_style),
+                ]));
+                lines.push(Line::from(t("confirm.reencrypt_note")));
+                if journaled > 0 {
+                    lines.push(Line::from(tf(
+                        "confirm.reencrypt_done",
+                        &[("count", &journaled)],
+                    )));
+                }
+                if archived > 0 {
+                    lines.push(Line::from(tf(
+                        "confirm.reencrypt_archived",
+                        &[("count", &archived)],
+                    )));
+                }
+            }
+            PendingAction::Copy {
+                source_bucket,
+                dest_bucket,
+                keys,
+                mode,
+                delete_source,
+                max_bandwidth,
+                rewrite,
+                storage_class,
+            } => {
+                let title = if *delete_source {
+                    t("confirm.move")
+                } else {
+                    t("confirm.copy")
+                };
+                lines.push(Line::from(vec![Span::styled(title, warn_style)]));
+                lines.push(Line::from(""));
+                lines.push(Line::from(vec![
+                    Span::raw(format!("  {} ", t("confirm.objects"))),
+                    Span::styled(format!("{}", keys.len()), highlight_style),
+                ]));
+                lines.push(Line::from(vec![
+                    Span::raw(format!("  {} ", t("confirm.from"))),
+                    Span::styled(source_bucket.as_str(), highlight_style),
+                ]));
+                lines.push(Line::from(vec![
+                    Span::raw(format!("  {} ", t("confirm.to"))),
+                    Span::styled(dest_bucket.as_str(), highlight_style),
+                ]));
+                if !rewrite.is_empty() {
+                    lines.push(Line::from(vec![
+                        Span::raw(format!("  {} ", t("confirm.rewrite"))),
+                        Span::styled(rewrite.label(), highlight_style),
+                    ]));
+                    if app.collision_check.is_some() {
+                        lines.push(Line::from(t("confirm.collisions_checking")));
+                    } else if app.collision_error.is_some() {
+                        lines.push(Line::from(Span::styled(
+                            t("confirm.collisions_failed"),
+                            warn_style,
+                        )));
+                    } else if let Some(review) = &app.collisions {
+                        lines.push(Line::from(Span::styled(
+                            tf(
+                                "confirm.collisions_undecided",
+                                &[("count", &review.collisions.len())],
+                            ),
+                            warn_style,
+                        )));
+                    }
+                }
+                if let Some(class) = storage_class {
+                    lines.push(Line::from(vec![
+                        Span::raw(format!("  {} ", t("confirm.copy_class"))),
+                        Span::styled(class.label(), highlight_style),
+                    ]));
+                }
+                lines.push(Line::from(vec![
+                    Span::raw(format!("  {} ", t("confirm.copy_mode"))),
+                    Span::styled(t(mode.label_key()), highlight_style),
+                ]));
+                lines.push(Line::from(t("confirm.copy_mode_hint")));
+                let bandwidth = if app.same_region(source_bucket, dest_bucket) {
+                    t("confirm.bandwidth_same_region").to_string()
+                } else {
+                    match app.copy_cap(source_bucket, dest_bucket, *max_bandwidth) {
+                        Some(cap) => format_rate(cap),
+                        None => t("confirm.bandwidth_uncapped").to_string(),
+                    }
+                };
+                lines.push(Line::from(vec![
+                    Span::raw(format!("  {} ", t("confirm.bandwidth"))),
+                    Span::styled(bandwidth, highlight_style),
+                ]));
+                if let Some(region) = app.bucket_region(dest_bucket)
+                    && let Some(measured) = app.throughput.get(region)
+                {
+                    let rate = format_rate(measured.bytes_per_second as u64);
+                    lines.push(Line::from(tf(
+                        "confirm.bandwidth_measured",
+                        &[("region", &region), ("rate", &rate)],
+                    )));
+                }
+                lines.push(Line::from(t("confirm.bandwidth_hint")));
+                if let Some(transform) = &app.transform {
+                    lines.push(Line::from(vec![
+                        Span::raw(format!("  {} ", t("confirm.transform"))),
+                        Span::styled(format!("`{}`", transform.command), highlight_style),
+                    ]));
+                    lines.push(Line::from(t("confirm.transform_note")));
+                }
+                if let (Some(source), Some(dest)) = (
+                    app.access_audits.get(source_bucket),
+                    app.access_audits.get(dest_bucket),
+                ) {
+                    let (wider, narrower) =
+                        access::drift(source, dest, keys.iter().map(String::as_str), rewrite);
+                    if wider > 0 {
+                        lines.push(Line::from(Span::styled(
+                            tf(
+                                "confirm.access_wider",
+                                &[("count", &wider), ("dest", dest_bucket)],
+                            ),
+                            warn_style,
+                        )));
+                    }
+                    if narrower > 0 {
+                        lines.push(Line::from(Span::styled(
+                            tf(
+                                "confirm.access_narrower",
+                                &[("count", &narrower), ("source", source_bucket)],
+                            ),
+                            warn_style,
+                        )));
+                    }
+                }
+                if *delete_source {
+                    lines.push(Line::from(Span::styled(
+                        t("confirm.move_warning"),
+                        warn_style,
+                    )));
+                }
+            }
+        }
+        // A retrieval's members come from the bundle view, not the listing
+        let from_listing = !matches!(action, PendingAction::RetrieveBundled { .. });
+        let excluded = app.excluded_count();
+        if from_listing && excluded > 0 {
+            lines.push(Line::from(tf("confirm.excluded", &[("count", &excluded)])));
+        }
+        if from_listing && !app.is_selection() && app.has_picks() {
+            let picked = app.picked_count();
+            lines.push(Line::from(tf("confirm.picked", &[("count", &picked)])));
+        }
+        let protected = app.protected_count();
+        if from_listing && protected > 0 {
+            lines.push(Line::from(tf(
+                "confirm.protected",
+                &[("count", &protected)],
+            )));
+        }
+        if let Some(bucket) = app.pending_bucket()
+            && !app.owners.owners().is_empty()
+        {
+            let objects = preview_objects(app);
+            let owners = app.owners.owners_of(
+                bucket,
+                objects.iter().map(|(key, size)| (key.as_str(), *size)),
+            );
+            if !owners.is_empty() {
+                lines.push(Line::from(t("confirm.owners")));
+            }
+            for (owner, count, _) in owners {
+                let mut spans = vec![Span::raw(tf(
+                    "confirm.owner_line",
+                    &[
+                        ("owner", &owner.label()),
+                        ("count", &count),
+                        ("prefix", &owner.prefix),
+                    ],
+                ))];
+                if owner.webhook_url.is_some() {
+                    spans.push(Span::styled(
+                        t("confirm.owner_notified"),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                lines.push(Line::from(spans));
+            }
+        }
+        if let Some(tally) = approval_tally(app) {
+            if tally.blocked > 0 {
+                lines.push(Line::from(Span::styled(
+                    tf(
+                        "confirm.approval_blocked",
+                        &[
+                            ("count", &tally.blocked),
+                            ("prefixes", &tally.blocked_prefixes.join(", ")),
+                        ],
+                    ),
+                    warn_style,
+                )));
+            }
+            if tally.pending > 0 {
+                lines.push(Line::from(Span::styled(
+                    tf(
+                        "confirm.approval_pending",
+                        &[
+                            ("count", &tally.pending),
+                            ("prefixes", &tally.pending_prefixes.join(", ")),
+                        ],
+                    ),
+                    warn_style,
+                )));
+            }
+            if tally.unreviewed > 0 {
+                lines.push(Line::from(Span::styled(
+                    tf(
+                        "confirm.approval_unreviewed",
+                        &[("count", &tally.unreviewed)],
+                    ),
+                    warn_style,
+                )));
+            }
+            if tally.blocked > 0 || (app.require_approval && tally.unapproved() > 0) {
+                lines.push(Line::from(Span::styled(
+                    t("confirm.approval_refused"),
+                    warn_style,
+                )));
+            }
+        }
+        if let Some(estimate) = estimate_duration(app, history, action) {
+            lines.push(Line::from(estimate));
+        }
+        if matches!(
+            action,
+            PendingAction::Transition { .. } | PendingAction::Restore { .. }
+        ) && target_count(app) > app.shard_keys
+        {
+            lines.push(Line::from(Span::styled(
+                tf("confirm.sharded", &[("size", &app.shard_keys)]),
+                warn_style,
+            )));
+        }
+        if let Some(window) = app.execution_window
+            && !window.is_open()
+        {
+            lines.push(Line::from(Span::styled(
+                tf("confirm.window", &[("window", &window.label())]),
+                warn_style,
+            )));
+        }
+        match &app.job_note {
+            Some(note) => lines.push(Line::from(vec![
+                Span::raw(format!("  {} ", t("confirm.note"))),
+                Span::styled(note.as_str(), highlight_style),
+            ])),
+            None => lines.push(Line::from(t("confirm.note_hint"))),
+        }
+        let required = app.tickets.as_ref().is_some_and(|tickets| tickets.required);
+        match (&app.job_ticket, &app.ticket_check) {
+            (_, Some(_)) => lines.push(Line::from(vec![
+                Span::raw(format!("  {} ", t("confirm.ticket"))),
+                Span::styled(
+                    tf("confirm.ticket_checking", &[("ticket", &app.ticket_input)]),
+                    highlight_style,
+                ),
+            ])),
+            (Some(ticket), None) => lines.push(Line::from(vec![
+                Span::raw(format!("  {} ", t("confirm.ticket"))),
+                Span::styled(ticket.id.as_str(), highlight_style),
+                Span::raw(
+                    ticket
+                        .url
+                        .as_ref()
+                        .map(|url| format!("  {url}"))
+                        .unwrap_or_default(),
+                ),
+            ])),
+            (None, None) if required => lines.push(Line::from(Span::styled(
+                t("confirm.ticket_required"),
+                warn_style,
+            ))),
+            (None, None) => lines.push(Line::from(t("confirm.ticket_hint"))),
+        }
+    }
+
+    lines.push(Line::from(""));
+    let mut keys = vec![
+        Span::styled(" Enter ", key_style),
+        Span::raw(t("confirm.confirm")),
+    ];
+    if matches!(
+        app.pending_action,
+        Some(PendingAction::Transition { .. } | PendingAction::Restore { .. })
+    ) {
+        keys.push(Span::styled(" b ", key_style));
+        keys.push(Span::raw(t("confirm.background")));
+    }
+    if matches!(app.pending_action, Some(PendingAction::Transition { .. })) {
+        keys.push(Span::styled(" r ", key_style));
+        keys.push(Span::raw(t("confirm.dry_run")));
+    }
+    keys.extend([
+        Span::styled(" v ", key_style),
+        Span::raw(t("confirm.preview")),
+        Span::styled(" t ", key_style),
+        Span::raw(t("confirm.schedule")),
+        Span::styled(" Esc ", key_style),
+        Span::raw(t("confirm.cancel")),
+    ]);
+    lines.push(Line::from(keys));
+
+    let block = Block::default()
+        .title(Span::styled(
+            t("confirm.title"),
+            Style::default()
+                .fg(Color::LightYellow)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .style(Style::default().bg(Color::Black));
+    let para = Paragraph::new(lines).block(block);
+    frame.render_widget(para, area);
+}
+
+/// "2h 05m" style duration for restore ETAs
+/// How long the pending action should take, from the mean request time of
+/// recent jobs of the same kind and the concurrency it will run with
+pub(super) fn estimate_duration(
+    app: &App,
+    history: &JobHistory,
+    action: &PendingAction,
+) -> Option<String> {
+    let (kind, count, concurrency, per_second) = match action {
+        PendingAction::Transition { target_class, .. } => (
+            JobKind::Transition,
+            transition_keys(app, target_class).0.len(),
+            1,
+            None,
+        ),
+        PendingAction::Restore { preset, .. } => {
+            let (pacing, _) = app.restore_settings(preset.as_deref());
+            (
+                JobKind::Restore,
+                target_count(app),
+                pacing.concurrency.max(1),
+                pacing.per_second,
+            )
+        }
+        PendingAction::Copy {
+            keys,
+            delete_source,
+            ..
+        } => {
+            let kind = if *delete_source {
+                JobKind::Move
+            } else {
+                JobKind::Copy
+            };
+            (kind, keys.len(), 1, None)
+        }
+        PendingAction::Reencrypt { .. } => {
+            (JobKind::Reencrypt, reencrypt_keys(app).0.len(), 1, None)
+        }
+        PendingAction::DeleteVersions { versions } => {
+            (JobKind::DeleteVersions, versions.len(), 1, None)
+        }
+        PendingAction::TransitionVersions { replays, .. } => {
+            (JobKind::TransitionVersions, replays.len(), 1, None)
+        }
+        PendingAction::RestoreVersions { versions, .. } => {
+            (JobKind::RestoreVersions, versions.len(), 1, None)
+        }
+        PendingAction::RestoreWave { .. }
+        | PendingAction::RunPolicy { .. }
+        | PendingAction::Bundle { .. }
+        | PendingAction::RetrieveBundled { .. } => return None,
+    };
+    if count == 0 {
+        return None;
+    }
+    let Some(latency) = history.request_latency(kind) else {
+        return Some(t("confirm.eta_unknown").to_string());
+    };
+    let mut estimate = latency * count.div_ceil(concurrency) as u32;
+    if let Some(rate) = per_second.filter(|rate| *rate > 0) {
+        estimate = estimate.max(Duration::from_secs_f64(count as f64 / rate as f64));
+    }
+    let how = if concurrency > 1 {
+        tf("confirm.eta_concurrent", &[("count", &concurrency)])
+    } else {
+        t("confirm.eta_sequential").to_string()
+    };
+    let estimate = chrono::Duration::from_std(estimate).unwrap_or(chrono::Duration::MAX);
+    Some(tf(
+        "confirm.eta",
+        &[("duration", &format_remaining(estimate)), ("how", &how)],
+    ))
+}
+
+pub(super) fn draw_preview_popup(frame: &mut ratatui::Frame, app: &App) {
+    let Some(preview) = &app.preview else {
+        return;
+    };
+    let area = centered_rect(85, 75, frame.size());
+    draw_modal_surface(frame, area);
+
+    let (count, bytes) = preview.total();
+    // The owner of the cursor's group is typed in the bottom border
+    let bottom = match &app.owner_input {
+        Some(input) => Line::from(vec![
+            Span::styled(
+                t("preview.owner_prompt"),
+                Style::default().fg(Color::LightMagenta),
+            ),
+            Span::styled(input.as_str(), Style::default().fg(Color::LightYellow)),
+            Span::styled(" ", Style::default().bg(Color::LightYellow)),
+        ]),
+        None => Line::from(t("preview.hint")),
+    };
+    let block = Block::default()
+        .title(tf(
+            "preview.title",
+            &[("count", &count), ("size", &format_size(bytes))],
+        ))
+        .title_bottom(bottom)
+        .borders(Borders::ALL)
+        .style(Style::default().bg(Color::Black));
+
+    let group_style = Style::default()
+        .fg(Color::LightCyan)
+        .add_modifier(Modifier::BOLD);
+    let count_style = Style::default().fg(Color::LightYellow);
+    let size_style = Style::default().fg(Color::DarkGray);
+    let owner_style = Style::default().fg(Color::LightMagenta);
+    let bucket = app.pending_bucket().unwrap_or_default();
+    let items: Vec<ListItem> = preview
+        .rows()
+        .into_iter()
+        .map(|row| {
+            let indent = "  ".repeat(row.depth);
+            let spans = match row.kind {
+                PreviewRowKind::Group { expanded } => {
+                    // An owner is shown where it takes over from the enclosing prefix's
+                    let parent = &row.path[..row.path.len() - row.name.len()];
+                    let owner = app
+                        .owners
+                        .lookup(bucket, &row.path)
+                        .filter(|owner| {
+                            app.owners
+                                .lookup(bucket, parent)
+                                .is_none_or(|above| !std::ptr::eq(above, *owner))
+                        })
+                        .map(|owner| tf("preview.owner", &[("owner", &owner.label())]))
+                        .unwrap_or_default();
+                    // Signed-off groups take the colour of their state, named where it changes
+                    let approval = app.approvals.lookup(bucket, &row.path).map(|a| a.state);
+                    let (name_style, state) = match approval {
+                        Some(state) => (
+                            group_style.fg(approval_color(state)),
+                            if app.approvals.lookup(bucket, parent).map(|a| a.state) == Some(state)
+                            {
+                                String::new()
+                            } else {
+                                format!("  [{}]", state.label())
+                            },
+                        ),
+                        None => (group_style, String::new()),
+                    };
+                    let recency = app
+                        .last_reads
+                        .get(bucket)
+                        .map(|reads| format!("  {}", reads.label(reads.under(&row.path))))
+                        .unwrap_or_default();
+                    vec![
+                        Span::raw(indent),
+                        Span::styled(if expanded { "▼ " } else { "▶ " }, name_style),
+                        Span::styled(row.name, name_style),
+                        Span::styled(state, name_style),
+                        Span::styled(format!("  {} objects", row.count), count_style),
+                        Span::styled(format!("  {}", format_size(row.bytes)), size_style),
+                        Span::styled(recency, count_style),
+                        Span::styled(owner, owner_style),
+                    ]
+                }
+                PreviewRowKind::Key => vec![
+                    Span::raw(format!("{indent}  ")),
+                    Span::raw(row.name),
+                    Span::styled(format!("  {}", format_size(row.bytes)), size_style),
+                ],
+                PreviewRowKind::More(rest) => vec![
+                    Span::raw(format!("{indent}  ")),
+                    Span::styled(
+                        format!("… {rest} more keys ({})", format_size(row.bytes)),
+                        size_style,
+                    ),
+                ],
+            };
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let mut state = ListState::default();
+    if !items.is_empty() {
+        state.select(Some(preview.cursor.min(items.len() - 1)));
+    }
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().bg(Color::Blue));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+pub(super) fn draw_dry_run_popup(frame: &mut ratatui::Frame, app: &App) {
+    let Some(dry_run) = &app.dry_run else {
+        return;
+    };
+    let area = centered_rect(85, 75, frame.size());
+    draw_modal_surface(frame, area);
+
+    let title_style = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let block = Block::default()
+        .title(Span::styled(
+            tf(
+                "dry_run.title",
+                &[
+                    ("bucket", &dry_run.bucket),
+                    ("class", &dry_run.target_class.label()),
+                ],
+            ),
+            title_style,
+        ))
+        .title_bottom(t("dry_run.hint"))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Rgb(20, 20, 30)));
+
+    let label_style = Style::default()
+        .fg(Color::LightBlue)
+        .add_modifier(Modifier::BOLD);
+    let hint_style = Style::default().fg(Color::DarkGray);
+    let warn_style = Style::default().fg(Color::LightYellow);
+
+    let (count, bytes) = dry_run.total(DryRunOutcome::Transition);
+    let mut text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(t("dry_run.would_transition"), label_style),
+            Span::styled(
+                tf(
+                    "dry_run.objects",
+                    &[("count", &count), ("size", &format_size(bytes))],
+                ),
+                Style::default().fg(Color::LightGreen),
+            ),
+        ]),
+    ];
+    for class in dry_run.by_class() {
+        text.push(Line::from(vec![
+            Span::raw(format!("  {:<28}", class.class.label())),
+            Span::raw(tf(
+                "dry_run.objects",
+                &[("count", &class.count), ("size", &format_size(class.bytes))],
+            )),
+        ]));
+    }
+    text.push(Line::from(""));
+    text.push(Line::from(Span::styled(t("dry_run.skipped"), label_style)));
+    for outcome in DryRunOutcome::SKIPPED {
+        let (count, bytes) = dry_run.total(outcome);
+        let style = if count > 0 { warn_style } else { hint_style };
+        text.push(Line::from(Span::styled(
+            format!(
+                "  {:<28}{}",
+                outcome.label(),
+                tf(
+                    "dry_run.objects",
+                    &[("count", &count), ("size", &format_size(bytes))]
+                )
+            ),
+            style,
+        )));
+    }
+    text.push(Line::from(""));
+
+    // Rows left for skipped keys inside the border
+    let rows = (area.height as usize).saturating_sub(text.len() + 2);
+    let mut skipped = dry_run.skipped().peekable();
+    if skipped.peek().is_none() {
+        text.push(Line::from(Span::styled(t("dry_run.none"), hint_style)));
+    }
+    for entry in skipped.skip(app.dry_run_scroll).take(rows) {
+        text.push(Line::from(vec![
+            Span::styled(format!(" {:<18}", entry.outcome.label()), warn_style),
+            Span::raw(entry.key.clone()),
+            Span::styled(
+                format!(
+                    "  {} {}",
+                    format_size(entry.size),
+                    entry.storage_class.label()
+                ),
+                hint_style,
+            ),
+        ]));
+    }
+    let para = Paragraph::new(text).block(block);
+    frame.render_widget(para, area);
+}
+
+pub(super) fn draw_comparison_popup(frame: &mut ratatui::Frame, app: &App) {
+    let Some(comparison) = &app.lifecycle_comparison else {
+        return;
+    };
+    let area = centered_rect(80, 70, frame.size());
+    draw_modal_surface(frame, area);
+
+    let rule = &comparison.rule;
+    let block = Block::default()
+        .title(Span::styled(
+            tf(
+                "compare.title",
+                &[
+                    ("prefix", &rule.prefix),
+                    ("class", &rule.storage_class.label()),
+                ],
+            ),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .title_bottom(t("compare.hint"))
+        .borders(Borders::ALL)
+        .style(Style::default().bg(Color::Black));
+
+    let hint_style = Style::default().fg(Color::DarkGray);
+    let warn_style = Style::default().fg(Color::LightYellow);
+    let Some(costs) = &comparison.costs else {
+        let text = vec![
+            Line::from(""),
+            Line::from(Span::styled(t("compare.no_prices"), warn_style)),
+        ];
+        frame.render_widget(Paragraph::new(text).block(block), area);
+        return;
+    };
+
+    let header_style = Style::default()
+        .fg(Color::LightBlue)
+        .add_modifier(Modifier::BOLD);
+    let (cheaper, dearer) = if costs.client_is_cheaper() {
+        (Color::LightGreen, Color::Gray)
+    } else {
+        (Color::Gray, Color::LightGreen)
+    };
+    let usd = |amount: f64| format!("${amount:.2}");
+    let cost_rows = [
+        (
+            t("compare.moved"),
+            costs.client.moved.to_string(),
+            costs.lifecycle.moved.to_string(),
+        ),
+        (
+            t("compare.timing"),
+            t("compare.now").to_string(),
+            tf("compare.by_day", &[("days", &costs.lifecycle_days)]),
+        ),
+        (
+            t("compare.requests"),
+            usd(costs.client.request_fees),
+            usd(costs.lifecycle.request_fees),
+        ),
+        (
+            t("compare.early_deletion"),
+            usd(costs.client.early_deletion),
+            usd(costs.lifecycle.early_deletion),
+        ),
+        (
+            t("compare.storage"),
+            usd(costs.client.storage),
+            usd(costs.lifecycle.storage),
+        ),
+    ];
+    let mut text = vec![
+        Line::from(""),
+        Line::from(tf(
+            "compare.objects",
+            &[
+                ("count", &costs.objects),
+                ("size", &format_size(costs.bytes)),
+                ("days", &rule.days),
+            ],
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!(
+                "{:<24}{:>18}{:>18}",
+                "",
+                t("compare.client"),
+                t("compare.lifecycle")
+            ),
+            header_style,
+        )),
+    ];
+    for (label, client, lifecycle) in cost_rows {
+        text.push(Line::from(format!(
+            "{label:<24}{client:>18}{lifecycle:>18}"
+        )));
+    }
+    text.push(Line::from(vec![
+        Span::styled(format!("{:<24}", t("compare.total")), header_style),
+        Span::styled(
+            format!("{:>18}", usd(costs.client.total())),
+            Style::default().fg(cheaper).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!("{:>18}", usd(costs.lifecycle.total())),
+            Style::default().fg(dearer).add_modifier(Modifier::BOLD),
+        ),
+    ]));
+    text.push(Line::from(""));
+    let saving = usd((costs.client.total() - costs.lifecycle.total()).abs());
+    text.push(Line::from(Span::styled(
+        if costs.objects == 0 {
+            t("compare.nothing").to_string()
+        } else if costs.client_is_cheaper() {
+            tf("compare.pick_client", &[("saving", &saving)])
+        } else {
+            tf("compare.pick_lifecycle", &[("saving", &saving)])
+        },
+        Style::default()
+            .fg(Color::LightGreen)
+            .add_modifier(Modifier::BOLD),
+    )));
+    text.push(Line::from(""));
+    if costs.too_small > 0 {
+        text.push(Line::from(Span::styled(
+            tf("compare.too_small", &[("count", &costs.too_small)]),
+            warn_style,
+        )));
+    }
+    if costs.multipart > 0 {
+        text.push(Line::from(Span::styled(
+            tf("compare.multipart", &[("count", &costs.multipart)]),
+            warn_style,
+        )));
+    }
+    if costs.undated > 0 {
+        text.push(Line::from(Span::styled(
+            tf("compare.undated", &[("count", &costs.undated)]),
+            warn_style,
+        )));
+    }
+    for warning in &comparison.warnings {
+        text.push(Line::from(Span::styled(
+            format!("⚠ Not exported: {warning}"),
+            warn_style,
+        )));
+    }
+    text.push(Line::from(Span::styled(
+        tf("compare.notes", &[("region", &costs.price_region)]),
+        hint_style,
+    )));
+    let para = Paragraph::new(text).block(block).wrap(Wrap { trim: false });
+    frame.render_widget(para, area);
+}
@@ -0,0 +1,109 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+
+use crate::app::App;
+use crate::models::RestoreState;
+
+use super::format_size;
+
+pub(super) fn draw_object_detail(frame: &mut ratatui::Frame, area: Rect, app: &App) {
+    let title_style = Style::default()
+        .fg(Color::LightYellow)
+        .add_modifier(Modifier::BOLD);
+    let block = Block::default()
+        .title(Span::styled("Selected object", title_style))
+        .borders(Borders::ALL)
+        .style(Style::default().bg(Color::Black));
+    let lines = if let Some(obj) = app.selected_object() {
+        let modified = obj
+            .last_modified
+            .clone()
+            .unwrap_or_else(|| "unknown".into());
+
+        // Match the restore status labels used in the objects list
+        let restore = match &obj.restore_state {
+            Some(RestoreState::Available) => match &obj.restore_expiry {
+                Some(expiry) => format!("Restored until {}", super::short_timestamp(expiry)),
+                None => "Restored".to_string(),
+            },
+            Some(RestoreState::InProgress { .. }) => "Restoring".to_string(),
+            Some(RestoreState::Expired) => "Expired".to_string(),
+            None => {
+                // Check if object is in Glacier and needs restore
+                if matches!(
+                    obj.storage_class,
+                    crate::models::StorageClassTier::GlacierFlexibleRetrieval
+                        | crate::models::StorageClassTier::GlacierDeepArchive
+                ) {
+                    "NeedsRestore".to_string()
+                } else {
+                    "N/A".to_string()
+                }
+            }
+        };
+
+        // Listings do not report encryption; HeadObject fills it in
+        let encryption = match &obj.encryption {
+            Some(encryption) => match encryption.kms_key() {
+                Some(key) => format!("{} ({key})", encryption.label()),
+                None => encryption.label().to_string(),
+            },
+            None => "not fetched (i fetches it)".to_string(),
+        };
+
+        // Only known once the bucket's read history was looked up
+        let last_read = match app
+            .selected_bucket_name()
+            .and_then(|bucket| app.last_reads.get(bucket))
+        {
+            Some(reads) => {
+                let activity = reads.activity_of(&obj.key);
+                let read = match activity.last_read {
+                    Some(at) => format!(
+                        "{} ({})",
+                        at.format("%Y-%m-%d %H:%M UTC"),
+                        reads.label(Some(at))
+                    ),
+                    None => format!("not since {}", reads.since.format("%Y-%m-%d")),
+                };
+                match activity.requests() {
+                    0 => read,
+                    _ => format!("{read}, {} GET, {} HEAD", activity.gets, activity.heads),
+                }
+            }
+            None => "not looked up (W reads access logs or CloudTrail)".to_string(),
+        };
+
+        let mut lines = vec![Line::from(format!("Key: {}", obj.key))];
+        if let Some(version) = &obj.version {
+            let state = match (version.is_latest, version.delete_marker) {
+                (true, false) => "current",
+                (true, true) => "delete marker, latest – the key reads as deleted",
+                (false, false) => "noncurrent",
+                (false, true) => "noncurrent delete marker",
+            };
+            lines.push(Line::from(format!("Version: {} ({state})", version.id)));
+        }
+        lines.extend([
+            Line::from(format!("Size: {}", format_size(obj.size))),
+            Line::from(format!("Storage: {}", obj.storage_class.label())),
+            Line::from(format!("Last modified: {}", modified)),
+            Line::from(format!("Last read: {}", last_read)),
+            Line::from(format!("Restore: {}", restore)),
+            Line::from(format!("Encryption: {}", encryption)),
+            Line::from(format!(
+                "Owner: {}",
+                obj.owner
+                    .as_deref()
+                    .unwrap_or("not listed (O lists owners)")
+            )),
+        ]);
+        lines
+    } else {
+        vec![Line::from("No object selected")]
+    };
+    let para = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
+    frame.render_widget(para, area);
+}
@@ -0,0 +1,567 @@
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{
+    Block, Borders, Cell as TableCell, List, ListItem, ListState, Paragraph, Row, Table,
+};
+
+use crate::app::{App, ShardState};
+use crate::i18n::{t, tf};
+use crate::journal::JobHistory;
+use crate::models::{RestoreState, TrackedRestoreRequest};
+use crate::multipart::MultipartStore;
+use crate::tracker::RestoreTracker;
+use crate::tui::RESTORE_CHECK_INTERVAL;
+
+use super::{centered_rect, draw_modal_surface, format_remaining, short_timestamp};
+
+pub(super) fn draw_tracked_requests_popup(frame: &mut ratatui::Frame, tracker: &RestoreTracker) {
+    let area = centered_rect(90, 70, frame.size());
+    draw_modal_surface(frame, area);
+
+    let block = Block::default()
+        .title(t("title.tracker"))
+        .borders(Borders::ALL)
+        .style(Style::default().bg(Color::Black));
+
+    // Pending restores first, soonest expected first; finished ones after
+    let mut requests: Vec<&TrackedRestoreRequest> = tracker.get_all_requests().iter().collect();
+    requests.sort_by_key(|req| {
+        (
+            !matches!(req.current_status, RestoreState::InProgress { .. }),
+            req.expected_ready_at(),
+        )
+    });
+
+    if requests.is_empty() {
+        let para = Paragraph::new(vec![
+            Line::from(""),
+            Line::from("No restore requests tracked yet."),
+            Line::from(""),
+            Line::from("Restore requests will appear here after you initiate them."),
+        ])
+        .block(block);
+        frame.render_widget(para, area);
+        return;
+    }
+
+    let now = chrono::Utc::now();
+    let rows: Vec<Row> = requests
+        .iter()
+        .map(|req| {
+            let expected = req.expected_ready_at();
+            let (state, state_style) = match &req.current_status {
+                RestoreState::InProgress { .. } => match expected {
+                    Some(eta) if eta > now => (
+                        format!(
+                            "Restoring {}% (~{} left)",
+                            req.progress_percent(now).unwrap_or_default(),
+                            format_remaining(eta - now)
+                        ),
+                        Style::default().fg(Color::Yellow),
+                    ),
+                    _ => (
+                        "Restoring (overdue)".to_string(),
+                        Style::default().fg(Color::LightRed),
+                    ),
+                },
+                RestoreState::Available => {
+                    let expires = req
+                        .expires_at
+                        .as_deref()
+                        .and_then(|at| chrono::DateTime::parse_from_rfc3339(at).ok());
+                    let state = match expires {
+                        Some(at) if at > now => {
+                            format!("Ready (expires in {})", format_remaining(at.to_utc() - now))
+                        }
+                        _ => "Ready".to_string(),
+                    };
+                    (state, Style::default().fg(Color::Green))
+                }
+                RestoreState::Expired => ("Expired".to_string(), Style::default().fg(Color::Red)),
+            };
+            let ready = match (&req.ready_at, expected) {
+                (Some(ready_at), _) => short_timestamp(ready_at),
+                (None, Some(eta)) => format!("~{}", eta.format("%Y-%m-%d %H:%M")),
+                (None, None) => "-".to_string(),
+            };
+            Row::new(vec![
+                TableCell::from(req.bucket.clone()),
+                TableCell::from(req.key.clone()),
+                TableCell::from(req.tier.label()),
+                TableCell::from(req.days.to_string()),
+                TableCell::from(short_timestamp(&req.requested_at)),
+                TableCell::from(ready),
+                TableCell::from(state).style(state_style),
+            ])
+        })
+        .collect();
+
+    let pending = requests
+        .iter()
+        .filter(|r| matches!(r.current_status, RestoreState::InProgress { .. }))
+        .count();
+    let header_style = Style::default().add_modifier(Modifier::BOLD);
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Percentage(15),
+            Constraint::Percentage(35),
+            Constraint::Length(10),
+            Constraint::Length(5),
+            Constraint::Length(20),
+            Constraint::Length(20),
+            Constraint::Min(28),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            "Bucket",
+            "Key",
+            "Tier",
+            "Days",
+            "Requested",
+            "Expected ready",
+            "State",
+        ])
+        .style(header_style)
+        .bottom_margin(1),
+    )
+    .block(block.title_bottom(format!(
+        " {pending} pending, {} total – checked every {}s{} ",
+        requests.len(),
+        RESTORE_CHECK_INTERVAL.as_secs(),
+        wave_summary(tracker)
+    )));
+    frame.render_widget(table, area);
+}
+
+/// "– waves: logs 2/5" suffix for the restore table while wave plans are open
+pub(super) fn wave_summary(tracker: &RestoreTracker) -> String {
+    let plans: Vec<String> = tracker
+        .wave_plans()
+        .iter()
+        .map(|plan| format!("{} {}/{}", plan.bucket, plan.submitted(), plan.waves.len()))
+        .collect();
+    if plans.is_empty() {
+        String::new()
+    } else {
+        format!(" – waves: {}", plans.join(", "))
+    }
+}
+
+/// Running jobs and queued actions listed above the job history
+pub(super) const QUEUED_JOB_ROWS: usize = 6;
+
+pub(super) fn draw_job_history_popup(frame: &mut ratatui::Frame, app: &App, history: &JobHistory) {
+    let area = centered_rect(85, 70, frame.size());
+    draw_modal_surface(frame, area);
+
+    let block = Block::default()
+        .title(t("title.history"))
+        .borders(Borders::ALL)
+        .style(Style::default().bg(Color::Black));
+
+    let area = draw_shard_matrix(frame, app, area);
+
+    // Jobs running in the background come first, then actions waiting for
+    // their run time or the execution window
+    let running = app.jobs.jobs();
+    let area = if running.is_empty() {
+        area
+    } else {
+        let height = (running.len().min(QUEUED_JOB_ROWS) + 2) as u16;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(height), Constraint::Min(3)])
+            .split(area);
+        let lines: Vec<Line> = running
+            .iter()
+            .take(QUEUED_JOB_ROWS)
+            .map(|job| {
+                let (state, color) = if job.cancelling() {
+                    ("cancelling ", Color::LightRed)
+                } else if job.started {
+                    ("running    ", Color::LightGreen)
+                } else {
+                    ("waiting    ", Color::LightYellow)
+                };
+                let failed_style = if job.failed > 0 {
+                    Style::default().fg(Color::Red)
+                } else {
+                    Style::default().fg(Color::DarkGray)
+                };
+                Line::from(vec![
+                    Span::styled(state, Style::default().fg(color)),
+                    Span::raw(format!("{} – {}  ", job.label, job.bucket)),
+                    Span::styled(
+                        format!("{}/{}", job.done, job.total),
+                        Style::default().fg(Color::LightGreen),
+                    ),
+                    Span::styled(format!("  {} failed", job.failed), failed_style),
+                ])
+            })
+            .collect();
+        let panel = Paragraph::new(lines).block(
+            Block::default()
+                .title(t("title.running"))
+                .borders(Borders::ALL)
+                .style(Style::default().bg(Color::Black)),
+        );
+        frame.render_widget(panel, chunks[0]);
+        chunks[1]
+    };
+
+    let queued = app.schedule.actions();
+    let area = if queued.is_empty() {
+        area
+    } else {
+        let height = (queued.len().min(QUEUED_JOB_ROWS) + 2) as u16;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(height), Constraint::Min(3)])
+            .split(area);
+        let mut title = t("title.queued").to_string();
+        if let Some(window) = app.execution_window {
+            title = tf("title.queued_window", &[("window", &window.label())]);
+        }
+        let lines: Vec<Line> = queued
+            .iter()
+            .take(QUEUED_JOB_ROWS)
+            .map(|action| {
+                let run_at = action.run_at().unwrap_or_default();
+                let run_at = app
+                    .execution_window
+                    .map_or(run_at, |window| window.earliest_from(run_at));
+                Line::from(vec![
+                    Span::raw(format!(
+                        "{:<20} ",
+                        run_at
+                            .with_timezone(&chrono::Local)
+                            .format("%Y-%m-%d %H:%M:%S")
+                    )),
+                    Span::styled("queued     ", Style::default().fg(Color::LightYellow)),
+                    Span::raw(action.describe()),
+                    Span::styled(
+                        action
+                            .ticket
+                            .as_ref()
+                            .map(|ticket| format!("  [{}]", ticket.id))
+                            .unwrap_or_default(),
+                        Style::default().fg(Color::LightBlue),
+                    ),
+                    Span::styled(
+                        action
+                            .note
+                            .as_ref()
+                            .map(|note| format!("  {note}"))
+                            .unwrap_or_default(),
+                        Style::default().fg(Color::Gray),
+                    ),
+                ])
+            })
+            .collect();
+        let queue = Paragraph::new(lines).block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .style(Style::default().bg(Color::Black)),
+        );
+        frame.render_widget(queue, chunks[0]);
+        chunks[1]
+    };
+
+    let items: Vec<ListItem> = history
+        .jobs()
+        .map(|job| {
+            let mut duration = job
+                .duration()
+                .map(|d| format!("{}s", d.num_seconds()))
+                .unwrap_or_else(|| "unfinished".to_string());
+            if job.interrupted {
+                duration.push_str(" (interrupted)");
+            }
+            let failed_style = if job.failed > 0 {
+                Style::default().fg(Color::Red)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("{:<20} ", short_timestamp(&job.started_at))),
+                Span::styled(
+                    format!("{:<11}", job.kind.label()),
+                    Style::default().fg(Color::LightCyan),
+                ),
+                Span::raw(format!("{} → {}  ", job.bucket, job.target)),
+                Span::styled(
+                    format!("{}/{} ok", job.succeeded, job.total),
+                    Style::default().fg(Color::LightGreen),
+                ),
+                Span::styled(format!("  {} failed", job.failed), failed_style),
+                Span::raw(format!("  {duration}")),
+                // Moves with keys left keep their manifest until finished
+                Span::styled(
+                    if app.moves.of_run(&job.id).is_some() {
+                        "  ↻ resumable"
+                    } else {
+                        ""
+                    },
+                    Style::default().fg(Color::Magenta),
+                ),
+                Span::styled(
+                    job.ticket
+                        .as_ref()
+                        .map(|ticket| format!("  [{}]", ticket.id))
+                        .unwrap_or_default(),
+                    Style::default().fg(Color::LightBlue),
+                ),
+                Span::styled(
+                    job.note
+                        .as_ref()
+                        .map(|note| format!("  {note}"))
+                        .unwrap_or_default(),
+                    Style::default().fg(Color::Gray),
+                ),
+            ]))
+        })
+        .collect();
+
+    if items.is_empty() {
+        let para = Paragraph::new("No batch jobs recorded yet.").block(block);
+        frame.render_widget(para, area);
+        return;
+    }
+
+    let mut state = ListState::default();
+    state.select(Some(app.history_cursor.min(items.len() - 1)));
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().bg(Color::Blue));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+/// Rows of the shard matrix panel, plans and shards together
+const SHARD_MATRIX_ROWS: usize = 12;
+
+fn shard_color(state: ShardState) -> Color {
+    match state {
+        ShardState::Pending => Color::DarkGray,
+        ShardState::Queued => Color::LightBlue,
+        ShardState::Running => Color::LightYellow,
+        ShardState::Done => Color::LightGreen,
+        ShardState::Failed => Color::LightRed,
+        ShardState::Interrupted => Color::Magenta,
+    }
+}
+
+/// One cell per shard of every sharded batch, colored by state, with the
+/// selected shard's details. Returns the area left below it.
+fn draw_shard_matrix(frame: &mut ratatui::Frame, app: &App, area: Rect) -> Rect {
+    let plans = app.shards.plans();
+    if plans.is_empty() {
+        return area;
+    }
+    // Two columns per cell, inside the border
+    let per_row = (area.width.saturating_sub(2) as usize / 2).max(1);
+    let selected = app
+        .selected_shard()
+        .map(|(plan, index)| (plan.id.as_str(), index));
+    let mut lines = Vec::new();
+    for plan in plans {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{} – {}  ", plan.label, plan.bucket),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                tf(
+                    "shards.progress",
+                    &[
+                        ("done", &plan.done_shards()),
+                        ("shards", &plan.shards.len()),
+                        ("succeeded", &plan.succeeded_keys()),
+                        ("keys", &plan.total_keys()),
+                    ],
+                ),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(
+                if plan.shared { t("shards.shared") } else { "" },
+                Style::default().fg(Color::Cyan),
+            ),
+        ]));
+        let cells: Vec<Span> = plan
+            .shards
+            .iter()
+            .enumerate()
+            .map(|(index, shard)| {
+                let mut style = Style::default().fg(shard_color(app.shard_state(plan, shard)));
+                if selected == Some((plan.id.as_str(), index)) {
+                    style = style.add_modifier(Modifier::REVERSED);
+                }
+                Span::styled("█ ", style)
+            })
+            .collect();
+        for row in cells.chunks(per_row) {
+            lines.push(Line::from(row.to_vec()));
+        }
+    }
+    if let Some((plan, index)) = app.selected_shard() {
+        let shard = &plan.shards[index];
+        let state = app.shard_state(plan, shard);
+        let running = match &shard.worker {
+            Some(worker) => format!(", on {worker}"),
+            None => shard
+                .latest_run()
+                .and_then(|run| app.jobs.get(run))
+                .map(|job| format!(", {}/{} this run", job.done, job.total))
+                .unwrap_or_default(),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{} ", state.label()),
+                Style::default().fg(shard_color(state)),
+            ),
+            Span::raw(tf(
+                "shards.detail",
+                &[
+                    ("index", &(index + 1)),
+                    ("name", &shard.name),
+                    ("keys", &shard.keys.len()),
+                    ("succeeded", &shard.succeeded),
+                    ("failed", &shard.failed),
+                    ("runs", &shard.runs.len()),
+                ],
+            )),
+            Span::styled(running, Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+    let height = (lines.len().min(SHARD_MATRIX_ROWS) + 2) as u16;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(height), Constraint::Min(3)])
+        .split(area);
+    let panel = Paragraph::new(lines).block(
+        Block::default()
+            .title(t("title.shards"))
+            .borders(Borders::ALL)
+            .style(Style::default().bg(Color::Black)),
+    );
+    frame.render_widget(panel, chunks[0]);
+    chunks[1]
+}
+
+pub(super) fn draw_job_detail_popup(frame: &mut ratatui::Frame, app: &App, history: &JobHistory) {
+    let area = centered_rect(85, 70, frame.size());
+    draw_modal_surface(frame, area);
+
+    let job = history.jobs().nth(app.history_cursor);
+    let title = job
+        .map(|job| {
+            format!(
+                "{} {} – per-key results (Esc back)",
+                job.kind.label(),
+                job.bucket
+            )
+        })
+        .unwrap_or_else(|| "Per-key results (Esc back)".to_string());
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .style(Style::default().bg(Color::Black));
+
+    // The ticket and the operator's note head the journal, above the
+    // scrolled entries
+    let mut lines: Vec<Line> = Vec::new();
+    if let Some(ticket) = job.and_then(|job| job.ticket.as_ref()) {
+        lines.push(Line::from(vec![
+            Span::styled("Ticket: ", Style::default().fg(Color::LightBlue)),
+            Span::raw(ticket.id.clone()),
+            Span::styled(
+                ticket
+                    .url
+                    .as_ref()
+                    .map(|url| format!("  {url}"))
+                    .unwrap_or_default(),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+    }
+    if let Some(note) = job.and_then(|job| job.note.as_ref()) {
+        lines.push(Line::from(vec![
+            Span::styled("Note: ", Style::default().fg(Color::LightBlue)),
+            Span::raw(note.clone()),
+        ]));
+    }
+    if !lines.is_empty() {
+        lines.push(Line::from(""));
+    }
+    lines.extend(app.history_entries.iter().skip(app.history_scroll).map(
+        |entry| match &entry.error {
+            None => Line::from(vec![
+                Span::styled("✓ ", Style::default().fg(Color::LightGreen)),
+                Span::raw(format!("{}  ", entry.key)),
+                Span::styled(
+                    entry.note.clone().unwrap_or_default(),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]),
+            Some(err) => Line::from(vec![
+                Span::styled("✗ ", Style::default().fg(Color::Red)),
+                Span::raw(format!("{}  ", entry.key)),
+                Span::styled(err.clone(), Style::default().fg(Color::Red)),
+            ]),
+        },
+    ));
+    if app.history_entries.is_empty() {
+        lines.push(Line::from("No journal entries for this job."));
+    }
+    let para = Paragraph::new(lines).block(block);
+    frame.render_widget(para, area);
+}
+
+pub(super) fn draw_resume_uploads_popup(
+    frame: &mut ratatui::Frame,
+    app: &App,
+    uploads: &MultipartStore,
+) {
+    let area = centered_rect(80, 50, frame.size());
+    draw_modal_surface(frame, area);
+
+    let block = Block::default()
+        .title(t("title.uploads"))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .style(Style::default().bg(Color::Black));
+
+    let items: Vec<ListItem> = uploads
+        .uploads()
+        .iter()
+        .map(|upload| {
+            let done = upload.part_count() as usize - upload.remaining_parts().len();
+            ListItem::new(Line::from(vec![
+                Span::raw(format!(
+                    "{}/{} → {}/{}  ",
+                    upload.source_bucket, upload.source_key, upload.dest_bucket, upload.dest_key
+                )),
+                Span::styled(
+                    format!("{}/{} parts", done, upload.part_count()),
+                    Style::default().fg(Color::LightYellow),
+                ),
+                Span::styled(
+                    format!("  started {}", short_timestamp(&upload.started_at)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]))
+        })
+        .collect();
+
+    let mut state = ListState::default();
+    if !items.is_empty() {
+        state.select(Some(app.upload_cursor.min(items.len() - 1)));
+    }
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().bg(Color::Blue));
+    frame.render_stateful_widget(list, area, &mut state);
+}
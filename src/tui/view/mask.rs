@@ -0,0 +1,415 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+use crate::app::{ActivePane, App, MaskEditorField};
+use crate::i18n::{t, tf};
+use crate::script;
+
+use super::{centered_rect, draw_modal_surface, highlight_border};
+
+pub(super) fn draw_mask_panel(frame: &mut ratatui::Frame, area: Rect, app: &App) {
+    let title_style = Style::default()
+        .fg(Color::LightMagenta)
+        .add_modifier(Modifier::BOLD);
+    let block = Block::default()
+        .title(Span::styled(t("mask.title"), title_style))
+        .borders(Borders::ALL)
+        .border_style(highlight_border(app.active_pane == ActivePane::MaskEditor))
+        .style(Style::default().bg(Color::Black));
+
+    let content = if let Some(query) = app.active_query() {
+        Line::from(vec![
+            Span::styled(t("mask.query"), Style::default().fg(Color::Cyan)),
+            Span::styled(query.label.as_str(), Style::default().fg(Color::LightGreen)),
+            Span::raw("  "),
+            Span::styled(
+                tf("mask.query_count", &[("count", &query.objects.len())]),
+                Style::default()
+                    .fg(Color::LightYellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("  "),
+            Span::styled("Esc", Style::default().bg(Color::DarkGray).fg(Color::White)),
+            Span::raw(t("mask.clear")),
+        ])
+    } else if let Some(mask) = &app.active_mask {
+        let count_style = Style::default()
+            .fg(Color::LightYellow)
+            .add_modifier(Modifier::BOLD);
+        Line::from(vec![
+            Span::styled(t("mask.active"), Style::default().fg(Color::Cyan)),
+            Span::styled(mask.summary(), Style::default().fg(Color::LightGreen)),
+            Span::raw("  "),
+            Span::styled(
+                tf(
+                    "mask.match_count",
+                    &[("count", &app.filtered_objects.len())],
+                ),
+                count_style,
+            ),
+            Span::raw("  "),
+            Span::styled("Esc", Style::default().bg(Color::DarkGray).fg(Color::White)),
+            Span::raw(t("mask.clear_then")),
+            Span::styled("m", Style::default().bg(Color::DarkGray).fg(Color::White)),
+            Span::raw(t("mask.edit")),
+        ])
+    } else {
+        Line::from(vec![
+            Span::styled(t("mask.none_press"), Style::default().fg(Color::Gray)),
+            Span::styled("m", Style::default().bg(Color::LightCyan).fg(Color::Black)),
+            Span::styled(t("mask.none_create"), Style::default().fg(Color::Gray)),
+        ])
+    };
+
+    let para = Paragraph::new(content).block(block);
+    frame.render_widget(para, area);
+}
+
+pub(super) fn draw_mask_popup<'a>(frame: &mut ratatui::Frame, app: &'a App) {
+    let area = centered_rect(70, 55, frame.size());
+    draw_modal_surface(frame, area);
+
+    let title_style = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let block = Block::default()
+        .title(Span::styled(t("mask.popup_title"), title_style))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Rgb(20, 20, 30)));
+
+    let label_style = Style::default()
+        .fg(Color::LightBlue)
+        .add_modifier(Modifier::BOLD);
+    let active_style = Style::default()
+        .fg(Color::LightYellow)
+        .add_modifier(Modifier::BOLD);
+    let inactive_style = Style::default().fg(Color::Gray);
+    let hint_style = Style::default().fg(Color::DarkGray);
+
+    // Create pattern field with cursor
+    let is_pattern_focused = matches!(app.mask_field, MaskEditorField::Pattern);
+    let mut pattern_spans = vec![Span::styled(t("mask.pattern"), label_style)];
+
+    if is_pattern_focused {
+        // Show cursor in pattern field
+        let before_cursor = &app.mask_draft.pattern[..app.mask_draft.cursor_pos];
+        let cursor_char = if app.mask_draft.cursor_pos < app.mask_draft.pattern.len() {
+            app.mask_draft
+                .pattern
+                .chars()
+                .nth(app.mask_draft.cursor_pos)
+                .unwrap()
+                .to_string()
+        } else {
+            " ".to_string()
+        };
+        let after_cursor = if app.mask_draft.cursor_pos < app.mask_draft.pattern.len() {
+            &app.mask_draft.pattern[app.mask_draft.cursor_pos + 1..]
+        } else {
+            ""
+        };
+
+        pattern_spans.push(Span::styled(before_cursor, active_style));
+        pattern_spans.push(Span::styled(
+            cursor_char,
+            Style::default().fg(Color::Black).bg(Color::LightYellow),
+        ));
+        pattern_spans.push(Span::styled(after_cursor, active_style));
+    } else {
+        let display = if app.mask_draft.pattern.is_empty() {
+            t("mask.empty")
+        } else {
+            &app.mask_draft.pattern
+        };
+        pattern_spans.push(Span::styled(display, inactive_style));
+    }
+
+    let is_owner_focused = matches!(app.mask_field, MaskEditorField::Owner);
+    let mut owner_spans = vec![Span::styled(
+        t("mask.owner"),
+        if is_owner_focused {
+            active_style
+        } else {
+            label_style
+        },
+    )];
+    if is_owner_focused {
+        let (before, after) = app.mask_draft.owner.split_at(app.mask_draft.owner_cursor);
+        owner_spans.push(Span::styled(before, active_style));
+        owner_spans.push(Span::styled(
+            " ",
+            Style::default().fg(Color::Black).bg(Color::LightYellow),
+        ));
+        owner_spans.push(Span::styled(after, active_style));
+    } else {
+        let display = if app.mask_draft.owner.is_empty() {
+            t("mask.any")
+        } else {
+            &app.mask_draft.owner
+        };
+        owner_spans.push(Span::styled(display, inactive_style));
+    }
+    owner_spans.push(Span::styled(t("mask.owner_hint"), hint_style));
+
+    let is_unread_focused = matches!(app.mask_field, MaskEditorField::Unread);
+    let unread_hint = match app.selected_last_reads() {
+        Some(reads) => tf(
+            "mask.unread_hint_known",
+            &[("days", &(chrono::Utc::now() - reads.since).num_days())],
+        ),
+        None => t("mask.unread_hint").to_string(),
+    };
+    let unread_spans = vec![
+        Span::styled(
+            t("mask.unread"),
+            if is_unread_focused {
+                active_style
+            } else {
+                label_style
+            },
+        ),
+        Span::styled(
+            app.mask_draft
+                .unread_days
+                .map_or(t("mask.any").to_string(), |days| {
+                    tf("mask.days", &[("days", &days)])
+                }),
+            if is_unread_focused {
+                active_style
+            } else {
+                inactive_style
+            },
+        ),
+        Span::styled(unread_hint, hint_style),
+    ];
+
+    let bound = |label: &'static str, field: MaskEditorField, text: &'a str, cursor: usize| {
+        let focused = app.mask_field == field;
+        let mut spans = vec![Span::styled(
+            label,
+            if focused { active_style } else { label_style },
+        )];
+        if focused {
+            let (before, after) = text.split_at(cursor);
+            spans.push(Span::styled(before, active_style));
+            spans.push(Span::styled(
+                " ",
+                Style::default().fg(Color::Black).bg(Color::LightYellow),
+            ));
+            spans.push(Span::styled(after, active_style));
+        } else {
+            spans.push(Span::styled(
+                if text.is_empty() { t("mask.any") } else { text },
+                inactive_style,
+            ));
+        }
+        spans
+    };
+    let draft = &app.mask_draft;
+    let mut size_spans = bound(
+        t("mask.min_size"),
+        MaskEditorField::MinSize,
+        &draft.min_size,
+        draft.min_size_cursor,
+    );
+    size_spans.extend(bound(
+        t("mask.max_size"),
+        MaskEditorField::MaxSize,
+        &draft.max_size,
+        draft.max_size_cursor,
+    ));
+    size_spans.push(Span::styled(t("mask.size_hint"), hint_style));
+    let mut age_spans = bound(
+        t("mask.older_than"),
+        MaskEditorField::OlderThan,
+        &draft.older_than,
+        draft.older_than_cursor,
+    );
+    age_spans.extend(bound(
+        t("mask.newer_than"),
+        MaskEditorField::NewerThan,
+        &draft.newer_than,
+        draft.newer_than_cursor,
+    ));
+    age_spans.push(Span::styled(t("mask.age_hint"), hint_style));
+
+    let text = vec![
+        Line::from(""),
+        Line::from(pattern_spans),
+        Line::from(vec![
+            Span::styled("          ", Style::default()),
+            Span::styled(t("mask.pattern_hint"), hint_style),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                t("mask.mode"),
+                if matches!(app.mask_field, MaskEditorField::Mode) {
+                    active_style
+                } else {
+                    label_style
+                },
+            ),
+            Span::styled(
+                app.mask_draft.kind.to_string(),
+                if matches!(app.mask_field, MaskEditorField::Mode) {
+                    active_style
+                } else {
+                    inactive_style
+                },
+            ),
+            Span::styled(t("mask.cycle_hint"), hint_style),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                t("mask.case"),
+                if matches!(app.mask_field, MaskEditorField::Case) {
+                    active_style
+                } else {
+                    label_style
+                },
+            ),
+            Span::styled(
+                if app.mask_draft.case_sensitive {
+                    t("mask.yes")
+                } else {
+                    t("mask.no")
+                },
+                if matches!(app.mask_field, MaskEditorField::Case) {
+                    active_style
+                } else {
+                    inactive_style
+                },
+            ),
+            Span::styled(t("mask.toggle_hint"), hint_style),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                t("mask.storage_class"),
+                if matches!(app.mask_field, MaskEditorField::StorageClass) {
+                    active_style
+                } else {
+                    label_style
+                },
+            ),
+            Span::styled(
+                app.mask_draft
+                    .storage_class_filter
+                    .as_ref()
+                    .map(|s| s.label())
+                    .unwrap_or(t("mask.any")),
+                if matches!(app.mask_field, MaskEditorField::StorageClass) {
+                    active_style
+                } else {
+                    inactive_style
+                },
+            ),
+            Span::styled(t("mask.cycle_hint"), hint_style),
+        ]),
+        Line::from(""),
+        Line::from(owner_spans),
+        Line::from(""),
+        Line::from(unread_spans),
+        Line::from(""),
+        Line::from(size_spans),
+        Line::from(""),
+        Line::from(age_spans),
+        Line::from(""),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                "Tab",
+                Style::default()
+                    .fg(Color::LightGreen)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(t("mask.keys_fields"), hint_style),
+            Span::styled(
+                "Enter",
+                Style::default()
+                    .fg(Color::LightGreen)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(t("mask.keys_apply"), hint_style),
+            Span::styled(
+                "Esc",
+                Style::default()
+                    .fg(Color::LightGreen)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(t("mask.keys_cancel"), hint_style),
+        ]),
+    ];
+    let para = Paragraph::new(text).block(block);
+    frame.render_widget(para, area);
+}
+
+pub(super) fn draw_script_popup(frame: &mut ratatui::Frame, app: &App) {
+    let area = centered_rect(80, 70, frame.size());
+    draw_modal_surface(frame, area);
+
+    let title_style = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let block = Block::default()
+        .title(Span::styled(t("mask.script_title"), title_style))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Rgb(20, 20, 30)));
+
+    let code_style = Style::default().fg(Color::LightYellow);
+    let cursor_style = Style::default().fg(Color::Black).bg(Color::LightYellow);
+    let label_style = Style::default()
+        .fg(Color::LightBlue)
+        .add_modifier(Modifier::BOLD);
+    let hint_style = Style::default().fg(Color::DarkGray);
+
+    let mut lines = Vec::new();
+    let mut offset = 0;
+    for line in app.script_draft.split('\n') {
+        let end = offset + line.len();
+        if (offset..=end).contains(&app.script_cursor) {
+            let at = app.script_cursor - offset;
+            let (before, rest) = line.split_at(at);
+            let mut chars = rest.chars();
+            let under = chars.next().map_or(" ".to_string(), |ch| ch.to_string());
+            lines.push(Line::from(vec![
+                Span::styled(before.to_string(), code_style),
+                Span::styled(under, cursor_style),
+                Span::styled(chars.as_str().to_string(), code_style),
+            ]));
+        } else {
+            lines.push(Line::from(Span::styled(line.to_string(), code_style)));
+        }
+        offset = end + 1;
+    }
+
+    lines.push(Line::from(""));
+    match script::predicate(&app.script_draft) {
+        Ok(_) => lines.push(Line::from(Span::styled(
+            t("mask.script_ok"),
+            Style::default().fg(Color::LightGreen),
+        ))),
+        Err(err) => lines.push(Line::from(Span::styled(
+            format!("✗ {err}"),
+            Style::default().fg(Color::LightRed),
+        ))),
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(t("mask.script_hint"), hint_style)));
+    for (name, meaning) in script::VARIABLES {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {name:<9}"), label_style),
+            Span::styled(*meaning, hint_style),
+        ]));
+    }
+    lines.push(Line::from(Span::styled(t("mask.script_keys"), hint_style)));
+
+    let para = Paragraph::new(lines).block(block);
+    frame.render_widget(para, area);
+}
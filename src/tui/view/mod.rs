@@ -0,0 +1,322 @@
+mod batch;
+mod bucket_tools;
+mod buckets;
+mod bundle;
+mod confirm;
+mod detail;
+mod jobs;
+mod mask;
+mod objects;
+mod policies;
+mod popups;
+mod status;
+
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Block, Clear};
+
+use crate::app::{App, AppMode};
+use crate::approvals::ApprovalState;
+use crate::journal::JobHistory;
+use crate::models::StorageClassTier;
+use crate::multipart::MultipartStore;
+use crate::tracker::RestoreTracker;
+use crate::tui::ascii;
+
+use batch::{draw_pause_popup, draw_progress_popup, draw_shutdown_popup, draw_telemetry_panel};
+use bucket_tools::{
+    draw_access_audit_popup, draw_collision_popup, draw_config_copy_popup, draw_copy_target_popup,
+    draw_create_bucket_popup, draw_decommission_popup, draw_reconcile_popup, draw_tag_editor_popup,
+};
+use buckets::draw_bucket_selector;
+use bundle::draw_bundle_popup;
+use confirm::{
+    draw_comparison_popup, draw_confirm_popup, draw_dry_run_popup, draw_job_note_popup,
+    draw_preview_popup, draw_retain_popup, draw_schedule_popup, draw_storage_popup,
+    draw_ticket_popup,
+};
+use detail::draw_object_detail;
+use jobs::{
+    draw_job_detail_popup, draw_job_history_popup, draw_resume_uploads_popup,
+    draw_tracked_requests_popup,
+};
+use mask::{draw_mask_panel, draw_mask_popup, draw_script_popup};
+use objects::{draw_objects, draw_split_objects};
+use policies::draw_policies_popup;
+use popups::{
+    draw_credential_error_popup, draw_full_key_popup, draw_help_popup, draw_jump_popup,
+    draw_kms_popup, draw_log_popup, draw_palette_popup, draw_protect_popup, draw_query_popup,
+};
+use status::{draw_command_bar, draw_offline_banner, draw_status};
+
+pub(super) fn draw(
+    frame: &mut ratatui::Frame,
+    app: &App,
+    tracker: &RestoreTracker,
+    history: &JobHistory,
+    uploads: &MultipartStore,
+) {
+    draw_layout(frame, app);
+
+    match app.mode {
+        AppMode::CredentialError => draw_credential_error_popup(frame),
+        AppMode::EditingMask => draw_mask_popup(frame, app),
+        AppMode::EditingScript => draw_script_popup(frame, app),
+        AppMode::SelectingStorageClass => draw_storage_popup(frame, app),
+        AppMode::Confirming => draw_confirm_popup(frame, app, history),
+        AppMode::PreviewingTargets => draw_preview_popup(frame, app),
+        AppMode::ViewingDryRun => draw_dry_run_popup(frame, app),
+        AppMode::ComparingMechanisms => draw_comparison_popup(frame, app),
+        AppMode::SchedulingAction => {
+            draw_confirm_popup(frame, app, history);
+            draw_schedule_popup(frame, app);
+        }
+        AppMode::ViewingReconciliation => draw_reconcile_popup(frame, app),
+        AppMode::EnteringRetainUntil => {
+            draw_confirm_popup(frame, app, history);
+            draw_retain_popup(frame, app);
+        }
+        AppMode::EnteringJobNote => {
+            draw_confirm_popup(frame, app, history);
+            draw_job_note_popup(frame, app);
+        }
+        AppMode::EnteringTicket => {
+            draw_confirm_popup(frame, app, history);
+            draw_ticket_popup(frame, app);
+        }
+        AppMode::ResolvingCollisions => {
+            draw_confirm_popup(frame, app, history);
+            draw_collision_popup(frame, app);
+        }
+        AppMode::Decommissioning => draw_decommission_popup(frame, app),
+        AppMode::AuditingAccess => draw_access_audit_popup(frame, app),
+        AppMode::CreatingBucket => draw_create_bucket_popup(frame, app),
+        AppMode::ChoosingCopyTarget => draw_copy_target_popup(frame, app),
+        AppMode::CopyingBucketConfig => draw_config_copy_popup(frame, app),
+        AppMode::EditingBucketTags => draw_tag_editor_popup(frame, app),
+        AppMode::ShowingHelp => draw_help_popup(frame),
+        AppMode::ViewingLog => draw_log_popup(frame, app),
+        AppMode::ViewingRestoreRequests => draw_tracked_requests_popup(frame, tracker),
+        AppMode::ViewingJobHistory => draw_job_history_popup(frame, app, history),
+        AppMode::ResumingUploads => draw_resume_uploads_popup(frame, app, uploads),
+        AppMode::ViewingJobDetail => draw_job_detail_popup(frame, app, history),
+        AppMode::ShowingProgress => draw_progress_popup(frame, app),
+        AppMode::ConfirmingShutdown => {
+            draw_progress_popup(frame, app);
+            draw_shutdown_popup(frame);
+        }
+        AppMode::BatchPaused => {
+            draw_progress_popup(frame, app);
+            draw_pause_popup(frame, app);
+        }
+        AppMode::JumpingToKey => draw_jump_popup(frame, app),
+        AppMode::EnteringKmsKey => draw_kms_popup(frame, app),
+        AppMode::QueryingInventory => draw_query_popup(frame, app),
+        AppMode::ProtectingKeys => draw_protect_popup(frame, app),
+        AppMode::ViewingKey => draw_full_key_popup(frame, app),
+        AppMode::CommandPalette => draw_palette_popup(frame, app),
+        AppMode::ManagingPolicies => draw_policies_popup(frame, app),
+        AppMode::ViewingBundle => draw_bundle_popup(frame, app),
+        AppMode::Browsing => {}
+    }
+    if app.ascii {
+        ascii::downgrade(frame.buffer_mut());
+    }
+}
+
+/// Panes, status and command bar shared by the main and batch screens
+fn draw_layout(frame: &mut ratatui::Frame, app: &App) {
+    let mut size = frame.size();
+    if let Some(down) = app.connectivity.offline_for() {
+        draw_offline_banner(frame, Rect { height: 1, ..size }, down);
+        size.y += 1;
+        size.height = size.height.saturating_sub(1);
+    }
+
+    // Main vertical split: content area, status, command bar
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(10),
+            Constraint::Length(4),
+            Constraint::Length(3),
+        ])
+        .split(size);
+
+    // Main content panel: bucket selector, mask, objects, object detail
+    let main_panel = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Bucket selector (compact)
+            Constraint::Length(5), // Mask panel
+            Constraint::Min(10),   // Objects list
+            Constraint::Length(8), // Selected object detail
+        ])
+        .split(vertical[0]);
+
+    draw_bucket_selector(frame, main_panel[0], app);
+    draw_mask_panel(frame, main_panel[1], app);
+    if let Some(split) = &app.split {
+        let halves = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(main_panel[2]);
+        draw_objects(frame, halves[0], app);
+        draw_split_objects(frame, halves[1], app, split);
+    } else {
+        draw_objects(frame, main_panel[2], app);
+    }
+    draw_object_detail(frame, main_panel[3], app);
+    draw_status(frame, vertical[1], app);
+    draw_command_bar(frame, vertical[2]);
+}
+
+/// Screen shown while a batch runs: the layout, progress, and the quit prompt
+pub(super) fn draw_batch(frame: &mut ratatui::Frame, app: &App) {
+    draw_layout(frame, app);
+    draw_progress_popup(frame, app);
+    if app.show_telemetry {
+        draw_telemetry_panel(frame, app);
+    }
+    if app.show_batch_log {
+        draw_log_popup(frame, app);
+    }
+    match app.mode {
+        AppMode::ConfirmingShutdown => draw_shutdown_popup(frame),
+        AppMode::BatchPaused => draw_pause_popup(frame, app),
+        _ => {}
+    }
+    if app.ascii {
+        ascii::downgrade(frame.buffer_mut());
+    }
+}
+
+fn format_remaining(remaining: chrono::Duration) -> String {
+    let minutes = remaining.num_minutes().max(1);
+    if minutes >= 24 * 60 {
+        format!("{}d {}h", minutes / (24 * 60), minutes / 60 % 24)
+    } else if minutes >= 60 {
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{minutes}m")
+    }
+}
+
+pub(super) fn short_timestamp(rfc3339: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(rfc3339)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|_| rfc3339.to_string())
+}
+
+fn draw_modal_surface(frame: &mut ratatui::Frame, area: Rect) {
+    frame.render_widget(Clear, area);
+    let backdrop = Block::default().style(Style::default().bg(Color::Black));
+    frame.render_widget(backdrop, area);
+
+    let canvas = frame.size();
+    let shadow_style = Style::default().bg(Color::DarkGray);
+    if area.y + area.height < canvas.height {
+        let shadow_width = area.width.min(canvas.width.saturating_sub(area.x + 1));
+        if shadow_width > 0 {
+            let shadow = Rect::new(area.x + 1, area.y + area.height, shadow_width, 1);
+            frame.render_widget(Block::default().style(shadow_style), shadow);
+        }
+    }
+    if area.x + area.width < canvas.width {
+        let shadow_height = area.height.min(canvas.height.saturating_sub(area.y + 1));
+        if shadow_height > 0 {
+            let shadow = Rect::new(area.x + area.width, area.y + 1, 1, shadow_height);
+            frame.render_widget(Block::default().style(shadow_style), shadow);
+        }
+    }
+}
+
+fn centered_rect(width_percent: u16, height_percent: u16, area: Rect) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - height_percent) / 2),
+            Constraint::Percentage(height_percent),
+            Constraint::Percentage((100 - height_percent) / 2),
+        ])
+        .split(area);
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - width_percent) / 2),
+            Constraint::Percentage(width_percent),
+            Constraint::Percentage((100 - width_percent) / 2),
+        ])
+        .split(vertical[1])[1]
+}
+
+fn highlight_border(active: bool) -> Style {
+    if active {
+        Style::default()
+            .fg(Color::LightYellow)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::DarkGray)
+    }
+}
+
+/// Window a key to `width` characters starting at `offset`, marking hidden text with ellipses
+fn clip_key(key: &str, offset: usize, width: usize) -> String {
+    let total = key.chars().count();
+    let offset = offset.min(total);
+    let mut visible: String = key.chars().skip(offset).take(width).collect();
+    if offset + width < total {
+        visible.pop();
+        visible.push('…');
+    }
+    if offset > 0 && !visible.is_empty() {
+        visible.remove(0);
+        visible.insert(0, '…');
+    }
+    format!("{:<width$}", visible, width = width)
+}
+
+fn format_size(size: i64) -> String {
+    const KB: f64 = 1024.0;
+    let kb = size as f64 / KB;
+    format!("{:>10.2} KB", kb)
+}
+
+/// Colour of a prefix by its sign-off, when one was imported for it
+fn approval_color(state: ApprovalState) -> Color {
+    match state {
+        ApprovalState::Approved => Color::LightGreen,
+        ApprovalState::Pending => Color::LightYellow,
+        ApprovalState::Blocked => Color::LightRed,
+    }
+}
+
+fn storage_class_color(storage_class: &StorageClassTier) -> Style {
+    match storage_class {
+        StorageClassTier::Standard => Style::default()
+            .fg(Color::LightGreen)
+            .add_modifier(Modifier::BOLD),
+        StorageClassTier::StandardIa => Style::default()
+            .fg(Color::LightYellow)
+            .add_modifier(Modifier::BOLD),
+        StorageClassTier::OneZoneIa => Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+        StorageClassTier::IntelligentTiering => Style::default()
+            .fg(Color::LightMagenta)
+            .add_modifier(Modifier::BOLD),
+        StorageClassTier::GlacierInstantRetrieval => Style::default()
+            .fg(Color::LightCyan)
+            .add_modifier(Modifier::BOLD),
+        StorageClassTier::GlacierFlexibleRetrieval => Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+        StorageClassTier::GlacierDeepArchive => Style::default()
+            .fg(Color::LightBlue)
+            .add_modifier(Modifier::BOLD),
+        StorageClassTier::ReducedRedundancy => Style::default()
+            .fg(Color::Magenta)
+            .add_modifier(Modifier::BOLD),
+        StorageClassTier::Unknown(_) => Style::default().fg(Color::DarkGray),
+    }
+}
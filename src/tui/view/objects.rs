@@ -0,0 +1,383 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState};
+
+use crate::app::{ActivePane, App, SplitPane};
+use crate::coldness::COLD_SCORE;
+use crate::i18n::{t, tf};
+use crate::models::RestoreState;
+
+use super::{approval_color, clip_key, format_size, highlight_border, storage_class_color};
+
+pub(super) fn draw_objects(frame: &mut ratatui::Frame, area: Rect, app: &App) {
+    let objects = app.active_objects();
+    let loaded_count = app.objects.len();
+    let total_count = app.total_object_count.unwrap_or(loaded_count);
+
+    let loading_indicator = match &app.listing {
+        Some(listing) if listing.is_loading_all() => tf(
+            "objects.loading_all",
+            &[
+                ("done", &listing.partitions_done),
+                ("ranges", &listing.partitions),
+            ],
+        ),
+        _ if app.is_loading_objects => " ⟳".to_string(),
+        Some(_) => " +".to_string(),
+        None => String::new(),
+    };
+    // HeadObject requests still queued for archived rows
+    let enriching = match app.enrichment.as_ref().map(|e| e.outstanding()) {
+        Some(count) if count > 0 => tf("objects.enriching", &[("count", &count)]),
+        _ => String::new(),
+    };
+
+    let mut window = app
+        .window_start()
+        .map(|start| tf("objects.from", &[("start", &start)]))
+        .unwrap_or_default();
+    if let Some(prefix) = app.current_prefix() {
+        window.insert_str(0, &tf("objects.in", &[("prefix", &prefix)]));
+    }
+    let folders = app.visible_folders();
+    if !folders.is_empty() {
+        window.push_str(&tf("objects.folders", &[("count", &folders.len())]));
+    }
+    if app.restore_sort {
+        window.push_str(t("objects.by_restore"));
+    }
+    if app.version_view {
+        let (count, bytes) = app
+            .objects
+            .iter()
+            .filter(|obj| obj.is_noncurrent())
+            .fold((0, 0), |(count, bytes), obj| (count + 1, bytes + obj.size));
+        window.push_str(&tf(
+            "objects.versions",
+            &[
+                ("count", &count),
+                ("size", &crate::waves::format_bytes(bytes.max(0) as u64)),
+            ],
+        ));
+    }
+
+    let excluded = match app.excluded_count() {
+        0 => String::new(),
+        count => tf("objects.excluded", &[("count", &count)]),
+    };
+    let picked = match app.picked_count() {
+        0 => String::new(),
+        count => tf("objects.picked", &[("count", &count)]),
+    };
+    let selection = match (&app.active_mask, app.restore_filter) {
+        (Some(mask), Some(status)) => Some(tf(
+            "objects.mask_restore",
+            &[("mask", &mask.summary()), ("status", &status.label())],
+        )),
+        (Some(mask), None) => Some(tf("objects.mask", &[("mask", &mask.summary())])),
+        (None, Some(status)) => Some(tf("objects.restore", &[("status", &status.label())])),
+        (None, None) => None,
+    };
+    let title = if let Some(selection) = selection {
+        let total = if loaded_count < total_count {
+            tf("objects.of_total", &[("total", &total_count)])
+        } else {
+            String::new()
+        };
+        tf(
+            "objects.title_selection",
+            &[
+                ("window", &window),
+                ("selection", &selection),
+                ("matches", &app.filtered_objects.len()),
+                ("excluded", &excluded),
+                ("loaded", &loaded_count),
+                ("total", &total),
+                ("loading", &loading_indicator),
+            ],
+        )
+    } else {
+        tf(
+            "objects.title",
+            &[
+                ("window", &window),
+                ("loaded", &loaded_count),
+                ("total", &total_count),
+                ("picked", &picked),
+                ("loading", &loading_indicator),
+            ],
+        )
+    };
+    let title_style = Style::default()
+        .fg(Color::LightCyan)
+        .add_modifier(Modifier::BOLD);
+    let block = Block::default()
+        .title(Line::from(vec![
+            Span::styled(title, title_style),
+            Span::styled(enriching, Style::default().fg(Color::DarkGray)),
+        ]))
+        .borders(Borders::ALL)
+        .border_style(highlight_border(app.active_pane == ActivePane::Objects))
+        .style(Style::default().bg(Color::Black));
+
+    // Calculate available width for the key column
+    // 2 (marker) + 1 (space) + 13 (size) + 1 (space) + 20 (storage) + 1 (space) + 13 (restore) + 2 (borders) = 53
+    // The version view adds 1 (space) + 10 (version ID)
+    let fixed_width = if app.version_view { 64 } else { 53 };
+    let key_width = area.width.saturating_sub(fixed_width).max(20) as usize;
+
+    // Subfolders come first, named relative to the current folder
+    let parent = app.current_prefix().unwrap_or_default();
+    let bucket = app.selected_bucket_name().unwrap_or_default();
+    let folder_items = folders.iter().enumerate().map(|(idx, folder)| {
+        let is_selected = app.folder_cursor == Some(idx);
+        let name = folder.strip_prefix(parent).unwrap_or(folder);
+        let approval = app.approvals.lookup(bucket, folder).map(|a| a.state);
+        // Cold prefixes stand out
+        let recency = app.last_reads.get(bucket).map(|reads| {
+            let coldness = reads.coldness_under(folder);
+            let color = if coldness.score >= COLD_SCORE {
+                Color::Cyan
+            } else {
+                Color::DarkGray
+            };
+            Span::styled(
+                format!("  {}", coldness.label()),
+                Style::default().fg(color),
+            )
+        });
+        ListItem::new(Line::from_iter(
+            [
+                Span::styled(
+                    if is_selected { "►" } else { " " },
+                    Style::default()
+                        .fg(Color::LightYellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(" "),
+                Span::styled(
+                    clip_key(name, app.key_scroll, key_width),
+                    Style::default()
+                        .fg(approval.map_or(Color::LightBlue, approval_color))
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(" "),
+                Span::styled(
+                    format!(
+                        "{:>13}",
+                        approval.map_or(t("objects.folder"), |state| state.label())
+                    ),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]
+            .into_iter()
+            .chain(recency),
+        ))
+    });
+    let object_items = objects.iter().enumerate().map(|(idx, obj)| {
+        let is_selected = app.folder_cursor.is_none() && idx == app.selected_object;
+        let is_excluded = app.is_selection() && app.is_excluded(&obj.key);
+        let is_picked = !app.is_selection() && app.is_picked(&obj.key);
+        let is_protected = app.is_protected(&obj.key);
+        let marker = if is_selected {
+            "►"
+        } else if is_protected {
+            "⛨"
+        } else if is_excluded {
+            "✗"
+        } else if is_picked {
+            "✓"
+        } else {
+            " "
+        };
+        let marker_style = if is_selected {
+            Style::default()
+                .fg(Color::LightYellow)
+                .add_modifier(Modifier::BOLD)
+        } else if is_protected {
+            Style::default()
+                .fg(Color::LightCyan)
+                .add_modifier(Modifier::BOLD)
+        } else if is_excluded {
+            Style::default().fg(Color::LightRed)
+        } else if is_picked {
+            Style::default()
+                .fg(Color::LightGreen)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        let key_style = if is_excluded {
+            Style::default()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::CROSSED_OUT)
+        } else if is_selected {
+            Style::default()
+                .fg(Color::LightGreen)
+                .add_modifier(Modifier::BOLD)
+        } else if is_picked {
+            Style::default().fg(Color::LightGreen)
+        } else if obj.is_noncurrent() {
+            Style::default().fg(Color::Gray)
+        } else {
+            Style::default().fg(Color::White)
+        };
+
+        // Scroll, then truncate or pad the key to fixed width
+        let key_display = clip_key(&obj.key, app.key_scroll, key_width);
+
+        // Version ID, shortened; the latest version's is highlighted
+        let version = obj.version.as_ref().map(|version| {
+            let id: String = version.id.chars().take(10).collect();
+            let color = if version.is_latest {
+                Color::LightGreen
+            } else {
+                Color::DarkGray
+            };
+            Span::styled(format!(" {id:<10}"), Style::default().fg(color))
+        });
+        let delete_marker = obj.version.as_ref().is_some_and(|v| v.delete_marker);
+
+        // Format storage class with fixed width
+        let storage_label = if delete_marker {
+            format!("{:<20}", t("objects.delete_marker"))
+        } else {
+            format!("{:<20}", obj.storage_class.label())
+        };
+        let storage_style = if delete_marker {
+            Style::default().fg(Color::Red)
+        } else {
+            storage_class_color(&obj.storage_class)
+        };
+
+        // Get restore status with more descriptive text
+        let (restore_symbol, restore_style) = match &obj.restore_state {
+            Some(RestoreState::Available) => (
+                t("objects.restored"),
+                Style::default()
+                    .fg(Color::LightGreen)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Some(RestoreState::InProgress { .. }) => (
+                t("objects.restoring"),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Some(RestoreState::Expired) => (t("objects.expired"), Style::default().fg(Color::Red)),
+            None => {
+                // Check if object is in Glacier and needs restore. The
+                // restore state of noncurrent versions is not looked up.
+                if obj.is_current()
+                    && matches!(
+                        obj.storage_class,
+                        crate::models::StorageClassTier::GlacierFlexibleRetrieval
+                            | crate::models::StorageClassTier::GlacierDeepArchive
+                    )
+                {
+                    (
+                        t("objects.needs_restore"),
+                        Style::default()
+                            .fg(Color::Magenta)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                } else {
+                    ("", Style::default().fg(Color::DarkGray))
+                }
+            }
+        };
+
+        let spans = [
+            Span::styled(marker.to_string(), marker_style),
+            Span::raw(" "),
+            Span::styled(key_display, key_style),
+        ]
+        .into_iter()
+        .chain(version)
+        .chain([
+            Span::raw(" "),
+            Span::styled(format_size(obj.size), Style::default().fg(Color::LightCyan)),
+            Span::raw(" "),
+            Span::styled(storage_label, storage_style),
+            Span::styled(restore_symbol, restore_style),
+        ]);
+
+        ListItem::new(Line::from_iter(spans))
+    });
+    let items: Vec<ListItem> = folder_items.chain(object_items).collect();
+    let mut state = ListState::default();
+    if !items.is_empty() {
+        state.select(Some(app.cursor_row().min(items.len() - 1)));
+    }
+    let list = List::new(items)
+        .highlight_style(Style::default().bg(Color::Blue))
+        .block(block);
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+pub(super) fn draw_split_objects(
+    frame: &mut ratatui::Frame,
+    area: Rect,
+    app: &App,
+    split: &SplitPane,
+) {
+    let loading_indicator = if split.listing.is_some() { " +" } else { "" };
+    let title = tf(
+        "objects.split_title",
+        &[
+            ("bucket", &split.bucket),
+            ("count", &split.objects.len()),
+            ("loading", &loading_indicator),
+        ],
+    );
+    let block = Block::default()
+        .title(Span::styled(
+            title,
+            Style::default()
+                .fg(Color::LightCyan)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_style(highlight_border(app.active_pane == ActivePane::PeerObjects))
+        .style(Style::default().bg(Color::Black));
+
+    // 2 (marker) + 1 + 13 (size) + 1 + 20 (storage) + 2 (borders) = 39
+    let key_width = area.width.saturating_sub(39).max(10) as usize;
+    let items: Vec<ListItem> = split
+        .objects
+        .iter()
+        .enumerate()
+        .map(|(idx, obj)| {
+            let is_selected = idx == split.selected;
+            let marker = if is_selected { "►" } else { " " };
+            let key_style = if is_selected {
+                Style::default()
+                    .fg(Color::LightGreen)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(marker, Style::default().fg(Color::LightYellow)),
+                Span::raw(" "),
+                Span::styled(clip_key(&obj.key, app.key_scroll, key_width), key_style),
+                Span::raw(" "),
+                Span::styled(format_size(obj.size), Style::default().fg(Color::LightCyan)),
+                Span::raw(" "),
+                Span::styled(
+                    format!("{:<20}", obj.storage_class.label()),
+                    storage_class_color(&obj.storage_class),
+                ),
+            ]))
+        })
+        .collect();
+    let mut state = ListState::default();
+    if !split.objects.is_empty() {
+        state.select(Some(split.selected.min(split.objects.len() - 1)));
+    }
+    let list = List::new(items)
+        .highlight_style(Style::default().bg(Color::Blue))
+        .block(block);
+    frame.render_stateful_widget(list, area, &mut state);
+}
@@ -0,0 +1,138 @@
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+
+use crate::app::{App, PolicyStep};
+use crate::i18n::{t, tf};
+
+use super::{centered_rect, draw_modal_surface, short_timestamp};
+
+pub(super) fn draw_policies_popup(frame: &mut ratatui::Frame, app: &App) {
+    let pane = &app.policy_pane;
+    let area = centered_rect(75, 60, frame.size());
+    draw_modal_surface(frame, area);
+
+    let title_style = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let block = Block::default()
+        .title(Span::styled(t("policies.title"), title_style))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Rgb(20, 20, 30)));
+
+    let label_style = Style::default()
+        .fg(Color::LightBlue)
+        .add_modifier(Modifier::BOLD);
+    let active_style = Style::default()
+        .fg(Color::LightYellow)
+        .add_modifier(Modifier::BOLD);
+    let hint_style = Style::default().fg(Color::DarkGray);
+
+    let policies = app.policies.policies();
+    let mut text = vec![Line::from("")];
+    if policies.is_empty() {
+        text.push(Line::from(Span::styled(t("policies.empty"), hint_style)));
+    }
+    for (index, policy) in policies.iter().enumerate() {
+        let typing = matches!(pane.step, PolicyStep::Naming | PolicyStep::Noting);
+        let style = if index == pane.cursor && !typing {
+            Style::default().bg(Color::DarkGray).fg(Color::White)
+        } else {
+            Style::default()
+        };
+        let mut spans = vec![
+            Span::styled(format!(" {:<20} ", policy.name), style.fg(Color::LightCyan)),
+            Span::styled(format!("{} → ", policy.mask.name), style),
+            Span::styled(policy.target_class.label(), style.fg(Color::LightGreen)),
+        ];
+        if let Some(days) = policy.restore_days {
+            spans.push(Span::styled(
+                tf("policies.restores", &[("days", &days)]),
+                style,
+            ));
+        }
+        spans.push(Span::styled(
+            format!("  {}", short_timestamp(&policy.created_at)),
+            style.fg(Color::DarkGray),
+        ));
+        if let Some(ticket) = &policy.ticket {
+            spans.push(Span::styled(
+                format!("  [{ticket}]"),
+                style.fg(Color::LightBlue),
+            ));
+        }
+        text.push(Line::from(spans));
+        if let Some(note) = &policy.note {
+            text.push(Line::from(Span::styled(
+                format!("   {note}"),
+                Style::default().fg(Color::Gray),
+            )));
+        }
+    }
+    text.push(Line::from(""));
+
+    let selected = policies.get(pane.cursor);
+    let bucket = app.selected_bucket_name().unwrap_or("-");
+    match pane.step {
+        PolicyStep::Browse | PolicyStep::Editing => {
+            text.push(Line::from(Span::styled(
+                tf("policies.hint", &[("bucket", &bucket)]),
+                hint_style,
+            )));
+        }
+        PolicyStep::Naming => {
+            let prompt = if pane.editing.is_some() {
+                t("policies.rename")
+            } else {
+                t("policies.name")
+            };
+            text.push(Line::from(vec![
+                Span::styled(prompt, label_style),
+                Span::styled(pane.name.as_str(), active_style),
+                Span::styled(" ", Style::default().bg(Color::LightYellow)),
+            ]));
+            text.push(Line::from(Span::styled(
+                t("policies.name_hint"),
+                hint_style,
+            )));
+        }
+        PolicyStep::Noting => {
+            text.push(Line::from(vec![
+                Span::styled(t("policies.note"), label_style),
+                Span::styled(pane.note.as_str(), active_style),
+                Span::styled(" ", Style::default().bg(Color::LightYellow)),
+            ]));
+            text.push(Line::from(Span::styled(
+                t("policies.note_hint"),
+                hint_style,
+            )));
+        }
+        PolicyStep::ConfirmDelete => {
+            if let Some(policy) = selected {
+                text.push(Line::from(Span::styled(
+                    tf("policies.confirm_delete", &[("name", &policy.name)]),
+                    active_style,
+                )));
+            }
+        }
+        PolicyStep::ConfirmRun => {
+            if let Some(policy) = selected {
+                text.push(Line::from(Span::styled(
+                    tf(
+                        "policies.confirm_run",
+                        &[
+                            ("name", &policy.name),
+                            ("bucket", &bucket),
+                            ("class", &policy.target_class.label()),
+                        ],
+                    ),
+                    active_style,
+                )));
+                text.push(Line::from(Span::styled(t("policies.run_note"), hint_style)));
+            }
+        }
+    }
+    let para = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+    frame.render_widget(para, area);
+}
@@ -0,0 +1,503 @@
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+
+use crate::app::App;
+use crate::i18n::{t, tf};
+use crate::tui::actions::{self, ACTIONS, Action, Section};
+
+use super::{centered_rect, draw_modal_surface, format_size};
+
+pub(super) fn draw_query_popup(frame: &mut ratatui::Frame, app: &App) {
+    let area = centered_rect(80, 30, frame.size());
+    draw_modal_surface(frame, area);
+
+    let title_style = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let (title, example, columns) = match (&app.athena, app.query_via_athena) {
+        (Some(config), true) => (
+            format!(" Query {}.{} with Athena ", config.database, config.table),
+            "size > 1048576 and storage_class = 'STANDARD'".to_string(),
+            format!(
+                "SQL condition on the inventory table; workgroup {}{}",
+                config.workgroup,
+                config
+                    .output_location
+                    .as_deref()
+                    .map(|o| format!(", results in {o}"))
+                    .unwrap_or_default()
+            ),
+        ),
+        _ => (
+            " Query inventory ".to_string(),
+            "s3://bucket/path/manifest.json where size > 1048576 and last_modified < '2023-01-01'"
+                .to_string(),
+            "Columns: key, size, last_modified, storage_class (as in the inventory)".to_string(),
+        ),
+    };
+    let block = Block::default()
+        .title(Span::styled(title, title_style))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Rgb(20, 20, 30)));
+
+    let hint_style = Style::default().fg(Color::DarkGray);
+    let text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                "Query: ",
+                Style::default()
+                    .fg(Color::LightBlue)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                app.query_input.as_str(),
+                Style::default()
+                    .fg(Color::LightYellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" ", Style::default().bg(Color::LightYellow)),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(example, hint_style)),
+        Line::from(Span::styled(columns, hint_style)),
+        Line::from(Span::styled("Enter run  Esc cancel", hint_style)),
+    ];
+    let para = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+    frame.render_widget(para, area);
+}
+
+pub(super) fn draw_protect_popup(frame: &mut ratatui::Frame, app: &App) {
+    let area = centered_rect(70, 50, frame.size());
+    draw_modal_surface(frame, area);
+
+    let title_style = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let bucket = app.selected_bucket_name().unwrap_or_default();
+    let block = Block::default()
+        .title(Span::styled(
+            tf("protect.title", &[("bucket", &bucket)]),
+            title_style,
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Rgb(20, 20, 30)));
+
+    let hint_style = Style::default().fg(Color::DarkGray);
+    let mut text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                t("protect.prompt"),
+                Style::default()
+                    .fg(Color::LightBlue)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                app.protect_input.as_str(),
+                Style::default()
+                    .fg(Color::LightYellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" ", Style::default().bg(Color::LightYellow)),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(t("protect.pattern_hint"), hint_style)),
+        Line::from(Span::styled(t("protect.keys"), hint_style)),
+        Line::from(""),
+    ];
+    let patterns: Vec<&str> = app.protected.patterns(bucket).collect();
+    if patterns.is_empty() {
+        text.push(Line::from(Span::styled(t("protect.none"), hint_style)));
+    }
+    for pattern in patterns {
+        text.push(Line::from(vec![
+            Span::styled("⛨ ", Style::default().fg(Color::LightCyan)),
+            Span::raw(pattern.to_string()),
+        ]));
+    }
+    let para = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+    frame.render_widget(para, area);
+}
+
+pub(super) fn draw_jump_popup(frame: &mut ratatui::Frame, app: &App) {
+    let area = centered_rect(60, 20, frame.size());
+    draw_modal_surface(frame, area);
+
+    let title_style = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let (title, hint) = if app.jump_sets_window {
+        (
+            " List from key ",
+            "Keys before this are not loaded; leave empty to list from the start",
+        )
+    } else {
+        (
+            " Go to key ",
+            "Full or partial key; unloaded regions are fetched with StartAfter",
+        )
+    };
+    let block = Block::default()
+        .title(Span::styled(title, title_style))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Rgb(20, 20, 30)));
+
+    let hint_style = Style::default().fg(Color::DarkGray);
+    let text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                "Key: ",
+                Style::default()
+                    .fg(Color::LightBlue)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                app.jump_input.as_str(),
+                Style::default()
+                    .fg(Color::LightYellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" ", Style::default().bg(Color::LightYellow)),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(hint, hint_style)),
+        Line::from(Span::styled("Enter confirm  Esc cancel", hint_style)),
+    ];
+    let para = Paragraph::new(text).block(block);
+    frame.render_widget(para, area);
+}
+
+pub(super) fn draw_kms_popup(frame: &mut ratatui::Frame, app: &App) {
+    let area = centered_rect(60, 20, frame.size());
+    draw_modal_surface(frame, area);
+
+    let block = Block::default()
+        .title(Span::styled(
+            t("title.kms"),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Rgb(20, 20, 30)));
+
+    let hint_style = Style::default().fg(Color::DarkGray);
+    let text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                t("kms.prompt"),
+                Style::default()
+                    .fg(Color::LightBlue)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                app.kms_input.as_str(),
+                Style::default()
+                    .fg(Color::LightYellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" ", Style::default().bg(Color::LightYellow)),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(t("kms.hint"), hint_style)),
+        Line::from(Span::styled(t("kms.keys"), hint_style)),
+    ];
+    let para = Paragraph::new(text).block(block);
+    frame.render_widget(para, area);
+}
+
+pub(super) fn draw_full_key_popup(frame: &mut ratatui::Frame, app: &App) {
+    let Some(obj) = app.selected_object() else {
+        return;
+    };
+    let area = centered_rect(70, 30, frame.size());
+    draw_modal_surface(frame, area);
+
+    let block = Block::default()
+        .title(Span::styled(
+            t("title.full_key"),
+            Style::default()
+                .fg(Color::LightYellow)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .style(Style::default().bg(Color::Black));
+
+    let lines = vec![
+        Line::from(Span::styled(
+            obj.key.as_str(),
+            Style::default()
+                .fg(Color::LightGreen)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(format!(
+            "{} chars · {} · {}",
+            obj.key.chars().count(),
+            format_size(obj.size).trim(),
+            obj.storage_class.label()
+        )),
+    ];
+    let para = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    frame.render_widget(para, area);
+}
+
+pub(super) fn draw_help_popup(frame: &mut ratatui::Frame) {
+    let area = centered_rect(80, 80, frame.size());
+    draw_modal_surface(frame, area);
+    let title_style = Style::default()
+        .fg(Color::LightYellow)
+        .add_modifier(Modifier::BOLD);
+    let block = Block::default()
+        .title(Span::styled(t("help.title"), title_style))
+        .borders(Borders::ALL)
+        .style(Style::default().bg(Color::Black));
+
+    let key_style = Style::default()
+        .fg(Color::LightCyan)
+        .add_modifier(Modifier::BOLD);
+    let header_style = Style::default()
+        .fg(Color::LightGreen)
+        .add_modifier(Modifier::BOLD);
+
+    // Three "key - description" pairs per line
+    let keys = |actions: &[&Action]| {
+        let mut spans = Vec::new();
+        for (index, action) in actions.iter().enumerate() {
+            if index > 0 {
+                spans.push(Span::raw("  "));
+            }
+            spans.push(Span::styled(action.key_label(), key_style));
+            spans.push(Span::raw(format!(" - {}", t(action.description))));
+        }
+        Line::from(spans)
+    };
+
+    let mut lines = vec![
+        Line::from(Span::styled(t("help.basic_workflow"), header_style)),
+        Line::from(t("help.workflow_1")),
+        Line::from(t("help.workflow_2")),
+        Line::from(t("help.workflow_3")),
+        Line::from(t("help.workflow_4")),
+    ];
+    for section in Section::ALL {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(t(section.header()), header_style)));
+        let notes: &[&str] = match section {
+            Section::Masks => &[
+                "help.mask_fields",
+                "help.mask_modes",
+                "help.mask_apply",
+                "help.mask_active",
+            ],
+            Section::Storage => &[
+                "help.transition_single",
+                "help.transition_mask",
+                "help.transition_restore_first",
+            ],
+            Section::Navigation | Section::Other => &[],
+        };
+        lines.extend(notes.iter().map(|note| Line::from(t(note))));
+        let actions: Vec<&Action> = ACTIONS
+            .iter()
+            .filter(|action| action.section == section)
+            .collect();
+        lines.extend(actions.chunks(3).map(keys));
+    }
+    let para = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
+    frame.render_widget(para, area);
+}
+
+pub(super) fn draw_log_popup(frame: &mut ratatui::Frame, app: &App) {
+    let area = centered_rect(70, 60, frame.size());
+    draw_modal_surface(frame, area);
+    let follow = if app.log_scroll == 0 {
+        Span::styled(t("log.following"), Style::default().fg(Color::Green))
+    } else {
+        Span::styled(
+            tf("log.paused", &[("count", &app.log_scroll)]),
+            Style::default().fg(Color::Yellow),
+        )
+    };
+    let job_log = app
+        .log_job
+        .as_deref()
+        .and_then(|job_id| app.job_log(job_id));
+    let mut bottom = vec![Span::raw(" "), follow, Span::raw(" ")];
+    let title = match job_log {
+        Some(log) => {
+            if log.dropped > 0 {
+                bottom.push(Span::styled(
+                    tf("log.dropped", &[("count", &log.dropped)]),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            tf("title.job_log", &[("job", &log.title)])
+        }
+        None => t("title.log").to_string(),
+    };
+    let block = Block::default()
+        .title(title)
+        .title_bottom(Line::from(bottom))
+        .borders(Borders::ALL)
+        .style(Style::default().bg(Color::Black));
+    let messages = app.shown_log();
+    if messages.is_empty() {
+        let empty = if job_log.is_some() {
+            "No messages for this job."
+        } else {
+            "No status messages yet."
+        };
+        let para = Paragraph::new(empty).block(block);
+        frame.render_widget(para, area);
+        return;
+    }
+
+    // Oldest at the top. Fill the view upwards from the last message shown,
+    // counting wrapped lines, so the newest message sits on the bottom row.
+    let width = area.width.saturating_sub(2).max(1) as usize;
+    let mut room = area.height.saturating_sub(2) as usize;
+    let end = messages.len() - app.log_scroll.min(messages.len());
+    let first = job_log.map_or(0, |log| log.dropped);
+    let mut lines: Vec<Line> = Vec::new();
+    for (idx, msg) in messages.iter().enumerate().take(end).rev() {
+        let line = format!("{:>3}. {}", first + idx + 1, msg);
+        let rows = line.chars().count().div_ceil(width).max(1);
+        if rows > room && !lines.is_empty() {
+            break;
+        }
+        room = room.saturating_sub(rows);
+        lines.push(Line::from(line));
+    }
+    lines.reverse();
+    let para = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
+    frame.render_widget(para, area);
+}
+
+pub(super) fn draw_credential_error_popup(frame: &mut ratatui::Frame) {
+    let area = centered_rect(70, 50, frame.size());
+    draw_modal_surface(frame, area);
+
+    let error_style = Style::default().fg(Color::Red).add_modifier(Modifier::BOLD);
+    let title_style = Style::default()
+        .fg(Color::LightYellow)
+        .add_modifier(Modifier::BOLD);
+    let key_style = Style::default()
+        .bg(Color::LightYellow)
+        .fg(Color::Black)
+        .add_modifier(Modifier::BOLD);
+
+    let block = Block::default()
+        .title(Span::styled(t("cred.title"), title_style))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red))
+        .style(Style::default().bg(Color::Black));
+
+    let lines = vec![
+        Line::from(""),
+        Line::from(vec![Span::styled(t("cred.failed"), error_style)]),
+        Line::from(""),
+        Line::from(t("cred.intro")),
+        Line::from(""),
+        Line::from(t("cred.cause_1")),
+        Line::from(t("cred.cause_2")),
+        Line::from(t("cred.cause_3")),
+        Line::from(t("cred.cause_4")),
+        Line::from(""),
+        Line::from(vec![Span::styled(t("cred.fix"), title_style)]),
+        Line::from(""),
+        Line::from(t("cred.fix_1")),
+        Line::from(t("cred.fix_2")),
+        Line::from(t("cred.fix_3")),
+        Line::from(t("cred.fix_4")),
+        Line::from(""),
+        Line::from(t("cred.more_info")),
+        Line::from("  https://docs.aws.amazon.com/cli/latest/userguide/cli-configure-files.html"),
+        Line::from(""),
+        Line::from(""),
+        Line::from(vec![
+            Span::raw(t("cred.press")),
+            Span::styled(t("cred.any_key"), key_style),
+            Span::raw(t("cred.to_exit")),
+        ]),
+    ];
+
+    let para = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
+    frame.render_widget(para, area);
+}
+
+pub(super) fn draw_palette_popup(frame: &mut ratatui::Frame, app: &App) {
+    let area = centered_rect(70, 60, frame.size());
+    draw_modal_surface(frame, area);
+
+    let title_style = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let block = Block::default()
+        .title(Span::styled(" Commands ", title_style))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Rgb(20, 20, 30)));
+
+    let hint_style = Style::default().fg(Color::DarkGray);
+    let mut text = vec![
+        Line::from(vec![
+            Span::styled(
+                ": ",
+                Style::default()
+                    .fg(Color::LightBlue)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                app.palette_input.as_str(),
+                Style::default()
+                    .fg(Color::LightYellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" ", Style::default().bg(Color::LightYellow)),
+        ]),
+        Line::from(Span::styled(
+            "Type to filter  ↑↓ select  Enter run  Esc cancel",
+            hint_style,
+        )),
+        Line::from(""),
+    ];
+    let matches = actions::palette_matches(&app.palette_input);
+    if matches.is_empty() {
+        text.push(Line::from(Span::styled("No matching command", hint_style)));
+    }
+    // Keep the highlighted command in view
+    let rows = (area.height as usize).saturating_sub(5).max(1);
+    let skip = (app.palette_cursor + 1).saturating_sub(rows);
+    for (index, action) in matches.iter().enumerate().skip(skip).take(rows) {
+        let style = if index == app.palette_cursor {
+            Style::default()
+                .bg(Color::Cyan)
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        text.push(Line::from(vec![
+            Span::styled(
+                format!("{:<10}", action.key_label()),
+                style.fg(Color::LightCyan),
+            ),
+            Span::styled(format!("{:<18}", action.name), style),
+            Span::styled(t(action.description), style),
+        ]));
+    }
+    let para = Paragraph::new(text).block(block);
+    frame.render_widget(para, area);
+}
@@ -0,0 +1,103 @@
+use std::time::Duration;
+
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+
+use crate::app::App;
+use crate::connectivity::{self, Health};
+use crate::i18n::{t, tf};
+use crate::tui::actions::ACTIONS;
+
+use super::format_remaining;
+
+/// Full-width line shown above the panes while AWS is unreachable
+pub(super) fn draw_offline_banner(frame: &mut ratatui::Frame, area: Rect, down: Duration) {
+    let duration = match chrono::Duration::from_std(down) {
+        Ok(down) if down.num_minutes() > 0 => format_remaining(down),
+        _ => format!("{}s", down.as_secs()),
+    };
+    let text = tf(
+        "connection.banner",
+        &[
+            ("duration", &duration),
+            ("interval", &connectivity::PROBE_INTERVAL.as_secs()),
+        ],
+    );
+    let banner = Paragraph::new(text).style(
+        Style::default()
+            .bg(Color::Red)
+            .fg(Color::White)
+            .add_modifier(Modifier::BOLD),
+    );
+    frame.render_widget(banner, area);
+}
+
+pub(super) fn draw_status(frame: &mut ratatui::Frame, area: Rect, app: &App) {
+    let lines: Vec<Line> = app
+        .status
+        .iter()
+        .rev()
+        .map(|msg| Line::from(msg.clone()))
+        .collect();
+    let (health, health_color) = match app.connectivity.health() {
+        Health::Online => (t("connection.online"), Color::Green),
+        Health::Degraded => (t("connection.degraded"), Color::Yellow),
+        Health::Offline => (t("connection.offline"), Color::Red),
+    };
+    // Progress of the running background job, and how many wait behind it
+    let jobs = match app.jobs.jobs() {
+        [] => String::new(),
+        [job, waiting @ ..] => {
+            let mut badge = format!(" ⚙ {} {}/{}", job.label, job.done, job.total);
+            if !waiting.is_empty() {
+                badge.push_str(&format!(" (+{} queued)", waiting.len()));
+            }
+            badge.push(' ');
+            badge
+        }
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Line::from(vec![
+            Span::styled(
+                "Status",
+                Style::default()
+                    .fg(Color::LightCyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" "),
+            Span::styled(health, Style::default().fg(health_color)),
+            Span::styled(jobs, Style::default().fg(Color::LightYellow)),
+        ]))
+        .style(Style::default().bg(Color::Black));
+    let para = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
+    frame.render_widget(para, area);
+}
+
+pub(super) fn draw_command_bar(frame: &mut ratatui::Frame, area: Rect) {
+    let key_style = Style::default()
+        .bg(Color::LightCyan)
+        .fg(Color::Black)
+        .add_modifier(Modifier::BOLD);
+    let commands: Vec<(String, &'static str)> = ACTIONS
+        .iter()
+        .filter_map(|action| Some((action.key_label(), action.bar?)))
+        .collect();
+    let mut spans = vec![Span::styled(" Tab ", key_style), Span::raw(" ")];
+    for (index, (key, label)) in commands.iter().enumerate() {
+        let label = t(label);
+        // Labels that start with their key read as one word: " m ask"
+        let rest = label.strip_prefix(key.as_str()).unwrap_or(label);
+        let separator = if index + 1 < commands.len() { " " } else { "" };
+        spans.push(Span::styled(format!(" {key} "), key_style));
+        spans.push(Span::raw(format!("{rest}{separator}")));
+    }
+    let help = Line::from(spans);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .style(Style::default().bg(Color::Blue).fg(Color::White));
+    let para = Paragraph::new(help).block(block);
+    frame.render_widget(para, area);
+}
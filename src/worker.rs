@@ -0,0 +1,411 @@
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use anyhow::Result;
+
+use crate::app::RestorePacing;
+use crate::aws::S3Service;
+use crate::coordination::{Claim, Manifest, ShardProgress, SharedState};
+use crate::errors;
+use crate::hooks::{HookFailure, ObjectHook};
+use crate::jobs::{JobEvent, JobQueue, JobWork};
+use crate::journal::JobHistory;
+use crate::models::StorageClassTier;
+use crate::multipart::MULTIPART_COPY_THRESHOLD;
+use crate::presets::RestorePresets;
+use crate::tracker::RestoreTracker;
+
+/// How long an idle worker waits before looking for shards again
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// A running shard's progress is saved and its claim renewed this often,
+/// well within the lease
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(15);
+
+/// `--worker`: no TUI; claim shards of the plans shared under
+/// `--state-bucket` and run them until Ctrl-C
+pub async fn run(
+    s3: S3Service,
+    state: SharedState,
+    hook: Option<ObjectHook>,
+    pacing: RestorePacing,
+) -> Result<()> {
+    let mut worker = Worker {
+        id: worker_id(),
+        s3,
+        state,
+        hook,
+        pacing,
+        presets: RestorePresets::new()?,
+        history: JobHistory::new()?,
+        tracker: RestoreTracker::new()?,
+        done: HashSet::new(),
+    };
+    eprintln!(
+        "bucket-brigade worker {} claiming shards from {}",
+        worker.id,
+        worker.state.url()
+    );
+    loop {
+        match worker.next_claim().await {
+            Ok(Some((manifest, claim, progress))) => {
+                if !worker.run_shard(&manifest, claim, progress).await? {
+                    break;
+                }
+                continue;
+            }
+            Ok(None) => {}
+            Err(err) => eprintln!(
+                "Could not read {}: {}",
+                worker.state.url(),
+                errors::describe(&err)
+            ),
+        }
+        tokio::select! {
+            _ = tokio::time::sleep(POLL_INTERVAL) => {}
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+    eprintln!("Worker {} stopped", worker.id);
+    Ok(())
+}
+
+/// Host name and process id, so several workers on one machine differ
+fn worker_id() -> String {
+    let host = std::env::var("HOSTNAME")
+        .or_else(|_| std::env::var("COMPUTERNAME"))
+        .ok()
+        .or_else(|| {
+            std::fs::read_to_string("/etc/hostname")
+                .ok()
+                .map(|name| name.trim().to_string())
+        })
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "worker".to_string());
+    format!("{host}-{}", std::process::id())
+}
+
+/// Why a checkpoint stopped a shard early
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Halt {
+    /// Another worker took the shard over after this one stalled
+    ClaimLost,
+    /// The plan was dropped from the TUI, or finished
+    PlanClosed,
+}
+
+struct Worker {
+    id: String,
+    s3: S3Service,
+    state: SharedState,
+    hook: Option<ObjectHook>,
+    pacing: RestorePacing,
+    presets: RestorePresets,
+    history: JobHistory,
+    tracker: RestoreTracker,
+    /// Shards seen done, which are not looked at again
+    done: HashSet<(String, usize)>,
+}
+
+impl Worker {
+    /// Claim the first shard of an open plan that needs work and no other
+    /// worker holds
+    async fn next_claim(&mut self) -> Result<Option<(Manifest, Claim, ShardProgress)>> {
+        let (s3, state) = (&self.s3, &self.state);
+        for plan_id in state.plan_ids(s3).await? {
+            if state.is_closed(s3, &plan_id).await? {
+                continue;
+            }
+            let Some(manifest) = state.manifest(s3, &plan_id).await? else {
+                continue;
+            };
+            for index in 0..manifest.shards.len() {
+                if self.done.contains(&(plan_id.clone(), index)) {
+                    continue;
+                }
+                let progress = state.progress(s3, &plan_id, index).await?;
+                if progress.as_ref().is_some_and(|(p, _)| p.is_done()) {
+                    self.done.insert((plan_id.clone(), index));
+                }
+                if progress.as_ref().is_some_and(|(p, _)| !p.wants_work()) {
+                    continue;
+                }
+                let Some((claim, progress)) = state.claim(s3, &plan_id, index, &self.id).await?
+                else {
+                    continue;
+                };
+                if !progress.wants_work() {
+                    state.release(s3, claim).await?;
+                    continue;
+                }
+                return Ok(Some((manifest, claim, progress)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Run the rest of a claimed shard as a background job, checkpointing as
+    /// it goes. Returns `false` when the worker should stop: Ctrl-C, or a
+    /// hook failure under `--hook-failure stop`.
+    async fn run_shard(
+        &mut self,
+        manifest: &Manifest,
+        mut claim: Claim,
+        mut progress: ShardProgress,
+    ) -> Result<bool> {
+        let (s3, state) = (self.s3.clone(), self.state.clone());
+        let index = claim.index;
+        let keys = state.shard_keys(&s3, &manifest.id, index).await?;
+
+        // A retry pass runs the keys that failed, in shard order
+        if progress.finished && progress.retry {
+            progress.retry_keys = Some(std::mem::take(&mut progress.failed_keys));
+            progress.cursor = 0;
+            progress.finished = false;
+            progress.retry = false;
+        }
+        if progress.workers.last() != Some(&self.id) {
+            progress.workers.push(self.id.clone());
+        }
+        let pass: Vec<(String, i64)> = match &progress.retry_keys {
+            Some(retry) => {
+                let retry: HashSet<&str> = retry.iter().map(String::as_str).collect();
+                keys.into_iter()
+                    .filter(|(key, _)| retry.contains(key.as_str()))
+                    .collect()
+            }
+            None => keys,
+        };
+        let start = progress.cursor.min(pass.len());
+        let remaining = pass[start..].to_vec();
+        // Restores complete out of order; the cursor only moves past keys
+        // whose every predecessor is handled, so a resumed run misses none
+        let position: HashMap<String, usize> = remaining
+            .iter()
+            .enumerate()
+            .map(|(i, (key, _))| (key.clone(), i))
+            .collect();
+        let mut handled = vec![false; remaining.len()];
+        let mut frontier = 0;
+
+        let shard = &manifest.shards[index];
+        let label = format!(
+            "{} – shard {}/{} {}",
+            manifest.label,
+            index + 1,
+            manifest.shards.len(),
+            shard.name
+        );
+        eprintln!(
+            "Claimed {label} in {}: {} of {} keys to go",
+            manifest.bucket,
+            remaining.len(),
+            pass.len()
+        );
+        let preset = manifest
+            .work
+            .restore_preset()
+            .and_then(|name| self.presets.get(name));
+        let (pacing, quiet) = self.pacing.with_preset(preset);
+        let work = JobWork::for_shard(&manifest.work, pacing, quiet);
+        let (kind, target) = work.journal_target();
+        let job_id = self.history.begin(
+            kind,
+            &manifest.bucket,
+            &target,
+            remaining.len(),
+            manifest.note.clone(),
+            manifest.ticket.clone(),
+        );
+        // The job queue transitions with a single CopyObject, which S3
+        // refuses over 5 GB; those keys fail here instead of at S3
+        let (large, objects): (Vec<_>, Vec<_>) = remaining.into_iter().partition(|(_, size)| {
+            matches!(work, JobWork::Transition { .. }) && *size > MULTIPART_COPY_THRESHOLD
+        });
+        if !large.is_empty() {
+            eprintln!(
+                "Left out {} objects over 5 GB – their multipart copies only run in the TUI's foreground",
+                large.len()
+            );
+        }
+        for (key, _) in large {
+            self.history.record(
+                &job_id,
+                &key,
+                Some("over 5 GB: multipart copies only run in the TUI's foreground".to_string()),
+            );
+            if !progress.failed_keys.contains(&key) {
+                progress.failed_keys.push(key.clone());
+            }
+            if let Some(&i) = position.get(&key) {
+                handled[i] = true;
+            }
+        }
+        while frontier < handled.len() && handled[frontier] {
+            frontier += 1;
+        }
+        let mut queue = JobQueue::default();
+        queue.submit(
+            &s3,
+            job_id.clone(),
+            label.clone(),
+            manifest.bucket.clone(),
+            work.clone(),
+            objects,
+            self.hook.clone(),
+        );
+
+        let mut keep_going = true;
+        let mut halted = None;
+        let mut checkpoint_at = tokio::time::Instant::now() + CHECKPOINT_INTERVAL;
+        loop {
+            tokio::select! {
+                _ = queue.ready() => {}
+                _ = tokio::time::sleep_until(checkpoint_at) => {
+                    checkpoint_at = tokio::time::Instant::now() + CHECKPOINT_INTERVAL;
+                    progress.cursor = start + frontier;
+                    match self.checkpoint(&mut claim, &progress).await {
+                        Ok(None) => {}
+                        Ok(Some(stop)) => {
+                            halted = Some(stop);
+                            queue.cancel(&job_id);
+                        }
+                        Err(err) => eprintln!("Checkpoint failed: {}", errors::describe(&err)),
+                    }
+                    continue;
+                }
+                _ = tokio::signal::ctrl_c(), if keep_going => {
+                    eprintln!("Stopping after the requests in flight – Ctrl-C again has no effect");
+                    keep_going = false;
+                    queue.cancel(&job_id);
+                    continue;
+                }
+            }
+            let mut finished = false;
+            while let Some(event) = queue.try_next() {
+                match event {
+                    JobEvent::Started { .. } => {}
+                    JobEvent::Object {
+                        key,
+                        result,
+                        elapsed,
+                        ..
+                    } => {
+                        self.history.time_request(&job_id, elapsed);
+                        match result {
+                            Ok(note) => {
+                                progress.succeeded += 1;
+                                self.history.record_with_note(&job_id, &key, None, note);
+                                if let JobWork::Restore {
+                                    days, tier, quiet, ..
+                                } = &work
+                                {
+                                    // Plans shared before storage classes were
+                                    // carried only had Flexible Retrieval
+                                    let storage_class = shard
+                                        .storage_classes
+                                        .get(&key)
+                                        .cloned()
+                                        .unwrap_or(StorageClassTier::GlacierFlexibleRetrieval);
+                                    self.tracker.add_request(
+                                        manifest.bucket.clone(),
+                                        key.clone(),
+                                        *days,
+                                        storage_class,
+                                        *tier,
+                                        *quiet,
+                                    );
+                                }
+                            }
+                            Err(err) => {
+                                self.history
+                                    .record(&job_id, &key, Some(errors::describe(&err)));
+                                if !progress.failed_keys.contains(&key) {
+                                    progress.failed_keys.push(key.clone());
+                                }
+                            }
+                        }
+                        if let Some(&i) = position.get(&key) {
+                            handled[i] = true;
+                        }
+                        while frontier < handled.len() && handled[frontier] {
+                            frontier += 1;
+                        }
+                    }
+                    JobEvent::HookFailed { key, err, .. } => {
+                        eprintln!("Hook failed for {key}: {err:#}");
+                        if self
+                            .hook
+                            .as_ref()
+                            .is_some_and(|hook| hook.on_failure == HookFailure::Stop)
+                        {
+                            keep_going = false;
+                        }
+                    }
+                    JobEvent::Finished { .. } => finished = true,
+                }
+            }
+            if finished {
+                break;
+            }
+        }
+
+        progress.cursor = start + frontier;
+        let complete = frontier == handled.len();
+        if complete {
+            progress.finished = true;
+            progress.retry_keys = None;
+            self.history.finish(&job_id);
+        } else {
+            self.history.interrupt(&job_id);
+        }
+        if halted == Some(Halt::ClaimLost) {
+            eprintln!("Lost the claim on {label}; another worker took it over");
+            return Ok(keep_going);
+        }
+        if halted == Some(Halt::PlanClosed) {
+            eprintln!("{label}: the batch was closed");
+        }
+        progress.updated_at = Some(chrono::Utc::now().to_rfc3339());
+        // Checkpoints that failed may have let the lease run out, and the
+        // shard's progress is then the new holder's to write
+        if !state.renew(&s3, &mut claim).await?
+            || !state.save_progress(&s3, &mut claim, &progress).await?
+        {
+            eprintln!("Lost the claim on {label}; another worker took it over");
+            return Ok(keep_going);
+        }
+        state.release(&s3, claim).await?;
+        if progress.is_done() {
+            self.done.insert((manifest.id.clone(), index));
+        }
+        eprintln!(
+            "{} {label}: {} succeeded, {} failed",
+            if complete { "Finished" } else { "Stopped" },
+            progress.succeeded,
+            progress.failed_keys.len()
+        );
+        Ok(keep_going)
+    }
+
+    /// Renew the claim and save progress, or say why the shard should stop
+    async fn checkpoint(
+        &self,
+        claim: &mut Claim,
+        progress: &ShardProgress,
+    ) -> Result<Option<Halt>> {
+        let (s3, state) = (&self.s3, &self.state);
+        if !state.renew(s3, claim).await? {
+            return Ok(Some(Halt::ClaimLost));
+        }
+        let mut progress = progress.clone();
+        progress.updated_at = Some(chrono::Utc::now().to_rfc3339());
+        if !state.save_progress(s3, claim, &progress).await? {
+            return Ok(Some(Halt::ClaimLost));
+        }
+        if state.is_closed(s3, &claim.plan_id).await? {
+            return Ok(Some(Halt::PlanClosed));
+        }
+        Ok(None)
+    }
+}